@@ -46,8 +46,9 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, graph::*, transition::*, AnimationClip, AnimationPlayer, AnimationPlugin,
-        Interpolation, Keyframes, VariableCurve,
+        animatable::*, graph::*, transition::*, AnimationClip, AnimationCursors, AnimationCurveRef,
+        AnimationPlayer, AnimationPlugin, Interpolation, Keyframes, KeyframesRef, QuantizedQuat,
+        VariableCurve,
     };
 }
 
@@ -76,6 +77,10 @@ pub enum Keyframes {
     ///
     /// [glTF design]: https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#animations
     Weights(Vec<f32>),
+    /// Keyframes for rotation, quantized to half the size of [`Keyframes::Rotation`].
+    ///
+    /// See [`AnimationClip::quantize_rotations`].
+    QuantizedRotation(Vec<QuantizedQuat>),
 }
 
 impl Keyframes {
@@ -85,6 +90,7 @@ impl Keyframes {
             Keyframes::Weights(vec) => vec.len(),
             Keyframes::Translation(vec) | Keyframes::Scale(vec) => vec.len(),
             Keyframes::Rotation(vec) => vec.len(),
+            Keyframes::QuantizedRotation(vec) => vec.len(),
         }
     }
 
@@ -94,6 +100,83 @@ impl Keyframes {
     }
 }
 
+/// The keyframes of a curve of an [`AnimationClip`], borrowed from the arrays of the clip.
+///
+/// Each variant has the layout of the matching variant of [`Keyframes`].
+#[derive(Clone, Copy, Debug)]
+pub enum KeyframesRef<'a> {
+    /// Keyframes for rotation.
+    Rotation(&'a [Quat]),
+    /// Keyframes for translation.
+    Translation(&'a [Vec3]),
+    /// Keyframes for scale.
+    Scale(&'a [Vec3]),
+    /// Keyframes for morph target weights, see [`Keyframes::Weights`].
+    Weights(&'a [f32]),
+    /// Keyframes for rotation, quantized to half the size of [`KeyframesRef::Rotation`].
+    QuantizedRotation(&'a [QuantizedQuat]),
+}
+
+impl KeyframesRef<'_> {
+    /// Returns the number of keyframes.
+    pub fn len(&self) -> usize {
+        match self {
+            KeyframesRef::Weights(keyframes) => keyframes.len(),
+            KeyframesRef::Translation(keyframes) | KeyframesRef::Scale(keyframes) => {
+                keyframes.len()
+            }
+            KeyframesRef::Rotation(keyframes) => keyframes.len(),
+            KeyframesRef::QuantizedRotation(keyframes) => keyframes.len(),
+        }
+    }
+
+    /// Returns true if the number of keyframes is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the rotation keyframe at `index`, dequantizing it if necessary.
+    ///
+    /// Must only be called on [`KeyframesRef::Rotation`] and
+    /// [`KeyframesRef::QuantizedRotation`].
+    fn rotation(&self, index: usize) -> Quat {
+        match self {
+            KeyframesRef::Rotation(keyframes) => keyframes[index],
+            KeyframesRef::QuantizedRotation(keyframes) => keyframes[index].to_quat(),
+            _ => unreachable!("not rotation keyframes"),
+        }
+    }
+}
+
+/// A unit quaternion quantized to 16 bits per component, half the size of a [`Quat`].
+///
+/// The quantization error is below `1e-4` radians, which is not noticeable in animations.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuantizedQuat([i16; 4]);
+
+impl QuantizedQuat {
+    /// Quantizes a rotation. The quaternion is normalized first.
+    pub fn new(rotation: Quat) -> Self {
+        Self(
+            rotation
+                .normalize()
+                .to_array()
+                .map(|component| (component * i16::MAX as f32).round() as i16),
+        )
+    }
+
+    /// Returns the quantized rotation as a normalized [`Quat`].
+    pub fn to_quat(self) -> Quat {
+        Quat::from_array(self.0.map(|component| component as f32 / i16::MAX as f32)).normalize()
+    }
+}
+
+impl From<Quat> for QuantizedQuat {
+    fn from(rotation: Quat) -> Self {
+        Self::new(rotation)
+    }
+}
+
 /// Describes how an attribute of a [`Transform`] or [`MorphWeights`] should be animated.
 ///
 /// `keyframe_timestamps` and `keyframes` should have the same length.
@@ -120,41 +203,98 @@ impl VariableCurve {
     /// To be more precise, this returns [`None`] if the frame is at or past the last keyframe:
     /// we cannot get the *next* keyframe to interpolate to in that case.
     pub fn find_current_keyframe(&self, seek_time: f32) -> Option<usize> {
-        // An Ok(keyframe_index) result means an exact result was found by binary search
-        // An Err result means the keyframe was not found, and the index is the keyframe
-        // PERF: finding the current keyframe can be optimised
-        let search_result = self
-            .keyframe_timestamps
-            .binary_search_by(|probe| probe.partial_cmp(&seek_time).unwrap());
-
-        // Subtract one for zero indexing!
-        let last_keyframe = self.keyframe_timestamps.len() - 1;
-
-        // We want to find the index of the keyframe before the current time
-        // If the keyframe is past the second-to-last keyframe, the animation cannot be interpolated.
-        let step_start = match search_result {
-            // An exact match was found, and it is the last keyframe (or something has gone terribly wrong).
-            // This means that the curve is finished.
-            Ok(n) if n >= last_keyframe => return None,
-            // An exact match was found, and it is not the last keyframe.
-            Ok(i) => i,
-            // No exact match was found, and the seek_time is before the start of the animation.
-            // This occurs because the binary search returns the index of where we could insert a value
-            // without disrupting the order of the vector.
-            // If the value is less than the first element, the index will be 0.
-            Err(0) => return None,
-            // No exact match was found, and it was after the last keyframe.
-            // The curve is finished.
-            Err(n) if n > last_keyframe => return None,
-            // No exact match was found, so return the previous keyframe to interpolate from.
-            Err(i) => i - 1,
-        };
+        find_current_keyframe(&self.keyframe_timestamps, seek_time)
+    }
 
-        // Consumers need to be able to interpolate between the return keyframe and the next
-        assert!(step_start < self.keyframe_timestamps.len());
+    /// Like [`find_current_keyframe`](Self::find_current_keyframe), but first checks whether
+    /// the current keyframe is `cursor` or the one after it, before searching.
+    ///
+    /// When an animation plays forward, the current keyframe rarely moves by more than one
+    /// keyframe per frame, so passing the keyframe found on the previous frame as the `cursor`
+    /// avoids searching most of the time.
+    pub fn find_current_keyframe_from(&self, seek_time: f32, cursor: usize) -> Option<usize> {
+        find_current_keyframe_from(&self.keyframe_timestamps, seek_time, cursor)
+    }
+}
+
+/// A curve of an [`AnimationClip`], borrowing its timestamps and keyframes from the arrays of
+/// the clip.
+///
+/// This is the sampled counterpart of the [`VariableCurve`] added to the clip.
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationCurveRef<'a> {
+    /// Timestamp for each of the keyframes.
+    pub keyframe_timestamps: &'a [f32],
+    /// List of the keyframes, see [`VariableCurve::keyframes`].
+    pub keyframes: KeyframesRef<'a>,
+    /// Interpolation method to use between keyframes.
+    pub interpolation: &'a Interpolation,
+}
+
+impl AnimationCurveRef<'_> {
+    /// Find the index of the keyframe at or before the current time.
+    ///
+    /// See [`VariableCurve::find_current_keyframe`].
+    pub fn find_current_keyframe(&self, seek_time: f32) -> Option<usize> {
+        find_current_keyframe(self.keyframe_timestamps, seek_time)
+    }
+
+    /// Find the index of the keyframe at or before the current time, starting from `cursor`.
+    ///
+    /// See [`VariableCurve::find_current_keyframe_from`].
+    pub fn find_current_keyframe_from(&self, seek_time: f32, cursor: usize) -> Option<usize> {
+        find_current_keyframe_from(self.keyframe_timestamps, seek_time, cursor)
+    }
+}
+
+/// Finds the index of the keyframe at or before `seek_time` in `timestamps`, if there is a
+/// keyframe after it to interpolate to.
+fn find_current_keyframe(timestamps: &[f32], seek_time: f32) -> Option<usize> {
+    // An Ok(keyframe_index) result means an exact result was found by binary search
+    // An Err result means the keyframe was not found, and the index is the keyframe
+    let search_result = timestamps.binary_search_by(|probe| probe.partial_cmp(&seek_time).unwrap());
+
+    // Subtract one for zero indexing!
+    let last_keyframe = timestamps.len() - 1;
+
+    // We want to find the index of the keyframe before the current time
+    // If the keyframe is past the second-to-last keyframe, the animation cannot be interpolated.
+    let step_start = match search_result {
+        // An exact match was found, and it is the last keyframe (or something has gone terribly wrong).
+        // This means that the curve is finished.
+        Ok(n) if n >= last_keyframe => return None,
+        // An exact match was found, and it is not the last keyframe.
+        Ok(i) => i,
+        // No exact match was found, and the seek_time is before the start of the animation.
+        // This occurs because the binary search returns the index of where we could insert a value
+        // without disrupting the order of the vector.
+        // If the value is less than the first element, the index will be 0.
+        Err(0) => return None,
+        // No exact match was found, and it was after the last keyframe.
+        // The curve is finished.
+        Err(n) if n > last_keyframe => return None,
+        // No exact match was found, so return the previous keyframe to interpolate from.
+        Err(i) => i - 1,
+    };
+
+    // Consumers need to be able to interpolate between the return keyframe and the next
+    assert!(step_start < timestamps.len());
+
+    Some(step_start)
+}
 
-        Some(step_start)
+/// Like [`find_current_keyframe`], but first checks whether the current keyframe is `cursor`
+/// or the one after it, before searching.
+fn find_current_keyframe_from(timestamps: &[f32], seek_time: f32, cursor: usize) -> Option<usize> {
+    for index in [cursor, cursor + 1] {
+        if index + 1 < timestamps.len()
+            && timestamps[index] <= seek_time
+            && seek_time < timestamps[index + 1]
+        {
+            return Some(index);
+        }
     }
+    find_current_keyframe(timestamps, seek_time)
 }
 
 /// Interpolation method to use between keyframes.
@@ -169,20 +309,120 @@ pub enum Interpolation {
     CubicSpline,
 }
 
-/// A list of [`VariableCurve`]s and the [`AnimationTargetId`]s to which they
+/// A list of animation curves and the [`AnimationTargetId`]s to which they
 /// apply.
 ///
 /// Because animation clips refer to targets by UUID, they can target any
 /// [`AnimationTarget`] with that ID.
+///
+/// The curves are stored as a struct of arrays: the timestamps of all the curves of the clip
+/// are stored in one array, and their keyframes in one array per kind of value. Each target
+/// only keeps the ranges of its curves in these arrays, so sampling the curves of many
+/// targets reads a few contiguous arrays. The keyframes are searched from the
+/// [`AnimationCursors`] of the targets, and the memory used by rotations can be halved with
+/// [`quantize_rotations`](Self::quantize_rotations).
 #[derive(Asset, Reflect, Clone, Debug, Default)]
 pub struct AnimationClip {
-    curves: AnimationCurves,
+    curves: HashMap<AnimationTargetId, Vec<PackedCurve>, NoOpHash>,
+    keyframes: ClipKeyframes,
     duration: f32,
 }
 
-/// A mapping from [`AnimationTargetId`] (e.g. bone in a skinned mesh) to the
-/// animation curves.
-pub type AnimationCurves = HashMap<AnimationTargetId, Vec<VariableCurve>, NoOpHash>;
+/// The kind of the keyframes of a [`PackedCurve`], which selects the array of
+/// [`ClipKeyframes`] they are stored in.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyframeKind {
+    Rotation,
+    Translation,
+    Scale,
+    Weights,
+    QuantizedRotation,
+}
+
+/// A curve of an [`AnimationClip`], as ranges of the arrays of its [`ClipKeyframes`].
+#[derive(Reflect, Clone, Debug)]
+struct PackedCurve {
+    timestamps_start: usize,
+    timestamps_len: usize,
+    keyframes_start: usize,
+    keyframes_len: usize,
+    kind: KeyframeKind,
+    interpolation: Interpolation,
+}
+
+/// The timestamps and keyframes of all the curves of an [`AnimationClip`].
+#[derive(Reflect, Clone, Debug, Default)]
+struct ClipKeyframes {
+    timestamps: Vec<f32>,
+    rotations: Vec<Quat>,
+    quantized_rotations: Vec<QuantizedQuat>,
+    /// The translation and scale keyframes.
+    vectors: Vec<Vec3>,
+    weights: Vec<f32>,
+}
+
+impl ClipKeyframes {
+    /// Appends the timestamps and keyframes of `curve`, and returns their ranges.
+    fn push(&mut self, curve: VariableCurve) -> PackedCurve {
+        fn append<T>(array: &mut Vec<T>, mut values: Vec<T>) -> (usize, usize) {
+            let start = array.len();
+            let len = values.len();
+            array.append(&mut values);
+            (start, len)
+        }
+
+        let timestamps_start = self.timestamps.len();
+        let timestamps_len = curve.keyframe_timestamps.len();
+        self.timestamps.extend(curve.keyframe_timestamps);
+        let (kind, (keyframes_start, keyframes_len)) = match curve.keyframes {
+            Keyframes::Rotation(keyframes) => (
+                KeyframeKind::Rotation,
+                append(&mut self.rotations, keyframes),
+            ),
+            Keyframes::Translation(keyframes) => (
+                KeyframeKind::Translation,
+                append(&mut self.vectors, keyframes),
+            ),
+            Keyframes::Scale(keyframes) => {
+                (KeyframeKind::Scale, append(&mut self.vectors, keyframes))
+            }
+            Keyframes::Weights(keyframes) => {
+                (KeyframeKind::Weights, append(&mut self.weights, keyframes))
+            }
+            Keyframes::QuantizedRotation(keyframes) => (
+                KeyframeKind::QuantizedRotation,
+                append(&mut self.quantized_rotations, keyframes),
+            ),
+        };
+        PackedCurve {
+            timestamps_start,
+            timestamps_len,
+            keyframes_start,
+            keyframes_len,
+            kind,
+            interpolation: curve.interpolation,
+        }
+    }
+
+    /// Returns the timestamps and keyframes of `curve`.
+    fn get<'a>(&'a self, curve: &'a PackedCurve) -> AnimationCurveRef<'a> {
+        let keyframes = curve.keyframes_start..curve.keyframes_start + curve.keyframes_len;
+        AnimationCurveRef {
+            keyframe_timestamps: &self.timestamps
+                [curve.timestamps_start..curve.timestamps_start + curve.timestamps_len],
+            keyframes: match curve.kind {
+                KeyframeKind::Rotation => KeyframesRef::Rotation(&self.rotations[keyframes]),
+                KeyframeKind::Translation => KeyframesRef::Translation(&self.vectors[keyframes]),
+                KeyframeKind::Scale => KeyframesRef::Scale(&self.vectors[keyframes]),
+                KeyframeKind::Weights => KeyframesRef::Weights(&self.weights[keyframes]),
+                KeyframeKind::QuantizedRotation => {
+                    KeyframesRef::QuantizedRotation(&self.quantized_rotations[keyframes])
+                }
+            },
+            interpolation: &curve.interpolation,
+        }
+    }
+}
 
 /// A unique [UUID] for an animation target (e.g. bone in a skinned mesh).
 ///
@@ -236,8 +476,12 @@ impl Hash for AnimationTargetId {
 /// Note that each entity can only be animated by one animation player at a
 /// time. However, you can change [`AnimationTarget`]'s `player` property at
 /// runtime to change which player is responsible for animating the entity.
+///
+/// [`AnimationCursors`] is a required component of `AnimationTarget`, so it is inserted
+/// automatically when missing.
 #[derive(Clone, Component, Reflect)]
 #[reflect(Component, MapEntities)]
+#[require(AnimationCursors)]
 pub struct AnimationTarget {
     /// The ID of this animation target.
     ///
@@ -248,17 +492,40 @@ pub struct AnimationTarget {
     pub player: Entity,
 }
 
-impl AnimationClip {
-    #[inline]
-    /// [`VariableCurve`]s for each animation target. Indexed by the [`AnimationTargetId`].
-    pub fn curves(&self) -> &AnimationCurves {
-        &self.curves
+/// The keyframes last sampled by the curves animating an [`AnimationTarget`], for each
+/// playing animation.
+///
+/// On the next frame, each curve starts looking for its current keyframe from there, which
+/// avoids searching the keyframes when animations play sequentially. This is updated by
+/// [`animate_targets`].
+#[derive(Clone, Component, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct AnimationCursors {
+    #[reflect(ignore)]
+    cursors: Vec<(AnimationNodeIndex, Vec<usize>)>,
+}
+
+impl AnimationCursors {
+    /// Returns the cursors of the curves of an animation, creating them if needed.
+    fn for_animation(&mut self, animation: AnimationNodeIndex, curve_count: usize) -> &mut [usize] {
+        let index = match self.cursors.iter().position(|(node, _)| *node == animation) {
+            Some(index) => index,
+            None => {
+                self.cursors.push((animation, Vec::new()));
+                self.cursors.len() - 1
+            }
+        };
+        let cursors = &mut self.cursors[index].1;
+        cursors.resize(curve_count, 0);
+        cursors
     }
+}
 
+impl AnimationClip {
+    /// Iterates over the [`AnimationTargetId`]s of the targets animated by this clip.
     #[inline]
-    /// Get mutable references of [`VariableCurve`]s for each animation target. Indexed by the [`AnimationTargetId`].
-    pub fn curves_mut(&mut self) -> &mut AnimationCurves {
-        &mut self.curves
+    pub fn targets(&self) -> impl Iterator<Item = AnimationTargetId> + '_ {
+        self.curves.keys().copied()
     }
 
     /// Gets the curves for a single animation target.
@@ -268,19 +535,9 @@ impl AnimationClip {
     pub fn curves_for_target(
         &self,
         target_id: AnimationTargetId,
-    ) -> Option<&'_ Vec<VariableCurve>> {
-        self.curves.get(&target_id)
-    }
-
-    /// Gets mutable references of the curves for a single animation target.
-    ///
-    /// Returns `None` if this clip doesn't animate the target.
-    #[inline]
-    pub fn curves_for_target_mut(
-        &mut self,
-        target_id: AnimationTargetId,
-    ) -> Option<&'_ mut Vec<VariableCurve>> {
-        self.curves.get_mut(&target_id)
+    ) -> Option<impl ExactSizeIterator<Item = AnimationCurveRef<'_>> + '_> {
+        let curves = self.curves.get(&target_id)?;
+        Some(curves.iter().map(|curve| self.keyframes.get(curve)))
     }
 
     /// Duration of the clip, represented in seconds.
@@ -306,8 +563,39 @@ impl AnimationClip {
         self.duration = self
             .duration
             .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
+        let curve = self.keyframes.push(curve);
         self.curves.entry(target_id).or_default().push(curve);
     }
+
+    /// Quantizes the rotation keyframes of this clip into [`Keyframes::QuantizedRotation`],
+    /// halving the memory they use.
+    ///
+    /// Curves using [`Interpolation::CubicSpline`] are left untouched, since their tangents
+    /// are not unit quaternions.
+    pub fn quantize_rotations(&mut self) {
+        let ClipKeyframes {
+            rotations,
+            quantized_rotations,
+            ..
+        } = &mut self.keyframes;
+        // The rotations that can't be quantized are moved back into a compacted array
+        let unquantized_rotations = std::mem::take(rotations);
+        for curve in self.curves.values_mut().flatten() {
+            if curve.kind != KeyframeKind::Rotation {
+                continue;
+            }
+            let keyframes = &unquantized_rotations
+                [curve.keyframes_start..curve.keyframes_start + curve.keyframes_len];
+            if matches!(curve.interpolation, Interpolation::CubicSpline) {
+                curve.keyframes_start = rotations.len();
+                rotations.extend_from_slice(keyframes);
+            } else {
+                curve.keyframes_start = quantized_rotations.len();
+                quantized_rotations.extend(keyframes.iter().copied().map(QuantizedQuat::new));
+                curve.kind = KeyframeKind::QuantizedRotation;
+            }
+        }
+    }
 }
 
 /// Repetition behavior of an animation.
@@ -771,6 +1059,7 @@ pub fn animate_targets(
         Entity,
         &AnimationTarget,
         Option<&Name>,
+        Option<&mut AnimationCursors>,
         AnyOf<(&mut Transform, &mut MorphWeights)>,
     )>,
 ) {
@@ -780,9 +1069,8 @@ pub fn animate_targets(
     // animation targets, which are evaluated in parallel.
 
     // Iterate over all animation targets in parallel.
-    targets
-        .par_iter_mut()
-        .for_each(|(id, target, name, (transform, morph_weights))| {
+    targets.par_iter_mut().for_each(
+        |(id, target, name, mut cursors, (transform, morph_weights))| {
             let Ok((animation_player, animation_graph_handle)) = players.get(target.player) else {
                 trace!(
                     "Either an animation player {:?} or a graph was missing for the target \
@@ -799,6 +1087,17 @@ pub fn animate_targets(
                 return;
             };
 
+            // Forget the cursors of the animations that stopped.
+            if let Some(ref mut cursors) = cursors {
+                if cursors.cursors.iter().any(|(animation, _)| {
+                    !animation_player.active_animations.contains_key(animation)
+                }) {
+                    cursors.cursors.retain(|(animation, _)| {
+                        animation_player.active_animations.contains_key(animation)
+                    });
+                }
+            }
+
             let mut target_context = AnimationTargetContext {
                 entity: id,
                 target,
@@ -846,24 +1145,53 @@ pub fn animate_targets(
                 let weight = active_animation.computed_weight;
                 total_weight += weight;
 
-                target_context.apply(curves, weight / total_weight, active_animation.seek_time);
+                let curve_cursors = match cursors {
+                    Some(ref mut cursors) => {
+                        cursors.for_animation(animation_graph_node_index, curves.len())
+                    }
+                    None => &mut [],
+                };
+                target_context.apply(
+                    curves,
+                    curve_cursors,
+                    weight / total_weight,
+                    active_animation.seek_time,
+                );
             }
-        });
+        },
+    );
 }
 
 impl AnimationTargetContext<'_> {
     /// Applies a clip to a single animation target according to the
     /// [`AnimationTargetContext`].
-    fn apply(&mut self, curves: &[VariableCurve], weight: f32, seek_time: f32) {
-        for curve in curves {
+    ///
+    /// `cursors` holds the keyframe last sampled by each curve, and is updated. It may be
+    /// empty, in which case the keyframes are searched from scratch.
+    fn apply<'a>(
+        &mut self,
+        curves: impl Iterator<Item = AnimationCurveRef<'a>>,
+        cursors: &mut [usize],
+        weight: f32,
+        seek_time: f32,
+    ) {
+        for (curve_index, curve) in curves.enumerate() {
             // Some curves have only one keyframe used to set a transform
             if curve.keyframe_timestamps.len() == 1 {
-                self.apply_single_keyframe(curve, weight);
+                self.apply_single_keyframe(&curve, weight);
                 return;
             }
 
             // Find the current keyframe
-            let Some(step_start) = curve.find_current_keyframe(seek_time) else {
+            let step_start = match cursors.get_mut(curve_index) {
+                Some(cursor) => {
+                    let step_start = curve.find_current_keyframe_from(seek_time, *cursor);
+                    *cursor = step_start.unwrap_or(0);
+                    step_start
+                }
+                None => curve.find_current_keyframe(seek_time),
+            };
+            let Some(step_start) = step_start else {
                 return;
             };
 
@@ -873,7 +1201,7 @@ impl AnimationTargetContext<'_> {
             let lerp = f32::inverse_lerp(timestamp_start, timestamp_end, seek_time);
 
             self.apply_tweened_keyframe(
-                curve,
+                &curve,
                 step_start,
                 lerp,
                 weight,
@@ -882,27 +1210,27 @@ impl AnimationTargetContext<'_> {
         }
     }
 
-    fn apply_single_keyframe(&mut self, curve: &VariableCurve, weight: f32) {
-        match &curve.keyframes {
-            Keyframes::Rotation(keyframes) => {
+    fn apply_single_keyframe(&mut self, curve: &AnimationCurveRef, weight: f32) {
+        match curve.keyframes {
+            keyframes @ (KeyframesRef::Rotation(_) | KeyframesRef::QuantizedRotation(_)) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.rotation = transform.rotation.slerp(keyframes[0], weight);
+                    transform.rotation = transform.rotation.slerp(keyframes.rotation(0), weight);
                 }
             }
 
-            Keyframes::Translation(keyframes) => {
+            KeyframesRef::Translation(keyframes) => {
                 if let Some(ref mut transform) = self.transform {
                     transform.translation = transform.translation.lerp(keyframes[0], weight);
                 }
             }
 
-            Keyframes::Scale(keyframes) => {
+            KeyframesRef::Scale(keyframes) => {
                 if let Some(ref mut transform) = self.transform {
                     transform.scale = transform.scale.lerp(keyframes[0], weight);
                 }
             }
 
-            Keyframes::Weights(keyframes) => {
+            KeyframesRef::Weights(keyframes) => {
                 let Some(ref mut morphs) = self.morph_weights else {
                     error!(
                         "Tried to animate morphs on {:?} ({:?}), but no `MorphWeights` was found",
@@ -923,26 +1251,34 @@ impl AnimationTargetContext<'_> {
 
     fn apply_tweened_keyframe(
         &mut self,
-        curve: &VariableCurve,
+        curve: &AnimationCurveRef,
         step_start: usize,
         lerp: f32,
         weight: f32,
         duration: f32,
     ) {
-        match (&curve.interpolation, &curve.keyframes) {
-            (Interpolation::Step, Keyframes::Rotation(keyframes)) => {
+        match (curve.interpolation, curve.keyframes) {
+            (
+                Interpolation::Step,
+                keyframes @ (KeyframesRef::Rotation(_) | KeyframesRef::QuantizedRotation(_)),
+            ) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.rotation = transform.rotation.slerp(keyframes[step_start], weight);
+                    transform.rotation = transform
+                        .rotation
+                        .slerp(keyframes.rotation(step_start), weight);
                 }
             }
 
-            (Interpolation::Linear, Keyframes::Rotation(keyframes)) => {
+            (
+                Interpolation::Linear,
+                keyframes @ (KeyframesRef::Rotation(_) | KeyframesRef::QuantizedRotation(_)),
+            ) => {
                 let Some(ref mut transform) = self.transform else {
                     return;
                 };
 
-                let rot_start = keyframes[step_start];
-                let mut rot_end = keyframes[step_start + 1];
+                let rot_start = keyframes.rotation(step_start);
+                let mut rot_end = keyframes.rotation(step_start + 1);
                 // Choose the smallest angle for the rotation
                 if rot_end.dot(rot_start) < 0.0 {
                     rot_end = -rot_end;
@@ -952,15 +1288,18 @@ impl AnimationTargetContext<'_> {
                 transform.rotation = transform.rotation.slerp(rot, weight);
             }
 
-            (Interpolation::CubicSpline, Keyframes::Rotation(keyframes)) => {
+            (
+                Interpolation::CubicSpline,
+                keyframes @ (KeyframesRef::Rotation(_) | KeyframesRef::QuantizedRotation(_)),
+            ) => {
                 let Some(ref mut transform) = self.transform else {
                     return;
                 };
 
-                let value_start = keyframes[step_start * 3 + 1];
-                let tangent_out_start = keyframes[step_start * 3 + 2];
-                let tangent_in_end = keyframes[(step_start + 1) * 3];
-                let value_end = keyframes[(step_start + 1) * 3 + 1];
+                let value_start = keyframes.rotation(step_start * 3 + 1);
+                let tangent_out_start = keyframes.rotation(step_start * 3 + 2);
+                let tangent_in_end = keyframes.rotation((step_start + 1) * 3);
+                let value_end = keyframes.rotation((step_start + 1) * 3 + 1);
                 let result = cubic_spline_interpolation(
                     value_start,
                     tangent_out_start,
@@ -972,14 +1311,14 @@ impl AnimationTargetContext<'_> {
                 transform.rotation = transform.rotation.slerp(result.normalize(), weight);
             }
 
-            (Interpolation::Step, Keyframes::Translation(keyframes)) => {
+            (Interpolation::Step, KeyframesRef::Translation(keyframes)) => {
                 if let Some(ref mut transform) = self.transform {
                     transform.translation =
                         transform.translation.lerp(keyframes[step_start], weight);
                 }
             }
 
-            (Interpolation::Linear, Keyframes::Translation(keyframes)) => {
+            (Interpolation::Linear, KeyframesRef::Translation(keyframes)) => {
                 let Some(ref mut transform) = self.transform else {
                     return;
                 };
@@ -990,7 +1329,7 @@ impl AnimationTargetContext<'_> {
                 transform.translation = transform.translation.lerp(result, weight);
             }
 
-            (Interpolation::CubicSpline, Keyframes::Translation(keyframes)) => {
+            (Interpolation::CubicSpline, KeyframesRef::Translation(keyframes)) => {
                 let Some(ref mut transform) = self.transform else {
                     return;
                 };
@@ -1010,13 +1349,13 @@ impl AnimationTargetContext<'_> {
                 transform.translation = transform.translation.lerp(result, weight);
             }
 
-            (Interpolation::Step, Keyframes::Scale(keyframes)) => {
+            (Interpolation::Step, KeyframesRef::Scale(keyframes)) => {
                 if let Some(ref mut transform) = self.transform {
                     transform.scale = transform.scale.lerp(keyframes[step_start], weight);
                 }
             }
 
-            (Interpolation::Linear, Keyframes::Scale(keyframes)) => {
+            (Interpolation::Linear, KeyframesRef::Scale(keyframes)) => {
                 let Some(ref mut transform) = self.transform else {
                     return;
                 };
@@ -1027,7 +1366,7 @@ impl AnimationTargetContext<'_> {
                 transform.scale = transform.scale.lerp(result, weight);
             }
 
-            (Interpolation::CubicSpline, Keyframes::Scale(keyframes)) => {
+            (Interpolation::CubicSpline, KeyframesRef::Scale(keyframes)) => {
                 let Some(ref mut transform) = self.transform else {
                     return;
                 };
@@ -1047,7 +1386,7 @@ impl AnimationTargetContext<'_> {
                 transform.scale = transform.scale.lerp(result, weight);
             }
 
-            (Interpolation::Step, Keyframes::Weights(keyframes)) => {
+            (Interpolation::Step, KeyframesRef::Weights(keyframes)) => {
                 let Some(ref mut morphs) = self.morph_weights else {
                     return;
                 };
@@ -1057,7 +1396,7 @@ impl AnimationTargetContext<'_> {
                 lerp_morph_weights(morphs.weights_mut(), morph_start.iter().copied(), weight);
             }
 
-            (Interpolation::Linear, Keyframes::Weights(keyframes)) => {
+            (Interpolation::Linear, KeyframesRef::Weights(keyframes)) => {
                 let Some(ref mut morphs) = self.morph_weights else {
                     return;
                 };
@@ -1072,7 +1411,7 @@ impl AnimationTargetContext<'_> {
                 lerp_morph_weights(morphs.weights_mut(), result, weight);
             }
 
-            (Interpolation::CubicSpline, Keyframes::Weights(keyframes)) => {
+            (Interpolation::CubicSpline, KeyframesRef::Weights(keyframes)) => {
                 let Some(ref mut morphs) = self.morph_weights else {
                     return;
                 };
@@ -1160,6 +1499,7 @@ impl Plugin for AnimationPlugin {
             .register_asset_reflect::<AnimationGraph>()
             .register_type::<AnimationPlayer>()
             .register_type::<AnimationTarget>()
+            .register_type::<AnimationCursors>()
             .register_type::<AnimationTransitions>()
             .register_type::<NodeIndex>()
             .add_systems(
@@ -1225,8 +1565,12 @@ impl AnimationGraphEvaluator {
 
 #[cfg(test)]
 mod tests {
-    use crate::VariableCurve;
-    use bevy_math::Vec3;
+    use crate::{
+        AnimationClip, AnimationTargetId, Interpolation, Keyframes, KeyframesRef, QuantizedQuat,
+        VariableCurve,
+    };
+    use bevy_math::{EulerRot, Quat, Vec3};
+    use uuid::Uuid;
 
     fn test_variable_curve() -> VariableCurve {
         let keyframe_timestamps = vec![1.0, 2.0, 3.0, 4.0];
@@ -1370,4 +1714,116 @@ mod tests {
             assert!(exact_keyframe == inexact_keyframe);
         }
     }
+
+    #[test]
+    fn keyframes_found_from_cursor_match_search() {
+        let curve = test_variable_curve();
+        let max_time = *curve.keyframe_timestamps.last().unwrap();
+
+        let mut cursor = 0;
+        let mut seek_time = 0.0;
+        while seek_time <= max_time + 0.5 {
+            let keyframe = curve.find_current_keyframe_from(seek_time, cursor);
+            assert_eq!(keyframe, curve.find_current_keyframe(seek_time));
+            cursor = keyframe.unwrap_or(0);
+            seek_time += 0.25;
+        }
+
+        // Jumping backward falls back to searching.
+        assert_eq!(curve.find_current_keyframe_from(1.5, 2), Some(0));
+    }
+
+    #[test]
+    fn clip_keyframes_are_packed_by_kind() {
+        let [a, b] = [1, 2].map(|id| AnimationTargetId(Uuid::from_u128(id)));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(a, test_variable_curve());
+        clip.add_curve_to_target(
+            b,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Rotation(vec![Quat::IDENTITY, Quat::from_rotation_x(1.0)]),
+                interpolation: Interpolation::Linear,
+            },
+        );
+        clip.add_curve_to_target(
+            a,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 5.0],
+                keyframes: Keyframes::Scale(vec![Vec3::ONE, Vec3::splat(2.0)]),
+                interpolation: Interpolation::Step,
+            },
+        );
+        assert_eq!(clip.duration(), 5.0);
+
+        let curves = clip.curves_for_target(a).unwrap().collect::<Vec<_>>();
+        assert_eq!(curves.len(), 2);
+        assert_eq!(curves[0].keyframe_timestamps, [1.0, 2.0, 3.0, 4.0]);
+        assert!(matches!(
+            curves[0].keyframes,
+            KeyframesRef::Translation(keyframes) if keyframes[1] == Vec3::splat(3.0)
+        ));
+        assert_eq!(curves[1].keyframe_timestamps, [0.0, 5.0]);
+        assert!(matches!(
+            curves[1].keyframes,
+            KeyframesRef::Scale(keyframes) if keyframes == [Vec3::ONE, Vec3::splat(2.0)]
+        ));
+        assert_eq!(clip.curves_for_target(b).unwrap().len(), 1);
+        assert!(clip
+            .curves_for_target(AnimationTargetId(Uuid::nil()))
+            .is_none());
+
+        // The curves of all the targets share one array per kind of value
+        assert_eq!(clip.keyframes.timestamps.len(), 8);
+        assert_eq!(clip.keyframes.vectors.len(), 6);
+        assert_eq!(clip.keyframes.rotations.len(), 2);
+    }
+
+    #[test]
+    fn quantized_rotations_leave_cubic_splines_untouched() {
+        let [a, b] = [1, 2].map(|id| AnimationTargetId(Uuid::from_u128(id)));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            a,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Rotation(vec![Quat::IDENTITY; 6]),
+                interpolation: Interpolation::CubicSpline,
+            },
+        );
+        let rotation = Quat::from_rotation_y(0.5);
+        clip.add_curve_to_target(
+            b,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Rotation(vec![Quat::IDENTITY, rotation]),
+                interpolation: Interpolation::Linear,
+            },
+        );
+        clip.quantize_rotations();
+
+        assert_eq!(clip.keyframes.rotations.len(), 6);
+        assert_eq!(clip.keyframes.quantized_rotations.len(), 2);
+        let curve = clip.curves_for_target(a).unwrap().next().unwrap();
+        assert!(
+            matches!(curve.keyframes, KeyframesRef::Rotation(keyframes) if keyframes.len() == 6)
+        );
+        let curve = clip.curves_for_target(b).unwrap().next().unwrap();
+        let KeyframesRef::QuantizedRotation(keyframes) = curve.keyframes else {
+            panic!("the rotations of linear curves are quantized");
+        };
+        assert!(keyframes[1].to_quat().angle_between(rotation) < 1e-4);
+    }
+
+    #[test]
+    fn quantized_quat_round_trip() {
+        for rotation in [
+            Quat::IDENTITY,
+            Quat::from_rotation_x(1.0),
+            Quat::from_euler(EulerRot::XYZ, 0.3, -2.0, 1.2),
+        ] {
+            let dequantized = QuantizedQuat::new(rotation).to_quat();
+            assert!(dequantized.angle_between(rotation) < 1e-4);
+        }
+    }
 }
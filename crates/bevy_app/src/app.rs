@@ -1,10 +1,13 @@
 use crate::{
-    First, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin, Plugins, PluginsState, SubApp,
-    SubApps,
+    sub_app_channel, First, Last, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin, Plugins,
+    PluginsState, SubApp, SubApps,
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    event::{event_update_system, ManualEventReader},
+    event::{
+        event_update_system, send_resource_changed_events, send_resource_changed_events_with_old,
+        ManualEventReader,
+    },
     intern::Interned,
     prelude::*,
     schedule::{ScheduleBuildSettings, ScheduleLabel},
@@ -345,6 +348,33 @@ impl App {
         self
     }
 
+    /// Adds one or more systems to the given schedule that only run the first time the schedule
+    /// runs.
+    ///
+    /// This is the equivalent of the [`Startup`](crate::Startup) schedule for any schedule, for
+    /// example to initialize something when a schedule that is conditionally run executes for the
+    /// first time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # let mut app = App::new();
+    /// # fn setup_fixed_simulation() {}
+    /// #
+    /// app.add_systems_once(FixedUpdate, setup_fixed_simulation);
+    /// ```
+    pub fn add_systems_once<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.main_mut().add_systems_once(schedule, systems);
+        self
+    }
+
     /// Registers a system and returns a [`SystemId`] so it can later be called by [`World::run_system`].
     ///
     /// It's possible to register the same systems more than once, they'll be stored separately.
@@ -397,6 +427,49 @@ impl App {
         self
     }
 
+    /// Sends a [`ResourceChanged<R>`] event in [`Last`] whenever the resource `R` is added or
+    /// changed.
+    ///
+    /// Use [`add_resource_changed_event_with_old`](Self::add_resource_changed_event_with_old) to
+    /// also get the value of the resource before the change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Resource)]
+    /// # struct Settings;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_resource_changed_event::<Settings>()
+    ///     .add_systems(Update, |mut events: EventReader<ResourceChanged<Settings>>| {
+    ///         for _ in events.read() {
+    ///             // React to the new settings
+    ///         }
+    ///     });
+    /// ```
+    pub fn add_resource_changed_event<R>(&mut self) -> &mut Self
+    where
+        R: Resource,
+    {
+        self.add_event::<ResourceChanged<R>>()
+            .add_systems(Last, send_resource_changed_events::<R>)
+    }
+
+    /// Sends a [`ResourceChanged<R>`] event in [`Last`] whenever the resource `R` is added or
+    /// changed, with the value of the resource before the change.
+    ///
+    /// This keeps a clone of the resource, updated every time it changes.
+    pub fn add_resource_changed_event_with_old<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Clone,
+    {
+        self.add_event::<ResourceChanged<R>>()
+            .add_systems(Last, send_resource_changed_events_with_old::<R>)
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
@@ -717,6 +790,40 @@ impl App {
         self.sub_apps.sub_apps.remove(&label.intern())
     }
 
+    /// Adds a [`sub_app_channel`] to send messages of type `T` from the main world to the
+    /// [`SubApp`] with the given label.
+    ///
+    /// The [`SubAppSender<T>`](crate::SubAppSender) is inserted as a resource in the main world, and the
+    /// [`SubAppReceiver<T>`](crate::SubAppReceiver) in the sub-app's world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`SubApp`] doesn't exist.
+    pub fn add_channel_to_sub_app<T: Send + 'static>(&mut self, label: impl AppLabel) -> &mut Self {
+        let (sender, receiver) = sub_app_channel::<T>();
+        self.sub_app_mut(label).insert_resource(receiver);
+        self.insert_resource(sender)
+    }
+
+    /// Adds a [`sub_app_channel`] to send messages of type `T` from the [`SubApp`] with the
+    /// given label to the main world, for example to report GPU readbacks or pipeline
+    /// compilation status from the render world.
+    ///
+    /// The [`SubAppSender<T>`](crate::SubAppSender) is inserted as a resource in the sub-app's world, and the
+    /// [`SubAppReceiver<T>`](crate::SubAppReceiver) in the main world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`SubApp`] doesn't exist.
+    pub fn add_channel_from_sub_app<T: Send + 'static>(
+        &mut self,
+        label: impl AppLabel,
+    ) -> &mut Self {
+        let (sender, receiver) = sub_app_channel::<T>();
+        self.sub_app_mut(label).insert_resource(sender);
+        self.insert_resource(receiver)
+    }
+
     /// Inserts a new `schedule` under the provided `label`, overwriting any existing
     /// schedule with the same label.
     pub fn add_schedule(&mut self, schedule: Schedule) -> &mut Self {
@@ -983,9 +1090,12 @@ impl Termination for AppExit {
 mod tests {
     use std::{marker::PhantomData, mem};
 
-    use bevy_ecs::{schedule::ScheduleLabel, system::Commands};
+    use bevy_ecs::{
+        schedule::{IntoSystemConfigs, ScheduleLabel},
+        system::{Commands, ResMut, Resource},
+    };
 
-    use crate::{App, AppExit, Plugin};
+    use crate::{App, AppExit, Plugin, Startup, StartupSet, Update};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -1076,6 +1186,46 @@ mod tests {
         assert_eq!(app.world().entities().len(), 2);
     }
 
+    #[test]
+    fn startup_sets_are_ordered() {
+        #[derive(Resource, Default)]
+        struct Order(Vec<StartupSet>);
+
+        fn push(set: StartupSet) -> impl FnMut(ResMut<Order>) {
+            move |mut order| order.0.push(set)
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Order>().add_systems(
+            Startup,
+            (
+                push(StartupSet::UserSetup).in_set(StartupSet::UserSetup),
+                push(StartupSet::RenderSetup).in_set(StartupSet::RenderSetup),
+                push(StartupSet::AssetSetup).in_set(StartupSet::AssetSetup),
+            ),
+        );
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<Order>().0,
+            vec![
+                StartupSet::AssetSetup,
+                StartupSet::RenderSetup,
+                StartupSet::UserSetup
+            ]
+        );
+    }
+
+    #[test]
+    fn add_systems_once_runs_systems_once() {
+        let mut app = App::new();
+        app.add_systems_once(Update, (foo, bar));
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().entities().len(), 2);
+    }
+
     #[test]
     #[should_panic]
     fn test_is_plugin_added_works_during_finish() {
@@ -0,0 +1,210 @@
+//! This module provides an opt-in deterministic mode for [Bevy](https://bevyengine.org) apps,
+//! as needed by lockstep multiplayer and replay verification.
+//!
+//! Bevy's query iteration order only depends on the order in which entities and components
+//! were spawned, inserted and removed, so it is already stable across runs fed with the same
+//! inputs. What is not stable by default is the order in which systems without explicit
+//! ordering run, and any randomness drawn from the operating system. The
+//! [`DeterminismPlugin`] takes care of both.
+
+use crate::{App, Plugin};
+use bevy_ecs::{
+    schedule::{ExecutorKind, Schedules},
+    system::Resource,
+};
+
+/// Makes the execution of an [`App`] reproducible.
+///
+/// This plugin:
+/// * inserts a [`DeterministicRng`] resource, seeded with [`seed`](Self::seed), which systems
+///   should use instead of any other source of randomness;
+/// * configures every schedule of the main world according to [`execution`](Self::execution),
+///   once all plugins have been built.
+///
+/// ```
+/// # use bevy_app::{App, DeterminismPlugin, DeterministicExecution};
+/// App::new().add_plugins(DeterminismPlugin {
+///     seed: 42,
+///     execution: DeterministicExecution::SingleThreaded,
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeterminismPlugin {
+    /// The seed of the [`DeterministicRng`].
+    pub seed: u64,
+    /// How systems are executed.
+    pub execution: DeterministicExecution,
+}
+
+/// How the [`DeterminismPlugin`] makes system execution deterministic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeterministicExecution {
+    /// Every schedule runs on a single thread, in a fixed topological order.
+    #[default]
+    SingleThreaded,
+    /// Schedules keep running systems in parallel, but two systems with conflicting data access
+    /// and no explicit ordering between them always run in the same order, see
+    /// [`ScheduleBuildSettings::order_ambiguous_systems`].
+    ///
+    /// [`ScheduleBuildSettings::order_ambiguous_systems`]: bevy_ecs::schedule::ScheduleBuildSettings::order_ambiguous_systems
+    Parallel,
+}
+
+impl Plugin for DeterminismPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DeterministicRng::new(self.seed));
+    }
+
+    fn cleanup(&self, app: &mut App) {
+        // Run in `cleanup` so that schedules added by other plugins are configured too.
+        let mut schedules = app.world_mut().resource_mut::<Schedules>();
+        for (_, schedule) in schedules.iter_mut() {
+            match self.execution {
+                DeterministicExecution::SingleThreaded => {
+                    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+                }
+                DeterministicExecution::Parallel => {
+                    let mut settings = schedule.get_build_settings();
+                    settings.order_ambiguous_systems = true;
+                    schedule.set_build_settings(settings);
+                }
+            }
+        }
+    }
+}
+
+/// A seeded pseudo-random number generator, producing the same sequence on every platform.
+///
+/// This is a [wyrand](https://github.com/wangyi-fudan/wyhash) generator: it is fast and has
+/// a small state, but is not suitable for cryptographic use.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct DeterministicRng {
+    state: u64,
+}
+
+impl DeterministicRng {
+    /// Creates a new generator from a seed.
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Creates an independent generator, seeded from this one.
+    ///
+    /// Forking gives each system or entity its own stream of numbers, so that drawing more
+    /// numbers in one place does not change the numbers drawn elsewhere.
+    pub fn fork(&mut self) -> Self {
+        Self::new(self.next_u64())
+    }
+
+    /// Returns the next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0xa076_1d64_78bd_642f);
+        let t = u128::from(self.state) * u128::from(self.state ^ 0xe703_7ed1_a0b4_28db);
+        (t >> 64) as u64 ^ t as u64
+    }
+
+    /// Returns the next random `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a random `f32` in the `[0, 1)` range.
+    pub fn next_f32(&mut self) -> f32 {
+        // Use the 24 most significant bits, the precision of an `f32` mantissa.
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a random `f64` in the `[0, 1)` range.
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the 53 most significant bits, the precision of an `f64` mantissa.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random `u64` in the `[0, bound)` range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is `0`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(
+            bound > 0,
+            "the bound of `DeterministicRng::below` must not be 0"
+        );
+        // Lemire's multiply-shift method, with rejection to remove the bias.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(bound);
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Update;
+    use bevy_ecs::schedule::LogLevel;
+
+    #[test]
+    fn rng_is_reproducible() {
+        let mut a = DeterministicRng::new(7);
+        let mut b = DeterministicRng::new(7);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        let mut forked = a.fork();
+        assert_ne!(forked.next_u64(), a.next_u64());
+    }
+
+    #[test]
+    fn rng_ranges() {
+        let mut rng = DeterministicRng::new(0);
+        for _ in 0..1000 {
+            assert!(rng.below(10) < 10);
+            assert!((0.0..1.0).contains(&rng.next_f32()));
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+        }
+    }
+
+    #[test]
+    fn single_threaded_schedules() {
+        let mut app = App::new();
+        app.add_systems(Update, || {})
+            .add_plugins(DeterminismPlugin::default());
+        app.finish();
+        app.cleanup();
+
+        let schedules = app.world().resource::<Schedules>();
+        assert_eq!(
+            schedules.get(Update).unwrap().get_executor_kind(),
+            ExecutorKind::SingleThreaded
+        );
+        assert!(app.world().contains_resource::<DeterministicRng>());
+    }
+
+    #[test]
+    fn parallel_schedules_order_ambiguous_systems() {
+        let mut app = App::new();
+        app.add_systems(Update, || {})
+            .add_plugins(DeterminismPlugin {
+                seed: 0,
+                execution: DeterministicExecution::Parallel,
+            });
+        app.edit_schedule(Update, |schedule| {
+            let mut settings = schedule.get_build_settings();
+            settings.ambiguity_detection = LogLevel::Error;
+            schedule.set_build_settings(settings);
+        });
+        app.finish();
+        app.cleanup();
+
+        let schedules = app.world().resource::<Schedules>();
+        let settings = schedules.get(Update).unwrap().get_build_settings();
+        assert!(settings.order_ambiguous_systems);
+        // The ambiguity detection chosen by the app is left untouched
+        assert_eq!(settings.ambiguity_detection, LogLevel::Error);
+    }
+}
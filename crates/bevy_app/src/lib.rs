@@ -8,21 +8,25 @@
 //! This crate is about everything concerning the highest-level, application layer of a Bevy app.
 
 mod app;
+mod determinism;
 mod main_schedule;
 mod panic_handler;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
 mod sub_app;
+mod sub_app_channel;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+pub use determinism::*;
 pub use main_schedule::*;
 pub use panic_handler::*;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
 pub use sub_app::*;
+pub use sub_app_channel::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
@@ -31,7 +35,8 @@ pub mod prelude {
         app::{App, AppExit},
         main_schedule::{
             First, FixedFirst, FixedLast, FixedPostUpdate, FixedPreUpdate, FixedUpdate, Last, Main,
-            PostStartup, PostUpdate, PreStartup, PreUpdate, SpawnScene, Startup, Update,
+            PostStartup, PostUpdate, PreStartup, PreUpdate, SpawnScene, Startup, StartupSet,
+            Update,
         },
         sub_app::SubApp,
         DynamicPlugin, Plugin, PluginGroup,
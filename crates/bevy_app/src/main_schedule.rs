@@ -1,6 +1,9 @@
 use crate::{App, Plugin};
 use bevy_ecs::{
-    schedule::{ExecutorKind, InternedScheduleLabel, Schedule, ScheduleLabel},
+    schedule::{
+        ExecutorKind, InternedScheduleLabel, IntoSystemSetConfigs, Schedule, ScheduleLabel,
+        SystemSet,
+    },
     system::{Local, Resource},
     world::{Mut, World},
 };
@@ -52,6 +55,34 @@ pub struct PreStartup;
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Startup;
 
+/// System sets of the [`Startup`] schedule, used to order the startup systems added by
+/// different plugins.
+///
+/// The sets run in the order of their variants. Plugins that need to initialize something the
+/// user startup code relies on should add their systems to the matching set, and user startup
+/// code that depends on it should run in [`StartupSet::UserSetup`]. Systems of the [`Startup`]
+/// schedule that aren't in any of these sets aren't ordered relative to them.
+///
+/// ```
+/// # use bevy_app::{prelude::*, StartupSet};
+/// # use bevy_ecs::prelude::*;
+/// # fn setup_camera() {}
+/// App::new().add_systems(Startup, setup_camera.in_set(StartupSet::UserSetup));
+/// ```
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StartupSet {
+    /// Initialization of the asset server and asset processing.
+    AssetSetup,
+    /// Initialization of the rendering resources, such as default materials.
+    ///
+    /// This runs after [`StartupSet::AssetSetup`], so assets can be loaded and added.
+    RenderSetup,
+    /// Startup code of the application itself.
+    ///
+    /// This runs after all the other startup sets.
+    UserSetup,
+}
+
 /// The schedule that runs once after [`Startup`].
 ///
 /// See the [`Main`] schedule for some details about how schedules are run.
@@ -253,7 +284,16 @@ impl Plugin for MainSchedulePlugin {
             .init_resource::<MainScheduleOrder>()
             .init_resource::<FixedMainScheduleOrder>()
             .add_systems(Main, Main::run_main)
-            .add_systems(FixedMain, FixedMain::run_fixed_main);
+            .add_systems(FixedMain, FixedMain::run_fixed_main)
+            .configure_sets(
+                Startup,
+                (
+                    StartupSet::AssetSetup,
+                    StartupSet::RenderSetup,
+                    StartupSet::UserSetup,
+                )
+                    .chain(),
+            );
 
         #[cfg(feature = "bevy_debug_stepping")]
         {
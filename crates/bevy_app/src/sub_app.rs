@@ -186,6 +186,15 @@ impl SubApp {
         self
     }
 
+    /// See [`App::add_systems_once`].
+    pub fn add_systems_once<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.add_systems(schedule, systems.run_if(run_once()))
+    }
+
     /// See [`App::register_system`].
     pub fn register_system<I: 'static, O: 'static, M, S: IntoSystem<I, O, M> + 'static>(
         &mut self,
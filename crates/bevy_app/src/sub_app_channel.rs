@@ -0,0 +1,83 @@
+use bevy_ecs::system::Resource;
+use bevy_utils::synccell::SyncCell;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Creates a typed channel to pass messages between the worlds of an [`App`](crate::App)
+/// and its [`SubApp`](crate::SubApp)s.
+///
+/// The [`SubAppSender`] and [`SubAppReceiver`] halves are resources meant to be inserted into
+/// two different worlds, which is what [`App::add_channel_to_sub_app`](crate::App::add_channel_to_sub_app)
+/// and [`App::add_channel_from_sub_app`](crate::App::add_channel_from_sub_app) do.
+///
+/// # Delivery guarantees
+///
+/// - Every message sent while the receiver exists is received exactly once.
+/// - Messages from a given sender are received in the order they were sent.
+/// - The channel is unbounded: sending never blocks, and messages are buffered until received.
+/// - Messages are not tied to frames. When the sub-app runs in parallel with the main app
+///   (for example with pipelined rendering), a message may only be received one or more
+///   frames after it was sent, so receivers should drain the channel every frame.
+pub fn sub_app_channel<T: Send + 'static>() -> (SubAppSender<T>, SubAppReceiver<T>) {
+    let (sender, receiver) = mpsc::channel();
+    (
+        SubAppSender(sender),
+        SubAppReceiver(SyncCell::new(receiver)),
+    )
+}
+
+/// The sending half of a [`sub_app_channel`].
+///
+/// Senders can be cloned to send messages from several places.
+#[derive(Resource, Debug)]
+pub struct SubAppSender<T: Send + 'static>(Sender<T>);
+
+impl<T: Send + 'static> Clone for SubAppSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Send + 'static> SubAppSender<T> {
+    /// Sends a message to the [`SubAppReceiver`].
+    ///
+    /// If the receiver has been dropped, the message is returned as an error.
+    pub fn send(&self, message: T) -> Result<(), T> {
+        self.0.send(message).map_err(|error| error.0)
+    }
+}
+
+/// The receiving half of a [`sub_app_channel`].
+#[derive(Resource)]
+pub struct SubAppReceiver<T: Send + 'static>(SyncCell<Receiver<T>>);
+
+impl<T: Send + 'static> SubAppReceiver<T> {
+    /// Returns the next pending message, if any.
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.0.get().try_recv().ok()
+    }
+
+    /// Returns an iterator over all the pending messages, removing them from the channel.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.0.get().try_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_received_in_order() {
+        let (sender, mut receiver) = sub_app_channel();
+        let other_sender = sender.clone();
+        sender.send(1).unwrap();
+        other_sender.send(2).unwrap();
+        sender.send(3).unwrap();
+
+        assert_eq!(receiver.drain().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(receiver.try_recv(), None);
+
+        drop(receiver);
+        assert_eq!(sender.send(4), Err(4));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Asset, AssetEvent, AssetId, AssetServer, Assets, Handle};
+use bevy_ecs::{
+    event::{Event, EventReader, EventWriter},
+    system::{Res, ResMut, Resource},
+};
+use bevy_utils::HashMap;
+use std::{any::TypeId, fmt::Debug, sync::Arc};
+
+/// Limits the memory used by the assets of the types registered with
+/// [`AssetApp::register_asset_size_estimator`](crate::AssetApp::register_asset_size_estimator).
+///
+/// While a budget is set, the assets of these types loaded by the [`AssetServer`] are kept in
+/// memory after their last [`Handle`] is dropped, so that loading them again is free. When the
+/// estimated size of the assets exceeds [`max_bytes`](Self::max_bytes), the least recently used
+/// of these unreferenced assets are unloaded, and an [`AssetEvicted`] event is sent for each of
+/// them. The assets with live handles, and the assets added directly to [`Assets`], are counted
+/// in the budget but never evicted.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AssetBudget {
+    /// The maximum estimated size of the assets, in bytes, or `None` to keep no unreferenced
+    /// asset in memory.
+    pub max_bytes: Option<usize>,
+    used_bytes: HashMap<TypeId, usize>,
+}
+
+impl AssetBudget {
+    /// Creates a budget of `max_bytes` bytes.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+            used_bytes: HashMap::default(),
+        }
+    }
+
+    /// Returns the estimated size of the assets of all the registered types, in bytes.
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes.values().sum()
+    }
+
+    /// Returns the estimated size of the assets of type `A`, in bytes.
+    pub fn used_bytes_of<A: Asset>(&self) -> usize {
+        self.used_bytes
+            .get(&TypeId::of::<A>())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the assets use more than [`max_bytes`](Self::max_bytes).
+    pub fn is_exceeded(&self) -> bool {
+        self.max_bytes
+            .is_some_and(|max_bytes| self.used_bytes() > max_bytes)
+    }
+}
+
+/// Sent when an unreferenced asset is unloaded to stay within the [`AssetBudget`].
+#[derive(Event)]
+pub struct AssetEvicted<A: Asset> {
+    /// The id of the unloaded asset.
+    pub id: AssetId<A>,
+    /// The estimated size of the asset, in bytes.
+    pub size: usize,
+}
+
+impl<A: Asset> Clone for AssetEvicted<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Asset> Copy for AssetEvicted<A> {}
+
+impl<A: Asset> Debug for AssetEvicted<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssetEvicted")
+            .field("id", &self.id)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// The assets of type `A` counted in the [`AssetBudget`].
+#[derive(Resource)]
+pub(crate) struct AssetBudgetTracker<A: Asset> {
+    size_of: fn(&A) -> usize,
+    assets: HashMap<AssetId<A>, TrackedAsset<A>>,
+    /// Incremented on every update, to find the least recently used assets.
+    tick: u64,
+}
+
+struct TrackedAsset<A: Asset> {
+    size: usize,
+    last_used: u64,
+    /// Keeps loaded assets in memory while a budget is set.
+    handle: Option<Handle<A>>,
+}
+
+impl<A: Asset> TrackedAsset<A> {
+    /// Returns `true` if only the budget keeps this asset alive.
+    fn is_evictable(&self) -> bool {
+        matches!(&self.handle, Some(Handle::Strong(handle)) if Arc::strong_count(handle) == 1)
+    }
+}
+
+impl<A: Asset> AssetBudgetTracker<A> {
+    pub(crate) fn new(size_of: fn(&A) -> usize) -> Self {
+        Self {
+            size_of,
+            assets: HashMap::default(),
+            tick: 0,
+        }
+    }
+}
+
+/// Updates the estimated size of the assets of type `A`, and evicts the least recently used
+/// unreferenced ones when the [`AssetBudget`] is exceeded.
+pub(crate) fn update_asset_budget<A: Asset>(
+    mut tracker: ResMut<AssetBudgetTracker<A>>,
+    mut budget: ResMut<AssetBudget>,
+    mut asset_events: EventReader<AssetEvent<A>>,
+    mut evicted_events: EventWriter<AssetEvicted<A>>,
+    assets: Res<Assets<A>>,
+    asset_server: Res<AssetServer>,
+) {
+    let tracker = &mut *tracker;
+    tracker.tick += 1;
+    let tick = tracker.tick;
+    let retain = budget.max_bytes.is_some();
+
+    for event in asset_events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let Some(asset) = assets.get(id) else {
+                    continue;
+                };
+                let size = (tracker.size_of)(asset);
+                let tracked = tracker.assets.entry(id).or_insert(TrackedAsset {
+                    size,
+                    last_used: tick,
+                    handle: None,
+                });
+                tracked.size = size;
+                if retain && tracked.handle.is_none() {
+                    tracked.handle = asset_server.get_id_handle(id);
+                }
+            }
+            AssetEvent::Removed { id } => {
+                tracker.assets.remove(&id);
+            }
+            AssetEvent::Unused { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    let mut used_bytes = 0;
+    for tracked in tracker.assets.values_mut() {
+        if !retain {
+            tracked.handle = None;
+        } else if !tracked.is_evictable() {
+            tracked.last_used = tick;
+        }
+        used_bytes += tracked.size;
+    }
+    budget.used_bytes.insert(TypeId::of::<A>(), used_bytes);
+
+    if !budget.is_exceeded() {
+        return;
+    }
+    let mut evictable = tracker
+        .assets
+        .iter()
+        .filter(|(_, tracked)| tracked.is_evictable())
+        .map(|(id, tracked)| (*id, tracked.last_used))
+        .collect::<Vec<_>>();
+    evictable.sort_unstable_by_key(|(_, last_used)| *last_used);
+    for (id, _) in evictable {
+        if !budget.is_exceeded() {
+            break;
+        }
+        // Dropping the last handle unloads the asset
+        let Some(tracked) = tracker.assets.remove(&id) else {
+            continue;
+        };
+        used_bytes -= tracked.size;
+        budget.used_bytes.insert(TypeId::of::<A>(), used_bytes);
+        evicted_events.send(AssetEvicted {
+            id,
+            size: tracked.size,
+        });
+    }
+}
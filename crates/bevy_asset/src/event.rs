@@ -41,6 +41,22 @@ impl<A: Asset> From<&AssetLoadFailedEvent<A>> for UntypedAssetLoadFailedEvent {
     }
 }
 
+/// An event emitted when an asset is reloaded, for each of the assets that depend on it.
+///
+/// Assets depending on the reloaded asset through other assets, such as a scene using a material
+/// using a reloaded texture, receive an event too. This lets systems caching data derived from an
+/// asset and its dependencies, such as bind groups, invalidate only the affected entries.
+///
+/// These events are only emitted when the [`AssetServer`](crate::AssetServer) is watching for
+/// changes, as that's when dependants are tracked.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssetDependencyReloadedEvent {
+    /// The asset depending on the reloaded asset.
+    pub id: UntypedAssetId,
+    /// The reloaded asset that triggered this event.
+    pub root: UntypedAssetId,
+}
+
 /// Events that occur for a specific loaded [`Asset`], such as "value changed" events and "dependency" events.
 #[derive(Event)]
 pub enum AssetEvent<A: Asset> {
@@ -21,6 +21,7 @@ pub mod prelude {
 }
 
 mod assets;
+mod budget;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -34,6 +35,7 @@ mod server;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
+pub use budget::{AssetBudget, AssetEvicted};
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -52,6 +54,7 @@ pub use server::*;
 pub use ron;
 
 use crate::{
+    budget::{update_asset_budget, AssetBudgetTracker},
     io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
     processor::{AssetProcessor, Process},
 };
@@ -200,7 +203,10 @@ impl Plugin for AssetPlugin {
                             watch,
                         ))
                         .insert_resource(processor)
-                        .add_systems(bevy_app::Startup, AssetProcessor::start);
+                        .add_systems(
+                            bevy_app::Startup,
+                            AssetProcessor::start.in_set(bevy_app::StartupSet::AssetSetup),
+                        );
                     }
                     #[cfg(not(feature = "asset_processor"))]
                     {
@@ -221,6 +227,8 @@ impl Plugin for AssetPlugin {
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
             .add_event::<UntypedAssetLoadFailedEvent>()
+            .add_event::<AssetDependencyReloadedEvent>()
+            .init_resource::<AssetBudget>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
             .add_systems(PreUpdate, handle_internal_asset_events)
             .register_type::<AssetPath>();
@@ -314,6 +322,12 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Counts the [`Asset`] type `A` in the [`AssetBudget`], using `size_of` to estimate the size
+    /// of each asset in bytes.
+    ///
+    /// The unreferenced assets of this type are kept in memory until the budget is exceeded, and
+    /// an [`AssetEvicted`] event is sent when they are unloaded.
+    fn register_asset_size_estimator<A: Asset>(&mut self, size_of: fn(&A) -> usize) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -417,6 +431,13 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn register_asset_size_estimator<A: Asset>(&mut self, size_of: fn(&A) -> usize) -> &mut Self {
+        self.init_resource::<AssetBudget>()
+            .insert_resource(AssetBudgetTracker::new(size_of))
+            .add_event::<AssetEvicted<A>>()
+            .add_systems(Last, update_asset_budget::<A>.after(AssetEvents))
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -441,9 +462,9 @@ mod tests {
             AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState,
-        RecursiveDependencyLoadState,
+        Asset, AssetApp, AssetBudget, AssetDependencyReloadedEvent, AssetEvent, AssetEvicted,
+        AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetServer, Assets,
+        DependencyLoadState, LoadState, RecursiveDependencyLoadState,
     };
     use bevy_app::{App, Update};
     use bevy_core::TaskPoolPlugin;
@@ -1145,6 +1166,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn evict_unused_assets_over_budget() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        for path in ["a.cool.ron", "b.cool.ron", "c.cool.ron"] {
+            dir.insert_asset_text(Path::new(path), SIMPLE_TEXT);
+        }
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .register_asset_size_estimator::<CoolText>(|text| text.text.len())
+            .insert_resource(AssetBudget::new(6));
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let mut evicted_reader = ManualEventReader::<AssetEvicted<CoolText>>::default();
+
+        gate_opener.open("a.cool.ron");
+        gate_opener.open("b.cool.ron");
+        let a = asset_server.load::<CoolText>("a.cool.ron");
+        let b = asset_server.load::<CoolText>("b.cool.ron");
+        run_app_until(&mut app, |world| {
+            (world.resource::<AssetBudget>().used_bytes() == 6).then_some(())
+        });
+
+        // The budget keeps the asset in memory after its handle is dropped
+        let a_id = a.id();
+        drop(a);
+        app.update();
+        app.update();
+        assert!(get(app.world(), a_id).is_some());
+
+        // Exceeding the budget unloads the unreferenced asset
+        gate_opener.open("c.cool.ron");
+        let c = asset_server.load::<CoolText>("c.cool.ron");
+        run_app_until(&mut app, |world| get(world, c.id()).map(|_| ()));
+        app.update();
+        let evicted_events = app.world().resource::<Events<AssetEvicted<CoolText>>>();
+        let evicted = evicted_reader
+            .read(evicted_events)
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert_eq!(evicted, vec![a_id]);
+        assert!(get(app.world(), a_id).is_none());
+        assert!(get(app.world(), b.id()).is_some());
+        assert_eq!(app.world().resource::<AssetBudget>().used_bytes(), 6);
+    }
+
     #[test]
     fn manual_asset_management() {
         // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
@@ -1467,6 +1539,85 @@ mod tests {
         });
     }
 
+    #[test]
+    fn reload_sends_dependency_reloaded_events() {
+        let dir = Dir::default();
+        let cool_text = |text: &str, dependencies: &[&str]| {
+            format!(
+                "(text: {text:?}, dependencies: {dependencies:?}, embedded_dependencies: [], sub_texts: [])"
+            )
+        };
+        dir.insert_asset_text(Path::new("a.cool.ron"), &cool_text("a", &["b.cool.ron"]));
+        dir.insert_asset_text(Path::new("b.cool.ron"), &cool_text("b", &["c.cool.ron"]));
+        dir.insert_asset_text(Path::new("c.cool.ron"), &cool_text("c", &[]));
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir.clone() };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(true),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        run_app_until(&mut app, |world| {
+            let server = world.resource::<AssetServer>();
+            server.is_loaded_with_dependencies(&a).then_some(())
+        });
+        let b = asset_server.get_handle::<CoolText>("b.cool.ron").unwrap();
+        let c = asset_server.get_handle::<CoolText>("c.cool.ron").unwrap();
+
+        // The initial loads don't send any event.
+        let mut reader = app
+            .world()
+            .resource::<Events<AssetDependencyReloadedEvent>>()
+            .get_reader();
+        assert_eq!(
+            reader
+                .read(
+                    app.world()
+                        .resource::<Events<AssetDependencyReloadedEvent>>()
+                )
+                .count(),
+            0
+        );
+
+        dir.insert_asset_text(Path::new("c.cool.ron"), &cool_text("c2", &[]));
+        asset_server.reload("c.cool.ron");
+        let mut received = Vec::new();
+        run_app_until(&mut app, |world| {
+            let events = world.resource::<Events<AssetDependencyReloadedEvent>>();
+            received.extend(reader.read(events).copied());
+            (received.len() >= 2).then_some(())
+        });
+        received.sort_by_key(|event| event.id != a.id().untyped());
+        assert_eq!(
+            received,
+            vec![
+                AssetDependencyReloadedEvent {
+                    id: a.id().untyped(),
+                    root: c.id().untyped(),
+                },
+                AssetDependencyReloadedEvent {
+                    id: b.id().untyped(),
+                    root: c.id().untyped(),
+                },
+            ]
+        );
+        assert_eq!(get::<CoolText>(app.world(), c.id()).unwrap().text, "c2");
+    }
+
     #[test]
     fn ignore_system_ambiguities_on_assets() {
         let mut app = App::new();
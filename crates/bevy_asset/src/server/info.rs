@@ -1,8 +1,8 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetDependencyReloadedEvent, AssetHandleProvider, AssetLoadError, AssetPath,
+    DependencyLoadState, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use bevy_ecs::world::World;
 use bevy_utils::tracing::warn;
@@ -27,6 +27,12 @@ pub(crate) struct AssetInfo {
     failed_rec_dependencies: HashSet<UntypedAssetId>,
     dependants_waiting_on_load: HashSet<UntypedAssetId>,
     dependants_waiting_on_recursive_dep_load: HashSet<UntypedAssetId>,
+    /// The dependencies of this asset.
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true`.
+    dependencies: HashSet<UntypedAssetId>,
+    /// The assets that have this asset as a dependency, notified when this asset is reloaded.
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true`.
+    dependants: HashSet<UntypedAssetId>,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
     /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
@@ -54,6 +60,8 @@ impl AssetInfo {
             loader_dependencies: HashMap::default(),
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
+            dependencies: HashSet::default(),
+            dependants: HashSet::default(),
             handle_drops_to_skip: 0,
         }
     }
@@ -378,6 +386,9 @@ impl AssetInfos {
         sender: &Sender<InternalAssetEvent>,
     ) {
         loaded_asset.value.insert(loaded_asset_id, world);
+        if self.watching_for_changes {
+            self.track_dependants(loaded_asset_id, &loaded_asset.dependencies);
+        }
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
@@ -494,6 +505,14 @@ impl AssetInfos {
             )
         };
 
+        // The dependants that aren't waiting on this asset were loaded with a previous version of it.
+        let reloaded_events = self.reloaded_dependant_events(loaded_asset_id, |id| {
+            !dependants_waiting_on_load.contains(&id)
+        });
+        if !reloaded_events.is_empty() {
+            world.send_event_batch(reloaded_events);
+        }
+
         for id in dependants_waiting_on_load {
             if let Some(info) = self.get_mut(id) {
                 info.loading_dependencies.remove(&loaded_asset_id);
@@ -524,6 +543,57 @@ impl AssetInfos {
         }
     }
 
+    /// Replaces the tracked dependencies of `id` with `dependencies`, updating the dependants of
+    /// the old and new dependencies.
+    fn track_dependants(&mut self, id: UntypedAssetId, dependencies: &HashSet<UntypedAssetId>) {
+        let Some(info) = self.infos.get_mut(&id) else {
+            return;
+        };
+        let old_dependencies = std::mem::replace(&mut info.dependencies, dependencies.clone());
+        for dependency in old_dependencies {
+            if let Some(dependency_info) = self.infos.get_mut(&dependency) {
+                dependency_info.dependants.remove(&id);
+            }
+        }
+        for dependency in dependencies {
+            if let Some(dependency_info) = self.infos.get_mut(dependency) {
+                dependency_info.dependants.insert(id);
+            }
+        }
+    }
+
+    /// Returns an [`AssetDependencyReloadedEvent`] for each asset depending on the reloaded
+    /// `root` asset, directly or not.
+    ///
+    /// Only the direct dependants for which `filter` returns `true` are considered.
+    fn reloaded_dependant_events(
+        &self,
+        root: UntypedAssetId,
+        filter: impl Fn(UntypedAssetId) -> bool,
+    ) -> Vec<AssetDependencyReloadedEvent> {
+        let Some(info) = self.infos.get(&root) else {
+            return Vec::new();
+        };
+        let mut visited = HashSet::new();
+        let mut pending: Vec<_> = info
+            .dependants
+            .iter()
+            .copied()
+            .filter(|id| filter(*id))
+            .collect();
+        let mut events = Vec::new();
+        while let Some(id) = pending.pop() {
+            if id == root || !visited.insert(id) {
+                continue;
+            }
+            events.push(AssetDependencyReloadedEvent { id, root });
+            if let Some(info) = self.infos.get(&id) {
+                pending.extend(info.dependants.iter().copied());
+            }
+        }
+        events
+    }
+
     /// Recursively propagates loaded state up the dependency tree.
     fn propagate_loaded_state(
         infos: &mut AssetInfos,
@@ -659,6 +729,13 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        if watching_for_changes {
+            for dependency in &info.dependencies {
+                if let Some(dependency_info) = infos.get_mut(dependency) {
+                    dependency_info.dependants.remove(&id);
+                }
+            }
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -1,6 +1,6 @@
 use crate::{
-    AudioSourceBundle, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode,
-    PlaybackSettings, SpatialAudioSink, SpatialListener,
+    effects::EffectsSource, AudioEffects, AudioSourceBundle, Decodable, DefaultSpatialScale,
+    GlobalVolume, PlaybackMode, PlaybackSettings, SinkEffects, SpatialAudioSink, SpatialListener,
 };
 use bevy_asset::{Asset, Assets, Handle};
 use bevy_ecs::{prelude::*, system::SystemParam};
@@ -105,6 +105,7 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             &Handle<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&AudioEffects>,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
@@ -119,10 +120,13 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
         return;
     };
 
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    for (entity, source_handle, settings, maybe_emitter_transform, maybe_effects) in
+        &query_nonplaying
+    {
         let Some(audio_source) = audio_sources.get(source_handle) else {
             continue;
         };
+        let effects = maybe_effects.map(SinkEffects::new).unwrap_or_default();
         // audio data is available (has loaded), begin playback and insert sink component
         if settings.spatial {
             let (left_ear, right_ear) = ear_positions.get();
@@ -167,26 +171,30 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
 
             match settings.mode {
                 PlaybackMode::Loop => {
-                    sink.append(audio_source.decoder().repeat_infinite());
-                    commands.entity(entity).insert(SpatialAudioSink { sink });
+                    append_with_effects(&sink, audio_source.decoder().repeat_infinite(), &effects);
+                    commands
+                        .entity(entity)
+                        .insert(SpatialAudioSink { sink, effects });
                 }
                 PlaybackMode::Once => {
-                    sink.append(audio_source.decoder());
-                    commands.entity(entity).insert(SpatialAudioSink { sink });
+                    append_with_effects(&sink, audio_source.decoder(), &effects);
+                    commands
+                        .entity(entity)
+                        .insert(SpatialAudioSink { sink, effects });
                 }
                 PlaybackMode::Despawn => {
-                    sink.append(audio_source.decoder());
+                    append_with_effects(&sink, audio_source.decoder(), &effects);
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((SpatialAudioSink { sink }, PlaybackDespawnMarker));
+                        .insert((SpatialAudioSink { sink, effects }, PlaybackDespawnMarker));
                 }
                 PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
+                    append_with_effects(&sink, audio_source.decoder(), &effects);
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((SpatialAudioSink { sink }, PlaybackRemoveMarker));
+                        .insert((SpatialAudioSink { sink, effects }, PlaybackRemoveMarker));
                 }
             };
         } else {
@@ -207,32 +215,65 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
 
             match settings.mode {
                 PlaybackMode::Loop => {
-                    sink.append(audio_source.decoder().repeat_infinite());
-                    commands.entity(entity).insert(AudioSink { sink });
+                    append_with_effects(&sink, audio_source.decoder().repeat_infinite(), &effects);
+                    commands.entity(entity).insert(AudioSink { sink, effects });
                 }
                 PlaybackMode::Once => {
-                    sink.append(audio_source.decoder());
-                    commands.entity(entity).insert(AudioSink { sink });
+                    append_with_effects(&sink, audio_source.decoder(), &effects);
+                    commands.entity(entity).insert(AudioSink { sink, effects });
                 }
                 PlaybackMode::Despawn => {
-                    sink.append(audio_source.decoder());
+                    append_with_effects(&sink, audio_source.decoder(), &effects);
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((AudioSink { sink }, PlaybackDespawnMarker));
+                        .insert((AudioSink { sink, effects }, PlaybackDespawnMarker));
                 }
                 PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
+                    append_with_effects(&sink, audio_source.decoder(), &effects);
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((AudioSink { sink }, PlaybackRemoveMarker));
+                        .insert((AudioSink { sink, effects }, PlaybackRemoveMarker));
                 }
             };
         }
     }
 }
 
+/// Appends `source` to `sink`, through the effects of the sink.
+///
+/// This is a separate function because appending the source in [`play_queued_audio_system`]
+/// confuses the trait solver with its `f32: FromSample<Source::DecoderItem>` bound.
+fn append_with_effects<S>(sink: &impl AppendSource, source: S, effects: &SinkEffects)
+where
+    S: Source + Send + 'static,
+    S::Item: rodio::Sample + Send,
+    f32: rodio::cpal::FromSample<S::Item>,
+{
+    sink.append_source(EffectsSource::new(
+        source.convert_samples(),
+        effects.clone(),
+    ));
+}
+
+/// The sinks sources can be appended to.
+trait AppendSource {
+    fn append_source(&self, source: impl Source<Item = f32> + Send + 'static);
+}
+
+impl AppendSource for Sink {
+    fn append_source(&self, source: impl Source<Item = f32> + Send + 'static) {
+        self.append(source);
+    }
+}
+
+impl AppendSource for SpatialSink {
+    fn append_source(&self, source: impl Source<Item = f32> + Send + 'static) {
+        self.append(source);
+    }
+}
+
 pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
     mut commands: Commands,
     query_nonspatial_despawn: Query<
@@ -0,0 +1,469 @@
+use std::{
+    f32::consts::TAU,
+    fmt,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::Duration,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use rodio::Source;
+
+use crate::{AudioSink, SpatialAudioSink};
+
+/// Parameters applied to the sound of an audio entity while it is playing.
+///
+/// Unlike [`PlaybackSettings`](crate::PlaybackSettings), changes to this component are applied
+/// to already-playing audio, so it can be updated every frame from gameplay systems, for example
+/// to muffle sounds that are occluded by walls or heard from under water.
+///
+/// The values are copied to the [`SinkEffects`] of the [`AudioSink`] or [`SpatialAudioSink`]
+/// of the entity, which can also be modified directly.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Default, Component)]
+pub struct AudioEffects {
+    /// The cutoff frequency of a low-pass filter applied to the sound, in hertz.
+    ///
+    /// `None` disables the filter.
+    pub low_pass_cutoff: Option<f32>,
+    /// The pitch multiplier of the sound.
+    ///
+    /// The value `1.0` is the "normal" pitch. Unlike the speed of the sink, this doesn't
+    /// affect the [`AudioSinkPlayback::speed`](crate::AudioSinkPlayback::speed) reported by the
+    /// sink, so both can be used independently. It is clamped by [`SinkEffects::set_pitch`].
+    pub pitch: f32,
+    /// The levels at which the sound is sent to auxiliary effects, such as reverb zones.
+    ///
+    /// The output of the [`SendEffect`] of each send, set with [`SinkEffects::set_send_effect`],
+    /// is scaled by its level and mixed with the sound. Sends without an effect are silent.
+    pub send_levels: [f32; SinkEffects::SEND_COUNT],
+}
+
+impl Default for AudioEffects {
+    fn default() -> Self {
+        Self {
+            low_pass_cutoff: None,
+            pitch: 1.0,
+            send_levels: [0.0; SinkEffects::SEND_COUNT],
+        }
+    }
+}
+
+/// Effect parameters of an audio sink, shared with the audio thread.
+///
+/// The sound of every sink created by Bevy goes through these parameters, which can be changed
+/// at any time with immediate effect. Cloning this returns a handle to the same parameters, so
+/// it can be moved to custom audio processing code running on another thread.
+#[derive(Clone, Debug)]
+pub struct SinkEffects(Arc<SinkEffectsState>);
+
+#[derive(Debug)]
+struct SinkEffectsState {
+    /// The `f32` bits of the low-pass cutoff. Non-finite values disable the filter.
+    low_pass_cutoff: AtomicU32,
+    /// The `f32` bits of the pitch multiplier.
+    pitch: AtomicU32,
+    /// The `f32` bits of the send levels.
+    send_levels: [AtomicU32; SinkEffects::SEND_COUNT],
+    /// The effects of the sends.
+    send_effects: [SendEffectSlot; SinkEffects::SEND_COUNT],
+    /// The `f32` bits of the gain.
+    gain: AtomicU32,
+}
+
+impl Default for SinkEffects {
+    fn default() -> Self {
+        Self(Arc::new(SinkEffectsState {
+            low_pass_cutoff: AtomicU32::new(f32::INFINITY.to_bits()),
+            pitch: AtomicU32::new(1.0f32.to_bits()),
+            send_levels: Default::default(),
+            send_effects: Default::default(),
+            gain: AtomicU32::new(1.0f32.to_bits()),
+        }))
+    }
+}
+
+impl SinkEffects {
+    /// The number of auxiliary sends of each sink.
+    pub const SEND_COUNT: usize = 4;
+
+    /// The lowest pitch multiplier, see [`SinkEffects::set_pitch`].
+    pub const MIN_PITCH: f32 = 1.0 / 64.0;
+
+    /// The highest pitch multiplier, see [`SinkEffects::set_pitch`].
+    pub const MAX_PITCH: f32 = 64.0;
+
+    /// Creates effect parameters initialized from `effects`.
+    pub fn new(effects: &AudioEffects) -> Self {
+        let sink_effects = Self::default();
+        sink_effects.apply(effects);
+        sink_effects
+    }
+
+    /// Sets all the parameters from `effects`.
+    pub fn apply(&self, effects: &AudioEffects) {
+        self.set_low_pass_cutoff(effects.low_pass_cutoff);
+        self.set_pitch(effects.pitch);
+        for (index, level) in effects.send_levels.iter().enumerate() {
+            self.set_send_level(index, *level);
+        }
+    }
+
+    /// Gets the cutoff frequency of the low-pass filter in hertz, if it is enabled.
+    pub fn low_pass_cutoff(&self) -> Option<f32> {
+        let cutoff = load(&self.0.low_pass_cutoff);
+        cutoff.is_finite().then_some(cutoff)
+    }
+
+    /// Sets the cutoff frequency of the low-pass filter in hertz.
+    ///
+    /// `None` disables the filter.
+    pub fn set_low_pass_cutoff(&self, cutoff: Option<f32>) {
+        store(
+            &self.0.low_pass_cutoff,
+            cutoff.map_or(f32::INFINITY, |cutoff| cutoff.max(0.0)),
+        );
+    }
+
+    /// Gets the pitch multiplier.
+    pub fn pitch(&self) -> f32 {
+        load(&self.0.pitch)
+    }
+
+    /// Sets the pitch multiplier.
+    ///
+    /// The value `1.0` is the "normal" pitch. The pitch is clamped between
+    /// [`SinkEffects::MIN_PITCH`] and [`SinkEffects::MAX_PITCH`], and NaN is ignored.
+    pub fn set_pitch(&self, pitch: f32) {
+        if !pitch.is_nan() {
+            store(
+                &self.0.pitch,
+                pitch.clamp(SinkEffects::MIN_PITCH, SinkEffects::MAX_PITCH),
+            );
+        }
+    }
+
+    /// Gets the level of the auxiliary send at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater or equal to [`SinkEffects::SEND_COUNT`].
+    pub fn send_level(&self, index: usize) -> f32 {
+        load(&self.0.send_levels[index])
+    }
+
+    /// Sets the level of the auxiliary send at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater or equal to [`SinkEffects::SEND_COUNT`].
+    pub fn set_send_level(&self, index: usize, level: f32) {
+        store(&self.0.send_levels[index], level);
+    }
+
+    /// Sets the effect processing the sound sent to the auxiliary send at `index`, replacing
+    /// the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater or equal to [`SinkEffects::SEND_COUNT`].
+    pub fn set_send_effect(&self, index: usize, effect: impl SendEffect) {
+        *self.0.send_effects[index].lock() = Some(Box::new(effect));
+    }
+
+    /// Removes the effect of the auxiliary send at `index`, which makes the send silent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater or equal to [`SinkEffects::SEND_COUNT`].
+    pub fn remove_send_effect(&self, index: usize) {
+        *self.0.send_effects[index].lock() = None;
+    }
+
+    /// Returns the sum of the outputs of the send effects for a `sample`, scaled by the levels
+    /// of their sends.
+    fn process_sends(&self, channel: usize, sample: f32, sample_rate: u32) -> f32 {
+        let mut output = 0.0;
+        for (level, effect) in self.0.send_levels.iter().zip(&self.0.send_effects) {
+            // The audio thread skips an effect being replaced rather than waiting for it
+            let Ok(mut effect) = effect.0.try_lock() else {
+                continue;
+            };
+            if let Some(effect) = effect.as_mut() {
+                output += load(level) * effect.process(channel, sample, sample_rate);
+            }
+        }
+        output
+    }
+
+    /// Gets the gain multiplied with the sound, on top of the volume of the sink.
+    pub fn gain(&self) -> f32 {
+        load(&self.0.gain)
+    }
+
+    /// Sets the gain multiplied with the sound, on top of the volume of the sink.
+    ///
+    /// This is set by [`AudioDucking`](crate::AudioDucking), and isn't part of the
+    /// [`AudioEffects`], so that it doesn't change the volume set by gameplay code.
+    pub fn set_gain(&self, gain: f32) {
+        store(&self.0.gain, gain.max(0.0));
+    }
+}
+
+/// An effect processing the sound sent to an auxiliary send of a sink, such as a reverb.
+///
+/// The effect runs on the audio thread, and its output is scaled by the level of the send and
+/// mixed with the sound of the sink.
+pub trait SendEffect: Send + 'static {
+    /// Processes a `sample` of the `channel` of a sound played at `sample_rate`, and returns the
+    /// output of the effect for this sample.
+    fn process(&mut self, channel: usize, sample: f32, sample_rate: u32) -> f32;
+}
+
+#[derive(Default)]
+struct SendEffectSlot(Mutex<Option<Box<dyn SendEffect>>>);
+
+impl SendEffectSlot {
+    fn lock(&self) -> MutexGuard<'_, Option<Box<dyn SendEffect>>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for SendEffectSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SendEffectSlot")
+            .field(&self.0.try_lock().map(|effect| effect.is_some()))
+            .finish()
+    }
+}
+
+fn load(value: &AtomicU32) -> f32 {
+    f32::from_bits(value.load(Ordering::Relaxed))
+}
+
+fn store(value: &AtomicU32, new_value: f32) {
+    value.store(new_value.to_bits(), Ordering::Relaxed);
+}
+
+/// A [`Source`] applying the [`SinkEffects`] of a sink to the sound of another source.
+pub(crate) struct EffectsSource<S> {
+    input: S,
+    effects: SinkEffects,
+    channels: u16,
+    /// The two input frames the output is interpolated between.
+    previous_frame: Vec<f32>,
+    next_frame: Vec<f32>,
+    /// The position of the output between `previous_frame` and `next_frame`.
+    position: f32,
+    /// The index of the next output sample in the current frame.
+    channel: usize,
+    /// The state of the low-pass filter, for each channel.
+    filtered: Vec<f32>,
+    /// Whether the input has ended, meaning `next_frame` is the last frame.
+    ended: bool,
+    /// Whether the output has ended.
+    done: bool,
+}
+
+impl<S: Source<Item = f32>> EffectsSource<S> {
+    pub(crate) fn new(input: S, effects: SinkEffects) -> Self {
+        let channels = input.channels();
+        let mut source = Self {
+            input,
+            effects,
+            channels,
+            previous_frame: vec![0.0; channels as usize],
+            next_frame: vec![0.0; channels as usize],
+            position: 0.0,
+            channel: 0,
+            filtered: vec![0.0; channels as usize],
+            ended: false,
+            done: false,
+        };
+        source.done = !source.read_frame();
+        source.ended = !source.read_frame();
+        source.filtered.clone_from(&source.previous_frame);
+        source
+    }
+
+    /// Reads the next input frame into `next_frame`, moving its current value to
+    /// `previous_frame`.
+    ///
+    /// Returns `false` if the input has ended, in which case `next_frame` holds the value of
+    /// `previous_frame`.
+    fn read_frame(&mut self) -> bool {
+        std::mem::swap(&mut self.previous_frame, &mut self.next_frame);
+        for channel in 0..self.next_frame.len() {
+            let Some(value) = self.input.next() else {
+                self.next_frame.clone_from(&self.previous_frame);
+                return false;
+            };
+            self.next_frame[channel] = value;
+        }
+        true
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for EffectsSource<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.done {
+            return None;
+        }
+        if self.channel == self.channels as usize {
+            self.channel = 0;
+            self.position += self.effects.pitch();
+            while self.position >= 1.0 {
+                self.position -= 1.0;
+                if self.ended {
+                    self.done = true;
+                    return None;
+                }
+                self.ended = !self.read_frame();
+            }
+        }
+
+        let channel = self.channel;
+        self.channel += 1;
+
+        let previous = self.previous_frame[channel];
+        let value = previous + (self.next_frame[channel] - previous) * self.position;
+
+        let sample_rate = self.input.sample_rate();
+        let value = match self.effects.low_pass_cutoff() {
+            Some(cutoff) => {
+                // A one-pole low-pass filter.
+                let alpha = 1.0 - (-TAU * cutoff / sample_rate as f32).exp();
+                self.filtered[channel] += alpha * (value - self.filtered[channel]);
+                self.filtered[channel]
+            }
+            None => {
+                self.filtered[channel] = value;
+                value
+            }
+        };
+        let sent = self.effects.process_sends(channel, value, sample_rate);
+        Some((value + sent) * self.effects.gain())
+    }
+}
+
+impl<S: Source<Item = f32>> Source for EffectsSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        // The input is resampled, so its frame boundaries can't be predicted.
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Copies the [`AudioEffects`] of audio entities to their sinks when they change.
+pub(crate) fn update_audio_effects(
+    sinks: Query<(&AudioEffects, &AudioSink), Changed<AudioEffects>>,
+    spatial_sinks: Query<(&AudioEffects, &SpatialAudioSink), Changed<AudioEffects>>,
+) {
+    for (effects, sink) in &sinks {
+        sink.effects().apply(effects);
+    }
+    for (effects, sink) in &spatial_sinks {
+        sink.effects().apply(effects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{AudioEffects, EffectsSource, SendEffect, SinkEffects};
+
+    fn process(channels: u16, samples: Vec<f32>, effects: &SinkEffects) -> Vec<f32> {
+        EffectsSource::new(SamplesBuffer::new(channels, 100, samples), effects.clone()).collect()
+    }
+
+    #[test]
+    fn default_effects_keep_the_sound() {
+        let samples = vec![1.0, -1.0, 0.5, -0.5, 0.25, -0.25];
+        assert_eq!(
+            process(2, samples.clone(), &SinkEffects::default()),
+            samples
+        );
+    }
+
+    #[test]
+    fn pitch_resamples_the_sound() {
+        let effects = SinkEffects::default();
+        effects.set_pitch(2.0);
+        assert_eq!(
+            process(1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0], &effects),
+            vec![0.0, 2.0, 4.0]
+        );
+
+        // The frames in between input frames are interpolated
+        effects.set_pitch(0.5);
+        assert_eq!(
+            process(1, vec![0.0, 1.0, 2.0], &effects),
+            vec![0.0, 0.5, 1.0, 1.5, 2.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn invalid_pitches_are_clamped() {
+        let effects = SinkEffects::default();
+        effects.set_pitch(-1.0);
+        assert_eq!(effects.pitch(), SinkEffects::MIN_PITCH);
+        effects.set_pitch(f32::INFINITY);
+        assert_eq!(effects.pitch(), SinkEffects::MAX_PITCH);
+        effects.set_pitch(f32::NAN);
+        assert_eq!(effects.pitch(), SinkEffects::MAX_PITCH);
+    }
+
+    #[test]
+    fn low_pass_filter_smooths_the_sound() {
+        let effects = SinkEffects::new(&AudioEffects {
+            low_pass_cutoff: Some(10.0),
+            ..Default::default()
+        });
+        let output = process(1, vec![0.0, 1.0, 1.0, 1.0, 1.0], &effects);
+        assert_eq!(output[0], 0.0);
+        for window in output.windows(2) {
+            assert!(window[0] < window[1]);
+        }
+        assert!(output.iter().all(|&sample| sample < 1.0));
+    }
+
+    #[test]
+    fn gain_and_sends_are_mixed_with_the_sound() {
+        struct Doubler;
+
+        impl SendEffect for Doubler {
+            fn process(&mut self, _channel: usize, sample: f32, _sample_rate: u32) -> f32 {
+                2.0 * sample
+            }
+        }
+
+        let effects = SinkEffects::default();
+        effects.set_gain(0.5);
+        assert_eq!(process(1, vec![1.0, 2.0], &effects), vec![0.5, 1.0]);
+
+        effects.set_send_level(1, 0.5);
+        // Sends without an effect are silent
+        assert_eq!(process(1, vec![1.0, 2.0], &effects), vec![0.5, 1.0]);
+        effects.set_send_effect(1, Doubler);
+        assert_eq!(process(1, vec![1.0, 2.0], &effects), vec![1.0, 2.0]);
+        effects.remove_send_effect(1);
+        assert_eq!(process(1, vec![1.0, 2.0], &effects), vec![0.5, 1.0]);
+    }
+}
@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_time::Time;
+
+use crate::{AudioSink, AudioSinkPlayback, SinkEffects, SpatialAudioSink};
+
+/// The shape of an [`AudioFade`], mapping the progress of the fade to the volume.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum FadeCurve {
+    /// The volume changes at a constant rate.
+    #[default]
+    Linear,
+    /// The volume changes slowly at the start of the fade, then quickly.
+    EaseIn,
+    /// The volume changes quickly at the start of the fade, then slowly.
+    EaseOut,
+    /// The volume changes slowly at both ends of the fade.
+    SmoothStep,
+    /// The power of the sound, the square of its volume, changes at a constant rate.
+    ///
+    /// In a crossfade between two sounds, their total power stays constant, which avoids the dip
+    /// in loudness of a linear crossfade.
+    EqualPower,
+}
+
+impl FadeCurve {
+    /// Returns the volume at `progress`, between `0.0` at the start of the fade and `1.0` at
+    /// its end.
+    pub fn volume(self, start: f32, target: f32, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        let t = match self {
+            FadeCurve::Linear => t,
+            FadeCurve::EaseIn => t * t,
+            FadeCurve::EaseOut => t * (2.0 - t),
+            FadeCurve::SmoothStep => t * t * (3.0 - 2.0 * t),
+            FadeCurve::EqualPower => {
+                return (start * start * (1.0 - t) + target * target * t).sqrt();
+            }
+        };
+        start + (target - start) * t
+    }
+}
+
+/// What happens to an audio entity at the end of its [`AudioFade`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum FadeEnd {
+    /// The sound keeps playing at the target volume.
+    #[default]
+    Keep,
+    /// The sound is paused.
+    Pause,
+    /// The entity is despawned, which stops the sound.
+    Despawn,
+}
+
+/// Fades the volume of the [`AudioSink`] or [`SpatialAudioSink`] of an entity to a target
+/// volume, over a duration of [`Time`].
+///
+/// The fade starts from the volume of the sink when it is created, so fading a sound in is done
+/// by playing it with a volume of zero. The component is removed at the end of the fade.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::AudioFade;
+/// fn change_music(mut commands: Commands, current: Entity, next: Entity) {
+///     // `next` is playing with a volume of zero
+///     let (fade_out, fade_in) = AudioFade::crossfade(1.0, Duration::from_secs(2));
+///     commands.entity(current).insert(fade_out);
+///     commands.entity(next).insert(fade_in);
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AudioFade {
+    /// The volume of the sink at the end of the fade.
+    pub target: f32,
+    /// The duration of the fade.
+    pub duration: Duration,
+    /// The shape of the fade.
+    pub curve: FadeCurve,
+    /// What happens to the entity at the end of the fade.
+    pub end: FadeEnd,
+    /// The volume of the sink when the fade started, once it has a sink.
+    start: Option<f32>,
+    elapsed: Duration,
+}
+
+impl AudioFade {
+    /// Fades the volume to `target` over `duration`, linearly.
+    pub fn to(target: f32, duration: Duration) -> Self {
+        Self {
+            target,
+            duration,
+            curve: FadeCurve::Linear,
+            end: FadeEnd::Keep,
+            start: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Fades the volume to zero over `duration`, then despawns the entity.
+    pub fn out(duration: Duration) -> Self {
+        Self::to(0.0, duration).with_end(FadeEnd::Despawn)
+    }
+
+    /// Returns the fades of a crossfade over `duration`: the first fades the current sound out
+    /// and despawns it, the second fades the next sound, playing with a volume of zero, in to
+    /// `volume`.
+    ///
+    /// Both fades use [`FadeCurve::EqualPower`].
+    pub fn crossfade(volume: f32, duration: Duration) -> (Self, Self) {
+        (
+            Self::out(duration).with_curve(FadeCurve::EqualPower),
+            Self::to(volume, duration).with_curve(FadeCurve::EqualPower),
+        )
+    }
+
+    /// Returns this fade with the given `curve`.
+    pub fn with_curve(mut self, curve: FadeCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Returns this fade with the given `end`.
+    pub fn with_end(mut self, end: FadeEnd) -> Self {
+        self.end = end;
+        self
+    }
+
+    /// Returns the progress of the fade, between `0.0` and `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        }
+    }
+}
+
+/// Lowers the volume of the [`AudioSink`] or [`SpatialAudioSink`] of an entity while any
+/// entity with [`DucksAudio`] is playing, such as lowering the music while a character speaks.
+///
+/// The ducking is applied with the [`SinkEffects::set_gain`](crate::SinkEffects::set_gain) of
+/// the sink, so it combines with the volume of the sink and with [`AudioFade`]s.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioDucking {
+    /// The gain of the sound while it is ducked, between `0.0` and `1.0`.
+    pub level: f32,
+    /// The time it takes to lower the sound to `level` once ducking starts.
+    pub attack: Duration,
+    /// The time it takes to restore the sound once ducking stops.
+    pub release: Duration,
+    /// The shape of the attack and release.
+    pub curve: FadeCurve,
+}
+
+impl Default for AudioDucking {
+    fn default() -> Self {
+        Self {
+            level: 0.3,
+            attack: Duration::from_millis(200),
+            release: Duration::from_millis(800),
+            curve: FadeCurve::Linear,
+        }
+    }
+}
+
+/// Marks an audio entity which ducks the sounds with [`AudioDucking`] while it is playing.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct DucksAudio;
+
+/// The state of the [`AudioDucking`] of an entity, inserted by [`update_audio_ducking`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub(crate) struct DuckingState {
+    /// The progress of the ducking, from `0.0` when the sound isn't ducked to `1.0` when it is
+    /// fully ducked.
+    progress: f32,
+}
+
+fn sink<'a>(
+    sink: Option<&'a AudioSink>,
+    spatial_sink: Option<&'a SpatialAudioSink>,
+) -> Option<&'a dyn AudioSinkPlayback> {
+    sink.map(|sink| sink as &dyn AudioSinkPlayback)
+        .or(spatial_sink.map(|sink| sink as &dyn AudioSinkPlayback))
+}
+
+fn sink_effects<'a>(
+    sink: Option<&'a AudioSink>,
+    spatial_sink: Option<&'a SpatialAudioSink>,
+) -> Option<&'a SinkEffects> {
+    sink.map(AudioSink::effects)
+        .or(spatial_sink.map(SpatialAudioSink::effects))
+}
+
+/// Updates the volume of the sinks with an [`AudioFade`], and ends the fades.
+pub(crate) fn update_audio_fades(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut fades: Query<(
+        Entity,
+        &mut AudioFade,
+        Option<&AudioSink>,
+        Option<&SpatialAudioSink>,
+    )>,
+) {
+    for (entity, mut fade, audio_sink, spatial_sink) in &mut fades {
+        // The sink is created once the audio source is loaded.
+        let Some(sink) = sink(audio_sink, spatial_sink) else {
+            continue;
+        };
+        let start = *fade.start.get_or_insert_with(|| sink.volume());
+        fade.elapsed += time.delta();
+        let progress = fade.progress();
+        sink.set_volume(fade.curve.volume(start, fade.target, progress));
+
+        if progress < 1.0 {
+            continue;
+        }
+        match fade.end {
+            FadeEnd::Keep => {
+                commands.entity(entity).remove::<AudioFade>();
+            }
+            FadeEnd::Pause => {
+                sink.pause();
+                commands.entity(entity).remove::<AudioFade>();
+            }
+            FadeEnd::Despawn => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+/// Updates the gain of the sinks with [`AudioDucking`], depending on whether any entity with
+/// [`DucksAudio`] is playing.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_audio_ducking(
+    mut commands: Commands,
+    time: Res<Time>,
+    ducking_sources: Query<(Option<&AudioSink>, Option<&SpatialAudioSink>), With<DucksAudio>>,
+    mut ducked: Query<(
+        Entity,
+        &AudioDucking,
+        Option<&mut DuckingState>,
+        Option<&AudioSink>,
+        Option<&SpatialAudioSink>,
+    )>,
+    mut removed_ducking: RemovedComponents<AudioDucking>,
+    restored: Query<(Option<&AudioSink>, Option<&SpatialAudioSink>), With<DuckingState>>,
+) {
+    let ducking = ducking_sources
+        .iter()
+        .filter_map(|(audio_sink, spatial_sink)| sink(audio_sink, spatial_sink))
+        .any(|sink| !sink.is_paused() && !sink.empty());
+
+    for (entity, ducking_settings, state, audio_sink, spatial_sink) in &mut ducked {
+        let Some(effects) = sink_effects(audio_sink, spatial_sink) else {
+            continue;
+        };
+        let mut progress = state.as_deref().copied().unwrap_or_default().progress;
+        let (duration, direction) = if ducking {
+            (ducking_settings.attack, 1.0)
+        } else {
+            (ducking_settings.release, -1.0)
+        };
+        progress = if duration.is_zero() {
+            direction.max(0.0)
+        } else {
+            (progress + direction * time.delta_seconds() / duration.as_secs_f32()).clamp(0.0, 1.0)
+        };
+        effects.set_gain(
+            ducking_settings
+                .curve
+                .volume(1.0, ducking_settings.level, progress),
+        );
+
+        match state {
+            Some(mut state) => state.progress = progress,
+            None => {
+                commands.entity(entity).insert(DuckingState { progress });
+            }
+        }
+    }
+
+    // Restore the sinks which aren't ducked anymore.
+    for entity in removed_ducking.read() {
+        if let Ok((audio_sink, spatial_sink)) = restored.get(entity) {
+            if let Some(effects) = sink_effects(audio_sink, spatial_sink) {
+                effects.set_gain(1.0);
+            }
+            commands.entity(entity).remove::<DuckingState>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_time::Time;
+    use rodio::Sink;
+
+    use super::{update_audio_ducking, AudioDucking, DucksAudio, FadeCurve};
+    use crate::{AudioSink, AudioSinkPlayback, SinkEffects};
+
+    #[test]
+    fn fade_curves() {
+        for curve in [
+            FadeCurve::Linear,
+            FadeCurve::EaseIn,
+            FadeCurve::EaseOut,
+            FadeCurve::SmoothStep,
+            FadeCurve::EqualPower,
+        ] {
+            assert!((curve.volume(0.2, 0.8, 0.0) - 0.2).abs() < 1e-6);
+            assert!((curve.volume(0.2, 0.8, 1.0) - 0.8).abs() < 1e-6);
+            assert_eq!(curve.volume(0.2, 0.8, -1.0), curve.volume(0.2, 0.8, 0.0));
+            assert_eq!(curve.volume(0.2, 0.8, 2.0), curve.volume(0.2, 0.8, 1.0));
+        }
+        assert_eq!(FadeCurve::Linear.volume(0.0, 1.0, 0.25), 0.25);
+        assert_eq!(FadeCurve::EaseIn.volume(0.0, 1.0, 0.5), 0.25);
+        assert_eq!(FadeCurve::EaseOut.volume(0.0, 1.0, 0.5), 0.75);
+        assert_eq!(FadeCurve::SmoothStep.volume(0.0, 1.0, 0.5), 0.5);
+    }
+
+    #[test]
+    fn equal_power_crossfades_keep_the_power_constant() {
+        for progress in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            let fade_out = FadeCurve::EqualPower.volume(0.8, 0.0, progress);
+            let fade_in = FadeCurve::EqualPower.volume(0.0, 0.8, progress);
+            assert!((fade_out * fade_out + fade_in * fade_in - 0.64).abs() < 1e-6);
+        }
+        // Fading between two non-zero volumes changes the power linearly
+        let volume = FadeCurve::EqualPower.volume(0.5, 1.0, 0.5);
+        assert!((volume * volume - 0.625).abs() < 1e-6);
+    }
+
+    fn idle_sink() -> AudioSink {
+        AudioSink {
+            sink: Sink::new_idle().0,
+            effects: SinkEffects::default(),
+        }
+    }
+
+    fn advance(world: &mut World, seconds: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(update_audio_ducking);
+    }
+
+    #[test]
+    fn ducking() {
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        let ducked = world
+            .spawn((
+                idle_sink(),
+                AudioDucking {
+                    level: 0.2,
+                    attack: Duration::from_secs(1),
+                    release: Duration::from_secs(2),
+                    curve: FadeCurve::Linear,
+                },
+            ))
+            .id();
+        let voice_sink = idle_sink();
+        voice_sink
+            .sink
+            .append(rodio::source::Zero::<f32>::new(1, 44100));
+        let voice = world.spawn((voice_sink, DucksAudio)).id();
+
+        let gain = |world: &World| world.get::<AudioSink>(ducked).unwrap().effects().gain();
+
+        // The attack lowers the gain while the voice plays
+        advance(&mut world, 0.5);
+        assert!((gain(&world) - 0.6).abs() < 1e-6);
+        advance(&mut world, 1.0);
+        assert!((gain(&world) - 0.2).abs() < 1e-6);
+
+        // The release restores it once the voice is paused
+        world.get::<AudioSink>(voice).unwrap().pause();
+        advance(&mut world, 1.0);
+        assert!((gain(&world) - 0.6).abs() < 1e-6);
+
+        // Removing the ducking restores the gain immediately
+        world.entity_mut(ducked).remove::<AudioDucking>();
+        advance(&mut world, 0.0);
+        assert_eq!(gain(&world), 1.0);
+    }
+}
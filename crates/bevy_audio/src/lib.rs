@@ -30,6 +30,8 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod effects;
+mod fade;
 mod pitch;
 mod sinks;
 
@@ -37,13 +39,16 @@ mod sinks;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioBundle, AudioSink, AudioSinkPlayback, AudioSource, AudioSourceBundle, Decodable,
-        GlobalVolume, Pitch, PitchBundle, PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBundle, AudioDucking, AudioEffects, AudioFade, AudioSink, AudioSinkPlayback,
+        AudioSource, AudioSourceBundle, Decodable, DucksAudio, GlobalVolume, Pitch, PitchBundle,
+        PlaybackSettings, SpatialAudioSink, SpatialListener,
     };
 }
 
 pub use audio::*;
 pub use audio_source::*;
+pub use effects::*;
+pub use fade::*;
 pub use pitch::*;
 
 pub use rodio::cpal::Sample as CpalSample;
@@ -82,6 +87,12 @@ impl Plugin for AudioPlugin {
             .register_type::<DefaultSpatialScale>()
             .register_type::<PlaybackMode>()
             .register_type::<PlaybackSettings>()
+            .register_type::<AudioEffects>()
+            .register_type::<FadeCurve>()
+            .register_type::<FadeEnd>()
+            .register_type::<AudioFade>()
+            .register_type::<AudioDucking>()
+            .register_type::<DucksAudio>()
             .insert_resource(self.global_volume)
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
             .configure_sets(
@@ -92,7 +103,14 @@ impl Plugin for AudioPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaySet),
+                (
+                    update_emitter_positions,
+                    update_listener_positions,
+                    update_audio_effects,
+                    update_audio_fades,
+                    update_audio_ducking,
+                )
+                    .in_set(AudioPlaySet),
             )
             .init_resource::<AudioOutput>();
 
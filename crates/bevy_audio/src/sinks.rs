@@ -3,6 +3,8 @@ use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
 use rodio::{Sink, SpatialSink};
 
+use crate::SinkEffects;
+
 /// Common interactions with an audio sink.
 pub trait AudioSinkPlayback {
     /// Gets the volume of the sound.
@@ -86,6 +88,7 @@ pub trait AudioSinkPlayback {
 #[derive(Component)]
 pub struct AudioSink {
     pub(crate) sink: Sink,
+    pub(crate) effects: SinkEffects,
 }
 
 impl AudioSinkPlayback for AudioSink {
@@ -126,6 +129,15 @@ impl AudioSinkPlayback for AudioSink {
     }
 }
 
+impl AudioSink {
+    /// Gets the effect parameters applied to the sound of this sink.
+    ///
+    /// See also [`AudioEffects`](crate::AudioEffects) to update them from a component.
+    pub fn effects(&self) -> &SinkEffects {
+        &self.effects
+    }
+}
+
 /// Used to control spatial audio during playback.
 ///
 /// Bevy inserts this component onto your entities when it begins playing an audio source
@@ -139,6 +151,7 @@ impl AudioSinkPlayback for AudioSink {
 #[derive(Component)]
 pub struct SpatialAudioSink {
     pub(crate) sink: SpatialSink,
+    pub(crate) effects: SinkEffects,
 }
 
 impl AudioSinkPlayback for SpatialAudioSink {
@@ -180,6 +193,13 @@ impl AudioSinkPlayback for SpatialAudioSink {
 }
 
 impl SpatialAudioSink {
+    /// Gets the effect parameters applied to the sound of this sink.
+    ///
+    /// See also [`AudioEffects`](crate::AudioEffects) to update them from a component.
+    pub fn effects(&self) -> &SinkEffects {
+        &self.effects
+    }
+
     /// Set the two ears position.
     pub fn set_ears_position(&self, left_position: Vec3, right_position: Vec3) {
         self.sink.set_left_ear_position(left_position.to_array());
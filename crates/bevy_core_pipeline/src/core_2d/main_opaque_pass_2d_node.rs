@@ -0,0 +1,71 @@
+use crate::core_2d::Opaque2d;
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    diagnostic::RecordDiagnostics,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_phase::ViewSortedRenderPhases,
+    render_resource::{RenderPassDescriptor, StoreOp},
+    renderer::RenderContext,
+    view::{ViewDepthTexture, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// A [`bevy_render::render_graph::Node`] that runs the [`Opaque2d`] phase, clearing the color
+/// and depth of the view.
+#[derive(Default)]
+pub struct MainOpaquePass2dNode;
+
+impl ViewNode for MainOpaquePass2dNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewDepthTexture,
+    );
+
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (camera, target, depth): bevy_ecs::query::QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let Some(opaque_phases) = world.get_resource::<ViewSortedRenderPhases<Opaque2d>>() else {
+            return Ok(());
+        };
+
+        let view_entity = graph.view_entity();
+        let Some(opaque_phase) = opaque_phases.get(&view_entity) else {
+            return Ok(());
+        };
+
+        #[cfg(feature = "trace")]
+        let _main_opaque_pass_2d = info_span!("main_opaque_pass_2d").entered();
+
+        let diagnostics = render_context.diagnostic_recorder();
+
+        // This runs even without items, to clear the color and depth of the view
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("main_opaque_pass_2d"),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        let pass_span = diagnostics.pass_span(&mut render_pass, "main_opaque_pass_2d");
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        if !opaque_phase.items.is_empty() {
+            opaque_phase.render(&mut render_pass, world, view_entity);
+        }
+
+        pass_span.end(&mut render_pass);
+
+        Ok(())
+    }
+}
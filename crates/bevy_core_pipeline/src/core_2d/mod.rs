@@ -1,4 +1,5 @@
 mod camera_2d;
+mod main_opaque_pass_2d_node;
 mod main_transparent_pass_2d_node;
 
 pub mod graph {
@@ -15,6 +16,7 @@ pub mod graph {
     pub enum Node2d {
         MsaaWriteback,
         StartMainPass,
+        MainOpaquePass,
         MainTransparentPass,
         EndMainPass,
         Bloom,
@@ -29,27 +31,46 @@ pub mod graph {
 use std::ops::Range;
 
 pub use camera_2d::*;
+pub use main_opaque_pass_2d_node::*;
 pub use main_transparent_pass_2d_node::*;
 
 use bevy_app::{App, Plugin};
 use bevy_ecs::{entity::EntityHashSet, prelude::*};
-use bevy_math::FloatOrd;
+use bevy_math::{FloatOrd, Vec3};
 use bevy_render::{
-    camera::Camera,
+    camera::{Camera, ExtractedCamera},
     extract_component::ExtractComponentPlugin,
+    prelude::Msaa,
     render_graph::{EmptyNode, RenderGraphApp, ViewNodeRunner},
     render_phase::{
         sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem,
         PhaseItemExtraIndex, SortedPhaseItem, ViewSortedRenderPhases,
     },
-    render_resource::CachedRenderPipelineId,
+    render_resource::{
+        CachedRenderPipelineId, Extent3d, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureUsages,
+    },
+    renderer::RenderDevice,
+    texture::TextureCache,
+    view::{ExtractedView, ViewDepthTexture},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
+use bevy_utils::HashMap;
 
 use crate::{tonemapping::TonemappingNode, upscaling::UpscalingNode};
 
 use self::graph::{Core2d, Node2d};
 
+/// The format of the depth texture of 2D views. Its stencil is used by sprite masks.
+///
+/// `Depth32FloatStencil8` requires an optional feature that isn't available everywhere, notably
+/// on WebGL2, so the depth has at least 24 bits of precision instead of the 32 bits of
+/// `Depth32Float`. With the orthographic projection of 2D cameras, the depth is linear in `Z`, so
+/// opaque items closer than `(far - near) / 2^24` along the view axis can be depth tested as if
+/// they had the same `Z`, about `0.00006` with the default `far` of `1000.0`. Overlapping opaque
+/// items need `Z` values farther apart than that to be depth tested reliably.
+pub const CORE_2D_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth24PlusStencil8;
+
 pub struct Core2dPlugin;
 
 impl Plugin for Core2dPlugin {
@@ -61,17 +82,27 @@ impl Plugin for Core2dPlugin {
             return;
         };
         render_app
+            .init_resource::<DrawFunctions<Opaque2d>>()
             .init_resource::<DrawFunctions<Transparent2d>>()
+            .init_resource::<ViewSortedRenderPhases<Opaque2d>>()
             .init_resource::<ViewSortedRenderPhases<Transparent2d>>()
             .add_systems(ExtractSchedule, extract_core_2d_camera_phases)
             .add_systems(
                 Render,
-                sort_phase_system::<Transparent2d>.in_set(RenderSet::PhaseSort),
+                (
+                    sort_phase_system::<Opaque2d>.in_set(RenderSet::PhaseSort),
+                    sort_phase_system::<Transparent2d>.in_set(RenderSet::PhaseSort),
+                    prepare_core_2d_depth_textures.in_set(RenderSet::PrepareResources),
+                ),
             );
 
         render_app
             .add_render_sub_graph(Core2d)
             .add_render_graph_node::<EmptyNode>(Core2d, Node2d::StartMainPass)
+            .add_render_graph_node::<ViewNodeRunner<MainOpaquePass2dNode>>(
+                Core2d,
+                Node2d::MainOpaquePass,
+            )
             .add_render_graph_node::<ViewNodeRunner<MainTransparentPass2dNode>>(
                 Core2d,
                 Node2d::MainTransparentPass,
@@ -84,6 +115,7 @@ impl Plugin for Core2dPlugin {
                 Core2d,
                 (
                     Node2d::StartMainPass,
+                    Node2d::MainOpaquePass,
                     Node2d::MainTransparentPass,
                     Node2d::EndMainPass,
                     Node2d::Tonemapping,
@@ -94,6 +126,80 @@ impl Plugin for Core2dPlugin {
     }
 }
 
+/// An opaque or alpha masked 2D [`PhaseItem`], drawn with depth writes before the
+/// [`Transparent2d`] items.
+///
+/// Unlike [`Transparent2d`], the items are drawn front to back, in decreasing order of their
+/// [`sort_key`](Self::sort_key), so that the depth test discards the fragments hidden by the
+/// items already drawn.
+///
+/// Items with the same sort key are drawn in the reverse of the order they were added to the
+/// phase. With the `Greater` depth test of opaque pipelines, the first item drawn at a depth
+/// stays visible, so the item added last covers the others, like in the [`Transparent2d`] phase.
+pub struct Opaque2d {
+    pub sort_key: FloatOrd,
+    pub entity: Entity,
+    pub pipeline: CachedRenderPipelineId,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub extra_index: PhaseItemExtraIndex,
+}
+
+impl PhaseItem for Opaque2d {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn extra_index(&self) -> PhaseItemExtraIndex {
+        self.extra_index
+    }
+
+    #[inline]
+    fn batch_range_and_extra_index_mut(&mut self) -> (&mut Range<u32>, &mut PhaseItemExtraIndex) {
+        (&mut self.batch_range, &mut self.extra_index)
+    }
+}
+
+impl SortedPhaseItem for Opaque2d {
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        self.sort_key
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        // Front to back, the stable sort keeps the items at the same depth in reverse order
+        items.reverse();
+        radsort::sort_by_key(items, |item| -item.sort_key().0);
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for Opaque2d {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
 pub struct Transparent2d {
     pub sort_key: FloatOrd,
     pub entity: Entity,
@@ -157,8 +263,21 @@ impl CachedRenderPipelinePhaseItem for Transparent2d {
     }
 }
 
+/// Returns the depth of `translation` seen from `view`, to use as the [`Transparent2d::sort_key`]
+/// of an item at this position.
+///
+/// This is the position along the backward axis of the view, so items closer to the camera are
+/// drawn last. For cameras looking down the `-Z` axis, even rotated around it, this is the `Z`
+/// coordinate of `translation`. Projecting on the view axis keeps the items in the right order
+/// for tilted cameras too, such as isometric ones.
+#[inline]
+pub fn view_depth_2d(view: &ExtractedView, translation: Vec3) -> f32 {
+    translation.dot(*view.transform.back())
+}
+
 pub fn extract_core_2d_camera_phases(
     mut commands: Commands,
+    mut opaque_2d_phases: ResMut<ViewSortedRenderPhases<Opaque2d>>,
     mut transparent_2d_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     cameras_2d: Extract<Query<(Entity, &Camera), With<Camera2d>>>,
     mut live_entities: Local<EntityHashSet>,
@@ -171,11 +290,60 @@ pub fn extract_core_2d_camera_phases(
         }
 
         commands.get_or_spawn(entity);
+        opaque_2d_phases.insert_or_clear(entity);
         transparent_2d_phases.insert_or_clear(entity);
 
         live_entities.insert(entity);
     }
 
     // Clear out all dead views.
+    opaque_2d_phases.retain(|camera_entity, _| live_entities.contains(camera_entity));
     transparent_2d_phases.retain(|camera_entity, _| live_entities.contains(camera_entity));
 }
+
+pub fn prepare_core_2d_depth_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    msaa: Res<Msaa>,
+    render_device: Res<RenderDevice>,
+    opaque_2d_phases: Res<ViewSortedRenderPhases<Opaque2d>>,
+    transparent_2d_phases: Res<ViewSortedRenderPhases<Transparent2d>>,
+    views_2d: Query<(Entity, &ExtractedCamera), With<Camera2d>>,
+) {
+    let mut textures = HashMap::default();
+    for (entity, camera) in &views_2d {
+        if !opaque_2d_phases.contains_key(&entity) || !transparent_2d_phases.contains_key(&entity) {
+            continue;
+        };
+        let Some(physical_target_size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let cached_texture = textures
+            .entry(camera.target.clone())
+            .or_insert_with(|| {
+                let descriptor = TextureDescriptor {
+                    label: Some("view_depth_texture_2d"),
+                    size: Extent3d {
+                        depth_or_array_layers: 1,
+                        width: physical_target_size.x,
+                        height: physical_target_size.y,
+                    },
+                    mip_level_count: 1,
+                    sample_count: msaa.samples(),
+                    dimension: TextureDimension::D2,
+                    format: CORE_2D_DEPTH_FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                };
+
+                texture_cache.get(&render_device, descriptor)
+            })
+            .clone();
+
+        // Each camera starts from an empty depth buffer, drawn items write values above `0.0`
+        commands
+            .entity(entity)
+            .insert(ViewDepthTexture::new(cached_texture, Some(0.0)).with_stencil());
+    }
+}
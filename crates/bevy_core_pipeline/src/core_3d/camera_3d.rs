@@ -85,7 +85,11 @@ pub enum Camera3dDepthLoadOp {
     /// Clear with a specified value.
     /// Note that 0.0 is the far plane due to bevy's use of reverse-z projections.
     Clear(f32),
-    /// Load from memory.
+    /// Keep the depth written by the cameras rendered before this one on the same target, so that
+    /// this camera's items are occluded by them.
+    ///
+    /// The first camera rendering to a target has no depth to keep: it clears the depth buffer
+    /// with `0.0` instead.
     Load,
 }
 
@@ -24,6 +24,7 @@ pub mod graph {
         MainOpaquePass,
         MainTransmissivePass,
         MainTransparentPass,
+        HalfResolutionTransparentPass,
         EndMainPass,
         Taa,
         MotionBlur,
@@ -607,6 +608,8 @@ pub fn prepare_core_3d_depth_textures(
     views_3d: Query<(Entity, &ExtractedCamera, Option<&DepthPrepass>, &Camera3d)>,
 ) {
     let mut render_target_usage = HashMap::default();
+    // The first camera on each target, which has no depth to load
+    let mut first_camera_for_target = HashMap::default();
     for (view, camera, depth_prepass, camera_3d) in &views_3d {
         if !opaque_3d_phases.contains_key(&view)
             || !alpha_mask_3d_phases.contains_key(&view)
@@ -626,6 +629,10 @@ pub fn prepare_core_3d_depth_textures(
             .entry(camera.target.clone())
             .and_modify(|u| *u |= usage)
             .or_insert_with(|| usage);
+        let first_camera = first_camera_for_target
+            .entry(camera.target.clone())
+            .or_insert(camera.sorted_camera_index_for_target);
+        *first_camera = (*first_camera).min(camera.sorted_camera_index_for_target);
     }
 
     let mut textures = HashMap::default();
@@ -667,6 +674,13 @@ pub fn prepare_core_3d_depth_textures(
             cached_texture,
             match camera_3d.depth_load_op {
                 Camera3dDepthLoadOp::Clear(v) => Some(v),
+                // The texture holds the depth of the previous frame for the first camera
+                Camera3dDepthLoadOp::Load
+                    if first_camera_for_target.get(&camera.target)
+                        == Some(&camera.sorted_camera_index_for_target) =>
+                {
+                    Some(0.0)
+                }
                 Camera3dDepthLoadOp::Load => None,
             },
         ));
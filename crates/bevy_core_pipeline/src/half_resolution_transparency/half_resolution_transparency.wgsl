@@ -0,0 +1,126 @@
+// Supports rendering selected transparent objects at half resolution.
+//
+// This shader contains two fullscreen passes:
+//
+// 1. `downsample_depth` reduces the main view's depth buffer to a half
+//    resolution depth buffer, keeping the closest depth of each 2×2 block, so
+//    that half resolution transparent objects are conservatively occluded by
+//    opaque geometry.
+//
+// 2. `upsample` composites the half resolution transparent color buffer over
+//    the main view target. When all four half resolution texels surrounding a
+//    pixel lie at a depth similar to the full resolution depth, the color is
+//    bilinearly filtered. Otherwise the pixel is near a depth discontinuity, and
+//    the texel whose depth best matches the full resolution depth is used
+//    instead (*nearest-depth upsampling*), which avoids halos around the edges
+//    of opaque objects.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::view::View
+
+// Parameters that control the upsample. See
+// `bevy_core_pipeline::half_resolution_transparency::HalfResolutionTransparencyUniform`
+// for information on what these parameters mean.
+struct HalfResolutionTransparencyParams {
+    /// The maximum relative difference in view-space depth between a pixel and
+    /// its surrounding half resolution texels for which bilinear filtering is
+    /// used.
+    depth_threshold: f32,
+
+    /// Padding.
+    pad_a: u32,
+    /// Padding.
+    pad_b: u32,
+    /// Padding.
+    pad_c: u32,
+}
+
+@group(0) @binding(0) var<uniform> view: View;
+
+// The depth texture for the main view.
+#ifdef MULTISAMPLED
+@group(0) @binding(1) var depth_texture: texture_depth_multisampled_2d;
+#else   // MULTISAMPLED
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+#endif  // MULTISAMPLED
+
+#ifdef UPSAMPLE
+// The half resolution depth texture produced by `downsample_depth`.
+@group(0) @binding(2) var half_resolution_depth_texture: texture_depth_2d;
+// The half resolution color texture that transparent objects were rendered to.
+@group(0) @binding(3) var half_resolution_color_texture: texture_2d<f32>;
+@group(0) @binding(4) var half_resolution_color_sampler: sampler;
+@group(0) @binding(5) var<uniform> params: HalfResolutionTransparencyParams;
+#endif  // UPSAMPLE
+
+// Loads a depth value from the full resolution depth buffer.
+fn load_full_resolution_depth(coords: vec2<i32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    return textureLoad(depth_texture, clamp(coords, vec2(0), size - 1), 0);
+}
+
+// Converts a depth value from normalized device coordinates to linear
+// view-space depth, for both perspective and orthographic projections.
+//
+// The depth is clamped slightly in front of the far plane so that the
+// background, which is infinitely far away with the default perspective
+// projection, still has a finite depth.
+fn linear_depth(ndc_depth: f32) -> f32 {
+    let view_position = view.inverse_projection * vec4(0.0, 0.0, max(ndc_depth, 1.0e-7), 1.0);
+    return -view_position.z / view_position.w;
+}
+
+// Writes the closest of the four full resolution depth values covered by each
+// half resolution texel. Bevy uses reverse Z, so the closest depth is the
+// largest one.
+@fragment
+fn downsample_depth(in: FullscreenVertexOutput) -> @builtin(frag_depth) f32 {
+    let coords = vec2<i32>(floor(in.position.xy)) * 2;
+    let depth = max(
+        max(load_full_resolution_depth(coords), load_full_resolution_depth(coords + vec2(1, 0))),
+        max(load_full_resolution_depth(coords + vec2(0, 1)), load_full_resolution_depth(coords + vec2(1, 1)))
+    );
+    return depth;
+}
+
+#ifdef UPSAMPLE
+
+@fragment
+fn upsample(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let full_resolution_depth =
+        linear_depth(load_full_resolution_depth(vec2<i32>(floor(in.position.xy))));
+
+    // Find the four half resolution texels that bilinear filtering would blend.
+    let half_size = vec2<i32>(textureDimensions(half_resolution_depth_texture));
+    let base = vec2<i32>(floor(in.position.xy * 0.5 - 0.5));
+    var offsets = array<vec2<i32>, 4>(vec2(0, 0), vec2(1, 0), vec2(0, 1), vec2(1, 1));
+
+    var nearest_coords = clamp(base, vec2(0), half_size - 1);
+    var nearest_distance = 1.0e30;
+    var max_distance = 0.0;
+    for (var i = 0u; i < 4u; i += 1u) {
+        let coords = clamp(base + offsets[i], vec2(0), half_size - 1);
+        let half_resolution_depth =
+            linear_depth(textureLoad(half_resolution_depth_texture, coords, 0));
+        let distance = abs(half_resolution_depth - full_resolution_depth);
+        max_distance = max(max_distance, distance);
+        if (distance < nearest_distance) {
+            nearest_distance = distance;
+            nearest_coords = coords;
+        }
+    }
+
+    // The color texture contains premultiplied alpha, which is composited over
+    // the view target by the blend state.
+    if (max_distance <= params.depth_threshold * full_resolution_depth) {
+        return textureSampleLevel(
+            half_resolution_color_texture,
+            half_resolution_color_sampler,
+            in.position.xy * 0.5 / vec2<f32>(half_size),
+            0.0
+        );
+    }
+    return textureLoad(half_resolution_color_texture, nearest_coords, 0);
+}
+
+#endif  // UPSAMPLE
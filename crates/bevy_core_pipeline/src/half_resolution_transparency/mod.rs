@@ -0,0 +1,768 @@
+//! Half resolution rendering of selected transparent objects.
+//!
+//! Effects such as particles and fog volumes tend to cover large portions of
+//! the screen with many overlapping transparent layers, which makes them
+//! fill-rate heavy, especially on low-end GPUs. Rendering them at half
+//! resolution divides the number of shaded fragments by four, at the cost of
+//! some sharpness.
+//!
+//! Adding [`HalfResolutionTransparencySettings`] to a 3D camera makes Bevy
+//! render every transparent object marked with [`RenderAtHalfResolution`] into
+//! an offscreen half resolution target instead of the main transparent pass.
+//! After the main transparent pass, the half resolution target is composited
+//! over the view target with a depth-aware upsample, which preserves the edges
+//! of opaque objects in front of the half resolution content.
+//!
+//! Half resolution objects are sorted among themselves, but are always drawn
+//! on top of the other transparent objects of the view, so this is best suited
+//! to content that doesn't need to interleave with other transparent objects.
+//! Only additive and alpha blending are supported; objects rendered at half
+//! resolution with other blend modes won't composite correctly.
+
+use std::ops::Range;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_color::LinearRgba;
+use bevy_ecs::{
+    entity::EntityHashSet,
+    prelude::*,
+    query::{QueryItem, ROQueryItem},
+    system::lifetimeless::Read,
+};
+use bevy_math::{FloatOrd, UVec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera, Viewport},
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
+    render_graph::{
+        NodeRunError, RenderGraphApp as _, RenderGraphContext, ViewNode, ViewNodeRunner,
+    },
+    render_phase::{
+        sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem,
+        PhaseItemExtraIndex, SortedPhaseItem, ViewSortedRenderPhases,
+    },
+    render_resource::{
+        binding_types::{
+            sampler, texture_2d, texture_depth_2d, texture_depth_2d_multisampled, uniform_buffer,
+        },
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{BevyDefault, CachedTexture, TextureCache},
+    view::{
+        prepare_view_targets, ExtractedView, Msaa, ViewDepthTexture, ViewTarget, ViewUniform,
+        ViewUniformOffset, ViewUniforms,
+    },
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::{info_once, prelude::default};
+
+use crate::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        Camera3d, CORE_3D_DEPTH_FORMAT, DEPTH_TEXTURE_SAMPLING_SUPPORTED,
+    },
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+
+const HALF_RESOLUTION_TRANSPARENCY_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(10470864047936962412);
+
+/// A plugin that adds support for rendering transparent objects at half
+/// resolution.
+pub struct HalfResolutionTransparencyPlugin;
+
+/// Renders the transparent objects marked with [`RenderAtHalfResolution`] at
+/// half resolution for this camera.
+///
+/// Objects marked with [`RenderAtHalfResolution`] are rendered normally by
+/// cameras without this component.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct HalfResolutionTransparencySettings {
+    /// The maximum relative difference in view-space depth between a pixel and
+    /// the half resolution texels surrounding it for which the upsample uses
+    /// bilinear filtering.
+    ///
+    /// Beyond this threshold the pixel is considered to be on a depth edge,
+    /// and the half resolution texel with the closest depth is used instead.
+    /// Lower values preserve edges better but make the result more aliased.
+    ///
+    /// The default is 0.1, that is 10% of the depth of the pixel.
+    pub depth_threshold: f32,
+}
+
+impl Default for HalfResolutionTransparencySettings {
+    fn default() -> Self {
+        Self {
+            depth_threshold: 0.1,
+        }
+    }
+}
+
+impl ExtractComponent for HalfResolutionTransparencySettings {
+    type QueryData = Read<Self>;
+    type QueryFilter = With<Camera3d>;
+    type Out = (Self, HalfResolutionTransparencyUniform);
+
+    fn extract_component(settings: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        if !DEPTH_TEXTURE_SAMPLING_SUPPORTED {
+            info_once!(
+                "Disabling half resolution transparency on this platform because depth textures aren't supported correctly"
+            );
+            return None;
+        }
+
+        Some((
+            *settings,
+            HalfResolutionTransparencyUniform {
+                depth_threshold: settings.depth_threshold,
+                pad_a: 0,
+                pad_b: 0,
+                pad_c: 0,
+            },
+        ))
+    }
+}
+
+/// Marks a transparent object to be rendered at half resolution by cameras
+/// with [`HalfResolutionTransparencySettings`].
+///
+/// This has no effect on opaque and alpha masked objects.
+#[derive(Component, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct RenderAtHalfResolution;
+
+/// Data about the half resolution upsample that's uploaded to the GPU.
+#[derive(Clone, Copy, Component, ShaderType)]
+pub struct HalfResolutionTransparencyUniform {
+    /// See [`HalfResolutionTransparencySettings::depth_threshold`].
+    depth_threshold: f32,
+
+    /// Padding.
+    pad_a: u32,
+    /// Padding.
+    pad_b: u32,
+    /// Padding.
+    pad_c: u32,
+}
+
+impl Plugin for HalfResolutionTransparencyPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HALF_RESOLUTION_TRANSPARENCY_SHADER_HANDLE,
+            "half_resolution_transparency.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<HalfResolutionTransparencySettings>()
+            .register_type::<RenderAtHalfResolution>()
+            .add_plugins((
+                ExtractComponentPlugin::<HalfResolutionTransparencySettings>::default(),
+                UniformComponentPlugin::<HalfResolutionTransparencyUniform>::default(),
+            ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<DrawFunctions<HalfResolutionTransparent3d>>()
+            .init_resource::<ViewSortedRenderPhases<HalfResolutionTransparent3d>>()
+            .init_resource::<SpecializedRenderPipelines<HalfResolutionTransparencyPipeline>>()
+            .add_systems(
+                ExtractSchedule,
+                extract_half_resolution_transparent_camera_phases,
+            )
+            .add_systems(
+                Render,
+                (
+                    (
+                        configure_half_resolution_transparency_view_targets,
+                        prepare_half_resolution_transparency_textures,
+                    )
+                        .after(prepare_view_targets)
+                        .in_set(RenderSet::ManageViews),
+                    prepare_half_resolution_transparency_pipelines.in_set(RenderSet::Prepare),
+                    sort_phase_system::<HalfResolutionTransparent3d>.in_set(RenderSet::PhaseSort),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<HalfResolutionTransparencyNode>>(
+                Core3d,
+                Node3d::HalfResolutionTransparentPass,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::MainTransparentPass,
+                    Node3d::HalfResolutionTransparentPass,
+                    Node3d::EndMainPass,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<HalfResolutionTransparencyPipeline>();
+    }
+}
+
+/// Transparent 3D [`SortedPhaseItem`]s rendered at half resolution.
+///
+/// Items are drawn into a half resolution target with a single sample, so
+/// pipelines queued into this phase must be specialized accordingly.
+pub struct HalfResolutionTransparent3d {
+    pub distance: f32,
+    pub pipeline: CachedRenderPipelineId,
+    pub entity: Entity,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub extra_index: PhaseItemExtraIndex,
+}
+
+impl PhaseItem for HalfResolutionTransparent3d {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn extra_index(&self) -> PhaseItemExtraIndex {
+        self.extra_index
+    }
+
+    #[inline]
+    fn batch_range_and_extra_index_mut(&mut self) -> (&mut Range<u32>, &mut PhaseItemExtraIndex) {
+        (&mut self.batch_range, &mut self.extra_index)
+    }
+}
+
+impl SortedPhaseItem for HalfResolutionTransparent3d {
+    // NOTE: Values increase towards the camera. Back-to-front ordering for transparent means we need an ascending sort.
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        FloatOrd(self.distance)
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        radsort::sort_by_key(items, |item| item.distance);
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for HalfResolutionTransparent3d {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+/// The offscreen targets that half resolution transparent objects are
+/// rendered to.
+#[derive(Component)]
+pub struct HalfResolutionTransparencyTextures {
+    /// The premultiplied color of the half resolution transparent objects.
+    pub color: CachedTexture,
+    /// The main view depth, downsampled to half resolution.
+    pub depth: CachedTexture,
+}
+
+/// The pipelines used to render half resolution transparency for a view.
+#[derive(Component)]
+pub struct HalfResolutionTransparencyPipelines {
+    /// Downsamples the main view depth into
+    /// [`HalfResolutionTransparencyTextures::depth`].
+    pub downsample_depth: CachedRenderPipelineId,
+    /// Composites [`HalfResolutionTransparencyTextures::color`] over the view
+    /// target.
+    pub upsample: CachedRenderPipelineId,
+}
+
+/// The bind group layouts and sampler shared among all invocations of the half
+/// resolution transparency shader.
+#[derive(Resource)]
+pub struct HalfResolutionTransparencyPipeline {
+    downsample_depth_layout: BindGroupLayout,
+    downsample_depth_multisampled_layout: BindGroupLayout,
+    upsample_layout: BindGroupLayout,
+    upsample_multisampled_layout: BindGroupLayout,
+    color_sampler: Sampler,
+}
+
+impl HalfResolutionTransparencyPipeline {
+    fn downsample_depth_layout(&self, multisampled: bool) -> &BindGroupLayout {
+        if multisampled {
+            &self.downsample_depth_multisampled_layout
+        } else {
+            &self.downsample_depth_layout
+        }
+    }
+
+    fn upsample_layout(&self, multisampled: bool) -> &BindGroupLayout {
+        if multisampled {
+            &self.upsample_multisampled_layout
+        } else {
+            &self.upsample_layout
+        }
+    }
+}
+
+impl FromWorld for HalfResolutionTransparencyPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let create_layouts = |label, depth_texture: BindGroupLayoutEntryBuilder| {
+            let downsample_depth_layout = render_device.create_bind_group_layout(
+                Some(&*format!(
+                    "half resolution transparency downsample depth {label}bind group layout"
+                )),
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (uniform_buffer::<ViewUniform>(true), depth_texture),
+                ),
+            );
+            let upsample_layout = render_device.create_bind_group_layout(
+                Some(&*format!(
+                    "half resolution transparency upsample {label}bind group layout"
+                )),
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (
+                        uniform_buffer::<ViewUniform>(true),
+                        depth_texture,
+                        texture_depth_2d(),
+                        texture_2d(TextureSampleType::Float { filterable: true }),
+                        sampler(SamplerBindingType::Filtering),
+                        uniform_buffer::<HalfResolutionTransparencyUniform>(true),
+                    ),
+                ),
+            );
+            (downsample_depth_layout, upsample_layout)
+        };
+
+        let (downsample_depth_layout, upsample_layout) = create_layouts("", texture_depth_2d());
+        let (downsample_depth_multisampled_layout, upsample_multisampled_layout) =
+            create_layouts("multisampled ", texture_depth_2d_multisampled());
+
+        HalfResolutionTransparencyPipeline {
+            downsample_depth_layout,
+            downsample_depth_multisampled_layout,
+            upsample_layout,
+            upsample_multisampled_layout,
+            color_sampler: render_device.create_sampler(&SamplerDescriptor {
+                label: Some("half resolution transparency sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            }),
+        }
+    }
+}
+
+/// A key that uniquely identifies half resolution transparency pipelines.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HalfResolutionTransparencyPipelineKey {
+    /// Whether we're downsampling the depth or compositing the color.
+    pass: HalfResolutionTransparencyPass,
+    /// Whether we're using HDR.
+    hdr: bool,
+    /// The number of samples of the main view target.
+    samples: u32,
+}
+
+/// Identifies a specific half resolution transparency fullscreen pass.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum HalfResolutionTransparencyPass {
+    /// Downsamples the main view depth to half resolution.
+    DownsampleDepth,
+    /// Composites the half resolution color over the view target.
+    Upsample,
+}
+
+impl SpecializedRenderPipeline for HalfResolutionTransparencyPipeline {
+    type Key = HalfResolutionTransparencyPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let multisampled = key.samples > 1;
+        let mut shader_defs = vec![];
+        if multisampled {
+            shader_defs.push("MULTISAMPLED".into());
+        }
+
+        match key.pass {
+            HalfResolutionTransparencyPass::DownsampleDepth => RenderPipelineDescriptor {
+                label: Some("half resolution transparency downsample depth pipeline".into()),
+                layout: vec![self.downsample_depth_layout(multisampled).clone()],
+                push_constant_ranges: vec![],
+                vertex: fullscreen_shader_vertex_state(),
+                primitive: default(),
+                depth_stencil: Some(DepthStencilState {
+                    format: CORE_3D_DEPTH_FORMAT,
+                    depth_write_enabled: true,
+                    depth_compare: CompareFunction::Always,
+                    stencil: default(),
+                    bias: default(),
+                }),
+                multisample: default(),
+                fragment: Some(FragmentState {
+                    shader: HALF_RESOLUTION_TRANSPARENCY_SHADER_HANDLE,
+                    shader_defs,
+                    entry_point: "downsample_depth".into(),
+                    targets: vec![],
+                }),
+            },
+            HalfResolutionTransparencyPass::Upsample => {
+                shader_defs.push("UPSAMPLE".into());
+                RenderPipelineDescriptor {
+                    label: Some("half resolution transparency upsample pipeline".into()),
+                    layout: vec![self.upsample_layout(multisampled).clone()],
+                    push_constant_ranges: vec![],
+                    vertex: fullscreen_shader_vertex_state(),
+                    primitive: default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState {
+                        count: key.samples,
+                        ..default()
+                    },
+                    fragment: Some(FragmentState {
+                        shader: HALF_RESOLUTION_TRANSPARENCY_SHADER_HANDLE,
+                        shader_defs,
+                        entry_point: "upsample".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: if key.hdr {
+                                ViewTarget::TEXTURE_FORMAT_HDR
+                            } else {
+                                TextureFormat::bevy_default()
+                            },
+                            blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Extracts the [`HalfResolutionTransparent3d`] phases of the cameras with
+/// [`HalfResolutionTransparencySettings`].
+pub fn extract_half_resolution_transparent_camera_phases(
+    mut half_resolution_transparent_3d_phases: ResMut<
+        ViewSortedRenderPhases<HalfResolutionTransparent3d>,
+    >,
+    cameras_3d: Extract<
+        Query<(Entity, &Camera), (With<Camera3d>, With<HalfResolutionTransparencySettings>)>,
+    >,
+    mut live_entities: Local<EntityHashSet>,
+) {
+    live_entities.clear();
+
+    if DEPTH_TEXTURE_SAMPLING_SUPPORTED {
+        for (entity, camera) in &cameras_3d {
+            if !camera.is_active {
+                continue;
+            }
+
+            half_resolution_transparent_3d_phases.insert_or_clear(entity);
+            live_entities.insert(entity);
+        }
+    }
+
+    half_resolution_transparent_3d_phases.retain(|entity, _| live_entities.contains(entity));
+}
+
+/// Configures depth textures so that the upsample shader can read from them.
+pub fn configure_half_resolution_transparency_view_targets(
+    mut view_targets: Query<&mut Camera3d, With<HalfResolutionTransparencySettings>>,
+) {
+    for mut camera_3d in view_targets.iter_mut() {
+        let mut depth_texture_usages = TextureUsages::from(camera_3d.depth_texture_usages);
+        depth_texture_usages |= TextureUsages::TEXTURE_BINDING;
+        camera_3d.depth_texture_usages = depth_texture_usages.into();
+    }
+}
+
+/// Creates the half resolution color and depth targets of each view.
+pub fn prepare_half_resolution_transparency_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(Entity, &ViewTarget), With<HalfResolutionTransparencySettings>>,
+) {
+    for (entity, view_target) in &views {
+        let full_size = view_target.main_texture().size();
+        let size = Extent3d {
+            width: full_size.width.div_ceil(2),
+            height: full_size.height.div_ceil(2),
+            depth_or_array_layers: 1,
+        };
+
+        let color = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("half resolution transparency color texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_target.main_texture_format(),
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        let depth = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("half resolution transparency depth texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: CORE_3D_DEPTH_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(HalfResolutionTransparencyTextures { color, depth });
+    }
+}
+
+/// Specializes the half resolution transparency pipelines of each view.
+pub fn prepare_half_resolution_transparency_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<HalfResolutionTransparencyPipeline>>,
+    half_resolution_transparency_pipeline: Res<HalfResolutionTransparencyPipeline>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ExtractedView), With<HalfResolutionTransparencySettings>>,
+) {
+    for (entity, view) in &views {
+        let mut specialize = |pass| {
+            pipelines.specialize(
+                &pipeline_cache,
+                &half_resolution_transparency_pipeline,
+                HalfResolutionTransparencyPipelineKey {
+                    pass,
+                    hdr: view.hdr,
+                    samples: msaa.samples(),
+                },
+            )
+        };
+
+        let downsample_depth = specialize(HalfResolutionTransparencyPass::DownsampleDepth);
+        let upsample = specialize(HalfResolutionTransparencyPass::Upsample);
+
+        commands
+            .entity(entity)
+            .insert(HalfResolutionTransparencyPipelines {
+                downsample_depth,
+                upsample,
+            });
+    }
+}
+
+/// Returns the camera viewport scaled down to half resolution.
+fn half_resolution_viewport(viewport: &Viewport) -> Viewport {
+    Viewport {
+        physical_position: viewport.physical_position / 2,
+        physical_size: (viewport.physical_size / 2).max(UVec2::ONE),
+        depth: viewport.depth.clone(),
+    }
+}
+
+/// A [`bevy_render::render_graph::Node`] that renders the
+/// [`HalfResolutionTransparent3d`] phase at half resolution and composites it
+/// over the view target.
+#[derive(Default)]
+pub struct HalfResolutionTransparencyNode;
+
+impl ViewNode for HalfResolutionTransparencyNode {
+    type ViewQuery = (
+        Read<ExtractedCamera>,
+        Read<ViewTarget>,
+        Read<ViewDepthTexture>,
+        Read<ViewUniformOffset>,
+        Read<HalfResolutionTransparencyTextures>,
+        Read<HalfResolutionTransparencyPipelines>,
+        Read<DynamicUniformIndex<HalfResolutionTransparencyUniform>>,
+    );
+
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (
+            camera,
+            target,
+            depth,
+            view_uniform_offset,
+            textures,
+            view_pipelines,
+            settings_uniform_index,
+        ): ROQueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.view_entity();
+
+        let Some(phase) = world
+            .resource::<ViewSortedRenderPhases<HalfResolutionTransparent3d>>()
+            .get(&view_entity)
+        else {
+            return Ok(());
+        };
+
+        // Skip the downsample and upsample entirely if there's nothing to
+        // render at half resolution.
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let half_resolution_transparency_pipeline =
+            world.resource::<HalfResolutionTransparencyPipeline>();
+        let (
+            Some(downsample_depth_pipeline),
+            Some(upsample_pipeline),
+            Some(view_uniforms_binding),
+            Some(settings_binding),
+        ) = (
+            pipeline_cache.get_render_pipeline(view_pipelines.downsample_depth),
+            pipeline_cache.get_render_pipeline(view_pipelines.upsample),
+            world.resource::<ViewUniforms>().uniforms.binding(),
+            world
+                .resource::<ComponentUniforms<HalfResolutionTransparencyUniform>>()
+                .uniforms()
+                .binding(),
+        )
+        else {
+            return Ok(());
+        };
+
+        let multisampled = depth.texture.sample_count() > 1;
+        let half_resolution_viewport = camera.viewport.as_ref().map(half_resolution_viewport);
+
+        // Downsample the depth buffer, so that half resolution objects are
+        // occluded by opaque ones.
+        {
+            let bind_group = render_context.render_device().create_bind_group(
+                Some("half resolution transparency downsample depth bind group"),
+                half_resolution_transparency_pipeline.downsample_depth_layout(multisampled),
+                &BindGroupEntries::sequential((view_uniforms_binding.clone(), depth.view())),
+            );
+
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("half_resolution_downsample_depth_pass"),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &textures.depth.default_view,
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(0.0),
+                        store: StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_render_pipeline(downsample_depth_pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[view_uniform_offset.offset]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        // Render the half resolution transparent objects, sorted back-to-front.
+        {
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("half_resolution_transparent_pass_3d"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &textures.color.default_view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(LinearRgba::NONE.into()),
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &textures.depth.default_view,
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Load,
+                        store: StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            if let Some(viewport) = half_resolution_viewport.as_ref() {
+                render_pass.set_camera_viewport(viewport);
+            }
+
+            phase.render(&mut render_pass, world, view_entity);
+        }
+
+        // Composite the result over the view target.
+        let bind_group = render_context.render_device().create_bind_group(
+            Some("half resolution transparency upsample bind group"),
+            half_resolution_transparency_pipeline.upsample_layout(multisampled),
+            &BindGroupEntries::sequential((
+                view_uniforms_binding,
+                depth.view(),
+                &textures.depth.default_view,
+                &textures.color.default_view,
+                &half_resolution_transparency_pipeline.color_sampler,
+                settings_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("half_resolution_upsample_pass"),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        render_pass.set_render_pipeline(upsample_pipeline);
+        render_pass.set_bind_group(
+            0,
+            &bind_group,
+            &[view_uniform_offset.offset, settings_uniform_index.index()],
+        );
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -17,8 +17,10 @@ pub mod deferred;
 pub mod dof;
 pub mod fullscreen_vertex_shader;
 pub mod fxaa;
+pub mod half_resolution_transparency;
 pub mod motion_blur;
 pub mod msaa_writeback;
+pub mod post_process_material;
 pub mod prepass;
 mod skybox;
 mod taa;
@@ -57,6 +59,7 @@ use crate::{
     dof::DepthOfFieldPlugin,
     fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE,
     fxaa::FxaaPlugin,
+    half_resolution_transparency::HalfResolutionTransparencyPlugin,
     motion_blur::MotionBlurPlugin,
     msaa_writeback::MsaaWritebackPlugin,
     prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
@@ -96,6 +99,7 @@ impl Plugin for CorePipelinePlugin {
                 CASPlugin,
                 MotionBlurPlugin,
                 DepthOfFieldPlugin,
+                HalfResolutionTransparencyPlugin,
             ));
     }
 }
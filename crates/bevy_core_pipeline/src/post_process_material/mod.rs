@@ -0,0 +1,297 @@
+//! Full-screen post-processing effects, defined by a fragment shader and a uniform struct.
+//!
+//! Implement [`PostProcessMaterial`] on a camera component, and add a
+//! [`PostProcessMaterialPlugin`] for it: every camera with this component then runs the
+//! fragment shader over its whole view target, at the point of the render graph given by
+//! [`PostProcessMaterial::placement`]. Effects at the same placement run by
+//! [`PostProcessMaterial::order`].
+//!
+//! The fragment shader reads the following bindings:
+//!
+//! ```wgsl
+//! #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+//!
+//! @group(0) @binding(0) var screen_texture: texture_2d<f32>;
+//! @group(0) @binding(1) var texture_sampler: sampler;
+//! @group(0) @binding(2) var<uniform> settings: MySettings;
+//!
+//! @fragment
+//! fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+//!     return textureSample(screen_texture, texture_sampler, in.uv);
+//! }
+//! ```
+
+use crate::{
+    core_2d::graph::{Core2d, Node2d},
+    core_3d::graph::{Core3d, Node3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+use bevy_app::prelude::*;
+use bevy_asset::{AssetServer, Handle};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin},
+    render_graph::{RenderGraphApp, RenderLabel, ViewNodeRunner},
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        encase::internal::WriteInto,
+        *,
+    },
+    renderer::RenderDevice,
+    texture::BevyDefault,
+    view::{ExtractedView, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::default;
+use std::{any::TypeId, marker::PhantomData};
+
+mod node;
+
+pub use node::PostProcessMaterialNode;
+
+/// A full-screen effect applied to the view of every camera with this component.
+///
+/// The component itself is the uniform struct of the effect: it is extracted every frame and
+/// bound to the fragment shader, so its fields can be animated from the main world. See the
+/// [module-level documentation](self) for the bindings available to the shader.
+///
+/// Add a [`PostProcessMaterialPlugin`] for each implementor to enable it.
+pub trait PostProcessMaterial:
+    Component + ExtractComponent<Out = Self> + ShaderType + WriteInto + Clone
+{
+    /// Returns the fragment shader of the effect, with a `fragment` entry point.
+    ///
+    /// There is no default post-processing shader, so [`ShaderRef::Default`] is not supported.
+    fn fragment_shader() -> ShaderRef;
+
+    /// Returns where the effect runs in the render graph of each camera. Defaults to
+    /// [`PostProcessPlacement::AfterTonemapping`].
+    fn placement() -> PostProcessPlacement {
+        PostProcessPlacement::AfterTonemapping
+    }
+
+    /// Returns the order of the effect among the effects with the same placement: effects with
+    /// a lower order run first, and effects with the same order run in the order their plugins
+    /// were added. Defaults to 0.
+    fn order() -> i32 {
+        0
+    }
+}
+
+/// Where a [`PostProcessMaterial`] runs in the render graph of a camera.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PostProcessPlacement {
+    /// After the main passes and before tonemapping. On HDR cameras, the shader reads and
+    /// writes linear HDR colors.
+    BeforeTonemapping,
+    /// After tonemapping, with the colors that will be displayed.
+    #[default]
+    AfterTonemapping,
+}
+
+/// The render graph label of the node running the [`PostProcessMaterial`] `M`, in both the
+/// [`Core2d`] and the [`Core3d`] graphs.
+///
+/// Use it to order other nodes relative to the effect.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PostProcessMaterialLabel(TypeId);
+
+impl PostProcessMaterialLabel {
+    /// Returns the label of the node running `M`.
+    pub fn of<M: PostProcessMaterial>() -> Self {
+        Self(TypeId::of::<M>())
+    }
+}
+
+/// The [`PostProcessMaterial`]s added to the render graph, in the order their plugins were added.
+#[derive(Resource, Default)]
+struct PostProcessMaterialOrders(Vec<(PostProcessPlacement, i32, PostProcessMaterialLabel)>);
+
+/// Adds support for the [`PostProcessMaterial`] `M`.
+pub struct PostProcessMaterialPlugin<M: PostProcessMaterial>(PhantomData<M>);
+
+impl<M: PostProcessMaterial> Default for PostProcessMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: PostProcessMaterial> Plugin for PostProcessMaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<M>::default(),
+            UniformComponentPlugin::<M>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        let label = PostProcessMaterialLabel::of::<M>();
+        let placement = M::placement();
+        let order = M::order();
+        let mut orders = render_app
+            .world_mut()
+            .get_resource_or_insert_with(PostProcessMaterialOrders::default);
+        let previous_effects: Vec<_> = orders
+            .0
+            .iter()
+            .filter(|(previous_placement, ..)| *previous_placement == placement)
+            .map(|(_, previous_order, previous_label)| (*previous_order, previous_label.clone()))
+            .collect();
+        orders.0.push((placement, order, label.clone()));
+
+        let (node_3d_edges, node_2d_edges) = match placement {
+            PostProcessPlacement::BeforeTonemapping => (
+                (Node3d::EndMainPass, Node3d::Tonemapping),
+                (Node2d::EndMainPass, Node2d::Tonemapping),
+            ),
+            PostProcessPlacement::AfterTonemapping => (
+                (Node3d::Tonemapping, Node3d::EndMainPassPostProcessing),
+                (Node2d::Tonemapping, Node2d::EndMainPassPostProcessing),
+            ),
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<PostProcessMaterialPipeline<M>>>()
+            .add_systems(
+                Render,
+                prepare_post_process_material_pipelines::<M>.in_set(RenderSet::Prepare),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessMaterialNode<M>>>(
+                Core3d,
+                label.clone(),
+            )
+            .add_render_graph_edges(Core3d, (node_3d_edges.0, label.clone(), node_3d_edges.1))
+            .add_render_graph_node::<ViewNodeRunner<PostProcessMaterialNode<M>>>(
+                Core2d,
+                label.clone(),
+            )
+            .add_render_graph_edges(Core2d, (node_2d_edges.0, label.clone(), node_2d_edges.1));
+
+        // Order the effect relative to each effect added before it at the same placement
+        for (previous_order, previous_label) in previous_effects {
+            let (first, second) = if previous_order <= order {
+                (previous_label, label.clone())
+            } else {
+                (label.clone(), previous_label)
+            };
+            render_app
+                .add_render_graph_edge(Core3d, first.clone(), second.clone())
+                .add_render_graph_edge(Core2d, first, second);
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<PostProcessMaterialPipeline<M>>();
+    }
+}
+
+/// The render pipeline of the [`PostProcessMaterial`] `M`.
+#[derive(Resource)]
+pub struct PostProcessMaterialPipeline<M: PostProcessMaterial> {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    shader: Handle<Shader>,
+    marker: PhantomData<M>,
+}
+
+impl<M: PostProcessMaterial> FromWorld for PostProcessMaterialPipeline<M> {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "post_process_material_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<M>(true),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        let shader = match M::fragment_shader() {
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => render_world.resource::<AssetServer>().load(path),
+            ShaderRef::Default => panic!(
+                "{} must return a fragment shader, there is no default post-processing shader",
+                std::any::type_name::<M>()
+            ),
+        };
+
+        PostProcessMaterialPipeline {
+            layout,
+            sampler,
+            shader,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: PostProcessMaterial> SpecializedRenderPipeline for PostProcessMaterialPipeline<M> {
+    type Key = TextureFormat;
+
+    fn specialize(&self, texture_format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("post_process_material".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+/// The pipeline used by a camera to run the [`PostProcessMaterial`] `M`.
+#[derive(Component)]
+pub struct CameraPostProcessMaterialPipeline<M: PostProcessMaterial> {
+    pub pipeline_id: CachedRenderPipelineId,
+    marker: PhantomData<M>,
+}
+
+pub fn prepare_post_process_material_pipelines<M: PostProcessMaterial>(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PostProcessMaterialPipeline<M>>>,
+    post_process_pipeline: Res<PostProcessMaterialPipeline<M>>,
+    views: Query<(Entity, &ExtractedView), With<M>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &post_process_pipeline,
+            if view.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(CameraPostProcessMaterialPipeline::<M> {
+                pipeline_id,
+                marker: PhantomData,
+            });
+    }
+}
@@ -0,0 +1,81 @@
+use std::marker::PhantomData;
+
+use crate::post_process_material::{
+    CameraPostProcessMaterialPipeline, PostProcessMaterial, PostProcessMaterialPipeline,
+};
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryItem;
+use bevy_render::{
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment,
+        RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::ViewTarget,
+};
+
+/// Runs the [`PostProcessMaterial`] `M` on the view target of a camera.
+pub struct PostProcessMaterialNode<M: PostProcessMaterial>(PhantomData<M>);
+
+impl<M: PostProcessMaterial> FromWorld for PostProcessMaterialNode<M> {
+    fn from_world(_world: &mut World) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: PostProcessMaterial> ViewNode for PostProcessMaterialNode<M> {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static CameraPostProcessMaterialPipeline<M>,
+        &'static DynamicUniformIndex<M>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, pipeline, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let post_process_pipeline = world.resource::<PostProcessMaterialPipeline<M>>();
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline.pipeline_id) else {
+            return Ok(());
+        };
+        let Some(uniforms) = world.resource::<ComponentUniforms<M>>().binding() else {
+            return Ok(());
+        };
+
+        let post_process = target.post_process_write();
+        let bind_group = render_context.render_device().create_bind_group(
+            "post_process_material_bind_group",
+            &post_process_pipeline.layout,
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &post_process_pipeline.sampler,
+                uniforms,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("post_process_material_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -68,6 +68,7 @@ impl Default for FpsOverlayConfig {
                 font: Handle::<Font>::default(),
                 font_size: 32.0,
                 color: Color::WHITE,
+                ..default()
             },
         }
     }
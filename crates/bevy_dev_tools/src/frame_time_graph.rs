@@ -0,0 +1,295 @@
+//! Module containing logic for the frame time graph overlay.
+
+use bevy_app::{Plugin, Startup, Update};
+use bevy_asset::Handle;
+use bevy_color::{Color, Srgba};
+use bevy_diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy_ecs::{
+    component::Component,
+    query::{With, Without},
+    schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+    system::{Commands, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::{BuildChildren, Children};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_math::Vec2;
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::default;
+
+/// Global [`ZIndex`] used to render the frame time graph overlay.
+///
+/// We use a number slightly under `i32::MAX` so you can render on top of it if you really need to.
+pub const FRAME_TIME_GRAPH_ZINDEX: i32 = i32::MAX - 33;
+
+/// A plugin that adds a frame time graph overlay to the Bevy application.
+///
+/// The overlay shows a bar graph of the recent frame times, along with the FPS, the 50th, 95th
+/// and 99th percentiles of the frame time and the GPU time. Everything is read from the
+/// [`DiagnosticsStore`], so the overlay works in any project using Bevy UI.
+///
+/// This plugin will add the [`FrameTimeDiagnosticsPlugin`] if it wasn't added before. The GPU
+/// time is only available when the `RenderDiagnosticsPlugin` is added and the platform supports
+/// timestamp queries.
+#[derive(Default)]
+pub struct FrameTimeGraphPlugin {
+    /// Starting configuration of overlay, this can be later be changed through [`FrameTimeGraphConfig`] resource.
+    pub config: FrameTimeGraphConfig,
+}
+
+impl Plugin for FrameTimeGraphPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        // TODO: Use plugin dependencies, see https://github.com/bevyengine/bevy/issues/69
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.insert_resource(self.config.clone())
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    toggle_graph,
+                    customize_graph.run_if(resource_changed::<FrameTimeGraphConfig>),
+                    update_graph,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configuration options for the frame time graph overlay.
+#[derive(Resource, Clone)]
+pub struct FrameTimeGraphConfig {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+    /// The key that toggles [`enabled`](Self::enabled), if any.
+    pub toggle_key: Option<KeyCode>,
+    /// Configuration of text in the overlay.
+    pub text_config: TextStyle,
+    /// The size of the graph, in logical pixels.
+    pub graph_size: Vec2,
+    /// The frame time, in milliseconds, that corresponds to the top of the graph.
+    ///
+    /// Longer frames are clamped to the top of the graph.
+    pub max_frame_time_ms: f32,
+    /// The frame time budget, in milliseconds.
+    ///
+    /// Frames that fit in the budget are drawn with [`good_color`](Self::good_color), and
+    /// those that don't with [`bad_color`](Self::bad_color).
+    pub target_frame_time_ms: f32,
+    /// The color of the bars of the frames that fit in the budget.
+    pub good_color: Color,
+    /// The color of the bars of the frames that exceed the budget.
+    pub bad_color: Color,
+}
+
+impl Default for FrameTimeGraphConfig {
+    fn default() -> Self {
+        FrameTimeGraphConfig {
+            enabled: true,
+            toggle_key: Some(KeyCode::F10),
+            text_config: TextStyle {
+                font: Handle::<Font>::default(),
+                font_size: 16.0,
+                color: Color::WHITE,
+                ..default()
+            },
+            graph_size: Vec2::new(240.0, 60.0),
+            max_frame_time_ms: 1000.0 / 30.0,
+            target_frame_time_ms: 1000.0 / 60.0,
+            good_color: Srgba::rgb(0.3, 0.8, 0.3).into(),
+            bad_color: Srgba::rgb(0.9, 0.3, 0.2).into(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct FrameTimeGraphRoot;
+
+#[derive(Component)]
+struct FrameTimeGraphBars;
+
+#[derive(Component)]
+struct FrameTimeGraphText;
+
+fn setup(
+    mut commands: Commands,
+    config: Res<FrameTimeGraphConfig>,
+    diagnostics: Res<DiagnosticsStore>,
+) {
+    let bar_count = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .map(|diagnostic| diagnostic.get_max_history_length())
+        .unwrap_or(120)
+        .max(1);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // We need to make sure the overlay doesn't affect the position of other UI nodes
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(4.0)),
+                    display: if config.enabled {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                // Render overlay on top of everything
+                z_index: ZIndex::Global(FRAME_TIME_GRAPH_ZINDEX),
+                ..default()
+            },
+            FrameTimeGraphRoot,
+        ))
+        .with_children(|c| {
+            c.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("FPS: ", config.text_config.clone()),
+                    TextSection::from_style(config.text_config.clone()),
+                ]),
+                FrameTimeGraphText,
+            ));
+            c.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Px(config.graph_size.x),
+                        height: Val::Px(config.graph_size.y),
+                        align_items: AlignItems::FlexEnd,
+                        ..default()
+                    },
+                    ..default()
+                },
+                FrameTimeGraphBars,
+            ))
+            .with_children(|c| {
+                for _ in 0..bar_count {
+                    c.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0 / bar_count as f32),
+                            height: Val::Percent(0.0),
+                            ..default()
+                        },
+                        background_color: config.good_color.into(),
+                        ..default()
+                    });
+                }
+            });
+        });
+}
+
+fn toggle_graph(
+    keyboard_input: Option<Res<ButtonInput<KeyCode>>>,
+    mut config: ResMut<FrameTimeGraphConfig>,
+) {
+    let (Some(keyboard_input), Some(toggle_key)) = (keyboard_input, config.toggle_key) else {
+        return;
+    };
+    if keyboard_input.just_pressed(toggle_key) {
+        config.enabled = !config.enabled;
+    }
+}
+
+fn customize_graph(
+    config: Res<FrameTimeGraphConfig>,
+    mut root_query: Query<&mut Style, With<FrameTimeGraphRoot>>,
+    mut bars_query: Query<&mut Style, (With<FrameTimeGraphBars>, Without<FrameTimeGraphRoot>)>,
+    mut text_query: Query<&mut Text, With<FrameTimeGraphText>>,
+) {
+    for mut style in &mut root_query {
+        style.display = if config.enabled {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for mut style in &mut bars_query {
+        style.width = Val::Px(config.graph_size.x);
+        style.height = Val::Px(config.graph_size.y);
+    }
+    for mut text in &mut text_query {
+        for section in text.sections.iter_mut() {
+            section.style = config.text_config.clone();
+        }
+    }
+}
+
+/// Returns the GPU time of the last frame in milliseconds, summed over the top-level render
+/// diagnostic spans.
+fn gpu_time_ms(diagnostics: &DiagnosticsStore) -> Option<f64> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            let mut components = diagnostic.path().components();
+            components.next() == Some("render")
+                && components.next().is_some()
+                && components.next() == Some("elapsed_gpu")
+                && components.next().is_none()
+        })
+        .filter_map(|diagnostic| diagnostic.value())
+        .reduce(|total, value| total + value)
+}
+
+fn update_graph(
+    config: Res<FrameTimeGraphConfig>,
+    diagnostics: Res<DiagnosticsStore>,
+    bars_query: Query<&Children, With<FrameTimeGraphBars>>,
+    mut bar_query: Query<(&mut Style, &mut BackgroundColor)>,
+    mut text_query: Query<&mut Text, With<FrameTimeGraphText>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let Some(frame_time) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+
+    for children in &bars_query {
+        // The most recent frame is drawn on the right.
+        let padding = children.len().saturating_sub(frame_time.history_len());
+        let values = (0..padding)
+            .map(|_| None)
+            .chain(frame_time.values().map(Some));
+        for (bar, value) in children.iter().zip(values) {
+            let Ok((mut style, mut background_color)) = bar_query.get_mut(*bar) else {
+                continue;
+            };
+            let value = value.copied().unwrap_or(0.0) as f32;
+            let height = (value / config.max_frame_time_ms * 100.0).clamp(0.0, 100.0);
+            style.height = Val::Percent(height);
+            background_color.0 = if value <= config.target_frame_time_ms {
+                config.good_color
+            } else {
+                config.bad_color
+            };
+        }
+    }
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed());
+    let mut readout = match fps {
+        Some(fps) => format!("{fps:.1}"),
+        None => "-".to_string(),
+    };
+    if let (Some(p50), Some(p95), Some(p99)) = (
+        frame_time.percentile(50.0),
+        frame_time.percentile(95.0),
+        frame_time.percentile(99.0),
+    ) {
+        readout += &format!("\nFrame: p50 {p50:.2} ms, p95 {p95:.2} ms, p99 {p99:.2} ms");
+    }
+    if let Some(gpu_time) = gpu_time_ms(&diagnostics) {
+        readout += &format!("\nGPU: {gpu_time:.2} ms");
+    }
+
+    for mut text in &mut text_query {
+        text.sections[1].value.clone_from(&readout);
+    }
+}
@@ -0,0 +1,506 @@
+//! A GUI-independent entity inspector.
+//!
+//! The [`InspectorPlugin`] exposes the entities of a running app, along with their reflected
+//! components, through a pair of channels. External tools, such as an editor or a web
+//! inspector connected over a socket, can send [`InspectorRequest`]s through an
+//! [`InspectorHandle`] and receive [`InspectorResponse`]s in return. The same operations are
+//! available as plain functions taking a [`World`], for in-process use.
+
+use bevy_app::{App, Last, Plugin};
+use bevy_core::Name;
+use bevy_ecs::{
+    component::{ComponentId, Tick},
+    entity::Entity,
+    query::{QueryBuilder, QueryState},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    system::Resource,
+    world::{EntityRef, Mut, World},
+};
+use bevy_reflect::{Reflect, ReflectFromReflect, TypeRegistry};
+use bevy_utils::HashMap;
+use crossbeam_channel::{Receiver, Sender};
+use std::fmt;
+
+/// Adds the [`InspectorHandle`] resource, and processes the requests sent through it at the
+/// end of every frame.
+#[derive(Default)]
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        let (request_sender, request_receiver) = crossbeam_channel::unbounded();
+        let (response_sender, response_receiver) = crossbeam_channel::unbounded();
+        app.insert_resource(InspectorHandle {
+            requests: request_sender,
+            responses: response_receiver,
+        })
+        .insert_resource(InspectorChannels {
+            requests: request_receiver,
+            responses: response_sender,
+            watches: HashMap::new(),
+        })
+        .add_systems(Last, process_inspector_requests);
+    }
+}
+
+/// Identifies a watch registered with [`InspectorRequest::Watch`].
+pub type WatchId = u32;
+
+/// A request sent to the inspector through an [`InspectorHandle`].
+#[derive(Debug)]
+pub enum InspectorRequest {
+    /// Lists every entity of the world. Answered with [`InspectorResponse::Entities`].
+    ListEntities,
+    /// Reads the reflected components of an entity. Answered with [`InspectorResponse::Entity`].
+    GetEntity(Entity),
+    /// Sets a component of an entity to a reflected value, inserting it if it is missing.
+    /// Answered with [`InspectorResponse::ComponentSet`].
+    SetComponent {
+        /// The entity to modify.
+        entity: Entity,
+        /// The type path of the component, as returned in [`ReflectedComponent::type_path`].
+        type_path: String,
+        /// The new value of the component, which must be convertible to it with
+        /// [`FromReflect`](bevy_reflect::FromReflect).
+        value: Box<dyn Reflect>,
+    },
+    /// Starts watching the entities that have all the given components.
+    ///
+    /// Every frame in which at least one of these components changed on a matching entity,
+    /// an [`InspectorResponse::Watch`] is sent with the new values of the changed entities.
+    /// Registering a watch with an existing id replaces it.
+    Watch {
+        /// The id of the watch, chosen by the caller.
+        id: WatchId,
+        /// The type paths of the watched components.
+        type_paths: Vec<String>,
+    },
+    /// Stops a watch registered with [`InspectorRequest::Watch`].
+    Unwatch(WatchId),
+}
+
+/// A response sent by the inspector, received through an [`InspectorHandle`].
+#[derive(Debug)]
+pub enum InspectorResponse {
+    /// Every entity of the world.
+    Entities(Vec<EntitySummary>),
+    /// The reflected components of an entity.
+    Entity {
+        /// The inspected entity.
+        entity: Entity,
+        /// Its reflected components.
+        components: Vec<ReflectedComponent>,
+    },
+    /// A component was successfully set.
+    ComponentSet {
+        /// The modified entity.
+        entity: Entity,
+        /// The type path of the modified component.
+        type_path: String,
+    },
+    /// The watched entities that changed this frame.
+    Watch {
+        /// The id of the watch.
+        id: WatchId,
+        /// The changed entities, along with the values of the watched components.
+        entities: Vec<(Entity, Vec<ReflectedComponent>)>,
+    },
+    /// A request failed.
+    Error(InspectorError),
+}
+
+/// A short description of an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntitySummary {
+    /// The entity.
+    pub entity: Entity,
+    /// Its [`Name`], if any.
+    pub name: Option<String>,
+    /// The names of all its components, including the ones that are not reflected.
+    pub components: Vec<String>,
+}
+
+/// A copy of a reflected component.
+#[derive(Debug)]
+pub struct ReflectedComponent {
+    /// The type path of the component, used to refer to it in [`InspectorRequest`]s.
+    pub type_path: String,
+    /// The value of the component.
+    pub value: Box<dyn Reflect>,
+}
+
+/// An error returned by the inspector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InspectorError {
+    /// The entity does not exist.
+    NoSuchEntity(Entity),
+    /// The type path is not registered in the [`AppTypeRegistry`], or the type does not
+    /// reflect `Component` and `FromReflect`.
+    UnknownComponent(String),
+    /// The value given to [`InspectorRequest::SetComponent`] can't be converted to the
+    /// component.
+    MismatchedType {
+        /// The type path of the component.
+        expected: String,
+        /// The type path of the given value.
+        found: String,
+    },
+}
+
+impl fmt::Display for InspectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InspectorError::NoSuchEntity(entity) => write!(f, "entity {entity:?} does not exist"),
+            InspectorError::UnknownComponent(type_path) => {
+                write!(f, "`{type_path}` is not a registered reflected component")
+            }
+            InspectorError::MismatchedType { expected, found } => {
+                write!(f, "expected a value of type `{expected}`, found `{found}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InspectorError {}
+
+/// A handle to send [`InspectorRequest`]s and receive [`InspectorResponse`]s.
+///
+/// The handle can be cloned out of the world and moved to another thread, for example to
+/// serve an external editor.
+#[derive(Resource, Clone)]
+pub struct InspectorHandle {
+    requests: Sender<InspectorRequest>,
+    responses: Receiver<InspectorResponse>,
+}
+
+impl InspectorHandle {
+    /// Sends a request, processed at the end of the current or next frame.
+    pub fn send(&self, request: InspectorRequest) {
+        // The receiver lives as long as the app, so this only fails once it was dropped.
+        let _ = self.requests.send(request);
+    }
+
+    /// Returns the next available response, if any.
+    pub fn try_recv(&self) -> Option<InspectorResponse> {
+        self.responses.try_recv().ok()
+    }
+
+    /// Returns the underlying response receiver, to block on or select over.
+    pub fn responses(&self) -> &Receiver<InspectorResponse> {
+        &self.responses
+    }
+}
+
+struct Watch {
+    component_ids: Vec<ComponentId>,
+    type_paths: Vec<String>,
+    /// The entities with all the watched components.
+    query: QueryState<EntityRef<'static>>,
+    last_run: Tick,
+}
+
+#[derive(Resource)]
+struct InspectorChannels {
+    requests: Receiver<InspectorRequest>,
+    responses: Sender<InspectorResponse>,
+    watches: HashMap<WatchId, Watch>,
+}
+
+/// Lists every entity of the `world`.
+pub fn list_entities(world: &World) -> Vec<EntitySummary> {
+    world
+        .iter_entities()
+        .map(|entity_ref| EntitySummary {
+            entity: entity_ref.id(),
+            name: entity_ref
+                .get::<Name>()
+                .map(|name| name.as_str().to_owned()),
+            components: world
+                .inspect_entity(entity_ref.id())
+                .into_iter()
+                .map(|info| info.name().to_owned())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Returns a copy of every reflected component of `entity`.
+///
+/// Components that are not registered in the `registry`, or that do not reflect `Component`,
+/// are skipped.
+pub fn inspect_entity(
+    world: &World,
+    registry: &TypeRegistry,
+    entity: Entity,
+) -> Result<Vec<ReflectedComponent>, InspectorError> {
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or(InspectorError::NoSuchEntity(entity))?;
+    Ok(world
+        .inspect_entity(entity)
+        .into_iter()
+        .filter_map(|info| reflect_component(registry, entity_ref, info.type_id()?))
+        .collect())
+}
+
+/// Sets the component with the given type path on `entity` to `value`, inserting the component
+/// if it is missing.
+///
+/// The value is converted to the component with [`FromReflect`](bevy_reflect::FromReflect)
+/// first, so a value of the wrong kind is rejected instead of being applied.
+pub fn set_component(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    type_path: &str,
+    value: &dyn Reflect,
+) -> Result<(), InspectorError> {
+    let registration = registry
+        .get_with_type_path(type_path)
+        .ok_or_else(|| InspectorError::UnknownComponent(type_path.to_owned()))?;
+    let (Some(reflect_component), Some(reflect_from_reflect)) = (
+        registration.data::<ReflectComponent>(),
+        registration.data::<ReflectFromReflect>(),
+    ) else {
+        return Err(InspectorError::UnknownComponent(type_path.to_owned()));
+    };
+    // Applying a value of another kind to the component panics, while the conversion fails.
+    let value =
+        reflect_from_reflect
+            .from_reflect(value)
+            .ok_or_else(|| InspectorError::MismatchedType {
+                expected: type_path.to_owned(),
+                found: value.reflect_type_path().to_owned(),
+            })?;
+    let mut entity_mut = world
+        .get_entity_mut(entity)
+        .ok_or(InspectorError::NoSuchEntity(entity))?;
+    reflect_component.apply_or_insert(&mut entity_mut, value.as_ref(), registry);
+    Ok(())
+}
+
+fn reflect_component(
+    registry: &TypeRegistry,
+    entity_ref: EntityRef,
+    type_id: std::any::TypeId,
+) -> Option<ReflectedComponent> {
+    let registration = registry.get(type_id)?;
+    let value = registration
+        .data::<ReflectComponent>()?
+        .reflect(entity_ref)?
+        .clone_value();
+    Some(ReflectedComponent {
+        type_path: registration.type_info().type_path().to_owned(),
+        value,
+    })
+}
+
+fn process_inspector_requests(world: &mut World) {
+    world.resource_scope(|world, mut channels: Mut<InspectorChannels>| {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let this_run = world.change_tick();
+
+        while let Ok(request) = channels.requests.try_recv() {
+            let response = match request {
+                InspectorRequest::ListEntities => {
+                    Some(InspectorResponse::Entities(list_entities(world)))
+                }
+                InspectorRequest::GetEntity(entity) => {
+                    Some(match inspect_entity(world, &registry, entity) {
+                        Ok(components) => InspectorResponse::Entity { entity, components },
+                        Err(error) => InspectorResponse::Error(error),
+                    })
+                }
+                InspectorRequest::SetComponent {
+                    entity,
+                    type_path,
+                    value,
+                } => Some(
+                    match set_component(world, &registry, entity, &type_path, value.as_ref()) {
+                        Ok(()) => InspectorResponse::ComponentSet { entity, type_path },
+                        Err(error) => InspectorResponse::Error(error),
+                    },
+                ),
+                InspectorRequest::Watch { id, type_paths } => {
+                    let component_ids = type_paths
+                        .iter()
+                        .map(|type_path| {
+                            registry
+                                .get_with_type_path(type_path)
+                                .and_then(|registration| {
+                                    world.components().get_id(registration.type_id())
+                                })
+                                .ok_or_else(|| InspectorError::UnknownComponent(type_path.clone()))
+                        })
+                        .collect::<Result<Vec<_>, _>>();
+                    match component_ids {
+                        Ok(component_ids) => {
+                            let mut builder = QueryBuilder::<EntityRef>::new(world);
+                            for &component_id in &component_ids {
+                                builder.with_id(component_id);
+                            }
+                            let query = builder.build();
+                            channels.watches.insert(
+                                id,
+                                Watch {
+                                    component_ids,
+                                    type_paths,
+                                    query,
+                                    // Report the current values on the first frame.
+                                    last_run: Tick::new(0),
+                                },
+                            );
+                            None
+                        }
+                        Err(error) => Some(InspectorResponse::Error(error)),
+                    }
+                }
+                InspectorRequest::Unwatch(id) => {
+                    channels.watches.remove(&id);
+                    None
+                }
+            };
+            if let Some(response) = response {
+                let _ = channels.responses.send(response);
+            }
+        }
+
+        let channels = &mut *channels;
+        for (&id, watch) in &mut channels.watches {
+            // Only the entities with all the watched components are checked for changes.
+            let entities: Vec<_> = watch
+                .query
+                .iter(world)
+                .filter(|entity_ref| {
+                    watch.component_ids.iter().any(|&component_id| {
+                        entity_ref
+                            .get_change_ticks_by_id(component_id)
+                            .is_some_and(|ticks| ticks.is_changed(watch.last_run, this_run))
+                    })
+                })
+                .map(|entity_ref| {
+                    let components = watch
+                        .type_paths
+                        .iter()
+                        .filter_map(|type_path| {
+                            let type_id = registry.get_with_type_path(type_path)?.type_id();
+                            reflect_component(&registry, entity_ref, type_id)
+                        })
+                        .collect();
+                    (entity_ref.id(), components)
+                })
+                .collect();
+            watch.last_run = this_run;
+            if !entities.is_empty() {
+                let _ = channels
+                    .responses
+                    .send(InspectorResponse::Watch { id, entities });
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_core::Name;
+    use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent, world::World};
+    use bevy_reflect::{DynamicStruct, Reflect, TypePath, TypeRegistry};
+
+    use super::{
+        set_component, InspectorError, InspectorHandle, InspectorPlugin, InspectorRequest,
+        InspectorResponse,
+    };
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        value: f32,
+    }
+
+    #[test]
+    fn set_component_rejects_mismatched_values() {
+        let mut world = World::new();
+        let mut registry = TypeRegistry::new();
+        registry.register::<Health>();
+        let entity = world.spawn_empty().id();
+        let type_path = Health::type_path();
+
+        set_component(
+            &mut world,
+            &registry,
+            entity,
+            type_path,
+            &Health { value: 5. },
+        )
+        .unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health { value: 5. }));
+
+        // Values of another kind are rejected instead of panicking when applied
+        for value in [
+            Box::new(10u32) as Box<dyn Reflect>,
+            Box::new(DynamicStruct::default()),
+        ] {
+            assert!(matches!(
+                set_component(&mut world, &registry, entity, type_path, value.as_ref()),
+                Err(InspectorError::MismatchedType { .. })
+            ));
+        }
+        assert_eq!(world.get::<Health>(entity), Some(&Health { value: 5. }));
+
+        assert_eq!(
+            set_component(&mut world, &registry, entity, "unknown::Type", &5u32),
+            Err(InspectorError::UnknownComponent("unknown::Type".to_owned()))
+        );
+        world.despawn(entity);
+        assert_eq!(
+            set_component(&mut world, &registry, entity, type_path, &Health::default()),
+            Err(InspectorError::NoSuchEntity(entity))
+        );
+    }
+
+    fn watched_entities(handle: &InspectorHandle) -> Option<Vec<(Entity, f32)>> {
+        match handle.try_recv()? {
+            InspectorResponse::Watch { id: 0, entities } => Some(
+                entities
+                    .into_iter()
+                    .map(|(entity, components)| {
+                        let health = components[0].value.downcast_ref::<Health>().unwrap();
+                        (entity, health.value)
+                    })
+                    .collect(),
+            ),
+            response => panic!("unexpected response {response:?}"),
+        }
+    }
+
+    #[test]
+    fn watches_report_changed_entities() {
+        let mut app = App::new();
+        app.add_plugins(InspectorPlugin).register_type::<Health>();
+        let entity = app.world_mut().spawn(Health { value: 1. }).id();
+        app.world_mut().spawn(Name::new("Unwatched"));
+        let handle = app.world().resource::<InspectorHandle>().clone();
+
+        handle.send(InspectorRequest::Watch {
+            id: 0,
+            type_paths: vec![Health::type_path().to_owned()],
+        });
+        app.update();
+        // The current values are reported on the first frame
+        assert_eq!(watched_entities(&handle), Some(vec![(entity, 1.)]));
+
+        app.update();
+        assert_eq!(watched_entities(&handle), None);
+
+        app.world_mut().get_mut::<Health>(entity).unwrap().value = 2.;
+        app.update();
+        assert_eq!(watched_entities(&handle), Some(vec![(entity, 2.)]));
+
+        handle.send(InspectorRequest::Unwatch(0));
+        app.world_mut().get_mut::<Health>(entity).unwrap().value = 3.;
+        app.update();
+        assert_eq!(watched_entities(&handle), None);
+    }
+}
@@ -14,6 +14,8 @@ use bevy_app::prelude::*;
 pub mod ci_testing;
 
 pub mod fps_overlay;
+pub mod frame_time_graph;
+pub mod inspector;
 
 #[cfg(feature = "bevy_ui_debug")]
 pub mod ui_debug_overlay;
@@ -253,6 +253,23 @@ impl Diagnostic {
         }
     }
 
+    /// Return the value below which `percentile` percent of this diagnostic's recent values
+    /// fall, using the nearest-rank method. `percentile` is clamped to `0.0..=100.0`.
+    ///
+    /// For example, the 99th percentile of the frame time is the duration that 99% of the
+    /// recent frames didn't exceed. This sorts a copy of the history, so it isn't as cheap as
+    /// [`average`](Self::average).
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        let mut values: Vec<f64> = self.values().copied().filter(|v| !v.is_nan()).collect();
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_unstable_by(f64::total_cmp);
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * values.len() as f64).ceil() as usize;
+        Some(values[rank.saturating_sub(1)])
+    }
+
     /// Return the number of elements for this diagnostic.
     pub fn history_len(&self) -> usize {
         self.history.len()
@@ -417,3 +434,48 @@ impl RegisterDiagnostic for App {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_utils::Instant;
+
+    use super::{Diagnostic, DiagnosticMeasurement, DiagnosticPath};
+
+    fn diagnostic_with(values: &[f64]) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("test"));
+        let time = Instant::now();
+        for &value in values {
+            diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+        }
+        diagnostic
+    }
+
+    #[test]
+    fn percentile_of_an_empty_history() {
+        assert_eq!(diagnostic_with(&[]).percentile(50.0), None);
+        // NaN values are ignored
+        assert_eq!(diagnostic_with(&[f64::NAN]).percentile(50.0), None);
+    }
+
+    #[test]
+    fn percentile_of_a_single_value() {
+        let diagnostic = diagnostic_with(&[4.0]);
+        for percentile in [0.0, 1.0, 50.0, 99.0, 100.0] {
+            assert_eq!(diagnostic.percentile(percentile), Some(4.0));
+        }
+    }
+
+    #[test]
+    fn percentile_uses_the_nearest_rank() {
+        let diagnostic = diagnostic_with(&[5.0, 1.0, f64::NAN, 4.0, 2.0, 3.0]);
+        assert_eq!(diagnostic.percentile(0.0), Some(1.0));
+        assert_eq!(diagnostic.percentile(20.0), Some(1.0));
+        assert_eq!(diagnostic.percentile(21.0), Some(2.0));
+        assert_eq!(diagnostic.percentile(50.0), Some(3.0));
+        assert_eq!(diagnostic.percentile(99.0), Some(5.0));
+        assert_eq!(diagnostic.percentile(100.0), Some(5.0));
+        // Out of range percentiles are clamped
+        assert_eq!(diagnostic.percentile(-10.0), Some(1.0));
+        assert_eq!(diagnostic.percentile(150.0), Some(5.0));
+    }
+}
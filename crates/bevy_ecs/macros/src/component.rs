@@ -1,7 +1,10 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result};
+use syn::{
+    parse_macro_input, parse_quote, punctuated::Punctuated, DeriveInput, Ident, LitStr, Path,
+    Result, Token, Type,
+};
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -49,6 +52,18 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     };
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
+    let register_required_components = (!attrs.requires.is_empty()).then(|| {
+        let required = &attrs.requires;
+        quote! {
+            fn register_required_components(
+                components: &mut #bevy_ecs_path::component::Components,
+                storages: &mut #bevy_ecs_path::storage::Storages,
+                required_components: &mut #bevy_ecs_path::component::RequiredComponents,
+            ) {
+                #(required_components.register::<#required>(components, storages);)*
+            }
+        }
+    });
 
     ast.generics
         .make_where_clause()
@@ -61,15 +76,19 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             const STORAGE_TYPE: #bevy_ecs_path::component::StorageType = #storage;
+
+            #register_required_components
         }
     })
 }
 
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
+pub const REQUIRE: &str = "require";
 
 struct Attrs {
     storage: StorageTy,
+    requires: Vec<Type>,
 }
 
 #[derive(Clone, Copy)]
@@ -85,6 +104,7 @@ const SPARSE_SET: &str = "SparseSet";
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        requires: Vec::new(),
     };
 
     for meta in ast.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
@@ -106,6 +126,11 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         })?;
     }
 
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident(REQUIRE)) {
+        let requires = attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated)?;
+        attrs.requires.extend(requires);
+    }
+
     Ok(attrs)
 }
 
@@ -248,6 +248,16 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
                     <(#(#param,)*) as SystemParam>::apply(state, system_meta, world);
                 }
 
+                #[inline]
+                unsafe fn validate_param(
+                    state: &Self::State,
+                    system_meta: &SystemMeta,
+                    world: UnsafeWorldCell,
+                ) -> bool {
+                    // SAFETY: Upheld by the caller.
+                    unsafe { <(#(#param,)*) as SystemParam>::validate_param(state, system_meta, world) }
+                }
+
                 #[inline]
                 unsafe fn get_param<'w, 's>(
                     state: &'s mut Self::State,
@@ -435,6 +445,16 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
                     <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::apply(&mut state.state, system_meta, world);
                 }
 
+                #[inline]
+                unsafe fn validate_param(
+                    state: &Self::State,
+                    system_meta: &#path::system::SystemMeta,
+                    world: #path::world::unsafe_world_cell::UnsafeWorldCell,
+                ) -> bool {
+                    // SAFETY: Upheld by the caller.
+                    unsafe { <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::validate_param(&state.state, system_meta, world) }
+                }
+
                 unsafe fn get_param<'w, 's>(
                     state: &'s mut Self::State,
                     system_meta: &#path::system::SystemMeta,
@@ -512,7 +532,7 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
     component::derive_resource(input)
 }
 
-#[proc_macro_derive(Component, attributes(component))]
+#[proc_macro_derive(Component, attributes(component, require))]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     component::derive_component(input)
 }
@@ -21,7 +21,7 @@
 
 use crate::{
     bundle::BundleId,
-    component::{ComponentId, Components, StorageType},
+    component::{ComponentId, Components, RequiredComponentConstructor, StorageType},
     entity::{Entity, EntityLocation},
     storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
 };
@@ -119,6 +119,21 @@ pub(crate) struct AddBundle {
     /// For each component iterated in the same order as the source [`Bundle`](crate::bundle::Bundle),
     /// indicate if the component is newly added to the target archetype or if it already existed
     pub bundle_status: Vec<ComponentStatus>,
+    /// The components that are newly added to the target archetype, with the components of the
+    /// [`Bundle`](crate::bundle::Bundle) first, followed by the required components
+    pub added: Vec<ComponentId>,
+    /// The constructors of the required components that are newly added to the target archetype,
+    /// in the same order as the last entries of `added`
+    pub required_components: Vec<RequiredComponentConstructor>,
+}
+
+impl AddBundle {
+    /// Returns an iterator over the required components that are newly added to the target archetype.
+    pub(crate) fn iter_required_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.added[self.added.len() - self.required_components.len()..]
+            .iter()
+            .copied()
+    }
 }
 
 /// This trait is used to report the status of [`Bundle`](crate::bundle::Bundle) components
@@ -202,12 +217,16 @@ impl Edges {
         bundle_id: BundleId,
         archetype_id: ArchetypeId,
         bundle_status: Vec<ComponentStatus>,
+        added: Vec<ComponentId>,
+        required_components: Vec<RequiredComponentConstructor>,
     ) {
         self.add_bundle.insert(
             bundle_id,
             AddBundle {
                 archetype_id,
                 bundle_status,
+                added,
+                required_components,
             },
         );
     }
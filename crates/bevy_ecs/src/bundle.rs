@@ -10,8 +10,10 @@ use crate::{
         AddBundle, Archetype, ArchetypeId, Archetypes, BundleComponentStatus, ComponentStatus,
         SpawnBundleStatus,
     },
-    component::{Component, ComponentId, Components, StorageType, Tick},
-    entity::{Entities, Entity, EntityLocation},
+    component::{
+        Component, ComponentId, Components, RequiredComponentConstructor, StorageType, Tick,
+    },
+    entity::{send_entity_spawned, Entities, Entity, EntityLocation},
     prelude::World,
     query::DebugCheckedUnwrap,
     storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
@@ -286,8 +288,14 @@ pub struct BundleInfo {
     id: BundleId,
     // SAFETY: Every ID in this list must be valid within the World that owns the BundleInfo,
     // must have its storage initialized (i.e. columns created in tables, sparse set created),
-    // and must be in the same order as the source bundle type writes its components in.
+    // and must be in the same order as the source bundle type writes its components in,
+    // followed by the required components that aren't explicitly part of the bundle.
     component_ids: Vec<ComponentId>,
+    // The number of components the source bundle type writes.
+    explicit_components_len: usize,
+    // The constructors of the required components, in the same order as the last entries of
+    // `component_ids`.
+    required_components: Vec<RequiredComponentConstructor>,
 }
 
 impl BundleInfo {
@@ -301,7 +309,7 @@ impl BundleInfo {
     unsafe fn new(
         bundle_type_name: &'static str,
         components: &Components,
-        component_ids: Vec<ComponentId>,
+        mut component_ids: Vec<ComponentId>,
         id: BundleId,
     ) -> BundleInfo {
         let mut deduped = component_ids.clone();
@@ -330,11 +338,31 @@ impl BundleInfo {
             panic!("Bundle {bundle_type_name} has duplicate components: {names}");
         }
 
+        let explicit_components_len = component_ids.len();
+        let mut required_components = Vec::new();
+        for index in 0..explicit_components_len {
+            // SAFETY: the caller ensures component_id is valid.
+            let info = unsafe { components.get_info_unchecked(component_ids[index]) };
+            for (required_id, constructor) in &info.required_components().0 {
+                if !component_ids.contains(required_id) {
+                    component_ids.push(*required_id);
+                    required_components.push(constructor.clone());
+                }
+            }
+        }
+
         // SAFETY: The caller ensures that component_ids:
         // - is valid for the associated world
         // - has had its storage initialized
         // - is in the same order as the source bundle type
-        BundleInfo { id, component_ids }
+        // Required components are valid and have had their storage initialized when the
+        // components requiring them were initialized.
+        BundleInfo {
+            id,
+            component_ids,
+            explicit_components_len,
+            required_components,
+        }
     }
 
     /// Returns a value identifying the associated [`Bundle`] type.
@@ -346,15 +374,40 @@ impl BundleInfo {
     /// Returns the [ID](ComponentId) of each component stored in this bundle.
     #[inline]
     pub fn components(&self) -> &[ComponentId] {
-        &self.component_ids
+        &self.component_ids[..self.explicit_components_len]
     }
 
     /// Returns an iterator over the [ID](ComponentId) of each component stored in this bundle.
     #[inline]
     pub fn iter_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components().iter().cloned()
+    }
+
+    /// Returns the [ID](ComponentId) of each component contributed by this bundle: the components
+    /// stored in the bundle, followed by the [required components](Component#required-components)
+    /// that aren't stored in it.
+    #[inline]
+    pub fn contributed_components(&self) -> &[ComponentId] {
+        &self.component_ids
+    }
+
+    /// Returns an iterator over the [ID](ComponentId) of each component contributed by this bundle.
+    ///
+    /// See [`BundleInfo::contributed_components`] for more information.
+    #[inline]
+    pub fn iter_contributed_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
         self.component_ids.iter().cloned()
     }
 
+    /// Returns an iterator over the [ID](ComponentId) of each [required component](Component#required-components)
+    /// contributed by this bundle that isn't stored in it.
+    #[inline]
+    pub fn iter_required_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.component_ids[self.explicit_components_len..]
+            .iter()
+            .cloned()
+    }
+
     /// This writes components from a given [`Bundle`] to the given entity.
     ///
     /// # Safety
@@ -371,6 +424,9 @@ impl BundleInfo {
     ///
     /// `table` must be the "new" table for `entity`. `table_row` must have space allocated for the
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
+    ///
+    /// `required_components` must be constructors of the required components of this bundle that
+    /// `entity` doesn't have yet.
     #[inline]
     #[allow(clippy::too_many_arguments)]
     unsafe fn write_components<T: DynamicBundle, S: BundleComponentStatus>(
@@ -378,6 +434,7 @@ impl BundleInfo {
         table: &mut Table,
         sparse_sets: &mut SparseSets,
         bundle_component_status: &S,
+        required_components: &[RequiredComponentConstructor],
         entity: Entity,
         table_row: TableRow,
         change_tick: Tick,
@@ -415,6 +472,14 @@ impl BundleInfo {
             }
             bundle_component += 1;
         });
+
+        for required_component in required_components {
+            // SAFETY: The caller ensures that `entity` doesn't have the required component yet,
+            // and the target table contains the required components.
+            unsafe {
+                required_component.initialize(table, sparse_sets, change_tick, table_row, entity);
+            }
+        }
     }
 
     /// Adds a bundle to the given archetype and returns the resulting archetype. This could be the
@@ -434,14 +499,34 @@ impl BundleInfo {
         }
         let mut new_table_components = Vec::new();
         let mut new_sparse_set_components = Vec::new();
-        let mut bundle_status = Vec::with_capacity(self.component_ids.len());
+        let mut bundle_status = Vec::with_capacity(self.explicit_components_len);
+        let mut added = Vec::new();
+        let mut added_required_components = Vec::new();
 
         let current_archetype = &mut archetypes[archetype_id];
-        for component_id in self.component_ids.iter().cloned() {
+        for component_id in self.iter_components() {
             if current_archetype.contains(component_id) {
                 bundle_status.push(ComponentStatus::Mutated);
             } else {
                 bundle_status.push(ComponentStatus::Added);
+                added.push(component_id);
+                // SAFETY: component_id exists
+                let component_info = unsafe { components.get_info_unchecked(component_id) };
+                match component_info.storage_type() {
+                    StorageType::Table => new_table_components.push(component_id),
+                    StorageType::SparseSet => new_sparse_set_components.push(component_id),
+                }
+            }
+        }
+
+        // Required components are only inserted if the entity doesn't have them yet.
+        for (component_id, constructor) in self
+            .iter_required_components()
+            .zip(self.required_components.iter())
+        {
+            if !current_archetype.contains(component_id) {
+                added.push(component_id);
+                added_required_components.push(constructor.clone());
                 // SAFETY: component_id exists
                 let component_info = unsafe { components.get_info_unchecked(component_id) };
                 match component_info.storage_type() {
@@ -454,7 +539,13 @@ impl BundleInfo {
         if new_table_components.is_empty() && new_sparse_set_components.is_empty() {
             let edges = current_archetype.edges_mut();
             // the archetype does not change when we add this bundle
-            edges.insert_add_bundle(self.id, archetype_id, bundle_status);
+            edges.insert_add_bundle(
+                self.id,
+                archetype_id,
+                bundle_status,
+                added,
+                added_required_components,
+            );
             archetype_id
         } else {
             let table_id;
@@ -502,6 +593,8 @@ impl BundleInfo {
                 self.id,
                 new_archetype_id,
                 bundle_status,
+                added,
+                added_required_components,
             );
             new_archetype_id
         }
@@ -654,6 +747,7 @@ impl<'w> BundleInserter<'w> {
                     table,
                     sparse_sets,
                     add_bundle,
+                    &add_bundle.required_components,
                     entity,
                     location.table_row,
                     self.change_tick,
@@ -692,6 +786,7 @@ impl<'w> BundleInserter<'w> {
                     table,
                     sparse_sets,
                     add_bundle,
+                    &add_bundle.required_components,
                     entity,
                     result.table_row,
                     self.change_tick,
@@ -771,6 +866,7 @@ impl<'w> BundleInserter<'w> {
                     new_table,
                     sparse_sets,
                     add_bundle,
+                    &add_bundle.required_components,
                     entity,
                     move_result.new_row,
                     self.change_tick,
@@ -788,20 +884,20 @@ impl<'w> BundleInserter<'w> {
             // SAFETY: All components in the bundle are guaranteed to exist in the World
             // as they must be initialized before creating the BundleInfo.
             unsafe {
-                deferred_world.trigger_on_add(
-                    entity,
-                    bundle_info
-                        .iter_components()
-                        .zip(add_bundle.bundle_status.iter())
-                        .filter(|(_, &status)| status == ComponentStatus::Added)
-                        .map(|(id, _)| id),
-                );
+                deferred_world.trigger_on_add(entity, add_bundle.added.iter().cloned());
             }
         }
         if new_archetype.has_on_insert() {
             // SAFETY: All components in the bundle are guaranteed to exist in the World
             // as they must be initialized before creating the BundleInfo.
-            unsafe { deferred_world.trigger_on_insert(entity, bundle_info.iter_components()) }
+            unsafe {
+                deferred_world.trigger_on_insert(
+                    entity,
+                    bundle_info
+                        .iter_components()
+                        .chain(add_bundle.iter_required_components()),
+                );
+            }
         }
 
         new_location
@@ -894,6 +990,7 @@ impl<'w> BundleSpawner<'w> {
                 table,
                 sparse_sets,
                 &SpawnBundleStatus,
+                &bundle_info.required_components,
                 entity,
                 table_row,
                 self.change_tick,
@@ -908,13 +1005,18 @@ impl<'w> BundleSpawner<'w> {
         if archetype.has_on_add() {
             // SAFETY: All components in the bundle are guaranteed to exist in the World
             // as they must be initialized before creating the BundleInfo.
-            unsafe { deferred_world.trigger_on_add(entity, bundle_info.iter_components()) };
+            unsafe {
+                deferred_world.trigger_on_add(entity, bundle_info.iter_contributed_components());
+            };
         }
         if archetype.has_on_insert() {
             // SAFETY: All components in the bundle are guaranteed to exist in the World
             // as they must be initialized before creating the BundleInfo.
-            unsafe { deferred_world.trigger_on_insert(entity, bundle_info.iter_components()) };
+            unsafe {
+                deferred_world.trigger_on_insert(entity, bundle_info.iter_contributed_components());
+            };
         }
+        send_entity_spawned(&mut deferred_world, entity, archetype.components());
 
         location
     }
@@ -1230,4 +1332,117 @@ mod tests {
         world.spawn(A).flush();
         assert_eq!(4, world.resource::<R>().0);
     }
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    #[require(Health)]
+    struct Armor(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[require(Armor)]
+    struct Knight;
+
+    #[derive(Component, Default)]
+    #[component(storage = "SparseSet")]
+    #[require(Cyclic2)]
+    struct Cyclic1;
+
+    #[derive(Component, Default)]
+    #[require(Cyclic1)]
+    struct Cyclic2;
+
+    #[derive(Component, Default)]
+    #[require(CyclicB)]
+    struct CyclicA;
+
+    #[derive(Component, Default)]
+    #[require(CyclicC)]
+    struct CyclicB;
+
+    #[derive(Component, Default)]
+    #[require(CyclicA)]
+    struct CyclicC;
+
+    #[test]
+    fn required_components_are_inserted_when_missing() {
+        let mut world = World::new();
+
+        let entity = world.spawn(Knight).id();
+        assert_eq!(world.get::<Armor>(entity), Some(&Armor(0)));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(0)));
+
+        let entity = world.spawn((Knight, Health(100))).id();
+        assert_eq!(world.get::<Armor>(entity), Some(&Armor(0)));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(100)));
+
+        // Inserting the component doesn't overwrite the components the entity already has.
+        let entity = world.spawn(Armor(5)).id();
+        world.entity_mut(entity).insert(Knight);
+        assert_eq!(world.get::<Armor>(entity), Some(&Armor(5)));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(0)));
+
+        // Removing the component doesn't remove its required components.
+        world.entity_mut(entity).remove::<Knight>();
+        assert!(world.get::<Armor>(entity).is_some());
+        assert!(world.get::<Health>(entity).is_some());
+    }
+
+    #[test]
+    fn required_components_trigger_hooks() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<Health>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| world.resource_mut::<R>().assert_order(1));
+
+        let entity = world.spawn_empty().id();
+        world.entity_mut(entity).insert(Armor(1));
+        assert_eq!(2, world.resource::<R>().0);
+
+        // The required component already exists, so its hooks don't run again.
+        world.entity_mut(entity).insert(Knight);
+        assert_eq!(2, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn cyclic_required_components() {
+        let mut world = World::new();
+        let entity = world.spawn(Cyclic1).id();
+        assert!(world.get::<Cyclic2>(entity).is_some());
+        let entity = world.spawn(Cyclic2).id();
+        assert!(world.get::<Cyclic1>(entity).is_some());
+    }
+
+    #[test]
+    fn transitive_cyclic_required_components() {
+        let mut world = World::new();
+        // Every component of the cycle requires the two others, whichever is initialized first
+        let entity = world.spawn(CyclicA).id();
+        assert!(world.get::<CyclicB>(entity).is_some());
+        assert!(world.get::<CyclicC>(entity).is_some());
+        let entity = world.spawn(CyclicB).id();
+        assert!(world.get::<CyclicC>(entity).is_some());
+        assert!(world.get::<CyclicA>(entity).is_some());
+        let entity = world.spawn(CyclicC).id();
+        assert!(world.get::<CyclicA>(entity).is_some());
+        assert!(world.get::<CyclicB>(entity).is_some());
+
+        for id in [
+            world.component_id::<CyclicA>(),
+            world.component_id::<CyclicB>(),
+            world.component_id::<CyclicC>(),
+        ] {
+            let id = id.unwrap();
+            let required = world
+                .components()
+                .get_info(id)
+                .unwrap()
+                .required_components();
+            assert_eq!(required.len(), 2);
+            assert!(!required.contains(id));
+        }
+    }
 }
@@ -5,7 +5,8 @@ use crate::{
     archetype::ArchetypeFlags,
     change_detection::MAX_CHANGE_AGE,
     entity::Entity,
-    storage::{SparseSetIndex, Storages},
+    query::DebugCheckedUnwrap,
+    storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
     system::{Local, Resource, SystemParam},
     world::{DeferredWorld, FromWorld, World},
 };
@@ -13,15 +14,17 @@ pub use bevy_ecs_macros::Component;
 use bevy_ptr::{OwningPtr, UnsafeCellDeref};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
-use bevy_utils::TypeIdMap;
+use bevy_utils::{HashSet, TypeIdMap};
 use std::cell::UnsafeCell;
 use std::{
     alloc::Layout,
     any::{Any, TypeId},
     borrow::Cow,
+    collections::VecDeque,
     marker::PhantomData,
     mem::needs_drop,
     ops::Deref,
+    sync::Arc,
 };
 
 /// A data type that can be used to store data for an [entity].
@@ -94,6 +97,37 @@ use std::{
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
+/// # Required components
+///
+/// Components can declare that they require other components with the `#[require(...)]`
+/// attribute. Whenever a component is inserted on an entity, the components it requires that
+/// the entity doesn't have yet are inserted along with it, using their [`Default`] value.
+/// Requirements are transitive: the components required by a required component are inserted too.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// #[derive(Component, Default, Debug, PartialEq)]
+/// struct Position(f32);
+///
+/// #[derive(Component, Default, Debug, PartialEq)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component)]
+/// #[require(Position, Velocity)]
+/// struct Player;
+///
+/// let mut world = World::new();
+/// // `Position` is inserted with its default value...
+/// let player = world.spawn((Player, Velocity(5.0))).id();
+/// assert_eq!(world.get::<Position>(player), Some(&Position(0.0)));
+/// // ...but components inserted explicitly are kept as they are.
+/// assert_eq!(world.get::<Velocity>(player), Some(&Velocity(5.0)));
+/// ```
+///
+/// Required components are only inserted when missing: they never overwrite the existing
+/// components of an entity, and removing the requiring component doesn't remove them.
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -157,6 +191,16 @@ pub trait Component: Send + Sync + 'static {
 
     /// Called when registering this component, allowing mutable access to its [`ComponentHooks`].
     fn register_component_hooks(_hooks: &mut ComponentHooks) {}
+
+    /// Called when registering this component, allowing it to declare the components it requires.
+    ///
+    /// See the [required components](Component#required-components) section for more information.
+    fn register_required_components(
+        _components: &mut Components,
+        _storages: &mut Storages,
+        _required_components: &mut RequiredComponents,
+    ) {
+    }
 }
 
 /// The storage used for a specific component type.
@@ -327,6 +371,7 @@ pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
     hooks: ComponentHooks,
+    required_components: RequiredComponents,
 }
 
 impl ComponentInfo {
@@ -386,6 +431,7 @@ impl ComponentInfo {
             id,
             descriptor,
             hooks: ComponentHooks::default(),
+            required_components: RequiredComponents::default(),
         }
     }
 
@@ -407,6 +453,12 @@ impl ComponentInfo {
     pub fn hooks(&self) -> &ComponentHooks {
         &self.hooks
     }
+
+    /// Returns the components required by this [`Component`], including the ones that are
+    /// required transitively.
+    pub fn required_components(&self) -> &RequiredComponents {
+        &self.required_components
+    }
 }
 
 /// A value which uniquely identifies the type of a [`Component`] of [`Resource`] within a
@@ -595,6 +647,9 @@ pub struct Components {
     components: Vec<ComponentInfo>,
     indices: TypeIdMap<ComponentId>,
     resource_indices: TypeIdMap<ComponentId>,
+    /// The components initialized since the outermost call to [`Components::init_component`],
+    /// whose required components are completed once it returns.
+    initializing: Vec<ComponentId>,
 }
 
 impl Components {
@@ -609,21 +664,60 @@ impl Components {
     #[inline]
     pub fn init_component<T: Component>(&mut self, storages: &mut Storages) -> ComponentId {
         let type_id = TypeId::of::<T>();
+        if let Some(&id) = self.indices.get(&type_id) {
+            return id;
+        }
 
-        let Components {
-            indices,
-            components,
-            ..
-        } = self;
-        *indices.entry(type_id).or_insert_with(|| {
-            let index = Components::init_component_inner(
-                components,
-                storages,
-                ComponentDescriptor::new::<T>(),
-            );
-            T::register_component_hooks(&mut components[index.index()].hooks);
-            index
-        })
+        let id = Components::init_component_inner(
+            &mut self.components,
+            storages,
+            ComponentDescriptor::new::<T>(),
+        );
+        // The id is registered before the required components so that cyclic requirements
+        // terminate.
+        self.indices.insert(type_id, id);
+        T::register_component_hooks(&mut self.components[id.index()].hooks);
+
+        let outermost = self.initializing.is_empty();
+        self.initializing.push(id);
+        let mut required_components = RequiredComponents::default();
+        T::register_required_components(self, storages, &mut required_components);
+        self.components[id.index()].required_components = required_components;
+
+        // The components initialized by a cycle of requirements are missing the requirements of
+        // the components still being initialized, so their requirements are completed once the
+        // whole cycle is known.
+        if outermost {
+            for initialized_id in std::mem::take(&mut self.initializing) {
+                let required_components = self.collect_required_components(initialized_id);
+                self.components[initialized_id.index()].required_components = required_components;
+            }
+        }
+        id
+    }
+
+    /// Returns the components required by the component `id`, directly or transitively.
+    ///
+    /// The constructors of the components required directly take precedence over the ones of
+    /// the components required transitively.
+    fn collect_required_components(&self, id: ComponentId) -> RequiredComponents {
+        let mut visited = HashSet::new();
+        visited.insert(id);
+        let mut required_components = RequiredComponents::default();
+        let mut queue = VecDeque::from([id]);
+        while let Some(current_id) = queue.pop_front() {
+            for (required_id, constructor) in
+                &self.components[current_id.index()].required_components.0
+            {
+                if visited.insert(*required_id) {
+                    required_components
+                        .0
+                        .push((*required_id, constructor.clone()));
+                    queue.push_back(*required_id);
+                }
+            }
+        }
+        required_components
     }
 
     /// Initializes a component described by `descriptor`.
@@ -833,6 +927,132 @@ impl Components {
     }
 }
 
+/// The components required by a [`Component`], along with the constructors used to insert them
+/// when they are missing.
+///
+/// See the [required components](Component#required-components) section for more information.
+#[derive(Clone, Default)]
+pub struct RequiredComponents(pub(crate) Vec<(ComponentId, RequiredComponentConstructor)>);
+
+impl std::fmt::Debug for RequiredComponents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter_ids()).finish()
+    }
+}
+
+impl RequiredComponents {
+    /// Requires the component `C`, which is inserted with its [`Default`] value when missing.
+    ///
+    /// The components required by `C` are required as well.
+    pub fn register<C: Component + Default>(
+        &mut self,
+        components: &mut Components,
+        storages: &mut Storages,
+    ) {
+        self.register_with::<C>(components, storages, C::default);
+    }
+
+    /// Requires the component `C`, which is inserted with the value returned by `constructor`
+    /// when missing.
+    ///
+    /// The components required by `C` are required as well. If `C` is already required, the
+    /// existing constructor is kept.
+    pub fn register_with<C: Component>(
+        &mut self,
+        components: &mut Components,
+        storages: &mut Storages,
+        constructor: fn() -> C,
+    ) {
+        let component_id = components.init_component::<C>(storages);
+        if !self.contains(component_id) {
+            self.0.push((
+                component_id,
+                RequiredComponentConstructor::new(component_id, constructor),
+            ));
+        }
+
+        // SAFETY: `component_id` was just initialized.
+        let info = unsafe { components.get_info_unchecked(component_id) };
+        for (id, constructor) in &info.required_components.0 {
+            if !self.contains(*id) {
+                self.0.push((*id, constructor.clone()));
+            }
+        }
+    }
+
+    /// Returns `true` if the component with the given [`ComponentId`] is required.
+    pub fn contains(&self, id: ComponentId) -> bool {
+        self.0.iter().any(|(required_id, _)| *required_id == id)
+    }
+
+    /// Returns an iterator over the [`ComponentId`]s of the required components.
+    pub fn iter_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.0.iter().map(|(id, _)| *id)
+    }
+
+    /// Returns the number of required components.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no components are required.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+type RequiredComponentConstructorFn =
+    dyn Fn(&mut Table, &mut SparseSets, Tick, TableRow, Entity) + Send + Sync;
+
+/// Writes the value of a required component to the storage of an entity that is missing it.
+#[derive(Clone)]
+pub struct RequiredComponentConstructor(Arc<RequiredComponentConstructorFn>);
+
+impl RequiredComponentConstructor {
+    fn new<C: Component>(component_id: ComponentId, constructor: fn() -> C) -> Self {
+        Self(Arc::new(
+            move |table, sparse_sets, change_tick, table_row, entity| {
+                OwningPtr::make(constructor(), |ptr| match C::STORAGE_TYPE {
+                    StorageType::Table => {
+                        // SAFETY: `initialize` requires the table to contain the component.
+                        let column =
+                            unsafe { table.get_column_mut(component_id).debug_checked_unwrap() };
+                        // SAFETY: `initialize` requires `table_row` to be allocated and the
+                        // component to be missing, and `ptr` points to a `C`.
+                        unsafe { column.initialize(table_row, ptr, change_tick) };
+                    }
+                    StorageType::SparseSet => {
+                        // SAFETY: The sparse set was created when the component was initialized.
+                        let sparse_set =
+                            unsafe { sparse_sets.get_mut(component_id).debug_checked_unwrap() };
+                        // SAFETY: `ptr` points to a `C`.
+                        unsafe { sparse_set.insert(entity, ptr, change_tick) };
+                    }
+                });
+            },
+        ))
+    }
+
+    /// Writes the default value of the required component for `entity`.
+    ///
+    /// # Safety
+    ///
+    /// - `table` must be the table of `entity`, must contain a column for the component if it
+    ///   uses table storage, and `table_row` must be the row allocated for `entity`.
+    /// - `entity` must not have the component yet.
+    #[inline]
+    pub(crate) unsafe fn initialize(
+        &self,
+        table: &mut Table,
+        sparse_sets: &mut SparseSets,
+        change_tick: Tick,
+        table_row: TableRow,
+        entity: Entity,
+    ) {
+        (self.0)(table, sparse_sets, change_tick, table_row, entity);
+    }
+}
+
 /// A wrapper over a mutable [`Components`] reference that allows for state initialization.
 /// This can be obtained with [`World::component_initializer`].
 pub struct ComponentInitializer<'w> {
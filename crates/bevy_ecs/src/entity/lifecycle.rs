@@ -0,0 +1,190 @@
+use crate as bevy_ecs;
+use crate::{
+    component::ComponentId,
+    entity::Entity,
+    event::{Event, Events},
+    world::DeferredWorld,
+};
+
+/// An [`Event`] sent when an entity is spawned, listing the components it was spawned with.
+///
+/// This event is opt-in: it is only sent once the [`Events<EntitySpawned>`] resource exists,
+/// for example after calling `App::add_event::<EntitySpawned>()`.
+///
+/// Entities are reported by [`World::spawn`], [`World::spawn_empty`], [`World::spawn_batch`],
+/// [`World::get_or_spawn`], [`World::insert_or_spawn_batch`] and [`Commands::spawn`], with the
+/// components they have right after spawning. Components inserted later aren't reported, and
+/// neither are entities reserved with [`Commands::spawn_empty`].
+///
+/// [`World::spawn`]: crate::world::World::spawn
+/// [`World::spawn_empty`]: crate::world::World::spawn_empty
+/// [`World::spawn_batch`]: crate::world::World::spawn_batch
+/// [`World::get_or_spawn`]: crate::world::World::get_or_spawn
+/// [`World::insert_or_spawn_batch`]: crate::world::World::insert_or_spawn_batch
+/// [`Commands::spawn`]: crate::system::Commands::spawn
+/// [`Commands::spawn_empty`]: crate::system::Commands::spawn_empty
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct EntitySpawned {
+    /// The spawned entity.
+    pub entity: Entity,
+    /// The components of the entity when it was spawned.
+    pub components: Box<[ComponentId]>,
+}
+
+/// An [`Event`] sent when an entity is despawned, listing the components it had.
+///
+/// This event is opt-in: it is only sent once the [`Events<EntityDespawned>`] resource exists,
+/// for example after calling `App::add_event::<EntityDespawned>()`.
+///
+/// Every despawn is reported, whether it goes through [`World::despawn`],
+/// [`EntityWorldMut::despawn`] or [`EntityCommands::despawn`].
+///
+/// [`World::despawn`]: crate::world::World::despawn
+/// [`EntityWorldMut::despawn`]: crate::world::EntityWorldMut::despawn
+/// [`EntityCommands::despawn`]: crate::system::EntityCommands::despawn
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct EntityDespawned {
+    /// The despawned entity.
+    pub entity: Entity,
+    /// The components of the entity when it was despawned.
+    pub components: Box<[ComponentId]>,
+}
+
+/// Sends an [`EntitySpawned`] event if the event is enabled in `world`.
+#[inline]
+pub(crate) fn send_entity_spawned(
+    world: &mut DeferredWorld,
+    entity: Entity,
+    components: impl Iterator<Item = ComponentId>,
+) {
+    if let Some(mut events) = world.get_resource_mut::<Events<EntitySpawned>>() {
+        events.send(EntitySpawned {
+            entity,
+            components: components.collect(),
+        });
+    }
+}
+
+/// Sends an [`EntityDespawned`] event if the event is enabled in `world`.
+#[inline]
+pub(crate) fn send_entity_despawned(
+    world: &mut DeferredWorld,
+    entity: Entity,
+    components: impl Iterator<Item = ComponentId>,
+) {
+    if let Some(mut events) = world.get_resource_mut::<Events<EntityDespawned>>() {
+        events.send(EntityDespawned {
+            entity,
+            components: components.collect(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::Component,
+        system::{Commands, RunSystemOnce},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    fn drain<E: Event>(world: &mut World) -> Vec<E> {
+        world.resource_mut::<Events<E>>().drain().collect()
+    }
+
+    #[test]
+    fn spawn_and_despawn_events() {
+        let mut world = World::new();
+        world.init_resource::<Events<EntitySpawned>>();
+        world.init_resource::<Events<EntityDespawned>>();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        let e1 = world.spawn((A, B)).id();
+        let e2 = world.spawn_empty().id();
+        let batch = world.spawn_batch([A, A]).collect::<Vec<_>>();
+        let e3 = world.run_system_once(|mut commands: Commands| commands.spawn(B).id());
+
+        let spawned = drain::<EntitySpawned>(&mut world);
+        assert_eq!(spawned.len(), 5);
+        assert_eq!(spawned[0].entity, e1);
+        assert_eq!(&*spawned[0].components, &[a, b]);
+        assert_eq!(spawned[1].entity, e2);
+        assert!(spawned[1].components.is_empty());
+        assert_eq!(spawned[2].entity, batch[0]);
+        assert_eq!(spawned[3].entity, batch[1]);
+        assert_eq!(&*spawned[3].components, &[a]);
+        assert_eq!(spawned[4].entity, e3);
+        assert_eq!(&*spawned[4].components, &[b]);
+
+        world.despawn(e1);
+        world.run_system_once(move |mut commands: Commands| commands.entity(e3).despawn());
+
+        let despawned = drain::<EntityDespawned>(&mut world);
+        assert_eq!(
+            despawned,
+            vec![
+                EntityDespawned {
+                    entity: e1,
+                    components: Box::new([a, b]),
+                },
+                EntityDespawned {
+                    entity: e3,
+                    components: Box::new([b]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn entities_spawned_at_a_given_id_are_reported() {
+        let mut world = World::new();
+        world.init_resource::<Events<EntitySpawned>>();
+        let a = world.init_component::<A>();
+
+        let existing = world.spawn(A).id();
+        let unused = [10, 11, 12].map(Entity::from_raw);
+        drain::<EntitySpawned>(&mut world);
+
+        // Existing entities aren't spawned again
+        world.get_or_spawn(existing);
+        world.get_or_spawn(unused[0]);
+        world
+            .insert_or_spawn_batch([(existing, A), (unused[1], A), (unused[2], A)])
+            .unwrap();
+
+        assert_eq!(
+            drain::<EntitySpawned>(&mut world),
+            vec![
+                EntitySpawned {
+                    entity: unused[0],
+                    components: Box::new([]),
+                },
+                EntitySpawned {
+                    entity: unused[1],
+                    components: Box::new([a]),
+                },
+                EntitySpawned {
+                    entity: unused[2],
+                    components: Box::new([a]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn events_are_opt_in() {
+        let mut world = World::new();
+        let entity = world.spawn(A).id();
+        world.despawn(entity);
+        assert!(!world.contains_resource::<Events<EntitySpawned>>());
+        assert!(!world.contains_resource::<Events<EntityDespawned>>());
+    }
+}
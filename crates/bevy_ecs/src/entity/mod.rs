@@ -45,6 +45,10 @@ pub use map_entities::*;
 mod hash;
 pub use hash::*;
 
+mod lifecycle;
+pub(crate) use lifecycle::{send_entity_despawned, send_entity_spawned};
+pub use lifecycle::{EntityDespawned, EntitySpawned};
+
 use bevy_utils::tracing::warn;
 
 use crate::{
@@ -4,7 +4,7 @@ use crate as bevy_ecs;
 use crate::batching::BatchingStrategy;
 use crate::change_detection::MutUntyped;
 use crate::{
-    change_detection::{DetectChangesMut, Mut},
+    change_detection::{DetectChanges, DetectChangesMut, Mut},
     component::{ComponentId, Tick},
     system::{Local, Res, ResMut, Resource, SystemParam},
     world::World,
@@ -1110,6 +1110,54 @@ impl<E: Event> ExactSizeIterator for SendBatchIds<E> {
     }
 }
 
+/// An [`Event`] sent when the resource `R` is added or changed.
+///
+/// These events are sent by [`send_resource_changed_events`] and
+/// [`send_resource_changed_events_with_old`], which compare the change ticks of the resource: a
+/// mutable access counts as a change, even if the value stays the same. Since they are regular
+/// events, systems in any schedule can react to a change without keeping a copy of the resource
+/// to compare with.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ResourceChanged<R: Resource> {
+    /// The value of the resource before the change.
+    ///
+    /// This is only known for events sent by [`send_resource_changed_events_with_old`], and is
+    /// `None` when the resource was added.
+    pub old: Option<R>,
+}
+
+/// Sends a [`ResourceChanged`] event, without the old value, when the resource `R` is added or
+/// changed.
+pub fn send_resource_changed_events<R: Resource>(
+    resource: Option<Res<R>>,
+    mut events: EventWriter<ResourceChanged<R>>,
+) {
+    if resource.is_some_and(|resource| resource.is_changed()) {
+        events.send(ResourceChanged { old: None });
+    }
+}
+
+/// Sends a [`ResourceChanged`] event with the previous value of the resource `R` when it is added
+/// or changed.
+///
+/// The resource is cloned every time it changes to be able to send its old value on the next
+/// change.
+pub fn send_resource_changed_events_with_old<R: Resource + Clone>(
+    resource: Option<Res<R>>,
+    mut previous: Local<Option<R>>,
+    mut events: EventWriter<ResourceChanged<R>>,
+) {
+    match resource {
+        Some(resource) if resource.is_changed() => {
+            let old = previous.replace(resource.clone());
+            events.send(ResourceChanged { old });
+        }
+        Some(_) => {}
+        // The resource is added again with no old value
+        None => *previous = None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::system::assert_is_read_only_system;
@@ -1536,4 +1584,45 @@ mod tests {
         });
         schedule.run(&mut world);
     }
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct TestResource(u32);
+
+    #[test]
+    fn resource_changed_events() {
+        use crate::prelude::*;
+
+        let mut world = World::new();
+        world.init_resource::<Events<ResourceChanged<TestResource>>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(send_resource_changed_events_with_old::<TestResource>);
+
+        let drain = |world: &mut World| {
+            world
+                .resource_mut::<Events<ResourceChanged<TestResource>>>()
+                .drain()
+                .map(|event| event.old)
+                .collect::<Vec<_>>()
+        };
+
+        schedule.run(&mut world);
+        assert!(drain(&mut world).is_empty());
+
+        world.insert_resource(TestResource(1));
+        schedule.run(&mut world);
+        assert_eq!(drain(&mut world), vec![None]);
+
+        schedule.run(&mut world);
+        assert!(drain(&mut world).is_empty());
+
+        world.resource_mut::<TestResource>().0 = 2;
+        schedule.run(&mut world);
+        assert_eq!(drain(&mut world), vec![Some(TestResource(1))]);
+
+        world.remove_resource::<TestResource>();
+        schedule.run(&mut world);
+        world.insert_resource(TestResource(3));
+        schedule.run(&mut world);
+        assert_eq!(drain(&mut world), vec![None]);
+    }
 }
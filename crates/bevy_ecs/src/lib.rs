@@ -44,8 +44,8 @@ pub mod prelude {
         bundle::Bundle,
         change_detection::{DetectChanges, DetectChangesMut, Mut, Ref},
         component::Component,
-        entity::{Entity, EntityMapper},
-        event::{Event, EventReader, EventWriter, Events},
+        entity::{Entity, EntityDespawned, EntityMapper, EntitySpawned},
+        event::{Event, EventReader, EventWriter, Events, ResourceChanged},
         query::{Added, AnyOf, Changed, Has, Or, QueryBuilder, QueryState, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
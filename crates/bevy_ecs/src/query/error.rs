@@ -23,12 +23,24 @@ pub enum QueryEntityError {
 
 /// An error that occurs when evaluating a [`Query`](crate::system::Query) or [`QueryState`](crate::query::QueryState) as a single expected result via
 /// [`get_single`](crate::system::Query::get_single) or [`get_single_mut`](crate::system::Query::get_single_mut).
-#[derive(Debug, Error)]
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
 pub enum QuerySingleError {
     /// No entity fits the query.
     #[error("No entities fit the query {0}")]
     NoEntities(&'static str),
     /// Multiple entities fit the query.
-    #[error("Multiple entities fit the query {0}")]
-    MultipleEntities(&'static str),
+    #[error("{count} entities fit the query {query}, but exactly one was expected. Candidates: {entities:?}")]
+    MultipleEntities {
+        /// The type name of the query.
+        query: &'static str,
+        /// The number of entities fitting the query.
+        count: usize,
+        /// The first entities fitting the query, at most [`QuerySingleError::MAX_CANDIDATES`].
+        entities: Vec<Entity>,
+    },
+}
+
+impl QuerySingleError {
+    /// The maximum number of candidate entities stored in [`QuerySingleError::MultipleEntities`].
+    pub const MAX_CANDIDATES: usize = 8;
 }
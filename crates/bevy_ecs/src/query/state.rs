@@ -1531,7 +1531,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     pub fn single<'w>(&mut self, world: &'w World) -> ROQueryItem<'w, D> {
         match self.get_single(world) {
             Ok(items) => items,
-            Err(error) => panic!("Cannot get single mutable query result: {error}"),
+            Err(error) => panic!("Cannot get single query result: {error}"),
         }
     }
 
@@ -1573,7 +1573,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         // SAFETY: query has unique world access
         match self.get_single_mut(world) {
             Ok(items) => items,
-            Err(error) => panic!("Cannot get single query result: {error}"),
+            Err(error) => panic!("Cannot get single mutable query result: {error}"),
         }
     }
 
@@ -1641,11 +1641,43 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         let extra = query.next().is_some();
 
         match (first, extra) {
-            (Some(r), false) => Ok(r),
-            (None, _) => Err(QuerySingleError::NoEntities(std::any::type_name::<Self>())),
-            (Some(_), _) => Err(QuerySingleError::MultipleEntities(std::any::type_name::<
-                Self,
-            >())),
+            (Some(r), false) => return Ok(r),
+            (None, _) => return Err(QuerySingleError::NoEntities(std::any::type_name::<Self>())),
+            (Some(_), true) => {}
+        }
+        // SAFETY: The caller ensures that `world` can be accessed by this query.
+        Err(unsafe { self.multiple_entities_error(world, last_run, this_run) })
+    }
+
+    /// Returns the [`QuerySingleError::MultipleEntities`] error listing the entities matching
+    /// the query.
+    ///
+    /// # Safety
+    ///
+    /// `world` must have permission to read the data this query filters on, and must be the
+    /// `World` this state was created with.
+    pub(crate) unsafe fn multiple_entities_error(
+        &self,
+        world: UnsafeWorldCell,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> QuerySingleError {
+        // Only fetch the entities, so that this doesn't alias any item returned by the query.
+        let entities_state = self.transmute_filtered::<Entity, F>(world.components());
+        let mut count = 0;
+        let mut entities = Vec::new();
+        // SAFETY: `Entity` is read-only and the filter is the one of this query, so the caller
+        // ensures that `world` can be accessed.
+        for entity in unsafe { entities_state.iter_unchecked_manual(world, last_run, this_run) } {
+            count += 1;
+            if entities.len() < QuerySingleError::MAX_CANDIDATES {
+                entities.push(entity);
+            }
+        }
+        QuerySingleError::MultipleEntities {
+            query: std::any::type_name::<Self>(),
+            count,
+            entities,
         }
     }
 }
@@ -1,9 +1,12 @@
+use std::any::TypeId;
+
 use crate::{
     component::Component,
-    entity::{Entity, EntityHashMap, MapEntities, SceneEntityMapper},
+    entity::{Entity, EntityHashMap, EntityMapper, MapEntities, SceneEntityMapper},
     world::World,
 };
-use bevy_reflect::FromType;
+use bevy_reflect::{FromType, Reflect, ReflectMut, TypeInfo, TypeRegistry, VariantInfo};
+use bevy_utils::HashSet;
 
 /// For a specific type of component, this maps any fields with values of type [`Entity`] to a new world.
 /// Since a given `Entity` ID is only valid for the world it came from, when performing deserialization
@@ -73,3 +76,202 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
         }
     }
 }
+
+/// Maps every [`Entity`] contained in a reflected `value`, including the ones nested in
+/// structs, enums, lists, arrays, and the keys and values of maps.
+///
+/// This is used to remap the entities of reflected components that do not register
+/// [`ReflectMapEntities`], for example when spawning a scene.
+pub fn map_reflected_entities(value: &mut dyn Reflect, entity_mapper: &mut dyn EntityMapper) {
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for i in 0..value.field_len() {
+                map_reflected_entities(value.field_at_mut(i).unwrap(), entity_mapper);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for i in 0..value.field_len() {
+                map_reflected_entities(value.field_mut(i).unwrap(), entity_mapper);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for i in 0..value.field_len() {
+                map_reflected_entities(value.field_mut(i).unwrap(), entity_mapper);
+            }
+        }
+        ReflectMut::List(value) => {
+            for i in 0..value.len() {
+                map_reflected_entities(value.get_mut(i).unwrap(), entity_mapper);
+            }
+        }
+        ReflectMut::Array(value) => {
+            for i in 0..value.len() {
+                map_reflected_entities(value.get_mut(i).unwrap(), entity_mapper);
+            }
+        }
+        ReflectMut::Map(value) => {
+            let keys_contain_entities = value
+                .iter()
+                .any(|(key, _)| reflected_contains_entities(key));
+            if keys_contain_entities {
+                // Keys can't be mutated in place, so the map is rebuilt with the mapped keys.
+                // Removing every entry first prevents a mapped key from replacing an entry
+                // that is yet to be mapped.
+                let keys: Vec<_> = value.iter().map(|(key, _)| key.clone_value()).collect();
+                let entries: Vec<_> = keys
+                    .into_iter()
+                    .filter_map(|key| {
+                        let entry = value.remove(key.as_reflect())?;
+                        Some((key, entry))
+                    })
+                    .collect();
+                for (mut key, mut entry) in entries {
+                    map_reflected_entities(key.as_reflect_mut(), entity_mapper);
+                    map_reflected_entities(entry.as_reflect_mut(), entity_mapper);
+                    value.insert_boxed(key, entry);
+                }
+            } else {
+                for i in 0..value.len() {
+                    map_reflected_entities(value.get_at_mut(i).unwrap().1, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for i in 0..value.field_len() {
+                map_reflected_entities(value.field_at_mut(i).unwrap(), entity_mapper);
+            }
+        }
+        ReflectMut::Value(value) => {
+            if let Some(entity) = value.downcast_mut::<Entity>() {
+                *entity = entity_mapper.map_entity(*entity);
+            }
+        }
+    }
+}
+
+fn reflected_contains_entities(value: &dyn Reflect) -> bool {
+    use bevy_reflect::ReflectRef;
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value.iter_fields().any(reflected_contains_entities),
+        ReflectRef::TupleStruct(value) => value.iter_fields().any(reflected_contains_entities),
+        ReflectRef::Tuple(value) => value.iter_fields().any(reflected_contains_entities),
+        ReflectRef::List(value) => value.iter().any(reflected_contains_entities),
+        ReflectRef::Array(value) => value.iter().any(reflected_contains_entities),
+        ReflectRef::Map(value) => value.iter().any(|(key, value)| {
+            reflected_contains_entities(key) || reflected_contains_entities(value)
+        }),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .any(|field| reflected_contains_entities(field.value())),
+        ReflectRef::Value(value) => value.is::<Entity>(),
+    }
+}
+
+/// Returns `true` if values of the type with the given [`TypeId`] may contain an [`Entity`],
+/// based on the [`TypeInfo`] of the type and of its fields.
+///
+/// Types missing from the `registry` are assumed to possibly contain entities.
+pub fn type_may_contain_entities(registry: &TypeRegistry, type_id: TypeId) -> bool {
+    fn visit(registry: &TypeRegistry, type_id: TypeId, visited: &mut HashSet<TypeId>) -> bool {
+        if type_id == TypeId::of::<Entity>() {
+            return true;
+        }
+        // Recursive types only need to be inspected once.
+        if !visited.insert(type_id) {
+            return false;
+        }
+        let Some(type_info) = registry.get_type_info(type_id) else {
+            return true;
+        };
+        let mut visit = |type_id| visit(registry, type_id, visited);
+        match type_info {
+            TypeInfo::Struct(info) => info.iter().any(|field| visit(field.type_id())),
+            TypeInfo::TupleStruct(info) => info.iter().any(|field| visit(field.type_id())),
+            TypeInfo::Tuple(info) => info.iter().any(|field| visit(field.type_id())),
+            TypeInfo::List(info) => visit(info.item_type_id()),
+            TypeInfo::Array(info) => visit(info.item_type_id()),
+            TypeInfo::Map(info) => visit(info.key_type_id()) || visit(info.value_type_id()),
+            TypeInfo::Enum(info) => info.iter().any(|variant| match variant {
+                VariantInfo::Struct(info) => info.iter().any(|field| visit(field.type_id())),
+                VariantInfo::Tuple(info) => info.iter().any(|field| visit(field.type_id())),
+                VariantInfo::Unit(_) => false,
+            }),
+            TypeInfo::Value(_) => false,
+        }
+    }
+
+    visit(registry, type_id, &mut HashSet::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::{entity::EntityHashMap, reflect::AppTypeRegistry};
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Default)]
+    struct Nested {
+        list: Vec<Entity>,
+        by_entity: HashMap<Entity, Option<Entity>>,
+    }
+
+    #[derive(crate::component::Component, Reflect, Default)]
+    struct Holder {
+        nested: Nested,
+        count: u32,
+    }
+
+    #[derive(Reflect)]
+    struct NoEntities {
+        values: Vec<f32>,
+    }
+
+    #[test]
+    fn map_nested_entities() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let mapped_a = world.spawn_empty().id();
+        let mapped_b = world.spawn_empty().id();
+
+        let mut holder = Holder {
+            nested: Nested {
+                list: vec![a, b],
+                by_entity: [(a, Some(b)), (b, None)].into_iter().collect(),
+            },
+            count: 3,
+        };
+
+        let mut entity_map = EntityHashMap::default();
+        entity_map.insert(a, mapped_a);
+        entity_map.insert(b, mapped_b);
+        SceneEntityMapper::world_scope(&mut entity_map, &mut world, |_, mapper| {
+            map_reflected_entities(&mut holder, mapper);
+        });
+
+        assert_eq!(holder.nested.list, vec![mapped_a, mapped_b]);
+        assert_eq!(holder.nested.by_entity.len(), 2);
+        assert_eq!(holder.nested.by_entity[&mapped_a], Some(mapped_b));
+        assert_eq!(holder.nested.by_entity[&mapped_b], None);
+        assert_eq!(holder.count, 3);
+    }
+
+    #[test]
+    fn types_containing_entities() {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Holder>();
+            registry.register::<NoEntities>();
+        }
+        let registry = registry.read();
+
+        assert!(type_may_contain_entities(&registry, TypeId::of::<Holder>()));
+        assert!(!type_may_contain_entities(
+            &registry,
+            TypeId::of::<NoEntities>()
+        ));
+    }
+}
@@ -13,13 +13,17 @@ mod component;
 mod entity_commands;
 mod from_world;
 mod map_entities;
+mod replication;
 mod resource;
 
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
-pub use map_entities::ReflectMapEntities;
+pub use map_entities::{map_reflected_entities, type_may_contain_entities, ReflectMapEntities};
+pub use replication::{
+    interpolate_reflect, InterpolateFn, ReplicatedComponent, ReplicationFlags, ReplicationRegistry,
+};
 pub use resource::{ReflectResource, ReflectResourceFns};
 
 /// A [`Resource`] storing [`TypeRegistry`] for
@@ -0,0 +1,578 @@
+//! Definitions for the [`ReplicationRegistry`].
+//!
+//! The registry maps a [`ComponentId`] to the functions needed to read a component
+//! out of an entity, write it back into another world and, optionally, blend between
+//! two received values. It does not pick a wire format: values are exchanged as
+//! `Box<dyn Reflect>`, which networking crates can then encode with the
+//! serializers of `bevy_reflect::serde` or any format of their choosing.
+
+use std::any::TypeId;
+
+use bevy_reflect::{
+    FromReflect, Reflect, ReflectMut, ReflectRef, TypeInfo, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::HashMap;
+
+use super::ReflectComponent;
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    system::Resource,
+    world::{EntityRef, EntityWorldMut, World},
+};
+
+bitflags::bitflags! {
+    /// Controls which parts of a component's lifetime are replicated.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct ReplicationFlags: u8 {
+        /// The component is sent when the entity is first replicated.
+        const SPAWN       = (1 << 0);
+        /// Changes to the component are sent after the entity was spawned.
+        const UPDATE      = (1 << 1);
+        /// Received values should be blended using the registered interpolation function
+        /// instead of being applied as-is.
+        const INTERPOLATE = (1 << 2);
+    }
+}
+
+impl Default for ReplicationFlags {
+    fn default() -> Self {
+        ReplicationFlags::SPAWN | ReplicationFlags::UPDATE
+    }
+}
+
+/// Blends between two reflected values of the same component type.
+///
+/// The third argument is the interpolation factor, where `0.0` returns the first value
+/// and `1.0` the second one. Returns `None` if either value is not of the expected type.
+pub type InterpolateFn = fn(&dyn Reflect, &dyn Reflect, f32) -> Option<Box<dyn Reflect>>;
+
+/// Blends between two reflected values by interpolating each of their `f32` and `f64` fields.
+///
+/// This is derived from the reflected shape of the values, and is the default [`InterpolateFn`]
+/// of the components of a [`ReplicationRegistry`]. The other fields, as well as the fields whose
+/// shape differs between the two values (like enum variants or list lengths), take the value
+/// of `to`. Returns `None` if the values do not represent the same type.
+///
+/// The returned value may be a dynamic type, like the values received from the network.
+pub fn interpolate_reflect(
+    from: &dyn Reflect,
+    to: &dyn Reflect,
+    t: f32,
+) -> Option<Box<dyn Reflect>> {
+    let represented_type =
+        |value: &dyn Reflect| value.get_represented_type_info().map(TypeInfo::type_id);
+    if represented_type(from) != represented_type(to) {
+        return None;
+    }
+    let mut value = to.clone_value();
+    interpolate_fields(value.as_mut(), from, t);
+    Some(value)
+}
+
+/// Interpolates the floating-point fields of `value` from the matching fields of `from`.
+fn interpolate_fields(value: &mut dyn Reflect, from: &dyn Reflect, t: f32) {
+    if let Some(value) = value.downcast_mut::<f32>() {
+        if let Some(from) = from.downcast_ref::<f32>() {
+            *value = from + (*value - from) * t;
+        }
+        return;
+    }
+    if let Some(value) = value.downcast_mut::<f64>() {
+        if let Some(from) = from.downcast_ref::<f64>() {
+            *value = from + (*value - from) * t as f64;
+        }
+        return;
+    }
+    match (value.reflect_mut(), from.reflect_ref()) {
+        (ReflectMut::Struct(value), ReflectRef::Struct(from)) => {
+            for index in 0..value.field_len() {
+                let Some(from_field) = value.name_at(index).and_then(|name| from.field(name))
+                else {
+                    continue;
+                };
+                interpolate_fields(value.field_at_mut(index).unwrap(), from_field, t);
+            }
+        }
+        (ReflectMut::TupleStruct(value), ReflectRef::TupleStruct(from)) => {
+            for index in 0..value.field_len().min(from.field_len()) {
+                interpolate_fields(
+                    value.field_mut(index).unwrap(),
+                    from.field(index).unwrap(),
+                    t,
+                );
+            }
+        }
+        (ReflectMut::Tuple(value), ReflectRef::Tuple(from)) => {
+            for index in 0..value.field_len().min(from.field_len()) {
+                interpolate_fields(
+                    value.field_mut(index).unwrap(),
+                    from.field(index).unwrap(),
+                    t,
+                );
+            }
+        }
+        (ReflectMut::Array(value), ReflectRef::Array(from)) if value.len() == from.len() => {
+            for index in 0..value.len() {
+                interpolate_fields(value.get_mut(index).unwrap(), from.get(index).unwrap(), t);
+            }
+        }
+        (ReflectMut::List(value), ReflectRef::List(from)) if value.len() == from.len() => {
+            for index in 0..value.len() {
+                interpolate_fields(value.get_mut(index).unwrap(), from.get(index).unwrap(), t);
+            }
+        }
+        (ReflectMut::Enum(value), ReflectRef::Enum(from))
+            if value.variant_name() == from.variant_name() =>
+        {
+            for index in 0..value.field_len().min(from.field_len()) {
+                interpolate_fields(
+                    value.field_at_mut(index).unwrap(),
+                    from.field_at(index).unwrap(),
+                    t,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The default [`InterpolateFn`] of a component registered with its type, which converts the
+/// result of [`interpolate_reflect`] back to the concrete component type.
+fn interpolate_component<C: Component + FromReflect>(
+    from: &dyn Reflect,
+    to: &dyn Reflect,
+    t: f32,
+) -> Option<Box<dyn Reflect>> {
+    let value = interpolate_reflect(from, to, t)?;
+    C::from_reflect(value.as_ref()).map(|value| Box::new(value) as Box<dyn Reflect>)
+}
+
+/// The replication data stored for a single component type in a [`ReplicationRegistry`].
+#[derive(Clone)]
+pub struct ReplicatedComponent {
+    component_id: ComponentId,
+    type_id: TypeId,
+    flags: ReplicationFlags,
+    reflect_component: ReflectComponent,
+    interpolate: InterpolateFn,
+}
+
+impl ReplicatedComponent {
+    /// The [`ComponentId`] of the replicated component.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// The [`TypeId`] of the replicated component.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The [`ReplicationFlags`] of the replicated component.
+    pub fn flags(&self) -> ReplicationFlags {
+        self.flags
+    }
+
+    /// The [`ReflectComponent`] used to read and write the component.
+    pub fn reflect_component(&self) -> &ReflectComponent {
+        &self.reflect_component
+    }
+
+    /// Returns an owned, reflected copy of the component on `entity`,
+    /// or `None` if the entity does not have it.
+    pub fn serialize(&self, entity: EntityRef) -> Option<Box<dyn Reflect>> {
+        self.reflect_component
+            .reflect(entity)
+            .map(Reflect::clone_value)
+    }
+
+    /// Applies a reflected value to `entity`, inserting the component if it is missing.
+    pub fn deserialize(
+        &self,
+        entity: &mut EntityWorldMut,
+        value: &dyn Reflect,
+        registry: &TypeRegistry,
+    ) {
+        self.reflect_component
+            .apply_or_insert(entity, value, registry);
+    }
+
+    /// Blends between `from` and `to` using the [`InterpolateFn`] of the component, which is
+    /// [`interpolate_reflect`] unless another one was set with
+    /// [`ReplicationRegistry::set_interpolation`].
+    ///
+    /// Returns `None` if the interpolation failed.
+    pub fn interpolate(
+        &self,
+        from: &dyn Reflect,
+        to: &dyn Reflect,
+        t: f32,
+    ) -> Option<Box<dyn Reflect>> {
+        (self.interpolate)(from, to, t)
+    }
+}
+
+/// A [`Resource`] listing the components that should be replicated across the network,
+/// along with the functions needed to do so.
+///
+/// This is a foundation for third-party networking crates: they can iterate the registry
+/// to snapshot entities and apply received values, without each game having to write
+/// per-component glue code.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{ReplicationFlags, ReplicationRegistry};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// let mut registry = ReplicationRegistry::default();
+/// registry.register::<Health>(&mut world, ReplicationFlags::default());
+///
+/// let entity = world.spawn(Health(10)).id();
+/// let snapshot = registry.snapshot(world.entity(entity), ReplicationFlags::SPAWN);
+/// assert_eq!(snapshot.len(), 1);
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct ReplicationRegistry {
+    components: Vec<ReplicatedComponent>,
+    indices: HashMap<ComponentId, usize>,
+}
+
+impl ReplicationRegistry {
+    /// Registers the component `C` for replication with the given flags,
+    /// initializing it in the `world` if needed.
+    ///
+    /// Received values are interpolated with [`interpolate_reflect`], converted back to `C`.
+    /// Registering a component twice overwrites its flags, keeping its interpolation function.
+    pub fn register<C: Component + Reflect + FromReflect>(
+        &mut self,
+        world: &mut World,
+        flags: ReplicationFlags,
+    ) -> ComponentId {
+        let component_id = world.init_component::<C>();
+        self.insert(
+            component_id,
+            TypeId::of::<C>(),
+            flags,
+            ReflectComponent::new(super::ReflectComponentFns::new::<C>()),
+            interpolate_component::<C>,
+        );
+        component_id
+    }
+
+    /// Registers a component for replication from its [`TypeRegistration`].
+    ///
+    /// Received values are interpolated with [`interpolate_reflect`].
+    /// Returns `None` if the type has no [`ReflectComponent`] type data,
+    /// or if it was never initialized as a component in the `world`.
+    pub fn register_reflected(
+        &mut self,
+        world: &World,
+        registration: &TypeRegistration,
+        flags: ReplicationFlags,
+    ) -> Option<ComponentId> {
+        let reflect_component = registration.data::<ReflectComponent>()?.clone();
+        let type_id = registration.type_id();
+        let component_id = world.components().get_id(type_id)?;
+        self.insert(
+            component_id,
+            type_id,
+            flags,
+            reflect_component,
+            interpolate_reflect,
+        );
+        Some(component_id)
+    }
+
+    /// Registers every type of the [`TypeRegistry`] that has [`ReflectComponent`] type data
+    /// and is known to the `world`, with the given flags.
+    ///
+    /// Types that are already registered are left untouched.
+    pub fn register_all_reflected(
+        &mut self,
+        world: &World,
+        registry: &TypeRegistry,
+        flags: ReplicationFlags,
+    ) {
+        for registration in registry.iter() {
+            let Some(component_id) = world.components().get_id(registration.type_id()) else {
+                continue;
+            };
+            if self.indices.contains_key(&component_id) {
+                continue;
+            }
+            self.register_reflected(world, registration, flags);
+        }
+    }
+
+    /// Overrides the function used to blend between received values of the component.
+    ///
+    /// Does nothing if the component is not registered.
+    pub fn set_interpolation(&mut self, component_id: ComponentId, interpolate: InterpolateFn) {
+        if let Some(component) = self.get_mut(component_id) {
+            component.interpolate = interpolate;
+        }
+    }
+
+    /// Sets the [`ReplicationFlags`] of a registered component.
+    ///
+    /// Does nothing if the component is not registered.
+    pub fn set_flags(&mut self, component_id: ComponentId, flags: ReplicationFlags) {
+        if let Some(component) = self.get_mut(component_id) {
+            component.flags = flags;
+        }
+    }
+
+    /// Removes a component from the registry, returning its replication data.
+    pub fn unregister(&mut self, component_id: ComponentId) -> Option<ReplicatedComponent> {
+        let index = self.indices.remove(&component_id)?;
+        let removed = self.components.swap_remove(index);
+        if let Some(moved) = self.components.get(index) {
+            self.indices.insert(moved.component_id, index);
+        }
+        Some(removed)
+    }
+
+    /// Returns the replication data of a component, if it is registered.
+    pub fn get(&self, component_id: ComponentId) -> Option<&ReplicatedComponent> {
+        self.indices
+            .get(&component_id)
+            .map(|&index| &self.components[index])
+    }
+
+    fn get_mut(&mut self, component_id: ComponentId) -> Option<&mut ReplicatedComponent> {
+        self.indices
+            .get(&component_id)
+            .map(|&index| &mut self.components[index])
+    }
+
+    /// Returns `true` if the component is registered for replication.
+    pub fn contains(&self, component_id: ComponentId) -> bool {
+        self.indices.contains_key(&component_id)
+    }
+
+    /// Iterates over all the registered components, in registration order
+    /// (until a component is unregistered).
+    pub fn iter(&self) -> impl Iterator<Item = &ReplicatedComponent> {
+        self.components.iter()
+    }
+
+    /// Collects the reflected values of every registered component present on `entity`
+    /// whose flags intersect `filter`.
+    pub fn snapshot(
+        &self,
+        entity: EntityRef,
+        filter: ReplicationFlags,
+    ) -> Vec<(ComponentId, Box<dyn Reflect>)> {
+        self.components
+            .iter()
+            .filter(|component| component.flags.intersects(filter))
+            .filter_map(|component| {
+                component
+                    .serialize(entity)
+                    .map(|value| (component.component_id, value))
+            })
+            .collect()
+    }
+
+    /// Applies a reflected value received for `component_id` to `entity`.
+    ///
+    /// Returns `false` if the component is not registered.
+    pub fn apply(
+        &self,
+        entity: &mut EntityWorldMut,
+        component_id: ComponentId,
+        value: &dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> bool {
+        let Some(component) = self.get(component_id) else {
+            return false;
+        };
+        component.deserialize(entity, value, registry);
+        true
+    }
+
+    fn insert(
+        &mut self,
+        component_id: ComponentId,
+        type_id: TypeId,
+        flags: ReplicationFlags,
+        reflect_component: ReflectComponent,
+        interpolate: InterpolateFn,
+    ) {
+        if let Some(existing) = self.get_mut(component_id) {
+            existing.flags = flags;
+            existing.reflect_component = reflect_component;
+            return;
+        }
+        self.indices.insert(component_id, self.components.len());
+        self.components.push(ReplicatedComponent {
+            component_id,
+            type_id,
+            flags,
+            reflect_component,
+            interpolate,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interpolate_reflect, ReplicationFlags, ReplicationRegistry};
+    use crate::prelude::{AppTypeRegistry, ReflectComponent};
+    use crate::{self as bevy_ecs, component::Component, world::World};
+    use bevy_reflect::{FromReflect, Reflect};
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Position(f32);
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[test]
+    fn snapshot_and_apply() {
+        let mut source = World::new();
+        let mut registry = ReplicationRegistry::default();
+        let position = registry.register::<Position>(&mut source, ReplicationFlags::default());
+        registry.register::<Name>(&mut source, ReplicationFlags::SPAWN);
+
+        let entity = source.spawn((Position(1.0), Name("a".into()))).id();
+
+        let updates = registry.snapshot(source.entity(entity), ReplicationFlags::UPDATE);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, position);
+
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Position>();
+
+        let mut destination = World::new();
+        let mut target = destination.spawn_empty();
+        for (component_id, value) in &updates {
+            assert!(registry.apply(
+                &mut target,
+                *component_id,
+                value.as_ref(),
+                &type_registry.read()
+            ));
+        }
+        assert_eq!(target.get::<Position>(), Some(&Position(1.0)));
+        assert!(target.get::<Name>().is_none());
+    }
+
+    #[test]
+    fn interpolation() {
+        let mut world = World::new();
+        let mut registry = ReplicationRegistry::default();
+        let position = registry.register::<Position>(
+            &mut world,
+            ReplicationFlags::default() | ReplicationFlags::INTERPOLATE,
+        );
+        let blended = registry
+            .get(position)
+            .unwrap()
+            .interpolate(&Position(0.0), &Position(2.0), 0.5)
+            .unwrap();
+        assert_eq!(blended.downcast_ref::<Position>(), Some(&Position(1.0)));
+        assert!(registry
+            .get(position)
+            .unwrap()
+            .interpolate(&Position(0.0), &Name("a".into()), 0.5)
+            .is_none());
+
+        registry.set_interpolation(position, |_, to, _| Some(to.clone_value()));
+        let blended = registry
+            .get(position)
+            .unwrap()
+            .interpolate(&Position(0.0), &Position(2.0), 0.5)
+            .unwrap();
+        assert_eq!(
+            Position::from_reflect(blended.as_ref()),
+            Some(Position(2.0))
+        );
+    }
+
+    #[derive(Reflect, PartialEq, Debug)]
+    enum Motion {
+        Idle,
+        Moving { speed: f64, steps: Vec<f32> },
+    }
+
+    #[derive(Reflect, PartialEq, Debug)]
+    struct Body {
+        motion: Motion,
+        target: (f32, u32),
+        label: String,
+    }
+
+    #[test]
+    fn interpolate_reflected_fields() {
+        let from = Body {
+            motion: Motion::Moving {
+                speed: 1.0,
+                steps: vec![0.0, 4.0],
+            },
+            target: (0.0, 1),
+            label: "from".into(),
+        };
+        let to = Body {
+            motion: Motion::Moving {
+                speed: 3.0,
+                steps: vec![2.0, 8.0],
+            },
+            target: (4.0, 2),
+            label: "to".into(),
+        };
+        let blended = interpolate_reflect(&from, &to, 0.25).unwrap();
+        assert_eq!(
+            Body::from_reflect(blended.as_ref()),
+            Some(Body {
+                motion: Motion::Moving {
+                    speed: 1.5,
+                    steps: vec![0.5, 5.0],
+                },
+                target: (1.0, 2),
+                label: "to".into(),
+            })
+        );
+
+        // Fields whose shape differs take the value of `to`
+        let idle = Body {
+            motion: Motion::Idle,
+            ..to
+        };
+        let blended = interpolate_reflect(&from, &idle, 0.25).unwrap();
+        assert_eq!(
+            Body::from_reflect(blended.as_ref()).map(|body| body.motion),
+            Some(Motion::Idle)
+        );
+    }
+
+    #[test]
+    fn register_all_reflected() {
+        let mut world = World::new();
+        let position = world.init_component::<Position>();
+
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Position>();
+            type_registry.register::<Name>();
+        }
+
+        let mut registry = ReplicationRegistry::default();
+        registry.register_all_reflected(&world, &type_registry.read(), ReplicationFlags::SPAWN);
+
+        // `Name` was never initialized in the world, so it cannot be registered.
+        assert_eq!(registry.iter().count(), 1);
+        assert!(registry.contains(position));
+
+        assert!(registry.unregister(position).is_some());
+        assert!(!registry.contains(position));
+    }
+}
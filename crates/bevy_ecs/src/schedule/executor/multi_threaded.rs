@@ -413,8 +413,10 @@ impl ExecutorState {
                 self.ready_systems.remove(system_index);
 
                 // SAFETY: `can_run` returned true, which means that:
-                // - It must have called `update_archetype_component_access` for each run condition.
-                // - There can be no systems running whose accesses would conflict with any conditions.
+                // - It must have called `update_archetype_component_access` for each run condition,
+                //   and for the system unless it has already been skipped.
+                // - There can be no systems running whose accesses would conflict with any conditions
+                //   or the system.
                 if unsafe {
                     !self.should_run(
                         system_index,
@@ -520,10 +522,13 @@ impl ExecutorState {
     ///   itself, and conditions for any of the system's sets.
     /// * `update_archetype_component` must have been called with `world`
     ///   for each run condition in `conditions`.
+    /// * `world` must have permission to read any world data required by
+    ///   the system, and `update_archetype_component` must have been called
+    ///   with `world` for the system, unless it has already been skipped.
     unsafe fn should_run(
         &mut self,
         system_index: usize,
-        _system: &BoxedSystem,
+        system: &mut BoxedSystem,
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
     ) -> bool {
@@ -566,6 +571,18 @@ impl ExecutorState {
 
         should_run &= system_conditions_met;
 
+        if should_run {
+            // SAFETY:
+            // - The caller ensures that `world` has permission to read any data
+            //   required by the system.
+            // - `update_archetype_component_access` has been called for the system.
+            let valid_params = unsafe { system.validate_param_unsafe(world) };
+            if !valid_params {
+                self.skipped_systems.insert(system_index);
+            }
+            should_run &= valid_params;
+        }
+
         should_run
     }
 
@@ -730,7 +747,11 @@ unsafe fn evaluate_and_fold_conditions(
         .map(|condition| {
             // SAFETY: The caller ensures that `world` has permission to
             // access any data required by the condition.
-            unsafe { __rust_begin_short_backtrace::readonly_run_unsafe(&mut **condition, world) }
+            // Conditions with invalid parameters are unmet.
+            unsafe {
+                condition.validate_param_unsafe(world)
+                    && __rust_begin_short_backtrace::readonly_run_unsafe(&mut **condition, world)
+            }
         })
         .fold(true, |acc, res| acc && res)
 }
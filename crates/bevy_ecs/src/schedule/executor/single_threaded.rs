@@ -101,6 +101,10 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            if !system.validate_param(world) {
+                continue;
+            }
+
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 if system.is_exclusive() {
                     __rust_begin_short_backtrace::run(&mut **system, world);
@@ -160,6 +164,10 @@ fn evaluate_and_fold_conditions(conditions: &mut [BoxedCondition], world: &mut W
     #[allow(clippy::unnecessary_fold)]
     conditions
         .iter_mut()
-        .map(|condition| __rust_begin_short_backtrace::readonly_run(&mut **condition, world))
+        .map(|condition| {
+            // Conditions with invalid parameters are unmet.
+            condition.validate_param(world)
+                && __rust_begin_short_backtrace::readonly_run(&mut **condition, world)
+        })
         .fold(true, |acc, res| acc && res)
 }
@@ -3,6 +3,7 @@ use std::{
     fmt::{Debug, Write},
 };
 
+use bevy_tasks::{ComputeTaskPool, TaskPool};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 use bevy_utils::{default, tracing::info};
@@ -426,6 +427,51 @@ impl Schedule {
         Ok(())
     }
 
+    /// Initializes the schedule, and updates the cached archetype accesses of its systems and run
+    /// conditions with the archetypes of the `world`, in parallel.
+    ///
+    /// The systems otherwise process the archetypes created since their last update right before
+    /// they run, which can cause a spike on the first frame after spawning many new kinds of
+    /// entities, such as after loading a level. Calling this once the entities are spawned, or
+    /// their archetypes created with [`World::init_archetype`], moves this work out of the frame.
+    ///
+    /// Use [`World::schedule_scope`] to warm up a schedule stored in the [`Schedules`].
+    pub fn warm_up(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
+        self.initialize(world)?;
+
+        let world = world.as_unsafe_world_cell_readonly();
+        let mut systems = self.executable.systems.iter_mut().collect::<Vec<_>>();
+        let mut conditions = self
+            .executable
+            .system_conditions
+            .iter_mut()
+            .chain(&mut self.executable.set_conditions)
+            .flatten()
+            .collect::<Vec<_>>();
+        let task_pool = ComputeTaskPool::get_or_init(TaskPool::default);
+        let thread_count = task_pool.thread_num().max(1);
+        task_pool.scope(|scope| {
+            let chunk_size = systems.len().div_ceil(thread_count).max(1);
+            for chunk in systems.chunks_mut(chunk_size) {
+                scope.spawn(async move {
+                    for system in chunk {
+                        system.update_archetype_component_access(world);
+                    }
+                });
+            }
+            let chunk_size = conditions.len().div_ceil(thread_count).max(1);
+            for chunk in conditions.chunks_mut(chunk_size) {
+                scope.spawn(async move {
+                    for condition in chunk {
+                        condition.update_archetype_component_access(world);
+                    }
+                });
+            }
+        });
+
+        Ok(())
+    }
+
     /// Returns the [`ScheduleGraph`].
     pub fn graph(&self) -> &ScheduleGraph {
         &self.graph
@@ -1134,6 +1180,13 @@ impl ScheduleGraph {
         self.optionally_check_conflicts(&conflicting_systems, components, schedule_label)?;
         self.conflicting_systems = conflicting_systems;
 
+        if self.settings.order_ambiguous_systems {
+            self.order_conflicting_systems(
+                &flat_results.disconnected,
+                &mut dependency_flattened_dag,
+            );
+        }
+
         // build the schedule
         Ok(self.build_schedule_inner(dependency_flattened_dag, hier_results.reachable))
     }
@@ -1379,6 +1432,40 @@ impl ScheduleGraph {
         conflicting_systems
     }
 
+    /// Orders each pair of unordered systems with conflicting access, including the pairs marked
+    /// as ambiguous, following the topological sort of the schedule.
+    ///
+    /// The edges agree with the topological sort, so it stays valid and the graph stays acyclic.
+    fn order_conflicting_systems(
+        &self,
+        flat_results_disconnected: &[(NodeId, NodeId)],
+        dependency_flattened_dag: &mut Dag,
+    ) {
+        let topsort_positions: HashMap<NodeId, usize> = dependency_flattened_dag
+            .topsort
+            .iter()
+            .enumerate()
+            .map(|(position, &node)| (node, position))
+            .collect();
+        for &(a, b) in flat_results_disconnected {
+            let system_a = self.systems[a.index()].get().unwrap();
+            let system_b = self.systems[b.index()].get().unwrap();
+            if system_a.is_exclusive()
+                || system_b.is_exclusive()
+                || !system_a
+                    .component_access()
+                    .is_compatible(system_b.component_access())
+            {
+                let (before, after) = if topsort_positions[&a] < topsort_positions[&b] {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                dependency_flattened_dag.graph.add_edge(before, after, ());
+            }
+        }
+    }
+
     fn build_schedule_inner(
         &self,
         dependency_flattened_dag: Dag,
@@ -2007,6 +2094,15 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// If set to true, systems with conflicting access and no ordering between them always run in
+    /// the same order, the one of the topological sort of the schedule, instead of an order that
+    /// can change from one run to the next with the multi-threaded executor.
+    ///
+    /// This includes the systems whose ambiguity is ignored. It is meant for apps that must run
+    /// deterministically, at the cost of some parallelism.
+    ///
+    /// Defaults to `false`.
+    pub order_ambiguous_systems: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -2025,6 +2121,7 @@ impl ScheduleBuildSettings {
             auto_insert_apply_deferred: true,
             use_shortnames: true,
             report_sets: true,
+            order_ambiguous_systems: false,
         }
     }
 }
@@ -2041,12 +2138,12 @@ mod tests {
 
     use crate::{
         self as bevy_ecs,
-        prelude::{Res, Resource},
+        prelude::{Component, Res, Resource},
         schedule::{
             tests::ResMut, IntoSystemConfigs, IntoSystemSetConfigs, Schedule,
             ScheduleBuildSettings, SystemSet,
         },
-        system::Commands,
+        system::{Commands, Query},
         world::World,
     };
 
@@ -2076,6 +2173,63 @@ mod tests {
         schedule.run(&mut world);
     }
 
+    #[test]
+    fn order_ambiguous_systems() {
+        fn first(_: ResMut<Resource1>) {}
+        fn second(_: ResMut<Resource1>) {}
+        fn unrelated(_: Res<Resource2>) {}
+
+        let mut world = World::new();
+        world.insert_resource(Resource1);
+        world.insert_resource(Resource2);
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            order_ambiguous_systems: true,
+            ..Default::default()
+        });
+        schedule.add_systems((first, second, unrelated));
+        schedule.initialize(&mut world).unwrap();
+
+        let executable = schedule.executable();
+        let dependencies = |name: &str| {
+            let index = executable
+                .systems
+                .iter()
+                .position(|system| system.name().ends_with(name))
+                .unwrap();
+            executable.system_dependencies[index]
+        };
+        // One of the conflicting systems runs after the other
+        assert_eq!(dependencies("first") + dependencies("second"), 1);
+        assert_eq!(dependencies("unrelated"), 0);
+    }
+
+    #[test]
+    fn warm_up_updates_archetype_accesses() {
+        #[derive(Component)]
+        struct A;
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((|_: Query<&mut A>| {}).run_if(|_: Query<&A>| true));
+
+        let archetype = world.init_archetype::<A>();
+        schedule.warm_up(&mut world).unwrap();
+
+        let component = world.component_id::<A>().unwrap();
+        let archetype_component = world.archetypes()[archetype]
+            .get_archetype_component_id(component)
+            .unwrap();
+        let system = &schedule.executable.systems[0];
+        assert!(system
+            .archetype_component_access()
+            .has_write(archetype_component));
+        let condition = &schedule.executable.system_conditions[0][0];
+        assert!(condition
+            .archetype_component_access()
+            .has_read(archetype_component));
+    }
+
     #[test]
     fn inserts_a_sync_point() {
         let mut schedule = Schedule::default();
@@ -112,6 +112,12 @@ where
         })
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: Delegate to other `System` implementations.
+        unsafe { self.system.validate_param_unsafe(world) }
+    }
+
     #[inline]
     fn run(&mut self, input: Self::In, world: &mut crate::prelude::World) -> Self::Out {
         self.func
@@ -177,6 +177,12 @@ where
         )
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: Delegate to the `System` implementations for `a` and `b`.
+        unsafe { self.a.validate_param_unsafe(world) && self.b.validate_param_unsafe(world) }
+    }
+
     fn run<'w>(&mut self, input: Self::In, world: &'w mut World) -> Self::Out {
         // SAFETY: Converting `&mut T` -> `&UnsafeCell<T>`
         // is explicitly allowed in the docs for `UnsafeCell`.
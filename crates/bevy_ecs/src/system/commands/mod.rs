@@ -5,7 +5,8 @@ use crate::{
     self as bevy_ecs,
     bundle::Bundle,
     component::ComponentId,
-    entity::{Entities, Entity},
+    entity::{Entities, Entity, EntitySpawned},
+    event::Events,
     system::{RunSystemWithInput, SystemId},
     world::command_queue::RawCommandQueue,
     world::{Command, CommandQueue, EntityWorldMut, FromWorld, World},
@@ -342,7 +343,7 @@ impl<'w, 's> Commands<'w, 's> {
     /// - [`spawn_batch`](Self::spawn_batch) to spawn entities with a bundle each.
     pub fn spawn<T: Bundle>(&mut self, bundle: T) -> EntityCommands {
         let mut e = self.spawn_empty();
-        e.insert(bundle);
+        e.add(spawn_with(bundle));
         e
     }
 
@@ -1207,6 +1208,18 @@ fn insert<T: Bundle>(bundle: T) -> impl EntityCommand {
     }
 }
 
+/// An [`EntityCommand`] that adds the components in a [`Bundle`] to a newly spawned entity,
+/// and reports it with an [`EntitySpawned`] event.
+fn spawn_with<T: Bundle>(bundle: T) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        insert(bundle).apply(entity, world);
+        if world.contains_resource::<Events<EntitySpawned>>() {
+            let components = world.entity(entity).archetype().components().collect();
+            world.send_event(EntitySpawned { entity, components });
+        }
+    }
+}
+
 /// An [`EntityCommand`] that attempts to add the components in a [`Bundle`] to an entity.
 fn try_insert(bundle: impl Bundle) -> impl EntityCommand {
     move |entity, world: &mut World| {
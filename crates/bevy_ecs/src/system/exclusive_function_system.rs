@@ -114,6 +114,10 @@ where
             let change_tick = world.change_tick.get_mut();
             self.system_meta.last_run.set(*change_tick);
             *change_tick = change_tick.wrapping_add(1);
+            #[cfg(feature = "track_change_systems")]
+            world
+                .change_systems
+                .record(self.system_meta.last_run, self.system_meta.name.clone());
 
             out
         })
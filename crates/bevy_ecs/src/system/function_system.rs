@@ -27,6 +27,8 @@ pub struct SystemMeta {
     is_send: bool,
     has_deferred: bool,
     pub(crate) last_run: Tick,
+    invalid_param_policy: InvalidParamPolicy,
+    invalid_param_warned: bool,
     #[cfg(feature = "trace")]
     pub(crate) system_span: Span,
     #[cfg(feature = "trace")]
@@ -43,6 +45,8 @@ impl SystemMeta {
             is_send: true,
             has_deferred: false,
             last_run: Tick::new(0),
+            invalid_param_policy: InvalidParamPolicy::default(),
+            invalid_param_warned: false,
             #[cfg(feature = "trace")]
             system_span: info_span!("system", name = name),
             #[cfg(feature = "trace")]
@@ -81,6 +85,102 @@ impl SystemMeta {
     pub fn set_has_deferred(&mut self) {
         self.has_deferred = true;
     }
+
+    /// Returns what the system does when one of its parameters is invalid.
+    #[inline]
+    pub fn invalid_param_policy(&self) -> InvalidParamPolicy {
+        self.invalid_param_policy
+    }
+
+    /// Sets what the system does when one of its parameters is invalid.
+    #[inline]
+    pub fn set_invalid_param_policy(&mut self, policy: InvalidParamPolicy) {
+        self.invalid_param_policy = policy;
+    }
+}
+
+/// What a [`System`] does when one of its parameters is invalid, for example when a [`Res`] is
+/// missing or a [`Single`] doesn't match exactly one entity.
+///
+/// This is configured per system with [`WithInvalidParamPolicy`].
+///
+/// [`Res`]: super::Res
+/// [`Single`]: super::Single
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InvalidParamPolicy {
+    /// The system runs anyway, and panics when acquiring the invalid parameter.
+    #[default]
+    Panic,
+    /// The system is skipped.
+    Skip,
+    /// The system is skipped, and a warning is logged the first time it happens.
+    WarnOnce,
+    /// The system is skipped, like with [`Skip`](Self::Skip), except when a
+    /// [`Single`](super::Single) matches several entities: in debug builds, this panics with the
+    /// name of the system and the matching entities instead.
+    ///
+    /// A `Single` matching no entity usually means that the entity isn't spawned yet, while
+    /// matching several entities usually means that a query filter is missing.
+    SkipMissing,
+}
+
+/// Allows configuring the [`InvalidParamPolicy`] of function systems.
+///
+/// This is useful for systems integrating with optional plugins, which can then run only when
+/// the resources of the plugin are available:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::WithInvalidParamPolicy;
+/// #
+/// # #[derive(Resource)]
+/// # struct OptionalPluginSettings;
+/// #
+/// fn configure_optional_plugin(mut settings: ResMut<OptionalPluginSettings>) {
+///     // ...
+/// }
+///
+/// # let mut schedule = Schedule::default();
+/// schedule.add_systems(configure_optional_plugin.skip_on_invalid_param());
+/// ```
+pub trait WithInvalidParamPolicy<Marker, F>: Sized
+where
+    F: SystemParamFunction<Marker>,
+{
+    /// Converts this function into a system with the given [`InvalidParamPolicy`].
+    fn with_invalid_param_policy(self, policy: InvalidParamPolicy) -> FunctionSystem<Marker, F>;
+
+    /// Converts this function into a system that is skipped when one of its parameters is
+    /// invalid.
+    fn skip_on_invalid_param(self) -> FunctionSystem<Marker, F> {
+        self.with_invalid_param_policy(InvalidParamPolicy::Skip)
+    }
+
+    /// Converts this function into a system that is skipped when one of its parameters is
+    /// invalid, logging a warning the first time it happens.
+    fn warn_once_on_invalid_param(self) -> FunctionSystem<Marker, F> {
+        self.with_invalid_param_policy(InvalidParamPolicy::WarnOnce)
+    }
+
+    /// Converts this function into a system that is skipped when one of its parameters is
+    /// missing, but that panics in debug builds when a [`Single`](super::Single) is ambiguous.
+    ///
+    /// See [`InvalidParamPolicy::SkipMissing`].
+    fn skip_on_missing_param(self) -> FunctionSystem<Marker, F> {
+        self.with_invalid_param_policy(InvalidParamPolicy::SkipMissing)
+    }
+}
+
+impl<Marker, F> WithInvalidParamPolicy<Marker, F> for F
+where
+    Marker: 'static,
+    F: SystemParamFunction<Marker>,
+{
+    fn with_invalid_param_policy(self, policy: InvalidParamPolicy) -> FunctionSystem<Marker, F> {
+        let mut system = IntoSystem::into_system(self);
+        system.system_meta.set_invalid_param_policy(policy);
+        system
+    }
 }
 
 // TODO: Actually use this in FunctionSystem. We should probably only do this once Systems are constructed using a World reference
@@ -433,7 +533,10 @@ where
         Self {
             func: self.func.clone(),
             param_state: None,
-            system_meta: SystemMeta::new::<F>(),
+            system_meta: SystemMeta {
+                invalid_param_policy: self.system_meta.invalid_param_policy,
+                ..SystemMeta::new::<F>()
+            },
             world_id: None,
             archetype_generation: ArchetypeGeneration::initial(),
             marker: PhantomData,
@@ -517,6 +620,14 @@ where
         let _span_guard = self.system_meta.system_span.enter();
 
         let change_tick = world.increment_change_tick();
+        #[cfg(feature = "track_change_systems")]
+        {
+            // SAFETY: The recorded systems are world metadata, behind a mutex.
+            let world_metadata = unsafe { world.world_metadata() };
+            world_metadata
+                .change_systems
+                .record(change_tick, self.system_meta.name.clone());
+        }
 
         // SAFETY:
         // - The caller has invoked `update_archetype_component_access`, which will panic
@@ -536,10 +647,44 @@ where
         out
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        if self.system_meta.invalid_param_policy == InvalidParamPolicy::Panic {
+            return true;
+        }
+        let param_state = self.param_state.as_ref().expect(Self::PARAM_MESSAGE);
+        // SAFETY:
+        // - The caller has invoked `update_archetype_component_access`, which will panic
+        //   if the world does not match.
+        // - All world accesses used by `F::Param` have been registered, so the caller
+        //   will ensure that there are no data access conflicts.
+        let valid = unsafe { F::Param::validate_param(param_state, &self.system_meta, world) };
+        if !valid
+            && self.system_meta.invalid_param_policy == InvalidParamPolicy::WarnOnce
+            && !self.system_meta.invalid_param_warned
+        {
+            bevy_utils::tracing::warn!(
+                "{} was skipped because some of its parameters are invalid, for example a missing resource.",
+                self.system_meta.name,
+            );
+            self.system_meta.invalid_param_warned = true;
+        }
+        valid
+    }
+
     #[inline]
     fn apply_deferred(&mut self, world: &mut World) {
+        // The commands use the next change tick, which is skipped afterward so that it is only
+        // attributed to this system
+        #[cfg(feature = "track_change_systems")]
+        world.change_systems.record(
+            world.change_tick(),
+            format!("{} (commands)", self.system_meta.name).into(),
+        );
         let param_state = self.param_state.as_mut().expect(Self::PARAM_MESSAGE);
         F::Param::apply(param_state, &self.system_meta, world);
+        #[cfg(feature = "track_change_systems")]
+        world.increment_change_tick();
     }
 
     #[inline]
@@ -346,15 +346,16 @@ mod tests {
         component::{Component, Components, Tick},
         entity::{Entities, Entity},
         prelude::AnyOf,
-        query::{Added, Changed, Or, With, Without},
+        query::{Added, Changed, Or, QuerySingleError, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
-            apply_deferred, common_conditions::resource_exists, Condition, IntoSystemConfigs,
-            Schedule,
+            apply_deferred, common_conditions::resource_exists, Condition, ExecutorKind,
+            IntoSystemConfigs, Schedule,
         },
         system::{
             Commands, In, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query, Res, ResMut,
-            Resource, StaticSystemParam, System, SystemState,
+            Resource, Single, StaticSystemParam, System, SystemParam, SystemState,
+            WithInvalidParamPolicy,
         },
         world::{FromWorld, World},
     };
@@ -535,6 +536,167 @@ mod tests {
         assert_eq!(world.resource::<Changed>().0, 2);
     }
 
+    #[test]
+    fn invalid_param_skips_system() {
+        #[derive(SystemParam)]
+        struct CustomParam<'w> {
+            _a: Res<'w, A>,
+        }
+
+        fn sys(_param: CustomParam, mut ran: ResMut<SystemRan>) {
+            *ran = SystemRan::Yes;
+        }
+
+        for executor in [
+            ExecutorKind::Simple,
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.insert_resource(SystemRan::No);
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems(sys.skip_on_invalid_param());
+
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::No);
+
+            world.insert_resource(A);
+            schedule.run(&mut world);
+            assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+        }
+    }
+
+    #[test]
+    #[should_panic = "Resource requested by"]
+    fn invalid_param_panics_by_default() {
+        fn sys(_: Res<A>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    fn invalid_param_fails_condition() {
+        fn condition(_: Res<A>) -> bool {
+            true
+        }
+
+        fn sys(mut ran: ResMut<SystemRan>) {
+            *ran = SystemRan::Yes;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(SystemRan::No);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sys.run_if(condition.warn_once_on_invalid_param()));
+
+        schedule.run(&mut world);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::No);
+
+        world.insert_resource(A);
+        schedule.run(&mut world);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+    }
+
+    #[test]
+    fn single_param() {
+        #[derive(Resource, Default)]
+        struct Total(usize);
+
+        fn sys(single: Single<&W<usize>>, mut total: ResMut<Total>) {
+            total.0 += single.0;
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Total>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sys.skip_on_invalid_param());
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Total>().0, 0);
+
+        world.spawn(W(2usize));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Total>().0, 2);
+
+        world.spawn(W(3usize));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Total>().0, 2);
+    }
+
+    #[test]
+    fn invalid_param_skips_combined_system() {
+        #[derive(Resource, Default)]
+        struct Total(usize);
+
+        fn first(mut ran: ResMut<SystemRan>) -> usize {
+            *ran = SystemRan::Yes;
+            1
+        }
+
+        fn second(In(input): In<usize>, single: Single<&W<usize>>, mut total: ResMut<Total>) {
+            total.0 += input + single.0;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(SystemRan::No);
+        world.init_resource::<Total>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(first.pipe(second.skip_on_invalid_param()));
+
+        // Only the param of `second` is invalid, which skips the whole combined system
+        schedule.run(&mut world);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::No);
+        assert_eq!(world.resource::<Total>().0, 0);
+
+        world.spawn(W(2usize));
+        schedule.run(&mut world);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+        assert_eq!(world.resource::<Total>().0, 3);
+    }
+
+    #[test]
+    #[should_panic = "is ambiguous: 2 entities fit the query"]
+    #[cfg(debug_assertions)]
+    fn single_param_skip_missing_panics_when_ambiguous() {
+        fn sys(_: Single<&W<usize>>) {}
+
+        let mut world = World::default();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sys.skip_on_missing_param());
+
+        // No matching entity: the system is skipped.
+        schedule.run(&mut world);
+
+        world.spawn(W(2usize));
+        world.spawn(W(3usize));
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn single_error_lists_candidates() {
+        let mut world = World::default();
+        let mut system_state = SystemState::<Query<&W<usize>>>::new(&mut world);
+        assert!(matches!(
+            system_state.get(&world).get_single(),
+            Err(QuerySingleError::NoEntities(_))
+        ));
+
+        let entities: Vec<_> = (0..10).map(|i| world.spawn(W(i)).id()).collect();
+        let Err(QuerySingleError::MultipleEntities {
+            count,
+            entities: candidates,
+            ..
+        }) = system_state.get(&world).get_single()
+        else {
+            panic!("expected the query to match multiple entities");
+        };
+        assert_eq!(count, 10);
+        assert_eq!(candidates.len(), QuerySingleError::MAX_CANDIDATES);
+        assert!(candidates.iter().all(|entity| entities.contains(entity)));
+    }
+
     #[test]
     #[should_panic = "error[B0001]"]
     fn option_has_no_filter_with() {
@@ -8,7 +8,11 @@ use crate::{
     },
     world::unsafe_world_cell::UnsafeWorldCell,
 };
-use std::borrow::Borrow;
+use std::{
+    borrow::Borrow,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 /// [System parameter] that provides selective access to the [`Component`] data stored in a [`World`].
 ///
@@ -1098,7 +1102,10 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     /// - [`single_mut`](Self::single_mut) to get the mutable query item.
     #[track_caller]
     pub fn single(&self) -> ROQueryItem<'_, D> {
-        self.get_single().unwrap()
+        match self.get_single() {
+            Ok(item) => item,
+            Err(error) => panic!("Cannot get single query result: {error}"),
+        }
     }
 
     /// Returns a single read-only query item when there is exactly one entity matching the query.
@@ -1120,8 +1127,8 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     ///         Err(QuerySingleError::NoEntities(_)) => {
     ///             println!("Error: There is no player!");
     ///         }
-    ///         Err(QuerySingleError::MultipleEntities(_)) => {
-    ///             println!("Error: There is more than one player!");
+    ///         Err(QuerySingleError::MultipleEntities { count, .. }) => {
+    ///             println!("Error: There are {count} players!");
     ///         }
     ///     }
     /// }
@@ -1175,7 +1182,10 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     /// - [`single`](Self::single) to get the read-only query item.
     #[track_caller]
     pub fn single_mut(&mut self) -> D::Item<'_> {
-        self.get_single_mut().unwrap()
+        match self.get_single_mut() {
+            Ok(item) => item,
+            Err(error) => panic!("Cannot get single mutable query result: {error}"),
+        }
     }
 
     /// Returns a single query item when there is exactly one entity matching the query.
@@ -1610,3 +1620,54 @@ impl<'w, 'q, Q: QueryData, F: QueryFilter> From<&'q mut Query<'w, '_, Q, F>>
         value.transmute_lens_filtered()
     }
 }
+
+/// [System parameter] that provides access to the query item of the single entity matching a
+/// [`Query`].
+///
+/// Unlike [`Query::single`], this doesn't panic inside the system when there isn't exactly one
+/// matching entity: the parameter is invalid instead, so the system can be skipped with an
+/// [`InvalidParamPolicy`](crate::system::InvalidParamPolicy). With the default policy, the system
+/// panics before running.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{Single, WithInvalidParamPolicy};
+/// #
+/// # #[derive(Component)]
+/// # struct Player { health: u32 }
+/// #
+/// fn heal_player(mut player: Single<&mut Player>) {
+///     player.health += 1;
+/// }
+///
+/// // Skip the system while there is no player, or more than one.
+/// # let mut schedule = Schedule::default();
+/// schedule.add_systems(heal_player.skip_on_invalid_param());
+/// ```
+///
+/// [System parameter]: crate::system::SystemParam
+pub struct Single<'w, D: QueryData, F: QueryFilter = ()> {
+    pub(crate) item: D::Item<'w>,
+    pub(crate) _filter: PhantomData<F>,
+}
+
+impl<'w, D: QueryData, F: QueryFilter> Deref for Single<'w, D, F> {
+    type Target = D::Item<'w>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<'w, D: QueryData, F: QueryFilter> DerefMut for Single<'w, D, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<'w, D: QueryData, F: QueryFilter> Single<'w, D, F> {
+    /// Returns the inner query item.
+    pub fn into_inner(self) -> D::Item<'w> {
+        self.item
+    }
+}
@@ -83,6 +83,35 @@ pub trait System: Send + Sync + 'static {
         ret
     }
 
+    /// Returns `false` if the system should be skipped because one of its parameters is invalid,
+    /// according to its [`InvalidParamPolicy`](crate::system::InvalidParamPolicy).
+    ///
+    /// Executors call this before running a system, and treat conditions with invalid
+    /// parameters as unmet.
+    ///
+    /// # Safety
+    ///
+    /// - The caller must ensure that `world` has permission to read any world data
+    ///   registered in [`Self::archetype_component_access`]. There must be no conflicting
+    ///   simultaneous accesses while the system is validated.
+    /// - The method [`Self::update_archetype_component_access`] must be called at some
+    ///   point before this one, with the same exact [`World`]. If `update_archetype_component_access`
+    ///   panics (or otherwise does not return for any reason), this method must not be called.
+    unsafe fn validate_param_unsafe(&mut self, _world: UnsafeWorldCell) -> bool {
+        true
+    }
+
+    /// Safe version of [`System::validate_param_unsafe`] that runs on a shared reference to the
+    /// world.
+    fn validate_param(&mut self, world: &World) -> bool {
+        let world_cell = world.as_unsafe_world_cell_readonly();
+        self.update_archetype_component_access(world_cell);
+        // SAFETY:
+        // - We have read-only access to the entire world.
+        // - `update_archetype_component_access` has been called.
+        unsafe { self.validate_param_unsafe(world_cell) }
+    }
+
     /// Applies any [`Deferred`](crate::system::Deferred) system parameters (or other system buffers) of this system to the world.
     ///
     /// This is where [`Commands`](crate::system::Commands) get applied.
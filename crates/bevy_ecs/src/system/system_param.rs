@@ -10,7 +10,7 @@ use crate::{
         Access, FilteredAccess, FilteredAccessSet, QueryData, QueryFilter, QueryState,
         ReadOnlyQueryData,
     },
-    system::{Query, SystemMeta},
+    system::{InvalidParamPolicy, Query, Single, SystemMeta},
     world::{unsafe_world_cell::UnsafeWorldCell, FromWorld, World},
 };
 use bevy_ecs_macros::impl_param_set;
@@ -159,6 +159,28 @@ pub unsafe trait SystemParam: Sized {
     #[allow(unused_variables)]
     fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {}
 
+    /// Returns `true` if [`get_param`](SystemParam::get_param) can be called without panicking.
+    ///
+    /// This is checked before running a system whose [`InvalidParamPolicy`] isn't
+    /// [`Panic`](InvalidParamPolicy::Panic), to skip it when a parameter is invalid, for example
+    /// when a [`Res`] is missing.
+    ///
+    /// # Safety
+    ///
+    /// - The passed [`UnsafeWorldCell`] must have read-only access to world data
+    ///   registered in [`init_state`](SystemParam::init_state).
+    /// - `world` must be the same [`World`] that was used to initialize [`state`](SystemParam::init_state).
+    /// - All `world`'s archetypes have been processed by [`new_archetype`](SystemParam::new_archetype).
+    #[inline]
+    #[allow(unused_variables)]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        true
+    }
+
     /// Creates a parameter to be passed into a [`SystemParamFunction`].
     ///
     /// [`SystemParamFunction`]: super::SystemParamFunction
@@ -248,6 +270,92 @@ unsafe impl<D: QueryData + 'static, F: QueryFilter + 'static> SystemParam for Qu
     }
 }
 
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl<'a, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> ReadOnlySystemParam
+    for Single<'a, D, F>
+{
+}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta. If
+// this Single conflicts with any prior access, a panic will occur.
+unsafe impl<'a, D: QueryData + 'static, F: QueryFilter + 'static> SystemParam for Single<'a, D, F> {
+    type State = QueryState<D, F>;
+    type Item<'w, 's> = Single<'w, D, F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Query::<'_, '_, D, F>::init_state(world, system_meta)
+    }
+
+    unsafe fn new_archetype(
+        state: &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        // SAFETY: Delegate to existing `SystemParam` implementations.
+        unsafe { Query::<'_, '_, D, F>::new_archetype(state, archetype, system_meta) };
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        let this_run = world.change_tick();
+        // SAFETY: We have registered all of the query's world accesses, so the caller ensures
+        // that `world` has permission to read any world data that the query needs.
+        let mut query = unsafe {
+            state
+                .as_nop()
+                .iter_unchecked_manual(world, system_meta.last_run, this_run)
+        };
+        let matched = query.next().is_some();
+        let ambiguous = matched && query.next().is_some();
+        if ambiguous
+            && cfg!(debug_assertions)
+            && system_meta.invalid_param_policy() == InvalidParamPolicy::SkipMissing
+        {
+            // SAFETY: Same as above.
+            let error =
+                unsafe { state.multiple_entities_error(world, system_meta.last_run, this_run) };
+            panic!(
+                "Single<{}, {}> requested by {} is ambiguous: {error}",
+                std::any::type_name::<D>(),
+                std::any::type_name::<F>(),
+                system_meta.name,
+            );
+        }
+        matched && !ambiguous
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        state.validate_world(world.id());
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        let result =
+            unsafe { state.get_single_unchecked_manual(world, system_meta.last_run, change_tick) };
+        match result {
+            Ok(item) => Single {
+                item,
+                _filter: PhantomData,
+            },
+            Err(error) => panic!(
+                "Single<{}, {}> requested by {} is invalid: {error}",
+                std::any::type_name::<D>(),
+                std::any::type_name::<F>(),
+                system_meta.name,
+            ),
+        }
+    }
+}
+
 impl<'w, 's, D: QueryData + 'static, F: QueryFilter + 'static> BuildableSystemParam
     for Query<'w, 's, D, F>
 {
@@ -511,6 +619,19 @@ unsafe impl<'a, T: Resource> SystemParam for Res<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource metadata.
+        unsafe { world.storages() }
+            .resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -606,6 +727,19 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource metadata.
+        unsafe { world.storages() }
+            .resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -1114,6 +1248,19 @@ unsafe impl<'a, T: 'static> SystemParam for NonSend<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource metadata.
+        unsafe { world.storages() }
+            .non_send_resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -1206,6 +1353,19 @@ unsafe impl<'a, T: 'static> SystemParam for NonSendMut<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource metadata.
+        unsafe { world.storages() }
+            .non_send_resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -1419,6 +1579,17 @@ macro_rules! impl_system_param_tuple {
                 $($param::apply($param, _system_meta, _world);)*
             }
 
+            #[inline]
+            unsafe fn validate_param(
+                state: &Self::State,
+                _system_meta: &SystemMeta,
+                _world: UnsafeWorldCell,
+            ) -> bool {
+                let ($($param,)*) = state;
+                // SAFETY: Upheld by the caller.
+                true $(&& unsafe { $param::validate_param($param, _system_meta, _world) })*
+            }
+
             #[inline]
             #[allow(clippy::unused_unit)]
             unsafe fn get_param<'w, 's>(
@@ -1567,6 +1738,15 @@ unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_,
         P::apply(state, system_meta, world);
     }
 
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Defer to the safety of P::SystemParam
+        unsafe { P::validate_param(state, system_meta, world) }
+    }
+
     unsafe fn get_param<'world, 'state>(
         state: &'state mut Self::State,
         system_meta: &SystemMeta,
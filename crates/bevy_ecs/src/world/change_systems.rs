@@ -0,0 +1,159 @@
+//! Records the systems mutating components and resources, enabled by the
+//! `track_change_systems` feature.
+
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{component::Tick, entity::Entity, prelude::Component, system::Resource};
+
+use super::World;
+
+/// The number of system runs remembered to find the system that last changed a component or a
+/// resource. Changes older than that are not attributed to a system anymore.
+pub const CHANGE_SYSTEMS_CAPACITY: usize = 1 << 16;
+
+/// The names of the last systems that ran, with the change tick of their run.
+///
+/// Every system run uses its own change tick, which is stored as the change tick of the
+/// components and resources it mutates: the tick of a change identifies its system.
+#[derive(Default)]
+pub(crate) struct ChangeSystems {
+    runs: Mutex<VecDeque<(Tick, Cow<'static, str>)>>,
+}
+
+impl ChangeSystems {
+    /// Records that the system `name` mutates the world with the change tick `tick`.
+    pub(crate) fn record(&self, tick: Tick, name: Cow<'static, str>) {
+        let mut runs = self.runs.lock().unwrap_or_else(PoisonError::into_inner);
+        if runs.len() == CHANGE_SYSTEMS_CAPACITY {
+            runs.pop_front();
+        }
+        runs.push_back((tick, name));
+    }
+
+    /// Returns the name of the last system that ran with the change tick `tick`.
+    fn get(&self, tick: Tick) -> Option<Cow<'static, str>> {
+        let runs = self.runs.lock().unwrap_or_else(PoisonError::into_inner);
+        runs.iter()
+            .rev()
+            .find(|(run_tick, _)| *run_tick == tick)
+            .map(|(_, name)| name.clone())
+    }
+}
+
+impl World {
+    /// Returns the name of the system that last changed the component `T` of `entity`, or `None`
+    /// if the entity doesn't have the component, or if it was changed outside of a system or too
+    /// long ago, see [`CHANGE_SYSTEMS_CAPACITY`].
+    ///
+    /// This helps finding the system that keeps overwriting a component, without bisecting the
+    /// systems. The changes made by [`Commands`](crate::system::Commands) are attributed to the
+    /// system that queued them, with a ` (commands)` suffix. The changes made directly through
+    /// the [`World`], outside of systems, may be attributed to the next system that runs.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn heal(mut query: Query<&mut Health>) {
+    ///     for mut health in &mut query {
+    ///         health.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn(Health(10)).id();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems(heal);
+    /// schedule.run(&mut world);
+    ///
+    /// let system = world.component_changed_by::<Health>(entity).unwrap();
+    /// assert!(system.ends_with("heal"));
+    /// ```
+    pub fn component_changed_by<T: Component>(&self, entity: Entity) -> Option<Cow<'static, str>> {
+        let ticks = self.get_entity(entity)?.get_change_ticks::<T>()?;
+        self.change_systems.get(ticks.last_changed_tick())
+    }
+
+    /// Returns the name of the system that last changed the resource `R`, or `None` if the
+    /// resource doesn't exist, or if it was changed outside of a system or too long ago, see
+    /// [`World::component_changed_by`].
+    pub fn resource_changed_by<R: Resource>(&self) -> Option<Cow<'static, str>> {
+        let ticks = self.get_resource_change_ticks::<R>()?;
+        self.change_systems.get(ticks.last_changed_tick())
+    }
+
+    /// Returns the name of the system that ran with the change tick `tick`, such as the
+    /// [`last_changed`](crate::change_detection::DetectChanges::last_changed) tick of a
+    /// component, see [`World::component_changed_by`].
+    pub fn system_at_tick(&self, tick: Tick) -> Option<Cow<'static, str>> {
+        self.change_systems.get(tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Resource)]
+    struct R(u32);
+
+    fn write_a(mut query: Query<&mut A>) {
+        for mut a in &mut query {
+            a.0 += 1;
+        }
+    }
+
+    fn read_a(query: Query<&A>, mut r: ResMut<R>) {
+        r.0 = query.iter().map(|a| a.0).sum();
+    }
+
+    fn insert_a(mut commands: Commands, query: Query<Entity, With<A>>) {
+        for entity in &query {
+            commands.entity(entity).insert(A(0));
+        }
+    }
+
+    #[test]
+    fn component_and_resource_changes() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+        world.insert_resource(R(0));
+        assert_eq!(world.component_changed_by::<A>(entity), None);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((write_a, read_a).chain());
+        schedule.run(&mut world);
+
+        assert!(world
+            .component_changed_by::<A>(entity)
+            .unwrap()
+            .ends_with("write_a"));
+        assert!(world
+            .resource_changed_by::<R>()
+            .unwrap()
+            .ends_with("read_a"));
+    }
+
+    #[test]
+    fn command_changes() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(insert_a);
+        schedule.run(&mut world);
+
+        assert!(world
+            .component_changed_by::<A>(entity)
+            .unwrap()
+            .ends_with("insert_a (commands)"));
+    }
+}
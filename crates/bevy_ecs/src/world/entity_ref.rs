@@ -3,7 +3,7 @@ use crate::{
     bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
-    entity::{Entities, Entity, EntityLocation},
+    entity::{send_entity_despawned, Entities, Entity, EntityLocation},
     query::Access,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
@@ -1200,6 +1200,8 @@ impl<'w> EntityWorldMut<'w> {
             }
         }
 
+        send_entity_despawned(&mut deferred_world, self.entity, archetype.components());
+
         for component_id in archetype.components() {
             world.removed_components.send(component_id, self.entity);
         }
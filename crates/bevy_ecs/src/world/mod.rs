@@ -1,5 +1,7 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+#[cfg(feature = "track_change_systems")]
+mod change_systems;
 pub(crate) mod command_queue;
 mod deferred_world;
 mod entity_ref;
@@ -10,6 +12,8 @@ pub mod unsafe_world_cell;
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
 use crate::component::ComponentInitializer;
 pub use crate::world::command_queue::CommandQueue;
+#[cfg(feature = "track_change_systems")]
+pub use change_systems::CHANGE_SYSTEMS_CAPACITY;
 pub use deferred_world::DeferredWorld;
 pub use entity_ref::{
     EntityMut, EntityRef, EntityWorldMut, Entry, FilteredEntityMut, FilteredEntityRef,
@@ -25,7 +29,7 @@ use crate::{
         Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, ComponentTicks,
         Components, Tick,
     },
-    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
+    entity::{send_entity_spawned, AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
     event::{Event, EventId, Events, SendBatchIds},
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
     removal_detection::RemovedComponentEvents,
@@ -115,6 +119,8 @@ pub struct World {
     pub(crate) last_change_tick: Tick,
     pub(crate) last_check_tick: Tick,
     pub(crate) command_queue: RawCommandQueue,
+    #[cfg(feature = "track_change_systems")]
+    pub(crate) change_systems: change_systems::ChangeSystems,
 }
 
 impl Default for World {
@@ -133,6 +139,8 @@ impl Default for World {
             last_change_tick: Tick::new(0),
             last_check_tick: Tick::new(0),
             command_queue: RawCommandQueue::new(),
+            #[cfg(feature = "track_change_systems")]
+            change_systems: Default::default(),
         }
     }
 }
@@ -509,6 +517,7 @@ impl World {
                 Some(unsafe { EntityWorldMut::new(self, entity, location) })
             }
             AllocAtWithoutReplacement::DidNotExist => {
+                send_entity_spawned(&mut self.into(), entity, std::iter::empty());
                 // SAFETY: entity was just allocated
                 Some(unsafe { self.spawn_at_empty_internal(entity) })
             }
@@ -758,6 +767,7 @@ impl World {
     pub fn spawn_empty(&mut self) -> EntityWorldMut {
         self.flush_entities();
         let entity = self.entities.alloc();
+        send_entity_spawned(&mut self.into(), entity, std::iter::empty());
         // SAFETY: entity was just allocated
         unsafe { self.spawn_at_empty_internal(entity) }
     }
@@ -2127,6 +2137,33 @@ impl World {
         // SAFETY: We just initialised the bundle so its id should definitely be valid.
         unsafe { self.bundles.get(id).debug_checked_unwrap() }
     }
+
+    /// Creates the archetype of the entities spawned with the [`Bundle`] `B` and its required
+    /// components, and returns its id.
+    ///
+    /// Archetypes are otherwise created when the first entity with a new set of components is
+    /// spawned, and the systems update their cached accesses with each new archetype before
+    /// their next run. Creating a known set of archetypes ahead of time, for example during a
+    /// loading screen, and warming up the schedules with [`Schedule::warm_up`] avoids a spike on
+    /// the first frame after spawning many new kinds of entities.
+    ///
+    /// [`Schedule::warm_up`]: crate::schedule::Schedule::warm_up
+    pub fn init_archetype<B: Bundle>(&mut self) -> ArchetypeId {
+        let id = self
+            .bundles
+            .init_info::<B>(&mut self.components, &mut self.storages);
+        // SAFETY: We just initialised the bundle so its id should definitely be valid.
+        let bundle_info = unsafe { self.bundles.get(id).debug_checked_unwrap() };
+        // SAFETY: The bundle was initialised with the components of this world.
+        unsafe {
+            bundle_info.add_bundle_to_archetype(
+                &mut self.archetypes,
+                &mut self.storages,
+                &self.components,
+                ArchetypeId::EMPTY,
+            )
+        }
+    }
 }
 
 impl World {
@@ -3152,4 +3189,23 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[test]
+    fn init_archetype() {
+        #[derive(Component)]
+        struct A;
+        #[derive(Component)]
+        #[require(A)]
+        struct B;
+
+        let mut world = World::new();
+        let archetype = world.init_archetype::<B>();
+        let archetype_count = world.archetypes().len();
+
+        let entity = world.spawn(B).id();
+        assert_eq!(world.entity(entity).archetype().id(), archetype);
+        assert!(world.entity(entity).contains::<A>());
+        assert_eq!(world.archetypes().len(), archetype_count);
+        assert_eq!(world.init_archetype::<(A, B)>(), archetype);
+    }
 }
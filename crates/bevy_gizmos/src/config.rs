@@ -165,6 +165,14 @@ pub struct GizmoConfig {
     ///
     /// Gizmos will only be rendered to cameras with intersecting layers.
     pub render_layers: RenderLayers,
+    /// Draw the gizmos in screen space, above the UI, instead of in the world.
+    ///
+    /// The positions of screen space gizmos are in logical pixels, from the top left corner of
+    /// the viewport of each camera rendering the gizmos, like UI nodes. Screen space gizmos are
+    /// only drawn when the `bevy_ui` feature is enabled and the `UiPlugin` is added.
+    ///
+    /// Defaults to `false`.
+    pub screen_space: bool,
 
     /// Describe how lines should join
     pub line_joints: GizmoLineJoint,
@@ -179,6 +187,7 @@ impl Default for GizmoConfig {
             line_style: GizmoLineStyle::Solid,
             depth_bias: 0.,
             render_layers: Default::default(),
+            screen_space: false,
 
             line_joints: GizmoLineJoint::None,
         }
@@ -190,6 +199,7 @@ pub(crate) struct GizmoMeshConfig {
     pub line_perspective: bool,
     pub line_style: GizmoLineStyle,
     pub render_layers: RenderLayers,
+    pub screen_space: bool,
 }
 
 impl From<&GizmoConfig> for GizmoMeshConfig {
@@ -198,6 +208,7 @@ impl From<&GizmoConfig> for GizmoMeshConfig {
             line_perspective: item.line_perspective,
             line_style: item.line_style,
             render_layers: item.render_layers.clone(),
+            screen_space: item.screen_space,
         }
     }
 }
@@ -0,0 +1,199 @@
+//! A module adding debug visualization of the frustum culling of cameras.
+
+use crate as bevy_gizmos;
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_color::{
+    palettes::basic::{LIME, RED},
+    Color, Oklcha,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    query::{QueryItem, With, Without},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    primitives::{Aabb, Frustum},
+    view::{InheritedVisibility, RenderLayers, VisibilitySystems, VisibleEntities},
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+
+/// A [`Plugin`] that provides visualization of the frustum culling of cameras for debugging.
+///
+/// For each camera with a [`ShowCullingGizmo`], or every camera if
+/// [`CullingGizmoConfigGroup::draw_all`] is set, this draws the [`Frustum`] of the camera, the
+/// [`Aabb`]s of the entities it sees, and the [`Aabb`]s of the entities which would be visible but
+/// were culled, because they are outside of the frustum. This helps diagnose objects disappearing at
+/// certain angles, which usually comes from an [`Aabb`] that doesn't match the rendered object.
+///
+/// The camera showing the gizmos should be a different one, as a camera is inside of its own
+/// frustum.
+pub struct CullingGizmoPlugin;
+
+impl Plugin for CullingGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<CullingGizmoConfigGroup>()
+            .register_type::<ShowCullingGizmo>()
+            .init_gizmo_group::<CullingGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_culling,
+                    draw_all_culling.run_if(|config: Res<GizmoConfigStore>| {
+                        config.config::<CullingGizmoConfigGroup>().1.draw_all
+                    }),
+                )
+                    .after(VisibilitySystems::CheckVisibility),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used for debug visualizations of the frustum culling of cameras.
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+pub struct CullingGizmoConfigGroup {
+    /// Draws the culling of all cameras when set to `true`.
+    ///
+    /// To draw the culling of a specific camera, you can add the [`ShowCullingGizmo`] component.
+    ///
+    /// Defaults to `false`.
+    pub draw_all: bool,
+    /// The color of the frustums.
+    ///
+    /// A color is chosen per camera if `None`.
+    ///
+    /// Defaults to `None`.
+    pub frustum_color: Option<Color>,
+    /// The color of the bounding boxes of the entities visible to a camera, which aren't drawn if
+    /// `None`.
+    ///
+    /// Defaults to [`LIME`].
+    pub visible_color: Option<Color>,
+    /// The color of the bounding boxes of the entities culled by a camera, which aren't drawn if
+    /// `None`.
+    ///
+    /// Defaults to [`RED`].
+    pub culled_color: Option<Color>,
+}
+
+impl Default for CullingGizmoConfigGroup {
+    fn default() -> Self {
+        Self {
+            draw_all: false,
+            frustum_color: None,
+            visible_color: Some(LIME.into()),
+            culled_color: Some(RED.into()),
+        }
+    }
+}
+
+/// Add this [`Component`] to a camera to draw its frustum culling.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct ShowCullingGizmo {
+    /// The color of the frustum.
+    ///
+    /// The frustum color from the [`CullingGizmoConfigGroup`] config is used if `None`.
+    pub color: Option<Color>,
+}
+
+type CameraQuery = (
+    Entity,
+    &'static Frustum,
+    &'static VisibleEntities,
+    Option<&'static RenderLayers>,
+);
+
+type EntityQuery = (
+    Entity,
+    &'static Aabb,
+    &'static GlobalTransform,
+    &'static InheritedVisibility,
+    Option<&'static RenderLayers>,
+);
+
+fn draw_culling(
+    cameras: Query<(CameraQuery, &ShowCullingGizmo), With<Camera>>,
+    entities: Query<EntityQuery>,
+    mut gizmos: Gizmos<CullingGizmoConfigGroup>,
+) {
+    for (camera, gizmo) in &cameras {
+        draw_camera_culling(camera, gizmo.color, &entities, &mut gizmos);
+    }
+}
+
+fn draw_all_culling(
+    cameras: Query<CameraQuery, (With<Camera>, Without<ShowCullingGizmo>)>,
+    entities: Query<EntityQuery>,
+    mut gizmos: Gizmos<CullingGizmoConfigGroup>,
+) {
+    for camera in &cameras {
+        draw_camera_culling(camera, None, &entities, &mut gizmos);
+    }
+}
+
+fn draw_camera_culling(
+    (camera, frustum, visible_entities, camera_layers): QueryItem<CameraQuery>,
+    color: Option<Color>,
+    entities: &Query<EntityQuery>,
+    gizmos: &mut Gizmos<CullingGizmoConfigGroup>,
+) {
+    let frustum_color = color
+        .or(gizmos.config_ext.frustum_color)
+        .unwrap_or_else(|| Oklcha::sequential_dispersed(camera.index()).into());
+    if let Some(corners) = frustum.corners() {
+        // Link the corners whose indices differ by a single bit
+        for (index, &corner) in corners.iter().enumerate() {
+            for bit in [1, 2, 4] {
+                if index & bit == 0 {
+                    gizmos.line(corner.into(), corners[index | bit].into(), frustum_color);
+                }
+            }
+        }
+    }
+
+    let visible: EntityHashSet = visible_entities
+        .entities
+        .values()
+        .flatten()
+        .copied()
+        .collect();
+    let default_layers = RenderLayers::default();
+    let camera_layers = camera_layers.unwrap_or(&default_layers);
+    let (visible_color, culled_color) = (
+        gizmos.config_ext.visible_color,
+        gizmos.config_ext.culled_color,
+    );
+    for (entity, &aabb, transform, inherited_visibility, layers) in entities {
+        let color = if visible.contains(&entity) {
+            visible_color
+        } else if inherited_visibility.get()
+            && camera_layers.intersects(layers.unwrap_or(&default_layers))
+        {
+            culled_color
+        } else {
+            None
+        };
+        if let Some(color) = color {
+            gizmos.cuboid(
+                *transform
+                    * GlobalTransform::from(
+                        Transform::from_translation(Vec3::from(aabb.center))
+                            .with_scale(Vec3::from(aabb.half_extents * 2.)),
+                    ),
+                color,
+            );
+        }
+    }
+}
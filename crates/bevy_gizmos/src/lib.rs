@@ -29,6 +29,9 @@ pub enum GizmoRenderSystem {
     /// Adds gizmos to the [`Transparent3d`](bevy_core_pipeline::core_3d::Transparent3d) render phase
     #[cfg(feature = "bevy_pbr")]
     QueueLineGizmos3d,
+    /// Adds screen space gizmos to the [`TransparentUi`](bevy_ui::TransparentUi) render phase
+    #[cfg(feature = "bevy_ui")]
+    QueueLineGizmosUi,
 }
 
 pub mod aabb;
@@ -36,6 +39,7 @@ pub mod arcs;
 pub mod arrows;
 pub mod circles;
 pub mod config;
+pub mod culling;
 pub mod gizmos;
 pub mod grid;
 pub mod primitives;
@@ -48,6 +52,8 @@ pub mod light;
 mod pipeline_2d;
 #[cfg(feature = "bevy_pbr")]
 mod pipeline_3d;
+#[cfg(feature = "bevy_ui")]
+mod pipeline_ui;
 
 /// The `bevy_gizmos` prelude.
 pub mod prelude {
@@ -58,6 +64,7 @@ pub mod prelude {
             DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore,
             GizmoLineJoint, GizmoLineStyle,
         },
+        culling::{CullingGizmoConfigGroup, ShowCullingGizmo},
         gizmos::Gizmos,
         primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
         AppGizmoBuilder,
@@ -101,6 +108,7 @@ use config::{
     DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoLineJoint,
     GizmoMeshConfig,
 };
+use culling::CullingGizmoPlugin;
 use gizmos::{GizmoStorage, Swap};
 #[cfg(feature = "bevy_pbr")]
 use light::LightGizmoPlugin;
@@ -111,7 +119,8 @@ const LINE_JOINT_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(11627807
 
 /// A [`Plugin`] that provides an immediate mode drawing api for visual debugging.
 ///
-/// Requires to be loaded after [`PbrPlugin`](bevy_pbr::PbrPlugin) or [`SpritePlugin`](bevy_sprite::SpritePlugin).
+/// Requires to be loaded after [`PbrPlugin`](bevy_pbr::PbrPlugin) or [`SpritePlugin`](bevy_sprite::SpritePlugin),
+/// and after [`UiPlugin`](bevy_ui::UiPlugin) to draw screen space gizmos.
 pub struct GizmoPlugin;
 
 impl Plugin for GizmoPlugin {
@@ -138,7 +147,7 @@ impl Plugin for GizmoPlugin {
             .init_resource::<LineGizmoHandles>()
             // We insert the Resource GizmoConfigStore into the world implicitly here if it does not exist.
             .init_gizmo_group::<DefaultGizmoConfigGroup>()
-            .add_plugins(AabbGizmoPlugin);
+            .add_plugins((AabbGizmoPlugin, CullingGizmoPlugin));
 
         #[cfg(feature = "bevy_pbr")]
         app.add_plugins(LightGizmoPlugin);
@@ -166,6 +175,12 @@ impl Plugin for GizmoPlugin {
         } else {
             bevy_utils::tracing::warn!("bevy_pbr feature is enabled but bevy_pbr::PbrPlugin was not detected. Are you sure you loaded GizmoPlugin after PbrPlugin?");
         }
+        #[cfg(feature = "bevy_ui")]
+        if app.is_plugin_added::<bevy_ui::UiPlugin>() {
+            app.add_plugins(pipeline_ui::LineGizmoUiPlugin);
+        } else {
+            bevy_utils::tracing::warn!("bevy_ui feature is enabled but bevy_ui::UiPlugin was not detected. Are you sure you loaded GizmoPlugin after UiPlugin?");
+        }
     }
 
     fn finish(&self, app: &mut bevy_app::App) {
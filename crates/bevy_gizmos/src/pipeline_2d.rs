@@ -7,7 +7,7 @@ use crate::{
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
-use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_core_pipeline::core_2d::{Transparent2d, CORE_2D_DEPTH_FORMAT};
 
 use bevy_ecs::{
     prelude::Entity,
@@ -139,7 +139,14 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             }),
             layout,
             primitive: PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_2D_DEPTH_FORMAT,
+                depth_write_enabled: false,
+                // Gizmos are drawn over the opaque items
+                depth_compare: CompareFunction::Always,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
             multisample: MultisampleState {
                 count: key.mesh_key.msaa_samples(),
                 mask: !0,
@@ -224,7 +231,14 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             }),
             layout,
             primitive: PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_2D_DEPTH_FORMAT,
+                depth_write_enabled: false,
+                // Gizmos are drawn over the opaque items
+                depth_compare: CompareFunction::Always,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
             multisample: MultisampleState {
                 count: key.mesh_key.msaa_samples(),
                 mask: !0,
@@ -273,7 +287,7 @@ fn queue_line_gizmos_2d(
 
         let render_layers = render_layers.unwrap_or_default();
         for (entity, handle, config) in &line_gizmos {
-            if !config.render_layers.intersects(render_layers) {
+            if config.screen_space || !config.render_layers.intersects(render_layers) {
                 continue;
             }
 
@@ -330,7 +344,7 @@ fn queue_line_joint_gizmos_2d(
 
         let render_layers = render_layers.unwrap_or_default();
         for (entity, handle, config) in &line_gizmos {
-            if !config.render_layers.intersects(render_layers) {
+            if config.screen_space || !config.render_layers.intersects(render_layers) {
                 continue;
             }
 
@@ -0,0 +1,342 @@
+use crate::{
+    config::{GizmoLineJoint, GizmoLineStyle, GizmoMeshConfig},
+    line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo,
+    DrawLineJointGizmo, GizmoRenderSystem, GpuLineGizmo, LineGizmo,
+    LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_JOINT_SHADER_HANDLE,
+    LINE_SHADER_HANDLE,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+
+use bevy_ecs::{
+    prelude::Entity,
+    query::With,
+    schedule::{IntoSystemConfigs, IntoSystemSetConfigs},
+    system::{Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::FloatOrd;
+use bevy_render::{
+    render_asset::{prepare_assets, RenderAssets},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItemExtraIndex, SetItemPipeline,
+        ViewSortedRenderPhases,
+    },
+    render_resource::*,
+    texture::BevyDefault,
+    view::{ExtractedView, RenderLayers, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+use bevy_ui::{DefaultCameraView, SetUiViewBindGroup, TransparentUi, UiPipeline};
+use bevy_utils::tracing::error;
+
+/// Draws the screen space gizmos in the UI pass, above the UI nodes.
+pub struct LineGizmoUiPlugin;
+
+impl Plugin for LineGizmoUiPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .add_render_command::<TransparentUi, DrawLineGizmoUi>()
+            .add_render_command::<TransparentUi, DrawLineJointGizmoUi>()
+            .init_resource::<SpecializedRenderPipelines<LineGizmoUiPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<LineJointGizmoUiPipeline>>()
+            .configure_sets(
+                Render,
+                GizmoRenderSystem::QueueLineGizmosUi
+                    .in_set(RenderSet::Queue)
+                    .ambiguous_with(bevy_ui::queue_uinodes),
+            )
+            .add_systems(
+                Render,
+                (queue_line_gizmos_ui, queue_line_joint_gizmos_ui)
+                    .in_set(GizmoRenderSystem::QueueLineGizmosUi)
+                    .after(prepare_assets::<GpuLineGizmo>),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<LineGizmoUiPipeline>();
+        render_app.init_resource::<LineJointGizmoUiPipeline>();
+    }
+}
+
+#[derive(Clone, Resource)]
+struct LineGizmoUiPipeline {
+    view_layout: BindGroupLayout,
+    uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LineGizmoUiPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        LineGizmoUiPipeline {
+            view_layout: render_world.resource::<UiPipeline>().view_layout.clone(),
+            uniform_layout: render_world
+                .resource::<LineGizmoUniformBindgroupLayout>()
+                .layout
+                .clone(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct LineGizmoUiPipelineKey {
+    hdr: bool,
+    strip: bool,
+    line_style: GizmoLineStyle,
+}
+
+impl SpecializedRenderPipeline for LineGizmoUiPipeline {
+    type Key = LineGizmoUiPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let shader_defs = vec![
+            #[cfg(feature = "webgl")]
+            "SIXTEEN_BYTE_ALIGNMENT".into(),
+        ];
+
+        let layout = vec![self.view_layout.clone(), self.uniform_layout.clone()];
+
+        let fragment_entry_point = match key.line_style {
+            GizmoLineStyle::Solid => "fragment_solid",
+            GizmoLineStyle::Dotted => "fragment_dotted",
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: LINE_SHADER_HANDLE,
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: line_gizmo_vertex_buffer_layouts(key.strip),
+            },
+            fragment: Some(FragmentState {
+                shader: LINE_SHADER_HANDLE,
+                shader_defs,
+                entry_point: fragment_entry_point.into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout,
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            // The UI pass is not multisampled
+            multisample: MultisampleState::default(),
+            label: Some("LineGizmo Pipeline UI".into()),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Resource)]
+struct LineJointGizmoUiPipeline {
+    view_layout: BindGroupLayout,
+    uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LineJointGizmoUiPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        LineJointGizmoUiPipeline {
+            view_layout: render_world.resource::<UiPipeline>().view_layout.clone(),
+            uniform_layout: render_world
+                .resource::<LineGizmoUniformBindgroupLayout>()
+                .layout
+                .clone(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct LineJointGizmoUiPipelineKey {
+    hdr: bool,
+    joints: GizmoLineJoint,
+}
+
+impl SpecializedRenderPipeline for LineJointGizmoUiPipeline {
+    type Key = LineJointGizmoUiPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let shader_defs = vec![
+            #[cfg(feature = "webgl")]
+            "SIXTEEN_BYTE_ALIGNMENT".into(),
+        ];
+
+        let layout = vec![self.view_layout.clone(), self.uniform_layout.clone()];
+
+        if key.joints == GizmoLineJoint::None {
+            error!("There is no entry point for line joints with GizmoLineJoints::None. Please consider aborting the drawing process before reaching this stage.");
+        };
+
+        let entry_point = match key.joints {
+            GizmoLineJoint::Miter => "vertex_miter",
+            GizmoLineJoint::Round(_) => "vertex_round",
+            GizmoLineJoint::None | GizmoLineJoint::Bevel => "vertex_bevel",
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: LINE_JOINT_SHADER_HANDLE,
+                entry_point: entry_point.into(),
+                shader_defs: shader_defs.clone(),
+                buffers: line_joint_gizmo_vertex_buffer_layouts(),
+            },
+            fragment: Some(FragmentState {
+                shader: LINE_JOINT_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout,
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            // The UI pass is not multisampled
+            multisample: MultisampleState::default(),
+            label: Some("LineJointGizmo Pipeline UI".into()),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+type DrawLineGizmoUi = (
+    SetItemPipeline,
+    SetUiViewBindGroup<0>,
+    SetLineGizmoBindGroup<1>,
+    DrawLineGizmo,
+);
+type DrawLineJointGizmoUi = (
+    SetItemPipeline,
+    SetUiViewBindGroup<0>,
+    SetLineGizmoBindGroup<1>,
+    DrawLineJointGizmo,
+);
+
+#[allow(clippy::too_many_arguments)]
+fn queue_line_gizmos_ui(
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    pipeline: Res<LineGizmoUiPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<LineGizmoUiPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
+    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
+    views: Query<(Entity, &ExtractedView, Option<&RenderLayers>), With<DefaultCameraView>>,
+) {
+    let draw_function = draw_functions.read().get_id::<DrawLineGizmoUi>().unwrap();
+
+    for (view_entity, view, render_layers) in &views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let render_layers = render_layers.unwrap_or_default();
+        for (entity, handle, config) in &line_gizmos {
+            if !config.screen_space || !config.render_layers.intersects(render_layers) {
+                continue;
+            }
+
+            let Some(line_gizmo) = line_gizmo_assets.get(handle) else {
+                continue;
+            };
+
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                LineGizmoUiPipelineKey {
+                    hdr: view.hdr,
+                    strip: line_gizmo.strip,
+                    line_style: config.line_style,
+                },
+            );
+
+            transparent_phase.add(TransparentUi {
+                entity,
+                draw_function,
+                pipeline,
+                // Draw above every UI node
+                sort_key: (FloatOrd(f32::INFINITY), entity.index()),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_line_joint_gizmos_ui(
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    pipeline: Res<LineJointGizmoUiPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<LineJointGizmoUiPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
+    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
+    views: Query<(Entity, &ExtractedView, Option<&RenderLayers>), With<DefaultCameraView>>,
+) {
+    let draw_function = draw_functions
+        .read()
+        .get_id::<DrawLineJointGizmoUi>()
+        .unwrap();
+
+    for (view_entity, view, render_layers) in &views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let render_layers = render_layers.unwrap_or_default();
+        for (entity, handle, config) in &line_gizmos {
+            if !config.screen_space || !config.render_layers.intersects(render_layers) {
+                continue;
+            }
+
+            let Some(line_gizmo) = line_gizmo_assets.get(handle) else {
+                continue;
+            };
+
+            if !line_gizmo.strip || line_gizmo.joints == GizmoLineJoint::None {
+                continue;
+            }
+
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                LineJointGizmoUiPipelineKey {
+                    hdr: view.hdr,
+                    joints: line_gizmo.joints,
+                },
+            );
+            transparent_phase.add(TransparentUi {
+                entity,
+                draw_function,
+                pipeline,
+                sort_key: (FloatOrd(f32::INFINITY), entity.index()),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
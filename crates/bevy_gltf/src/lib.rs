@@ -31,6 +31,32 @@
 //!     });
 //! }
 //! ```
+//!
+//! ## Spawning large scenes over several frames
+//!
+//! Spawning a scene with thousands of entities in a single frame causes a visible hitch. Adding a
+//! [`SceneSpawnBudget`](bevy_scene::SceneSpawnBudget) next to the `SceneBundle` spawns the scene a
+//! few entities at a time instead, and sends a
+//! [`SceneInstanceReady`](bevy_scene::SceneInstanceReady) event once it is complete.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_asset::prelude::*;
+//! # use bevy_scene::{prelude::*, SceneSpawnBudget};
+//! # use std::time::Duration;
+//!
+//! fn spawn_gltf(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn((
+//!         SceneBundle {
+//!             scene: asset_server.load("models/FlightHelmet/FlightHelmet.gltf#Scene0"),
+//!             ..Default::default()
+//!         },
+//!         // Spend at most 2 milliseconds per frame spawning the scene
+//!         SceneSpawnBudget::Duration(Duration::from_millis(2)),
+//!     ));
+//! }
+//! ```
+//!
 //! # Loading parts of a glTF asset
 //!
 //! ## Using `Gltf`
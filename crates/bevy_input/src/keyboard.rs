@@ -70,9 +70,10 @@ use bevy_ecs::entity::Entity;
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     event::{Event, EventReader},
-    system::ResMut,
+    system::{Local, ResMut, Resource},
 };
 use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
 use smol_str::SmolStr;
 
 #[cfg(feature = "serialize")]
@@ -86,7 +87,8 @@ use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 /// ## Usage
 ///
 /// The event is consumed inside of the [`keyboard_input_system`]
-/// to update the [`ButtonInput<KeyCode>`](ButtonInput<KeyCode>) resource.
+/// to update the [`ButtonInput<KeyCode>`](ButtonInput<KeyCode>), [`ButtonInput<Key>`] and
+/// [`KeyboardLayout`] resources.
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
 #[cfg_attr(
@@ -105,29 +107,141 @@ pub struct KeyboardInput {
     pub window: Entity,
 }
 
-/// Updates the [`ButtonInput<KeyCode>`] resource with the latest [`KeyboardInput`] events.
+/// Updates the [`ButtonInput<KeyCode>`], [`ButtonInput<Key>`] and [`KeyboardLayout`] resources
+/// with the latest [`KeyboardInput`] events.
+///
+/// A logical key is released with the physical key which pressed it, so that it doesn't stay
+/// pressed when a modifier changes the key produced in between, such as `a` being released as `A`
+/// when <kbd>Shift</kbd> is pressed while holding it.
 ///
 /// ## Differences
 ///
 /// The main difference between the [`KeyboardInput`] event and the [`ButtonInput<KeyCode>`] resources is that
 /// the latter have convenient functions such as [`ButtonInput::pressed`], [`ButtonInput::just_pressed`] and [`ButtonInput::just_released`].
+#[allow(clippy::doc_markdown)] // Clippy doesn't like our use of <kbd>.
 pub fn keyboard_input_system(
-    mut key_input: ResMut<ButtonInput<KeyCode>>,
+    mut key_code_input: ResMut<ButtonInput<KeyCode>>,
+    mut key_input: ResMut<ButtonInput<Key>>,
+    mut keyboard_layout: ResMut<KeyboardLayout>,
+    mut pressed_keys: Local<HashMap<KeyCode, Key>>,
     mut keyboard_input_events: EventReader<KeyboardInput>,
 ) {
-    // Avoid clearing if it's not empty to ensure change detection is not triggered.
+    // Clear the just pressed and released buttons without triggering change detection, so that
+    // the resources are only changed by the events.
+    key_code_input.bypass_change_detection().clear();
     key_input.bypass_change_detection().clear();
     for event in keyboard_input_events.read() {
         let KeyboardInput {
-            key_code, state, ..
+            key_code,
+            logical_key,
+            state,
+            ..
         } = event;
         match state {
-            ButtonState::Pressed => key_input.press(*key_code),
-            ButtonState::Released => key_input.release(*key_code),
+            ButtonState::Pressed => {
+                // The characters produced with modifiers aren't those printed on the keys.
+                let modified = key_code_input.any_pressed([
+                    KeyCode::ShiftLeft,
+                    KeyCode::ShiftRight,
+                    KeyCode::AltRight,
+                ]);
+                match logical_key {
+                    // Caps Lock is toggled rather than held, so the letters are learned in
+                    // lowercase whether it is on or not.
+                    Key::Character(character) if !modified => keyboard_layout.learn(
+                        *key_code,
+                        Key::Character(SmolStr::new(character.to_lowercase())),
+                    ),
+                    Key::Character(_) => {}
+                    _ => keyboard_layout.learn(*key_code, logical_key.clone()),
+                }
+
+                key_code_input.press(*key_code);
+                if let Some(previous) = pressed_keys.insert(*key_code, logical_key.clone()) {
+                    if !pressed_keys.values().any(|key| *key == previous) {
+                        key_input.release(previous);
+                    }
+                }
+                key_input.press(logical_key.clone());
+            }
+            ButtonState::Released => {
+                key_code_input.release(*key_code);
+                let logical_key = pressed_keys
+                    .remove(key_code)
+                    .unwrap_or_else(|| logical_key.clone());
+                // Keep the key pressed while another physical key produces it, like Shift.
+                if !pressed_keys.values().any(|key| *key == logical_key) {
+                    key_input.release(logical_key);
+                }
+            }
         }
     }
 }
 
+/// The logical keys produced by the physical keys of the keyboard, with the current layout.
+///
+/// Platforms don't let applications query the keyboard layout, so each physical key is mapped to
+/// the logical key it produced the last time it was pressed without <kbd>Shift</kbd> or
+/// <kbd>AltGr</kbd>, with the characters in lowercase to ignore <kbd>Caps Lock</kbd>. Until a
+/// key is pressed, [`KeyCode::us_layout_key`] gives the key it produces on a US keyboard, which
+/// can be used as a fallback to show keybindings:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::keyboard::{Key, KeyCode, KeyboardLayout};
+/// fn keybinding_label(layout: Res<KeyboardLayout>) {
+///     // "Z" on an AZERTY keyboard, once the key has been pressed
+///     let key = layout
+///         .logical_key(KeyCode::KeyW)
+///         .cloned()
+///         .or_else(|| KeyCode::KeyW.us_layout_key());
+///     if let Some(Key::Character(character)) = key {
+///         println!("Move forward: {}", character.to_uppercase());
+///     }
+/// }
+/// ```
+///
+/// ## Updating
+///
+/// The resource is updated inside of the [`keyboard_input_system`].
+#[derive(Resource, Debug, Default, Clone)]
+#[allow(clippy::doc_markdown)] // Clippy doesn't like our use of <kbd>.
+pub struct KeyboardLayout {
+    keys: HashMap<KeyCode, Key>,
+}
+
+impl KeyboardLayout {
+    /// Returns the logical key produced by the physical key `key_code`, if it was pressed.
+    pub fn logical_key(&self, key_code: KeyCode) -> Option<&Key> {
+        self.keys.get(&key_code)
+    }
+
+    /// Returns the physical key producing `logical_key`, if it was pressed.
+    pub fn key_code(&self, logical_key: &Key) -> Option<KeyCode> {
+        self.keys
+            .iter()
+            .find(|(_, key)| *key == logical_key)
+            .map(|(key_code, _)| *key_code)
+    }
+
+    /// Iterates over the physical keys which were pressed, and the logical keys they produced.
+    pub fn iter(&self) -> impl Iterator<Item = (KeyCode, &Key)> {
+        self.keys.iter().map(|(key_code, key)| (*key_code, key))
+    }
+
+    /// Maps the physical key `key_code` to `logical_key`.
+    ///
+    /// This can be used to restore a layout saved from a previous run.
+    pub fn learn(&mut self, key_code: KeyCode, logical_key: Key) {
+        self.keys.insert(key_code, logical_key);
+    }
+
+    /// Forgets the logical keys of all the physical keys, such as when the layout changes.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
+
 /// Contains the platform-native physical key identifier
 ///
 /// The exact values vary from platform to platform (which is part of why this is a per-platform
@@ -655,6 +769,113 @@ pub enum KeyCode {
     F35,
 }
 
+impl KeyCode {
+    /// Returns the logical key produced by this physical key on a US keyboard, without modifiers.
+    ///
+    /// This is `None` for the keys whose logical key depends on more than the layout, such as the
+    /// numpad keys with <kbd>NumLock</kbd>, and for uncommon keys.
+    #[allow(clippy::doc_markdown)] // Clippy doesn't like our use of <kbd>.
+    pub fn us_layout_key(self) -> Option<Key> {
+        let character = match self {
+            KeyCode::Backquote => "`",
+            KeyCode::Backslash => "\\",
+            KeyCode::BracketLeft => "[",
+            KeyCode::BracketRight => "]",
+            KeyCode::Comma => ",",
+            KeyCode::Digit0 => "0",
+            KeyCode::Digit1 => "1",
+            KeyCode::Digit2 => "2",
+            KeyCode::Digit3 => "3",
+            KeyCode::Digit4 => "4",
+            KeyCode::Digit5 => "5",
+            KeyCode::Digit6 => "6",
+            KeyCode::Digit7 => "7",
+            KeyCode::Digit8 => "8",
+            KeyCode::Digit9 => "9",
+            KeyCode::Equal => "=",
+            KeyCode::KeyA => "a",
+            KeyCode::KeyB => "b",
+            KeyCode::KeyC => "c",
+            KeyCode::KeyD => "d",
+            KeyCode::KeyE => "e",
+            KeyCode::KeyF => "f",
+            KeyCode::KeyG => "g",
+            KeyCode::KeyH => "h",
+            KeyCode::KeyI => "i",
+            KeyCode::KeyJ => "j",
+            KeyCode::KeyK => "k",
+            KeyCode::KeyL => "l",
+            KeyCode::KeyM => "m",
+            KeyCode::KeyN => "n",
+            KeyCode::KeyO => "o",
+            KeyCode::KeyP => "p",
+            KeyCode::KeyQ => "q",
+            KeyCode::KeyR => "r",
+            KeyCode::KeyS => "s",
+            KeyCode::KeyT => "t",
+            KeyCode::KeyU => "u",
+            KeyCode::KeyV => "v",
+            KeyCode::KeyW => "w",
+            KeyCode::KeyX => "x",
+            KeyCode::KeyY => "y",
+            KeyCode::KeyZ => "z",
+            KeyCode::Minus => "-",
+            KeyCode::Period => ".",
+            KeyCode::Quote => "'",
+            KeyCode::Semicolon => ";",
+            KeyCode::Slash => "/",
+            KeyCode::NumpadAdd => "+",
+            KeyCode::NumpadDivide => "/",
+            KeyCode::NumpadMultiply => "*",
+            KeyCode::NumpadSubtract => "-",
+            _ => {
+                return match self {
+                    KeyCode::AltLeft => Some(Key::Alt),
+                    KeyCode::AltRight => Some(Key::AltGraph),
+                    KeyCode::Backspace | KeyCode::NumpadBackspace => Some(Key::Backspace),
+                    KeyCode::CapsLock => Some(Key::CapsLock),
+                    KeyCode::ContextMenu => Some(Key::ContextMenu),
+                    KeyCode::ControlLeft | KeyCode::ControlRight => Some(Key::Control),
+                    KeyCode::Enter | KeyCode::NumpadEnter => Some(Key::Enter),
+                    KeyCode::SuperLeft | KeyCode::SuperRight => Some(Key::Super),
+                    KeyCode::ShiftLeft | KeyCode::ShiftRight => Some(Key::Shift),
+                    KeyCode::Space => Some(Key::Space),
+                    KeyCode::Tab => Some(Key::Tab),
+                    KeyCode::Delete => Some(Key::Delete),
+                    KeyCode::End => Some(Key::End),
+                    KeyCode::Home => Some(Key::Home),
+                    KeyCode::Insert => Some(Key::Insert),
+                    KeyCode::PageDown => Some(Key::PageDown),
+                    KeyCode::PageUp => Some(Key::PageUp),
+                    KeyCode::ArrowDown => Some(Key::ArrowDown),
+                    KeyCode::ArrowLeft => Some(Key::ArrowLeft),
+                    KeyCode::ArrowRight => Some(Key::ArrowRight),
+                    KeyCode::ArrowUp => Some(Key::ArrowUp),
+                    KeyCode::NumLock => Some(Key::NumLock),
+                    KeyCode::Escape => Some(Key::Escape),
+                    KeyCode::PrintScreen => Some(Key::PrintScreen),
+                    KeyCode::ScrollLock => Some(Key::ScrollLock),
+                    KeyCode::Pause => Some(Key::Pause),
+                    KeyCode::F1 => Some(Key::F1),
+                    KeyCode::F2 => Some(Key::F2),
+                    KeyCode::F3 => Some(Key::F3),
+                    KeyCode::F4 => Some(Key::F4),
+                    KeyCode::F5 => Some(Key::F5),
+                    KeyCode::F6 => Some(Key::F6),
+                    KeyCode::F7 => Some(Key::F7),
+                    KeyCode::F8 => Some(Key::F8),
+                    KeyCode::F9 => Some(Key::F9),
+                    KeyCode::F10 => Some(Key::F10),
+                    KeyCode::F11 => Some(Key::F11),
+                    KeyCode::F12 => Some(Key::F12),
+                    _ => None,
+                };
+            }
+        };
+        Some(Key::Character(SmolStr::new_inline(character)))
+    }
+}
+
 /// Contains the platform-native logical key identifier, known as keysym.
 ///
 /// Exactly what that means differs from platform to platform, but the values are to some degree
@@ -1443,3 +1664,141 @@ pub enum Key {
     /// General-purpose function key.
     F35,
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{entity::Entity, event::Events, schedule::Schedule, world::World};
+    use smol_str::SmolStr;
+
+    use crate::{ButtonInput, ButtonState};
+
+    use super::{keyboard_input_system, Key, KeyCode, KeyboardInput, KeyboardLayout};
+
+    fn keyboard_world() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ButtonInput<Key>>();
+        world.init_resource::<KeyboardLayout>();
+        world.init_resource::<Events<KeyboardInput>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(keyboard_input_system);
+        (world, schedule)
+    }
+
+    fn character(character: &str) -> Key {
+        Key::Character(SmolStr::new(character))
+    }
+
+    fn send(world: &mut World, key_code: KeyCode, logical_key: Key, state: ButtonState) {
+        world.send_event(KeyboardInput {
+            key_code,
+            logical_key,
+            state,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    #[test]
+    fn logical_keys_are_released_with_their_physical_key() {
+        let (mut world, mut schedule) = keyboard_world();
+
+        send(
+            &mut world,
+            KeyCode::KeyA,
+            character("a"),
+            ButtonState::Pressed,
+        );
+        send(
+            &mut world,
+            KeyCode::ShiftLeft,
+            Key::Shift,
+            ButtonState::Pressed,
+        );
+        schedule.run(&mut world);
+        assert!(world.resource::<ButtonInput<Key>>().pressed(character("a")));
+
+        // The key is released as "A" since Shift is held, but it pressed "a"
+        send(
+            &mut world,
+            KeyCode::KeyA,
+            character("A"),
+            ButtonState::Released,
+        );
+        schedule.run(&mut world);
+        let key_input = world.resource::<ButtonInput<Key>>();
+        assert!(key_input.just_released(character("a")));
+        assert!(!key_input.pressed(character("A")));
+        assert!(key_input.pressed(Key::Shift));
+
+        // A logical key stays pressed while another physical key produces it
+        send(
+            &mut world,
+            KeyCode::ShiftRight,
+            Key::Shift,
+            ButtonState::Pressed,
+        );
+        send(
+            &mut world,
+            KeyCode::ShiftLeft,
+            Key::Shift,
+            ButtonState::Released,
+        );
+        schedule.run(&mut world);
+        assert!(world.resource::<ButtonInput<Key>>().pressed(Key::Shift));
+        send(
+            &mut world,
+            KeyCode::ShiftRight,
+            Key::Shift,
+            ButtonState::Released,
+        );
+        schedule.run(&mut world);
+        assert!(!world.resource::<ButtonInput<Key>>().pressed(Key::Shift));
+    }
+
+    #[test]
+    fn keyboard_layout_learns_the_unmodified_keys() {
+        let (mut world, mut schedule) = keyboard_world();
+
+        // The AZERTY key at the position of W on a QWERTY keyboard, with Caps Lock on
+        send(
+            &mut world,
+            KeyCode::KeyW,
+            character("Z"),
+            ButtonState::Pressed,
+        );
+        send(
+            &mut world,
+            KeyCode::KeyW,
+            character("Z"),
+            ButtonState::Released,
+        );
+        send(&mut world, KeyCode::Enter, Key::Enter, ButtonState::Pressed);
+        send(
+            &mut world,
+            KeyCode::Enter,
+            Key::Enter,
+            ButtonState::Released,
+        );
+        // Shift changes the characters of the keys
+        send(
+            &mut world,
+            KeyCode::ShiftLeft,
+            Key::Shift,
+            ButtonState::Pressed,
+        );
+        send(
+            &mut world,
+            KeyCode::Digit1,
+            character("1"),
+            ButtonState::Pressed,
+        );
+        schedule.run(&mut world);
+
+        let layout = world.resource::<KeyboardLayout>();
+        assert_eq!(layout.logical_key(KeyCode::KeyW), Some(&character("z")));
+        assert_eq!(layout.key_code(&character("z")), Some(KeyCode::KeyW));
+        assert_eq!(layout.logical_key(KeyCode::Enter), Some(&Key::Enter));
+        assert_eq!(layout.logical_key(KeyCode::ShiftLeft), Some(&Key::Shift));
+        assert_eq!(layout.logical_key(KeyCode::Digit1), None);
+    }
+}
@@ -18,8 +18,10 @@ pub mod common_conditions;
 pub mod gamepad;
 pub mod keyboard;
 pub mod mouse;
+pub mod pointer;
 pub mod touch;
 pub mod touchpad;
+pub mod virtual_gamepad;
 
 pub use axis::*;
 pub use button_input::*;
@@ -41,10 +43,14 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_reflect::Reflect;
-use keyboard::{keyboard_input_system, KeyCode, KeyboardInput};
+use keyboard::{keyboard_input_system, Key, KeyCode, KeyboardInput, KeyboardLayout};
 use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
 use touch::{touch_screen_input_system, TouchInput, Touches};
 use touchpad::{TouchpadMagnify, TouchpadRotate};
+use virtual_gamepad::{
+    virtual_gamepad_system, VirtualButton, VirtualButtonState, VirtualStick, VirtualStickMode,
+    VirtualStickState,
+};
 
 use gamepad::{
     gamepad_axis_event_system, gamepad_button_event_system, gamepad_connection_system,
@@ -70,6 +76,8 @@ impl Plugin for InputPlugin {
             // keyboard
             .add_event::<KeyboardInput>()
             .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<Key>>()
+            .init_resource::<KeyboardLayout>()
             .add_systems(PreUpdate, keyboard_input_system.in_set(InputSystem))
             // mouse
             .add_event::<MouseButtonInput>()
@@ -108,7 +116,15 @@ impl Plugin for InputPlugin {
             // touch
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
-            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem));
+            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem))
+            // virtual gamepad
+            .add_systems(
+                PreUpdate,
+                virtual_gamepad_system
+                    .after(touch_screen_input_system)
+                    .before(gamepad_event_system)
+                    .in_set(InputSystem),
+            );
 
         // Register common types
         app.register_type::<ButtonState>()
@@ -119,7 +135,12 @@ impl Plugin for InputPlugin {
             .register_type::<TouchInput>()
             .register_type::<GamepadEvent>()
             .register_type::<GamepadButtonInput>()
-            .register_type::<GamepadSettings>();
+            .register_type::<GamepadSettings>()
+            .register_type::<VirtualStick>()
+            .register_type::<VirtualStickMode>()
+            .register_type::<VirtualStickState>()
+            .register_type::<VirtualButton>()
+            .register_type::<VirtualButtonState>();
     }
 }
 
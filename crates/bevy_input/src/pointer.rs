@@ -0,0 +1,12 @@
+//! The pointers that can interact with entities on screen.
+
+use bevy_reflect::Reflect;
+
+/// Identifies a pointer: the mouse or a finger on a touch screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum PointerId {
+    /// The mouse, using its left button.
+    Mouse,
+    /// A touch, with the id reported by [`Touch::id`](crate::touch::Touch::id).
+    Touch(u64),
+}
@@ -0,0 +1,366 @@
+//! On-screen controls for touch screens, exposed as a virtual gamepad.
+//!
+//! A [`VirtualStick`] or a [`VirtualButton`] captures the touches that start in its
+//! [`touch_area`](VirtualStick::touch_area), and reports them as the axes and buttons of a
+//! [`Gamepad`]. The virtual gamepad is connected as soon as a control refers to it, so the
+//! same systems can read the input of physical gamepads and on-screen controls through
+//! [`Axis<GamepadAxis>`](crate::Axis) and [`ButtonInput<GamepadButton>`](crate::ButtonInput).
+//!
+//! These components only handle input. All positions are in logical pixels of the window, like
+//! [`Touch::position`](crate::touch::Touch::position), so that the app can draw the controls
+//! with its UI of choice, for example using [`VirtualStickState::knob_position`].
+
+use crate::{
+    gamepad::{
+        Gamepad, GamepadAxisChangedEvent, GamepadAxisType, GamepadButtonChangedEvent,
+        GamepadButtonType, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    },
+    touch::Touches,
+};
+use bevy_ecs::prelude::*;
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::HashSet;
+
+/// The [`Gamepad`] used by default by virtual controls.
+///
+/// Its id is chosen to not collide with the ids assigned to physical gamepads.
+pub const VIRTUAL_GAMEPAD: Gamepad = Gamepad { id: usize::MAX };
+
+/// How the center of a [`VirtualStick`] moves with the touch controlling it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum VirtualStickMode {
+    /// The stick stays at [`VirtualStick::center`].
+    #[default]
+    Fixed,
+    /// The stick is centered where the touch starts, anywhere in the touch area.
+    Floating,
+    /// Like [`Floating`](Self::Floating), but the stick also follows the touch when it moves
+    /// further than [`VirtualStick::radius`] from the center.
+    Dynamic,
+}
+
+/// An on-screen analog stick, controlled by a touch.
+///
+/// Its position is reported on the [`x_axis`](Self::x_axis) and [`y_axis`](Self::y_axis) of
+/// the [`gamepad`](Self::gamepad), in the `[-1.0, 1.0]` range, with the Y axis pointing up.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+#[require(VirtualStickState)]
+pub struct VirtualStick {
+    /// The gamepad reporting the stick.
+    pub gamepad: Gamepad,
+    /// The axis reporting the horizontal position of the stick.
+    pub x_axis: GamepadAxisType,
+    /// The axis reporting the vertical position of the stick.
+    pub y_axis: GamepadAxisType,
+    /// The area in which touches start controlling the stick.
+    pub touch_area: Rect,
+    /// The center of the stick when it is not touched, and while it is in
+    /// [`VirtualStickMode::Fixed`].
+    pub center: Vec2,
+    /// The distance from the center at which the stick is fully tilted.
+    pub radius: f32,
+    /// The fraction of the [`radius`](Self::radius) around the center in which the stick
+    /// reports no tilt. The rest of the range is rescaled to start at `0.0`.
+    pub dead_zone: f32,
+    /// How the center of the stick moves with the touch.
+    pub mode: VirtualStickMode,
+}
+
+impl Default for VirtualStick {
+    fn default() -> Self {
+        Self {
+            gamepad: VIRTUAL_GAMEPAD,
+            x_axis: GamepadAxisType::LeftStickX,
+            y_axis: GamepadAxisType::LeftStickY,
+            touch_area: Rect::default(),
+            center: Vec2::ZERO,
+            radius: 50.0,
+            dead_zone: 0.1,
+            mode: VirtualStickMode::Fixed,
+        }
+    }
+}
+
+impl VirtualStick {
+    /// Returns the tilt of the stick for a touch at `offset` from its center, after applying
+    /// the dead zone.
+    pub fn tilt(&self, offset: Vec2) -> Vec2 {
+        let distance = offset.length() / self.radius;
+        if distance <= self.dead_zone || !distance.is_finite() {
+            return Vec2::ZERO;
+        }
+        let scaled = ((distance - self.dead_zone) / (1.0 - self.dead_zone)).min(1.0);
+        // Window coordinates grow downwards, while gamepad axes point up.
+        offset.normalize() * scaled * Vec2::new(1.0, -1.0)
+    }
+}
+
+/// The current state of a [`VirtualStick`], updated by [`virtual_gamepad_system`].
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct VirtualStickState {
+    touch: Option<u64>,
+    center: Option<Vec2>,
+    tilt: Vec2,
+}
+
+impl VirtualStickState {
+    /// Returns the id of the touch controlling the stick, if any.
+    pub fn touch(&self) -> Option<u64> {
+        self.touch
+    }
+
+    /// Returns the current center of the stick, which moves in [`VirtualStickMode::Floating`]
+    /// and [`VirtualStickMode::Dynamic`].
+    pub fn center(&self, stick: &VirtualStick) -> Vec2 {
+        self.center.unwrap_or(stick.center)
+    }
+
+    /// Returns the tilt of the stick, as reported on its axes.
+    pub fn tilt(&self) -> Vec2 {
+        self.tilt
+    }
+
+    /// Returns where the knob of the stick should be drawn, in logical pixels of the window.
+    pub fn knob_position(&self, stick: &VirtualStick) -> Vec2 {
+        self.center(stick) + self.tilt * Vec2::new(1.0, -1.0) * stick.radius
+    }
+}
+
+/// An on-screen button, pressed while a touch that started in its area is held.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+#[require(VirtualButtonState)]
+pub struct VirtualButton {
+    /// The gamepad reporting the button.
+    pub gamepad: Gamepad,
+    /// The reported button.
+    pub button: GamepadButtonType,
+    /// The area in which touches press the button.
+    pub touch_area: Rect,
+}
+
+impl Default for VirtualButton {
+    fn default() -> Self {
+        Self {
+            gamepad: VIRTUAL_GAMEPAD,
+            button: GamepadButtonType::South,
+            touch_area: Rect::default(),
+        }
+    }
+}
+
+/// The current state of a [`VirtualButton`], updated by [`virtual_gamepad_system`].
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct VirtualButtonState {
+    touch: Option<u64>,
+}
+
+impl VirtualButtonState {
+    /// Returns the id of the touch pressing the button, if any.
+    pub fn touch(&self) -> Option<u64> {
+        self.touch
+    }
+
+    /// Returns `true` if the button is pressed.
+    pub fn pressed(&self) -> bool {
+        self.touch.is_some()
+    }
+}
+
+/// Updates the virtual controls from the [`Touches`], and sends the corresponding
+/// [`GamepadEvent`]s.
+///
+/// Virtual gamepads are connected when a control first refers to them, and disconnected once
+/// no control does.
+pub fn virtual_gamepad_system(
+    touches: Res<Touches>,
+    mut sticks: Query<(&VirtualStick, &mut VirtualStickState)>,
+    mut buttons: Query<(&VirtualButton, &mut VirtualButtonState)>,
+    mut gamepad_events: EventWriter<GamepadEvent>,
+    mut connected: Local<HashSet<Gamepad>>,
+) {
+    let used: HashSet<Gamepad> = sticks
+        .iter()
+        .map(|(stick, _)| stick.gamepad)
+        .chain(buttons.iter().map(|(button, _)| button.gamepad))
+        .collect();
+    for &gamepad in used.difference(&connected) {
+        gamepad_events.send(
+            GamepadConnectionEvent::new(
+                gamepad,
+                GamepadConnection::Connected(GamepadInfo {
+                    name: "Virtual Gamepad".to_string(),
+                }),
+            )
+            .into(),
+        );
+    }
+    for &gamepad in connected.difference(&used) {
+        gamepad_events
+            .send(GamepadConnectionEvent::new(gamepad, GamepadConnection::Disconnected).into());
+    }
+    *connected = used;
+
+    // A touch controls at most one virtual control.
+    let mut claimed: HashSet<u64> = sticks
+        .iter()
+        .filter_map(|(_, state)| state.touch)
+        .chain(buttons.iter().filter_map(|(_, state)| state.touch))
+        .collect();
+    let mut claim = |area: Rect| {
+        let touch = touches
+            .iter_just_pressed()
+            .find(|touch| !claimed.contains(&touch.id()) && area.contains(touch.position()))?;
+        claimed.insert(touch.id());
+        Some(touch)
+    };
+
+    for (stick, mut state) in &mut sticks {
+        let held = state.touch;
+        let touch = match held {
+            Some(id) => touches.get_pressed(id),
+            None => claim(stick.touch_area).map(|touch| {
+                state.touch = Some(touch.id());
+                state.center = match stick.mode {
+                    VirtualStickMode::Fixed => None,
+                    VirtualStickMode::Floating | VirtualStickMode::Dynamic => {
+                        Some(touch.position())
+                    }
+                };
+                touch
+            }),
+        };
+
+        let tilt = match touch {
+            Some(touch) => {
+                let mut offset = touch.position() - state.center(stick);
+                if stick.mode == VirtualStickMode::Dynamic && offset.length() > stick.radius {
+                    let drag = offset - offset.normalize() * stick.radius;
+                    state.center = Some(state.center(stick) + drag);
+                    offset -= drag;
+                }
+                stick.tilt(offset)
+            }
+            None => {
+                state.touch = None;
+                state.center = None;
+                Vec2::ZERO
+            }
+        };
+
+        if tilt.x != state.tilt.x {
+            gamepad_events
+                .send(GamepadAxisChangedEvent::new(stick.gamepad, stick.x_axis, tilt.x).into());
+        }
+        if tilt.y != state.tilt.y {
+            gamepad_events
+                .send(GamepadAxisChangedEvent::new(stick.gamepad, stick.y_axis, tilt.y).into());
+        }
+        if tilt != state.tilt {
+            state.tilt = tilt;
+        }
+    }
+
+    for (button, mut state) in &mut buttons {
+        let was_pressed = state.pressed();
+        let held = state.touch;
+        match held {
+            Some(id) if touches.get_pressed(id).is_none() => state.touch = None,
+            Some(_) => {}
+            None => state.touch = claim(button.touch_area).map(|touch| touch.id()),
+        }
+        if state.pressed() != was_pressed {
+            let value = if state.pressed() { 1.0 } else { 0.0 };
+            gamepad_events
+                .send(GamepadButtonChangedEvent::new(button.gamepad, button.button, value).into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gamepad::{GamepadAxis, GamepadButton},
+        touch::{TouchInput, TouchPhase},
+        Axis, ButtonInput, InputPlugin,
+    };
+    use bevy_app::App;
+
+    fn touch(app: &mut App, phase: TouchPhase, position: Vec2) {
+        app.world_mut().send_event(TouchInput {
+            phase,
+            position,
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id: 0,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn stick_tilt_dead_zone() {
+        let stick = VirtualStick {
+            radius: 10.0,
+            dead_zone: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(stick.tilt(Vec2::new(4.0, 0.0)), Vec2::ZERO);
+        assert_eq!(stick.tilt(Vec2::new(7.5, 0.0)), Vec2::new(0.5, 0.0));
+        assert_eq!(stick.tilt(Vec2::new(0.0, 20.0)), Vec2::new(0.0, -1.0));
+        assert_eq!(stick.tilt(Vec2::ZERO), Vec2::ZERO);
+    }
+
+    #[test]
+    fn virtual_controls_drive_gamepad() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+        app.world_mut().spawn(VirtualStick {
+            touch_area: Rect::new(0.0, 0.0, 100.0, 100.0),
+            radius: 10.0,
+            dead_zone: 0.0,
+            mode: VirtualStickMode::Floating,
+            ..Default::default()
+        });
+        app.world_mut().spawn(VirtualButton {
+            touch_area: Rect::new(100.0, 0.0, 200.0, 100.0),
+            ..Default::default()
+        });
+        app.update();
+
+        let x_axis = GamepadAxis::new(VIRTUAL_GAMEPAD, GamepadAxisType::LeftStickX);
+        let south = GamepadButton::new(VIRTUAL_GAMEPAD, GamepadButtonType::South);
+
+        touch(&mut app, TouchPhase::Started, Vec2::new(50.0, 50.0));
+        assert_eq!(
+            app.world().resource::<Axis<GamepadAxis>>().get(x_axis),
+            Some(0.0)
+        );
+        touch(&mut app, TouchPhase::Moved, Vec2::new(60.0, 50.0));
+        assert_eq!(
+            app.world().resource::<Axis<GamepadAxis>>().get(x_axis),
+            Some(1.0)
+        );
+        touch(&mut app, TouchPhase::Ended, Vec2::new(60.0, 50.0));
+        assert_eq!(
+            app.world().resource::<Axis<GamepadAxis>>().get(x_axis),
+            Some(0.0)
+        );
+
+        touch(&mut app, TouchPhase::Started, Vec2::new(150.0, 50.0));
+        assert!(app
+            .world()
+            .resource::<ButtonInput<GamepadButton>>()
+            .pressed(south));
+        touch(&mut app, TouchPhase::Ended, Vec2::new(150.0, 50.0));
+        assert!(!app
+            .world()
+            .resource::<ButtonInput<GamepadButton>>()
+            .pressed(south));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    primitives::{InfinitePlane3d, Plane2d},
-    Dir2, Dir3, Vec2, Vec3,
+    bounding::{Aabb2d, Aabb3d, BoundingCircle, BoundingSphere, RayCast2d, RayCast3d},
+    primitives::{InfinitePlane3d, Plane2d, Triangle3d},
+    Dir2, Dir3, Quat, Rotation2d, Vec2, Vec3,
 };
 
 #[cfg(feature = "bevy_reflect")]
@@ -55,6 +56,42 @@ impl Ray2d {
         }
         None
     }
+
+    /// Get the distance to an [`Aabb2d`] if the ray intersects it
+    ///
+    /// The distance is zero if the ray starts inside of the box.
+    #[inline]
+    pub fn intersect_aabb(&self, aabb: &Aabb2d) -> Option<f32> {
+        RayCast2d::from_ray(*self, f32::MAX).aabb_intersection_at(aabb)
+    }
+
+    /// Get the distance to a [`BoundingCircle`] if the ray intersects it
+    ///
+    /// The distance is zero if the ray starts inside of the circle.
+    #[inline]
+    pub fn intersect_circle(&self, circle: &BoundingCircle) -> Option<f32> {
+        RayCast2d::from_ray(*self, f32::MAX).circle_intersection_at(circle)
+    }
+
+    /// Get the distance to an oriented box centered at `center` and rotated by `rotation` if the
+    /// ray intersects it
+    ///
+    /// The distance is zero if the ray starts inside of the box.
+    #[inline]
+    pub fn intersect_obb(
+        &self,
+        center: Vec2,
+        rotation: Rotation2d,
+        half_size: Vec2,
+    ) -> Option<f32> {
+        // Rotations preserve distances, so the distance in the space of the box is the same.
+        let inverse = rotation.inverse();
+        let local_ray = Self {
+            origin: inverse * (self.origin - center),
+            direction: inverse * self.direction,
+        };
+        local_ray.intersect_aabb(&Aabb2d::new(Vec2::ZERO, half_size))
+    }
 }
 
 /// An infinite half-line starting at `origin` and going in `direction` in 3D space.
@@ -104,6 +141,67 @@ impl Ray3d {
         }
         None
     }
+
+    /// Get the distance to an [`Aabb3d`] if the ray intersects it
+    ///
+    /// The distance is zero if the ray starts inside of the box.
+    #[inline]
+    pub fn intersect_aabb(&self, aabb: &Aabb3d) -> Option<f32> {
+        RayCast3d::from_ray(*self, f32::MAX).aabb_intersection_at(aabb)
+    }
+
+    /// Get the distance to a [`BoundingSphere`] if the ray intersects it
+    ///
+    /// The distance is zero if the ray starts inside of the sphere.
+    #[inline]
+    pub fn intersect_sphere(&self, sphere: &BoundingSphere) -> Option<f32> {
+        RayCast3d::from_ray(*self, f32::MAX).sphere_intersection_at(sphere)
+    }
+
+    /// Get the distance to an oriented box centered at `center` and rotated by `rotation` if the
+    /// ray intersects it
+    ///
+    /// The distance is zero if the ray starts inside of the box.
+    #[inline]
+    pub fn intersect_obb(&self, center: Vec3, rotation: Quat, half_size: Vec3) -> Option<f32> {
+        // Rotations preserve distances, so the distance in the space of the box is the same.
+        let inverse = rotation.inverse();
+        let local_ray = Self {
+            origin: inverse * (self.origin - center),
+            direction: inverse * self.direction,
+        };
+        local_ray.intersect_aabb(&Aabb3d::new(Vec3::ZERO, half_size))
+    }
+
+    /// Get the distance to a [`Triangle3d`] if the ray intersects it, from either side
+    #[inline]
+    pub fn intersect_triangle(&self, triangle: &Triangle3d) -> Option<f32> {
+        // Möller–Trumbore intersection, solving for the barycentric coordinates of the hit
+        let [a, b, c] = triangle.vertices;
+        let edge_ab = b - a;
+        let edge_ac = c - a;
+        let p = self.direction.cross(edge_ac);
+        let determinant = edge_ab.dot(p);
+        // The ray is parallel to the triangle
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let inverse_determinant = determinant.recip();
+        let offset = self.origin - a;
+        let u = offset.dot(p) * inverse_determinant;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(edge_ab);
+        let v = self.direction.dot(q) * inverse_determinant;
+        if v < 0. || u + v > 1. {
+            return None;
+        }
+
+        let distance = edge_ac.dot(q) * inverse_determinant;
+        (distance > f32::EPSILON).then_some(distance)
+    }
 }
 
 #[cfg(test)]
@@ -192,4 +290,92 @@ mod tests {
             )
             .is_none());
     }
+
+    #[test]
+    fn intersect_bounding_volumes_2d() {
+        let ray = Ray2d::new(Vec2::ZERO, Vec2::X);
+
+        let aabb = Aabb2d::new(Vec2::new(3., 0.), Vec2::ONE);
+        assert_eq!(ray.intersect_aabb(&aabb), Some(2.));
+        assert_eq!(
+            Ray2d::new(Vec2::new(3., 0.), Vec2::X).intersect_aabb(&aabb),
+            Some(0.)
+        );
+        assert!(ray
+            .intersect_aabb(&Aabb2d::new(Vec2::new(-3., 0.), Vec2::ONE))
+            .is_none());
+
+        let circle = BoundingCircle::new(Vec2::new(3., 0.), 1.);
+        assert_eq!(ray.intersect_circle(&circle), Some(2.));
+        assert!(ray
+            .intersect_circle(&BoundingCircle::new(Vec2::new(3., 2.), 1.))
+            .is_none());
+
+        // The corner of the rotated square is closer than its side
+        let distance = ray
+            .intersect_obb(
+                Vec2::new(3., 0.),
+                Rotation2d::degrees(45.),
+                Vec2::splat(0.5),
+            )
+            .unwrap();
+        assert!((distance - (3. - std::f32::consts::FRAC_1_SQRT_2)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn intersect_bounding_volumes_3d() {
+        let ray = Ray3d::new(Vec3::ZERO, Vec3::Z);
+
+        let aabb = Aabb3d::new(Vec3::new(0., 0., 3.), Vec3::ONE);
+        assert_eq!(ray.intersect_aabb(&aabb), Some(2.));
+        assert!(ray
+            .intersect_aabb(&Aabb3d::new(Vec3::new(0., 0., -3.), Vec3::ONE))
+            .is_none());
+
+        let sphere = BoundingSphere::new(Vec3::new(0., 0., 3.), 1.);
+        assert_eq!(ray.intersect_sphere(&sphere), Some(2.));
+        assert!(ray
+            .intersect_sphere(&BoundingSphere::new(Vec3::new(2., 0., 3.), 1.))
+            .is_none());
+
+        // The corner of the rotated cube is closer than its face
+        let distance = ray
+            .intersect_obb(
+                Vec3::new(0., 0., 3.),
+                Quat::from_rotation_y(std::f32::consts::FRAC_PI_4),
+                Vec3::splat(0.5),
+            )
+            .unwrap();
+        assert!((distance - (3. - std::f32::consts::FRAC_1_SQRT_2)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn intersect_triangle() {
+        let ray = Ray3d::new(Vec3::ZERO, Vec3::Z);
+        let triangle = Triangle3d::new(
+            Vec3::new(-1., -1., 2.),
+            Vec3::new(1., -1., 2.),
+            Vec3::new(0., 1., 2.),
+        );
+        assert_eq!(ray.intersect_triangle(&triangle), Some(2.));
+
+        // Both sides are hit
+        let reversed = Triangle3d::new(
+            triangle.vertices[0],
+            triangle.vertices[2],
+            triangle.vertices[1],
+        );
+        assert_eq!(ray.intersect_triangle(&reversed), Some(2.));
+
+        // Behind, outside of the triangle, and parallel to it
+        assert!(Ray3d::new(Vec3::ZERO, Vec3::NEG_Z)
+            .intersect_triangle(&triangle)
+            .is_none());
+        assert!(Ray3d::new(Vec3::new(2., 0., 0.), Vec3::Z)
+            .intersect_triangle(&triangle)
+            .is_none());
+        assert!(Ray3d::new(Vec3::new(0., -2., 2.), Vec3::Y)
+            .intersect_triangle(&triangle)
+            .is_none());
+    }
 }
@@ -344,6 +344,93 @@ impl IRect {
         r
     }
 
+    /// Create a new rectangle by moving each side inwards by a separate amount.
+    ///
+    /// The minimum corner is moved by `min_inset` and the maximum corner by `-max_inset`, so
+    /// positive values produce a smaller rectangle. This is useful to remove borders or padding,
+    /// whose thickness can differ on each side. If this would result in a negative width or
+    /// height, the rectangle is collapsed instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::{IRect, IVec2};
+    /// let r = IRect::new(0, 0, 10, 6); // w=10 h=6
+    /// let r2 = r.inset(IVec2::new(1, 2), IVec2::new(3, 0)); // w=6 h=4
+    /// assert_eq!(r2.min, IVec2::new(1, 2));
+    /// assert_eq!(r2.max, IVec2::new(7, 6));
+    /// ```
+    #[inline]
+    pub fn inset(&self, min_inset: IVec2, max_inset: IVec2) -> Self {
+        let mut r = Self {
+            min: self.min + min_inset,
+            max: self.max - max_inset,
+        };
+        // Collapse min over max to enforce invariants and ensure e.g. width() or
+        // height() never return a negative value.
+        r.min = r.min.min(r.max);
+        r
+    }
+
+    /// Check if this rectangle overlaps another one.
+    ///
+    /// Rectangles that only share an edge or a corner don't overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::IRect;
+    /// let r1 = IRect::new(0, 0, 5, 1); // w=5 h=1
+    /// assert!(r1.intersects(IRect::new(1, -1, 3, 3)));
+    /// assert!(!r1.intersects(IRect::new(5, 0, 6, 1)));
+    /// ```
+    #[inline]
+    pub fn intersects(&self, other: Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Check if this rectangle entirely contains another one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::IRect;
+    /// let r = IRect::new(0, 0, 5, 5); // w=5 h=5
+    /// assert!(r.contains_rect(IRect::new(1, 1, 5, 3)));
+    /// assert!(!r.contains_rect(IRect::new(1, 1, 6, 3)));
+    /// ```
+    #[inline]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        (other.min.cmpge(self.min) & other.max.cmple(self.max)).all()
+    }
+
+    /// The four corners of the rectangle.
+    ///
+    /// The corners are ordered starting from [`IRect::min`], going along the X axis first:
+    /// `[(min.x, min.y), (max.x, min.y), (max.x, max.y), (min.x, max.y)]`. In coordinate systems
+    /// where the Y axis points down, such as UI, this is the top-left, top-right, bottom-right and
+    /// bottom-left corners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::{IRect, IVec2};
+    /// let r = IRect::new(0, 0, 5, 1); // w=5 h=1
+    /// assert_eq!(
+    ///     r.corners(),
+    ///     [IVec2::ZERO, IVec2::new(5, 0), IVec2::new(5, 1), IVec2::new(0, 1)]
+    /// );
+    /// ```
+    #[inline]
+    pub fn corners(&self) -> [IVec2; 4] {
+        [
+            self.min,
+            IVec2::new(self.max.x, self.min.y),
+            self.max,
+            IVec2::new(self.min.x, self.max.y),
+        ]
+    }
+
     /// Returns self as [`Rect`] (f32)
     #[inline]
     pub fn as_rect(&self) -> Rect {
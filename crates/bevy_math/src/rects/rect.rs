@@ -332,6 +332,164 @@ impl Rect {
         r
     }
 
+    /// Create a new rectangle by moving each side inwards by a separate amount.
+    ///
+    /// The minimum corner is moved by `min_inset` and the maximum corner by `-max_inset`, so
+    /// positive values produce a smaller rectangle. This is useful to remove borders or padding,
+    /// whose thickness can differ on each side. If this would result in a negative width or
+    /// height, the rectangle is collapsed instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::{Rect, Vec2};
+    /// let r = Rect::new(0., 0., 10., 6.); // w=10 h=6
+    /// let r2 = r.inset(Vec2::new(1., 2.), Vec2::new(3., 0.)); // w=6 h=4
+    /// assert!(r2.min.abs_diff_eq(Vec2::new(1., 2.), 1e-5));
+    /// assert!(r2.max.abs_diff_eq(Vec2::new(7., 6.), 1e-5));
+    /// ```
+    #[inline]
+    pub fn inset(&self, min_inset: Vec2, max_inset: Vec2) -> Self {
+        let mut r = Self {
+            min: self.min + min_inset,
+            max: self.max - max_inset,
+        };
+        // Collapse min over max to enforce invariants and ensure e.g. width() or
+        // height() never return a negative value.
+        r.min = r.min.min(r.max);
+        r
+    }
+
+    /// Check if this rectangle overlaps another one.
+    ///
+    /// Rectangles that only share an edge or a corner don't overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::Rect;
+    /// let r1 = Rect::new(0., 0., 5., 1.); // w=5 h=1
+    /// assert!(r1.intersects(Rect::new(1., -1., 3., 3.)));
+    /// assert!(!r1.intersects(Rect::new(5., 0., 6., 1.)));
+    /// ```
+    #[inline]
+    pub fn intersects(&self, other: Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Check if this rectangle entirely contains another one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 5., 5.); // w=5 h=5
+    /// assert!(r.contains_rect(Rect::new(1., 1., 5., 3.)));
+    /// assert!(!r.contains_rect(Rect::new(1., 1., 6., 3.)));
+    /// ```
+    #[inline]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        (other.min.cmpge(self.min) & other.max.cmple(self.max)).all()
+    }
+
+    /// The four corners of the rectangle.
+    ///
+    /// The corners are ordered starting from [`Rect::min`], going along the X axis first:
+    /// `[(min.x, min.y), (max.x, min.y), (max.x, max.y), (min.x, max.y)]`. In coordinate systems
+    /// where the Y axis points down, such as UI, this is the top-left, top-right, bottom-right and
+    /// bottom-left corners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::{Rect, Vec2};
+    /// let r = Rect::new(0., 0., 5., 1.); // w=5 h=1
+    /// assert_eq!(
+    ///     r.corners(),
+    ///     [Vec2::ZERO, Vec2::new(5., 0.), Vec2::new(5., 1.), Vec2::new(0., 1.)]
+    /// );
+    /// ```
+    #[inline]
+    pub fn corners(&self) -> [Vec2; 4] {
+        [
+            self.min,
+            Vec2::new(self.max.x, self.min.y),
+            self.max,
+            Vec2::new(self.min.x, self.max.y),
+        ]
+    }
+
+    /// Limit the radii of rounded corners so that they fit in this rectangle.
+    ///
+    /// Each radius is clamped between zero and half the length of the shortest side. The radii
+    /// are ordered like the [`corners`](Self::corners) of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 10., 4.); // w=10 h=4
+    /// assert_eq!(r.clamp_corner_radii([1., 5., -1., 2.]), [1., 2., 0., 2.]);
+    /// ```
+    #[inline]
+    pub fn clamp_corner_radii(&self, corner_radii: [f32; 4]) -> [f32; 4] {
+        let max_radius = 0.5 * self.size().min_element();
+        corner_radii.map(|radius| radius.clamp(0., max_radius.max(0.)))
+    }
+
+    /// The signed distance from `point` to the boundary of this rectangle with rounded corners.
+    ///
+    /// The distance is negative inside the rectangle and positive outside of it. The radii are
+    /// ordered like the [`corners`](Self::corners) of the rectangle, and should have been limited
+    /// with [`Rect::clamp_corner_radii`]. This is the same function used to draw rounded UI nodes
+    /// in shaders, so it can be used to find the points covered by them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::{Rect, Vec2};
+    /// let r = Rect::new(0., 0., 10., 10.); // w=10 h=10
+    /// let radii = [4., 0., 0., 0.];
+    /// assert!((r.rounded_signed_distance(Vec2::new(5., 0.), radii)).abs() < 1e-5);
+    /// assert!(r.rounded_signed_distance(Vec2::new(5., 5.), radii) < 0.);
+    /// // The rounded corner doesn't cover its own corner point.
+    /// assert!(r.rounded_signed_distance(Vec2::ZERO, radii) > 0.);
+    /// ```
+    pub fn rounded_signed_distance(&self, point: Vec2, corner_radii: [f32; 4]) -> f32 {
+        let point = point - self.center();
+        let [min_min, max_min, max_max, min_max] = corner_radii;
+        let radius = match (point.x > 0., point.y > 0.) {
+            (false, false) => min_min,
+            (true, false) => max_min,
+            (true, true) => max_max,
+            (false, true) => min_max,
+        };
+        // Vector from the corner closest to the point, to the point.
+        let corner_to_point = point.abs() - self.half_size();
+        // Vector from the center of the radius circle to the point.
+        let q = corner_to_point + radius;
+        q.max(Vec2::ZERO).length() + q.max_element().min(0.) - radius
+    }
+
+    /// Check if a point lies within this rectangle with rounded corners, inclusive of its edges.
+    ///
+    /// The radii are ordered like the [`corners`](Self::corners) of the rectangle. See
+    /// [`Rect::rounded_signed_distance`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_math::{Rect, Vec2};
+    /// let r = Rect::new(0., 0., 10., 10.); // w=10 h=10
+    /// let radii = r.clamp_corner_radii([5.; 4]);
+    /// assert!(r.contains_rounded(Vec2::new(5., 1.), radii));
+    /// assert!(!r.contains_rounded(Vec2::new(1., 1.), radii));
+    /// ```
+    #[inline]
+    pub fn contains_rounded(&self, point: Vec2, corner_radii: [f32; 4]) -> bool {
+        self.rounded_signed_distance(point, corner_radii) <= 0.
+    }
+
     /// Build a new rectangle from this one with its coordinates expressed
     /// relative to `other` in a normalized ([0..1] x [0..1]) coordinate system.
     ///
@@ -490,4 +648,35 @@ mod tests {
         assert!(r2.min.abs_diff_eq(Vec2::new(-0.8, -0.8), 1e-5));
         assert!(r2.max.abs_diff_eq(Vec2::new(0.8, 0.8), 1e-5));
     }
+
+    #[test]
+    fn rect_inset() {
+        let r = Rect::from_center_size(Vec2::ZERO, Vec2::ONE); // [-0.5,-0.5] - [0.5,0.5]
+
+        let r2 = r.inset(Vec2::new(0.1, 0.2), Vec2::new(0.3, 0.));
+        assert!(r2.min.abs_diff_eq(Vec2::new(-0.4, -0.3), 1e-5));
+        assert!(r2.max.abs_diff_eq(Vec2::new(0.2, 0.5), 1e-5));
+
+        // collapsed
+        let r2 = r.inset(Vec2::splat(0.8), Vec2::splat(0.8));
+        assert!(r2.is_empty());
+        assert!(r2.width() <= 1e-5);
+    }
+
+    #[test]
+    fn rect_rounded() {
+        let r = Rect::from_center_size(Vec2::ZERO, Vec2::splat(10.)); // [-5,-5] - [5,5]
+        let radii = r.clamp_corner_radii([1., 2., 3., 4.]);
+
+        // Each radius only affects its own corner.
+        for (corner, radius) in r.corners().into_iter().zip(radii) {
+            let inward = -corner.signum();
+            assert!(!r.contains_rounded(corner + 0.2 * radius * inward, radii));
+            assert!(r.contains_rounded(corner + radius * inward, radii));
+        }
+
+        // Without rounding, this is the distance to the edges.
+        assert!((r.rounded_signed_distance(Vec2::new(1., 0.), [0.; 4]) + 4.).abs() < 1e-5);
+        assert!((r.rounded_signed_distance(Vec2::new(0., 7.), [0.; 4]) - 2.).abs() < 1e-5);
+    }
 }
@@ -284,6 +284,7 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
             material_layout,
             vertex_shader,
             fragment_shader,
+            instance_data_layout,
             ..
         } = pipeline.clone();
         let base_pipeline = MaterialPipeline::<B> {
@@ -291,6 +292,7 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
             material_layout,
             vertex_shader,
             fragment_shader,
+            instance_data_layout,
             marker: Default::default(),
         };
         let base_key = MaterialPipelineKey::<B> {
@@ -0,0 +1,304 @@
+//! Per-instance data for materials.
+//!
+//! Entities that share the same mesh and material are automatically batched into a single
+//! instanced draw call. [`InstancedMaterial`] extends this to materials whose appearance also
+//! depends on a small amount of per-entity data, such as a tint color, without breaking the
+//! batches: the per-entity data of all the entities using the material is packed into a single
+//! storage buffer that the material's shaders index per instance.
+
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::SRes, SystemParamItem},
+};
+use bevy_render::{
+    batching::gpu_preprocessing::BatchedInstanceBuffers,
+    render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
+    render_resource::{
+        binding_types::storage_buffer_read_only, encase::internal::WriteInto, BindGroup,
+        BindGroupEntries, BindGroupLayoutEntries, BindGroupLayoutEntry, ShaderSize, ShaderStages,
+        ShaderType, StorageBuffer,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    ExtractMeshesSet, Material, MaterialPipeline, MeshInputUniform, MeshUniform,
+    RenderMeshInstances,
+};
+
+/// A [`Material`] whose shaders read a value of [`InstancedMaterial::InstanceData`] for each
+/// entity using it.
+///
+/// The per-instance data is read from the [`InstancedMaterial::InstanceData`] component of each
+/// entity, or its [`Default`] value if the entity doesn't have one. Changing it doesn't require
+/// creating a new material asset, so entities that only differ by their per-instance data keep
+/// sharing the same instanced draw calls.
+///
+/// Instanced materials require the [`InstancedMaterialPlugin`] to be added alongside the
+/// [`MaterialPlugin`](crate::MaterialPlugin).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_pbr::{InstancedMaterial, Material};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_render::render_resource::{AsBindGroup, ShaderRef, ShaderType};
+/// # use bevy_color::LinearRgba;
+/// # use bevy_asset::Asset;
+/// #[derive(AsBindGroup, Debug, Clone, Asset, TypePath)]
+/// pub struct TintedMaterial {}
+///
+/// impl Material for TintedMaterial {
+///     fn vertex_shader() -> ShaderRef {
+///         "shaders/tinted_material.wgsl".into()
+///     }
+///
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/tinted_material.wgsl".into()
+///     }
+/// }
+///
+/// // The per-instance data, stored as a component on each entity.
+/// #[derive(Component, ShaderType, Clone, Default)]
+/// pub struct Tint {
+///     color: LinearRgba,
+/// }
+///
+/// impl InstancedMaterial for TintedMaterial {
+///     type InstanceData = Tint;
+/// }
+/// ```
+///
+/// In WGSL shaders, the per-instance data is bound as a storage buffer, which is indexed with the
+/// index stored in the mesh uniform:
+///
+/// ```wgsl
+/// #import bevy_pbr::mesh_functions::get_instance_data_index
+///
+/// struct Tint {
+///     color: vec4<f32>,
+/// }
+///
+/// @group(3) @binding(0) var<storage> tints: array<Tint>;
+///
+/// let tint = tints[get_instance_data_index(instance_index)];
+/// ```
+///
+/// The `MATERIAL_INSTANCE_DATA` shader def is set when this binding is available. The per-instance
+/// data is only bound in the main passes: it isn't available in the prepass and shadow passes.
+/// It requires storage buffers, so it isn't supported on WebGL 2.
+pub trait InstancedMaterial: Material {
+    /// The data read by the shaders of this material for each entity.
+    type InstanceData: Component + ShaderType + ShaderSize + WriteInto + Clone + Default;
+}
+
+/// Adds the necessary ECS resources and render logic to upload the per-instance data of the
+/// given [`InstancedMaterial`] type.
+///
+/// This must be added alongside the [`MaterialPlugin`](crate::MaterialPlugin) of the material.
+pub struct InstancedMaterialPlugin<M: InstancedMaterial>(PhantomData<M>);
+
+impl<M: InstancedMaterial> Default for InstancedMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: InstancedMaterial> Plugin for InstancedMaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .insert_resource(MaterialInstanceDataLayout::<M> {
+                entries: BindGroupLayoutEntries::single(
+                    ShaderStages::VERTEX_FRAGMENT,
+                    storage_buffer_read_only::<M::InstanceData>(false),
+                )
+                .to_vec(),
+                marker: PhantomData,
+            })
+            .init_resource::<RenderMaterialInstanceData<M>>()
+            .init_resource::<MaterialInstanceDataBindGroup<M>>()
+            .add_systems(
+                ExtractSchedule,
+                extract_material_instance_data::<M>.after(ExtractMeshesSet),
+            )
+            .add_systems(
+                Render,
+                (
+                    prepare_material_instance_data::<M>.in_set(RenderSet::PrepareResources),
+                    prepare_material_instance_data_bind_group::<M>
+                        .in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+}
+
+/// The bind group layout entries of the per-instance data of a [`Material`], if it is an
+/// [`InstancedMaterial`].
+///
+/// This is read by [`MaterialPipeline`] to create its `instance_data_layout`.
+#[derive(Resource)]
+pub(crate) struct MaterialInstanceDataLayout<M: Material> {
+    pub(crate) entries: Vec<BindGroupLayoutEntry>,
+    marker: PhantomData<M>,
+}
+
+/// The per-instance data of all the visible entities using the [`InstancedMaterial`] `M`.
+#[derive(Resource)]
+pub struct RenderMaterialInstanceData<M: InstancedMaterial> {
+    /// The buffer containing the per-instance data.
+    ///
+    /// Each mesh instance using the material stores the index of its data in this buffer in its
+    /// [`MeshUniform`].
+    pub buffer: StorageBuffer<Vec<M::InstanceData>>,
+}
+
+impl<M: InstancedMaterial> Default for RenderMaterialInstanceData<M> {
+    fn default() -> Self {
+        let mut buffer = StorageBuffer::default();
+        buffer.set_label(Some("material_instance_data_buffer"));
+        Self { buffer }
+    }
+}
+
+/// The bind group of the per-instance data of a [`Material`], if it is an [`InstancedMaterial`].
+#[derive(Resource)]
+pub struct MaterialInstanceDataBindGroup<M: Material> {
+    bind_group: Option<BindGroup>,
+    marker: PhantomData<M>,
+}
+
+impl<M: Material> Default for MaterialInstanceDataBindGroup<M> {
+    fn default() -> Self {
+        Self {
+            bind_group: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Gathers the per-instance data of the visible entities using the [`InstancedMaterial`] `M`,
+/// and stores its index in their mesh instance.
+pub fn extract_material_instance_data<M: InstancedMaterial>(
+    mut material_instance_data: ResMut<RenderMaterialInstanceData<M>>,
+    mut render_mesh_instances: ResMut<RenderMeshInstances>,
+    mut batched_instance_buffers: Option<
+        ResMut<BatchedInstanceBuffers<MeshUniform, MeshInputUniform>>,
+    >,
+    instances_query: Extract<Query<(Entity, Option<&M::InstanceData>), With<Handle<M>>>>,
+) {
+    let values = material_instance_data.buffer.get_mut();
+    values.clear();
+
+    for (entity, instance_data) in &instances_query {
+        let index = values.len() as u32;
+        match *render_mesh_instances {
+            RenderMeshInstances::CpuBuilding(ref mut render_mesh_instances) => {
+                // Invisible entities aren't extracted.
+                let Some(render_mesh_instance) = render_mesh_instances.get_mut(&entity) else {
+                    continue;
+                };
+                render_mesh_instance.shared.instance_data_index = index;
+            }
+            RenderMeshInstances::GpuBuilding(ref mut render_mesh_instances) => {
+                let Some(render_mesh_instance) = render_mesh_instances.get_mut(&entity) else {
+                    continue;
+                };
+                render_mesh_instance.shared.instance_data_index = index;
+
+                // The mesh input uniforms have already been built at this point, so patch the
+                // index in.
+                let Some(mesh_input_uniform) =
+                    batched_instance_buffers.as_mut().and_then(|buffers| {
+                        buffers
+                            .current_input_buffer
+                            .values_mut()
+                            .get_mut(u32::from(render_mesh_instance.current_uniform_index) as usize)
+                    })
+                else {
+                    continue;
+                };
+                mesh_input_uniform.instance_data_index = index;
+            }
+        }
+
+        values.push(instance_data.cloned().unwrap_or_default());
+    }
+}
+
+/// Uploads the per-instance data of the [`InstancedMaterial`] `M` to the GPU.
+pub fn prepare_material_instance_data<M: InstancedMaterial>(
+    mut material_instance_data: ResMut<RenderMaterialInstanceData<M>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if material_instance_data.buffer.get().is_empty() {
+        return;
+    }
+    material_instance_data
+        .buffer
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// Creates the [`MaterialInstanceDataBindGroup`] of the [`InstancedMaterial`] `M`.
+pub fn prepare_material_instance_data_bind_group<M: InstancedMaterial>(
+    mut bind_group: ResMut<MaterialInstanceDataBindGroup<M>>,
+    material_instance_data: Res<RenderMaterialInstanceData<M>>,
+    material_pipeline: Res<MaterialPipeline<M>>,
+    render_device: Res<RenderDevice>,
+) {
+    bind_group.bind_group = None;
+    if material_instance_data.buffer.get().is_empty() {
+        return;
+    }
+    let (Some(layout), Some(binding)) = (
+        material_pipeline.instance_data_layout.as_ref(),
+        material_instance_data.buffer.binding(),
+    ) else {
+        return;
+    };
+    bind_group.bind_group = Some(render_device.create_bind_group(
+        "material_instance_data_bind_group",
+        layout,
+        &BindGroupEntries::single(binding),
+    ));
+}
+
+/// Sets the bind group of the per-instance data of a [`Material`] at the configured `I` index.
+///
+/// This does nothing if the material isn't an [`InstancedMaterial`].
+pub struct SetMaterialInstanceDataBindGroup<M: Material, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: Material, const I: usize> RenderCommand<P>
+    for SetMaterialInstanceDataBindGroup<M, I>
+{
+    type Param = Option<SRes<MaterialInstanceDataBindGroup<M>>>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        _item_query: Option<()>,
+        bind_group: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = bind_group else {
+            return RenderCommandResult::Success;
+        };
+        let Some(bind_group) = &bind_group.into_inner().bind_group else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
@@ -25,6 +25,7 @@ mod bundle;
 pub mod deferred;
 mod extended_material;
 mod fog;
+mod instanced_material;
 mod light;
 mod light_probe;
 mod lightmap;
@@ -35,6 +36,7 @@ mod prepass;
 mod render;
 mod ssao;
 mod ssr;
+mod terrain;
 mod volumetric_fog;
 
 use bevy_color::{Color, LinearRgba};
@@ -43,6 +45,7 @@ use std::marker::PhantomData;
 pub use bundle::*;
 pub use extended_material::*;
 pub use fog::*;
+pub use instanced_material::*;
 pub use light::*;
 pub use light_probe::*;
 pub use lightmap::*;
@@ -53,6 +56,7 @@ pub use prepass::*;
 pub use render::*;
 pub use ssao::*;
 pub use ssr::*;
+pub use terrain::*;
 pub use volumetric_fog::*;
 
 pub mod prelude {
@@ -284,6 +288,7 @@ impl Plugin for PbrPlugin {
             .register_type::<CascadeShadowConfig>()
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
+            .register_type::<ContactShadows>()
             .register_type::<ClusterConfig>()
             .register_type::<CubemapVisibleEntities>()
             .register_type::<DirectionalLight>()
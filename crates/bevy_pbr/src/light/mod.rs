@@ -464,6 +464,40 @@ pub struct NotShadowReceiver;
 #[reflect(Component, Default)]
 pub struct TransmittedShadowReceiver;
 
+/// Add this component to a [`PointLight`], [`SpotLight`] or [`DirectionalLight`] to enable
+/// screen-space contact shadows for it.
+///
+/// Contact shadows are found by marching from each pixel towards the light through the depth
+/// prepass, and catch the small-scale occlusion that shadow maps miss, such as where objects
+/// touch the ground. They work whether or not the light has shadows enabled, and only appear on
+/// cameras with a [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass). Meshes with
+/// [`NotShadowReceiver`] don't receive them.
+///
+/// Contact shadows aren't supported on WebGL2, nor for point and spot lights on platforms
+/// without storage buffers, whose lights are stored in a uniform buffer.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct ContactShadows {
+    /// The distance towards the light that is searched for occluders, in world units.
+    ///
+    /// Longer distances find larger contact shadows, but miss more of the thin occluders.
+    pub length: f32,
+    /// The assumed thickness of the surfaces in the depth prepass, in world units.
+    ///
+    /// Only occluders within this distance in front of the ray cast a shadow, which avoids
+    /// shadows from surfaces far in front of the receiver.
+    pub thickness: f32,
+}
+
+impl Default for ContactShadows {
+    fn default() -> Self {
+        Self {
+            length: 0.1,
+            thickness: 0.05,
+        }
+    }
+}
+
 /// Add this component to a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d)
 /// to control how to anti-alias shadow edges.
 ///
@@ -2215,4 +2249,18 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn uniform_point_lights_fit_in_webgl2_uniform_buffer() {
+        use bevy_render::render_resource::ShaderType;
+
+        // The contact shadow fields are left out of the uniform buffer, which is at most 16384
+        // bytes on WebGL2
+        assert_eq!(GpuUniformPointLight::min_size().get(), 64);
+        assert_eq!(
+            GpuPointLightsUniform::min_size().get(),
+            64 * MAX_UNIFORM_BUFFER_POINT_LIGHTS as u64
+        );
+        assert!(GpuPointLightsUniform::min_size().get() <= 16384);
+    }
 }
@@ -10,6 +10,7 @@ use bevy_core_pipeline::{
         AlphaMask3d, Camera3d, Opaque3d, Opaque3dBinKey, ScreenSpaceTransmissionQuality,
         Transmissive3d, Transparent3d,
     },
+    half_resolution_transparency::HalfResolutionTransparent3d,
     prepass::{
         DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, OpaqueNoLightmap3dBinKey,
     },
@@ -260,6 +261,7 @@ where
                 .add_render_command::<Shadow, DrawPrepass<M>>()
                 .add_render_command::<Transmissive3d, DrawMaterial<M>>()
                 .add_render_command::<Transparent3d, DrawMaterial<M>>()
+                .add_render_command::<HalfResolutionTransparent3d, DrawMaterial<M>>()
                 .add_render_command::<Opaque3d, DrawMaterial<M>>()
                 .add_render_command::<AlphaMask3d, DrawMaterial<M>>()
                 .init_resource::<SpecializedMeshPipelines<MaterialPipeline<M>>>()
@@ -355,6 +357,9 @@ pub struct MaterialPipeline<M: Material> {
     pub material_layout: BindGroupLayout,
     pub vertex_shader: Option<Handle<Shader>>,
     pub fragment_shader: Option<Handle<Shader>>,
+    /// The layout of the per-instance data bind group, if the material is an
+    /// [`InstancedMaterial`] and the [`InstancedMaterialPlugin`] was added.
+    pub instance_data_layout: Option<BindGroupLayout>,
     pub marker: PhantomData<M>,
 }
 
@@ -365,6 +370,7 @@ impl<M: Material> Clone for MaterialPipeline<M> {
             material_layout: self.material_layout.clone(),
             vertex_shader: self.vertex_shader.clone(),
             fragment_shader: self.fragment_shader.clone(),
+            instance_data_layout: self.instance_data_layout.clone(),
             marker: PhantomData,
         }
     }
@@ -392,6 +398,17 @@ where
 
         descriptor.layout.insert(2, self.material_layout.clone());
 
+        if let Some(instance_data_layout) = &self.instance_data_layout {
+            descriptor.layout.insert(3, instance_data_layout.clone());
+            descriptor
+                .vertex
+                .shader_defs
+                .push("MATERIAL_INSTANCE_DATA".into());
+            if let Some(fragment) = descriptor.fragment.as_mut() {
+                fragment.shader_defs.push("MATERIAL_INSTANCE_DATA".into());
+            }
+        }
+
         M::specialize(self, &mut descriptor, layout, key)?;
         Ok(descriptor)
     }
@@ -415,6 +432,12 @@ impl<M: Material> FromWorld for MaterialPipeline<M> {
                 ShaderRef::Handle(handle) => Some(handle),
                 ShaderRef::Path(path) => Some(asset_server.load(path)),
             },
+            instance_data_layout: world.get_resource::<MaterialInstanceDataLayout<M>>().map(
+                |layout| {
+                    render_device
+                        .create_bind_group_layout("material_instance_data_layout", &layout.entries)
+                },
+            ),
             marker: PhantomData,
         }
     }
@@ -425,6 +448,7 @@ type DrawMaterial<M> = (
     SetMeshViewBindGroup<0>,
     SetMeshBindGroup<1>,
     SetMaterialBindGroup<M, 2>,
+    SetMaterialInstanceDataBindGroup<M, 3>,
     DrawMesh,
 );
 
@@ -521,11 +545,13 @@ pub fn queue_material_meshes<M: Material>(
         alpha_mask_draw_functions,
         transmissive_draw_functions,
         transparent_draw_functions,
+        half_resolution_transparent_draw_functions,
     ): (
         Res<DrawFunctions<Opaque3d>>,
         Res<DrawFunctions<AlphaMask3d>>,
         Res<DrawFunctions<Transmissive3d>>,
         Res<DrawFunctions<Transparent3d>>,
+        Res<DrawFunctions<HalfResolutionTransparent3d>>,
     ),
     material_pipeline: Res<MaterialPipeline<M>>,
     mut pipelines: ResMut<SpecializedMeshPipelines<MaterialPipeline<M>>>,
@@ -540,7 +566,10 @@ pub fn queue_material_meshes<M: Material>(
     mut opaque_render_phases: ResMut<ViewBinnedRenderPhases<Opaque3d>>,
     mut alpha_mask_render_phases: ResMut<ViewBinnedRenderPhases<AlphaMask3d>>,
     mut transmissive_render_phases: ResMut<ViewSortedRenderPhases<Transmissive3d>>,
-    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    (mut transparent_render_phases, mut half_resolution_transparent_render_phases): (
+        ResMut<ViewSortedRenderPhases<Transparent3d>>,
+        ResMut<ViewSortedRenderPhases<HalfResolutionTransparent3d>>,
+    ),
     mut views: Query<(
         Entity,
         &ExtractedView,
@@ -600,6 +629,14 @@ pub fn queue_material_meshes<M: Material>(
         let draw_alpha_mask_pbr = alpha_mask_draw_functions.read().id::<DrawMaterial<M>>();
         let draw_transmissive_pbr = transmissive_draw_functions.read().id::<DrawMaterial<M>>();
         let draw_transparent_pbr = transparent_draw_functions.read().id::<DrawMaterial<M>>();
+        let draw_half_resolution_transparent_pbr = half_resolution_transparent_draw_functions
+            .read()
+            .id::<DrawMaterial<M>>();
+
+        // Only present if the camera renders transparent objects at half
+        // resolution.
+        let mut half_resolution_transparent_phase =
+            half_resolution_transparent_render_phases.get_mut(&view_entity);
 
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
             | MeshPipelineKey::from_hdr(view.hdr);
@@ -701,6 +738,26 @@ pub fn queue_material_meshes<M: Material>(
                 mesh_key |= MeshPipelineKey::VISIBILITY_RANGE_DITHER;
             }
 
+            let is_blended = !matches!(
+                mesh_key.intersection(
+                    MeshPipelineKey::BLEND_RESERVED_BITS | MeshPipelineKey::MAY_DISCARD
+                ),
+                MeshPipelineKey::BLEND_OPAQUE
+                    | MeshPipelineKey::BLEND_ALPHA_TO_COVERAGE
+                    | MeshPipelineKey::MAY_DISCARD
+            );
+            let half_resolution_phase = half_resolution_transparent_phase.as_mut().filter(|_| {
+                is_blended
+                    && mesh_instance
+                        .flags
+                        .contains(RenderMeshInstanceFlags::HALF_RESOLUTION)
+            });
+            if half_resolution_phase.is_some() {
+                // The half resolution target is never multisampled.
+                mesh_key.remove(MeshPipelineKey::MSAA_RESERVED_BITS);
+                mesh_key |= MeshPipelineKey::HALF_RESOLUTION;
+            }
+
             let pipeline_id = pipelines.specialize(
                 &pipeline_cache,
                 &material_pipeline,
@@ -778,14 +835,25 @@ pub fn queue_material_meshes<M: Material>(
                 _ => {
                     let distance = rangefinder.distance_translation(&mesh_instance.translation)
                         + material.properties.depth_bias;
-                    transparent_phase.add(Transparent3d {
-                        entity: *visible_entity,
-                        draw_function: draw_transparent_pbr,
-                        pipeline: pipeline_id,
-                        distance,
-                        batch_range: 0..1,
-                        extra_index: PhaseItemExtraIndex::NONE,
-                    });
+                    if let Some(half_resolution_phase) = half_resolution_phase {
+                        half_resolution_phase.add(HalfResolutionTransparent3d {
+                            entity: *visible_entity,
+                            draw_function: draw_half_resolution_transparent_pbr,
+                            pipeline: pipeline_id,
+                            distance,
+                            batch_range: 0..1,
+                            extra_index: PhaseItemExtraIndex::NONE,
+                        });
+                    } else {
+                        transparent_phase.add(Transparent3d {
+                            entity: *visible_entity,
+                            draw_function: draw_transparent_pbr,
+                            pipeline: pipeline_id,
+                            distance,
+                            batch_range: 0..1,
+                            extra_index: PhaseItemExtraIndex::NONE,
+                        });
+                    }
                 }
             }
         }
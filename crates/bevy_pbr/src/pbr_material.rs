@@ -472,6 +472,49 @@ pub struct StandardMaterial {
     #[cfg(feature = "pbr_multi_layer_material_textures")]
     pub clearcoat_normal_texture: Option<Handle<Image>>,
 
+    /// A secondary color texture tiled over the surface to add close-up detail
+    /// to the [`StandardMaterial::base_color_texture`].
+    ///
+    /// The detail color is multiplied with the base color and doubled, so a
+    /// value of `0.5` leaves the base color unchanged, darker values darken it
+    /// and lighter values brighten it. It is sampled with the first UV channel,
+    /// scaled by [`StandardMaterial::detail_base_color_uv_scale`].
+    ///
+    /// As this is a modulation map, it should not be loaded as sRGB.
+    #[texture(25)]
+    #[sampler(26)]
+    #[cfg(feature = "pbr_detail_textures")]
+    pub detail_base_color_texture: Option<Handle<Image>>,
+
+    /// How many times the [`StandardMaterial::detail_base_color_texture`] tiles
+    /// over the UV range of the mesh.
+    ///
+    /// Defaults to [`Vec2::ONE`].
+    #[cfg(feature = "pbr_detail_textures")]
+    pub detail_base_color_uv_scale: Vec2,
+
+    /// A secondary normal map tiled over the surface to add close-up detail to
+    /// the [`StandardMaterial::normal_map_texture`].
+    ///
+    /// Both normal maps are blended together, and the detail normal map can also
+    /// be used without a main normal map. It must use the same format and
+    /// conventions as the main normal map, and has the same requirements. It is
+    /// sampled with the first UV channel, scaled by
+    /// [`StandardMaterial::detail_normal_map_uv_scale`].
+    ///
+    /// As this is a non-color map, it must not be loaded as sRGB.
+    #[texture(27)]
+    #[sampler(28)]
+    #[cfg(feature = "pbr_detail_textures")]
+    pub detail_normal_map_texture: Option<Handle<Image>>,
+
+    /// How many times the [`StandardMaterial::detail_normal_map_texture`] tiles
+    /// over the UV range of the mesh.
+    ///
+    /// Defaults to [`Vec2::ONE`].
+    #[cfg(feature = "pbr_detail_textures")]
+    pub detail_normal_map_uv_scale: Vec2,
+
     /// Support two-sided lighting by automatically flipping the normals for "back" faces
     /// within the PBR lighting shader.
     ///
@@ -739,6 +782,14 @@ impl Default for StandardMaterial {
             clearcoat_normal_channel: UvChannel::Uv0,
             #[cfg(feature = "pbr_multi_layer_material_textures")]
             clearcoat_normal_texture: None,
+            #[cfg(feature = "pbr_detail_textures")]
+            detail_base_color_texture: None,
+            #[cfg(feature = "pbr_detail_textures")]
+            detail_base_color_uv_scale: Vec2::ONE,
+            #[cfg(feature = "pbr_detail_textures")]
+            detail_normal_map_texture: None,
+            #[cfg(feature = "pbr_detail_textures")]
+            detail_normal_map_uv_scale: Vec2::ONE,
             flip_normal_map_y: false,
             double_sided: false,
             cull_mode: Some(Face::Back),
@@ -874,6 +925,10 @@ pub struct StandardMaterialUniform {
     pub max_relief_mapping_search_steps: u32,
     /// ID for specifying which deferred lighting pass should be used for rendering this material, if any.
     pub deferred_lighting_pass_id: u32,
+    /// How many times the detail base color texture tiles over the mesh UVs.
+    pub detail_base_color_uv_scale: Vec2,
+    /// How many times the detail normal map tiles over the mesh UVs.
+    pub detail_normal_map_uv_scale: Vec2,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -975,6 +1030,14 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         let mut emissive = self.emissive.to_vec4();
         emissive[3] = self.emissive_exposure_weight;
 
+        #[cfg(feature = "pbr_detail_textures")]
+        let (detail_base_color_uv_scale, detail_normal_map_uv_scale) = (
+            self.detail_base_color_uv_scale,
+            self.detail_normal_map_uv_scale,
+        );
+        #[cfg(not(feature = "pbr_detail_textures"))]
+        let (detail_base_color_uv_scale, detail_normal_map_uv_scale) = (Vec2::ONE, Vec2::ONE);
+
         StandardMaterialUniform {
             base_color: LinearRgba::from(self.base_color).to_vec4(),
             emissive,
@@ -999,6 +1062,8 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             max_relief_mapping_search_steps: self.parallax_mapping_method.max_steps(),
             deferred_lighting_pass_id: self.deferred_lighting_pass_id as u32,
             uv_transform: self.uv_transform.into(),
+            detail_base_color_uv_scale,
+            detail_normal_map_uv_scale,
         }
     }
 }
@@ -1026,6 +1091,8 @@ bitflags! {
         const CLEARCOAT_UV             = 0x10000;
         const CLEARCOAT_ROUGHNESS_UV   = 0x20000;
         const CLEARCOAT_NORMAL_UV      = 0x40000;
+        const DETAIL_BASE_COLOR        = 0x80000;
+        const DETAIL_NORMAL_MAP        = 0x100000;
         const DEPTH_BIAS            = 0xffffffff_00000000;
     }
 }
@@ -1123,6 +1190,18 @@ impl From<&StandardMaterial> for StandardMaterialKey {
             );
         }
 
+        #[cfg(feature = "pbr_detail_textures")]
+        {
+            key.set(
+                StandardMaterialKey::DETAIL_BASE_COLOR,
+                material.detail_base_color_texture.is_some(),
+            );
+            key.set(
+                StandardMaterialKey::DETAIL_NORMAL_MAP,
+                material.detail_normal_map_texture.is_some(),
+            );
+        }
+
         key.insert(StandardMaterialKey::from_bits_retain(
             (material.depth_bias as u64) << STANDARD_MATERIAL_KEY_DEPTH_BIAS_SHIFT,
         ));
@@ -1270,6 +1349,18 @@ impl Material for StandardMaterial {
                     StandardMaterialKey::CLEARCOAT_NORMAL_UV,
                     "STANDARD_MATERIAL_CLEARCOAT_NORMAL_UV_B",
                 ),
+                (
+                    StandardMaterialKey::DETAIL_BASE_COLOR,
+                    "STANDARD_MATERIAL_DETAIL_BASE_COLOR",
+                ),
+                (
+                    StandardMaterialKey::DETAIL_NORMAL_MAP,
+                    "STANDARD_MATERIAL_DETAIL_NORMAL_MAP",
+                ),
+                (
+                    StandardMaterialKey::NORMAL_MAP | StandardMaterialKey::DETAIL_NORMAL_MAP,
+                    "STANDARD_MATERIAL_NORMAL_MAP_OR_DETAIL_NORMAL_MAP",
+                ),
             ] {
                 if key.bind_group_data.intersects(flags) {
                     shader_defs.push(shader_def.into());
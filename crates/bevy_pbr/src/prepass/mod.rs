@@ -436,6 +436,10 @@ where
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(7));
         }
 
+        if cfg!(feature = "pbr_detail_textures") {
+            shader_defs.push("PBR_DETAIL_TEXTURES_SUPPORTED".into());
+        }
+
         if key
             .mesh_key
             .contains(MeshPipelineKey::MOTION_VECTOR_PREPASS)
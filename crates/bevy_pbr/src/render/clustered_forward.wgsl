@@ -25,6 +25,17 @@ fn view_z_to_z_slice(view_z: f32, is_orthographic: bool) -> u32 {
     return min(z_slice, bindings::lights.cluster_dimensions.z - 1u);
 }
 
+// Returns the view-space z coordinate of a world-space position, as expected by
+// `fragment_cluster_index`.
+fn world_position_to_view_z(world_position: vec4<f32>) -> f32 {
+    return dot(vec4<f32>(
+        bindings::view.inverse_view[0].z,
+        bindings::view.inverse_view[1].z,
+        bindings::view.inverse_view[2].z,
+        bindings::view.inverse_view[3].z
+    ), world_position);
+}
+
 fn fragment_cluster_index(frag_coord: vec2<f32>, view_z: f32, is_orthographic: bool) -> u32 {
     let xy = vec2<u32>(floor((frag_coord - bindings::view.viewport.xy) * bindings::lights.cluster_factors.xy));
     let z_slice = view_z_to_z_slice(view_z, is_orthographic);
@@ -53,6 +64,37 @@ fn unpack_offset_and_counts(cluster_index: u32) -> vec3<u32> {
 #endif
 }
 
+// The ranges of indices to pass to `get_light_id` to iterate over the point and
+// spot lights of a cluster.
+//
+// Point lights are in `point_lights.x..point_lights.y` and spot lights are in
+// `spot_lights.x..spot_lights.y`.
+struct ClusterLightRanges {
+    point_lights: vec2<u32>,
+    spot_lights: vec2<u32>,
+}
+
+// Returns the ranges of clustered light indices affecting the fragment at
+// `frag_coord` and `world_position`.
+//
+// This works in any pass using the mesh view bindings, including the
+// transparent pass and custom materials.
+fn fragment_cluster_light_ranges(
+    frag_coord: vec2<f32>,
+    world_position: vec4<f32>,
+    is_orthographic: bool,
+) -> ClusterLightRanges {
+    let view_z = world_position_to_view_z(world_position);
+    let cluster_index = fragment_cluster_index(frag_coord, view_z, is_orthographic);
+    let offset_and_counts = unpack_offset_and_counts(cluster_index);
+    let point_lights_end = offset_and_counts[0] + offset_and_counts[1];
+    let spot_lights_end = point_lights_end + offset_and_counts[2];
+    return ClusterLightRanges(
+        vec2<u32>(offset_and_counts[0], point_lights_end),
+        vec2<u32>(point_lights_end, spot_lights_end),
+    );
+}
+
 fn get_light_id(index: u32) -> u32 {
 #if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 3
     return bindings::cluster_light_index_lists.data[index];
@@ -39,6 +39,7 @@ pub struct ExtractedPointLight {
     pub shadow_depth_bias: f32,
     pub shadow_normal_bias: f32,
     pub spot_light_angles: Option<(f32, f32)>,
+    pub contact_shadows: Option<ContactShadows>,
 }
 
 #[derive(Component, Debug)]
@@ -50,6 +51,7 @@ pub struct ExtractedDirectionalLight {
     pub volumetric: bool,
     pub shadow_depth_bias: f32,
     pub shadow_normal_bias: f32,
+    pub contact_shadows: Option<ContactShadows>,
     pub cascade_shadow_config: CascadeShadowConfig,
     pub cascades: EntityHashMap<Vec<Cascade>>,
     pub frusta: EntityHashMap<Vec<Frustum>>,
@@ -67,17 +69,48 @@ pub struct GpuPointLight {
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
     spot_light_tan_angle: f32,
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
+}
+
+/// A [`GpuPointLight`] stored in a uniform buffer, on platforms without storage buffers.
+///
+/// The contact shadow fields are left out, as contact shadows aren't supported on these
+/// platforms, so that more lights fit in the buffer.
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuUniformPointLight {
+    light_custom_data: Vec4,
+    color_inverse_square_range: Vec4,
+    position_radius: Vec4,
+    flags: u32,
+    shadow_depth_bias: f32,
+    shadow_normal_bias: f32,
+    spot_light_tan_angle: f32,
+}
+
+impl From<&GpuPointLight> for GpuUniformPointLight {
+    fn from(light: &GpuPointLight) -> Self {
+        Self {
+            light_custom_data: light.light_custom_data,
+            color_inverse_square_range: light.color_inverse_square_range,
+            position_radius: light.position_radius,
+            flags: light.flags,
+            shadow_depth_bias: light.shadow_depth_bias,
+            shadow_normal_bias: light.shadow_normal_bias,
+            spot_light_tan_angle: light.spot_light_tan_angle,
+        }
+    }
 }
 
 #[derive(ShaderType)]
 pub struct GpuPointLightsUniform {
-    data: Box<[GpuPointLight; MAX_UNIFORM_BUFFER_POINT_LIGHTS]>,
+    data: Box<[GpuUniformPointLight; MAX_UNIFORM_BUFFER_POINT_LIGHTS]>,
 }
 
 impl Default for GpuPointLightsUniform {
     fn default() -> Self {
         Self {
-            data: Box::new([GpuPointLight::default(); MAX_UNIFORM_BUFFER_POINT_LIGHTS]),
+            data: Box::new([GpuUniformPointLight::default(); MAX_UNIFORM_BUFFER_POINT_LIGHTS]),
         }
     }
 }
@@ -112,10 +145,9 @@ impl GpuPointLights {
     fn set(&mut self, mut lights: Vec<GpuPointLight>) {
         match self {
             GpuPointLights::Uniform(buffer) => {
-                let len = lights.len().min(MAX_UNIFORM_BUFFER_POINT_LIGHTS);
-                let src = &lights[..len];
-                let dst = &mut buffer.get_mut().data[..len];
-                dst.copy_from_slice(src);
+                for (dst, src) in buffer.get_mut().data.iter_mut().zip(&lights) {
+                    *dst = src.into();
+                }
             }
             GpuPointLights::Storage(buffer) => {
                 buffer.get_mut().data.clear();
@@ -152,6 +184,7 @@ bitflags::bitflags! {
     struct PointLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
         const SPOT_LIGHT_Y_NEGATIVE      = 1 << 1;
+        const CONTACT_SHADOWS            = 1 << 2;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -176,6 +209,8 @@ pub struct GpuDirectionalLight {
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
     skip: u32,
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -184,6 +219,7 @@ bitflags::bitflags! {
     struct DirectionalLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
         const VOLUMETRIC                 = 1 << 1;
+        const CONTACT_SHADOWS            = 1 << 2;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -204,7 +240,7 @@ pub struct GpuLights {
     spot_light_shadowmap_offset: i32,
 }
 
-// NOTE: this must be kept in sync with the same constants in pbr.frag
+// NOTE: this must be kept in sync with the same constants in mesh_view_types.wgsl
 pub const MAX_UNIFORM_BUFFER_POINT_LIGHTS: usize = 256;
 
 //NOTE: When running bevy on Adreno GPU chipsets in WebGL, any value above 1 will result in a crash
@@ -335,6 +371,7 @@ pub fn extract_lights(
             &GlobalTransform,
             &ViewVisibility,
             &Frustum,
+            Option<&ContactShadows>,
         )>,
     >,
     directional_lights: Extract<
@@ -350,6 +387,7 @@ pub fn extract_lights(
                 &ViewVisibility,
                 Option<&RenderLayers>,
                 Option<&VolumetricLight>,
+                Option<&ContactShadows>,
             ),
             Without<SpotLight>,
         >,
@@ -376,8 +414,14 @@ pub fn extract_lights(
 
     let mut point_lights_values = Vec::with_capacity(*previous_point_lights_len);
     for entity in global_point_lights.iter().copied() {
-        let Ok((point_light, cubemap_visible_entities, transform, view_visibility, frusta)) =
-            point_lights.get(entity)
+        let Ok((
+            point_light,
+            cubemap_visible_entities,
+            transform,
+            view_visibility,
+            frusta,
+            contact_shadows,
+        )) = point_lights.get(entity)
         else {
             continue;
         };
@@ -403,6 +447,7 @@ pub fn extract_lights(
                 * point_light_texel_size
                 * std::f32::consts::SQRT_2,
             spot_light_angles: None,
+            contact_shadows: contact_shadows.copied(),
         };
         point_lights_values.push((
             entity,
@@ -418,8 +463,14 @@ pub fn extract_lights(
 
     let mut spot_lights_values = Vec::with_capacity(*previous_spot_lights_len);
     for entity in global_point_lights.iter().copied() {
-        if let Ok((spot_light, visible_entities, transform, view_visibility, frustum)) =
-            spot_lights.get(entity)
+        if let Ok((
+            spot_light,
+            visible_entities,
+            transform,
+            view_visibility,
+            frustum,
+            contact_shadows,
+        )) = spot_lights.get(entity)
         {
             if !view_visibility.get() {
                 continue;
@@ -452,6 +503,7 @@ pub fn extract_lights(
                             * texel_size
                             * std::f32::consts::SQRT_2,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
+                        contact_shadows: contact_shadows.copied(),
                     },
                     render_visible_entities,
                     *frustum,
@@ -473,6 +525,7 @@ pub fn extract_lights(
         view_visibility,
         maybe_layers,
         volumetric_light,
+        contact_shadows,
     ) in &directional_lights
     {
         if !view_visibility.get() {
@@ -491,6 +544,7 @@ pub fn extract_lights(
                 shadow_depth_bias: directional_light.shadow_depth_bias,
                 // The factor of SQRT_2 is for the worst-case diagonal offset
                 shadow_normal_bias: directional_light.shadow_normal_bias * std::f32::consts::SQRT_2,
+                contact_shadows: contact_shadows.copied(),
                 cascade_shadow_config: cascade_config.clone(),
                 cascades: cascades.cascades.clone(),
                 frusta: frusta.frusta.clone(),
@@ -855,6 +909,12 @@ pub fn prepare_lights(
         {
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
+        if light.contact_shadows.is_some() {
+            flags |= PointLightFlags::CONTACT_SHADOWS;
+        }
+        let (contact_shadow_length, contact_shadow_thickness) = light
+            .contact_shadows
+            .map_or((0.0, 0.0), |contact| (contact.length, contact.thickness));
 
         let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
             Some((inner, outer)) => {
@@ -901,6 +961,8 @@ pub fn prepare_lights(
             shadow_depth_bias: light.shadow_depth_bias,
             shadow_normal_bias: light.shadow_normal_bias,
             spot_light_tan_angle,
+            contact_shadow_length,
+            contact_shadow_thickness,
         });
         global_light_meta.entity_to_index.insert(entity, index);
     }
@@ -925,6 +987,12 @@ pub fn prepare_lights(
         if light.shadows_enabled && (index < directional_shadow_enabled_count) {
             flags |= DirectionalLightFlags::SHADOWS_ENABLED;
         }
+        if light.contact_shadows.is_some() {
+            flags |= DirectionalLightFlags::CONTACT_SHADOWS;
+        }
+        let (contact_shadow_length, contact_shadow_thickness) = light
+            .contact_shadows
+            .map_or((0.0, 0.0), |contact| (contact.length, contact.thickness));
 
         let num_cascades = light
             .cascade_shadow_config
@@ -947,6 +1015,8 @@ pub fn prepare_lights(
             num_cascades: num_cascades as u32,
             cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
             depth_texture_base_index: num_directional_cascades_enabled as u32,
+            contact_shadow_length,
+            contact_shadow_thickness,
         };
         if index < directional_shadow_enabled_count {
             num_directional_cascades_enabled += num_cascades;
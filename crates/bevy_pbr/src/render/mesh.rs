@@ -4,6 +4,7 @@ use bevy_asset::{load_internal_asset, AssetId};
 use bevy_core_pipeline::{
     core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
+    half_resolution_transparency::{HalfResolutionTransparent3d, RenderAtHalfResolution},
 };
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::entity::EntityHashMap;
@@ -135,6 +136,7 @@ impl Plugin for MeshRenderPlugin {
             BinnedRenderPhasePlugin::<AlphaMask3dDeferred, MeshPipeline>::default(),
             SortedRenderPhasePlugin::<Transmissive3d, MeshPipeline>::default(),
             SortedRenderPhasePlugin::<Transparent3d, MeshPipeline>::default(),
+            SortedRenderPhasePlugin::<HalfResolutionTransparent3d, MeshPipeline>::default(),
         ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
@@ -277,6 +279,9 @@ pub struct MeshUniform {
     //
     // (MSB: most significant bit; LSB: least significant bit.)
     pub lightmap_uv_rect: UVec2,
+    // The index of the per-instance data of this mesh's material, or `u32::MAX` if its material
+    // isn't an [`InstancedMaterial`](crate::InstancedMaterial).
+    pub instance_data_index: u32,
 }
 
 /// Information that has to be transferred from CPU to GPU in order to produce
@@ -307,6 +312,17 @@ pub struct MeshInputUniform {
     ///
     /// This is used for TAA. If not present, this will be `u32::MAX`.
     pub previous_input_index: u32,
+    /// The index of the per-instance data of this mesh's material, if its
+    /// material is an [`InstancedMaterial`](crate::InstancedMaterial).
+    ///
+    /// If not present, this will be `u32::MAX`.
+    pub instance_data_index: u32,
+    /// Padding.
+    pub pad_a: u32,
+    /// Padding.
+    pub pad_b: u32,
+    /// Padding.
+    pub pad_c: u32,
 }
 
 /// Information about each mesh instance needed to cull it on GPU.
@@ -343,6 +359,7 @@ impl MeshUniform {
             inverse_transpose_model_a,
             inverse_transpose_model_b,
             flags: mesh_transforms.flags,
+            instance_data_index: u32::MAX,
         }
     }
 }
@@ -412,6 +429,8 @@ bitflags::bitflags! {
         const AUTOMATIC_BATCHING      = 1 << 1;
         /// The mesh had a transform last frame and so is eligible for TAA.
         const HAVE_PREVIOUS_TRANSFORM = 1 << 2;
+        /// The mesh is rendered at half resolution by cameras that support it.
+        const HALF_RESOLUTION         = 1 << 3;
     }
 }
 
@@ -457,6 +476,11 @@ pub struct RenderMeshInstanceShared {
     pub material_bind_group_id: AtomicMaterialBindGroupId,
     /// Various flags.
     pub flags: RenderMeshInstanceFlags,
+    /// The index of the per-instance data of the material, if the material is
+    /// an [`InstancedMaterial`](crate::InstancedMaterial).
+    ///
+    /// This is filled in during extraction, and is `u32::MAX` otherwise.
+    pub instance_data_index: u32,
 }
 
 /// Information that is gathered during the parallel portion of mesh extraction
@@ -516,6 +540,7 @@ impl RenderMeshInstanceShared {
         handle: &Handle<Mesh>,
         not_shadow_caster: bool,
         no_automatic_batching: bool,
+        render_at_half_resolution: bool,
     ) -> Self {
         let mut mesh_instance_flags = RenderMeshInstanceFlags::empty();
         mesh_instance_flags.set(RenderMeshInstanceFlags::SHADOW_CASTER, !not_shadow_caster);
@@ -527,12 +552,17 @@ impl RenderMeshInstanceShared {
             RenderMeshInstanceFlags::HAVE_PREVIOUS_TRANSFORM,
             previous_transform.is_some(),
         );
+        mesh_instance_flags.set(
+            RenderMeshInstanceFlags::HALF_RESOLUTION,
+            render_at_half_resolution,
+        );
 
         RenderMeshInstanceShared {
             mesh_asset_id: handle.id(),
 
             flags: mesh_instance_flags,
             material_bind_group_id: AtomicMaterialBindGroupId::default(),
+            instance_data_index: u32::MAX,
         }
     }
 
@@ -695,6 +725,10 @@ impl RenderMeshInstanceGpuBuilder {
                 Some(previous_input_index) => previous_input_index.into(),
                 None => u32::MAX,
             },
+            instance_data_index: self.shared.instance_data_index,
+            pad_a: 0,
+            pad_b: 0,
+            pad_c: 0,
         });
 
         // Record the [`RenderMeshInstance`].
@@ -782,6 +816,7 @@ pub fn extract_meshes_for_cpu_building(
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
             Has<VisibilityRange>,
+            Has<RenderAtHalfResolution>,
         )>,
     >,
 ) {
@@ -799,6 +834,7 @@ pub fn extract_meshes_for_cpu_building(
             not_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            render_at_half_resolution,
         )| {
             if !view_visibility.get() {
                 return;
@@ -821,6 +857,7 @@ pub fn extract_meshes_for_cpu_building(
                 handle,
                 not_shadow_caster,
                 no_automatic_batching,
+                render_at_half_resolution,
             );
 
             let transform = transform.affine();
@@ -883,6 +920,7 @@ pub fn extract_meshes_for_gpu_building(
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
             Has<VisibilityRange>,
+            Has<RenderAtHalfResolution>,
         )>,
     >,
     cameras_query: Extract<Query<(), (With<Camera>, With<GpuCulling>)>>,
@@ -917,6 +955,7 @@ pub fn extract_meshes_for_gpu_building(
             not_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            render_at_half_resolution,
         )| {
             if !view_visibility.get() {
                 return;
@@ -939,6 +978,7 @@ pub fn extract_meshes_for_gpu_building(
                 handle,
                 not_shadow_caster,
                 no_automatic_batching,
+                render_at_half_resolution,
             );
 
             let lightmap_uv_rect =
@@ -1167,11 +1207,14 @@ impl GetBatchData for MeshPipeline {
         let mesh_instance = mesh_instances.get(&entity)?;
         let maybe_lightmap = lightmaps.render_lightmaps.get(&entity);
 
+        let mut mesh_uniform = MeshUniform::new(
+            &mesh_instance.transforms,
+            maybe_lightmap.map(|lightmap| lightmap.uv_rect),
+        );
+        mesh_uniform.instance_data_index = mesh_instance.instance_data_index;
+
         Some((
-            MeshUniform::new(
-                &mesh_instance.transforms,
-                maybe_lightmap.map(|lightmap| lightmap.uv_rect),
-            ),
+            mesh_uniform,
             mesh_instance.should_batch().then_some((
                 mesh_instance.material_bind_group_id.get(),
                 mesh_instance.mesh_asset_id,
@@ -1223,10 +1266,12 @@ impl GetFullBatchData for MeshPipeline {
         let mesh_instance = mesh_instances.get(&entity)?;
         let maybe_lightmap = lightmaps.render_lightmaps.get(&entity);
 
-        Some(MeshUniform::new(
+        let mut mesh_uniform = MeshUniform::new(
             &mesh_instance.transforms,
             maybe_lightmap.map(|lightmap| lightmap.uv_rect),
-        ))
+        );
+        mesh_uniform.instance_data_index = mesh_instance.instance_data_index;
+        Some(mesh_uniform)
     }
 
     fn get_binned_index(
@@ -1343,7 +1388,8 @@ bitflags::bitflags! {
         const IRRADIANCE_VOLUME                 = 1 << 14;
         const VISIBILITY_RANGE_DITHER           = 1 << 15;
         const SCREEN_SPACE_REFLECTIONS          = 1 << 16;
-        const LAST_FLAG                         = Self::SCREEN_SPACE_REFLECTIONS.bits();
+        const HALF_RESOLUTION                   = 1 << 17;
+        const LAST_FLAG                         = Self::HALF_RESOLUTION.bits();
 
         // Bitfields
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
@@ -1557,6 +1603,9 @@ impl SpecializedMeshPipeline for MeshPipeline {
         if cfg!(feature = "pbr_multi_layer_material_textures") {
             shader_defs.push("PBR_MULTI_LAYER_MATERIAL_TEXTURES_SUPPORTED".into());
         }
+        if cfg!(feature = "pbr_detail_textures") {
+            shader_defs.push("PBR_DETAIL_TEXTURES_SUPPORTED".into());
+        }
 
         let mut bind_group_layout = vec![self.get_view_layout(key.into()).clone()];
 
@@ -1750,6 +1799,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("VISIBILITY_RANGE_DITHER".into());
         }
 
+        if key.contains(MeshPipelineKey::HALF_RESOLUTION) {
+            shader_defs.push("HALF_RESOLUTION".into());
+        }
+
         if self.binding_arrays_are_usable {
             shader_defs.push("MULTIPLE_LIGHT_PROBES_IN_ARRAY".into());
         }
@@ -21,6 +21,12 @@ fn get_previous_model_matrix(instance_index: u32) -> mat4x4<f32> {
     return affine3_to_square(mesh[instance_index].previous_model);
 }
 
+// Returns the index of the per-instance data of the material, if the material
+// is an `InstancedMaterial`.
+fn get_instance_data_index(instance_index: u32) -> u32 {
+    return mesh[instance_index].instance_data_index;
+}
+
 fn mesh_position_local_to_world(model: mat4x4<f32>, vertex_position: vec4<f32>) -> vec4<f32> {
     return model * vertex_position;
 }
@@ -15,6 +15,10 @@ struct Mesh {
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     lightmap_uv_rect: vec2<u32>,
+    // The index of the per-instance data of the material, if the material is an
+    // `InstancedMaterial`. Use bevy_pbr::mesh_functions::get_instance_data_index
+    // to read it.
+    instance_data_index: u32,
 };
 
 #ifdef SKINNED
@@ -11,10 +11,16 @@ struct PointLight {
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
     spot_light_tan_angle: f32,
+#if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 3
+    // Left out of the uniform buffer, to fit more lights in it
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
+#endif
 };
 
 const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32   = 1u;
 const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+const POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT: u32    = 4u;
 
 struct DirectionalCascade {
     view_projection: mat4x4<f32>,
@@ -34,10 +40,13 @@ struct DirectionalLight {
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
     skip: u32,
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
 };
 
 const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
 const DIRECTIONAL_LIGHT_FLAGS_VOLUMETRIC_BIT: u32      = 2u;
+const DIRECTIONAL_LIGHT_FLAGS_CONTACT_SHADOWS_BIT: u32 = 4u;
 
 struct Lights {
     // NOTE: this array size must be kept in sync with the constants defined in bevy_pbr/src/render/light.rs
@@ -31,3 +31,9 @@
 @group(2) @binding(23) var clearcoat_normal_texture: texture_2d<f32>;
 @group(2) @binding(24) var clearcoat_normal_sampler: sampler;
 #endif
+#ifdef PBR_DETAIL_TEXTURES_SUPPORTED
+@group(2) @binding(25) var detail_base_color_texture: texture_2d<f32>;
+@group(2) @binding(26) var detail_base_color_sampler: sampler;
+@group(2) @binding(27) var detail_normal_map_texture: texture_2d<f32>;
+@group(2) @binding(28) var detail_normal_map_sampler: sampler;
+#endif
@@ -43,6 +43,12 @@ fn pbr_input_from_vertex_output(
     pbr_input.is_orthographic = view.projection[3].w == 1.0;
     pbr_input.V = pbr_functions::calculate_view(in.world_position, pbr_input.is_orthographic);
     pbr_input.frag_coord = in.position;
+#ifdef HALF_RESOLUTION
+    // Half resolution objects are rasterized at half the size of the view, so
+    // scale the fragment coordinates back up to keep screen-space lookups, such
+    // as the light clusters, in sync with the full resolution view.
+    pbr_input.frag_coord = vec4(in.position.xy * 2.0, in.position.zw);
+#endif
     pbr_input.world_position = in.world_position;
 
 #ifdef VERTEX_COLORS
@@ -165,6 +171,21 @@ fn pbr_input_from_standard_material(
 #endif // ALPHA_TO_COVERAGE
 
     }
+
+#ifdef STANDARD_MATERIAL_DETAIL_BASE_COLOR
+    // The detail color is doubled so that a mid-grey detail texture leaves the
+    // base color unchanged.
+    let detail_color = pbr_functions::sample_texture(
+        pbr_bindings::detail_base_color_texture,
+        pbr_bindings::detail_base_color_sampler,
+        uv * pbr_bindings::material.detail_base_color_uv_scale,
+        bias,
+    ).rgb;
+    pbr_input.material.base_color = vec4(
+        pbr_input.material.base_color.rgb * detail_color * 2.0,
+        pbr_input.material.base_color.a,
+    );
+#endif  // STANDARD_MATERIAL_DETAIL_BASE_COLOR
 #endif // VERTEX_UVS
 
     pbr_input.material.flags = pbr_bindings::material.flags;
@@ -338,7 +359,7 @@ fn pbr_input_from_standard_material(
         }
 #endif
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
-        let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(in.position.xy), 0i).r;
+        let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(pbr_input.frag_coord.xy), 0i).r;
         let ssao_multibounce = gtao_multibounce(ssao, pbr_input.material.base_color.rgb);
         diffuse_occlusion = min(diffuse_occlusion, ssao_multibounce);
         // Use SSAO to estimate the specular occlusion.
@@ -357,9 +378,10 @@ fn pbr_input_from_standard_material(
 #ifdef VERTEX_UVS
 #ifdef VERTEX_TANGENTS
 
-#ifdef STANDARD_MATERIAL_NORMAL_MAP
+#ifdef STANDARD_MATERIAL_NORMAL_MAP_OR_DETAIL_NORMAL_MAP
 
-        let Nt = pbr_functions::sample_texture(
+#ifdef STANDARD_MATERIAL_NORMAL_MAP
+        var Nt = pbr_functions::sample_texture(
             pbr_bindings::normal_map_texture,
             pbr_bindings::normal_map_sampler,
 #ifdef STANDARD_MATERIAL_NORMAL_MAP_UV_B
@@ -369,6 +391,20 @@ fn pbr_input_from_standard_material(
 #endif
             bias,
         ).rgb;
+#else
+        // A flat normal, so that the detail normal map can be used on its own.
+        var Nt = vec3(0.5, 0.5, 1.0);
+#endif  // STANDARD_MATERIAL_NORMAL_MAP
+
+#ifdef STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+        let detail_Nt = pbr_functions::sample_texture(
+            pbr_bindings::detail_normal_map_texture,
+            pbr_bindings::detail_normal_map_sampler,
+            uv * pbr_bindings::material.detail_normal_map_uv_scale,
+            bias,
+        ).rgb;
+        Nt = pbr_functions::blend_detail_normal(Nt, detail_Nt);
+#endif  // STANDARD_MATERIAL_DETAIL_NORMAL_MAP
 
         pbr_input.N = pbr_functions::apply_normal_mapping(
             pbr_bindings::material.flags,
@@ -380,7 +416,7 @@ fn pbr_input_from_standard_material(
             view.mip_bias,
         );
 
-#endif  // STANDARD_MATERIAL_NORMAL_MAP
+#endif  // STANDARD_MATERIAL_NORMAL_MAP_OR_DETAIL_NORMAL_MAP
 
 #ifdef STANDARD_MATERIAL_CLEARCOAT
 
@@ -143,7 +143,7 @@ fn prepare_world_normal(
 ) -> vec3<f32> {
     var output: vec3<f32> = world_normal;
 #ifndef VERTEX_TANGENTS
-#ifndef STANDARD_MATERIAL_NORMAL_MAP
+#ifndef STANDARD_MATERIAL_NORMAL_MAP_OR_DETAIL_NORMAL_MAP
     // NOTE: When NOT using normal-mapping, if looking at the back face of a double-sided
     // material, the normal needs to be inverted. This is a branchless version of that.
     output = (f32(!double_sided || is_front) * 2.0 - 1.0) * output;
@@ -204,6 +204,19 @@ fn apply_normal_mapping(
     return normalize(N);
 }
 
+// Blends a detail tangent-space normal on top of a base one, using whiteout blending.
+//
+// Both normals, as well as the result, are encoded in [0, 1] like in normal map textures.
+// Only the xy components are read so that 2-component normal maps are supported.
+fn blend_detail_normal(base_Nt: vec3<f32>, detail_Nt: vec3<f32>) -> vec3<f32> {
+    let base_xy = base_Nt.xy * 2.0 - 1.0;
+    let detail_xy = detail_Nt.xy * 2.0 - 1.0;
+    let base_z = sqrt(max(1.0 - dot(base_xy, base_xy), 0.0));
+    let detail_z = sqrt(max(1.0 - dot(detail_xy, detail_xy), 0.0));
+    let Nt = normalize(vec3(base_xy + detail_xy, base_z * detail_z));
+    return Nt * 0.5 + 0.5;
+}
+
 // NOTE: Correctly calculates the view vector depending on whether
 // the projection is orthographic or perspective.
 fn calculate_view(
@@ -239,6 +252,53 @@ fn calculate_F0(base_color: vec3<f32>, metallic: f32, reflectance: f32) -> vec3<
 }
 
 #ifndef PREPASS_FRAGMENT
+// Accumulates the direct lighting from the clustered point and spot lights in
+// `ranges`, which can be looked up with `clustering::fragment_cluster_light_ranges`.
+//
+// This is the light loop of `apply_pbr_lighting`, exposed so that custom
+// materials, including those rendered in the transparent pass, can be lit by
+// clustered lights without reimplementing it. Shadow maps are sampled at
+// `world_position` if `receives_shadows` is true and the light casts shadows,
+// and contact shadows if `receives_contact_shadows` is true.
+fn cluster_light_loop(
+    lighting_input: ptr<function, lighting::LightingInput>,
+    ranges: clustering::ClusterLightRanges,
+    world_position: vec4<f32>,
+    world_normal: vec3<f32>,
+    receives_shadows: bool,
+    receives_contact_shadows: bool,
+) -> vec3<f32> {
+    var direct_light = vec3<f32>(0.0);
+
+    for (var i: u32 = ranges.point_lights.x; i < ranges.point_lights.y; i = i + 1u) {
+        let light_id = clustering::get_light_id(i);
+        var shadow: f32 = 1.0;
+        if (receives_shadows
+                && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
+            shadow = shadows::fetch_point_shadow(light_id, world_position, world_normal);
+        }
+        if (receives_contact_shadows) {
+            shadow *= shadows::fetch_point_contact_shadow(light_id, world_position);
+        }
+        direct_light += lighting::point_light(light_id, lighting_input) * shadow;
+    }
+
+    for (var i: u32 = ranges.spot_lights.x; i < ranges.spot_lights.y; i = i + 1u) {
+        let light_id = clustering::get_light_id(i);
+        var shadow: f32 = 1.0;
+        if (receives_shadows
+                && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
+            shadow = shadows::fetch_spot_shadow(light_id, world_position, world_normal);
+        }
+        if (receives_contact_shadows) {
+            shadow *= shadows::fetch_point_contact_shadow(light_id, world_position);
+        }
+        direct_light += lighting::spot_light(light_id, lighting_input) * shadow;
+    }
+
+    return direct_light;
+}
+
 fn apply_pbr_lighting(
     in: pbr_types::PbrInput,
 ) -> vec4<f32> {
@@ -341,83 +401,41 @@ fn apply_pbr_lighting(
 #endif  // STANDARD_MATERIAL_CLEARCOAT
 #endif  // STANDARD_MATERIAL_DIFFUSE_TRANSMISSION
 
-    let view_z = dot(vec4<f32>(
-        view_bindings::view.inverse_view[0].z,
-        view_bindings::view.inverse_view[1].z,
-        view_bindings::view.inverse_view[2].z,
-        view_bindings::view.inverse_view[3].z
-    ), in.world_position);
+    let view_z = clustering::world_position_to_view_z(in.world_position);
     let cluster_index = clustering::fragment_cluster_index(in.frag_coord.xy, view_z, in.is_orthographic);
     let offset_and_counts = clustering::unpack_offset_and_counts(cluster_index);
 
-    // Point lights (direct)
-    for (var i: u32 = offset_and_counts[0]; i < offset_and_counts[0] + offset_and_counts[1]; i = i + 1u) {
-        let light_id = clustering::get_light_id(i);
-        var shadow: f32 = 1.0;
-        if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
-                && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
-            shadow = shadows::fetch_point_shadow(light_id, in.world_position, in.world_normal);
-        }
-
-        let light_contrib = lighting::point_light(light_id, &lighting_input);
-        direct_light += light_contrib * shadow;
-
-#ifdef STANDARD_MATERIAL_DIFFUSE_TRANSMISSION
-        // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
-        // world position, inverted normal and view vectors, and the following simplified
-        // values for a fully diffuse transmitted light contribution approximation:
-        //
-        // roughness = 1.0;
-        // NdotV = 1.0;
-        // R = vec3<f32>(0.0) // doesn't really matter
-        // F_ab = vec2<f32>(0.1)
-        // F0 = vec3<f32>(0.0)
-        var transmitted_shadow: f32 = 1.0;
-        if ((in.flags & (MESH_FLAGS_SHADOW_RECEIVER_BIT | MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT)) == (MESH_FLAGS_SHADOW_RECEIVER_BIT | MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT)
-                && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
-            transmitted_shadow = shadows::fetch_point_shadow(light_id, diffuse_transmissive_lobe_world_position, -in.world_normal);
-        }
-
-        let transmitted_light_contrib =
-            lighting::point_light(light_id, &transmissive_lighting_input);
-        transmitted_light += transmitted_light_contrib * transmitted_shadow;
-#endif
-    }
-
-    // Spot lights (direct)
-    for (var i: u32 = offset_and_counts[0] + offset_and_counts[1]; i < offset_and_counts[0] + offset_and_counts[1] + offset_and_counts[2]; i = i + 1u) {
-        let light_id = clustering::get_light_id(i);
-
-        var shadow: f32 = 1.0;
-        if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
-                && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
-            shadow = shadows::fetch_spot_shadow(light_id, in.world_position, in.world_normal);
-        }
-
-        let light_contrib = lighting::spot_light(light_id, &lighting_input);
-        direct_light += light_contrib * shadow;
+    // Point and spot lights (direct)
+    let cluster_light_ranges = clustering::fragment_cluster_light_ranges(in.frag_coord.xy, in.world_position, in.is_orthographic);
+    let receives_shadows = (in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u;
+    direct_light += cluster_light_loop(
+        &lighting_input,
+        cluster_light_ranges,
+        in.world_position,
+        in.world_normal,
+        receives_shadows,
+        receives_shadows,
+    );
 
 #ifdef STANDARD_MATERIAL_DIFFUSE_TRANSMISSION
-        // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
-        // world position, inverted normal and view vectors, and the following simplified
-        // values for a fully diffuse transmitted light contribution approximation:
-        //
-        // roughness = 1.0;
-        // NdotV = 1.0;
-        // R = vec3<f32>(0.0) // doesn't really matter
-        // F_ab = vec2<f32>(0.1)
-        // F0 = vec3<f32>(0.0)
-        var transmitted_shadow: f32 = 1.0;
-        if ((in.flags & (MESH_FLAGS_SHADOW_RECEIVER_BIT | MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT)) == (MESH_FLAGS_SHADOW_RECEIVER_BIT | MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT)
-                && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
-            transmitted_shadow = shadows::fetch_spot_shadow(light_id, diffuse_transmissive_lobe_world_position, -in.world_normal);
-        }
-
-        let transmitted_light_contrib =
-            lighting::spot_light(light_id, &transmissive_lighting_input);
-        transmitted_light += transmitted_light_contrib * transmitted_shadow;
+    // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
+    // world position, inverted normal and view vectors, and the following simplified
+    // values for a fully diffuse transmitted light contribution approximation:
+    //
+    // roughness = 1.0;
+    // NdotV = 1.0;
+    // R = vec3<f32>(0.0) // doesn't really matter
+    // F_ab = vec2<f32>(0.1)
+    // F0 = vec3<f32>(0.0)
+    transmitted_light += cluster_light_loop(
+        &transmissive_lighting_input,
+        cluster_light_ranges,
+        diffuse_transmissive_lobe_world_position,
+        -in.world_normal,
+        (in.flags & (MESH_FLAGS_SHADOW_RECEIVER_BIT | MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT)) == (MESH_FLAGS_SHADOW_RECEIVER_BIT | MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT),
+        false,
+    );
 #endif
-    }
 
     // directional lights (direct)
     let n_directional_lights = view_bindings::lights.n_directional_lights;
@@ -434,6 +452,9 @@ fn apply_pbr_lighting(
                 && (view_bindings::lights.directional_lights[i].flags & mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = shadows::fetch_directional_shadow(i, in.world_position, in.world_normal, view_z);
         }
+        if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u) {
+            shadow *= shadows::fetch_directional_contact_shadow(i, in.world_position);
+        }
 
         var light_contrib = lighting::directional_light(i, &lighting_input);
 
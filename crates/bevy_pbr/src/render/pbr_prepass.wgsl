@@ -51,7 +51,7 @@ fn fragment(
 
 #ifdef VERTEX_UVS
 #ifdef VERTEX_TANGENTS
-#ifdef STANDARD_MATERIAL_NORMAL_MAP
+#ifdef STANDARD_MATERIAL_NORMAL_MAP_OR_DETAIL_NORMAL_MAP
 
 #ifdef STANDARD_MATERIAL_NORMAL_MAP_UV_B
         let uv = (material.uv_transform * vec3(in.uv_b, 1.0)).xy;
@@ -68,12 +68,27 @@ fn fragment(
         bias.mip_bias = view.mip_bias;
 #endif  // MESHLET_MESH_MATERIAL_PASS
 
-        let Nt = pbr_functions::sample_texture(
+#ifdef STANDARD_MATERIAL_NORMAL_MAP
+        var Nt = pbr_functions::sample_texture(
             pbr_bindings::normal_map_texture,
             pbr_bindings::normal_map_sampler,
             uv,
             bias,
         ).rgb;
+#else
+        var Nt = vec3(0.5, 0.5, 1.0);
+#endif  // STANDARD_MATERIAL_NORMAL_MAP
+
+#ifdef STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+        let detail_uv = (material.uv_transform * vec3(in.uv, 1.0)).xy;
+        let detail_Nt = pbr_functions::sample_texture(
+            pbr_bindings::detail_normal_map_texture,
+            pbr_bindings::detail_normal_map_sampler,
+            detail_uv * material.detail_normal_map_uv_scale,
+            bias,
+        ).rgb;
+        Nt = pbr_functions::blend_detail_normal(Nt, detail_Nt);
+#endif  // STANDARD_MATERIAL_DETAIL_NORMAL_MAP
 
         normal = pbr_functions::apply_normal_mapping(
             material.flags,
@@ -85,7 +100,7 @@ fn fragment(
             view.mip_bias,
         );
 
-#endif  // STANDARD_MATERIAL_NORMAL_MAP
+#endif  // STANDARD_MATERIAL_NORMAL_MAP_OR_DETAIL_NORMAL_MAP
 #endif  // VERTEX_TANGENTS
 #endif  // VERTEX_UVS
 
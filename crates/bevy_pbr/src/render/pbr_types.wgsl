@@ -26,6 +26,8 @@ struct StandardMaterial {
     max_relief_mapping_search_steps: u32,
     /// ID for specifying which deferred lighting pass should be used for rendering this material, if any.
     deferred_lighting_pass_id: u32,
+    detail_base_color_uv_scale: vec2<f32>,
+    detail_normal_map_uv_scale: vec2<f32>,
 };
 
 // !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
@@ -87,6 +89,8 @@ fn standard_material_new() -> StandardMaterial {
     material.deferred_lighting_pass_id = 1u;
     // scale 1, translation 0, rotation 0
     material.uv_transform = mat3x3<f32>(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0);
+    material.detail_base_color_uv_scale = vec2(1.0);
+    material.detail_normal_map_uv_scale = vec2(1.0);
 
     return material;
 }
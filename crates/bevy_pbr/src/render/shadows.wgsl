@@ -1,9 +1,15 @@
 #define_import_path bevy_pbr::shadows
 
 #import bevy_pbr::{
-    mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    mesh_view_types::{
+        POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+        POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT,
+        DIRECTIONAL_LIGHT_FLAGS_CONTACT_SHADOWS_BIT,
+    },
     mesh_view_bindings as view_bindings,
-    shadow_sampling::{SPOT_SHADOW_TEXEL_SIZE, sample_shadow_cubemap, sample_shadow_map}
+    prepass_utils,
+    shadow_sampling::{SPOT_SHADOW_TEXEL_SIZE, sample_shadow_cubemap, sample_shadow_map},
+    view_transformations,
 }
 
 #import bevy_render::{
@@ -13,6 +19,9 @@
 
 const flip_z: vec3<f32> = vec3<f32>(1.0, 1.0, -1.0);
 
+// The number of depth prepass samples taken along the ray of contact shadows.
+const CONTACT_SHADOW_STEPS: u32 = 16u;
+
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = &view_bindings::point_lights.data[light_id];
 
@@ -187,6 +196,71 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
     return shadow;
 }
 
+// Marches `ray_length` world units from the fragment towards the light through the depth prepass,
+// and returns 0.0 if the ray passes less than `thickness` world units behind a surface.
+//
+// Returns 1.0 when there is no depth prepass to march through.
+fn fetch_contact_shadow(frag_position: vec4<f32>, direction_to_light: vec3<f32>, ray_length: f32, thickness: f32) -> f32 {
+#ifdef DEPTH_PREPASS
+#ifndef WEBGL2
+    let ray_step = direction_to_light * (ray_length / f32(CONTACT_SHADOW_STEPS));
+    // Start one step away from the fragment, which would otherwise shadow itself.
+    for (var i: u32 = 1u; i <= CONTACT_SHADOW_STEPS; i = i + 1u) {
+        let ray_ndc = view_transformations::position_world_to_ndc(frag_position.xyz + ray_step * f32(i));
+        if (any(abs(ray_ndc.xy) > vec2<f32>(1.0))) {
+            break;
+        }
+
+        let frag_coord = view_transformations::ndc_to_frag_coord(ray_ndc.xy);
+        let scene_depth = prepass_utils::prepass_depth(vec4<f32>(frag_coord, 0.0, 0.0), 0u);
+        // -z is forward, so the scene is in front of the ray when its view z is greater.
+        let depth_difference = view_transformations::depth_ndc_to_view_z(scene_depth)
+            - view_transformations::depth_ndc_to_view_z(ray_ndc.z);
+        if (depth_difference > 0.0 && depth_difference < thickness) {
+            return 0.0;
+        }
+    }
+#endif
+#endif
+    return 1.0;
+}
+
+fn fetch_point_contact_shadow(light_id: u32, frag_position: vec4<f32>) -> f32 {
+#if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 3
+    let light = &view_bindings::point_lights.data[light_id];
+    if (((*light).flags & POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT) == 0u) {
+        return 1.0;
+    }
+
+    let surface_to_light = (*light).position_radius.xyz - frag_position.xyz;
+    // Don't search for occluders past the light.
+    let ray_length = min((*light).contact_shadow_length, length(surface_to_light));
+    return fetch_contact_shadow(
+        frag_position,
+        normalize(surface_to_light),
+        ray_length,
+        (*light).contact_shadow_thickness
+    );
+#else
+    // The point lights in the uniform buffer don't have contact shadows
+    return 1.0;
+#endif
+}
+
+fn fetch_directional_contact_shadow(light_id: u32, frag_position: vec4<f32>) -> f32 {
+    let light = &view_bindings::lights.directional_lights[light_id];
+    if (((*light).flags & DIRECTIONAL_LIGHT_FLAGS_CONTACT_SHADOWS_BIT) == 0u) {
+        return 1.0;
+    }
+
+    return fetch_contact_shadow(
+        frag_position,
+        (*light).direction_to_light,
+        (*light).contact_shadow_length,
+        (*light).contact_shadow_thickness
+    );
+}
+
 fn cascade_debug_visualization(
     output_color: vec3<f32>,
     light_id: u32,
@@ -0,0 +1,119 @@
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::alpha_discard,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    pbr_deferred_functions::deferred_output,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#endif
+
+struct TerrainMaterial {
+    layer_tiling: array<vec4<f32>, 4>,
+    layer_count: u32,
+    triplanar_sharpness: f32,
+}
+
+@group(2) @binding(100) var<uniform> terrain: TerrainMaterial;
+@group(2) @binding(101) var control_texture: texture_2d_array<f32>;
+@group(2) @binding(102) var control_sampler: sampler;
+@group(2) @binding(103) var base_color_texture: texture_2d_array<f32>;
+@group(2) @binding(104) var base_color_sampler: sampler;
+@group(2) @binding(105) var normal_map_texture: texture_2d_array<f32>;
+@group(2) @binding(106) var normal_map_sampler: sampler;
+
+// The weights of the projections along the X, Y and Z axes.
+fn projection_weights(N: vec3<f32>) -> vec3<f32> {
+#ifdef TERRAIN_TRIPLANAR
+    let weights = pow(abs(N), vec3(terrain.triplanar_sharpness));
+    return weights / (weights.x + weights.y + weights.z);
+#else
+    return vec3(0.0, 1.0, 0.0);
+#endif
+}
+
+fn sample_base_color(layer: u32, position: vec3<f32>, weights: vec3<f32>) -> vec4<f32> {
+    var color = textureSample(base_color_texture, base_color_sampler, position.xz, layer) * weights.y;
+#ifdef TERRAIN_TRIPLANAR
+    color += textureSample(base_color_texture, base_color_sampler, position.zy, layer) * weights.x;
+    color += textureSample(base_color_texture, base_color_sampler, position.xy, layer) * weights.z;
+#endif
+    return color;
+}
+
+#ifdef TERRAIN_NORMAL_MAP
+fn sample_normal(uv: vec2<f32>, layer: u32) -> vec3<f32> {
+    return textureSample(normal_map_texture, normal_map_sampler, uv, layer).rgb * 2.0 - 1.0;
+}
+
+// Blends the normal maps of the projections with the surface normal, with the "whiteout" blend
+// described in https://bgolus.medium.com/normal-mapping-for-a-triplanar-shader-10bf39dca05a
+fn sample_world_normal(layer: u32, position: vec3<f32>, N: vec3<f32>, weights: vec3<f32>) -> vec3<f32> {
+    let tangent_y = sample_normal(position.xz, layer);
+    var normal = vec3(tangent_y.xy + N.xz, abs(tangent_y.z) * N.y).xzy * weights.y;
+#ifdef TERRAIN_TRIPLANAR
+    let tangent_x = sample_normal(position.zy, layer);
+    let tangent_z = sample_normal(position.xy, layer);
+    normal += vec3(tangent_x.xy + N.zy, abs(tangent_x.z) * N.x).zyx * weights.x;
+    normal += vec3(tangent_z.xy + N.xy, abs(tangent_z.z) * N.z) * weights.z;
+#endif
+    return normal;
+}
+#endif
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    let world_position = in.world_position.xyz;
+    let N = normalize(pbr_input.world_normal);
+    let weights = projection_weights(N);
+
+    var total_weight = 0.0;
+    var base_color = vec4(0.0);
+    var normal = vec3(0.0);
+    // Every layer is sampled, as sampling in non-uniform control flow isn't allowed
+    for (var layer = 0u; layer < terrain.layer_count; layer += 1u) {
+#ifdef VERTEX_UVS_A
+        let control = textureSample(control_texture, control_sampler, in.uv, layer / 4u);
+#else
+        let control = vec4(1.0);
+#endif
+        let layer_weight = control[layer % 4u];
+        let position = world_position * terrain.layer_tiling[layer / 4u][layer % 4u];
+        total_weight += layer_weight;
+        base_color += sample_base_color(layer, position, weights) * layer_weight;
+#ifdef TERRAIN_NORMAL_MAP
+        normal += sample_world_normal(layer, position, N, weights) * layer_weight;
+#endif
+    }
+
+    if total_weight > 0.0 {
+        pbr_input.material.base_color *= base_color / total_weight;
+#ifdef TERRAIN_NORMAL_MAP
+        pbr_input.N = normalize(normal);
+#endif
+    }
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+#ifdef PREPASS_PIPELINE
+    let out = deferred_output(in, pbr_input);
+#else
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#endif
+
+    return out;
+}
@@ -0,0 +1,288 @@
+//! A material for heightmap terrains, blending several textured layers.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Asset, Handle};
+use bevy_math::Vec4;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    mesh::MeshVertexBufferLayoutRef,
+    render_asset::RenderAssets,
+    render_resource::*,
+    renderer::RenderDevice,
+    texture::{FallbackImage, GpuImage, Image},
+};
+
+use crate::{
+    ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+    MaterialPlugin, StandardMaterial,
+};
+
+pub const TERRAIN_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(4317625819402371286);
+
+/// The maximum number of layers of a [`TerrainExtension`].
+pub const MAX_TERRAIN_LAYERS: usize = 16;
+
+/// A [`StandardMaterial`] whose base color and normals are blended from the layers of a
+/// [`TerrainExtension`].
+pub type TerrainMaterial = ExtendedMaterial<StandardMaterial, TerrainExtension>;
+
+/// A [`Plugin`] that renders [`TerrainMaterial`]s.
+///
+/// It isn't part of the default plugins, and must be added to render terrains.
+pub struct TerrainMaterialPlugin;
+
+impl Plugin for TerrainMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TERRAIN_SHADER_HANDLE,
+            "render/terrain.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<TerrainExtension>()
+            .add_plugins(MaterialPlugin::<TerrainMaterial>::default());
+    }
+}
+
+/// Extends a [`StandardMaterial`] with layers of textures blended by splat mapping, so that
+/// heightmap terrains can mix grass, rock or sand without a custom shader.
+///
+/// The weight of each layer is read from a control texture, mapped on the mesh UVs so that it
+/// covers the whole terrain. The layers are projected on the terrain in world space, from above
+/// or along the three axes with [`triplanar`](Self::triplanar), which keeps steep cliffs from
+/// being stretched. The blended color is multiplied by the base color of the
+/// [`StandardMaterial`], whose other properties, such as its roughness, apply to every layer.
+///
+/// The textures are 2D array textures, with one layer per terrain layer, which can be loaded from
+/// images with their layers stacked vertically with
+/// [`ImageLoaderSettings::array_layout`](bevy_render::texture::ImageLoaderSettings::array_layout).
+/// The layer textures should use a repeating sampler. The material views every texture as a 2D
+/// array of all its layers, ignoring its
+/// [`texture_view_descriptor`](Image::texture_view_descriptor), so that textures with a single
+/// layer, such as the control texture of up to four layers, can be used without modifying them.
+#[derive(Asset, Reflect, Debug, Clone)]
+#[reflect(Default, Debug)]
+pub struct TerrainExtension {
+    /// The weights of the layers, four layers per texture layer: the red channel of its first
+    /// layer is the weight of the first terrain layer, its alpha channel the weight of the fourth
+    /// terrain layer, and the red channel of its second layer the weight of the fifth one.
+    ///
+    /// The weights are normalized, so they don't have to add up to one. The texture should be
+    /// linear, and not sRGB.
+    pub control_texture: Handle<Image>,
+    /// The base color of each layer.
+    pub base_color_texture: Handle<Image>,
+    /// The tangent space normal map of each layer, which should be linear, and not sRGB.
+    ///
+    /// The terrain uses the normals of its mesh if this is `None`.
+    pub normal_map_texture: Option<Handle<Image>>,
+    /// How many times the texture of each layer repeats per world unit, which also sets the
+    /// number of layers, up to [`MAX_TERRAIN_LAYERS`].
+    pub layer_tiling: Vec<f32>,
+    /// Whether the layers are projected along the three world axes and blended depending on the
+    /// surface normal, instead of only from above.
+    ///
+    /// This avoids stretching the textures on steep slopes, but samples the textures three times.
+    pub triplanar: bool,
+    /// How sharp the transition between the projections of [`triplanar`](Self::triplanar)
+    /// mapping is. Higher values reduce the blurry areas where the projections blend.
+    ///
+    /// Defaults to `4.0`.
+    pub triplanar_sharpness: f32,
+}
+
+impl Default for TerrainExtension {
+    fn default() -> Self {
+        Self {
+            control_texture: Handle::default(),
+            base_color_texture: Handle::default(),
+            normal_map_texture: None,
+            layer_tiling: Vec::new(),
+            triplanar: false,
+            triplanar_sharpness: 4.0,
+        }
+    }
+}
+
+impl TerrainExtension {
+    /// Creates a terrain extension with the given control and base color textures, and a tiling
+    /// for each layer.
+    pub fn new(
+        control_texture: Handle<Image>,
+        base_color_texture: Handle<Image>,
+        layer_tiling: impl Into<Vec<f32>>,
+    ) -> Self {
+        Self {
+            control_texture,
+            base_color_texture,
+            layer_tiling: layer_tiling.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The GPU representation of the uniform data of a [`TerrainExtension`].
+#[derive(Clone, Default, ShaderType)]
+pub struct TerrainUniform {
+    /// The tiling of the layers, packed four per vector.
+    pub layer_tiling: [Vec4; MAX_TERRAIN_LAYERS / 4],
+    pub layer_count: u32,
+    pub triplanar_sharpness: f32,
+}
+
+impl AsBindGroupShaderType<TerrainUniform> for TerrainExtension {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> TerrainUniform {
+        let layer_count = self.layer_tiling.len().min(MAX_TERRAIN_LAYERS);
+        let mut layer_tiling = [Vec4::ZERO; MAX_TERRAIN_LAYERS / 4];
+        for (index, &tiling) in self.layer_tiling[..layer_count].iter().enumerate() {
+            layer_tiling[index / 4][index % 4] = tiling;
+        }
+        TerrainUniform {
+            layer_tiling,
+            layer_count: layer_count as u32,
+            triplanar_sharpness: self.triplanar_sharpness,
+        }
+    }
+}
+
+/// The bindings of a [`TerrainExtension`], whose textures are bound by its [`AsBindGroup`]
+/// implementation before being viewed as 2D arrays.
+#[derive(AsBindGroup)]
+struct TerrainBindings {
+    #[uniform(100)]
+    uniform: TerrainUniform,
+    #[texture(101, dimension = "2d_array")]
+    #[sampler(102)]
+    control_texture: Handle<Image>,
+    #[texture(103, dimension = "2d_array")]
+    #[sampler(104)]
+    base_color_texture: Handle<Image>,
+    #[texture(105, dimension = "2d_array")]
+    #[sampler(106)]
+    normal_map_texture: Option<Handle<Image>>,
+}
+
+impl AsBindGroup for TerrainExtension {
+    type Data = TerrainExtensionKey;
+
+    fn label() -> Option<&'static str> {
+        Some("TerrainExtension")
+    }
+
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<GpuImage>,
+        fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self::Data>, AsBindGroupError> {
+        let mut bind_group = TerrainBindings {
+            uniform: self.as_bind_group_shader_type(images),
+            control_texture: self.control_texture.clone(),
+            base_color_texture: self.base_color_texture.clone(),
+            normal_map_texture: self.normal_map_texture.clone(),
+        }
+        .unprepared_bind_group(layout, render_device, images, fallback_image)?;
+
+        // The views of the images are only 2D arrays if they have several layers, or if their
+        // descriptor says so. Images with a single layer, including the default image, are
+        // viewed as 2D arrays here instead, so that the images shared with other materials are
+        // left untouched.
+        for (binding, resource) in &mut bind_group.bindings {
+            let handle = match binding {
+                101 => Some(&self.control_texture),
+                103 => Some(&self.base_color_texture),
+                105 => self.normal_map_texture.as_ref(),
+                _ => None,
+            };
+            let Some(image) = handle.and_then(|handle| images.get(handle)) else {
+                continue;
+            };
+            if image.texture.dimension() != TextureDimension::D2 {
+                continue;
+            }
+            *resource = OwnedBindingResource::TextureView(
+                image
+                    .texture
+                    .create_view(&TextureViewDescriptor {
+                        dimension: Some(TextureViewDimension::D2Array),
+                        ..Default::default()
+                    })
+                    .into(),
+            );
+        }
+
+        Ok(UnpreparedBindGroup {
+            bindings: bind_group.bindings,
+            data: self.into(),
+        })
+    }
+
+    fn bind_group_layout_entries(render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry> {
+        TerrainBindings::bind_group_layout_entries(render_device)
+    }
+}
+
+/// The pipeline key of a [`TerrainExtension`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TerrainExtensionKey {
+    triplanar: bool,
+    normal_map: bool,
+}
+
+impl From<&TerrainExtension> for TerrainExtensionKey {
+    fn from(extension: &TerrainExtension) -> Self {
+        Self {
+            triplanar: extension.triplanar,
+            normal_map: extension.normal_map_texture.is_some(),
+        }
+    }
+}
+
+impl MaterialExtension for TerrainExtension {
+    fn fragment_shader() -> ShaderRef {
+        TERRAIN_SHADER_HANDLE.into()
+    }
+
+    fn deferred_fragment_shader() -> ShaderRef {
+        TERRAIN_SHADER_HANDLE.into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            if key.bind_group_data.triplanar {
+                fragment.shader_defs.push("TERRAIN_TRIPLANAR".into());
+            }
+            if key.bind_group_data.normal_map {
+                fragment.shader_defs.push("TERRAIN_NORMAL_MAP".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec4;
+    use bevy_render::{render_asset::RenderAssets, render_resource::AsBindGroupShaderType};
+
+    use super::{TerrainExtension, MAX_TERRAIN_LAYERS};
+
+    #[test]
+    fn terrain_uniform_packs_layer_tiling() {
+        let extension = TerrainExtension {
+            layer_tiling: (1..=MAX_TERRAIN_LAYERS + 2).map(|i| i as f32).collect(),
+            ..Default::default()
+        };
+        let uniform = extension.as_bind_group_shader_type(&RenderAssets::default());
+        assert_eq!(uniform.layer_count, MAX_TERRAIN_LAYERS as u32);
+        assert_eq!(uniform.layer_tiling[0], Vec4::new(1., 2., 3., 4.));
+        assert_eq!(uniform.layer_tiling[3], Vec4::new(13., 14., 15., 16.));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin};
-use bevy_app::{Plugin, Startup, Update};
+use bevy_app::{Plugin, Startup, StartupSet, Update};
 use bevy_asset::{load_internal_asset, Asset, Assets, Handle};
 use bevy_color::{Color, LinearRgba};
 use bevy_ecs::prelude::*;
@@ -37,7 +37,10 @@ impl Plugin for WireframePlugin {
             .register_type::<WireframeColor>()
             .init_resource::<WireframeConfig>()
             .add_plugins(MaterialPlugin::<WireframeMaterial>::default())
-            .add_systems(Startup, setup_global_wireframe_material)
+            .add_systems(
+                Startup,
+                setup_global_wireframe_material.in_set(StartupSet::RenderSetup),
+            )
             .add_systems(
                 Update,
                 (
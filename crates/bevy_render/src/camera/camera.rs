@@ -35,6 +35,7 @@ use bevy_window::{
     WindowScaleFactorChanged,
 };
 use std::ops::Range;
+use thiserror::Error;
 use wgpu::{BlendState, LoadOp, TextureFormat, TextureUsages};
 
 use super::{ClearColorConfig, Projection};
@@ -197,12 +198,32 @@ impl Default for PhysicalCameraParameters {
 ///
 /// Adding a camera is typically done by adding a bundle, either the `Camera2dBundle` or the
 /// `Camera3dBundle`.
+///
+/// # Camera stacking
+///
+/// Several cameras can render to the same [`RenderTarget`], to layer a weapon viewmodel over the
+/// world, or the world over a sky rendered by another camera:
+///
+/// - the cameras are rendered by increasing [`order`](Self::order), each one drawing over the
+///   previous ones. Two active cameras sharing a target and an order are rendered in an
+///   unspecified order, and a warning is logged;
+/// - the first camera clears the target, and the following ones set
+///   [`clear_color`](Self::clear_color) to [`ClearColorConfig::None`] to draw over it;
+/// - the 3D cameras on a target share a depth buffer, which each one clears or keeps according to
+///   the `depth_load_op` of its `Camera3d`. Clearing it lets a viewmodel be drawn over the world
+///   without being cut by the walls the player stands against, keeping it lets the camera
+///   be occluded by what the previous cameras drew. 2D cameras always clear the depth buffer;
+/// - [`msaa_writeback`](Self::msaa_writeback) keeps the output of the previous cameras when MSAA
+///   is enabled, and [`output_mode`](Self::output_mode) lets the intermediate cameras skip
+///   writing to the target until the last one.
 #[derive(Component, Debug, Reflect, Clone)]
 #[reflect(Component, Default)]
 pub struct Camera {
     /// If set, this camera will render to the given [`Viewport`] rectangle within the configured [`RenderTarget`].
     pub viewport: Option<Viewport>,
     /// Cameras with a higher order are rendered later, and thus on top of lower order cameras.
+    ///
+    /// See [camera stacking](Camera#camera-stacking) to layer cameras on the same target.
     pub order: isize,
     /// If this is set to `true`, this camera will be rendered to its specified [`RenderTarget`]. If `false`, this
     /// camera will not be rendered.
@@ -346,36 +367,70 @@ impl Camera {
 
     /// Given a position in world space, use the camera to compute the viewport-space coordinates.
     ///
+    /// The viewport-space coordinates are in logical pixels, relative to the top-left corner of the
+    /// [`RenderTarget`]: if this camera has a custom [`Viewport`], its position is taken into
+    /// account, so the result can be compared with [`Window::cursor_position`] directly. Positions
+    /// outside of the viewport on the X and Y axes are still returned, which is useful to point
+    /// towards off-screen objects.
+    ///
     /// To get the coordinates in Normalized Device Coordinates, you should use
     /// [`world_to_ndc`](Self::world_to_ndc).
     ///
-    /// Returns `None` if any of these conditions occur:
-    /// - The computed coordinates are beyond the near or far plane
-    /// - The logical viewport size cannot be computed. See [`logical_viewport_size`](Camera::logical_viewport_size)
-    /// - The world coordinates cannot be mapped to the Normalized Device Coordinates. See [`world_to_ndc`](Camera::world_to_ndc)
+    /// # Errors
+    ///
+    /// - [`ViewportConversionError::NoViewportSize`] if the logical viewport rect cannot be
+    ///   computed. See [`logical_viewport_rect`](Camera::logical_viewport_rect).
+    /// - [`ViewportConversionError::InvalidData`] if the world position cannot be mapped to the
+    ///   Normalized Device Coordinates. See [`world_to_ndc`](Camera::world_to_ndc).
+    /// - [`ViewportConversionError::PastNearPlane`] if the position is behind the camera or closer
+    ///   than its near plane.
+    /// - [`ViewportConversionError::PastFarPlane`] if the position is beyond the far plane of the
+    ///   camera.
+    ///
     /// May also panic if `glam_assert` is enabled. See [`world_to_ndc`](Camera::world_to_ndc).
     #[doc(alias = "world_to_screen")]
     pub fn world_to_viewport(
         &self,
         camera_transform: &GlobalTransform,
         world_position: Vec3,
-    ) -> Option<Vec2> {
-        let target_size = self.logical_viewport_size()?;
-        let ndc_space_coords = self.world_to_ndc(camera_transform, world_position)?;
-        // NDC z-values outside of 0 < z < 1 are outside the (implicit) camera frustum and are thus not in viewport-space
-        if ndc_space_coords.z < 0.0 || ndc_space_coords.z > 1.0 {
-            return None;
+    ) -> Result<Vec2, ViewportConversionError> {
+        let target_rect = self
+            .logical_viewport_rect()
+            .ok_or(ViewportConversionError::NoViewportSize)?;
+        let ndc_space_coords = self
+            .world_to_ndc(camera_transform, world_position)
+            .ok_or(ViewportConversionError::InvalidData)?;
+        // NDC z-values outside of 0 < z < 1 are outside the (implicit) camera frustum and are thus not in viewport-space.
+        // The projections use a reversed Z buffer, so the near plane is at z = 1.
+        if ndc_space_coords.z > 1.0 {
+            return Err(ViewportConversionError::PastNearPlane);
+        }
+        if ndc_space_coords.z < 0.0 {
+            // Positions behind a perspective camera also end up with a negative depth.
+            let view_position = camera_transform
+                .compute_matrix()
+                .inverse()
+                .transform_point3(world_position);
+            return Err(if view_position.z >= 0.0 {
+                ViewportConversionError::PastNearPlane
+            } else {
+                ViewportConversionError::PastFarPlane
+            });
         }
 
-        // Once in NDC space, we can discard the z element and rescale x/y to fit the screen
-        let mut viewport_position = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * target_size;
+        // Once in NDC space, we can discard the z element and rescale x/y to fit the viewport
+        let mut rect_relative = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0;
         // Flip the Y co-ordinate origin from the bottom to the top.
-        viewport_position.y = target_size.y - viewport_position.y;
-        Some(viewport_position)
+        rect_relative.y = 1.0 - rect_relative.y;
+        Ok(target_rect.min + rect_relative * target_rect.size())
     }
 
     /// Returns a ray originating from the camera, that passes through everything beyond `viewport_position`.
     ///
+    /// The viewport position is in logical pixels, relative to the top-left corner of the
+    /// [`RenderTarget`], like [`Window::cursor_position`]: if this camera has a custom
+    /// [`Viewport`], its position is taken into account.
+    ///
     /// The resulting ray starts on the near plane of the camera.
     ///
     /// If the camera's projection is orthographic the direction of the ray is always equal to `camera_transform.forward()`.
@@ -383,19 +438,23 @@ impl Camera {
     /// To get the world space coordinates with Normalized Device Coordinates, you should use
     /// [`ndc_to_world`](Self::ndc_to_world).
     ///
-    /// Returns `None` if any of these conditions occur:
-    /// - The logical viewport size cannot be computed. See [`logical_viewport_size`](Camera::logical_viewport_size)
-    /// - The near or far plane cannot be computed. This can happen if the `camera_transform`, the `world_position`, or the projection matrix defined by [`CameraProjection`] contain `NAN`.
+    /// # Errors
+    ///
+    /// - [`ViewportConversionError::NoViewportSize`] if the logical viewport rect cannot be
+    ///   computed. See [`logical_viewport_rect`](Camera::logical_viewport_rect).
+    /// - [`ViewportConversionError::OutsideViewport`] if `viewport_position` isn't inside the
+    ///   viewport of this camera.
+    /// - [`ViewportConversionError::InvalidData`] if the near or far plane cannot be computed.
+    ///   This can happen if the `camera_transform`, the `viewport_position`, or the projection
+    ///   matrix defined by [`CameraProjection`] contain `NAN`.
+    ///
     /// Panics if the projection matrix is null and `glam_assert` is enabled.
     pub fn viewport_to_world(
         &self,
         camera_transform: &GlobalTransform,
-        mut viewport_position: Vec2,
-    ) -> Option<Ray3d> {
-        let target_size = self.logical_viewport_size()?;
-        // Flip the Y co-ordinate origin from the top to the bottom.
-        viewport_position.y = target_size.y - viewport_position.y;
-        let ndc = viewport_position * 2. / target_size - Vec2::ONE;
+        viewport_position: Vec2,
+    ) -> Result<Ray3d, ViewportConversionError> {
+        let ndc = self.viewport_to_ndc(viewport_position)?;
 
         let ndc_to_world =
             camera_transform.compute_matrix() * self.computed.projection_matrix.inverse();
@@ -404,38 +463,88 @@ impl Camera {
         let world_far_plane = ndc_to_world.project_point3(ndc.extend(f32::EPSILON));
 
         // The fallible direction constructor ensures that world_near_plane and world_far_plane aren't NaN.
-        Dir3::new(world_far_plane - world_near_plane).map_or(None, |direction| {
-            Some(Ray3d {
+        Dir3::new(world_far_plane - world_near_plane)
+            .map(|direction| Ray3d {
                 origin: world_near_plane,
                 direction,
             })
-        })
+            .map_err(|_| ViewportConversionError::InvalidData)
+    }
+
+    /// Returns the ray cast by a pointer at `viewport_position` through this camera, or `None`
+    /// if the camera isn't active or doesn't see the pointer.
+    ///
+    /// The viewport position is in logical pixels, relative to the top-left corner of the
+    /// [`RenderTarget`], like [`Window::cursor_position`] and touch positions. This is the ray
+    /// the picking backends intersect with the entities seen by the camera.
+    ///
+    /// See [`viewport_to_world`](Self::viewport_to_world) for the reasons a ray can't be
+    /// computed.
+    pub fn viewport_ray(
+        &self,
+        camera_transform: &GlobalTransform,
+        viewport_position: Vec2,
+    ) -> Option<Ray3d> {
+        if !self.is_active {
+            return None;
+        }
+        self.viewport_to_world(camera_transform, viewport_position)
+            .ok()
     }
 
     /// Returns a 2D world position computed from a position on this [`Camera`]'s viewport.
     ///
     /// Useful for 2D cameras and other cameras with an orthographic projection pointing along the Z axis.
+    /// This is cheaper than [`viewport_to_world`](Self::viewport_to_world), since it only
+    /// projects the position on the near plane.
+    ///
+    /// The viewport position is in logical pixels, relative to the top-left corner of the
+    /// [`RenderTarget`], like [`Window::cursor_position`]: if this camera has a custom
+    /// [`Viewport`], its position is taken into account.
     ///
     /// To get the world space coordinates with Normalized Device Coordinates, you should use
     /// [`ndc_to_world`](Self::ndc_to_world).
     ///
-    /// Returns `None` if any of these conditions occur:
-    /// - The logical viewport size cannot be computed. See [`logical_viewport_size`](Camera::logical_viewport_size)
-    /// - The viewport position cannot be mapped to the world. See [`ndc_to_world`](Camera::ndc_to_world)
+    /// # Errors
+    ///
+    /// - [`ViewportConversionError::NoViewportSize`] if the logical viewport rect cannot be
+    ///   computed. See [`logical_viewport_rect`](Camera::logical_viewport_rect).
+    /// - [`ViewportConversionError::OutsideViewport`] if `viewport_position` isn't inside the
+    ///   viewport of this camera.
+    /// - [`ViewportConversionError::InvalidData`] if the viewport position cannot be mapped to
+    ///   the world. See [`ndc_to_world`](Camera::ndc_to_world).
+    ///
     /// May panic. See [`ndc_to_world`](Camera::ndc_to_world).
     pub fn viewport_to_world_2d(
         &self,
         camera_transform: &GlobalTransform,
-        mut viewport_position: Vec2,
-    ) -> Option<Vec2> {
-        let target_size = self.logical_viewport_size()?;
-        // Flip the Y co-ordinate origin from the top to the bottom.
-        viewport_position.y = target_size.y - viewport_position.y;
-        let ndc = viewport_position * 2. / target_size - Vec2::ONE;
+        viewport_position: Vec2,
+    ) -> Result<Vec2, ViewportConversionError> {
+        let ndc = self.viewport_to_ndc(viewport_position)?;
+
+        let world_near_plane = self
+            .ndc_to_world(camera_transform, ndc.extend(1.))
+            .ok_or(ViewportConversionError::InvalidData)?;
 
-        let world_near_plane = self.ndc_to_world(camera_transform, ndc.extend(1.))?;
+        Ok(world_near_plane.truncate())
+    }
 
-        Some(world_near_plane.truncate())
+    /// Converts a position relative to the top-left corner of the [`RenderTarget`] to the X and
+    /// Y Normalized Device Coordinates of this camera's viewport.
+    fn viewport_to_ndc(&self, viewport_position: Vec2) -> Result<Vec2, ViewportConversionError> {
+        let target_rect = self
+            .logical_viewport_rect()
+            .ok_or(ViewportConversionError::NoViewportSize)?;
+        if viewport_position.is_nan() {
+            return Err(ViewportConversionError::InvalidData);
+        }
+        if !target_rect.contains(viewport_position) {
+            return Err(ViewportConversionError::OutsideViewport);
+        }
+        let mut rect_relative = (viewport_position - target_rect.min) / target_rect.size();
+        // Flip the Y co-ordinate origin from the top to the bottom.
+        rect_relative.y = 1.0 - rect_relative.y;
+        Ok(rect_relative * 2. - Vec2::ONE)
     }
 
     /// Given a position in world space, use the camera's viewport to compute the Normalized Device Coordinates.
@@ -481,6 +590,31 @@ impl Camera {
     }
 }
 
+/// An error returned when converting between viewport and world coordinates with a [`Camera`].
+///
+/// See [`Camera::world_to_viewport`], [`Camera::viewport_to_world`] and
+/// [`Camera::viewport_to_world_2d`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewportConversionError {
+    /// The size of the viewport isn't known yet, because the camera hasn't been updated by
+    /// [`camera_system`] or its [`RenderTarget`] is invalid.
+    #[error("the viewport size of the camera isn't available")]
+    NoViewportSize,
+    /// The viewport position is outside of the viewport of the camera.
+    #[error("the position is outside of the viewport of the camera")]
+    OutsideViewport,
+    /// The world position is behind the camera or closer than its near plane.
+    #[error("the position is behind the near plane of the camera")]
+    PastNearPlane,
+    /// The world position is beyond the far plane of the camera.
+    #[error("the position is beyond the far plane of the camera")]
+    PastFarPlane,
+    /// The computation produced a NaN, because the camera transform, the projection or the
+    /// converted position is invalid.
+    #[error("the conversion produced NaN values")]
+    InvalidData,
+}
+
 /// Control how this camera outputs once rendering is completed.
 #[derive(Debug, Clone, Copy)]
 pub enum CameraOutputMode {
@@ -1043,3 +1177,105 @@ impl TemporalJitter {
 #[derive(Default, Component, Reflect)]
 #[reflect(Default, Component)]
 pub struct MipBias(pub f32);
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{UVec2, Vec2, Vec3};
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{
+        Camera, ComputedCameraValues, RenderTargetInfo, Viewport, ViewportConversionError,
+    };
+    use crate::camera::{CameraProjection, OrthographicProjection};
+
+    /// A camera rendering to the right half of a 200x100 logical pixels target, with a scale
+    /// factor of 2.
+    fn camera() -> Camera {
+        let mut projection = OrthographicProjection::default();
+        projection.update(100., 100.);
+        Camera {
+            viewport: Some(Viewport {
+                physical_position: UVec2::new(200, 0),
+                physical_size: UVec2::new(200, 200),
+                ..Default::default()
+            }),
+            computed: ComputedCameraValues {
+                projection_matrix: projection.get_projection_matrix(),
+                target_info: Some(RenderTargetInfo {
+                    physical_size: UVec2::new(400, 200),
+                    scale_factor: 2.,
+                }),
+                old_viewport_size: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn viewport_conversions_use_viewport_position() {
+        let camera = camera();
+        let transform = GlobalTransform::IDENTITY;
+
+        // The center of the viewport is the origin of the world.
+        assert_eq!(
+            camera.world_to_viewport(&transform, Vec3::ZERO),
+            Ok(Vec2::new(150., 50.))
+        );
+        let world_position = camera
+            .viewport_to_world_2d(&transform, Vec2::new(175., 25.))
+            .unwrap();
+        assert!(world_position.abs_diff_eq(Vec2::new(25., 25.), 1e-4));
+        let ray = camera
+            .viewport_to_world(&transform, Vec2::new(175., 25.))
+            .unwrap();
+        assert!(ray.origin.truncate().abs_diff_eq(Vec2::new(25., 25.), 1e-4));
+
+        assert_eq!(
+            camera.viewport_to_world_2d(&transform, Vec2::new(50., 50.)),
+            Err(ViewportConversionError::OutsideViewport)
+        );
+        assert_eq!(
+            camera.viewport_to_world_2d(&transform, Vec2::NAN),
+            Err(ViewportConversionError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn viewport_ray() {
+        let mut camera = camera();
+        let transform = GlobalTransform::IDENTITY;
+
+        let ray = camera.viewport_ray(&transform, Vec2::new(175., 25.));
+        assert_eq!(
+            ray,
+            camera
+                .viewport_to_world(&transform, Vec2::new(175., 25.))
+                .ok()
+        );
+        assert!(ray.is_some());
+        assert_eq!(camera.viewport_ray(&transform, Vec2::new(50., 50.)), None);
+
+        camera.is_active = false;
+        assert_eq!(camera.viewport_ray(&transform, Vec2::new(175., 25.)), None);
+    }
+
+    #[test]
+    fn world_to_viewport_errors() {
+        let camera = camera();
+        let transform = GlobalTransform::IDENTITY;
+        let far = OrthographicProjection::default().far;
+
+        assert_eq!(
+            camera.world_to_viewport(&transform, Vec3::new(0., 0., 1.)),
+            Err(ViewportConversionError::PastNearPlane)
+        );
+        assert_eq!(
+            camera.world_to_viewport(&transform, Vec3::new(0., 0., -far - 1.)),
+            Err(ViewportConversionError::PastFarPlane)
+        );
+        assert_eq!(
+            Camera::default().world_to_viewport(&transform, Vec3::ZERO),
+            Err(ViewportConversionError::NoViewportSize)
+        );
+    }
+}
@@ -45,3 +45,21 @@ fn rgb_to_hsv(rgb: vec3<f32>) -> vec3<f32> {
     return vec3(h, s, x_max);
 }
 
+
+// Converts sRGB-encoded color components to linear color components.
+//
+// <https://en.wikipedia.org/wiki/SRGB#From_sRGB_to_CIE_XYZ>
+fn srgb_to_linear(srgb: vec3<f32>) -> vec3<f32> {
+    let low = srgb / 12.92;
+    let high = pow((srgb + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, srgb <= vec3(0.04045));
+}
+
+// Converts linear color components to sRGB-encoded color components.
+//
+// <https://en.wikipedia.org/wiki/SRGB#From_CIE_XYZ_to_sRGB>
+fn linear_to_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 12.92;
+    let high = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, linear <= vec3(0.0031308));
+}
@@ -3,6 +3,7 @@
 //! For more info, see [`RenderDiagnosticsPlugin`].
 
 pub(crate) mod internal;
+mod statistics;
 
 use std::{borrow::Cow, marker::PhantomData, sync::Arc};
 
@@ -10,6 +11,9 @@ use bevy_app::{App, Plugin, PreUpdate};
 
 use crate::RenderApp;
 
+pub(crate) use self::statistics::RenderStatisticsRecorder;
+pub use self::statistics::{RenderStatistics, RenderStatisticsPlugin, ViewRenderStatistics};
+
 use self::internal::{
     sync_diagnostics, DiagnosticsRecorder, Pass, RenderDiagnosticsMutex, WriteTimestamp,
 };
@@ -0,0 +1,233 @@
+use std::{
+    ops::{Add, AddAssign, Sub},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut, Resource},
+};
+use bevy_reflect::Reflect;
+use bevy_utils::Instant;
+
+use crate::{Render, RenderApp, RenderSet};
+
+/// Counts the draw calls, instances, triangles and phase items of each view, and copies them
+/// back to the main world in [`ViewRenderStatistics`].
+///
+/// The totals over all views are also added to the
+/// [`DiagnosticsStore`](bevy_diagnostic::DiagnosticsStore) if it exists, at the paths of the
+/// `RenderStatisticsPlugin` constants, so that they are shown by the
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin).
+///
+/// Only the draws of the render phases are counted, which leaves out full screen passes such as
+/// post processing.
+#[derive(Default)]
+pub struct RenderStatisticsPlugin;
+
+impl RenderStatisticsPlugin {
+    /// The number of draw calls of all views.
+    pub const DRAW_CALLS: DiagnosticPath = DiagnosticPath::const_new("render/draw_calls");
+    /// The number of instances drawn by all views.
+    pub const INSTANCES: DiagnosticPath = DiagnosticPath::const_new("render/instances");
+    /// The number of triangles drawn by all views, see [`RenderStatistics::triangles`].
+    pub const TRIANGLES: DiagnosticPath = DiagnosticPath::const_new("render/triangles");
+    /// The number of phase items drawn by all views.
+    pub const PHASE_ITEMS: DiagnosticPath = DiagnosticPath::const_new("render/phase_items");
+}
+
+impl Plugin for RenderStatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        let render_statistics_mutex = RenderStatisticsMutex::default();
+        app.init_resource::<ViewRenderStatistics>()
+            .insert_resource(render_statistics_mutex.clone())
+            .add_systems(PreUpdate, sync_render_statistics);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(render_statistics_mutex)
+                .init_resource::<RenderStatisticsRecorder>()
+                .add_systems(Render, send_render_statistics.in_set(RenderSet::Cleanup));
+        }
+    }
+}
+
+/// The work submitted to the GPU to render a view, see [`RenderStatisticsPlugin`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct RenderStatistics {
+    /// The number of draw calls, including the indirect ones.
+    ///
+    /// The indirect draw calls whose count is read from a buffer by the GPU are counted once.
+    pub draw_calls: u32,
+    /// The number of instances drawn by the direct draw calls.
+    pub instances: u64,
+    /// The number of triangles drawn by the direct draw calls.
+    ///
+    /// This assumes that every draw call uses a triangle list: strips, lines and points are
+    /// counted as if they were triangle lists. The instances and triangles of indirect draw
+    /// calls are only known by the GPU, so they aren't counted.
+    pub triangles: u64,
+    /// The number of phase items drawn, each of which may draw several entities as one batch.
+    pub phase_items: u32,
+}
+
+impl Add for RenderStatistics {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            draw_calls: self.draw_calls + rhs.draw_calls,
+            instances: self.instances + rhs.instances,
+            triangles: self.triangles + rhs.triangles,
+            phase_items: self.phase_items + rhs.phase_items,
+        }
+    }
+}
+
+impl AddAssign for RenderStatistics {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for RenderStatistics {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            draw_calls: self.draw_calls - rhs.draw_calls,
+            instances: self.instances - rhs.instances,
+            triangles: self.triangles - rhs.triangles,
+            phase_items: self.phase_items - rhs.phase_items,
+        }
+    }
+}
+
+/// The [`RenderStatistics`] of each view in the last rendered frame, see
+/// [`RenderStatisticsPlugin`].
+///
+/// The views are the entities of the render world: the views of cameras have the entity of their
+/// camera, while the other views, such as the shadow views of lights, only exist in the render
+/// world.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ViewRenderStatistics {
+    views: EntityHashMap<RenderStatistics>,
+}
+
+impl ViewRenderStatistics {
+    /// Returns the statistics of the view `entity`, or `None` if it wasn't rendered.
+    pub fn get(&self, entity: Entity) -> Option<RenderStatistics> {
+        self.views.get(&entity).copied()
+    }
+
+    /// Returns the statistics of each rendered view.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, RenderStatistics)> + '_ {
+        self.views
+            .iter()
+            .map(|(&entity, &statistics)| (entity, statistics))
+    }
+
+    /// Returns the sum of the statistics of all views.
+    pub fn total(&self) -> RenderStatistics {
+        self.views
+            .values()
+            .fold(RenderStatistics::default(), |total, &statistics| {
+                total + statistics
+            })
+    }
+}
+
+/// Collects the [`RenderStatistics`] of the views while the render phases are drawn, from the
+/// render graph nodes which only have shared access to the render world.
+#[derive(Resource, Default)]
+pub(crate) struct RenderStatisticsRecorder(Mutex<ViewRenderStatistics>);
+
+impl RenderStatisticsRecorder {
+    /// Adds `statistics` to the statistics of `view` for the current frame.
+    pub(crate) fn record(&self, view: Entity, statistics: RenderStatistics) {
+        let mut views = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        *views.views.entry(view).or_default() += statistics;
+    }
+}
+
+/// Sends the [`ViewRenderStatistics`] of the render world to the main world.
+#[derive(Resource, Default, Clone)]
+struct RenderStatisticsMutex(Arc<Mutex<Option<ViewRenderStatistics>>>);
+
+fn send_render_statistics(
+    recorder: Res<RenderStatisticsRecorder>,
+    mutex: Res<RenderStatisticsMutex>,
+) {
+    let statistics =
+        std::mem::take(&mut *recorder.0.lock().unwrap_or_else(PoisonError::into_inner));
+    *mutex.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(statistics);
+}
+
+fn sync_render_statistics(
+    mutex: Res<RenderStatisticsMutex>,
+    mut view_statistics: ResMut<ViewRenderStatistics>,
+    store: Option<ResMut<DiagnosticsStore>>,
+) {
+    let Some(statistics) = mutex
+        .0
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+    else {
+        return;
+    };
+    *view_statistics = statistics;
+
+    let Some(mut store) = store else {
+        return;
+    };
+    let time = Instant::now();
+    let total = view_statistics.total();
+    for (path, value) in [
+        (RenderStatisticsPlugin::DRAW_CALLS, total.draw_calls as f64),
+        (RenderStatisticsPlugin::INSTANCES, total.instances as f64),
+        (RenderStatisticsPlugin::TRIANGLES, total.triangles as f64),
+        (
+            RenderStatisticsPlugin::PHASE_ITEMS,
+            total.phase_items as f64,
+        ),
+    ] {
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()));
+        }
+        store
+            .get_mut(&path)
+            .unwrap()
+            .add_measurement(DiagnosticMeasurement { time, value });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_add_up_per_view() {
+        let recorder = RenderStatisticsRecorder::default();
+        let first = Entity::from_raw(0);
+        let second = Entity::from_raw(1);
+        let draw = RenderStatistics {
+            draw_calls: 1,
+            instances: 2,
+            triangles: 4,
+            phase_items: 1,
+        };
+        recorder.record(first, draw);
+        recorder.record(first, draw);
+        recorder.record(second, draw);
+
+        let statistics = recorder.0.into_inner().unwrap();
+        assert_eq!(statistics.get(first), Some(draw + draw));
+        assert_eq!(statistics.get(second), Some(draw));
+        assert_eq!(statistics.total(), draw + draw + draw);
+        assert_eq!(statistics.total() - draw, draw + draw);
+    }
+}
@@ -43,7 +43,10 @@ fn extract_time(mut commands: Commands, time: Extract<Res<Time>>) {
 }
 
 /// Contains global values useful when writing shaders.
-/// Currently only contains values related to time.
+///
+/// It is bound as `globals` in the view bind group of the 2D, 3D and UI pipelines, including
+/// sprites, 2D meshes and materials. Per-view values, like the resolution of the viewport, are
+/// part of the [`ViewUniform`](crate::view::ViewUniform) instead.
 #[derive(Default, Clone, Resource, ExtractResource, Reflect, ShaderType)]
 #[reflect(Resource, Default)]
 pub struct GlobalsUniform {
@@ -55,9 +58,9 @@ pub struct GlobalsUniform {
     /// Frame count since the start of the app.
     /// It wraps to zero when it reaches the maximum value of a u32.
     frame_count: u32,
-    /// WebGL2 structs must be 16 byte aligned.
-    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    _wasm_padding: f32,
+    /// A pseudo-random number, different every frame, to seed noise and other random effects.
+    /// It only depends on the frame count, so it is the same on every run.
+    seed: u32,
 }
 
 /// The buffer containing the [`GlobalsUniform`]
@@ -77,8 +80,16 @@ fn prepare_globals_buffer(
     buffer.time = time.elapsed_seconds_wrapped();
     buffer.delta_time = time.delta_seconds();
     buffer.frame_count = frame_count.0;
+    buffer.seed = hash_frame_count(frame_count.0);
 
     globals_buffer
         .buffer
         .write_buffer(&render_device, &render_queue);
 }
+
+/// Hashes the frame count with the PCG hash, to get a well distributed seed for each frame.
+fn hash_frame_count(frame_count: u32) -> u32 {
+    let state = frame_count.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
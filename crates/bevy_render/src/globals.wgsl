@@ -9,8 +9,7 @@ struct Globals {
     // Frame count since the start of the app.
     // It wraps to zero when it reaches the maximum value of a u32.
     frame_count: u32,
-#ifdef SIXTEEN_BYTE_ALIGNMENT
-    // WebGL2 structs must be 16 byte aligned.
-    _webgl2_padding: f32
-#endif
+    // A pseudo-random number, different every frame, to seed noise and other random effects.
+    // It only depends on the frame count, so it is the same on every run.
+    seed: u32,
 };
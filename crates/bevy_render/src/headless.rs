@@ -0,0 +1,245 @@
+//! Rendering without a window, to an offscreen image read back to the CPU every frame.
+//!
+//! See [`HeadlessRenderPlugin`].
+
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_time::TimeUpdateStrategy;
+use bevy_utils::tracing::error;
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+    ImageDataLayout, Maintain, MapMode, TextureDimension, TextureFormat, TextureUsages,
+};
+
+use crate::{
+    camera::RenderTarget,
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_resource::PipelineCache,
+    renderer::{render_system, RenderDevice, RenderQueue},
+    texture::{GpuImage, Image, TextureFormatPixelInfo},
+    Render, RenderApp, RenderSet,
+};
+
+/// Renders to an offscreen image instead of a window, and reads each frame back to the CPU, to
+/// write screenshot regression tests running in CI.
+///
+/// The cameras rendering the tested scene target the image of the [`HeadlessRenderTarget`], and
+/// the test steps the frames by calling [`App::update`], then compares
+/// [`HeadlessRenderTarget::take_frame`] with a reference image. Each frame advances the [`Time`]
+/// by [`frame_time`](Self::frame_time), so that animations are the same on every run.
+///
+/// The rest of the app must run without a window, and compile the pipelines as they are needed:
+///
+/// ```no_run
+/// # use bevy_app::{App, PluginGroup};
+/// # use bevy_render::{headless::HeadlessRenderPlugin, RenderPlugin};
+/// # use bevy_window::{ExitCondition, WindowPlugin};
+/// # fn configure(default_plugins: impl PluginGroup) {
+/// let mut app = App::new();
+/// app.add_plugins((
+///     default_plugins
+///         .set(WindowPlugin {
+///             primary_window: None,
+///             exit_condition: ExitCondition::DontExit,
+///             close_when_requested: false,
+///         })
+///         .set(RenderPlugin {
+///             synchronous_pipeline_compilation: true,
+///             ..Default::default()
+///         }),
+///     // The frames are only read back once all their pipelines are compiled.
+///     HeadlessRenderPlugin::default(),
+/// ));
+/// # }
+/// ```
+///
+/// `DefaultPlugins` should also disable the `WinitPlugin`, which requires a display, and the
+/// [`PipelinedRenderingPlugin`](crate::pipelined_rendering::PipelinedRenderingPlugin), which
+/// renders each frame during the next update.
+///
+/// [`Time`]: bevy_time::Time
+pub struct HeadlessRenderPlugin {
+    /// The size of the image rendered to, in physical pixels.
+    pub size: UVec2,
+    /// The time elapsed between two frames.
+    pub frame_time: Duration,
+}
+
+impl Default for HeadlessRenderPlugin {
+    fn default() -> Self {
+        Self {
+            size: UVec2::new(1280, 720),
+            frame_time: Duration::from_secs_f64(1. / 60.),
+        }
+    }
+}
+
+impl Plugin for HeadlessRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(self.frame_time))
+            .add_plugins(ExtractResourcePlugin::<HeadlessRenderTarget>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                read_back_headless_frame
+                    .in_set(RenderSet::Render)
+                    .after(render_system),
+            );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage |= TextureUsages::COPY_SRC
+            | TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::TEXTURE_BINDING;
+        let image = app.world_mut().resource_mut::<Assets<Image>>().add(image);
+
+        app.insert_resource(HeadlessRenderTarget {
+            image,
+            frame: Default::default(),
+        });
+    }
+}
+
+/// The image rendered to by the [`HeadlessRenderPlugin`], and the last frame read back from it.
+#[derive(Resource, Clone, Debug)]
+pub struct HeadlessRenderTarget {
+    image: Handle<Image>,
+    frame: Arc<Mutex<Option<Image>>>,
+}
+
+impl HeadlessRenderTarget {
+    /// Returns the image rendered to.
+    pub fn image(&self) -> &Handle<Image> {
+        &self.image
+    }
+
+    /// Returns the [`RenderTarget`] of the cameras rendering to the image.
+    pub fn render_target(&self) -> RenderTarget {
+        RenderTarget::Image(self.image.clone())
+    }
+
+    /// Takes the last frame read back from the image, or returns `None` if no frame was rendered
+    /// since the last call.
+    ///
+    /// The frames rendered while pipelines are still compiling aren't read back, as they may be
+    /// missing some of the scene.
+    pub fn take_frame(&self) -> Option<Image> {
+        self.frame
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl ExtractResource for HeadlessRenderTarget {
+    type Source = Self;
+
+    fn extract_resource(source: &Self::Source) -> Self {
+        source.clone()
+    }
+}
+
+/// Copies the image of the [`HeadlessRenderTarget`] to a buffer once the frame is rendered, and
+/// waits for the GPU to read it back.
+fn read_back_headless_frame(
+    target: Res<HeadlessRenderTarget>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let Some(gpu_image) = gpu_images.get(target.image.id()) else {
+        return;
+    };
+    if pipeline_cache.waiting_pipelines().next().is_some() {
+        return;
+    }
+
+    let size = gpu_image.size;
+    let row_bytes = size.x as usize * gpu_image.texture_format.pixel_size();
+    let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("headless_read_back_buffer"),
+        size: (padded_row_bytes * size.y as usize) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("headless_read_back"),
+    });
+    encoder.copy_texture_to_buffer(
+        gpu_image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+    );
+    render_queue.submit([encoder.finish()]);
+
+    // Waiting for the GPU keeps each frame read back during its own update.
+    let buffer_slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    render_device.map_buffer(&buffer_slice, MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    render_device.poll(Maintain::Wait);
+    match receiver.recv() {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => {
+            error!("Failed to read back the headless frame: {err}");
+            return;
+        }
+        Err(_) => return,
+    }
+
+    let data = buffer_slice
+        .get_mapped_range()
+        .chunks(padded_row_bytes)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect();
+    buffer.unmap();
+
+    *target.frame.lock().unwrap_or_else(PoisonError::into_inner) = Some(Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        gpu_image.texture_format,
+        RenderAssetUsages::default(),
+    ));
+}
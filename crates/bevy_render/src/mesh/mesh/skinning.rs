@@ -1,12 +1,16 @@
 use bevy_asset::{Asset, Handle};
+use bevy_core::Name;
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
     prelude::ReflectComponent,
     reflect::ReflectMapEntities,
+    system::{Commands, Query, SystemParam},
 };
+use bevy_hierarchy::{BuildChildren, Parent};
 use bevy_math::Mat4;
 use bevy_reflect::prelude::*;
+use bevy_transform::components::GlobalTransform;
 use std::ops::Deref;
 
 #[derive(Component, Debug, Default, Clone, Reflect)]
@@ -39,3 +43,126 @@ impl Deref for SkinnedMeshInverseBindposes {
         &self.0
     }
 }
+
+/// A [`SystemParam`] giving access to the joints of [`SkinnedMesh`]es by name.
+///
+/// Joint transforms reflect the output of the animation systems once
+/// [`TransformSystem::TransformPropagate`](bevy_transform::TransformSystem::TransformPropagate) has run in [`PostUpdate`](bevy_app::PostUpdate).
+#[derive(SystemParam)]
+pub struct SkinnedMeshJoints<'w, 's> {
+    skinned_meshes: Query<'w, 's, &'static SkinnedMesh>,
+    names: Query<'w, 's, &'static Name>,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
+}
+
+impl<'w, 's> SkinnedMeshJoints<'w, 's> {
+    /// Returns the joint entity of the skinned mesh `skinned_mesh` whose [`Name`] is `joint_name`.
+    ///
+    /// Returns `None` if `skinned_mesh` has no [`SkinnedMesh`] component, or if none of its
+    /// joints is named `joint_name`.
+    pub fn find_joint(&self, skinned_mesh: Entity, joint_name: &str) -> Option<Entity> {
+        let skinned_mesh = self.skinned_meshes.get(skinned_mesh).ok()?;
+        skinned_mesh.joints.iter().copied().find(|joint| {
+            self.names
+                .get(*joint)
+                .is_ok_and(|name| name.as_str() == joint_name)
+        })
+    }
+
+    /// Returns the world-space transform of the joint named `joint_name` of `skinned_mesh`.
+    pub fn joint_transform(
+        &self,
+        skinned_mesh: Entity,
+        joint_name: &str,
+    ) -> Option<GlobalTransform> {
+        let joint = self.find_joint(skinned_mesh, joint_name)?;
+        self.transforms.get(joint).ok().copied()
+    }
+}
+
+/// Attaches an entity to a named joint of a [`SkinnedMesh`].
+///
+/// The entity is made a child of the joint entity by [`attach_to_bones`], so that its
+/// [`Transform`](bevy_transform::components::Transform) becomes relative to the joint and
+/// follows it as the skinned mesh is animated. If the joint can't be found yet (for example
+/// because the scene containing the skinned mesh is still being spawned), the attachment is
+/// retried every frame.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct AttachedToBone {
+    /// The entity holding the [`SkinnedMesh`] component.
+    pub skinned_mesh: Entity,
+    /// The [`Name`] of the joint to attach to.
+    pub joint_name: Name,
+}
+
+impl AttachedToBone {
+    /// Creates a new [`AttachedToBone`] attaching to the joint `joint_name` of `skinned_mesh`.
+    pub fn new(skinned_mesh: Entity, joint_name: impl Into<Name>) -> Self {
+        Self {
+            skinned_mesh,
+            joint_name: joint_name.into(),
+        }
+    }
+}
+
+impl MapEntities for AttachedToBone {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.skinned_mesh = entity_mapper.map_entity(self.skinned_mesh);
+    }
+}
+
+/// Parents each entity with an [`AttachedToBone`] component to the joint it references.
+pub fn attach_to_bones(
+    mut commands: Commands,
+    attached: Query<(Entity, &AttachedToBone, Option<&Parent>)>,
+    joints: SkinnedMeshJoints,
+) {
+    for (entity, attached_to_bone, parent) in &attached {
+        let Some(joint) =
+            joints.find_joint(attached_to_bone.skinned_mesh, &attached_to_bone.joint_name)
+        else {
+            continue;
+        };
+        if parent.map(Parent::get) != Some(joint) {
+            commands.entity(joint).add_child(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_hierarchy::Children;
+
+    use super::*;
+
+    #[test]
+    fn attach_to_named_joint() {
+        let mut world = World::new();
+        let root = world.spawn(Name::new("root")).id();
+        let hand = world
+            .spawn((Name::new("hand"), GlobalTransform::default()))
+            .id();
+        let mesh = world
+            .spawn(SkinnedMesh {
+                inverse_bindposes: Default::default(),
+                joints: vec![root, hand],
+            })
+            .id();
+        let sword = world.spawn(AttachedToBone::new(mesh, "hand")).id();
+        let shield = world.spawn(AttachedToBone::new(mesh, "missing")).id();
+
+        world.run_system_once(attach_to_bones);
+
+        assert_eq!(world.get::<Parent>(sword).map(Parent::get), Some(hand));
+        assert!(world.get::<Parent>(shield).is_none());
+        assert_eq!(&**world.get::<Children>(hand).unwrap(), &[sword]);
+
+        world.run_system_once(move |joints: SkinnedMeshJoints| {
+            assert_eq!(joints.find_joint(mesh, "root"), Some(root));
+            assert!(joints.joint_transform(mesh, "hand").is_some());
+            assert!(joints.joint_transform(mesh, "root").is_none());
+        });
+    }
+}
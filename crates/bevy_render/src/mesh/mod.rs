@@ -12,9 +12,10 @@ use std::{
 };
 
 use crate::{render_asset::RenderAssetPlugin, texture::GpuImage, RenderApp};
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::AssetApp;
-use bevy_ecs::{entity::Entity, system::Resource};
+use bevy_ecs::{entity::Entity, schedule::IntoSystemConfigs, system::Resource};
+use bevy_transform::TransformSystem;
 
 /// Adds the [`Mesh`] as an asset and makes sure that they are extracted and prepared for the GPU.
 pub struct MeshPlugin;
@@ -25,9 +26,14 @@ impl Plugin for MeshPlugin {
             .init_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_asset_reflect::<Mesh>()
             .register_type::<skinning::SkinnedMesh>()
+            .register_type::<skinning::AttachedToBone>()
             .register_type::<Vec<Entity>>()
             // 'Mesh' must be prepared after 'Image' as meshes rely on the morph target image being ready
-            .add_plugins(RenderAssetPlugin::<GpuMesh, GpuImage>::default());
+            .add_plugins(RenderAssetPlugin::<GpuMesh, GpuImage>::default())
+            .add_systems(
+                PostUpdate,
+                skinning::attach_to_bones.before(TransformSystem::TransformPropagate),
+            );
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -262,6 +262,33 @@ impl Frustum {
         Self { half_spaces }
     }
 
+    /// Returns the 8 corners of the frustum, where the planes of its half spaces meet.
+    ///
+    /// The bit `i` of the index of a corner is set if the corner is on the second half space of
+    /// the pair `i` of opposite half spaces, so two corners are linked by an edge of the frustum if
+    /// their indices differ by a single bit.
+    ///
+    /// Returns `None` if three of the planes don't meet at a single point, such as for a frustum
+    /// without a far plane.
+    pub fn corners(&self) -> Option<[Vec3A; 8]> {
+        let mut corners = [Vec3A::ZERO; 8];
+        for (index, corner) in corners.iter_mut().enumerate() {
+            let [a, b, c] =
+                [0, 1, 2].map(|pair| self.half_spaces[pair * 2 + ((index >> pair) & 1)]);
+            let (b_cross_c, c_cross_a, a_cross_b) = (
+                b.normal().cross(c.normal()),
+                c.normal().cross(a.normal()),
+                a.normal().cross(b.normal()),
+            );
+            let determinant = a.normal().dot(b_cross_c);
+            if determinant.abs() <= f32::EPSILON {
+                return None;
+            }
+            *corner = -(a.d() * b_cross_c + b.d() * c_cross_a + c.d() * a_cross_b) / determinant;
+        }
+        Some(corners)
+    }
+
     /// Checks if a sphere intersects the frustum.
     #[inline]
     pub fn intersects_sphere(&self, sphere: &Sphere, intersect_far: bool) -> bool {
@@ -329,6 +356,35 @@ pub struct CascadesFrusta {
 mod tests {
     use super::*;
 
+    #[test]
+    fn corners_of_orthographic_frustum() {
+        let frustum =
+            Frustum::from_view_projection(&Mat4::orthographic_rh(-1.0, 1.0, -2.0, 2.0, 0.0, 10.0));
+        let corners = frustum.corners().unwrap();
+        for x in [-1.0, 1.0] {
+            for y in [-2.0, 2.0] {
+                for z in [0.0, -10.0] {
+                    let expected = Vec3A::new(x, y, z);
+                    assert!(
+                        corners
+                            .iter()
+                            .any(|corner| corner.abs_diff_eq(expected, 1e-4)),
+                        "{expected} isn't a corner of {corners:?}"
+                    );
+                }
+            }
+        }
+        // The corners linked by an edge differ by a single coordinate
+        assert_eq!(
+            (corners[0] - corners[1])
+                .abs()
+                .cmpgt(Vec3A::splat(1e-4))
+                .bitmask()
+                .count_ones(),
+            1
+        );
+    }
+
     // A big, offset frustum
     fn big_frustum() -> Frustum {
         Frustum {
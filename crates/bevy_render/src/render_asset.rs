@@ -1,6 +1,6 @@
 use crate::{ExtractSchedule, MainWorld, Render, RenderApp, RenderSet};
 use bevy_app::{App, Plugin, SubApp};
-use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
+use bevy_asset::{Asset, AssetDependencyReloadedEvent, AssetEvent, AssetId, Assets};
 use bevy_ecs::{
     prelude::{Commands, EventReader, IntoSystemConfigs, ResMut, Resource},
     schedule::SystemConfigs,
@@ -224,6 +224,7 @@ impl<A: RenderAsset> RenderAssets<A> {
 struct CachedExtractRenderAssetSystemState<A: RenderAsset> {
     state: SystemState<(
         EventReader<'static, 'static, AssetEvent<A::SourceAsset>>,
+        EventReader<'static, 'static, AssetDependencyReloadedEvent>,
         ResMut<'static, Assets<A::SourceAsset>>,
     )>,
 }
@@ -241,7 +242,7 @@ impl<A: RenderAsset> FromWorld for CachedExtractRenderAssetSystemState<A> {
 fn extract_render_asset<A: RenderAsset>(mut commands: Commands, mut main_world: ResMut<MainWorld>) {
     main_world.resource_scope(
         |world, mut cached_state: Mut<CachedExtractRenderAssetSystemState<A>>| {
-            let (mut events, mut assets) = cached_state.state.get_mut(world);
+            let (mut events, mut dependency_events, mut assets) = cached_state.state.get_mut(world);
 
             let mut changed_assets = HashSet::default();
             let mut removed = HashSet::default();
@@ -263,6 +264,15 @@ fn extract_render_asset<A: RenderAsset>(mut commands: Commands, mut main_world:
                 }
             }
 
+            // The render asset may have been prepared from the previous version of the dependency.
+            for event in dependency_events.read() {
+                if let Ok(id) = event.id.try_typed::<A::SourceAsset>() {
+                    if !removed.contains(&id) {
+                        changed_assets.insert(id);
+                    }
+                }
+            }
+
             let mut extracted_assets = Vec::new();
             let mut added = HashSet::new();
             for id in changed_assets.drain() {
@@ -1,6 +1,9 @@
 use crate::{
     camera::Viewport,
-    diagnostic::internal::{Pass, PassKind, WritePipelineStatistics, WriteTimestamp},
+    diagnostic::{
+        internal::{Pass, PassKind, WritePipelineStatistics, WriteTimestamp},
+        RenderStatistics,
+    },
     render_resource::{
         BindGroup, BindGroupId, Buffer, BufferId, BufferSlice, RenderPipeline, RenderPipelineId,
         ShaderStages,
@@ -104,6 +107,7 @@ impl DrawState {
 pub struct TrackedRenderPass<'a> {
     pass: RenderPass<'a>,
     state: DrawState,
+    statistics: RenderStatistics,
 }
 
 impl<'a> TrackedRenderPass<'a> {
@@ -118,10 +122,26 @@ impl<'a> TrackedRenderPass<'a> {
                 vertex_buffers: vec![None; max_vertex_buffers],
                 ..default()
             },
+            statistics: default(),
             pass,
         }
     }
 
+    /// Returns the draw calls, instances and triangles drawn with this pass so far.
+    ///
+    /// The phase items are counted by the render phases, not by the pass.
+    pub fn statistics(&self) -> RenderStatistics {
+        self.statistics
+    }
+
+    /// Counts a direct draw of the `vertices` of every instance of `instances`.
+    fn count_draw(&mut self, vertices: &Range<u32>, instances: &Range<u32>) {
+        let instance_count = instances.len() as u64;
+        self.statistics.draw_calls += 1;
+        self.statistics.instances += instance_count;
+        self.statistics.triangles += vertices.len() as u64 / 3 * instance_count;
+    }
+
     /// Returns the wgpu [`RenderPass`].
     pub fn wgpu_pass(&mut self) -> &mut RenderPass<'a> {
         &mut self.pass
@@ -246,6 +266,7 @@ impl<'a> TrackedRenderPass<'a> {
     /// The active vertex buffer(s) can be set with [`TrackedRenderPass::set_vertex_buffer`].
     pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
         detailed_trace!("draw: {:?} {:?}", vertices, instances);
+        self.count_draw(&vertices, &instances);
         self.pass.draw(vertices, instances);
     }
 
@@ -260,6 +281,7 @@ impl<'a> TrackedRenderPass<'a> {
             base_vertex,
             instances
         );
+        self.count_draw(&indices, &instances);
         self.pass.draw_indexed(indices, base_vertex, instances);
     }
 
@@ -282,6 +304,7 @@ impl<'a> TrackedRenderPass<'a> {
     /// ```
     pub fn draw_indirect(&mut self, indirect_buffer: &'a Buffer, indirect_offset: u64) {
         detailed_trace!("draw indirect: {:?} {}", indirect_buffer, indirect_offset);
+        self.statistics.draw_calls += 1;
         self.pass.draw_indirect(indirect_buffer, indirect_offset);
     }
 
@@ -310,6 +333,7 @@ impl<'a> TrackedRenderPass<'a> {
             indirect_buffer,
             indirect_offset
         );
+        self.statistics.draw_calls += 1;
         self.pass
             .draw_indexed_indirect(indirect_buffer, indirect_offset);
     }
@@ -343,6 +367,7 @@ impl<'a> TrackedRenderPass<'a> {
             indirect_offset,
             count
         );
+        self.statistics.draw_calls += count;
         self.pass
             .multi_draw_indirect(indirect_buffer, indirect_offset, count);
     }
@@ -385,6 +410,7 @@ impl<'a> TrackedRenderPass<'a> {
             count_offset,
             max_count
         );
+        self.statistics.draw_calls += 1;
         self.pass.multi_draw_indirect_count(
             indirect_buffer,
             indirect_offset,
@@ -425,6 +451,7 @@ impl<'a> TrackedRenderPass<'a> {
             indirect_offset,
             count
         );
+        self.statistics.draw_calls += count;
         self.pass
             .multi_draw_indexed_indirect(indirect_buffer, indirect_offset, count);
     }
@@ -469,6 +496,7 @@ impl<'a> TrackedRenderPass<'a> {
             count_offset,
             max_count
         );
+        self.statistics.draw_calls += 1;
         self.pass.multi_draw_indexed_indirect_count(
             indirect_buffer,
             indirect_offset,
@@ -44,6 +44,7 @@ use crate::{
         no_gpu_preprocessing::{self, BatchedInstanceBuffer},
         GetFullBatchData,
     },
+    diagnostic::{RenderStatistics, RenderStatisticsRecorder},
     render_resource::{CachedRenderPipelineId, GpuArrayBufferIndex, PipelineCache},
     Render, RenderApp, RenderSet,
 };
@@ -276,6 +277,9 @@ where
         let mut draw_functions = draw_functions.write();
         draw_functions.prepare(world);
 
+        let start_statistics = render_pass.statistics();
+        let mut phase_items = 0;
+
         // Encode draws for batchables.
         debug_assert_eq!(self.batchable_keys.len(), self.batch_sets.len());
         for (key, batch_set) in self.batchable_keys.iter().zip(self.batch_sets.iter()) {
@@ -294,6 +298,7 @@ where
                 };
 
                 draw_function.draw(world, render_pass, view, &binned_phase_item);
+                phase_items += 1;
             }
         }
 
@@ -342,8 +347,11 @@ where
                 };
 
                 draw_function.draw(world, render_pass, view, &binned_phase_item);
+                phase_items += 1;
             }
         }
+
+        record_statistics(world, render_pass, view, start_statistics, phase_items);
     }
 
     pub fn is_empty(&self) -> bool {
@@ -689,6 +697,9 @@ where
         let mut draw_functions = draw_functions.write();
         draw_functions.prepare(world);
 
+        let start_statistics = render_pass.statistics();
+        let mut phase_items = 0;
+
         let mut index = 0;
         while index < items.len() {
             let item = &items[index];
@@ -699,8 +710,27 @@ where
                 let draw_function = draw_functions.get_mut(item.draw_function()).unwrap();
                 draw_function.draw(world, render_pass, view, item);
                 index += batch_range.len();
+                phase_items += 1;
             }
         }
+
+        record_statistics(world, render_pass, view, start_statistics, phase_items);
+    }
+}
+
+/// Adds the draws of `render_pass` since `start_statistics` to the statistics of `view`, if the
+/// [`RenderStatisticsPlugin`](crate::diagnostic::RenderStatisticsPlugin) was added.
+fn record_statistics(
+    world: &World,
+    render_pass: &TrackedRenderPass,
+    view: Entity,
+    start_statistics: RenderStatistics,
+    phase_items: u32,
+) {
+    if let Some(recorder) = world.get_resource::<RenderStatisticsRecorder>() {
+        let mut statistics = render_pass.statistics() - start_statistics;
+        statistics.phase_items = phase_items;
+        recorder.record(view, statistics);
     }
 }
 
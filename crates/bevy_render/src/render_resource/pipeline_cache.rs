@@ -265,7 +265,9 @@ impl ShaderCache {
                         )?;
                     }
 
-                    composer.add_composable_module(shader.into())?;
+                    if let Err(err) = composer.add_composable_module(shader.into()) {
+                        return Err(ShaderProcessError::new(&err, composer).into());
+                    }
                 }
             }
             // if we fail to add a module the composer will tell us what is missing
@@ -327,9 +329,11 @@ impl ShaderCache {
                     "processing shader {:?}, with shader defs {:?}",
                     id, shader_defs
                 );
-                let shader_source = match &shader.source {
+                // Also returns the shader defs used to compose the shader, if it was composed by
+                // naga_oil.
+                let (shader_source, composed_shader_defs) = match &shader.source {
                     #[cfg(feature = "shader_format_spirv")]
-                    Source::SpirV(data) => make_spirv(data),
+                    Source::SpirV(data) => (make_spirv(data), None),
                     #[cfg(not(feature = "shader_format_spirv"))]
                     Source::SpirV(_) => {
                         unimplemented!(
@@ -362,14 +366,18 @@ impl ShaderCache {
                             })
                             .collect::<std::collections::HashMap<_, _>>();
 
-                        let naga = self.composer.make_naga_module(
-                            naga_oil::compose::NagaModuleDescriptor {
-                                shader_defs,
+                        let naga = self
+                            .composer
+                            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                                shader_defs: shader_defs.clone(),
                                 ..shader.into()
-                            },
-                        )?;
+                            })
+                            .map_err(|err| ShaderProcessError::new(&err, &self.composer))?;
 
-                        wgpu::ShaderSource::Naga(Cow::Owned(naga))
+                        (
+                            wgpu::ShaderSource::Naga(Cow::Owned(naga)),
+                            Some(shader_defs),
+                        )
                     }
                 };
 
@@ -391,6 +399,26 @@ impl ShaderCache {
                 if let Some(Some(wgpu::Error::Validation { description, .. })) =
                     bevy_utils::futures::now_or_never(error)
                 {
+                    // The error refers to the composed shader: if it wasn't validated while being
+                    // composed, compose it again with validation to find where it comes from.
+                    if let Some(shader_defs) = composed_shader_defs {
+                        if !self.composer.validate {
+                            self.composer.validate = true;
+                            let result = self.composer.make_naga_module(
+                                naga_oil::compose::NagaModuleDescriptor {
+                                    shader_defs,
+                                    ..shader.into()
+                                },
+                            );
+                            let error = result
+                                .err()
+                                .map(|err| ShaderProcessError::new(&err, &self.composer));
+                            self.composer.validate = false;
+                            if let Some(error) = error {
+                                return Err(PipelineCacheError::ProcessShaderError(error));
+                            }
+                        }
+                    }
                     return Err(PipelineCacheError::CreateShaderModule(description));
                 }
 
@@ -933,9 +961,7 @@ impl PipelineCache {
 
                 // Shader could not be processed ... retrying won't help
                 PipelineCacheError::ProcessShaderError(err) => {
-                    let error_detail =
-                        err.emit_to_string(&self.shader_cache.lock().unwrap().composer);
-                    error!("failed to process shader:\n{}", error_detail);
+                    error!("failed to process shader:\n{}", err.report);
                     return;
                 }
                 PipelineCacheError::CreateShaderModule(description) => {
@@ -1021,7 +1047,7 @@ pub enum PipelineCacheError {
     )]
     ShaderNotLoaded(AssetId<Shader>),
     #[error(transparent)]
-    ProcessShaderError(#[from] naga_oil::compose::ComposerError),
+    ProcessShaderError(#[from] ShaderProcessError),
     #[error("Shader import not yet available.")]
     ShaderImportNotYetAvailable,
     #[error("Could not create shader module: {0}")]
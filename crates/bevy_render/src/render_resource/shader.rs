@@ -1,6 +1,10 @@
 use super::ShaderDefVal;
 use crate::define_atomic_id;
-use bevy_asset::{io::Reader, Asset, AssetLoader, AssetPath, Handle, LoadContext};
+use bevy_app::App;
+use bevy_asset::{
+    io::Reader, Asset, AssetLoader, AssetPath, AssetServer, Assets, Handle, LoadContext,
+};
+use bevy_ecs::system::Resource;
 use bevy_reflect::TypePath;
 use bevy_utils::tracing::error;
 use futures_lite::AsyncReadExt;
@@ -22,6 +26,66 @@ pub enum ShaderReflectError {
     #[error(transparent)]
     Validation(#[from] naga::WithSpan<naga::valid::ValidationError>),
 }
+
+/// An error that occurred while processing a shader and its imports, mapped back to the shader
+/// file it originates from.
+///
+/// The file can be an imported shader library rather than the shader of the pipeline.
+#[derive(Error, Debug, Clone)]
+#[error("{report}")]
+pub struct ShaderProcessError {
+    /// The path of the shader file the error originates from.
+    pub path: String,
+    /// The one-based line and column of the error in the shader file, if known.
+    pub location: Option<(usize, usize)>,
+    /// A report of the error, quoting the relevant lines of the shader file.
+    pub report: String,
+}
+
+impl ShaderProcessError {
+    /// Maps `error` back to the shader file it originates from.
+    ///
+    /// This must be called before the modules of `composer` change, as the source of the shader
+    /// is read from them.
+    pub(crate) fn new(
+        error: &naga_oil::compose::ComposerError,
+        composer: &naga_oil::compose::Composer,
+    ) -> Self {
+        let path = error.source.path(composer).clone();
+        let report = error.emit_to_string(composer);
+        let location = report_location(&report, &path);
+        Self {
+            path,
+            location,
+            report,
+        }
+    }
+}
+
+/// Finds the location of an error in a report produced by `naga_oil`, which points to it with a
+/// `┌─ path:line:column` header.
+fn report_location(report: &str, path: &str) -> Option<(usize, usize)> {
+    let mut plain = String::with_capacity(report.len());
+    let mut chars = report.chars();
+    while let Some(c) = chars.next() {
+        // Skip the terminal color escape sequences.
+        if c == '\u{1b}' {
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            plain.push(c);
+        }
+    }
+
+    let header = format!("┌─ {path}:");
+    let start = plain.find(&header)? + header.len();
+    let mut numbers = plain[start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .map(str::parse);
+    let line = numbers.next()?.ok()?;
+    let column = numbers.next()?.ok()?;
+    Some((line, column))
+}
+
 /// A shader, as defined by its [`ShaderSource`](wgpu::ShaderSource) and [`ShaderStage`](naga::ShaderStage)
 /// This is an "unprocessed" shader. It can contain preprocessor directives.
 #[derive(Asset, TypePath, Debug, Clone)]
@@ -341,3 +405,95 @@ impl From<&'static str> for ShaderRef {
         Self::Path(AssetPath::from(path))
     }
 }
+
+/// The shader libraries registered with [`ShaderLibraryApp`].
+///
+/// The shaders are kept loaded for the lifetime of the app, so that other shaders can import
+/// them even when no pipeline currently uses them.
+#[derive(Resource, Default)]
+pub struct ShaderLibrary {
+    shaders: Vec<Handle<Shader>>,
+}
+
+impl ShaderLibrary {
+    /// Keeps `shader` loaded for the lifetime of the app.
+    pub fn add(&mut self, shader: Handle<Shader>) {
+        self.shaders.push(shader);
+    }
+
+    /// Returns the handles of the registered shader libraries.
+    pub fn iter(&self) -> impl Iterator<Item = &Handle<Shader>> {
+        self.shaders.iter()
+    }
+}
+
+/// Adds methods to [`App`] to register shader libraries that other shaders can import.
+///
+/// A shader library declaring an import path with `#define_import_path my_crate::my_module` can be
+/// imported by name from any shader, including the engine's, with `#import my_crate::my_module`.
+/// Libraries without an import path can only be imported by their asset path, with
+/// `#import "shaders/my_module.wgsl"`.
+///
+/// Errors in imported libraries are reported with the path and line of the library file, see
+/// [`ShaderProcessError`].
+pub trait ShaderLibraryApp {
+    /// Loads the shader library at `path` with the [`AssetServer`], and keeps it loaded.
+    fn add_shader_library<'a>(&mut self, path: impl Into<AssetPath<'a>>) -> &mut Self;
+
+    /// Adds the shader library `shader`, and keeps it loaded.
+    ///
+    /// This can be used for libraries embedded in the binary of a crate with
+    /// `Shader::from_wgsl(include_str!("my_module.wgsl"), file!())`. The path given to the
+    /// shader is used when reporting errors.
+    fn add_shader_library_source(&mut self, shader: Shader) -> &mut Self;
+}
+
+impl ShaderLibraryApp for App {
+    fn add_shader_library<'a>(&mut self, path: impl Into<AssetPath<'a>>) -> &mut Self {
+        let handle = self.world().resource::<AssetServer>().load(path);
+        self.world_mut()
+            .get_resource_or_insert_with(ShaderLibrary::default)
+            .add(handle);
+        self
+    }
+
+    fn add_shader_library_source(&mut self, shader: Shader) -> &mut Self {
+        let handle = self
+            .world_mut()
+            .resource_mut::<Assets<Shader>>()
+            .add(shader);
+        self.world_mut()
+            .get_resource_or_insert_with(ShaderLibrary::default)
+            .add(handle);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shader, ShaderProcessError};
+
+    #[test]
+    fn process_errors_point_to_library_file() {
+        let mut composer = naga_oil::compose::Composer::default();
+        let library = Shader::from_wgsl(
+            "#define_import_path my_crate::library\n\nfn value() -> f32 {\n    return 1.0 +;\n}\n",
+            "my_crate/library.wgsl",
+        );
+        let shader = Shader::from_wgsl(
+            "#import my_crate::library::value\n\n@compute @workgroup_size(1)\nfn main() {\n    let x = value();\n}\n",
+            "my_crate/main.wgsl",
+        );
+
+        let error = match composer.add_composable_module((&library).into()) {
+            Ok(_) => composer
+                .make_naga_module((&shader).into())
+                .expect_err("the library is invalid"),
+            Err(error) => error,
+        };
+
+        let error = ShaderProcessError::new(&error, &composer);
+        assert_eq!(error.path, "my_crate/library.wgsl");
+        assert_eq!(error.location.map(|(line, _)| line), Some(4));
+    }
+}
@@ -3,11 +3,12 @@ use basis_universal::{
 };
 use wgpu::{AstcBlock, AstcChannel, Extent3d, TextureDimension, TextureFormat};
 
-use super::{CompressedImageFormats, Image, TextureError};
+use super::{CompressedImageFormats, Image, TextureError, TranscodeQuality, TranscodeSettings};
 
 pub fn basis_buffer_to_image(
     buffer: &[u8],
     supported_compressed_formats: CompressedImageFormats,
+    transcode_settings: TranscodeSettings,
     is_srgb: bool,
 ) -> Result<Image, TextureError> {
     let mut transcoder = Transcoder::new();
@@ -28,8 +29,10 @@ pub fn basis_buffer_to_image(
 
     // First deal with transcoding to the desired format
     // FIXME: Use external metadata to transcode to more appropriate formats for 1- or 2-component sources
-    let (transcode_format, texture_format) =
-        get_transcoded_formats(supported_compressed_formats, is_srgb);
+    let (transcode_format, texture_format) = get_transcoded_formats(
+        transcode_settings.supported_targets(supported_compressed_formats),
+        is_srgb,
+    );
     let basis_texture_format = transcoder.basis_texture_format(buffer);
     if !basis_texture_format.can_transcode_to_format(transcode_format) {
         return Err(TextureError::UnsupportedTextureFormat(format!(
@@ -82,7 +85,7 @@ pub fn basis_buffer_to_image(
                     TranscodeParameters {
                         image_index,
                         level_index,
-                        decode_flags: Some(DecodeFlags::HIGH_QUALITY),
+                        decode_flags: Some(decode_flags(transcode_settings.quality)),
                         ..Default::default()
                     },
                 )
@@ -120,6 +123,14 @@ pub fn basis_buffer_to_image(
     Ok(image)
 }
 
+/// Returns the Basis Universal decode flags matching a [`TranscodeQuality`].
+pub(crate) fn decode_flags(quality: TranscodeQuality) -> DecodeFlags {
+    match quality {
+        TranscodeQuality::Fast => DecodeFlags::empty(),
+        TranscodeQuality::High => DecodeFlags::HIGH_QUALITY,
+    }
+}
+
 pub fn get_transcoded_formats(
     supported_compressed_formats: CompressedImageFormats,
     is_srgb: bool,
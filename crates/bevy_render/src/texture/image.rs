@@ -622,6 +622,19 @@ impl Image {
         });
     }
 
+    /// Takes a 2D image containing vertically stacked images of the same size, and reinterprets
+    /// it as a 3D texture, where each of the stacked images becomes one slice along the depth of
+    /// the texture. This is primarily for use with the `texture_3d` shader type, for lookup
+    /// tables or volumetric data.
+    ///
+    /// # Panics
+    /// Panics if the texture is not 2D, has more than one layers or is not evenly dividable into
+    /// the `depth` slices.
+    pub fn reinterpret_stacked_2d_as_3d(&mut self, depth: u32) {
+        self.reinterpret_stacked_2d_as_array(depth);
+        self.texture_descriptor.dimension = TextureDimension::D3;
+    }
+
     /// Convert a texture from a format to another. Only a few formats are
     /// supported as input and output:
     /// - `TextureFormat::R8Unorm`
@@ -653,10 +666,36 @@ impl Image {
     /// Load a bytes buffer in a [`Image`], according to type `image_type`, using the `image`
     /// crate
     pub fn from_buffer(
+        #[cfg(all(debug_assertions, feature = "dds"))] name: String,
+        buffer: &[u8],
+        image_type: ImageType,
+        supported_compressed_formats: CompressedImageFormats,
+        is_srgb: bool,
+        image_sampler: ImageSampler,
+        asset_usage: RenderAssetUsages,
+    ) -> Result<Image, TextureError> {
+        Self::from_buffer_with_transcode_settings(
+            #[cfg(all(debug_assertions, feature = "dds"))]
+            name,
+            buffer,
+            image_type,
+            supported_compressed_formats,
+            TranscodeSettings::default(),
+            is_srgb,
+            image_sampler,
+            asset_usage,
+        )
+    }
+
+    /// Load a bytes buffer in a [`Image`], like [`Image::from_buffer`], using the given
+    /// [`TranscodeSettings`] for Basis Universal textures and mip levels.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_buffer_with_transcode_settings(
         #[cfg(all(debug_assertions, feature = "dds"))] name: String,
         buffer: &[u8],
         image_type: ImageType,
         #[allow(unused_variables)] supported_compressed_formats: CompressedImageFormats,
+        transcode_settings: TranscodeSettings,
         is_srgb: bool,
         image_sampler: ImageSampler,
         asset_usage: RenderAssetUsages,
@@ -671,9 +710,12 @@ impl Image {
 
         let mut image = match format {
             #[cfg(feature = "basis-universal")]
-            ImageFormat::Basis => {
-                basis_buffer_to_image(buffer, supported_compressed_formats, is_srgb)?
-            }
+            ImageFormat::Basis => basis_buffer_to_image(
+                buffer,
+                supported_compressed_formats,
+                transcode_settings,
+                is_srgb,
+            )?,
             #[cfg(feature = "dds")]
             ImageFormat::Dds => dds_buffer_to_image(
                 #[cfg(debug_assertions)]
@@ -683,9 +725,12 @@ impl Image {
                 is_srgb,
             )?,
             #[cfg(feature = "ktx2")]
-            ImageFormat::Ktx2 => {
-                ktx2_buffer_to_image(buffer, supported_compressed_formats, is_srgb)?
-            }
+            ImageFormat::Ktx2 => ktx2_buffer_to_image(
+                buffer,
+                supported_compressed_formats,
+                transcode_settings,
+                is_srgb,
+            )?,
             _ => {
                 let image_crate_format = format
                     .as_image_crate_format()
@@ -697,10 +742,71 @@ impl Image {
                 Self::from_dynamic(dyn_img, is_srgb, asset_usage)
             }
         };
+        image.skip_mip_levels(transcode_settings.skip_mip_levels);
         image.sampler = image_sampler;
         Ok(image)
     }
 
+    /// Removes up to `count` of the largest mip levels of the image, always keeping at least
+    /// one level.
+    ///
+    /// This reduces the resolution and memory usage of textures that ship with a full mip chain,
+    /// for example on low-end devices. The image data must be laid out layer by layer, each
+    /// layer holding its mip levels from the largest to the smallest, as produced by the
+    /// texture loaders.
+    pub fn skip_mip_levels(&mut self, count: u32) {
+        let mip_level_count = self.texture_descriptor.mip_level_count;
+        let count = count.min(mip_level_count.saturating_sub(1));
+        if count == 0 {
+            return;
+        }
+
+        let format = self.texture_descriptor.format;
+        let Some(block_bytes) = format.block_copy_size(None) else {
+            return;
+        };
+        let (block_width, block_height) = format.block_dimensions();
+        let size = self.texture_descriptor.size;
+        let is_3d = self.texture_descriptor.dimension == TextureDimension::D3;
+        let layer_count = if is_3d { 1 } else { size.depth_or_array_layers };
+        let level_bytes = |level: u32| {
+            let width = (size.width >> level).max(1);
+            let height = (size.height >> level).max(1);
+            let depth = if is_3d {
+                (size.depth_or_array_layers >> level).max(1)
+            } else {
+                1
+            };
+            let blocks_x = (width + block_width - 1) / block_width;
+            let blocks_y = (height + block_height - 1) / block_height;
+            (blocks_x * blocks_y * depth * block_bytes) as usize
+        };
+        let skipped_bytes: usize = (0..count).map(level_bytes).sum();
+        let kept_bytes: usize = (count..mip_level_count).map(level_bytes).sum();
+        if self.data.len() != (skipped_bytes + kept_bytes) * layer_count as usize {
+            // The data doesn't match the expected layout, leave the image untouched.
+            return;
+        }
+
+        let mut data = Vec::with_capacity(kept_bytes * layer_count as usize);
+        for layer in 0..layer_count as usize {
+            let start = layer * (skipped_bytes + kept_bytes) + skipped_bytes;
+            data.extend_from_slice(&self.data[start..start + kept_bytes]);
+        }
+        self.data = data;
+        self.texture_descriptor.mip_level_count -= count;
+        self.texture_descriptor.size = Extent3d {
+            width: (size.width >> count).max(1),
+            height: (size.height >> count).max(1),
+            depth_or_array_layers: if is_3d {
+                (size.depth_or_array_layers >> count).max(1)
+            } else {
+                size.depth_or_array_layers
+            },
+        }
+        .physical_size(format);
+    }
+
     /// Whether the texture format is compressed or uncompressed
     pub fn is_compressed(&self) -> bool {
         let format_description = self.texture_descriptor.format;
@@ -941,6 +1047,80 @@ impl CompressedImageFormats {
     }
 }
 
+/// The compressed format Basis Universal textures are transcoded to when loaded.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranscodeTarget {
+    /// Picks the best format supported by the GPU: ASTC, which is common on mobile, then BC7 on
+    /// desktop, then ETC2.
+    #[default]
+    Auto,
+    /// Transcode to ASTC.
+    Astc,
+    /// Transcode to BC7, or BC4 and BC5 for one and two channel textures.
+    Bc,
+    /// Transcode to ETC2, or EAC for one and two channel textures.
+    Etc2,
+    /// Transcode to uncompressed RGBA.
+    Uncompressed,
+}
+
+impl TranscodeTarget {
+    /// Returns the formats that transcoding may target, given the formats supported by the GPU.
+    ///
+    /// If the requested format isn't supported, the result is empty and textures are
+    /// transcoded to an uncompressed format.
+    pub fn filter(self, supported: CompressedImageFormats) -> CompressedImageFormats {
+        supported
+            & match self {
+                TranscodeTarget::Auto => CompressedImageFormats::all(),
+                TranscodeTarget::Astc => CompressedImageFormats::ASTC_LDR,
+                TranscodeTarget::Bc => CompressedImageFormats::BC,
+                TranscodeTarget::Etc2 => CompressedImageFormats::ETC2,
+                TranscodeTarget::Uncompressed => CompressedImageFormats::NONE,
+            }
+    }
+}
+
+/// The trade-off between speed and quality when transcoding Basis Universal textures.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranscodeQuality {
+    /// Faster transcoding, with lower quality.
+    Fast,
+    /// Slower transcoding, with higher quality.
+    #[default]
+    High,
+}
+
+/// Settings for textures that need to be transcoded when loaded, and for their mip chain.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TranscodeSettings {
+    /// The format to transcode to.
+    pub target: TranscodeTarget,
+    /// The quality of the transcoding.
+    pub quality: TranscodeQuality,
+    /// The number of the largest mip levels to drop, see [`Image::skip_mip_levels`].
+    pub skip_mip_levels: u32,
+}
+
+impl TranscodeSettings {
+    /// Returns the formats that transcoding may target, and warns when the requested format isn't
+    /// supported by the GPU so the texture falls back to an uncompressed format.
+    #[cfg(feature = "basis-universal")]
+    pub(crate) fn supported_targets(
+        &self,
+        supported: CompressedImageFormats,
+    ) -> CompressedImageFormats {
+        let targets = self.target.filter(supported);
+        if targets.is_empty() && self.target != TranscodeTarget::Uncompressed {
+            bevy_utils::tracing::warn!(
+                "Transcoding to {:?} is not supported by this GPU, falling back to an uncompressed format",
+                self.target,
+            );
+        }
+        targets
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -965,6 +1145,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn skip_mip_levels() {
+        let mut image = Image::new(
+            Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 2,
+            },
+            TextureDimension::D2,
+            vec![0; 32],
+            TextureFormat::R8Unorm,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        image.texture_descriptor.mip_level_count = 3;
+        // Two layers of 4x4, 2x2 and 1x1 levels, each level filled with its index.
+        let layer = [[0u8; 16].as_slice(), &[1; 4], &[2; 1]].concat();
+        image.data = [layer.clone(), layer].concat();
+
+        image.skip_mip_levels(1);
+        assert_eq!(image.texture_descriptor.mip_level_count, 2);
+        assert_eq!(image.size(), UVec2::new(2, 2));
+        assert_eq!(image.data, vec![1, 1, 1, 1, 2, 1, 1, 1, 1, 2]);
+
+        // At least one level is always kept.
+        image.skip_mip_levels(5);
+        assert_eq!(image.texture_descriptor.mip_level_count, 1);
+        assert_eq!(image.data, vec![2, 2]);
+    }
+
     #[test]
     fn image_default_size() {
         let image = Image::default();
@@ -8,8 +8,9 @@ use crate::{
     texture::{Image, ImageFormat, ImageType, TextureError},
 };
 
-use super::{CompressedImageFormats, ImageSampler};
+use super::{CompressedImageFormats, ImageSampler, TranscodeSettings};
 use serde::{Deserialize, Serialize};
+use wgpu::{TextureDimension, TextureViewDescriptor, TextureViewDimension};
 
 /// Loader for images that can be read by the `image` crate.
 #[derive(Clone)]
@@ -59,6 +60,64 @@ pub struct ImageLoaderSettings {
     pub is_srgb: bool,
     pub sampler: ImageSampler,
     pub asset_usage: RenderAssetUsages,
+    /// How Basis Universal textures are transcoded, and how many mip levels are dropped.
+    pub transcode: TranscodeSettings,
+    /// Loads an image of vertically stacked images of the same size as a texture array or a 3D
+    /// texture, if set.
+    pub array_layout: Option<ImageArrayLayout>,
+}
+
+/// How a 2D image of vertically stacked images of the same size is loaded, see
+/// [`ImageLoaderSettings::array_layout`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageArrayLayout {
+    /// Each of the `layers` stacked images is a layer of a 2D array texture, see
+    /// [`Image::reinterpret_stacked_2d_as_array`].
+    Array { layers: u32 },
+    /// Each of the `depth` stacked images is a slice of a 3D texture, see
+    /// [`Image::reinterpret_stacked_2d_as_3d`].
+    Volume { depth: u32 },
+}
+
+impl ImageArrayLayout {
+    /// Returns the number of stacked images.
+    pub fn count(&self) -> u32 {
+        match *self {
+            ImageArrayLayout::Array { layers } => layers,
+            ImageArrayLayout::Volume { depth } => depth,
+        }
+    }
+
+    /// Reinterprets `image`, a 2D image of vertically stacked images, with this layout.
+    ///
+    /// Array textures are always viewed as 2D arrays, even with a single layer, which would
+    /// otherwise be viewed as a 2D texture.
+    fn apply(self, image: &mut Image) -> Result<(), ImageLoaderError> {
+        let count = self.count();
+        if image.texture_descriptor.dimension != TextureDimension::D2
+            || image.texture_descriptor.size.depth_or_array_layers != 1
+            || image.texture_descriptor.mip_level_count != 1
+            || count == 0
+            || image.height() % count != 0
+        {
+            return Err(ImageLoaderError::InvalidArrayLayout {
+                width: image.width(),
+                height: image.height(),
+                layout: self,
+            });
+        }
+        match self {
+            ImageArrayLayout::Array { layers } => {
+                image.reinterpret_stacked_2d_as_array(layers);
+                image.texture_view_descriptor = Some(TextureViewDescriptor {
+                    dimension: Some(TextureViewDimension::D2Array),
+                    ..Default::default()
+                });
+            }
+            ImageArrayLayout::Volume { depth } => image.reinterpret_stacked_2d_as_3d(depth),
+        }
+        Ok(())
+    }
 }
 
 impl Default for ImageLoaderSettings {
@@ -68,6 +127,8 @@ impl Default for ImageLoaderSettings {
             is_srgb: true,
             sampler: ImageSampler::Default,
             asset_usage: RenderAssetUsages::default(),
+            transcode: TranscodeSettings::default(),
+            array_layout: None,
         }
     }
 }
@@ -79,6 +140,12 @@ pub enum ImageLoaderError {
     Io(#[from] std::io::Error),
     #[error("Could not load texture file: {0}")]
     FileTexture(#[from] FileTextureError),
+    #[error("Could not load an image of size {width}x{height} as {layout:?}: it must be a single 2D image without mip levels, whose height is a multiple of the number of stacked images")]
+    InvalidArrayLayout {
+        width: u32,
+        height: u32,
+        layout: ImageArrayLayout,
+    },
 }
 
 impl AssetLoader for ImageLoader {
@@ -101,12 +168,13 @@ impl AssetLoader for ImageLoader {
             }
             ImageFormatSetting::Format(format) => ImageType::Format(format),
         };
-        Ok(Image::from_buffer(
+        let mut image = Image::from_buffer_with_transcode_settings(
             #[cfg(all(debug_assertions, feature = "dds"))]
             load_context.path().display().to_string(),
             &bytes,
             image_type,
             self.supported_compressed_formats,
+            settings.transcode,
             settings.is_srgb,
             settings.sampler.clone(),
             settings.asset_usage,
@@ -114,7 +182,13 @@ impl AssetLoader for ImageLoader {
         .map_err(|err| FileTextureError {
             error: err,
             path: format!("{}", load_context.path().display()),
-        })?)
+        })?;
+
+        if let Some(layout) = settings.array_layout {
+            layout.apply(&mut image)?;
+        }
+
+        Ok(image)
     }
 
     fn extensions(&self) -> &[&str] {
@@ -150,3 +224,81 @@ impl std::fmt::Display for FileTextureError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureViewDimension};
+
+    use super::{ImageArrayLayout, ImageLoaderError};
+    use crate::{render_asset::RenderAssetUsages, texture::Image};
+
+    fn stacked_image(width: u32, height: u32) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn single_layer_arrays_are_viewed_as_arrays() {
+        let mut image = stacked_image(4, 4);
+        ImageArrayLayout::Array { layers: 1 }
+            .apply(&mut image)
+            .unwrap();
+
+        assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 1);
+        let view = image.texture_view_descriptor.unwrap();
+        assert_eq!(view.dimension, Some(TextureViewDimension::D2Array));
+    }
+
+    #[test]
+    fn stacked_images_are_loaded_as_volumes() {
+        let mut image = stacked_image(4, 12);
+        ImageArrayLayout::Volume { depth: 3 }
+            .apply(&mut image)
+            .unwrap();
+
+        assert_eq!(image.texture_descriptor.dimension, TextureDimension::D3);
+        assert_eq!(
+            image.texture_descriptor.size,
+            Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_array_layouts_are_rejected() {
+        for layout in [
+            ImageArrayLayout::Array { layers: 0 },
+            ImageArrayLayout::Array { layers: 3 },
+            ImageArrayLayout::Volume { depth: 5 },
+        ] {
+            let mut image = stacked_image(4, 8);
+            assert!(matches!(
+                layout.apply(&mut image),
+                Err(ImageLoaderError::InvalidArrayLayout {
+                    width: 4,
+                    height: 8,
+                    ..
+                })
+            ));
+        }
+
+        // Already an array
+        let mut image = stacked_image(4, 8);
+        image.reinterpret_stacked_2d_as_array(2);
+        assert!(ImageArrayLayout::Array { layers: 2 }
+            .apply(&mut image)
+            .is_err());
+    }
+}
@@ -2,9 +2,7 @@
 use std::io::Read;
 
 #[cfg(feature = "basis-universal")]
-use basis_universal::{
-    DecodeFlags, LowLevelUastcTranscoder, SliceParametersUastc, TranscoderBlockFormat,
-};
+use basis_universal::{LowLevelUastcTranscoder, SliceParametersUastc, TranscoderBlockFormat};
 use bevy_color::Srgba;
 use bevy_utils::default;
 #[cfg(any(feature = "flate2", feature = "ruzstd"))]
@@ -18,11 +16,16 @@ use wgpu::{
     TextureViewDimension,
 };
 
-use super::{CompressedImageFormats, DataFormat, Image, TextureError, TranscodeFormat};
+#[cfg(feature = "basis-universal")]
+use super::basis::decode_flags;
+use super::{
+    CompressedImageFormats, DataFormat, Image, TextureError, TranscodeFormat, TranscodeSettings,
+};
 
 pub fn ktx2_buffer_to_image(
     buffer: &[u8],
     supported_compressed_formats: CompressedImageFormats,
+    #[allow(unused_variables)] transcode_settings: TranscodeSettings,
     is_srgb: bool,
 ) -> Result<Image, TextureError> {
     let ktx2 = ktx2::Reader::new(buffer)
@@ -149,7 +152,11 @@ pub fn ktx2_buffer_to_image(
                 #[cfg(feature = "basis-universal")]
                 TranscodeFormat::Uastc(data_format) => {
                     let (transcode_block_format, texture_format) =
-                        get_transcoded_formats(supported_compressed_formats, data_format, is_srgb);
+                        get_transcoded_formats(
+                            transcode_settings.supported_targets(supported_compressed_formats),
+                            data_format,
+                            is_srgb,
+                        );
                     let texture_format_info = texture_format;
                     let (block_width_pixels, block_height_pixels) = (
                         texture_format_info.block_dimensions().0,
@@ -186,7 +193,7 @@ pub fn ktx2_buffer_to_image(
                                     .transcode_slice(
                                         &level_data[offset..(offset + level_bytes)],
                                         slice_parameters,
-                                        DecodeFlags::HIGH_QUALITY,
+                                        decode_flags(transcode_settings.quality),
                                         transcode_block_format,
                                     )
                                     .map(|mut transcoded_level| transcoded[level].append(&mut transcoded_level))
@@ -1518,7 +1525,12 @@ mod tests {
             0x4a,
         ];
         let supported_compressed_formats = CompressedImageFormats::empty();
-        let result = ktx2_buffer_to_image(&buffer, supported_compressed_formats, true);
+        let result = ktx2_buffer_to_image(
+            &buffer,
+            supported_compressed_formats,
+            Default::default(),
+            true,
+        );
         assert!(result.is_ok());
     }
 }
@@ -81,10 +81,13 @@ impl ColorAttachment {
     }
 }
 
-/// A wrapper for a [`TextureView`] that is used as a depth-only [`RenderPassDepthStencilAttachment`].
+/// A wrapper for a [`TextureView`] that is used as a depth [`RenderPassDepthStencilAttachment`],
+/// and optionally as a stencil one.
 pub struct DepthAttachment {
     pub view: TextureView,
     clear_value: Option<f32>,
+    /// Whether the texture has a stencil aspect, see [`DepthAttachment::with_stencil`].
+    stencil: bool,
     is_first_call: Arc<AtomicBool>,
 }
 
@@ -93,10 +96,18 @@ impl DepthAttachment {
         Self {
             view,
             clear_value,
+            stencil: false,
             is_first_call: Arc::new(AtomicBool::new(clear_value.is_some())),
         }
     }
 
+    /// Makes the attachment write to the stencil aspect of the texture, which must have one. The
+    /// stencil is cleared to `0` along with the depth, and loaded otherwise.
+    pub fn with_stencil(mut self) -> Self {
+        self.stencil = true;
+        self
+    }
+
     /// Get this texture view as an attachment. The attachment will be cleared with a value of
     /// `clear_value` if this is the first time calling this function with `store` == [`StoreOp::Store`],
     /// and a clear value was provided, otherwise it will be loaded.
@@ -116,7 +127,14 @@ impl DepthAttachment {
                 },
                 store,
             }),
-            stencil_ops: None,
+            stencil_ops: self.stencil.then_some(Operations {
+                load: if first_call {
+                    LoadOp::Clear(0)
+                } else {
+                    LoadOp::Load
+                },
+                store,
+            }),
         }
     }
 }
@@ -101,6 +101,7 @@ impl Plugin for ViewPlugin {
             .register_type::<ViewVisibility>()
             .register_type::<Msaa>()
             .register_type::<NoFrustumCulling>()
+            .register_type::<CullingVolume>()
             .register_type::<RenderLayers>()
             .register_type::<Visibility>()
             .register_type::<VisibleEntities>()
@@ -693,6 +694,15 @@ impl ViewDepthTexture {
         }
     }
 
+    /// Makes the attachment write to the stencil aspect of the texture, which must have one, see
+    /// [`DepthAttachment::with_stencil`].
+    pub fn with_stencil(self) -> Self {
+        Self {
+            texture: self.texture,
+            attachment: self.attachment.with_stencil(),
+        }
+    }
+
     pub fn get_attachment(&self, store: StoreOp) -> RenderPassDepthStencilAttachment {
         self.attachment.get_attachment(store)
     }
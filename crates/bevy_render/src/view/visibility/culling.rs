@@ -0,0 +1,115 @@
+use bevy_ecs::prelude::*;
+use bevy_math::{Affine3A, Vec3, Vec3A};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::GlobalTransform;
+
+use crate::primitives::{Aabb, Frustum, Sphere};
+
+/// The bounds used to cull an entity against the [`Frustum`] of the views, instead of its
+/// [`Aabb`].
+///
+/// The [`Aabb`] of meshes and sprites is computed automatically, which is enough for most
+/// entities. This component lets an entity provide tighter or looser bounds, for example a sphere
+/// for a shape rotating in place, or a box enclosing the whole range of a vertex animation.
+///
+/// The volume is in the local space of the entity, and is moved with its [`GlobalTransform`].
+/// It is used by [`check_visibility`](super::check_visibility); the shadows of lights are still
+/// culled with the [`Aabb`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub enum CullingVolume {
+    /// A sphere. With a non-uniform scale, its radius is scaled along the largest axis.
+    Sphere {
+        /// The center of the sphere.
+        center: Vec3,
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// A box, oriented with the entity.
+    Box(Aabb),
+    /// An infinite volume: the entity is never culled by the frustum.
+    ///
+    /// Unlike with [`NoFrustumCulling`](super::NoFrustumCulling), the [`Aabb`] of the entity is
+    /// still computed, for the systems relying on it.
+    #[default]
+    Infinite,
+}
+
+impl CullingVolume {
+    /// Returns `true` if this volume, moved with `transform`, intersects `frustum`.
+    pub fn intersects_frustum(&self, frustum: &Frustum, transform: &GlobalTransform) -> bool {
+        match self {
+            CullingVolume::Sphere { center, radius } => {
+                let model = transform.affine();
+                let sphere = Sphere {
+                    center: model.transform_point3a((*center).into()),
+                    radius: radius * max_scale(&model),
+                };
+                frustum.intersects_sphere(&sphere, false)
+            }
+            CullingVolume::Box(aabb) => {
+                let model = transform.affine();
+                let sphere = Sphere {
+                    center: model.transform_point3a(aabb.center),
+                    radius: transform.radius_vec3a(aabb.half_extents),
+                };
+                // Do quick sphere-based frustum culling before the box-based one
+                frustum.intersects_sphere(&sphere, false)
+                    && frustum.intersects_obb(aabb, &model, true, false)
+            }
+            CullingVolume::Infinite => true,
+        }
+    }
+}
+
+/// Returns the largest scale of the axes of `model`.
+fn max_scale(model: &Affine3A) -> f32 {
+    let matrix = model.matrix3;
+    Vec3A::new(
+        matrix.x_axis.length(),
+        matrix.y_axis.length(),
+        matrix.z_axis.length(),
+    )
+    .max_element()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Mat4, Vec3, Vec3A};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::CullingVolume;
+    use crate::primitives::{Aabb, Frustum};
+
+    #[test]
+    fn intersects_frustum() {
+        // Looking down -Z, with a 2x2 view
+        let frustum =
+            Frustum::from_view_projection(&Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.1, 100.0));
+        let identity = GlobalTransform::IDENTITY;
+        let scaled = GlobalTransform::from(Transform::from_scale(Vec3::splat(2.0)));
+
+        let sphere = CullingVolume::Sphere {
+            center: Vec3::new(0.8, 0.0, -5.0),
+            radius: 0.4,
+        };
+        assert!(sphere.intersects_frustum(&frustum, &scaled));
+        let sphere = CullingVolume::Sphere {
+            center: Vec3::new(1.6, 0.0, -10.0),
+            radius: 0.4,
+        };
+        assert!(!sphere.intersects_frustum(&frustum, &identity));
+
+        let outside_box = CullingVolume::Box(Aabb {
+            center: Vec3A::new(5.0, 0.0, -10.0),
+            half_extents: Vec3A::splat(0.5),
+        });
+        assert!(!outside_box.intersects_frustum(&frustum, &identity));
+        assert!(outside_box.intersects_frustum(
+            &frustum,
+            &GlobalTransform::from(Transform::from_xyz(-5.0, 0.0, 0.0))
+        ));
+
+        assert!(CullingVolume::Infinite.intersects_frustum(&frustum, &identity));
+    }
+}
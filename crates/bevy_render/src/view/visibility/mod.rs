@@ -1,8 +1,10 @@
+mod culling;
 mod range;
 mod render_layers;
 
 use std::any::TypeId;
 
+pub use culling::*;
 pub use range::*;
 pub use render_layers::*;
 
@@ -30,8 +32,12 @@ use super::NoCpuCulling;
 ///
 /// This is done by the `visibility_propagate_system` which uses the entity hierarchy and
 /// `Visibility` to set the values of each entity's [`InheritedVisibility`] component.
+///
+/// [`InheritedVisibility`] and [`ViewVisibility`] are required components of `Visibility`, so they
+/// are inserted automatically when missing.
 #[derive(Component, Clone, Copy, Reflect, Debug, PartialEq, Eq, Default)]
 #[reflect(Component, Default)]
+#[require(InheritedVisibility, ViewVisibility)]
 pub enum Visibility {
     /// An entity with `Visibility::Inherited` will inherit the Visibility of its [`Parent`].
     ///
@@ -228,6 +234,15 @@ impl VisibleEntities {
     {
         self.get_mut::<QF>().push(entity);
     }
+
+    /// Retains only the entities for which `f` returns `true`, for every type of entity.
+    ///
+    /// This is meant for the [`VisibilitySystems::AdditionalCulling`] systems.
+    pub fn retain(&mut self, mut f: impl FnMut(Entity) -> bool) {
+        for entities in self.entities.values_mut() {
+            entities.retain(|entity| f(*entity));
+        }
+    }
 }
 
 /// A convenient alias for `With<Handle<Mesh>>`, for use with
@@ -247,6 +262,13 @@ pub enum VisibilitySystems {
     /// Label for the [`check_visibility`] system updating [`ViewVisibility`]
     /// of each entity and the [`VisibleEntities`] of each view.
     CheckVisibility,
+    /// Label for systems culling more entities from the [`VisibleEntities`] of each view after
+    /// [`CheckVisibility`](Self::CheckVisibility), with
+    /// [`VisibleEntities::retain`]. This is where plugins add culling passes which don't rely
+    /// on the bounds of each entity, such as portal or room-based culling.
+    ///
+    /// The [`ViewVisibility`] of the culled entities is left set, so it stays conservative.
+    AdditionalCulling,
 }
 
 pub struct VisibilityPlugin;
@@ -261,6 +283,7 @@ impl Plugin for VisibilityPlugin {
                 .before(CheckVisibility)
                 .after(TransformSystem::TransformPropagate),
         )
+        .configure_sets(PostUpdate, AdditionalCulling.after(CheckVisibility))
         .add_systems(
             PostUpdate,
             (
@@ -410,6 +433,7 @@ pub fn check_visibility<QF>(
             &mut ViewVisibility,
             Option<&RenderLayers>,
             Option<&Aabb>,
+            Option<&CullingVolume>,
             &GlobalTransform,
             Has<NoFrustumCulling>,
             Has<VisibilityRange>,
@@ -440,6 +464,7 @@ pub fn check_visibility<QF>(
                     mut view_visibility,
                     maybe_entity_mask,
                     maybe_model_aabb,
+                    maybe_culling_volume,
                     transform,
                     no_frustum_culling,
                     has_visibility_range,
@@ -465,9 +490,13 @@ pub fn check_visibility<QF>(
                     return;
                 }
 
-                // If we have an aabb, do frustum culling
+                // If we have a culling volume or an aabb, do frustum culling
                 if !no_frustum_culling && !no_cpu_culling {
-                    if let Some(model_aabb) = maybe_model_aabb {
+                    if let Some(culling_volume) = maybe_culling_volume {
+                        if !culling_volume.intersects_frustum(frustum, transform) {
+                            return;
+                        }
+                    } else if let Some(model_aabb) = maybe_model_aabb {
                         let model = transform.affine();
                         let model_sphere = Sphere {
                             center: model.transform_point3a(model_aabb.center),
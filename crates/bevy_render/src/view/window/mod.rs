@@ -3,16 +3,22 @@ use crate::{
         BindGroupEntries, PipelineCache, SpecializedRenderPipelines, SurfaceTexture, TextureView,
     },
     renderer::{RenderAdapter, RenderDevice, RenderInstance},
-    texture::TextureFormatPixelInfo,
+    texture::{Image, TextureFormatPixelInfo},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet, WgpuWrapper,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::{entity::EntityHashMap, prelude::*};
 #[cfg(target_os = "linux")]
 use bevy_utils::warn_once;
-use bevy_utils::{default, tracing::debug, HashSet};
+use bevy_utils::{
+    default,
+    tracing::{debug, warn},
+    HashSet,
+};
 use bevy_window::{
     CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosing,
+    WindowIcon,
 };
 use std::{
     num::NonZeroU32,
@@ -36,7 +42,10 @@ pub struct WindowRenderPlugin;
 
 impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ScreenshotPlugin);
+        app.add_plugins(ScreenshotPlugin).add_systems(
+            PostUpdate,
+            update_window_icons.run_if(resource_exists::<Assets<Image>>),
+        );
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -60,6 +69,45 @@ impl Plugin for WindowRenderPlugin {
     }
 }
 
+/// Sets the [`icon`](Window::icon) of the [`Window`] on the same entity from an [`Image`] asset.
+///
+/// The icon is updated whenever the image is loaded or modified. The image must be kept in the
+/// main world, see [`RenderAssetUsages::MAIN_WORLD`](crate::render_asset::RenderAssetUsages::MAIN_WORLD).
+#[derive(Component, Clone, Debug)]
+pub struct WindowIconImage(pub Handle<Image>);
+
+fn update_window_icons(
+    mut image_events: EventReader<AssetEvent<Image>>,
+    images: Res<Assets<Image>>,
+    mut windows: Query<(Ref<WindowIconImage>, &mut Window)>,
+) {
+    let changed_images: HashSet<AssetId<Image>> = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (icon_image, mut window) in &mut windows {
+        if !icon_image.is_changed() && !changed_images.contains(&icon_image.0.id()) {
+            continue;
+        }
+        let Some(image) = images.get(&icon_image.0) else {
+            continue;
+        };
+        let Some(rgba) = image.convert(TextureFormat::Rgba8UnormSrgb) else {
+            warn!(
+                "Could not use an image with format {:?} as a window icon",
+                image.texture_descriptor.format
+            );
+            continue;
+        };
+        let (width, height) = (rgba.width(), rgba.height());
+        window.icon = WindowIcon::from_rgba(rgba.data, width, height);
+    }
+}
+
 pub struct ExtractedWindow {
     /// An entity that contains the components in [`Window`].
     pub entity: Entity,
@@ -11,7 +11,7 @@ use bevy_ecs::{
 use bevy_render::prelude::{InheritedVisibility, ViewVisibility, Visibility};
 use bevy_transform::components::{GlobalTransform, Transform};
 
-use crate::{DynamicScene, InstanceId, Scene, SceneSpawner};
+use crate::{DynamicScene, InstanceId, Scene, SceneSpawnBudget, SceneSpawner};
 
 /// [`InstanceId`] of a spawned scene. It can be used with the [`SceneSpawner`] to
 /// interact with the spawned scene.
@@ -22,6 +22,8 @@ pub struct SceneInstance(pub(crate) InstanceId);
 ///
 /// The scene from `scene` will be spawned as a child of the entity with this component.
 /// Once it's spawned, the entity will have a [`SceneInstance`] component.
+///
+/// Adding a [`SceneSpawnBudget`] to the entity spawns the scene over several frames.
 #[derive(Default, Bundle, Clone)]
 pub struct SceneBundle {
     /// Handle to the scene to spawn.
@@ -70,7 +72,12 @@ pub struct DynamicSceneBundle {
 pub fn scene_spawner(
     mut commands: Commands,
     mut scene_to_spawn: Query<
-        (Entity, &Handle<Scene>, Option<&mut SceneInstance>),
+        (
+            Entity,
+            &Handle<Scene>,
+            Option<&mut SceneInstance>,
+            Option<&SceneSpawnBudget>,
+        ),
         (Changed<Handle<Scene>>, Without<Handle<DynamicScene>>),
     >,
     mut dynamic_scene_to_spawn: Query<
@@ -79,8 +86,11 @@ pub fn scene_spawner(
     >,
     mut scene_spawner: ResMut<SceneSpawner>,
 ) {
-    for (entity, scene, instance) in &mut scene_to_spawn {
-        let new_instance = scene_spawner.spawn_as_child(scene.clone(), entity);
+    for (entity, scene, instance, budget) in &mut scene_to_spawn {
+        let new_instance = match budget {
+            Some(&budget) => scene_spawner.spawn_as_child_amortized(scene.clone(), entity, budget),
+            None => scene_spawner.spawn_as_child(scene.clone(), entity),
+        };
         if let Some(mut old_instance) = instance {
             scene_spawner.despawn_instance(**old_instance);
             *old_instance = SceneInstance(new_instance);
@@ -1,12 +1,17 @@
 use crate::{ron, DynamicSceneBuilder, Scene, SceneSpawnError};
-use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::entity::{EntityHashMap, EntityHashSet, SceneEntityMapper};
 use bevy_ecs::{
     entity::Entity,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    reflect::{
+        map_reflected_entities, type_may_contain_entities, AppTypeRegistry, ReflectComponent,
+        ReflectMapEntities,
+    },
     world::World,
 };
+use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
 use bevy_reflect::{Reflect, TypePath, TypeRegistry};
-use bevy_utils::TypeIdMap;
+use bevy_utils::{HashSet, TypeIdMap};
+use std::any::TypeId;
 
 #[cfg(feature = "serialize")]
 use crate::serde::SceneSerializer;
@@ -98,6 +103,10 @@ impl DynamicScene {
         // This is so we can update the scene-internal references to references
         // of the actual entities in the world.
         let mut scene_mappings: TypeIdMap<Vec<Entity>> = Default::default();
+        // Reflected components that contain entities but don't register `ReflectMapEntities`
+        // are mapped through reflection instead.
+        let mut reflected_mappings: TypeIdMap<Vec<Entity>> = Default::default();
+        let mut contains_entities: TypeIdMap<bool> = Default::default();
 
         for scene_entity in &self.entities {
             // Fetch the entity with the given entity id from the `entity_map`
@@ -134,6 +143,16 @@ impl DynamicScene {
                         .entry(registration.type_id())
                         .or_default()
                         .push(entity);
+                } else if *contains_entities
+                    .entry(registration.type_id())
+                    .or_insert_with(|| {
+                        type_may_contain_entities(&type_registry, registration.type_id())
+                    })
+                {
+                    reflected_mappings
+                        .entry(registration.type_id())
+                        .or_default()
+                        .push(entity);
                 }
 
                 // If the entity already has the given component attached,
@@ -152,6 +171,12 @@ impl DynamicScene {
                 map_entities_reflect.map_entities(world, entity_map, &entities);
             }
         }
+        for (type_id, entities) in reflected_mappings.into_iter() {
+            let reflect_component = type_registry
+                .get_type_data::<ReflectComponent>(type_id)
+                .expect("we only track the entities of reflected components");
+            map_reflected_component_entities(world, entity_map, reflect_component, &entities);
+        }
 
         Ok(())
     }
@@ -170,6 +195,125 @@ impl DynamicScene {
         self.write_to_world_with(world, entity_map, &registry)
     }
 
+    /// Replaces the hierarchy of `root` in the world with the one stored in this scene, usually
+    /// extracted with [`DynamicSceneBuilder::extract_hierarchy`]. This is the building block of
+    /// save games: snapshot a hierarchy, serialize it, and restore it after loading it back.
+    ///
+    /// `root` is the root of the hierarchy in the scene, and `entity_map` maps the entities of
+    /// the scene to the live entities of the world. It is usually the map filled when the scene
+    /// was first written to the world, or maps each entity to itself when the scene was extracted
+    /// from the same world.
+    ///
+    /// Live entities of the scene are updated in place, so references to them from outside of
+    /// the hierarchy stay valid. Entities of the scene that are missing from `entity_map` or were
+    /// despawned are spawned again and added to `entity_map`. The entities added to the live
+    /// hierarchy since the snapshot are despawned. Other entities of the world are left untouched.
+    ///
+    /// Like in [`write_to_world`](Self::write_to_world), references from the scene to entities
+    /// missing from `entity_map` are mapped to new entities, except for the parent of `root`
+    /// which is kept.
+    ///
+    /// The components whose type is stored in the scene are replaced, the other components of
+    /// the live entities are left untouched.
+    ///
+    /// Every type of the scene is checked before the world is modified, so the world is left
+    /// unchanged if this method returns a [`SceneSpawnError`].
+    ///
+    /// Returns the live root of the restored hierarchy.
+    pub fn restore_hierarchy(
+        &self,
+        world: &mut World,
+        root: Entity,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<Entity, SceneSpawnError> {
+        if !self.entities.iter().any(|entity| entity.entity == root) {
+            return Err(SceneSpawnError::MissingHierarchyRoot { entity: root });
+        }
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let scene_types = self.validate(&type_registry.read())?;
+
+        // Forget the despawned entities, so that they are spawned again
+        for scene_entity in &self.entities {
+            if entity_map
+                .get(&scene_entity.entity)
+                .is_some_and(|&entity| world.get_entity(entity).is_none())
+            {
+                entity_map.remove(&scene_entity.entity);
+            }
+        }
+        let live_entities: EntityHashSet = self
+            .entities
+            .iter()
+            .filter_map(|scene_entity| entity_map.get(&scene_entity.entity).copied())
+            .collect();
+
+        let mut parent = None;
+        if let Some(&live_root) = entity_map.get(&root) {
+            // The link to the parent of the root is restored once the scene is written
+            parent = world.get::<Parent>(live_root).map(Parent::get);
+            world.entity_mut(live_root).remove_parent();
+            for entity in hierarchy_entities(world, live_root) {
+                if !live_entities.contains(&entity) {
+                    // Detach the entity so that its parent doesn't keep it in its `Children`
+                    world.entity_mut(entity).remove_parent();
+                    world.despawn(entity);
+                }
+            }
+        }
+
+        // Remove the stored components from the live entities, so that the scene replaces them
+        // instead of being applied on top of them
+        {
+            let type_registry = type_registry.read();
+            for entity in live_entities.iter().copied() {
+                let mut entity_mut = world.entity_mut(entity);
+                for type_id in &scene_types {
+                    if let Some(reflect_component) =
+                        type_registry.get_type_data::<ReflectComponent>(*type_id)
+                    {
+                        reflect_component.remove(&mut entity_mut);
+                    }
+                }
+            }
+        }
+
+        self.write_to_world_with(world, entity_map, &type_registry)?;
+
+        // The parent of the root is outside of the scene, so the scene can't be trusted with it
+        let root = entity_map[&root];
+        let parent = parent.or_else(|| world.get::<Parent>(root).map(Parent::get));
+        world.entity_mut(root).remove::<Parent>();
+        if let Some(parent) = parent.filter(|&parent| world.get_entity(parent).is_some()) {
+            world.entity_mut(parent).add_child(root);
+        }
+        Ok(root)
+    }
+
+    /// Checks that every resource and component of the scene can be written to a world, and
+    /// returns the types of the components.
+    fn validate(&self, type_registry: &TypeRegistry) -> Result<HashSet<TypeId>, SceneSpawnError> {
+        for resource in &self.resources {
+            let registration = registration_of(&**resource, type_registry)?;
+            if registration.data::<ReflectResource>().is_none() {
+                return Err(SceneSpawnError::UnregisteredResource {
+                    type_path: registration.type_info().type_path().to_string(),
+                });
+            }
+        }
+
+        let mut types = HashSet::new();
+        for component in self.entities.iter().flat_map(|entity| &entity.components) {
+            let registration = registration_of(&**component, type_registry)?;
+            if registration.data::<ReflectComponent>().is_none() {
+                return Err(SceneSpawnError::UnregisteredComponent {
+                    type_path: registration.type_info().type_path().to_string(),
+                });
+            }
+            types.insert(registration.type_id());
+        }
+        Ok(types)
+    }
+
     // TODO: move to AssetSaver when it is implemented
     /// Serialize this dynamic scene into the official Bevy scene format (`.scn` / `.scn.ron`).
     ///
@@ -184,6 +328,54 @@ impl DynamicScene {
     }
 }
 
+/// Returns the registration of the type represented by `value`.
+fn registration_of<'a>(
+    value: &dyn Reflect,
+    type_registry: &'a TypeRegistry,
+) -> Result<&'a bevy_reflect::TypeRegistration, SceneSpawnError> {
+    let type_info =
+        value
+            .get_represented_type_info()
+            .ok_or_else(|| SceneSpawnError::NoRepresentedType {
+                type_path: value.reflect_type_path().to_string(),
+            })?;
+    type_registry.get(type_info.type_id()).ok_or_else(|| {
+        SceneSpawnError::UnregisteredButReflectedType {
+            type_path: type_info.type_path().to_string(),
+        }
+    })
+}
+
+/// Returns `root` and all of its descendants.
+pub(crate) fn hierarchy_entities(world: &World, root: Entity) -> Vec<Entity> {
+    let mut entities = vec![root];
+    let mut index = 0;
+    while let Some(&entity) = entities.get(index) {
+        if let Some(children) = world.get::<Children>(entity) {
+            entities.extend(children.iter().copied());
+        }
+        index += 1;
+    }
+    entities
+}
+
+/// Maps the entities contained in the `reflect_component` of each of the `entities`, using
+/// [`map_reflected_entities`].
+pub(crate) fn map_reflected_component_entities(
+    world: &mut World,
+    entity_map: &mut EntityHashMap<Entity>,
+    reflect_component: &ReflectComponent,
+    entities: &[Entity],
+) {
+    SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
+        for &entity in entities {
+            if let Some(mut component) = reflect_component.reflect_mut(world.entity_mut(entity)) {
+                map_reflected_entities(&mut *component, mapper);
+            }
+        }
+    });
+}
+
 /// Serialize a given Rust data structure into rust object notation (ron).
 #[cfg(feature = "serialize")]
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
@@ -198,7 +390,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use bevy_ecs::entity::EntityHashMap;
+    use bevy_ecs::entity::{Entity, EntityHashMap};
     use bevy_ecs::{reflect::AppTypeRegistry, world::Command, world::World};
     use bevy_hierarchy::{Parent, PushChild};
 
@@ -280,4 +472,188 @@ mod tests {
             "something is wrong with the this test or the code reloading scenes since the relationship between scene entities is broken"
         );
     }
+
+    #[test]
+    fn reflected_entities_are_mapped_without_map_entities() {
+        use bevy_ecs::{component::Component, reflect::ReflectComponent};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        struct Targets(Vec<Option<Entity>>);
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource_mut::<AppTypeRegistry>()
+            .write()
+            .register::<Targets>();
+        let target = world.spawn_empty().id();
+        let holder = world.spawn(Targets(vec![Some(target), None])).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entity(target)
+            .extract_entity(holder)
+            .build();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let targets = world.get::<Targets>(entity_map[&holder]).unwrap();
+        assert_eq!(targets.0, vec![Some(entity_map[&target]), None]);
+    }
+
+    #[test]
+    fn restore_hierarchy() {
+        use bevy_ecs::{component::Component, reflect::ReflectComponent};
+        use bevy_hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Health>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        let parent = world.spawn_empty().id();
+        let root = world.spawn(Health(10)).set_parent(parent).id();
+        let kept = world.spawn(Health(5)).set_parent(root).id();
+        let removed = world.spawn(Health(1)).set_parent(root).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_hierarchy(root)
+            .build();
+        assert_eq!(scene.entities.len(), 3);
+
+        // Change the hierarchy after the snapshot
+        world.get_mut::<Health>(kept).unwrap().0 = 0;
+        world.entity_mut(removed).despawn_recursive();
+        let added = world.spawn(Health(3)).set_parent(root).id();
+
+        // The scene was extracted from this world, so its entities are the live ones
+        let mut entity_map: EntityHashMap<Entity> = scene
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity.entity))
+            .collect();
+        assert_eq!(
+            scene
+                .restore_hierarchy(&mut world, root, &mut entity_map)
+                .unwrap(),
+            root
+        );
+
+        // Live entities are updated in place, the others are despawned or spawned again
+        assert_eq!(world.get::<Health>(kept), Some(&Health(5)));
+        assert!(world.get_entity(added).is_none());
+        let children = world.get::<Children>(root).unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0], kept);
+        assert_ne!(children[1], removed);
+        assert_eq!(world.get::<Health>(children[1]), Some(&Health(1)));
+        assert_eq!(world.get::<Parent>(children[1]).unwrap().get(), root);
+
+        // The root stays attached to its parent outside of the scene
+        assert_eq!(world.get::<Parent>(root).unwrap().get(), parent);
+        assert_eq!(world.get::<Children>(parent).unwrap().to_vec(), vec![root]);
+    }
+
+    #[test]
+    fn restore_hierarchy_only_touches_mapped_entities() {
+        use bevy_ecs::{component::Component, reflect::ReflectComponent};
+        use bevy_hierarchy::{BuildWorldChildren, Children};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+
+        let mut source = World::new();
+        source.insert_resource(registry.clone());
+        let root = source.spawn(Health(10)).id();
+        let child = source.spawn(Health(5)).set_parent(root).id();
+        let scene = DynamicSceneBuilder::from_world(&source)
+            .extract_hierarchy(root)
+            .build();
+
+        // The entities of the scene collide with unrelated entities of this world
+        let mut world = World::new();
+        world.insert_resource(registry);
+        let unrelated_root = world.spawn(Health(100)).id();
+        let unrelated_child = world.spawn(Health(50)).id();
+        assert_eq!((unrelated_root, unrelated_child), (root, child));
+
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let live_root = entity_map[&root];
+        let live_child = entity_map[&child];
+
+        world.get_mut::<Health>(live_child).unwrap().0 = 0;
+        let added = world.spawn(Health(3)).set_parent(live_root).id();
+
+        assert_eq!(
+            scene
+                .restore_hierarchy(&mut world, root, &mut entity_map)
+                .unwrap(),
+            live_root
+        );
+
+        assert_eq!(world.get::<Health>(live_child), Some(&Health(5)));
+        assert!(world.get_entity(added).is_none());
+        assert_eq!(
+            world.get::<Children>(live_root).unwrap().to_vec(),
+            vec![live_child]
+        );
+        assert_eq!(world.get::<Health>(unrelated_root), Some(&Health(100)));
+        assert_eq!(world.get::<Health>(unrelated_child), Some(&Health(50)));
+        assert!(world.get::<Children>(unrelated_root).is_none());
+    }
+
+    #[test]
+    fn restore_hierarchy_removes_despawned_children_from_their_parent() {
+        use bevy_ecs::{component::Component, reflect::ReflectComponent};
+        use bevy_hierarchy::{BuildWorldChildren, Children};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        // `Children` isn't stored in the scene, so the restore can't overwrite it
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Health>();
+            registry.register::<Parent>();
+        }
+        let root = world.spawn(Health(10)).id();
+        let child = world.spawn(Health(5)).set_parent(root).id();
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_hierarchy(root)
+            .build();
+
+        let added = world.spawn(Health(3)).set_parent(root).id();
+        let mut entity_map: EntityHashMap<Entity> =
+            [(root, root), (child, child)].into_iter().collect();
+        scene
+            .restore_hierarchy(&mut world, root, &mut entity_map)
+            .unwrap();
+
+        assert!(world.get_entity(added).is_none());
+        assert_eq!(world.get::<Children>(root).unwrap().to_vec(), vec![child]);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{DynamicEntity, DynamicScene, SceneFilter};
+use crate::{dynamic_scene::hierarchy_entities, DynamicEntity, DynamicScene, SceneFilter};
 use bevy_ecs::component::{Component, ComponentId};
 use bevy_ecs::system::Resource;
 use bevy_ecs::{
@@ -198,6 +198,18 @@ impl<'w> DynamicSceneBuilder<'w> {
         self.extract_entities(std::iter::once(entity))
     }
 
+    /// Extract an entity and all of its descendants from the builder's [`World`].
+    ///
+    /// The resulting scene can replace the live hierarchy later on, with
+    /// [`DynamicScene::restore_hierarchy`].
+    ///
+    /// Re-extracting an entity that was already extracted will have no effect.
+    #[must_use]
+    pub fn extract_hierarchy(self, root: Entity) -> Self {
+        let entities = hierarchy_entities(self.original_world, root);
+        self.extract_entities(entities.into_iter())
+    }
+
     /// Despawns all entities with no components.
     ///
     /// These were likely created because none of their components were present in the provided type registry upon extraction.
@@ -244,6 +256,54 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// [`deny`]: Self::deny
     #[must_use]
     pub fn extract_entities(mut self, entities: impl Iterator<Item = Entity>) -> Self {
+        let filter = std::mem::take(&mut self.component_filter);
+        self = self.extract_entities_with_filter(entities, &filter);
+        self.component_filter = filter;
+        self
+    }
+
+    /// Extract entities from the builder's [`World`], with the components allowed by `filter`
+    /// instead of the filter of the builder.
+    ///
+    /// Re-extracting an entity that was already extracted will have no effect.
+    ///
+    /// This method may be used to extract different components from different groups of
+    /// entities, such as the state of the players and only the positions of the props of a level:
+    /// ```
+    /// # use bevy_scene::{DynamicSceneBuilder, SceneFilter};
+    /// # use bevy_ecs::reflect::AppTypeRegistry;
+    /// # use bevy_ecs::{
+    /// #     component::Component, prelude::Entity, query::With, reflect::ReflectComponent, world::World,
+    /// # };
+    /// # use bevy_reflect::Reflect;
+    /// # #[derive(Component, Default, Reflect)]
+    /// # #[reflect(Component)]
+    /// # struct Player;
+    /// # #[derive(Component, Default, Reflect)]
+    /// # #[reflect(Component)]
+    /// # struct Prop;
+    /// # #[derive(Component, Default, Reflect)]
+    /// # #[reflect(Component)]
+    /// # struct Position;
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// let mut players = world.query_filtered::<Entity, With<Player>>();
+    /// let mut props = world.query_filtered::<Entity, With<Prop>>();
+    ///
+    /// let scene = DynamicSceneBuilder::from_world(&world)
+    ///     .extract_entities(players.iter(&world))
+    ///     .extract_entities_with_filter(
+    ///         props.iter(&world),
+    ///         &SceneFilter::deny_all().allow::<Position>(),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn extract_entities_with_filter(
+        mut self,
+        entities: impl Iterator<Item = Entity>,
+        filter: &SceneFilter,
+    ) -> Self {
         let type_registry = self.original_world.resource::<AppTypeRegistry>().read();
 
         for entity in entities {
@@ -265,7 +325,7 @@ impl<'w> DynamicSceneBuilder<'w> {
                         .get_info(component_id)?
                         .type_id()?;
 
-                    let is_denied = self.component_filter.is_denied_by_id(type_id);
+                    let is_denied = filter.is_denied_by_id(type_id);
 
                     if is_denied {
                         // Component is either in the denylist or _not_ in the allowlist
@@ -359,6 +419,7 @@ mod tests {
     use bevy_reflect::Reflect;
 
     use super::DynamicSceneBuilder;
+    use crate::SceneFilter;
 
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
@@ -495,6 +556,39 @@ mod tests {
         assert_eq!(scene_entities, [entity_a_b, entity_a]);
     }
 
+    #[test]
+    fn extract_entities_with_filter() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ComponentB>();
+        }
+        world.insert_resource(atr);
+
+        let entity_a = world.spawn((ComponentA, ComponentB)).id();
+        let entity_b = world.spawn((ComponentA, ComponentB)).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny::<ComponentA>()
+            .extract_entity(entity_a)
+            .extract_entities_with_filter(
+                [entity_a, entity_b].into_iter(),
+                &SceneFilter::deny_all().allow::<ComponentA>(),
+            )
+            .build();
+
+        assert_eq!(scene.entities.len(), 2);
+        assert_eq!(scene.entities[0].entity, entity_a);
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert!(scene.entities[0].components[0].represents::<ComponentB>());
+        assert_eq!(scene.entities[1].entity, entity_b);
+        assert_eq!(scene.entities[1].components.len(), 1);
+        assert!(scene.entities[1].components[0].represents::<ComponentA>());
+    }
+
     #[test]
     fn remove_componentless_entity() {
         let mut world = World::default();
@@ -1,11 +1,17 @@
-use crate::{DynamicScene, InstanceInfo, SceneSpawnError};
+use crate::{
+    dynamic_scene::map_reflected_component_entities, DynamicScene, InstanceInfo, SceneSpawnError,
+};
 use bevy_asset::Asset;
-use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::entity::{Entity, EntityHashMap};
 use bevy_ecs::{
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
+    reflect::{
+        type_may_contain_entities, AppTypeRegistry, ReflectComponent, ReflectMapEntities,
+        ReflectResource,
+    },
     world::World,
 };
-use bevy_reflect::TypePath;
+use bevy_reflect::{TypePath, TypeRegistry};
+use bevy_utils::TypeIdMap;
 
 /// To spawn a scene, you can use either:
 /// * [`SceneSpawner::spawn`](crate::SceneSpawner::spawn)
@@ -61,7 +67,32 @@ impl Scene {
         };
 
         let type_registry = type_registry.read();
+        self.write_resources_to_world_with(world, &type_registry)?;
+        self.write_entities_to_world_with(
+            world,
+            &self.entities(),
+            &mut instance_info.entity_map,
+            &type_registry,
+        )?;
+
+        Ok(instance_info)
+    }
+
+    /// Returns the entities of the scene, in the order they are written to a world.
+    pub(crate) fn entities(&self) -> Vec<Entity> {
+        self.world
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+            .collect()
+    }
 
+    /// Writes the resources of the scene to the given world.
+    pub(crate) fn write_resources_to_world_with(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
         // Resources archetype
         for (component_id, resource_data) in self.world.storages().resources.iter() {
             if !resource_data.is_present() {
@@ -89,51 +120,91 @@ impl Scene {
                     type_path: registration.type_info().type_path().to_string(),
                 }
             })?;
-            reflect_resource.copy(&self.world, world, &type_registry);
+            reflect_resource.copy(&self.world, world, type_registry);
         }
 
-        for archetype in self.world.archetypes().iter() {
-            for scene_entity in archetype.entities() {
-                let entity = *instance_info
-                    .entity_map
-                    .entry(scene_entity.id())
-                    .or_insert_with(|| world.spawn_empty().id());
-                for component_id in archetype.components() {
-                    let component_info = self
-                        .world
-                        .components()
-                        .get_info(component_id)
-                        .expect("component_ids in archetypes should have ComponentInfo");
-
-                    let reflect_component = type_registry
-                        .get(component_info.type_id().unwrap())
-                        .ok_or_else(|| SceneSpawnError::UnregisteredType {
-                            std_type_name: component_info.name().to_string(),
-                        })
-                        .and_then(|registration| {
-                            registration.data::<ReflectComponent>().ok_or_else(|| {
-                                SceneSpawnError::UnregisteredComponent {
-                                    type_path: registration.type_info().type_path().to_string(),
-                                }
-                            })
-                        })?;
-                    reflect_component.copy(
-                        &self.world,
-                        world,
-                        scene_entity.id(),
-                        entity,
-                        &type_registry,
-                    );
+        Ok(())
+    }
+
+    /// Writes the components of `scene_entities` to the given world, and maps the entities they
+    /// reference with `entity_map`.
+    ///
+    /// The entities missing from `entity_map` are spawned. This lets a scene be written a few
+    /// entities at a time, as long as the entities referenced by the written ones are already in
+    /// `entity_map`.
+    pub(crate) fn write_entities_to_world_with(
+        &self,
+        world: &mut World,
+        scene_entities: &[Entity],
+        entity_map: &mut EntityHashMap<Entity>,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        // Components that reference other entities, and the entities using them.
+        let mut scene_mappings: TypeIdMap<Vec<Entity>> = Default::default();
+        // Reflected components that contain entities but don't register `ReflectMapEntities`
+        // are mapped through reflection.
+        let mut reflected_mappings: TypeIdMap<Vec<Entity>> = Default::default();
+        let mut contains_entities: TypeIdMap<bool> = Default::default();
+
+        for &scene_entity in scene_entities {
+            // The scene may have changed since the entities to write were collected
+            let Some(scene_entity_ref) = self.world.get_entity(scene_entity) else {
+                continue;
+            };
+            let entity = *entity_map
+                .entry(scene_entity)
+                .or_insert_with(|| world.spawn_empty().id());
+            for component_id in scene_entity_ref.archetype().components() {
+                let component_info = self
+                    .world
+                    .components()
+                    .get_info(component_id)
+                    .expect("component_ids in archetypes should have ComponentInfo");
+
+                let registration = type_registry
+                    .get(component_info.type_id().unwrap())
+                    .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                        std_type_name: component_info.name().to_string(),
+                    })?;
+                let reflect_component =
+                    registration.data::<ReflectComponent>().ok_or_else(|| {
+                        SceneSpawnError::UnregisteredComponent {
+                            type_path: registration.type_info().type_path().to_string(),
+                        }
+                    })?;
+                if registration.data::<ReflectMapEntities>().is_some() {
+                    scene_mappings
+                        .entry(registration.type_id())
+                        .or_default()
+                        .push(entity);
+                } else if *contains_entities
+                    .entry(registration.type_id())
+                    .or_insert_with(|| {
+                        type_may_contain_entities(type_registry, registration.type_id())
+                    })
+                {
+                    reflected_mappings
+                        .entry(registration.type_id())
+                        .or_default()
+                        .push(entity);
                 }
+                reflect_component.copy(&self.world, world, scene_entity, entity, type_registry);
             }
         }
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_all_entities(world, &mut instance_info.entity_map);
-            }
+        for (type_id, entities) in scene_mappings.into_iter() {
+            let map_entities_reflect = type_registry
+                .get_type_data::<ReflectMapEntities>(type_id)
+                .expect("we only track the entities of components reflecting `MapEntities`");
+            map_entities_reflect.map_entities(world, entity_map, &entities);
+        }
+        for (type_id, entities) in reflected_mappings.into_iter() {
+            let reflect_component = type_registry
+                .get_type_data::<ReflectComponent>(type_id)
+                .expect("we only track the entities of reflected components");
+            map_reflected_component_entities(world, entity_map, reflect_component, &entities);
         }
 
-        Ok(instance_info)
+        Ok(())
     }
 }
@@ -2,6 +2,7 @@ use crate::{DynamicScene, Scene};
 use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
     event::{Event, Events, ManualEventReader},
     reflect::AppTypeRegistry,
@@ -9,17 +10,24 @@ use bevy_ecs::{
     world::{Command, Mut, World},
 };
 use bevy_hierarchy::{BuildWorldChildren, DespawnRecursiveExt, Parent, PushChild};
-use bevy_utils::{tracing::error, HashMap, HashSet};
+use bevy_utils::{tracing::error, HashMap, HashSet, Instant};
+use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
 
 /// Emitted when [`crate::SceneInstance`] becomes ready to use.
 ///
+/// This is emitted for the scenes queued for spawn, with or without a parent. For the scenes
+/// spawned over several frames, see [`SceneSpawnBudget`], it is emitted once all their entities
+/// are spawned.
+///
 /// See also [`SceneSpawner::instance_is_ready`].
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Event)]
 pub struct SceneInstanceReady {
-    /// Entity to which the scene was spawned as a child.
-    pub parent: Entity,
+    /// The instance which is ready.
+    pub instance_id: InstanceId,
+    /// Entity to which the scene was spawned as a child, if any.
+    pub parent: Option<Entity>,
 }
 
 /// Information about a scene instance.
@@ -39,6 +47,108 @@ impl InstanceId {
     }
 }
 
+/// How much of a scene is spawned each frame, to spawn large scenes over several frames instead
+/// of spawning thousands of entities at once in a single frame.
+///
+/// Use it with [`SceneSpawner::spawn_amortized`], or add it to an entity with a
+/// [`SceneBundle`](crate::SceneBundle) before the scene is spawned.
+///
+/// The entities of the scene are spawned right away without components, then their components
+/// are added a few entities at a time, and the roots of the scene are added to its parent as soon
+/// as they are complete. The instance is only ready, and [`SceneInstanceReady`] is only sent, once
+/// all the entities are complete.
+///
+/// Each scene spawned over several frames uses its own budget.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneSpawnBudget {
+    /// Completes at most this many entities each frame.
+    Entities(usize),
+    /// Completes entities until this duration has elapsed each frame.
+    ///
+    /// At least one entity is completed each frame, even if it takes longer.
+    Duration(Duration),
+}
+
+/// A scene being spawned over several frames, see [`SceneSpawnBudget`].
+struct AmortizedSpawn {
+    handle: Handle<Scene>,
+    instance_id: InstanceId,
+    budget: SceneSpawnBudget,
+    /// The entities of the scene, or `None` until the spawn starts.
+    scene_entities: Option<Vec<Entity>>,
+    /// The number of entities of the scene already complete.
+    completed: usize,
+    entity_map: EntityHashMap<Entity>,
+}
+
+impl AmortizedSpawn {
+    /// Completes the entities of the scene that fit in the budget, and returns whether all of
+    /// them are complete.
+    fn spawn_next(
+        &mut self,
+        world: &mut World,
+        scene: &Scene,
+        parent: Option<Entity>,
+    ) -> Result<bool, SceneSpawnError> {
+        let start = Instant::now();
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        let scene_entities = match &mut self.scene_entities {
+            Some(scene_entities) => scene_entities,
+            scene_entities @ None => {
+                scene.write_resources_to_world_with(world, &type_registry)?;
+                // Spawn all the entities first, so that the completed entities can reference
+                // the others
+                let entities = scene.entities();
+                for &scene_entity in &entities {
+                    self.entity_map
+                        .insert(scene_entity, world.spawn_empty().id());
+                }
+                scene_entities.insert(entities)
+            }
+        };
+
+        while self.completed < scene_entities.len() {
+            let count = match self.budget {
+                SceneSpawnBudget::Entities(count) => count.max(1),
+                SceneSpawnBudget::Duration(_) => 1,
+            };
+            let end = (self.completed + count).min(scene_entities.len());
+            let completed = &scene_entities[self.completed..end];
+            scene.write_entities_to_world_with(
+                world,
+                completed,
+                &mut self.entity_map,
+                &type_registry,
+            )?;
+            self.completed = end;
+
+            if let Some(parent) = parent {
+                for scene_entity in completed {
+                    let entity = self.entity_map[scene_entity];
+                    // Only the roots of the scene don't have a parent
+                    if !world.entity(entity).contains::<Parent>() {
+                        PushChild {
+                            parent,
+                            child: entity,
+                        }
+                        .apply(world);
+                    }
+                }
+            }
+
+            match self.budget {
+                SceneSpawnBudget::Entities(_) => break,
+                SceneSpawnBudget::Duration(duration) if start.elapsed() >= duration => break,
+                SceneSpawnBudget::Duration(_) => {}
+            }
+        }
+
+        Ok(self.completed == scene_entities.len())
+    }
+}
+
 /// Handles spawning and despawning scenes in the world, either synchronously or batched through the [`scene_spawner_system`].
 ///
 /// Synchronous methods: (Scene operations will take effect immediately)
@@ -56,6 +166,8 @@ impl InstanceId {
 /// - [`spawn_dynamic_as_child`](Self::spawn_dynamic_as_child)
 /// - [`spawn`](Self::spawn)
 /// - [`spawn_as_child`](Self::spawn_as_child)
+/// - [`spawn_amortized`](Self::spawn_amortized)
+/// - [`spawn_as_child_amortized`](Self::spawn_as_child_amortized)
 /// - [`despawn`](Self::despawn)
 /// - [`despawn_instance`](Self::despawn_instance)
 #[derive(Default, Resource)]
@@ -65,6 +177,7 @@ pub struct SceneSpawner {
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
+    amortized_scenes_to_spawn: Vec<AmortizedSpawn>,
     scenes_to_despawn: Vec<AssetId<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
@@ -122,6 +235,12 @@ pub enum SceneSpawnError {
         /// Id of the non-existent scene.
         id: AssetId<Scene>,
     },
+    /// The root of the hierarchy to restore is not in the scene.
+    #[error("scene does not contain the root of the hierarchy {entity:?}")]
+    MissingHierarchyRoot {
+        /// The root entity missing from the scene.
+        entity: Entity,
+    },
 }
 
 impl SceneSpawner {
@@ -159,6 +278,42 @@ impl SceneSpawner {
         instance_id
     }
 
+    /// Schedule the spawn of a new instance of the provided scene over several frames, spawning
+    /// as much of it as `budget` allows each frame.
+    ///
+    /// See [`SceneSpawnBudget`] for how the scene is spawned.
+    pub fn spawn_amortized(
+        &mut self,
+        id: impl Into<Handle<Scene>>,
+        budget: SceneSpawnBudget,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.amortized_scenes_to_spawn.push(AmortizedSpawn {
+            handle: id.into(),
+            instance_id,
+            budget,
+            scene_entities: None,
+            completed: 0,
+            entity_map: EntityHashMap::default(),
+        });
+        instance_id
+    }
+
+    /// Schedule the spawn of a new instance of the provided scene as a child of `parent` over
+    /// several frames, spawning as much of it as `budget` allows each frame.
+    ///
+    /// See [`SceneSpawnBudget`] for how the scene is spawned.
+    pub fn spawn_as_child_amortized(
+        &mut self,
+        id: impl Into<Handle<Scene>>,
+        parent: Entity,
+        budget: SceneSpawnBudget,
+    ) -> InstanceId {
+        let instance_id = self.spawn_amortized(id, budget);
+        self.scenes_with_parent.push((instance_id, parent));
+        instance_id
+    }
+
     /// Schedule the despawn of all instances of the provided dynamic scene.
     pub fn despawn(&mut self, id: impl Into<AssetId<DynamicScene>>) {
         self.scenes_to_despawn.push(id.into());
@@ -186,12 +341,25 @@ impl SceneSpawner {
     /// Immediately despawns a scene instance, removing all its entities from the world.
     pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
         if let Some(instance) = self.spawned_instances.remove(instance_id) {
-            for &entity in instance.entity_map.values() {
-                if let Some(mut entity_mut) = world.get_entity_mut(entity) {
-                    entity_mut.remove_parent();
-                    entity_mut.despawn_recursive();
-                };
-            }
+            Self::despawn_entities(world, &instance.entity_map);
+        }
+        // The instance may still be spawning over several frames
+        if let Some(index) = self
+            .amortized_scenes_to_spawn
+            .iter()
+            .position(|spawn| spawn.instance_id == *instance_id)
+        {
+            let spawn = self.amortized_scenes_to_spawn.remove(index);
+            Self::despawn_entities(world, &spawn.entity_map);
+        }
+    }
+
+    fn despawn_entities(world: &mut World, entity_map: &EntityHashMap<Entity>) {
+        for &entity in entity_map.values() {
+            if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.remove_parent();
+                entity_mut.despawn_recursive();
+            };
         }
     }
 
@@ -308,6 +476,7 @@ impl SceneSpawner {
                         .entry(handle.id())
                         .or_insert_with(HashSet::new);
                     spawned.insert(instance_id);
+                    self.send_ready_without_parent(world, instance_id);
                 }
                 Err(SceneSpawnError::NonExistentScene { .. }) => {
                     self.dynamic_scenes_to_spawn.push((handle, instance_id));
@@ -320,7 +489,7 @@ impl SceneSpawner {
 
         for (scene_handle, instance_id) in scenes_to_spawn {
             match self.spawn_sync_internal(world, scene_handle.id(), instance_id) {
-                Ok(_) => {}
+                Ok(_) => self.send_ready_without_parent(world, instance_id),
                 Err(SceneSpawnError::NonExistentRealScene { .. }) => {
                     self.scenes_to_spawn.push((scene_handle, instance_id));
                 }
@@ -331,6 +500,56 @@ impl SceneSpawner {
         Ok(())
     }
 
+    /// Immediately spawns the part of each scene scheduled for spawn over several frames that fits
+    /// in its [`SceneSpawnBudget`].
+    pub fn spawn_amortized_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let amortized_scenes_to_spawn = std::mem::take(&mut self.amortized_scenes_to_spawn);
+
+        for mut spawn in amortized_scenes_to_spawn {
+            let parent = self
+                .scenes_with_parent
+                .iter()
+                .find(|(instance_id, _)| *instance_id == spawn.instance_id)
+                .map(|&(_, parent)| parent);
+            let complete = world.resource_scope(|world, scenes: Mut<Assets<Scene>>| {
+                // Wait for the scene to be loaded
+                let Some(scene) = scenes.get(spawn.handle.id()) else {
+                    return Ok(false);
+                };
+                spawn.spawn_next(world, scene, parent)
+            })?;
+
+            if complete {
+                self.spawned_instances.insert(
+                    spawn.instance_id,
+                    InstanceInfo {
+                        entity_map: spawn.entity_map,
+                    },
+                );
+                self.send_ready_without_parent(world, spawn.instance_id);
+            } else {
+                self.amortized_scenes_to_spawn.push(spawn);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends [`SceneInstanceReady`] for a spawned instance, unless it has a parent: the event is
+    /// then sent once the instance is added to its parent.
+    fn send_ready_without_parent(&self, world: &mut World, instance_id: InstanceId) {
+        if !self
+            .scenes_with_parent
+            .iter()
+            .any(|(parented_instance_id, _)| *parented_instance_id == instance_id)
+        {
+            world.send_event(SceneInstanceReady {
+                instance_id,
+                parent: None,
+            });
+        }
+    }
+
     pub(crate) fn set_scene_instance_parent_sync(&mut self, world: &mut World) {
         let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
@@ -356,7 +575,10 @@ impl SceneSpawner {
                     }
                 }
 
-                world.send_event(SceneInstanceReady { parent });
+                world.send_event(SceneInstanceReady {
+                    instance_id,
+                    parent: Some(parent),
+                });
             } else {
                 self.scenes_with_parent.push((instance_id, parent));
             }
@@ -407,6 +629,10 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner
             .scenes_to_spawn
             .retain(|(_, instance)| !dead_instances.contains(instance));
+        for instance in &dead_instances {
+            // Despawns the entities already spawned by the scenes spawned over several frames
+            scene_spawner.despawn_instance_sync(world, instance);
+        }
 
         let scene_asset_events = world.resource::<Events<AssetEvent<DynamicScene>>>();
 
@@ -428,6 +654,9 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner
             .spawn_queued_scenes(world)
             .unwrap_or_else(|err| panic!("{}", err));
+        scene_spawner
+            .spawn_amortized_scenes(world)
+            .unwrap_or_else(|err| panic!("{}", err));
         scene_spawner
             .update_spawned_scenes(world, &updated_spawned_scenes)
             .unwrap();
@@ -444,6 +673,7 @@ mod tests {
     use bevy_ecs::query::With;
     use bevy_ecs::system::{Commands, Res, ResMut, RunSystemOnce};
     use bevy_ecs::{component::Component, system::Query};
+    use bevy_hierarchy::Children;
     use bevy_reflect::Reflect;
 
     use crate::{DynamicSceneBuilder, ScenePlugin};
@@ -522,11 +752,11 @@ mod tests {
                 });
 
         // Spawn scene.
-        let scene_entity = app.world_mut().run_system_once(
+        let (scene_entity, instance_id) = app.world_mut().run_system_once(
             move |mut commands: Commands<'_, '_>, mut scene_spawner: ResMut<'_, SceneSpawner>| {
                 let scene_entity = commands.spawn_empty().id();
-                scene_spawner.spawn_dynamic_as_child(scene.clone(), scene_entity);
-                scene_entity
+                let instance_id = scene_spawner.spawn_dynamic_as_child(scene.clone(), scene_entity);
+                (scene_entity, instance_id)
             },
         );
 
@@ -539,7 +769,8 @@ mod tests {
                 assert_eq!(
                     events.next().expect("found no `SceneInstanceReady` event"),
                     &SceneInstanceReady {
-                        parent: scene_entity
+                        instance_id,
+                        parent: Some(scene_entity),
                     },
                     "`SceneInstanceReady` contains the wrong parent entity"
                 );
@@ -590,4 +821,90 @@ mod tests {
         app.update();
         check(app.world_mut(), 0);
     }
+
+    #[test]
+    fn spawn_amortized() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin));
+        app.register_type::<A>();
+
+        let mut scene_world = World::new();
+        for i in 0..10 {
+            scene_world.spawn(A(i));
+        }
+        let scene = app
+            .world()
+            .resource::<AssetServer>()
+            .add(Scene::new(scene_world));
+
+        let parent = app.world_mut().spawn_empty().id();
+        let instance_id = app
+            .world_mut()
+            .resource_mut::<SceneSpawner>()
+            .spawn_as_child_amortized(scene, parent, SceneSpawnBudget::Entities(4));
+
+        let check = |world: &mut World, expected_count: usize, ready: bool| {
+            assert_eq!(world.query::<&A>().iter(world).len(), expected_count);
+            assert_eq!(
+                world.entity(parent).get::<Children>().unwrap().len(),
+                expected_count
+            );
+            assert_eq!(
+                world
+                    .resource::<SceneSpawner>()
+                    .instance_is_ready(instance_id),
+                ready
+            );
+            let events = world.resource::<Events<SceneInstanceReady>>();
+            assert_eq!(events.get_reader().read(events).count(), usize::from(ready));
+        };
+
+        app.update();
+        check(app.world_mut(), 4, false);
+        app.update();
+        check(app.world_mut(), 8, false);
+        app.update();
+        check(app.world_mut(), 10, true);
+    }
+
+    #[test]
+    fn spawn_amortized_without_parent() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin));
+        app.register_type::<A>();
+
+        let mut scene_world = World::new();
+        for i in 0..3 {
+            scene_world.spawn(A(i));
+        }
+        let scene = app
+            .world()
+            .resource::<AssetServer>()
+            .add(Scene::new(scene_world));
+
+        let instance_id = app
+            .world_mut()
+            .resource_mut::<SceneSpawner>()
+            .spawn_amortized(scene, SceneSpawnBudget::Entities(2));
+
+        let ready_events = |world: &World| {
+            let events = world.resource::<Events<SceneInstanceReady>>();
+            events
+                .get_reader()
+                .read(events)
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        app.update();
+        assert!(ready_events(app.world()).is_empty());
+        app.update();
+        assert_eq!(
+            ready_events(app.world()),
+            [SceneInstanceReady {
+                instance_id,
+                parent: None,
+            }]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 #![allow(deprecated)]
 
-use crate::{Sprite, TextureAtlas};
+use crate::{Sprite, TextureAtlas, Tilemap};
 use bevy_asset::Handle;
 use bevy_ecs::bundle::Bundle;
 use bevy_render::{
@@ -34,6 +34,36 @@ pub struct SpriteBundle {
     pub view_visibility: ViewVisibility,
 }
 
+/// A [`Bundle`] of components for drawing a [`Tilemap`].
+#[derive(Bundle, Clone, Debug)]
+pub struct TilemapBundle {
+    /// The tiles of the tilemap, and the tileset they are drawn from.
+    pub tilemap: Tilemap,
+    /// The local transform of the tilemap, relative to its parent.
+    pub transform: Transform,
+    /// The absolute transform of the tilemap. This should generally not be written to directly.
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
+
+impl From<Tilemap> for TilemapBundle {
+    fn from(tilemap: Tilemap) -> Self {
+        Self {
+            tilemap,
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+        }
+    }
+}
+
 /// A [`Bundle`] of components for drawing a single sprite from a sprite sheet (also referred
 /// to as a `TextureAtlas`) or for animated sprites.
 ///
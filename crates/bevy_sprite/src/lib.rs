@@ -10,12 +10,17 @@
 //! Provides 2D sprite rendering functionality.
 mod bundle;
 mod dynamic_texture_atlas_builder;
+mod light_2d;
 mod mesh2d;
+mod parallax;
+mod picking;
 mod render;
 mod sprite;
+mod sprite_animation;
 mod texture_atlas;
 mod texture_atlas_builder;
 mod texture_slice;
+mod tilemap;
 
 pub mod prelude {
     #[allow(deprecated)]
@@ -24,10 +29,17 @@ pub mod prelude {
 
     #[doc(hidden)]
     pub use crate::{
-        bundle::SpriteBundle,
-        sprite::{ImageScaleMode, Sprite},
+        bundle::{SpriteBundle, TilemapBundle},
+        light_2d::{AmbientLight2d, PointLight2d},
+        parallax::{ParallaxLayer, ParallaxRepeat},
+        sprite::{
+            AlphaMode2d, ImageScaleMode, Sprite, SpriteMask, SpriteMaskInteraction, SpriteOutline,
+            SpriteSampler, SpriteShadow, SpriteSortKey,
+        },
+        sprite_animation::{AnimationFrame, AnimationMode, SpriteAnimation, SpriteAnimationEvent},
         texture_atlas::{TextureAtlas, TextureAtlasLayout},
         texture_slice::{BorderRect, SliceScaleMode, TextureSlice, TextureSlicer},
+        tilemap::Tilemap,
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
 }
@@ -35,24 +47,34 @@ pub mod prelude {
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
+pub use light_2d::*;
 pub use mesh2d::*;
+pub use parallax::*;
+pub use picking::*;
 pub use render::*;
 pub use sprite::*;
+pub use sprite_animation::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
 pub use texture_slice::*;
+pub use tilemap::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetApp, Assets, Handle};
-use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_core_pipeline::core_2d::{Opaque2d, Transparent2d};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
+    batching::gpu_preprocessing::GpuPreprocessingSupport,
     extract_component::{ExtractComponent, ExtractComponentPlugin},
+    extract_resource::ExtractResourcePlugin,
+    graph::CameraDriverLabel,
     mesh::Mesh,
     primitives::Aabb,
+    render_graph::RenderGraph,
     render_phase::AddRenderCommand,
     render_resource::{Shader, SpecializedRenderPipelines},
-    texture::Image,
+    renderer::RenderDevice,
+    texture::{Image, ImagePlugin, ImageSamplerDescriptor},
     view::{check_visibility, NoFrustumCulling, VisibilitySystems},
     ExtractSchedule, Render, RenderApp, RenderSet,
 };
@@ -64,12 +86,17 @@ pub struct SpritePlugin;
 pub const SPRITE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(2763343953151597127);
 pub const SPRITE_VIEW_BINDINGS_SHADER_HANDLE: Handle<Shader> =
     Handle::weak_from_u128(8846920112458963210);
+pub const SPRITE_VERTEX_OUTPUT_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(5230957114624887104);
+pub const SPRITE_TEXTURE_BINDINGS_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(1709432874519823655);
 
 /// System set for sprite rendering.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SpriteSystem {
     ExtractSprites,
     ComputeSlices,
+    AnimateSprites,
 }
 
 /// A component that marks entities that aren't themselves sprites but become
@@ -102,24 +129,67 @@ impl Plugin for SpritePlugin {
             "render/sprite_view_bindings.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            SPRITE_VERTEX_OUTPUT_SHADER_HANDLE,
+            "render/sprite_vertex_output.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SPRITE_TEXTURE_BINDINGS_SHADER_HANDLE,
+            "render/sprite_texture_bindings.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SPRITE_CULLING_SHADER_HANDLE,
+            "render/sprite_culling.wgsl",
+            Shader::from_wgsl
+        );
         app.init_asset::<TextureAtlasLayout>()
             .register_asset_reflect::<TextureAtlasLayout>()
             .register_type::<Sprite>()
+            .register_type::<AlphaMode2d>()
+            .register_type::<SpriteOutline>()
+            .register_type::<SpriteShadow>()
+            .register_type::<SpriteMask>()
+            .register_type::<SpriteMaskInteraction>()
+            .register_type::<SpriteSampler>()
+            .register_type::<SpriteSortKey>()
+            .register_type::<ParallaxLayer>()
+            .register_type::<SpriteColorSpace>()
+            .init_resource::<SpriteColorSpace>()
+            .register_type::<SpritePixelSnap>()
+            .init_resource::<SpritePixelSnap>()
+            .register_type::<AmbientLight2d>()
+            .register_type::<PointLight2d>()
             .register_type::<ImageScaleMode>()
             .register_type::<TextureSlicer>()
             .register_type::<Anchor>()
             .register_type::<TextureAtlas>()
             .register_type::<Mesh2dHandle>()
             .register_type::<SpriteSource>()
+            .register_type::<SpriteAnimation>()
+            .register_type::<AnimationFrame>()
+            .register_type::<AnimationMode>()
+            .add_event::<SpriteAnimationEvent>()
             .add_plugins((
                 Mesh2dRenderPlugin,
                 ColorMaterialPlugin,
+                TilemapPlugin,
                 ExtractComponentPlugin::<SpriteSource>::default(),
+                ExtractResourcePlugin::<SpriteColorSpace>::default(),
+                ExtractResourcePlugin::<SpritePixelSnap>::default(),
+                ExtractComponentPlugin::<AmbientLight2d>::default(),
             ))
             .add_systems(
                 PostUpdate,
                 (
                     calculate_bounds_2d.in_set(VisibilitySystems::CalculateBounds),
+                    animate_sprites
+                        .in_set(SpriteSystem::AnimateSprites)
+                        .before(SpriteSystem::ComputeSlices),
                     (
                         compute_slices_on_asset_event,
                         compute_slices_on_sprite_change,
@@ -136,16 +206,23 @@ impl Plugin for SpritePlugin {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ImageBindGroups>()
+                .init_resource::<SpriteColorSpace>()
+                .init_resource::<SpritePixelSnap>()
                 .init_resource::<SpecializedRenderPipelines<SpritePipeline>>()
                 .init_resource::<SpriteMeta>()
+                .init_resource::<SpriteBatches>()
                 .init_resource::<ExtractedSprites>()
                 .init_resource::<SpriteAssetEvents>()
+                .init_resource::<ExtractedPointLights2d>()
+                .init_resource::<Lights2dMeta>()
+                .add_render_command::<Opaque2d, DrawSprite>()
                 .add_render_command::<Transparent2d, DrawSprite>()
                 .add_systems(
                     ExtractSchedule,
                     (
                         extract_sprites.in_set(SpriteSystem::ExtractSprites),
                         extract_sprite_events,
+                        extract_point_lights_2d,
                     ),
                 )
                 .add_systems(
@@ -154,6 +231,7 @@ impl Plugin for SpritePlugin {
                         queue_sprites
                             .in_set(RenderSet::Queue)
                             .ambiguous_with(queue_material2d_meshes::<ColorMaterial>),
+                        prepare_lights_2d.in_set(RenderSet::PrepareResources),
                         prepare_sprite_image_bind_groups.in_set(RenderSet::PrepareBindGroups),
                         prepare_sprite_view_bind_groups.in_set(RenderSet::PrepareBindGroups),
                     ),
@@ -162,8 +240,41 @@ impl Plugin for SpritePlugin {
     }
 
     fn finish(&self, app: &mut App) {
+        let default_sampler = app
+            .get_added_plugins::<ImagePlugin>()
+            .first()
+            .map_or_else(ImageSamplerDescriptor::linear, |plugin| {
+                plugin.default_sampler.clone()
+            });
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<SpritePipeline>();
+
+            // Images with the default sampler repeat with a repeating copy of it
+            let world = render_app.world_mut();
+            let default_repeat_sampler = world
+                .resource::<RenderDevice>()
+                .create_sampler(&render::repeating(default_sampler).as_wgpu());
+            world
+                .resource_mut::<SpritePipeline>()
+                .default_repeat_sampler = default_repeat_sampler;
+
+            // The sprites of the views with `GpuCulling` are culled on the GPU when supported
+            let gpu_preprocessing_support =
+                render_app.world().resource::<GpuPreprocessingSupport>();
+            if *gpu_preprocessing_support == GpuPreprocessingSupport::Culling {
+                render_app
+                    .init_resource::<SpriteCullingBuffers>()
+                    .init_resource::<SpriteCullingPipeline>()
+                    .add_systems(
+                        Render,
+                        prepare_sprite_culling_bind_group
+                            .in_set(RenderSet::PrepareBindGroups)
+                            .after(prepare_sprite_image_bind_groups),
+                    );
+                let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+                render_graph.add_node(SpriteCullingLabel, SpriteCullingNode);
+                render_graph.add_node_edge(SpriteCullingLabel, CameraDriverLabel);
+            }
         }
     }
 }
@@ -0,0 +1,64 @@
+use bevy_color::Color;
+use bevy_ecs::{component::Component, query::With, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{camera::Camera, extract_component::ExtractComponent, view::Visibility};
+use bevy_transform::components::Transform;
+
+/// Enables 2D lighting for a camera, and sets the light that reaches every sprite.
+///
+/// The sprites seen by a camera with this component are shaded by the [`PointLight2d`]s, using
+/// their [`Sprite::normal_map`](crate::Sprite::normal_map) if they have one. This includes the
+/// text drawn in 2D, but not the sprites using a [`SpriteMaterial`](crate::SpriteMaterial), nor
+/// their outlines and shadows. Without this component, sprites are drawn unlit.
+#[derive(Component, ExtractComponent, Debug, Clone, Copy, PartialEq, Reflect)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct AmbientLight2d {
+    /// The color of the light.
+    pub color: Color,
+    /// The factor by which the color of the sprites is multiplied where no other light reaches
+    /// them.
+    pub brightness: f32,
+}
+
+impl Default for AmbientLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            brightness: 0.2,
+        }
+    }
+}
+
+/// A light shining on the sprites around it, seen by cameras with an [`AmbientLight2d`].
+///
+/// The light is placed [`height`](Self::height) above the sprites at its translation, so that
+/// the normal maps of the sprites are lit from the side near the light and not only from above.
+///
+/// The light only reaches the cameras sharing one of its
+/// [`RenderLayers`](bevy_render::view::RenderLayers), and up to
+/// [`MAX_POINT_LIGHTS_2D`](crate::MAX_POINT_LIGHTS_2D) lights are drawn by each camera.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+#[require(Transform, Visibility)]
+pub struct PointLight2d {
+    /// The color of the light.
+    pub color: Color,
+    /// The factor by which the color of the sprites is multiplied at the position of the light.
+    pub intensity: f32,
+    /// The distance from the light at which it stops lighting sprites, in world units.
+    pub radius: f32,
+    /// The distance between the light and the plane of the sprites, in world units.
+    pub height: f32,
+}
+
+impl Default for PointLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 256.0,
+            height: 32.0,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use bevy_app::{App, Plugin};
 use bevy_asset::{Asset, AssetApp, AssetId, AssetServer, Handle};
 use bevy_core_pipeline::{
-    core_2d::Transparent2d,
+    core_2d::{view_depth_2d, Transparent2d},
     tonemapping::{DebandDither, Tonemapping},
 };
 use bevy_derive::{Deref, DerefMut};
@@ -37,7 +37,7 @@ use std::marker::PhantomData;
 
 use crate::{
     DrawMesh2d, Mesh2dHandle, Mesh2dPipeline, Mesh2dPipelineKey, RenderMesh2dInstances,
-    SetMesh2dBindGroup, SetMesh2dViewBindGroup, WithMesh2d,
+    SetMesh2dBindGroup, SetMesh2dViewBindGroup, SpriteColorSpace, WithMesh2d,
 };
 
 /// Materials are used alongside [`Material2dPlugin`] and [`MaterialMesh2dBundle`]
@@ -370,6 +370,7 @@ pub fn queue_material2d_meshes<M: Material2d>(
     mut pipelines: ResMut<SpecializedMeshPipelines<Material2dPipeline<M>>>,
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
+    color_space: Res<SpriteColorSpace>,
     render_meshes: Res<RenderAssets<GpuMesh>>,
     render_materials: Res<RenderAssets<PreparedMaterial2d<M>>>,
     mut render_mesh_instances: ResMut<RenderMesh2dInstances>,
@@ -398,6 +399,9 @@ pub fn queue_material2d_meshes<M: Material2d>(
 
         let mut view_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
             | Mesh2dPipelineKey::from_hdr(view.hdr);
+        if *color_space == SpriteColorSpace::Srgb {
+            view_key |= Mesh2dPipelineKey::SRGB_VERTEX_COLORS;
+        }
 
         if !view.hdr {
             if let Some(tonemapping) = tonemapping {
@@ -444,16 +448,15 @@ pub fn queue_material2d_meshes<M: Material2d>(
 
             mesh_instance.material_bind_group_id = material_2d.get_bind_group_id();
 
-            let mesh_z = mesh_instance.transforms.transform.translation.z;
+            let mesh_depth = view_depth_2d(view, mesh_instance.transforms.transform.translation);
             transparent_phase.add(Transparent2d {
                 entity: *visible_entity,
                 draw_function: draw_transparent_2d,
                 pipeline: pipeline_id,
                 // NOTE: Back-to-front ordering for transparent with ascending sort means far should have the
-                // lowest sort key and getting closer should increase. As we have
-                // -z in front of the camera, the largest distance is -far with values increasing toward the
-                // camera. As such we can just use mesh_z as the distance
-                sort_key: FloatOrd(mesh_z + material_2d.depth_bias),
+                // lowest sort key and getting closer should increase, which is the depth along the
+                // backward axis of the view.
+                sort_key: FloatOrd(mesh_depth + material_2d.depth_bias),
                 // Batching is done in batch_and_prepare_render_phase
                 batch_range: 0..1,
                 extra_index: PhaseItemExtraIndex::NONE,
@@ -8,6 +8,10 @@
 #import bevy_core_pipeline::tonemapping
 #endif
 
+#ifdef SRGB_VERTEX_COLORS
+#import bevy_render::color_operations::srgb_to_linear
+#endif
+
 struct Vertex {
     @builtin(instance_index) instance_index: u32,
 #ifdef VERTEX_POSITIONS
@@ -55,7 +59,11 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 #endif
 
 #ifdef VERTEX_COLORS
+#ifdef SRGB_VERTEX_COLORS
+    out.color = vec4<f32>(srgb_to_linear(vertex.color.rgb), vertex.color.a);
+#else
     out.color = vertex.color;
+#endif
 #endif
     return out;
 }
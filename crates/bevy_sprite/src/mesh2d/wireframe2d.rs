@@ -1,5 +1,5 @@
 use crate::{Material2d, Material2dKey, Material2dPlugin, Mesh2dHandle};
-use bevy_app::{Plugin, Startup, Update};
+use bevy_app::{Plugin, Startup, StartupSet, Update};
 use bevy_asset::{load_internal_asset, Asset, Assets, Handle};
 use bevy_color::{LinearRgba, Srgba};
 use bevy_ecs::prelude::*;
@@ -37,7 +37,10 @@ impl Plugin for Wireframe2dPlugin {
             .register_type::<Wireframe2dColor>()
             .init_resource::<Wireframe2dConfig>()
             .add_plugins(Material2dPlugin::<Wireframe2dMaterial>::default())
-            .add_systems(Startup, setup_global_wireframe_material)
+            .add_systems(
+                Startup,
+                setup_global_wireframe_material.in_set(StartupSet::RenderSetup),
+            )
             .add_systems(
                 Update,
                 (
@@ -0,0 +1,145 @@
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent};
+use bevy_math::{BVec2, Vec2, Vec3Swizzles};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::NoFrustumCulling;
+use bevy_transform::components::GlobalTransform;
+
+/// Moves a sprite with a fraction of the movement of a camera when it is rendered, for parallax
+/// scrolling backgrounds made of several layers.
+///
+/// The sprite is rendered at its position offset by the translation of the
+/// [`camera`](Self::camera) multiplied by the [`factor`](Self::factor), without changing its
+/// [`Transform`](bevy_transform::components::Transform). With a factor of `0.0`, the sprite stays
+/// in place like the rest of the world, and with a factor of `1.0`, it follows the camera like a
+/// distant sky.
+///
+/// Along the axes of its [`repeat`](Self::repeat) mode, the sprite follows the camera and its
+/// image scrolls instead, repeating infinitely. The sprite should then be large enough to cover
+/// the view of the camera. Repeating isn't supported by sprites with an
+/// [`ImageScaleMode`](crate::ImageScaleMode).
+///
+/// Repeating layers are drawn with [`Sprite::uv_repeat`](crate::Sprite::uv_repeat) set, so their
+/// image is sampled with a repeating sampler even when its own sampler clamps to its edges. The
+/// whole texture wraps, not the [`Sprite::rect`](crate::Sprite::rect) or the section of a
+/// [`TextureAtlas`](crate::TextureAtlas) displayed by the sprite, so repeating layers should use
+/// standalone images.
+///
+/// Parallax layers aren't frustum culled, as they aren't rendered where their transform places
+/// them.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[require(NoFrustumCulling)]
+pub struct ParallaxLayer {
+    /// The camera the layer moves with.
+    pub camera: Entity,
+    /// The fraction of the movement of the camera the layer follows, along each axis.
+    pub factor: Vec2,
+    /// The axes along which the image of the sprite repeats.
+    pub repeat: ParallaxRepeat,
+}
+
+impl Default for ParallaxLayer {
+    fn default() -> Self {
+        Self {
+            camera: Entity::PLACEHOLDER,
+            factor: Vec2::ZERO,
+            repeat: ParallaxRepeat::None,
+        }
+    }
+}
+
+impl ParallaxLayer {
+    /// Creates a layer following the given `factor` of the movement of `camera` along both axes.
+    pub fn new(camera: Entity, factor: f32) -> Self {
+        Self {
+            camera,
+            factor: Vec2::splat(factor),
+            repeat: ParallaxRepeat::None,
+        }
+    }
+
+    /// Returns this layer with the given [`factor`](Self::factor) per axis.
+    pub const fn with_factor(mut self, factor: Vec2) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Returns this layer with the given [`repeat`](Self::repeat) mode.
+    pub const fn with_repeat(mut self, repeat: ParallaxRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Returns the transform a sprite with the given `transform` is rendered with, when the
+    /// camera has the given `camera_transform`, and the offset of its image in world space along
+    /// the repeating axes.
+    pub fn apply(
+        &self,
+        transform: &GlobalTransform,
+        camera_transform: &GlobalTransform,
+    ) -> (GlobalTransform, Vec2) {
+        let camera = camera_transform.translation().xy();
+        let translation = transform.translation().xy();
+        let offset = camera * self.factor;
+        let repeat = self.repeat.axes();
+        // The repeating axes follow the camera, the image moving by `offset` relative to it
+        let movement = Vec2::select(repeat, camera - translation, offset);
+        let image_offset = Vec2::select(repeat, offset - movement, Vec2::ZERO);
+        let mut affine = transform.affine();
+        affine.translation += movement.extend(0.).into();
+        (affine.into(), image_offset)
+    }
+}
+
+/// The axes along which the image of a [`ParallaxLayer`] repeats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum ParallaxRepeat {
+    /// The sprite doesn't repeat.
+    #[default]
+    None,
+    /// The sprite repeats horizontally.
+    X,
+    /// The sprite repeats vertically.
+    Y,
+    /// The sprite repeats along both axes.
+    Both,
+}
+
+impl ParallaxRepeat {
+    /// Returns whether the sprite repeats along each axis.
+    pub fn axes(self) -> BVec2 {
+        match self {
+            ParallaxRepeat::None => BVec2::FALSE,
+            ParallaxRepeat::X => BVec2::new(true, false),
+            ParallaxRepeat::Y => BVec2::new(false, true),
+            ParallaxRepeat::Both => BVec2::TRUE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Vec2, Vec3};
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{ParallaxLayer, ParallaxRepeat};
+
+    #[test]
+    fn parallax_layer_offsets() {
+        let transform = GlobalTransform::from_translation(Vec3::new(10., 20., -1.));
+        let camera = GlobalTransform::from_translation(Vec3::new(100., 50., 0.));
+
+        let layer = ParallaxLayer::new(Entity::PLACEHOLDER, 0.5);
+        let (parallax, image_offset) = layer.apply(&transform, &camera);
+        assert_eq!(parallax.translation(), Vec3::new(60., 45., -1.));
+        assert_eq!(image_offset, Vec2::ZERO);
+
+        let layer = layer.with_repeat(ParallaxRepeat::X);
+        let (parallax, image_offset) = layer.apply(&transform, &camera);
+        // The sprite follows the camera horizontally, and its image stays at the parallax position
+        assert_eq!(parallax.translation(), Vec3::new(100., 45., -1.));
+        assert_eq!(image_offset, Vec2::new(-40., 0.));
+    }
+}
@@ -0,0 +1,403 @@
+//! A picking backend for sprites: the [`SpriteHits`] resource lists the sprites under each
+//! pointer.
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_input::{pointer::PointerId, touch::Touches, InputSystem};
+use bevy_math::{Ray3d, Rect, UVec2, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, NormalizedRenderTarget},
+    render_resource::TextureFormat,
+    texture::Image,
+    view::{RenderLayers, ViewVisibility},
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use bevy_window::{PrimaryWindow, Window};
+
+use crate::{ImageScaleMode, Sprite, TextureAtlas, TextureAtlasLayout};
+
+/// Adds the [`SpriteHits`] resource, updated in [`update_sprite_hits`] with the sprites under
+/// each pointer.
+///
+/// This requires the input resources added by the `InputPlugin`.
+#[derive(Default)]
+pub struct SpritePickingBackend;
+
+impl Plugin for SpritePickingBackend {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpriteHits>()
+            .init_resource::<SpritePickingSettings>()
+            .register_type::<SpritePickingSettings>()
+            .register_type::<SpritePickingMode>()
+            .add_systems(PreUpdate, update_sprite_hits.after(InputSystem));
+    }
+}
+
+/// How the sprites are hit-tested against the pointers.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum SpritePickingMode {
+    /// A pointer hits a sprite anywhere in its quad.
+    BoundingBox,
+    /// A pointer only hits the pixels of a sprite with an alpha above the threshold, so the
+    /// transparent parts of its image can't be picked.
+    ///
+    /// The image of the sprite must be kept in the main world, with
+    /// [`RenderAssetUsages::MAIN_WORLD`](bevy_render::render_asset::RenderAssetUsages::MAIN_WORLD),
+    /// and use an 8 bits or 32 bits float RGBA format. Other sprites, as well as sprites using an
+    /// [`ImageScaleMode`], are hit-tested against their quad.
+    AlphaThreshold(f32),
+}
+
+/// Configures the [`SpritePickingBackend`].
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SpritePickingSettings {
+    /// How the sprites are hit-tested.
+    pub mode: SpritePickingMode,
+}
+
+impl Default for SpritePickingSettings {
+    fn default() -> Self {
+        Self {
+            mode: SpritePickingMode::AlphaThreshold(0.1),
+        }
+    }
+}
+
+/// A sprite under a pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteHit {
+    /// The sprite entity.
+    pub entity: Entity,
+    /// The camera the sprite was hit through.
+    pub camera: Entity,
+    /// The hit position, in world space.
+    pub position: Vec3,
+    /// The distance from the camera to the hit position, along the pointer ray.
+    pub depth: f32,
+}
+
+/// The sprites under each pointer, sorted from the topmost one.
+///
+/// Sprites seen through cameras with a higher [`Camera::order`] come first, then the sprites
+/// closest to the camera. Updated in [`update_sprite_hits`].
+#[derive(Resource, Debug, Default)]
+pub struct SpriteHits {
+    pointers: HashMap<PointerId, Vec<SpriteHit>>,
+}
+
+impl SpriteHits {
+    /// Returns the sprites under `pointer`, from the topmost one.
+    pub fn get(&self, pointer: PointerId) -> &[SpriteHit] {
+        self.pointers.get(&pointer).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the topmost sprite under `pointer`.
+    pub fn topmost(&self, pointer: PointerId) -> Option<&SpriteHit> {
+        self.get(pointer).first()
+    }
+
+    /// Iterates over the pointers currently over a window, whether they hit a sprite or not.
+    pub fn pointers(&self) -> impl Iterator<Item = PointerId> + '_ {
+        self.pointers.keys().copied()
+    }
+
+    /// Returns `true` if `entity` is under any pointer.
+    pub fn is_hovered(&self, entity: Entity) -> bool {
+        self.pointers
+            .values()
+            .any(|hits| hits.iter().any(|hit| hit.entity == entity))
+    }
+}
+
+/// Updates the [`SpriteHits`] from the mouse cursor and the touches.
+#[allow(clippy::too_many_arguments)]
+pub fn update_sprite_hits(
+    mut sprite_hits: ResMut<SpriteHits>,
+    settings: Res<SpritePickingSettings>,
+    camera_query: Query<(Entity, &Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    touches_input: Res<Touches>,
+    images: Res<Assets<Image>>,
+    texture_atlases: Res<Assets<TextureAtlasLayout>>,
+    sprite_query: Query<(
+        Entity,
+        &Sprite,
+        &Handle<Image>,
+        &GlobalTransform,
+        &ViewVisibility,
+        Option<&TextureAtlas>,
+        Option<&RenderLayers>,
+        Has<ImageScaleMode>,
+    )>,
+) {
+    let primary_window = primary_window.iter().next();
+    let default_layers = RenderLayers::default();
+
+    // The pointer rays of the given window position, for each active camera rendering to a
+    // window that sees it. If `window_position` is `None`, the cursor position of each window is
+    // used.
+    let camera_rays = |window_position: Option<Vec2>| {
+        camera_query
+            .iter()
+            .filter_map(|(entity, camera, transform, layers)| {
+                let Some(NormalizedRenderTarget::Window(window_ref)) =
+                    camera.target.normalize(primary_window)
+                else {
+                    return None;
+                };
+                let position = window_position.or_else(|| {
+                    windows
+                        .get(window_ref.entity())
+                        .ok()
+                        .and_then(Window::cursor_position)
+                })?;
+                let ray = camera.viewport_ray(transform, position)?;
+                Some((entity, camera.order, ray, layers.unwrap_or(&default_layers)))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut pointers = vec![(PointerId::Mouse, camera_rays(None))];
+    for touch in touches_input.iter() {
+        pointers.push((
+            PointerId::Touch(touch.id()),
+            camera_rays(Some(touch.position())),
+        ));
+    }
+
+    sprite_hits.pointers.clear();
+    for (pointer, rays) in pointers {
+        let mut hits = Vec::new();
+        for (camera, order, ray, camera_layers) in &rays {
+            for (entity, sprite, image_handle, transform, visibility, atlas, layers, sliced) in
+                &sprite_query
+            {
+                if !visibility.get() || !camera_layers.intersects(layers.unwrap_or(&default_layers))
+                {
+                    continue;
+                }
+                let image = images.get(image_handle);
+                let texture_rect = texture_rect(sprite, atlas, &texture_atlases);
+                let Some(size) = sprite
+                    .custom_size
+                    .or_else(|| texture_rect.map(|rect| rect.size()))
+                    .or_else(|| image.map(Image::size_f32))
+                else {
+                    continue;
+                };
+                let Some((depth, uv)) = hit_sprite(*ray, transform, size, sprite.anchor.as_vec())
+                else {
+                    continue;
+                };
+                if let (SpritePickingMode::AlphaThreshold(threshold), Some(image), false) =
+                    (settings.mode, image, sliced)
+                {
+                    let uv = Vec2::new(
+                        if sprite.flip_x { 1.0 - uv.x } else { uv.x },
+                        if sprite.flip_y { 1.0 - uv.y } else { uv.y },
+                    );
+                    let rect = texture_rect
+                        .unwrap_or_else(|| Rect::from_corners(Vec2::ZERO, image.size_f32()));
+                    if alpha_at(image, rect, uv).is_some_and(|alpha| alpha <= threshold) {
+                        continue;
+                    }
+                }
+                hits.push((
+                    *order,
+                    SpriteHit {
+                        entity,
+                        camera: *camera,
+                        position: ray.get_point(depth),
+                        depth,
+                    },
+                ));
+            }
+        }
+        hits.sort_by(|(order_a, a), (order_b, b)| {
+            order_b
+                .cmp(order_a)
+                .then_with(|| a.depth.total_cmp(&b.depth))
+        });
+        sprite_hits
+            .pointers
+            .insert(pointer, hits.into_iter().map(|(_, hit)| hit).collect());
+    }
+}
+
+/// Returns the region of the image drawn by the sprite, like the sprite extraction does.
+fn texture_rect(
+    sprite: &Sprite,
+    atlas: Option<&TextureAtlas>,
+    texture_atlases: &Assets<TextureAtlasLayout>,
+) -> Option<Rect> {
+    let atlas_rect = atlas.and_then(|atlas| atlas.texture_rect(texture_atlases));
+    match (atlas_rect, sprite.rect) {
+        (None, None) => None,
+        (None, Some(sprite_rect)) => Some(sprite_rect),
+        (Some(atlas_rect), None) => Some(atlas_rect.as_rect()),
+        (Some(atlas_rect), Some(mut sprite_rect)) => {
+            sprite_rect.min += atlas_rect.min.as_vec2();
+            sprite_rect.max += atlas_rect.min.as_vec2();
+            Some(sprite_rect)
+        }
+    }
+}
+
+/// Intersects `ray` with the quad of a sprite of the given size and anchor.
+///
+/// Returns the distance along the ray and the texture coordinates of the hit, with `(0, 0)` at
+/// the top-left corner of the sprite, before flipping.
+fn hit_sprite(
+    ray: Ray3d,
+    transform: &GlobalTransform,
+    size: Vec2,
+    anchor: Vec2,
+) -> Option<(f32, Vec2)> {
+    let world_to_local = transform.affine().inverse();
+    let origin = world_to_local.transform_point3(ray.origin);
+    let direction = world_to_local.transform_vector3(*ray.direction);
+    // The ray is parallel to the sprite
+    if direction.z.abs() <= f32::EPSILON {
+        return None;
+    }
+    let distance = -origin.z / direction.z;
+    if distance < 0.0 {
+        return None;
+    }
+    let local = (origin + direction * distance).truncate();
+    // The quad of the sprite is translated by `size * (-anchor - 0.5)`
+    let uv = local / size + anchor + 0.5;
+    if !(0.0..=1.0).contains(&uv.x) || !(0.0..=1.0).contains(&uv.y) {
+        return None;
+    }
+    let position = transform.transform_point(local.extend(0.0));
+    let depth = (position - ray.origin).dot(*ray.direction);
+    Some((depth, Vec2::new(uv.x, 1.0 - uv.y)))
+}
+
+/// Returns the alpha of the pixel of `image` at the texture coordinates `uv` of `rect`, or `None`
+/// if the image format isn't supported.
+fn alpha_at(image: &Image, rect: Rect, uv: Vec2) -> Option<f32> {
+    let image_size = image.size();
+    if image_size.cmpeq(UVec2::ZERO).any() {
+        return None;
+    }
+    let pixel = (rect.min + uv * rect.size())
+        .floor()
+        .as_uvec2()
+        .min(image_size - 1);
+    let index = (pixel.y * image_size.x + pixel.x) as usize;
+    match image.texture_descriptor.format {
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bgra8Unorm
+        | TextureFormat::Bgra8UnormSrgb => image
+            .data
+            .get(index * 4 + 3)
+            .map(|alpha| *alpha as f32 / 255.0),
+        TextureFormat::Rgba32Float => image
+            .data
+            .get(index * 16 + 12..index * 16 + 16)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Dir3, Quat, Ray3d, Rect, Vec2, Vec3};
+    use bevy_render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    };
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::{alpha_at, hit_sprite};
+
+    fn ray_at(x: f32, y: f32) -> Ray3d {
+        Ray3d {
+            origin: Vec3::new(x, y, 100.0),
+            direction: Dir3::NEG_Z,
+        }
+    }
+
+    #[test]
+    fn hit_sprite_quad() {
+        let transform = GlobalTransform::from(Transform::from_xyz(10.0, 0.0, 5.0));
+        let size = Vec2::new(20.0, 10.0);
+
+        let (depth, uv) = hit_sprite(ray_at(10.0, 0.0), &transform, size, Vec2::ZERO).unwrap();
+        assert!((depth - 95.0).abs() < 1e-4);
+        assert!(uv.abs_diff_eq(Vec2::splat(0.5), 1e-5));
+
+        // Top-left corner of the sprite
+        let (_, uv) = hit_sprite(ray_at(1.0, 4.0), &transform, size, Vec2::ZERO).unwrap();
+        assert!(uv.abs_diff_eq(Vec2::new(0.05, 0.1), 1e-5));
+
+        assert!(hit_sprite(ray_at(21.0, 0.0), &transform, size, Vec2::ZERO).is_none());
+
+        // Anchored on its bottom-left corner, the sprite covers `(10, 0)..(30, 10)`
+        let anchor = Vec2::new(-0.5, -0.5);
+        assert!(hit_sprite(ray_at(25.0, 8.0), &transform, size, anchor).is_some());
+        assert!(hit_sprite(ray_at(5.0, 0.0), &transform, size, anchor).is_none());
+    }
+
+    #[test]
+    fn hit_anchored_sprite() {
+        let transform = GlobalTransform::from(Transform::from_xyz(10.0, 0.0, 5.0));
+        let size = Vec2::new(20.0, 10.0);
+
+        // Anchored on its top-right corner, the sprite covers `(-10, -10)..(10, 0)`
+        let anchor = Vec2::new(0.5, 0.5);
+        let (_, uv) = hit_sprite(ray_at(-5.0, -8.0), &transform, size, anchor).unwrap();
+        assert!(uv.abs_diff_eq(Vec2::new(0.25, 0.8), 1e-5));
+        assert!(hit_sprite(ray_at(15.0, 5.0), &transform, size, anchor).is_none());
+
+        // Anchored on the middle of its left side, the sprite covers `(10, -5)..(30, 5)`
+        let anchor = Vec2::new(-0.5, 0.0);
+        let (_, uv) = hit_sprite(ray_at(12.0, 4.0), &transform, size, anchor).unwrap();
+        assert!(uv.abs_diff_eq(Vec2::new(0.1, 0.1), 1e-5));
+        assert!(hit_sprite(ray_at(8.0, 0.0), &transform, size, anchor).is_none());
+    }
+
+    #[test]
+    fn hit_rotated_sprite() {
+        let transform = GlobalTransform::from(
+            Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::splat(2.0)),
+        );
+        // Rotated by 90 degrees and scaled, the sprite covers `(-2, -20)..(2, 20)`
+        let size = Vec2::new(20.0, 2.0);
+        assert!(hit_sprite(ray_at(1.0, 15.0), &transform, size, Vec2::ZERO).is_some());
+        assert!(hit_sprite(ray_at(15.0, 1.0), &transform, size, Vec2::ZERO).is_none());
+    }
+
+    #[test]
+    fn alpha_threshold() {
+        // A 2x1 image, with an opaque left pixel and a transparent right pixel
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![255, 255, 255, 255, 255, 255, 255, 0],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        let rect = Rect::new(0.0, 0.0, 2.0, 1.0);
+        assert_eq!(alpha_at(&image, rect, Vec2::new(0.25, 0.5)), Some(1.0));
+        assert_eq!(alpha_at(&image, rect, Vec2::new(0.75, 0.5)), Some(0.0));
+        assert_eq!(alpha_at(&image, rect, Vec2::ONE), Some(0.0));
+
+        let right_pixel = Rect::new(1.0, 0.0, 2.0, 1.0);
+        assert_eq!(alpha_at(&image, right_pixel, Vec2::ZERO), Some(0.0));
+    }
+}
@@ -0,0 +1,584 @@
+//! GPU frustum culling of sprites.
+//!
+//! The sprites seen by the cameras with [`GpuCulling`](bevy_render::view::GpuCulling) are
+//! batched on the CPU as usual, but their instances aren't built on the CPU: the extracted data of
+//! each sprite is uploaded as is, and a compute shader builds the instances, removes the ones
+//! outside of the frustum of their view, and writes the number of visible instances to the
+//! indirect parameters of their batch. Adding [`NoCpuCulling`](bevy_render::view::NoCpuCulling)
+//! to the camera skips the CPU culling, so that only the GPU culls the sprites. The sprites are
+//! still extracted, sorted and batched on the CPU.
+//!
+//! The instances are culled in chunks of [`SPRITE_CULLING_WORKGROUP_SIZE`] sprites, in three
+//! passes so that the visible sprites keep their order and transparent sprites are still drawn
+//! back to front:
+//! - `count_visible` counts the visible sprites of each chunk.
+//! - `scan_chunks` turns the counts of the chunks of each batch into the offsets of their visible
+//!   sprites, and writes the number of visible sprites of the batch.
+//! - `write_visible` writes the instances of the visible sprites of each chunk at its offset.
+//!
+//! This requires compute shaders and indirect draws with a first instance, see
+//! [`GpuPreprocessingSupport`](bevy_render::batching::gpu_preprocessing::GpuPreprocessingSupport).
+//! On other platforms, `GpuCulling` is ignored and the sprites are only culled on the CPU.
+
+use std::{mem, num::NonZeroU64, ops::Range};
+
+use bevy_asset::Handle;
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    system::{Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::{Rect, Vec2, Vec4};
+use bevy_render::{
+    batching::gpu_preprocessing::IndirectParameters,
+    primitives::Frustum,
+    render_graph::{Node, NodeRunError, RenderGraphContext, RenderLabel},
+    render_resource::{
+        binding_types::{storage_buffer, storage_buffer_read_only_sized, storage_buffer_sized},
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, Buffer,
+        BufferBinding, BufferDescriptor, BufferSize, BufferUsages, CachedComputePipelineId,
+        ComputePass, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor,
+        PipelineCache, RawBufferVec, Shader, ShaderStages,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+};
+use bytemuck::{Pod, Zeroable};
+
+use super::{ExtractedSprite, SpriteEffect, SpriteInstance};
+use crate::SpriteColorSpace;
+
+/// The handle to the `sprite_culling.wgsl` compute shader.
+pub const SPRITE_CULLING_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(6391110092661384172);
+
+/// The number of sprites culled by each workgroup of the culling shader.
+pub const SPRITE_CULLING_WORKGROUP_SIZE: u32 = 64;
+
+/// The maximum number of workgroups along each dimension of a dispatch, the default
+/// `max_compute_workgroups_per_dimension` limit of wgpu.
+const MAX_WORKGROUPS_PER_DIMENSION: u32 = 65535;
+
+/// The render graph node culling the sprites on the GPU, run once per frame before the cameras.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct SpriteCullingLabel;
+
+/// The extracted data of a sprite culled on the GPU, from which the shader builds its
+/// [`SpriteInstance`].
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct CulledSprite {
+    /// The affine transform of the sprite, transposed to 3x4.
+    model_transpose: [Vec4; 3],
+    color: [f32; 4],
+    /// The area of the image displayed by the sprite, in pixels.
+    rect: [f32; 4],
+    /// The UV offset of the sprite, followed by its UV scale.
+    uv_offset_scale: [f32; 4],
+    /// The custom size of the sprite, followed by its anchor.
+    custom_size_anchor: [f32; 4],
+    /// The size of the image of the sprite, followed by the thickness of its outline.
+    image_size_outline: [f32; 4],
+    /// See the `CULLED_SPRITE_FLAGS_*` constants.
+    flags: u32,
+    texture_index: u32,
+    alpha_cutoff: f32,
+    emissive: f32,
+}
+
+const CULLED_SPRITE_FLAGS_FLIP_X: u32 = 1 << 0;
+const CULLED_SPRITE_FLAGS_FLIP_Y: u32 = 1 << 1;
+const CULLED_SPRITE_FLAGS_CUSTOM_SIZE: u32 = 1 << 2;
+/// The color of the sprite is converted to sRGB, see [`SpriteColorSpace::Srgb`].
+const CULLED_SPRITE_FLAGS_SRGB_COLOR: u32 = 1 << 3;
+
+/// A batch of sprites culled by the shader.
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct CulledSpriteBatch {
+    first_instance: u32,
+    instance_count: u32,
+    /// The first chunk of [`SPRITE_CULLING_WORKGROUP_SIZE`] sprites of the batch.
+    first_chunk: u32,
+    chunk_count: u32,
+    /// The index of the frustum of the view of the batch.
+    view_index: u32,
+}
+
+/// The half spaces of the [`Frustum`] of a view culled on the GPU.
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct SpriteCullingFrustum {
+    half_spaces: [Vec4; 6],
+}
+
+/// The buffers of the sprites culled on the GPU, filled while the sprites are batched.
+#[derive(Resource)]
+pub struct SpriteCullingBuffers {
+    sprites: RawBufferVec<CulledSprite>,
+    batches: RawBufferVec<CulledSpriteBatch>,
+    /// The index of the batch of each chunk of [`SPRITE_CULLING_WORKGROUP_SIZE`] sprites.
+    chunks: RawBufferVec<u32>,
+    frusta: RawBufferVec<SpriteCullingFrustum>,
+    /// The index of the frustum of each view in `frusta`, so that the views with several render
+    /// phases only push their frustum once.
+    view_indices: EntityHashMap<u32>,
+    /// The indirect parameters of each culled batch, in the same order as `batches`.
+    indirect_parameters: RawBufferVec<IndirectParameters>,
+    /// The visible instances of the culled batches, each starting at the same index as the
+    /// batch in `sprites`.
+    culled_instances: Option<Buffer>,
+    /// The number of visible sprites of each chunk, then the offset of the visible sprites of the
+    /// chunk in its batch. Only used by the shader.
+    chunk_offsets: Option<Buffer>,
+    bind_group: Option<BindGroup>,
+}
+
+impl Default for SpriteCullingBuffers {
+    fn default() -> Self {
+        Self {
+            sprites: RawBufferVec::new(BufferUsages::STORAGE),
+            batches: RawBufferVec::new(BufferUsages::STORAGE),
+            chunks: RawBufferVec::new(BufferUsages::STORAGE),
+            frusta: RawBufferVec::new(BufferUsages::STORAGE),
+            view_indices: EntityHashMap::default(),
+            indirect_parameters: RawBufferVec::new(BufferUsages::STORAGE | BufferUsages::INDIRECT),
+            culled_instances: None,
+            chunk_offsets: None,
+            bind_group: None,
+        }
+    }
+}
+
+impl SpriteCullingBuffers {
+    pub(crate) fn clear(&mut self) {
+        self.sprites.clear();
+        self.batches.clear();
+        self.chunks.clear();
+        self.frusta.clear();
+        self.view_indices.clear();
+        self.indirect_parameters.clear();
+    }
+
+    /// Adds the frustum of a view culled on the GPU if it wasn't added yet, and returns its
+    /// index.
+    pub(crate) fn push_view(&mut self, view: Entity, frustum: &Frustum) -> u32 {
+        let frusta = &mut self.frusta;
+        *self.view_indices.entry(view).or_insert_with(|| {
+            frusta.push(SpriteCullingFrustum {
+                half_spaces: frustum.half_spaces.map(|half_space| half_space.normal_d()),
+            }) as u32
+        })
+    }
+
+    /// Adds a batch in the view `view_index`, starting at the next sprite, and returns its index
+    /// and its first sprite. Its sprites are set by [`SpriteCullingBuffers::set_batch_range`].
+    pub(crate) fn push_batch(&mut self, view_index: u32) -> (u32, u32) {
+        let first_instance = self.sprites.len() as u32;
+        self.indirect_parameters.push(IndirectParameters {
+            vertex_or_index_count: 6,
+            instance_count: 0,
+            first_vertex: 0,
+            base_vertex_or_first_instance: 0,
+            first_instance,
+        });
+        let index = self.batches.push(CulledSpriteBatch {
+            first_instance,
+            instance_count: 0,
+            first_chunk: 0,
+            chunk_count: 0,
+            view_index,
+        }) as u32;
+        (index, first_instance)
+    }
+
+    /// Adds a sprite to the last batch. `rect` is the area of the image displayed by the sprite
+    /// and `image_size` the size of the image, in pixels.
+    pub(crate) fn push_sprite(
+        &mut self,
+        sprite: &ExtractedSprite,
+        rect: Rect,
+        image_size: Vec2,
+        color_space: SpriteColorSpace,
+        texture_index: u32,
+        alpha_cutoff: f32,
+    ) {
+        let transform = sprite.transform.affine();
+        let transpose_model_3x3 = transform.matrix3.transpose();
+        let outline = match sprite.effect {
+            SpriteEffect::Outline { thickness } => Vec2::splat(thickness.max(0.0)),
+            SpriteEffect::None | SpriteEffect::Silhouette => Vec2::ZERO,
+        };
+        let custom_size = sprite.custom_size.unwrap_or(Vec2::ZERO);
+
+        let mut flags = 0;
+        if sprite.flip_x {
+            flags |= CULLED_SPRITE_FLAGS_FLIP_X;
+        }
+        if sprite.flip_y {
+            flags |= CULLED_SPRITE_FLAGS_FLIP_Y;
+        }
+        if sprite.custom_size.is_some() {
+            flags |= CULLED_SPRITE_FLAGS_CUSTOM_SIZE;
+        }
+        if color_space == SpriteColorSpace::Srgb {
+            flags |= CULLED_SPRITE_FLAGS_SRGB_COLOR;
+        }
+
+        self.sprites.push(CulledSprite {
+            model_transpose: [
+                transpose_model_3x3.x_axis.extend(transform.translation.x),
+                transpose_model_3x3.y_axis.extend(transform.translation.y),
+                transpose_model_3x3.z_axis.extend(transform.translation.z),
+            ],
+            color: sprite.color.to_f32_array(),
+            rect: [rect.min.x, rect.min.y, rect.max.x, rect.max.y],
+            uv_offset_scale: [
+                sprite.uv_offset.x,
+                sprite.uv_offset.y,
+                sprite.uv_scale.x,
+                sprite.uv_scale.y,
+            ],
+            custom_size_anchor: [
+                custom_size.x,
+                custom_size.y,
+                sprite.anchor.x,
+                sprite.anchor.y,
+            ],
+            image_size_outline: [image_size.x, image_size.y, outline.x, outline.y],
+            flags,
+            texture_index,
+            alpha_cutoff,
+            emissive: sprite.emissive,
+        });
+    }
+
+    /// Sets the sprites of the batch at `index` once they are all known, and splits them in
+    /// chunks of [`SPRITE_CULLING_WORKGROUP_SIZE`] sprites.
+    pub(crate) fn set_batch_range(&mut self, index: u32, range: &Range<u32>) {
+        let instance_count = range.end - range.start;
+        let chunk_count = instance_count.div_ceil(SPRITE_CULLING_WORKGROUP_SIZE);
+        let batch = &mut self.batches.values_mut()[index as usize];
+        batch.instance_count = instance_count;
+        batch.first_chunk = self.chunks.len() as u32;
+        batch.chunk_count = chunk_count;
+        for _ in 0..chunk_count {
+            self.chunks.push(index);
+        }
+    }
+
+    /// Returns the buffer of the instances drawn by the culled batches.
+    pub(crate) fn culled_instances(&self) -> Option<&Buffer> {
+        self.culled_instances.as_ref()
+    }
+
+    /// Returns the buffer of indirect parameters, and the offset of the parameters of the batch
+    /// at `index`.
+    pub(crate) fn indirect_parameters(&self, index: u32) -> Option<(&Buffer, u64)> {
+        let offset = index as u64 * mem::size_of::<IndirectParameters>() as u64;
+        Some((self.indirect_parameters.buffer()?, offset))
+    }
+}
+
+/// Returns the number of workgroups to dispatch along x and y to run `count` workgroups, without
+/// exceeding the maximum number of workgroups along each dimension. The shader skips the
+/// workgroups past `count`.
+fn workgroup_grid(count: u32) -> (u32, u32) {
+    if count <= MAX_WORKGROUPS_PER_DIMENSION {
+        (count, 1)
+    } else {
+        (
+            MAX_WORKGROUPS_PER_DIMENSION,
+            count.div_ceil(MAX_WORKGROUPS_PER_DIMENSION),
+        )
+    }
+}
+
+/// Creates a storage buffer of at least `size` bytes in `buffer`, unless it is already large
+/// enough.
+fn reserve_buffer(
+    buffer: &mut Option<Buffer>,
+    render_device: &RenderDevice,
+    label: &'static str,
+    size: u64,
+    usage: BufferUsages,
+) {
+    if buffer.as_ref().map_or(true, |buffer| buffer.size() < size) {
+        *buffer = Some(render_device.create_buffer(&BufferDescriptor {
+            label: Some(label),
+            size,
+            usage,
+            mapped_at_creation: false,
+        }));
+    }
+}
+
+/// Binds the first `len` elements of a buffer, instead of the whole buffer which can be larger.
+fn binding<T>(buffer: &Buffer, len: usize) -> BufferBinding {
+    BufferBinding {
+        buffer,
+        offset: 0,
+        size: BufferSize::new((len * mem::size_of::<T>()) as u64),
+    }
+}
+
+/// The compute pipelines of the `sprite_culling.wgsl` shader.
+#[derive(Resource)]
+pub struct SpriteCullingPipeline {
+    layout: BindGroupLayout,
+    count_visible_id: CachedComputePipelineId,
+    scan_chunks_id: CachedComputePipelineId,
+    write_visible_id: CachedComputePipelineId,
+}
+
+impl FromWorld for SpriteCullingPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "sprite_culling_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    // `sprites`
+                    storage_buffer_read_only_sized(
+                        false,
+                        NonZeroU64::new(mem::size_of::<CulledSprite>() as u64),
+                    ),
+                    // `culled_instances`
+                    storage_buffer_sized(
+                        false,
+                        NonZeroU64::new(mem::size_of::<SpriteInstance>() as u64),
+                    ),
+                    // `batches`
+                    storage_buffer_read_only_sized(
+                        false,
+                        NonZeroU64::new(mem::size_of::<CulledSpriteBatch>() as u64),
+                    ),
+                    // `chunks`
+                    storage_buffer_read_only_sized(false, NonZeroU64::new(4)),
+                    // `chunk_offsets`
+                    storage_buffer_sized(false, NonZeroU64::new(4)),
+                    // `frusta`
+                    storage_buffer_read_only_sized(
+                        false,
+                        NonZeroU64::new(mem::size_of::<SpriteCullingFrustum>() as u64),
+                    ),
+                    // `indirect_parameters`
+                    storage_buffer::<IndirectParameters>(false),
+                ),
+            ),
+        );
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let mut queue_pipeline = |label: &'static str, entry_point: &'static str| {
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(label.into()),
+                layout: vec![layout.clone()],
+                push_constant_ranges: vec![],
+                shader: SPRITE_CULLING_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: entry_point.into(),
+            })
+        };
+        let count_visible_id =
+            queue_pipeline("sprite_culling_count_visible_pipeline", "count_visible");
+        let scan_chunks_id = queue_pipeline("sprite_culling_scan_chunks_pipeline", "scan_chunks");
+        let write_visible_id =
+            queue_pipeline("sprite_culling_write_visible_pipeline", "write_visible");
+
+        Self {
+            layout,
+            count_visible_id,
+            scan_chunks_id,
+            write_visible_id,
+        }
+    }
+}
+
+/// Writes the buffers of the culled sprites to the GPU and creates their bind group, after the
+/// sprites are batched.
+pub fn prepare_sprite_culling_bind_group(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    culling_pipeline: Res<SpriteCullingPipeline>,
+    mut culling_buffers: ResMut<SpriteCullingBuffers>,
+) {
+    let culling_buffers = &mut *culling_buffers;
+    culling_buffers.bind_group = None;
+    if culling_buffers.sprites.is_empty() {
+        return;
+    }
+
+    reserve_buffer(
+        &mut culling_buffers.culled_instances,
+        &render_device,
+        "sprite_culled_instance_buffer",
+        (culling_buffers.sprites.len() * mem::size_of::<SpriteInstance>()) as u64,
+        BufferUsages::STORAGE | BufferUsages::VERTEX,
+    );
+    reserve_buffer(
+        &mut culling_buffers.chunk_offsets,
+        &render_device,
+        "sprite_culling_chunk_offset_buffer",
+        (culling_buffers.chunks.len() * mem::size_of::<u32>()) as u64,
+        BufferUsages::STORAGE,
+    );
+
+    culling_buffers
+        .sprites
+        .write_buffer(&render_device, &render_queue);
+    culling_buffers
+        .batches
+        .write_buffer(&render_device, &render_queue);
+    culling_buffers
+        .chunks
+        .write_buffer(&render_device, &render_queue);
+    culling_buffers
+        .frusta
+        .write_buffer(&render_device, &render_queue);
+    culling_buffers
+        .indirect_parameters
+        .write_buffer(&render_device, &render_queue);
+
+    let (
+        Some(sprites),
+        Some(culled_instances),
+        Some(batches),
+        Some(chunks),
+        Some(chunk_offsets),
+        Some(frusta),
+        Some(indirect_parameters),
+    ) = (
+        culling_buffers.sprites.buffer(),
+        culling_buffers.culled_instances.as_ref(),
+        culling_buffers.batches.buffer(),
+        culling_buffers.chunks.buffer(),
+        culling_buffers.chunk_offsets.as_ref(),
+        culling_buffers.frusta.buffer(),
+        culling_buffers.indirect_parameters.buffer(),
+    )
+    else {
+        return;
+    };
+    let chunk_count = culling_buffers.chunks.len();
+    culling_buffers.bind_group = Some(render_device.create_bind_group(
+        "sprite_culling_bind_group",
+        &culling_pipeline.layout,
+        &BindGroupEntries::sequential((
+            sprites.as_entire_binding(),
+            culled_instances.as_entire_binding(),
+            // The shader reads the number of batches and chunks from the length of their arrays
+            binding::<CulledSpriteBatch>(batches, culling_buffers.batches.len()),
+            binding::<u32>(chunks, chunk_count),
+            binding::<u32>(chunk_offsets, chunk_count),
+            frusta.as_entire_binding(),
+            indirect_parameters.as_entire_binding(),
+        )),
+    ));
+}
+
+/// Runs the culling shader on every batch of sprites seen by a view culled on the GPU.
+#[derive(Default)]
+pub struct SpriteCullingNode;
+
+impl Node for SpriteCullingNode {
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let culling_buffers = world.resource::<SpriteCullingBuffers>();
+        let culling_pipeline = world.resource::<SpriteCullingPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(bind_group) = &culling_buffers.bind_group else {
+            return Ok(());
+        };
+        let (Some(count_visible), Some(scan_chunks), Some(write_visible)) = (
+            pipeline_cache.get_compute_pipeline(culling_pipeline.count_visible_id),
+            pipeline_cache.get_compute_pipeline(culling_pipeline.scan_chunks_id),
+            pipeline_cache.get_compute_pipeline(culling_pipeline.write_visible_id),
+        ) else {
+            // This will happen while the pipelines are being compiled and is fine.
+            return Ok(());
+        };
+
+        let mut compute_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("sprite_culling"),
+                    timestamp_writes: None,
+                });
+        compute_pass.set_bind_group(0, bind_group, &[]);
+        let chunk_count = culling_buffers.chunks.len() as u32;
+        let batch_count = culling_buffers.batches.len() as u32;
+        // Each workgroup counts the visible sprites of a chunk
+        dispatch(&mut compute_pass, count_visible, chunk_count);
+        // Each workgroup computes the offsets of the chunks of a batch
+        dispatch(&mut compute_pass, scan_chunks, batch_count);
+        // Each workgroup writes the visible sprites of a chunk
+        dispatch(&mut compute_pass, write_visible, chunk_count);
+
+        Ok(())
+    }
+}
+
+/// Runs `count` workgroups of `pipeline`.
+fn dispatch(compute_pass: &mut ComputePass, pipeline: &ComputePipeline, count: u32) {
+    let (x, y) = workgroup_grid(count);
+    compute_pass.set_pipeline(pipeline);
+    compute_pass.dispatch_workgroups(x, y, 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workgroup_grid_fits_in_dispatch_limits() {
+        assert_eq!(workgroup_grid(0), (0, 1));
+        assert_eq!(workgroup_grid(1000), (1000, 1));
+        assert_eq!(workgroup_grid(65535), (65535, 1));
+        assert_eq!(workgroup_grid(65536), (65535, 2));
+
+        // A million sprites in a single batch
+        let chunks = 1_000_000u32.div_ceil(SPRITE_CULLING_WORKGROUP_SIZE) * 100;
+        let (x, y) = workgroup_grid(chunks);
+        assert!(x <= MAX_WORKGROUPS_PER_DIMENSION && y <= MAX_WORKGROUPS_PER_DIMENSION);
+        assert!(x * y >= chunks);
+    }
+
+    #[test]
+    fn views_push_their_frustum_once() {
+        let mut buffers = SpriteCullingBuffers::default();
+        let (first, second) = (Entity::from_raw(1), Entity::from_raw(2));
+        let frustum = Frustum::default();
+
+        // The opaque and transparent phases of a view share its frustum
+        assert_eq!(buffers.push_view(first, &frustum), 0);
+        assert_eq!(buffers.push_view(first, &frustum), 0);
+        assert_eq!(buffers.push_view(second, &frustum), 1);
+        assert_eq!(buffers.frusta.len(), 2);
+
+        buffers.clear();
+        assert_eq!(buffers.push_view(second, &frustum), 0);
+    }
+
+    #[test]
+    fn batches_are_split_in_chunks() {
+        let mut buffers = SpriteCullingBuffers::default();
+        let (first, first_instance) = buffers.push_batch(0);
+        assert_eq!(first_instance, 0);
+        let (second, _) = buffers.push_batch(0);
+        let (third, _) = buffers.push_batch(0);
+        buffers.set_batch_range(first, &(0..130));
+        buffers.set_batch_range(second, &(130..130));
+        buffers.set_batch_range(third, &(130..194));
+
+        let batches = buffers.batches.values();
+        assert_eq!((batches[0].first_chunk, batches[0].chunk_count), (0, 3));
+        assert_eq!(batches[1].chunk_count, 0);
+        assert_eq!((batches[2].first_chunk, batches[2].chunk_count), (3, 1));
+        assert_eq!(buffers.chunks.values(), &[0, 0, 0, 2]);
+    }
+}
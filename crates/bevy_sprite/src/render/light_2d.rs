@@ -0,0 +1,249 @@
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec4;
+use bevy_render::{
+    render_resource::{DynamicUniformBuffer, ShaderType},
+    renderer::{RenderDevice, RenderQueue},
+    view::{ExtractedView, InheritedVisibility, RenderLayers},
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::tracing::warn;
+
+use crate::{AmbientLight2d, PointLight2d};
+
+/// The maximum number of [`PointLight2d`]s shading the sprites.
+///
+// NOTE: this must be kept in sync with the size of the array in `sprite_view_bindings.wgsl`
+pub const MAX_POINT_LIGHTS_2D: usize = 64;
+
+pub struct ExtractedPointLight2d {
+    /// The position of the light on the plane of the sprites, its height above them, and its
+    /// radius
+    pub position_height_radius: Vec4,
+    /// The color of the light, multiplied by its intensity
+    pub color: LinearRgba,
+    /// The layers of the cameras lit by the light
+    pub render_layers: RenderLayers,
+}
+
+#[derive(Resource, Default)]
+pub struct ExtractedPointLights2d {
+    pub lights: Vec<ExtractedPointLight2d>,
+}
+
+pub fn extract_point_lights_2d(
+    mut extracted_point_lights: ResMut<ExtractedPointLights2d>,
+    point_lights: Extract<
+        Query<(
+            &PointLight2d,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&RenderLayers>,
+        )>,
+    >,
+) {
+    extracted_point_lights.lights.clear();
+    for (point_light, transform, inherited_visibility, render_layers) in &point_lights {
+        if !inherited_visibility.get() {
+            continue;
+        }
+
+        let position = transform.translation().truncate();
+        extracted_point_lights.lights.push(ExtractedPointLight2d {
+            position_height_radius: Vec4::new(
+                position.x,
+                position.y,
+                point_light.height,
+                point_light.radius,
+            ),
+            color: LinearRgba::from(point_light.color) * point_light.intensity,
+            render_layers: render_layers.unwrap_or_default().clone(),
+        });
+    }
+}
+
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuPointLight2d {
+    position_height_radius: Vec4,
+    color: Vec4,
+}
+
+/// The lights shading the sprites of a view, sent as a uniform to the shader.
+#[derive(Clone, ShaderType, Debug)]
+pub struct GpuLights2d {
+    point_lights: [GpuPointLight2d; MAX_POINT_LIGHTS_2D],
+    ambient_color: Vec4,
+    point_light_count: u32,
+}
+
+impl Default for GpuLights2d {
+    fn default() -> Self {
+        Self {
+            point_lights: [GpuPointLight2d::default(); MAX_POINT_LIGHTS_2D],
+            ambient_color: Vec4::ONE,
+            point_light_count: 0,
+        }
+    }
+}
+
+impl GpuLights2d {
+    /// Returns the lights of a view with `ambient_light`, from the `point_lights` sharing a layer
+    /// with the view, and whether point lights after the first [`MAX_POINT_LIGHTS_2D`] were
+    /// ignored.
+    fn new(
+        ambient_light: &AmbientLight2d,
+        view_layers: &RenderLayers,
+        point_lights: &[ExtractedPointLight2d],
+    ) -> (Self, bool) {
+        let mut gpu_lights = GpuLights2d {
+            ambient_color: (LinearRgba::from(ambient_light.color) * ambient_light.brightness)
+                .to_vec4(),
+            ..Default::default()
+        };
+        let mut point_lights = point_lights
+            .iter()
+            .filter(|point_light| view_layers.intersects(&point_light.render_layers));
+        for (gpu_point_light, point_light) in
+            gpu_lights.point_lights.iter_mut().zip(&mut point_lights)
+        {
+            *gpu_point_light = GpuPointLight2d {
+                position_height_radius: point_light.position_height_radius,
+                color: point_light.color.to_vec4(),
+            };
+            gpu_lights.point_light_count += 1;
+        }
+        let truncated = point_lights.next().is_some();
+        (gpu_lights, truncated)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Lights2dMeta {
+    pub gpu_lights: DynamicUniformBuffer<GpuLights2d>,
+}
+
+/// Inserted on views to index their lights in [`Lights2dMeta`].
+#[derive(Component)]
+pub struct ViewLights2dUniformOffset {
+    pub offset: u32,
+}
+
+/// Writes the lights of every view to the GPU. Views without an [`AmbientLight2d`] share default
+/// lights, since the sprite view bind group is shared by lit and unlit pipelines.
+pub fn prepare_lights_2d(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut lights_meta: ResMut<Lights2dMeta>,
+    mut max_point_lights_warning_emitted: Local<bool>,
+    extracted_point_lights: Res<ExtractedPointLights2d>,
+    views: Query<(Entity, Option<&AmbientLight2d>, Option<&RenderLayers>), With<ExtractedView>>,
+) {
+    if views.is_empty() {
+        return;
+    }
+    let lit_view_count = views
+        .iter()
+        .filter(|(_, ambient_light, _)| ambient_light.is_some())
+        .count();
+    let Some(mut writer) =
+        lights_meta
+            .gpu_lights
+            .get_writer(lit_view_count + 1, &render_device, &render_queue)
+    else {
+        return;
+    };
+
+    let unlit_offset = writer.write(&GpuLights2d::default());
+    for (entity, ambient_light, render_layers) in &views {
+        let offset = match ambient_light {
+            Some(ambient_light) => {
+                let (gpu_lights, truncated) = GpuLights2d::new(
+                    ambient_light,
+                    render_layers.unwrap_or_default(),
+                    &extracted_point_lights.lights,
+                );
+                if truncated && !*max_point_lights_warning_emitted {
+                    warn!(
+                        "MAX_POINT_LIGHTS_2D ({}) exceeded, the other 2D point lights are ignored",
+                        MAX_POINT_LIGHTS_2D
+                    );
+                    *max_point_lights_warning_emitted = true;
+                }
+                writer.write(&gpu_lights)
+            }
+            None => unlit_offset,
+        };
+        commands
+            .entity(entity)
+            .insert(ViewLights2dUniformOffset { offset });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::{Color, LinearRgba};
+    use bevy_math::Vec4;
+    use bevy_render::view::RenderLayers;
+
+    use super::{ExtractedPointLight2d, GpuLights2d, MAX_POINT_LIGHTS_2D};
+    use crate::AmbientLight2d;
+
+    fn point_light(x: f32, render_layers: RenderLayers) -> ExtractedPointLight2d {
+        ExtractedPointLight2d {
+            position_height_radius: Vec4::new(x, 0.0, 32.0, 256.0),
+            color: LinearRgba::WHITE,
+            render_layers,
+        }
+    }
+
+    #[test]
+    fn views_are_lit_by_lights_on_their_layers() {
+        let ambient_light = AmbientLight2d {
+            color: Color::WHITE,
+            brightness: 0.5,
+        };
+        let point_lights = [
+            point_light(0.0, RenderLayers::default()),
+            point_light(1.0, RenderLayers::layer(1)),
+            point_light(2.0, RenderLayers::from_layers(&[0, 1])),
+        ];
+
+        let (gpu_lights, truncated) =
+            GpuLights2d::new(&ambient_light, &RenderLayers::default(), &point_lights);
+        assert!(!truncated);
+        assert_eq!(gpu_lights.ambient_color, Vec4::new(0.5, 0.5, 0.5, 0.5));
+        assert_eq!(gpu_lights.point_light_count, 2);
+        assert_eq!(gpu_lights.point_lights[0].position_height_radius.x, 0.0);
+        assert_eq!(gpu_lights.point_lights[1].position_height_radius.x, 2.0);
+
+        let (gpu_lights, _) =
+            GpuLights2d::new(&ambient_light, &RenderLayers::layer(1), &point_lights);
+        assert_eq!(gpu_lights.point_light_count, 2);
+        assert_eq!(gpu_lights.point_lights[0].position_height_radius.x, 1.0);
+    }
+
+    #[test]
+    fn extra_point_lights_are_ignored() {
+        let point_lights: Vec<_> = (0..MAX_POINT_LIGHTS_2D + 1)
+            .map(|i| point_light(i as f32, RenderLayers::default()))
+            .collect();
+
+        let (gpu_lights, truncated) = GpuLights2d::new(
+            &AmbientLight2d::default(),
+            &RenderLayers::default(),
+            &point_lights[..MAX_POINT_LIGHTS_2D],
+        );
+        assert!(!truncated);
+        assert_eq!(gpu_lights.point_light_count as usize, MAX_POINT_LIGHTS_2D);
+
+        let (gpu_lights, truncated) = GpuLights2d::new(
+            &AmbientLight2d::default(),
+            &RenderLayers::default(),
+            &point_lights,
+        );
+        assert!(truncated);
+        assert_eq!(gpu_lights.point_light_count as usize, MAX_POINT_LIGHTS_2D);
+    }
+}
@@ -0,0 +1,456 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{Asset, AssetApp, AssetServer, Handle};
+use bevy_core_pipeline::{
+    core_2d::Transparent2d,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::FloatOrd;
+use bevy_render::{
+    render_asset::{
+        prepare_assets, PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets,
+    },
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+    },
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroup, BindGroupLayout, OwnedBindingResource,
+        PipelineCache, RenderPipelineDescriptor, Shader, ShaderRef, SpecializedRenderPipeline,
+        SpecializedRenderPipelines,
+    },
+    renderer::RenderDevice,
+    texture::{FallbackImage, GpuImage},
+    view::{ExtractedView, Msaa, VisibleEntities},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use fixedbitset::FixedBitSet;
+
+use super::{
+    queue_sprites, sprite_view_key, DrawSpriteBatch, ExtractedSprites, SetSpriteTextureBindGroup,
+    SetSpriteViewBindGroup, SpriteBatches, SpriteEffect, SpritePipeline, SpritePipelineKey,
+};
+use crate::{Sprite, SpriteColorSpace, SpritePixelSnap, SpriteSystem, WithSprite};
+
+/// Sprite materials replace the built-in fragment shader of the [`Sprite`]s they are added to,
+/// while keeping the sprite's quad, batching and texture.
+///
+/// Add a [`SpriteMaterialPlugin`] for each material type, then insert a `Handle<M>` next to a
+/// [`Sprite`] to draw it with the material. The outlines and shadows of the sprite are still
+/// drawn by the built-in shader.
+///
+/// Sprite materials must implement [`AsBindGroup`] to define how data will be transferred to the
+/// GPU and bound in shaders, like [`Material2d`](crate::Material2d)s.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_sprite::{SpriteBundle, SpriteMaterial};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_render::render_resource::{AsBindGroup, ShaderRef};
+/// # use bevy_color::LinearRgba;
+/// # use bevy_asset::{AssetServer, Assets, Asset};
+///
+/// #[derive(AsBindGroup, Debug, Clone, Asset, TypePath)]
+/// pub struct FlashMaterial {
+///     #[uniform(0)]
+///     flash_color: LinearRgba,
+/// }
+///
+/// impl SpriteMaterial for FlashMaterial {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/flash_material.wgsl".into()
+///     }
+/// }
+///
+/// fn setup(
+///     mut commands: Commands,
+///     mut materials: ResMut<Assets<FlashMaterial>>,
+///     asset_server: Res<AssetServer>,
+/// ) {
+///     commands.spawn((
+///         SpriteBundle {
+///             texture: asset_server.load("player.png"),
+///             ..Default::default()
+///         },
+///         materials.add(FlashMaterial {
+///             flash_color: LinearRgba::WHITE,
+///         }),
+///     ));
+/// }
+/// ```
+/// The fragment shader receives the output of the sprite vertex shader, and can sample the image
+/// of the sprite. The material's bindings are in group 2:
+///
+/// ```wgsl
+/// #import bevy_sprite::{
+///     sprite_texture_bindings::{sprite_texture, sprite_sampler},
+///     sprite_vertex_output::VertexOutput,
+/// }
+///
+/// @group(2) @binding(0) var<uniform> flash_color: vec4<f32>;
+///
+/// @fragment
+/// fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+///     let color = textureSample(sprite_texture, sprite_sampler, in.uv);
+///     return vec4(mix(color.rgb, flash_color.rgb, flash_color.a), color.a) * in.color;
+/// }
+/// ```
+///
+/// The fragment shader is responsible for tonemapping and debanding when the
+/// `TONEMAP_IN_SHADER` and `DEBAND_DITHER` shader defs are set, as in `sprite.wgsl`.
+pub trait SpriteMaterial: AsBindGroup + Asset + Clone + Sized {
+    /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the
+    /// built-in sprite fragment shader will be used.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Customizes the default [`RenderPipelineDescriptor`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: SpriteMaterialKey<Self>) {}
+}
+
+/// Adds the necessary ECS resources and render logic to enable rendering [`Sprite`]s with a
+/// [`SpriteMaterial`].
+pub struct SpriteMaterialPlugin<M: SpriteMaterial>(PhantomData<M>);
+
+impl<M: SpriteMaterial> Default for SpriteMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: SpriteMaterial> Plugin for SpriteMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        app.init_asset::<M>()
+            .add_plugins(RenderAssetPlugin::<PreparedSpriteMaterial<M>>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<Transparent2d, DrawSpriteMaterial<M>>()
+                .init_resource::<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>()
+                .add_systems(
+                    ExtractSchedule,
+                    extract_sprite_materials::<M>.after(SpriteSystem::ExtractSprites),
+                )
+                .add_systems(
+                    Render,
+                    queue_sprite_materials::<M>
+                        .in_set(RenderSet::Queue)
+                        .after(prepare_assets::<PreparedSpriteMaterial<M>>)
+                        .ambiguous_with(queue_sprites),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<SpriteMaterialPipeline<M>>();
+        }
+    }
+}
+
+/// Render pipeline data for a given [`SpriteMaterial`].
+#[derive(Resource)]
+pub struct SpriteMaterialPipeline<M: SpriteMaterial> {
+    pub sprite_pipeline: SpritePipeline,
+    pub sprite_material_layout: BindGroupLayout,
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<M>,
+}
+
+pub struct SpriteMaterialKey<M: SpriteMaterial> {
+    pub sprite_key: SpritePipelineKey,
+    pub bind_group_data: M::Data,
+}
+
+impl<M: SpriteMaterial> Eq for SpriteMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: SpriteMaterial> PartialEq for SpriteMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.sprite_key == other.sprite_key && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: SpriteMaterial> Clone for SpriteMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sprite_key: self.sprite_key,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: SpriteMaterial> Hash for SpriteMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.sprite_key.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+impl<M: SpriteMaterial> Clone for SpriteMaterialPipeline<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sprite_pipeline: self.sprite_pipeline.clone(),
+            sprite_material_layout: self.sprite_material_layout.clone(),
+            fragment_shader: self.fragment_shader.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: SpriteMaterial> SpecializedRenderPipeline for SpriteMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = SpriteMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.sprite_pipeline.specialize(key.sprite_key);
+        if let Some(fragment_shader) = &self.fragment_shader {
+            descriptor.fragment.as_mut().unwrap().shader = fragment_shader.clone();
+        }
+        descriptor.layout.push(self.sprite_material_layout.clone());
+        descriptor.label = Some("sprite_material_pipeline".into());
+
+        M::specialize(&mut descriptor, key);
+        descriptor
+    }
+}
+
+impl<M: SpriteMaterial> FromWorld for SpriteMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let sprite_material_layout = M::bind_group_layout(render_device);
+
+        SpriteMaterialPipeline {
+            sprite_pipeline: world.resource::<SpritePipeline>().clone(),
+            sprite_material_layout,
+            fragment_shader: match M::fragment_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+/// [`RenderCommand`] for sprites drawn with a [`SpriteMaterial`].
+pub type DrawSpriteMaterial<M> = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteTextureBindGroup<1>,
+    SetSpriteMaterialBindGroup<M, 2>,
+    DrawSpriteBatch,
+);
+
+pub struct SetSpriteMaterialBindGroup<M: SpriteMaterial, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: SpriteMaterial, const I: usize> RenderCommand<P>
+    for SetSpriteMaterialBindGroup<M, I>
+{
+    type Param = (
+        SRes<RenderAssets<PreparedSpriteMaterial<M>>>,
+        SRes<SpriteBatches>,
+    );
+    type ViewQuery = Entity;
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        view: Entity,
+        _entity: Option<()>,
+        (materials, sprite_batches): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let materials = materials.into_inner();
+        let Some(material_id) = sprite_batches
+            .into_inner()
+            .get(view, item.entity())
+            .and_then(|batch| batch.material)
+        else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(material) = material_id
+            .try_typed::<M>()
+            .ok()
+            .and_then(|id| materials.get(id))
+        else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, &material.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+/// Marks the extracted sprites whose entity has a `Handle<M>` as drawn with that material.
+pub fn extract_sprite_materials<M: SpriteMaterial>(
+    mut extracted_sprites: ResMut<ExtractedSprites>,
+    material_query: Extract<Query<&Handle<M>, With<Sprite>>>,
+) {
+    if material_query.is_empty() {
+        return;
+    }
+
+    for (entity, extracted_sprite) in extracted_sprites.sprites.iter_mut() {
+        // Outlines and shadows keep being drawn by the built-in shader
+        if extracted_sprite.effect != SpriteEffect::None {
+            continue;
+        }
+        let entity = extracted_sprite.original_entity.unwrap_or(*entity);
+        if let Ok(handle) = material_query.get(entity) {
+            extracted_sprite.material = Some(handle.id().untyped());
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_sprite_materials<M: SpriteMaterial>(
+    mut view_entities: Local<FixedBitSet>,
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    material_pipeline: Res<SpriteMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    color_space: Res<SpriteColorSpace>,
+    pixel_snap: Res<SpritePixelSnap>,
+    render_materials: Res<RenderAssets<PreparedSpriteMaterial<M>>>,
+    extracted_sprites: Res<ExtractedSprites>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut views: Query<(
+        Entity,
+        &VisibleEntities,
+        &ExtractedView,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    let mut msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
+    if *color_space == SpriteColorSpace::Srgb {
+        msaa_key |= SpritePipelineKey::SRGB_COLORS;
+    }
+    if *pixel_snap == SpritePixelSnap::Enabled {
+        msaa_key |= SpritePipelineKey::PIXEL_SNAP;
+    }
+
+    let draw_sprite_material_function = draw_functions.read().id::<DrawSpriteMaterial<M>>();
+
+    for (view_entity, visible_entities, view, tonemapping, dither) in &mut views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let view_key = sprite_view_key(view, tonemapping, dither) | msaa_key;
+
+        view_entities.clear();
+        view_entities.extend(
+            visible_entities
+                .iter::<WithSprite>()
+                .map(|e| e.index() as usize),
+        );
+
+        for (entity, extracted_sprite) in extracted_sprites.sprites.iter() {
+            let index = extracted_sprite.original_entity.unwrap_or(*entity).index();
+            if !view_entities.contains(index as usize) {
+                continue;
+            }
+            let Some(material_id) = extracted_sprite
+                .material
+                .and_then(|material| material.try_typed::<M>().ok())
+            else {
+                continue;
+            };
+            let Some(material) = render_materials.get(material_id) else {
+                continue;
+            };
+
+            let mut sprite_key = view_key;
+            if extracted_sprite.premultiplied_alpha {
+                sprite_key |= SpritePipelineKey::PREMULTIPLIED_ALPHA;
+            }
+            sprite_key |= extracted_sprite.stencil.pipeline_key();
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &material_pipeline,
+                SpriteMaterialKey {
+                    sprite_key,
+                    bind_group_data: material.key.clone(),
+                },
+            );
+
+            transparent_phase.add(Transparent2d {
+                draw_function: draw_sprite_material_function,
+                pipeline,
+                entity: *entity,
+                // These items will be sorted by depth with other phase items
+                sort_key: FloatOrd(extracted_sprite.view_depth(view)),
+                // batch_range and dynamic_offset will be calculated in prepare_sprites
+                batch_range: 0..0,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+/// Data prepared for a [`SpriteMaterial`] instance.
+pub struct PreparedSpriteMaterial<T: SpriteMaterial> {
+    pub bindings: Vec<(u32, OwnedBindingResource)>,
+    pub bind_group: BindGroup,
+    pub key: T::Data,
+}
+
+impl<M: SpriteMaterial> RenderAsset for PreparedSpriteMaterial<M> {
+    type SourceAsset = M;
+
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<RenderAssets<GpuImage>>,
+        SRes<FallbackImage>,
+        SRes<SpriteMaterialPipeline<M>>,
+    );
+
+    fn prepare_asset(
+        material: Self::SourceAsset,
+        (render_device, images, fallback_image, pipeline): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
+        match material.as_bind_group(
+            &pipeline.sprite_material_layout,
+            render_device,
+            images,
+            fallback_image,
+        ) {
+            Ok(prepared) => Ok(PreparedSpriteMaterial {
+                bindings: prepared.bindings,
+                bind_group: prepared.bind_group,
+                key: prepared.data,
+            }),
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                Err(PrepareAssetError::RetryNextUpdate(material))
+            }
+        }
+    }
+}
@@ -1,13 +1,23 @@
-use std::ops::Range;
+mod culling;
+mod light_2d;
+mod material;
+
+pub use culling::*;
+pub use light_2d::*;
+pub use material::*;
+
+use std::{num::NonZeroU32, ops::Range};
 
 use crate::{
     texture_atlas::{TextureAtlas, TextureAtlasLayout},
-    ComputedTextureSlices, Sprite, WithSprite, SPRITE_SHADER_HANDLE,
+    AlphaMode2d, AmbientLight2d, ComputedTextureSlices, ParallaxLayer, ParallaxRepeat, Sprite,
+    SpriteColorSpace, SpriteMask, SpriteMaskInteraction, SpriteOutline, SpritePixelSnap,
+    SpriteSampler, SpriteShadow, SpriteSortKey, WithSprite, SPRITE_SHADER_HANDLE,
 };
-use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
-use bevy_color::LinearRgba;
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle, UntypedAssetId};
+use bevy_color::{LinearRgba, Srgba};
 use bevy_core_pipeline::{
-    core_2d::Transparent2d,
+    core_2d::{view_depth_2d, Opaque2d, Transparent2d, CORE_2D_DEPTH_FORMAT},
     tonemapping::{
         get_lut_bind_group_layout_entries, get_lut_bindings, DebandDither, Tonemapping,
         TonemappingLuts,
@@ -18,24 +28,29 @@ use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
+use bevy_hierarchy::Children;
 use bevy_math::{Affine3A, FloatOrd, Quat, Rect, Vec2, Vec4};
 use bevy_render::{
-    render_asset::RenderAssets,
+    globals::{GlobalsBuffer, GlobalsUniform},
+    primitives::Frustum,
+    render_asset::{RenderAssetUsages, RenderAssets},
     render_phase::{
-        DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
-        SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+        CachedRenderPipelinePhaseItem, DrawFunctions, PhaseItem, PhaseItemExtraIndex,
+        RenderCommand, RenderCommandResult, SetItemPipeline, TrackedRenderPass,
+        ViewSortedRenderPhases,
     },
     render_resource::{
         binding_types::{sampler, texture_2d, uniform_buffer},
         *,
     },
     renderer::{RenderDevice, RenderQueue},
+    settings::WgpuFeatures,
     texture::{
-        BevyDefault, DefaultImageSampler, FallbackImage, GpuImage, Image, ImageSampler,
-        TextureFormatPixelInfo,
+        BevyDefault, DefaultImageSampler, FallbackImage, GpuImage, Image, ImageAddressMode,
+        ImageFilterMode, ImageSampler, ImageSamplerDescriptor, TextureFormatPixelInfo,
     },
     view::{
-        ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms,
+        ExtractedView, GpuCulling, Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms,
         ViewVisibility, VisibleEntities,
     },
     Extract,
@@ -45,11 +60,49 @@ use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
 use fixedbitset::FixedBitSet;
 
-#[derive(Resource)]
+/// The maximum number of images bound together by a sprite batch, when binding arrays are
+/// supported.
+///
+/// This must match the size of the binding arrays in `sprite_texture_bindings.wgsl`.
+pub const MAX_SPRITE_BATCH_TEXTURES: usize = 16;
+
+/// Returns `true` if the sprites can sample from arrays of images, so that sprites with
+/// different images are drawn in the same batch.
+pub fn sprite_binding_arrays_are_usable(render_device: &RenderDevice) -> bool {
+    let limits = render_device.limits();
+    // The tonemapping LUT of the view is also bound in the fragment stage
+    let required = MAX_SPRITE_BATCH_TEXTURES as u32 + 1;
+    !cfg!(feature = "shader_format_glsl")
+        && limits.max_sampled_textures_per_shader_stage >= required
+        && limits.max_samplers_per_shader_stage >= required
+        && render_device.features().contains(
+            WgpuFeatures::TEXTURE_BINDING_ARRAY
+                | WgpuFeatures::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
+        )
+}
+
+#[derive(Resource, Clone)]
 pub struct SpritePipeline {
     view_layout: BindGroupLayout,
     material_layout: BindGroupLayout,
+    /// The layout binding arrays of images, if they are supported.
+    bindless_material_layout: Option<BindGroupLayout>,
+    /// The layout of the normal map of lit sprites.
+    normal_map_layout: BindGroupLayout,
     pub dummy_white_gpu_image: GpuImage,
+    /// The normal map of the lit sprites without one, facing the camera.
+    flat_normal_map_bind_group: BindGroup,
+    /// The samplers used by [`SpriteSampler::Nearest`] and [`SpriteSampler::Linear`].
+    nearest_sampler: Sampler,
+    linear_sampler: Sampler,
+    /// The samplers used by [`SpriteSampler::Nearest`] and [`SpriteSampler::Linear`] when the UVs
+    /// of the sprite repeat.
+    nearest_repeat_sampler: Sampler,
+    linear_repeat_sampler: Sampler,
+    /// A repeating copy of the default sampler of the images, used by [`SpriteSampler::Image`]
+    /// when the UVs of the sprite repeat. It is replaced by the `ImagePlugin`'s one in
+    /// `SpritePlugin::finish`.
+    pub(crate) default_repeat_sampler: Sampler,
 }
 
 impl FromWorld for SpritePipeline {
@@ -76,6 +129,11 @@ impl FromWorld for SpritePipeline {
                         2,
                         tonemapping_lut_entries[1].visibility(ShaderStages::FRAGMENT),
                     ),
+                    (3, uniform_buffer::<GlobalsUniform>(false)),
+                    (
+                        4,
+                        uniform_buffer::<GpuLights2d>(true).visibility(ShaderStages::FRAGMENT),
+                    ),
                 ),
             ),
         );
@@ -90,8 +148,31 @@ impl FromWorld for SpritePipeline {
                 ),
             ),
         );
-        let dummy_white_gpu_image = {
-            let image = Image::default();
+        let bindless_material_layout =
+            sprite_binding_arrays_are_usable(&render_device).then(|| {
+                let count = NonZeroU32::new(MAX_SPRITE_BATCH_TEXTURES as u32).unwrap();
+                render_device.create_bind_group_layout(
+                    "sprite_bindless_material_layout",
+                    &BindGroupLayoutEntries::sequential(
+                        ShaderStages::FRAGMENT,
+                        (
+                            texture_2d(TextureSampleType::Float { filterable: true }).count(count),
+                            sampler(SamplerBindingType::Filtering).count(count),
+                        ),
+                    ),
+                )
+            });
+        let normal_map_layout = render_device.create_bind_group_layout(
+            "sprite_normal_map_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+        let create_gpu_image = |image: Image| {
             let texture = render_device.create_texture(&image.texture_descriptor);
             let sampler = match image.sampler {
                 ImageSampler::Default => (**default_sampler).clone(),
@@ -121,15 +202,84 @@ impl FromWorld for SpritePipeline {
                 mip_level_count: image.texture_descriptor.mip_level_count,
             }
         };
+        let dummy_white_gpu_image = create_gpu_image(Image::default());
+        // The normals are stored in linear space, from [-1, 1] to [0, 1]
+        let flat_normal_map = create_gpu_image(Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[128, 128, 255, 255],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        ));
+        let flat_normal_map_bind_group = render_device.create_bind_group(
+            "sprite_flat_normal_map_bind_group",
+            &normal_map_layout,
+            &BindGroupEntries::sequential((
+                &flat_normal_map.texture_view,
+                &flat_normal_map.sampler,
+            )),
+        );
+
+        let nearest_sampler =
+            render_device.create_sampler(&ImageSamplerDescriptor::nearest().as_wgpu());
+        let linear_sampler =
+            render_device.create_sampler(&ImageSamplerDescriptor::linear().as_wgpu());
+        let nearest_repeat_sampler =
+            render_device.create_sampler(&repeating(ImageSamplerDescriptor::nearest()).as_wgpu());
+        let linear_repeat_sampler =
+            render_device.create_sampler(&repeating(ImageSamplerDescriptor::linear()).as_wgpu());
+        // The default sampler of the `ImagePlugin` is linear by default
+        let default_repeat_sampler = linear_repeat_sampler.clone();
 
         SpritePipeline {
             view_layout,
             material_layout,
+            bindless_material_layout,
+            normal_map_layout,
             dummy_white_gpu_image,
+            flat_normal_map_bind_group,
+            nearest_sampler,
+            linear_sampler,
+            nearest_repeat_sampler,
+            linear_repeat_sampler,
+            default_repeat_sampler,
+        }
+    }
+}
+
+/// Returns `descriptor` with its UVs repeating along both axes.
+pub(crate) fn repeating(descriptor: ImageSamplerDescriptor) -> ImageSamplerDescriptor {
+    ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..descriptor
+    }
+}
+
+impl SpritePipeline {
+    /// Returns the sampler used to draw `gpu_image` with the given [`SamplerKey`].
+    fn sampler<'a>(&'a self, key: SamplerKey, gpu_image: &'a GpuImage) -> &'a Sampler {
+        match (key.sampler, key.repeat) {
+            (SpriteSampler::Image, false) => &gpu_image.sampler,
+            // Images with their own sampler descriptor are resolved by `repeat_sampler` during
+            // extraction, so only the images with the default sampler are left
+            (SpriteSampler::Image, true) => &self.default_repeat_sampler,
+            (SpriteSampler::Nearest, false) => &self.nearest_sampler,
+            (SpriteSampler::Linear, false) => &self.linear_sampler,
+            (SpriteSampler::Nearest, true) => &self.nearest_repeat_sampler,
+            (SpriteSampler::Linear, true) => &self.linear_repeat_sampler,
         }
     }
 }
 
+/// The sampler used to draw the image of a sprite.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct SamplerKey {
+    sampler: SpriteSampler,
+    /// Whether the UVs of the sprite repeat, see [`Sprite::uv_repeat`]
+    repeat: bool,
+}
+
 bitflags::bitflags! {
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     #[repr(transparent)]
@@ -140,6 +290,18 @@ bitflags::bitflags! {
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
         const DEBAND_DITHER                     = 1 << 2;
+        const PREMULTIPLIED_ALPHA               = 1 << 3;
+        const SRGB_COLORS                       = 1 << 4;
+        const OUTLINE                           = 1 << 5;
+        const SILHOUETTE                        = 1 << 6;
+        const BINDLESS                          = 1 << 7;
+        const ALPHA_MASK                        = 1 << 8;
+        const OPAQUE                            = 1 << 9;
+        const PIXEL_SNAP                        = 1 << 10;
+        const LIGHTING                          = 1 << 11;
+        const STENCIL_WRITE                     = 1 << 12;
+        const STENCIL_INSIDE                    = 1 << 13;
+        const STENCIL_OUTSIDE                   = 1 << 14;
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const TONEMAP_METHOD_RESERVED_BITS      = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
         const TONEMAP_METHOD_NONE               = 0 << Self::TONEMAP_METHOD_SHIFT_BITS;
@@ -225,13 +387,90 @@ impl SpecializedRenderPipeline for SpritePipeline {
             }
         }
 
+        if key.contains(SpritePipelineKey::SRGB_COLORS) {
+            shader_defs.push("SRGB_COLORS".into());
+        }
+
+        if key.contains(SpritePipelineKey::PIXEL_SNAP) {
+            shader_defs.push("SPRITE_PIXEL_SNAP".into());
+        }
+
+        if key.contains(SpritePipelineKey::OUTLINE) {
+            shader_defs.push("SPRITE_OUTLINE".into());
+        } else if key.contains(SpritePipelineKey::SILHOUETTE) {
+            shader_defs.push("SPRITE_SILHOUETTE".into());
+        }
+
+        let material_layout = match &self.bindless_material_layout {
+            Some(layout) if key.contains(SpritePipelineKey::BINDLESS) => {
+                shader_defs.push("SPRITE_BINDLESS".into());
+                layout.clone()
+            }
+            _ => self.material_layout.clone(),
+        };
+        let mut layout = vec![self.view_layout.clone(), material_layout];
+        if key.contains(SpritePipelineKey::LIGHTING) {
+            shader_defs.push("SPRITE_LIGHTING".into());
+            layout.push(self.normal_map_layout.clone());
+        }
+
+        let mut blend = if key.contains(SpritePipelineKey::PREMULTIPLIED_ALPHA) {
+            shader_defs.push("PREMULTIPLIED_ALPHA".into());
+            Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING)
+        } else {
+            Some(BlendState::ALPHA_BLENDING)
+        };
+
+        // Opaque and alpha masked sprites are drawn without blending, and write their depth
+        let mut depth_write_enabled =
+            key.intersects(SpritePipelineKey::OPAQUE | SpritePipelineKey::ALPHA_MASK);
+        if depth_write_enabled {
+            shader_defs.push("SPRITE_OPAQUE".into());
+            blend = None;
+        }
+
+        // Masks write their group to the stencil instead of being drawn, and the sprites they
+        // clip are only drawn where the stencil matches their group
+        let mut write_mask = ColorWrites::ALL;
+        let (stencil_compare, stencil_pass_op, stencil_write_mask) =
+            if key.contains(SpritePipelineKey::STENCIL_WRITE) {
+                depth_write_enabled = false;
+                write_mask = ColorWrites::empty();
+                (CompareFunction::Always, StencilOperation::Replace, !0)
+            } else if key.contains(SpritePipelineKey::STENCIL_INSIDE) {
+                (CompareFunction::Equal, StencilOperation::Keep, 0)
+            } else if key.contains(SpritePipelineKey::STENCIL_OUTSIDE) {
+                (CompareFunction::NotEqual, StencilOperation::Keep, 0)
+            } else {
+                (CompareFunction::Always, StencilOperation::Keep, 0)
+            };
+        let stencil_face = StencilFaceState {
+            compare: stencil_compare,
+            fail_op: StencilOperation::Keep,
+            depth_fail_op: StencilOperation::Keep,
+            pass_op: stencil_pass_op,
+        };
+        if key.contains(SpritePipelineKey::ALPHA_MASK) {
+            shader_defs.push("SPRITE_ALPHA_MASK".into());
+        }
+        // Opaque sprites are drawn front to back, and the effects of a sprite right behind it,
+        // which often ends up at the same depth in the depth buffer: they must not cover what
+        // was drawn at the same depth.
+        let depth_compare = if depth_write_enabled
+            || key.intersects(SpritePipelineKey::OUTLINE | SpritePipelineKey::SILHOUETTE)
+        {
+            CompareFunction::Greater
+        } else {
+            CompareFunction::GreaterEqual
+        };
+
         let format = match key.contains(SpritePipelineKey::HDR) {
             true => ViewTarget::TEXTURE_FORMAT_HDR,
             false => TextureFormat::bevy_default(),
         };
 
         let instance_rate_vertex_buffer_layout = VertexBufferLayout {
-            array_stride: 80,
+            array_stride: 112,
             step_mode: VertexStepMode::Instance,
             attributes: vec![
                 // @location(0) i_model_transpose_col0: vec4<f32>,
@@ -264,6 +503,18 @@ impl SpecializedRenderPipeline for SpritePipeline {
                     offset: 64,
                     shader_location: 4,
                 },
+                // @location(5) i_effect: vec4<f32>,
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 80,
+                    shader_location: 5,
+                },
+                // @location(6) i_emissive: f32,
+                VertexAttribute {
+                    format: VertexFormat::Float32,
+                    offset: 96,
+                    shader_location: 6,
+                },
             ],
         };
 
@@ -280,11 +531,11 @@ impl SpecializedRenderPipeline for SpritePipeline {
                 entry_point: "fragment".into(),
                 targets: vec![Some(ColorTargetState {
                     format,
-                    blend: Some(BlendState::ALPHA_BLENDING),
-                    write_mask: ColorWrites::ALL,
+                    blend,
+                    write_mask,
                 })],
             }),
-            layout: vec![self.view_layout.clone(), self.material_layout.clone()],
+            layout,
             primitive: PrimitiveState {
                 front_face: FrontFace::Ccw,
                 cull_mode: None,
@@ -294,7 +545,18 @@ impl SpecializedRenderPipeline for SpritePipeline {
                 topology: PrimitiveTopology::TriangleList,
                 strip_index_format: None,
             },
-            depth_stencil: None,
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_2D_DEPTH_FORMAT,
+                depth_write_enabled,
+                depth_compare,
+                stencil: StencilState {
+                    front: stencil_face,
+                    back: stencil_face,
+                    read_mask: !0,
+                    write_mask: stencil_write_mask,
+                },
+                bias: DepthBiasState::default(),
+            }),
             multisample: MultisampleState {
                 count: key.msaa_samples(),
                 mask: !0,
@@ -306,6 +568,101 @@ impl SpecializedRenderPipeline for SpritePipeline {
     }
 }
 
+/// How an [`ExtractedSprite`] draws its image.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SpriteEffect {
+    /// Draws the image, tinted by the color of the sprite.
+    #[default]
+    None,
+    /// Draws an outline around the visible pixels of the image, filled with the color of the
+    /// sprite. The quad of the sprite grows by `thickness` on each side to fit the outline.
+    Outline { thickness: f32 },
+    /// Draws the silhouette of the image, filled with the color of the sprite.
+    Silhouette,
+}
+
+impl SpriteEffect {
+    /// The number of steps by which the sort key of the sprite is moved backward, so that
+    /// shadows are drawn before outlines, and outlines before the sprite itself.
+    fn depth_steps(&self) -> u32 {
+        match self {
+            SpriteEffect::None => 0,
+            SpriteEffect::Outline { .. } => 1,
+            SpriteEffect::Silhouette => 2,
+        }
+    }
+
+    /// The size of the outline on each side of a quad of size `quad_size`, relative to its size.
+    ///
+    /// The shader grows the quad by this much to fit the outline.
+    fn outline_size(&self, quad_size: Vec2) -> Vec2 {
+        match self {
+            SpriteEffect::Outline { thickness } if quad_size.cmpgt(Vec2::ZERO).all() => {
+                thickness.max(0.0) / quad_size
+            }
+            SpriteEffect::None | SpriteEffect::Outline { .. } | SpriteEffect::Silhouette => {
+                Vec2::ZERO
+            }
+        }
+    }
+}
+
+/// How an [`ExtractedSprite`] uses the stencil buffer, see [`SpriteMask`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpriteStencil {
+    /// The sprite is not clipped by masks.
+    #[default]
+    None,
+    /// The sprite is a mask of the group, written to the stencil instead of being drawn.
+    WriteMask(u8),
+    /// The sprite is only drawn inside of the masks of the group.
+    InsideMask(u8),
+    /// The sprite is only drawn outside of the masks of the group.
+    OutsideMask(u8),
+}
+
+impl SpriteStencil {
+    /// Returns the [`SpritePipelineKey`] flag drawing the sprite with this stencil.
+    pub fn pipeline_key(self) -> SpritePipelineKey {
+        match self {
+            SpriteStencil::None => SpritePipelineKey::NONE,
+            SpriteStencil::WriteMask(_) => SpritePipelineKey::STENCIL_WRITE,
+            SpriteStencil::InsideMask(_) => SpritePipelineKey::STENCIL_INSIDE,
+            SpriteStencil::OutsideMask(_) => SpritePipelineKey::STENCIL_OUTSIDE,
+        }
+    }
+
+    /// The number of steps by which the sort key of the sprite is moved backward, so that masks
+    /// are drawn before the sprites they clip when they share their depth, like the children of a
+    /// mask, including the outlines and shadows of these sprites.
+    fn depth_steps(self) -> u32 {
+        match self {
+            SpriteStencil::WriteMask(_) => 3,
+            SpriteStencil::None | SpriteStencil::InsideMask(_) | SpriteStencil::OutsideMask(_) => 0,
+        }
+    }
+
+    /// Returns the stencil reference the sprite is drawn with: the group of its mask.
+    pub fn reference(self) -> u32 {
+        match self {
+            SpriteStencil::None => 0,
+            SpriteStencil::WriteMask(group)
+            | SpriteStencil::InsideMask(group)
+            | SpriteStencil::OutsideMask(group) => group as u32,
+        }
+    }
+}
+
+impl From<SpriteMaskInteraction> for SpriteStencil {
+    fn from(interaction: SpriteMaskInteraction) -> Self {
+        match interaction {
+            SpriteMaskInteraction::None => SpriteStencil::None,
+            SpriteMaskInteraction::VisibleInsideMask(group) => SpriteStencil::InsideMask(group),
+            SpriteMaskInteraction::VisibleOutsideMask(group) => SpriteStencil::OutsideMask(group),
+        }
+    }
+}
+
 pub struct ExtractedSprite {
     pub transform: GlobalTransform,
     pub color: LinearRgba,
@@ -319,11 +676,46 @@ pub struct ExtractedSprite {
     pub flip_x: bool,
     pub flip_y: bool,
     pub anchor: Vec2,
+    /// Whether the image uses premultiplied alpha, see [`Sprite::premultiplied_alpha`]
+    pub premultiplied_alpha: bool,
+    /// How the transparency of the image is handled, see [`Sprite::alpha_mode`]
+    pub alpha_mode: AlphaMode2d,
+    /// How the image is drawn, used for the outlines and shadows of sprites
+    pub effect: SpriteEffect,
+    /// Overrides the sampler of the image, see [`SpriteSampler`]
+    pub sampler: SpriteSampler,
+    /// The offset of the image in the sprite, see [`Sprite::uv_offset`]
+    pub uv_offset: Vec2,
+    /// How many times the image fits in the sprite, see [`Sprite::uv_scale`]
+    pub uv_scale: Vec2,
+    /// Whether the image repeats, see [`Sprite::uv_repeat`]
+    pub uv_repeat: bool,
+    /// Overrides the depth used to sort the sprite, see [`SpriteSortKey`]
+    pub sort_key: Option<f32>,
+    /// Asset ID of the normal map of this sprite, see [`Sprite::normal_map`]
+    pub normal_map: Option<AssetId<Image>>,
+    /// Multiplies the color of the sprite, see [`Sprite::emissive`]
+    pub emissive: f32,
+    /// How the sprite is clipped by masks, see [`SpriteMask`]
+    pub stencil: SpriteStencil,
+    /// The [`SpriteMaterial`](crate::SpriteMaterial) drawing this sprite instead of the built-in
+    /// sprite shader, set by [`SpriteMaterialPlugin`](crate::SpriteMaterialPlugin)
+    pub material: Option<UntypedAssetId>,
     /// For cases where additional [`ExtractedSprites`] are created during extraction, this stores the
     /// entity that caused that creation for use in determining visibility.
     pub original_entity: Option<Entity>,
 }
 
+impl ExtractedSprite {
+    /// Returns the depth used to sort this sprite in `view`: its [`sort_key`](Self::sort_key) if
+    /// set, or else the depth of its translation along the view axis.
+    #[inline]
+    pub fn view_depth(&self, view: &ExtractedView) -> f32 {
+        self.sort_key
+            .unwrap_or_else(|| view_depth_2d(view, self.transform.translation()))
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ExtractedSprites {
     pub sprites: EntityHashMap<ExtractedSprite>,
@@ -346,10 +738,34 @@ pub fn extract_sprite_events(
     }
 }
 
+/// Collects the group of the nearest [`SpriteMask`] ancestor of every descendant of a mask, once
+/// per frame instead of walking up the ancestors of every sprite.
+fn collect_masked_descendants(
+    masked_descendants: &mut EntityHashMap<u8>,
+    masks: &Query<(Entity, &SpriteMask)>,
+    children: &Query<&Children>,
+) {
+    masked_descendants.clear();
+    let mut stack = Vec::new();
+    for (mask_entity, mask) in masks {
+        stack.extend(children.get(mask_entity).into_iter().flatten());
+        while let Some(entity) = stack.pop() {
+            // The descendants of nested masks are clipped by the nested mask
+            if masks.contains(entity) {
+                continue;
+            }
+            masked_descendants.insert(entity, mask.group);
+            stack.extend(children.get(entity).into_iter().flatten());
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn extract_sprites(
     mut commands: Commands,
     mut extracted_sprites: ResMut<ExtractedSprites>,
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
+    images: Extract<Res<Assets<Image>>>,
     sprite_query: Extract<
         Query<(
             Entity,
@@ -359,22 +775,95 @@ pub fn extract_sprites(
             &Handle<Image>,
             Option<&TextureAtlas>,
             Option<&ComputedTextureSlices>,
+            Option<&SpriteOutline>,
+            Option<&SpriteShadow>,
+            Option<&SpriteSampler>,
+            Option<&SpriteSortKey>,
+            Option<&SpriteMask>,
+            Option<&SpriteMaskInteraction>,
+            Option<&ParallaxLayer>,
         )>,
     >,
+    children: Extract<Query<&Children>>,
+    masks: Extract<Query<(Entity, &SpriteMask)>>,
+    camera_transforms: Extract<Query<&GlobalTransform>>,
+    mut masked_descendants: Local<EntityHashMap<u8>>,
 ) {
     extracted_sprites.sprites.clear();
-    for (entity, view_visibility, sprite, transform, handle, sheet, slices) in sprite_query.iter() {
+    collect_masked_descendants(&mut masked_descendants, &masks, &children);
+    for (
+        entity,
+        view_visibility,
+        sprite,
+        transform,
+        handle,
+        sheet,
+        slices,
+        outline,
+        shadow,
+        sampler,
+        sort_key,
+        mask,
+        mask_interaction,
+        parallax,
+    ) in sprite_query.iter()
+    {
         if !view_visibility.get() {
             continue;
         }
 
+        let (transform, image_offset) = parallax
+            .and_then(|parallax| {
+                let camera_transform = camera_transforms.get(parallax.camera).ok()?;
+                Some(parallax.apply(transform, camera_transform))
+            })
+            .unwrap_or((*transform, Vec2::ZERO));
+        let transform = &transform;
+
+        let sampler = sampler.copied().unwrap_or_default();
+        let sort_key = sort_key.map(|sort_key| sort_key.0);
+        // Masks are drawn with an alpha cutoff, so that their shape follows their image
+        let (stencil, alpha_mode) = match (mask, mask_interaction) {
+            (Some(mask), _) => (
+                SpriteStencil::WriteMask(mask.group),
+                AlphaMode2d::Mask(mask.alpha_cutoff),
+            ),
+            (None, Some(interaction)) => ((*interaction).into(), sprite.alpha_mode),
+            (None, None) => (
+                masked_descendants
+                    .get(&entity)
+                    .map_or(SpriteStencil::None, |&group| {
+                        SpriteStencil::InsideMask(group)
+                    }),
+                sprite.alpha_mode,
+            ),
+        };
+        // Masks are not drawn, so neither are their outlines and shadows
+        let (outline, shadow) = match mask {
+            Some(_) => (None, None),
+            None => (outline, shadow),
+        };
         if let Some(slices) = slices {
-            extracted_sprites.sprites.extend(
-                slices
-                    .extract_sprites(transform, entity, sprite, handle)
-                    .map(|e| (commands.spawn_empty().id(), e)),
-            );
+            for slice in slices.extract_sprites(transform, entity, sprite, handle) {
+                let slice = ExtractedSprite {
+                    sampler,
+                    sort_key,
+                    stencil,
+                    alpha_mode,
+                    ..slice
+                };
+                if let Some(shadow) = shadow {
+                    extracted_sprites
+                        .sprites
+                        .insert(commands.spawn_empty().id(), shadow_of(&slice, shadow));
+                }
+                extracted_sprites
+                    .sprites
+                    .insert(commands.spawn_empty().id(), slice);
+            }
         } else {
+            let uv_repeat = sprite.uv_repeat
+                || parallax.is_some_and(|parallax| parallax.repeat != ParallaxRepeat::None);
             let atlas_rect = sheet.and_then(|s| s.texture_rect(&texture_atlases));
             let rect = match (atlas_rect, sprite.rect) {
                 (None, None) => None,
@@ -388,23 +877,107 @@ pub fn extract_sprites(
                 }
             };
 
+            // The image of a repeating parallax layer scrolls inside of the sprite
+            let mut uv_offset = sprite.uv_offset;
+            if image_offset != Vec2::ZERO {
+                let size = sprite
+                    .custom_size
+                    .or(rect.map(|rect| rect.size()))
+                    .or(images.get(handle).map(Image::size_f32))
+                    .unwrap_or(Vec2::ONE);
+                let local_offset = transform
+                    .affine()
+                    .inverse()
+                    .transform_vector3(image_offset.extend(0.))
+                    .truncate();
+                // The UV Y axis points down
+                uv_offset -= local_offset * Vec2::new(1., -1.) / size * sprite.uv_scale;
+            }
+
             // PERF: we don't check in this function that the `Image` asset is ready, since it should be in most cases and hashing the handle is expensive
-            extracted_sprites.sprites.insert(
-                entity,
-                ExtractedSprite {
-                    color: sprite.color.into(),
-                    transform: *transform,
-                    rect,
-                    // Pass the custom size
-                    custom_size: sprite.custom_size,
-                    flip_x: sprite.flip_x,
-                    flip_y: sprite.flip_y,
-                    image_handle_id: handle.id(),
-                    anchor: sprite.anchor.as_vec(),
-                    original_entity: None,
-                },
-            );
+            let extracted_sprite = ExtractedSprite {
+                color: sprite.color.into(),
+                transform: *transform,
+                rect,
+                // Pass the custom size
+                custom_size: sprite.custom_size,
+                flip_x: sprite.flip_x,
+                flip_y: sprite.flip_y,
+                image_handle_id: handle.id(),
+                anchor: sprite.anchor.as_vec(),
+                premultiplied_alpha: sprite.premultiplied_alpha,
+                alpha_mode,
+                effect: SpriteEffect::None,
+                sampler: repeat_sampler(sampler, uv_repeat, images.get(handle)),
+                uv_offset,
+                uv_scale: sprite.uv_scale,
+                uv_repeat,
+                sort_key,
+                normal_map: sprite.normal_map.as_ref().map(Handle::id),
+                emissive: sprite.emissive,
+                stencil,
+                material: None,
+                original_entity: None,
+            };
+
+            if let Some(shadow) = shadow {
+                extracted_sprites.sprites.insert(
+                    commands.spawn_empty().id(),
+                    ExtractedSprite {
+                        original_entity: Some(entity),
+                        ..shadow_of(&extracted_sprite, shadow)
+                    },
+                );
+            }
+            if let Some(outline) = outline {
+                extracted_sprites.sprites.insert(
+                    commands.spawn_empty().id(),
+                    ExtractedSprite {
+                        color: outline.color.into(),
+                        effect: SpriteEffect::Outline {
+                            thickness: outline.thickness,
+                        },
+                        alpha_mode: AlphaMode2d::Blend,
+                        emissive: 1.0,
+                        original_entity: Some(entity),
+                        ..extracted_sprite
+                    },
+                );
+            }
+            extracted_sprites.sprites.insert(entity, extracted_sprite);
+        }
+    }
+}
+
+/// Returns the [`SpriteSampler`] drawing an image with `sampler`.
+///
+/// The sampler descriptor of an image doesn't necessarily repeat, so when the UVs of a sprite
+/// repeat, [`SpriteSampler::Image`] is replaced by the repeating sampler with the same
+/// magnification filter. Images with the default sampler keep [`SpriteSampler::Image`], and are
+/// drawn with [`SpritePipeline`]'s repeating copy of the default sampler.
+fn repeat_sampler(sampler: SpriteSampler, repeat: bool, image: Option<&Image>) -> SpriteSampler {
+    match (sampler, image.map(|image| &image.sampler)) {
+        (SpriteSampler::Image, Some(ImageSampler::Descriptor(descriptor))) if repeat => {
+            match descriptor.mag_filter {
+                ImageFilterMode::Nearest => SpriteSampler::Nearest,
+                ImageFilterMode::Linear => SpriteSampler::Linear,
+            }
         }
+        _ => sampler,
+    }
+}
+
+/// Returns the silhouette of `sprite` drawn by a [`SpriteShadow`].
+///
+/// Like outlines, shadows are always blended, even for opaque sprites.
+fn shadow_of(sprite: &ExtractedSprite, shadow: &SpriteShadow) -> ExtractedSprite {
+    ExtractedSprite {
+        transform: GlobalTransform::from_translation(shadow.offset.extend(0.0)) * sprite.transform,
+        color: shadow.color.into(),
+        effect: SpriteEffect::Silhouette,
+        alpha_mode: AlphaMode2d::Blend,
+        emissive: 1.0,
+        ..*sprite
     }
 }
 
@@ -415,11 +988,25 @@ struct SpriteInstance {
     pub i_model_transpose: [Vec4; 3],
     pub i_color: [f32; 4],
     pub i_uv_offset_scale: [f32; 4],
+    // The size of the outline on each side, relative to the size of the quad, followed by the
+    // index of the image in the batch and the alpha cutoff of alpha masked sprites
+    pub i_effect: [f32; 4],
+    pub i_emissive: f32,
+    // Keeps the size a multiple of 16 bytes, like the instances written by the culling shader
+    pub _padding: [f32; 3],
 }
 
 impl SpriteInstance {
     #[inline]
-    fn from(transform: &Affine3A, color: &LinearRgba, uv_offset_scale: &Vec4) -> Self {
+    fn from(
+        transform: &Affine3A,
+        color: [f32; 4],
+        uv_offset_scale: &Vec4,
+        outline_size: Vec2,
+        texture_index: u32,
+        alpha_cutoff: f32,
+        emissive: f32,
+    ) -> Self {
         let transpose_model_3x3 = transform.matrix3.transpose();
         Self {
             i_model_transpose: [
@@ -427,8 +1014,16 @@ impl SpriteInstance {
                 transpose_model_3x3.y_axis.extend(transform.translation.y),
                 transpose_model_3x3.z_axis.extend(transform.translation.z),
             ],
-            i_color: color.to_f32_array(),
+            i_color: color,
             i_uv_offset_scale: uv_offset_scale.to_array(),
+            i_effect: [
+                outline_size.x,
+                outline_size.y,
+                texture_index as f32,
+                alpha_cutoff,
+            ],
+            i_emissive: emissive,
+            _padding: [0.0; 3],
         }
     }
 }
@@ -453,26 +1048,74 @@ pub struct SpriteViewBindGroup {
     pub value: BindGroup,
 }
 
-#[derive(Component, PartialEq, Eq, Clone)]
+#[derive(PartialEq, Eq, Clone)]
 pub struct SpriteBatch {
     image_handle_id: AssetId<Image>,
+    sampler: SamplerKey,
+    /// The index of the bind group in [`ImageBindGroups`] binding all the images of the batch,
+    /// if the batch uses binding arrays.
+    bindless_index: Option<usize>,
+    pub(crate) material: Option<UntypedAssetId>,
+    lighting: SpriteBatchLighting,
+    /// The stencil reference of the sprites, see [`SpriteStencil::reference`].
+    stencil_reference: u32,
+    /// The index of the batch in [`SpriteCullingBuffers`], if its view culls the sprites on the
+    /// GPU.
+    culled_index: Option<u32>,
     range: Range<u32>,
 }
 
+/// The batches of sprites of every view, rebuilt every frame.
+///
+/// The batches are stored per view, since a sprite seen by several views can start a batch in each
+/// of them, with different instances and lighting.
+#[derive(Resource, Default)]
+pub struct SpriteBatches {
+    /// The batches keyed by their view and by the first phase item of the batch.
+    values: HashMap<(Entity, Entity), SpriteBatch>,
+}
+
+impl SpriteBatches {
+    /// Returns the batch starting at `item` in `view`.
+    #[inline]
+    pub fn get(&self, view: Entity, item: Entity) -> Option<&SpriteBatch> {
+        self.values.get(&(view, item))
+    }
+}
+
+/// How the sprites of a [`SpriteBatch`] are lit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SpriteBatchLighting {
+    /// The sprites are drawn by a pipeline without lighting.
+    Unlit,
+    /// The sprites have no normal map, they are lit as if they were facing the camera.
+    Flat,
+    /// The sprites are lit using the given normal map.
+    NormalMap(AssetId<Image>),
+}
+
 #[derive(Resource, Default)]
 pub struct ImageBindGroups {
-    values: HashMap<AssetId<Image>, BindGroup>,
+    values: HashMap<(AssetId<Image>, SamplerKey), BindGroup>,
+    /// The bind groups of the normal maps of lit sprites.
+    normal_maps: HashMap<(AssetId<Image>, SamplerKey), BindGroup>,
+    /// The bind groups of the batches using binding arrays, rebuilt every frame.
+    bindless: Vec<BindGroup>,
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn queue_sprites(
     mut view_entities: Local<FixedBitSet>,
-    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    opaque_draw_functions: Res<DrawFunctions<Opaque2d>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent2d>>,
     sprite_pipeline: Res<SpritePipeline>,
     mut pipelines: ResMut<SpecializedRenderPipelines<SpritePipeline>>,
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
+    color_space: Res<SpriteColorSpace>,
+    pixel_snap: Res<SpritePixelSnap>,
     extracted_sprites: Res<ExtractedSprites>,
+    mut opaque_render_phases: ResMut<ViewSortedRenderPhases<Opaque2d>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(
         Entity,
@@ -480,43 +1123,42 @@ pub fn queue_sprites(
         &ExtractedView,
         Option<&Tonemapping>,
         Option<&DebandDither>,
+        Has<AmbientLight2d>,
     )>,
 ) {
-    let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
+    let mut msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
+    if *color_space == SpriteColorSpace::Srgb {
+        msaa_key |= SpritePipelineKey::SRGB_COLORS;
+    }
+    if *pixel_snap == SpritePixelSnap::Enabled {
+        msaa_key |= SpritePipelineKey::PIXEL_SNAP;
+    }
+
+    let draw_opaque_sprite_function = opaque_draw_functions.read().id::<DrawSprite>();
+    let draw_transparent_sprite_function = transparent_draw_functions.read().id::<DrawSprite>();
 
-    let draw_sprite_function = draw_functions.read().id::<DrawSprite>();
+    // The phases are sorted with a stable sort: queuing the sprites by image keeps the ones at the
+    // same depth, such as the sprites sharing a `SpriteSortKey`, grouped so they can be batched,
+    // and queuing them by entity within an image gives them the same order every frame
+    let mut sprites = extracted_sprites.sprites.iter().collect::<Vec<_>>();
+    sprites.sort_unstable_by_key(|&(&entity, sprite)| (sprite.image_handle_id, entity));
 
-    for (view_entity, visible_entities, view, tonemapping, dither) in &mut views {
-        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+    for (view_entity, visible_entities, view, tonemapping, dither, lit) in &mut views {
+        let (Some(opaque_phase), Some(transparent_phase)) = (
+            opaque_render_phases.get_mut(&view_entity),
+            transparent_render_phases.get_mut(&view_entity),
+        ) else {
             continue;
         };
 
-        let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
-
-        if !view.hdr {
-            if let Some(tonemapping) = tonemapping {
-                view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
-                view_key |= match tonemapping {
-                    Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
-                    Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
-                    Tonemapping::ReinhardLuminance => {
-                        SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
-                    }
-                    Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
-                    Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
-                    Tonemapping::SomewhatBoringDisplayTransform => {
-                        SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
-                    }
-                    Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
-                    Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
-                };
-            }
-            if let Some(DebandDither::Enabled) = dither {
-                view_key |= SpritePipelineKey::DEBAND_DITHER;
-            }
+        let mut view_key = sprite_view_key(view, tonemapping, dither) | msaa_key;
+        if sprite_pipeline.bindless_material_layout.is_some() {
+            view_key |= SpritePipelineKey::BINDLESS;
         }
 
-        let pipeline = pipelines.specialize(&pipeline_cache, &sprite_pipeline, view_key);
+        // Pipelines are specialized on first use, indexed by effect, premultiplied alpha, alpha
+        // mode and stencil.
+        let mut view_pipelines = [None; 72];
 
         view_entities.clear();
         view_entities.extend(
@@ -529,42 +1171,153 @@ pub fn queue_sprites(
             .items
             .reserve(extracted_sprites.sprites.len());
 
-        for (entity, extracted_sprite) in extracted_sprites.sprites.iter() {
+        for &(entity, extracted_sprite) in &sprites {
             let index = extracted_sprite.original_entity.unwrap_or(*entity).index();
 
-            if !view_entities.contains(index as usize) {
+            // Sprites with a material are queued by their `SpriteMaterialPlugin`
+            if !view_entities.contains(index as usize) || extracted_sprite.material.is_some() {
                 continue;
             }
 
             // These items will be sorted by depth with other phase items
-            let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
+            let sort_key = FloatOrd(depth_before(
+                extracted_sprite.view_depth(view),
+                extracted_sprite.effect.depth_steps() + extracted_sprite.stencil.depth_steps(),
+            ));
 
-            // Add the item to the render phase
-            transparent_phase.add(Transparent2d {
-                draw_function: draw_sprite_function,
-                pipeline,
-                entity: *entity,
-                sort_key,
-                // batch_range and dynamic_offset will be calculated in prepare_sprites
-                batch_range: 0..0,
-                extra_index: PhaseItemExtraIndex::NONE,
+            // Outlines and shadows are not lit
+            let (effect_index, mut key) = match extracted_sprite.effect {
+                SpriteEffect::None if lit => (0, view_key | SpritePipelineKey::LIGHTING),
+                SpriteEffect::None => (0, view_key),
+                SpriteEffect::Outline { .. } => (1, view_key | SpritePipelineKey::OUTLINE),
+                SpriteEffect::Silhouette => (2, view_key | SpritePipelineKey::SILHOUETTE),
+            };
+            if extracted_sprite.premultiplied_alpha {
+                key |= SpritePipelineKey::PREMULTIPLIED_ALPHA;
+            }
+            let alpha_index = match extracted_sprite.alpha_mode {
+                AlphaMode2d::Blend => 0,
+                AlphaMode2d::Mask(_) => {
+                    key |= SpritePipelineKey::ALPHA_MASK;
+                    1
+                }
+                AlphaMode2d::Opaque => {
+                    key |= SpritePipelineKey::OPAQUE;
+                    2
+                }
+            };
+            let stencil_index = match extracted_sprite.stencil {
+                SpriteStencil::None => 0,
+                SpriteStencil::WriteMask(_) => 1,
+                SpriteStencil::InsideMask(_) => 2,
+                SpriteStencil::OutsideMask(_) => 3,
+            };
+            key |= extracted_sprite.stencil.pipeline_key();
+            let slot = ((effect_index * 2 + usize::from(extracted_sprite.premultiplied_alpha)) * 3
+                + alpha_index)
+                * 4
+                + stencil_index;
+            let pipeline = *view_pipelines[slot].get_or_insert_with(|| {
+                pipelines.specialize(&pipeline_cache, &sprite_pipeline, key)
             });
+
+            // Add the item to the render phase
+            // batch_range and dynamic_offset will be calculated in prepare_sprites
+            // Masks and the sprites they clip are drawn in order in the transparent pass
+            if extracted_sprite.alpha_mode == AlphaMode2d::Blend
+                || extracted_sprite.stencil != SpriteStencil::None
+            {
+                transparent_phase.add(Transparent2d {
+                    draw_function: draw_transparent_sprite_function,
+                    pipeline,
+                    entity: *entity,
+                    sort_key,
+                    batch_range: 0..0,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            } else {
+                opaque_phase.add(Opaque2d {
+                    draw_function: draw_opaque_sprite_function,
+                    pipeline,
+                    entity: *entity,
+                    sort_key,
+                    batch_range: 0..0,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            }
         }
     }
 }
 
+/// Returns the [`SpritePipelineKey`] flags that depend on the view: HDR, tonemapping and
+/// debanding.
+pub fn sprite_view_key(
+    view: &ExtractedView,
+    tonemapping: Option<&Tonemapping>,
+    dither: Option<&DebandDither>,
+) -> SpritePipelineKey {
+    let mut view_key = SpritePipelineKey::from_hdr(view.hdr);
+
+    if !view.hdr {
+        if let Some(tonemapping) = tonemapping {
+            view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
+            view_key |= match tonemapping {
+                Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
+                Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
+                Tonemapping::ReinhardLuminance => {
+                    SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
+                }
+                Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
+                Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
+                Tonemapping::SomewhatBoringDisplayTransform => {
+                    SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
+                }
+                Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
+                Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
+            };
+        }
+        if let Some(DebandDither::Enabled) = dither {
+            view_key |= SpritePipelineKey::DEBAND_DITHER;
+        }
+    }
+
+    view_key
+}
+
+/// Returns the float `steps` representable values before `depth`, so that the effects of a
+/// sprite are sorted right before it.
+fn depth_before(depth: f32, steps: u32) -> f32 {
+    let mut depth = depth;
+    for _ in 0..steps {
+        depth = if depth == 0.0 {
+            -f32::from_bits(1)
+        } else if depth > 0.0 {
+            f32::from_bits(depth.to_bits() - 1)
+        } else {
+            f32::from_bits(depth.to_bits() + 1)
+        };
+    }
+    depth
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn prepare_sprite_view_bind_groups(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
     sprite_pipeline: Res<SpritePipeline>,
     view_uniforms: Res<ViewUniforms>,
+    globals_buffer: Res<GlobalsBuffer>,
+    lights_meta: Res<Lights2dMeta>,
     views: Query<(Entity, &Tonemapping), With<ExtractedView>>,
     tonemapping_luts: Res<TonemappingLuts>,
     images: Res<RenderAssets<GpuImage>>,
     fallback_image: Res<FallbackImage>,
 ) {
-    let Some(view_binding) = view_uniforms.uniforms.binding() else {
+    let (Some(view_binding), Some(globals), Some(lights)) = (
+        view_uniforms.uniforms.binding(),
+        globals_buffer.buffer.binding(),
+        lights_meta.gpu_lights.binding(),
+    ) else {
         return;
     };
 
@@ -578,6 +1331,8 @@ pub fn prepare_sprite_view_bind_groups(
                 (0, view_binding.clone()),
                 (1, lut_bindings.0),
                 (2, lut_bindings.1),
+                (3, globals.clone()),
+                (4, lights.clone()),
             )),
         );
 
@@ -589,8 +1344,7 @@ pub fn prepare_sprite_view_bind_groups(
 
 #[allow(clippy::too_many_arguments)]
 pub fn prepare_sprite_image_bind_groups(
-    mut commands: Commands,
-    mut previous_len: Local<usize>,
+    mut sprite_batches: ResMut<SpriteBatches>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut sprite_meta: ResMut<SpriteMeta>,
@@ -598,8 +1352,12 @@ pub fn prepare_sprite_image_bind_groups(
     mut image_bind_groups: ResMut<ImageBindGroups>,
     gpu_images: Res<RenderAssets<GpuImage>>,
     extracted_sprites: Res<ExtractedSprites>,
-    mut phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut opaque_phases: ResMut<ViewSortedRenderPhases<Opaque2d>>,
+    mut transparent_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut culling_buffers: Option<ResMut<SpriteCullingBuffers>>,
+    views: Query<(Has<AmbientLight2d>, Option<&Frustum>, Has<GpuCulling>)>,
     events: Res<SpriteAssetEvents>,
+    color_space: Res<SpriteColorSpace>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
@@ -609,82 +1367,343 @@ pub fn prepare_sprite_image_bind_groups(
             // Images don't have dependencies
             AssetEvent::LoadedWithDependencies { .. } => {}
             AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
-                image_bind_groups.values.remove(id);
+                image_bind_groups
+                    .values
+                    .retain(|(image_id, _), _| image_id != id);
+                image_bind_groups
+                    .normal_maps
+                    .retain(|(image_id, _), _| image_id != id);
             }
         };
     }
 
-    let mut batches: Vec<(Entity, SpriteBatch)> = Vec::with_capacity(*previous_len);
-
-    // Clear the sprite instances
+    // Clear the sprite instances and batches
     sprite_meta.sprite_instance_buffer.clear();
-
-    // Index buffer indices
-    let mut index = 0;
+    sprite_batches.values.clear();
 
     let image_bind_groups = &mut *image_bind_groups;
+    image_bind_groups.bindless.clear();
+    if let Some(culling_buffers) = culling_buffers.as_mut() {
+        culling_buffers.clear();
+    }
 
-    for transparent_phase in phases.values_mut() {
+    let mut batcher = SpriteBatcher {
+        render_device: &render_device,
+        sprite_pipeline: &sprite_pipeline,
+        gpu_images: &gpu_images,
+        extracted_sprites: &extracted_sprites,
+        color_space: *color_space,
+        image_bind_groups,
+        sprite_meta: &mut sprite_meta,
+        culling_buffers: culling_buffers.as_deref_mut(),
+        batches: &mut sprite_batches.values,
+        batch_textures: Vec::with_capacity(MAX_SPRITE_BATCH_TEXTURES),
+        index: 0,
+    };
+    for (view, opaque_phase) in opaque_phases.iter_mut() {
+        let (lit, culling_view) = batcher.view_settings(*view, views.get(*view).ok());
+        batcher.batch_phase(*view, &mut opaque_phase.items, lit, culling_view);
+    }
+    for (view, transparent_phase) in transparent_phases.iter_mut() {
+        let (lit, culling_view) = batcher.view_settings(*view, views.get(*view).ok());
+        batcher.batch_phase(*view, &mut transparent_phase.items, lit, culling_view);
+    }
+    // The instance counts of the batches culled on the GPU are known once they are all batched
+    if let Some(culling_buffers) = culling_buffers.as_mut() {
+        for batch in sprite_batches.values.values() {
+            if let Some(culled_index) = batch.culled_index {
+                culling_buffers.set_batch_range(culled_index, &batch.range);
+            }
+        }
+    }
+
+    sprite_meta
+        .sprite_instance_buffer
+        .write_buffer(&render_device, &render_queue);
+
+    if sprite_meta.sprite_index_buffer.len() != 6 {
+        sprite_meta.sprite_index_buffer.clear();
+
+        // NOTE: This code is creating 6 indices pointing to 4 vertices.
+        // The vertices form the corners of a quad based on their two least significant bits.
+        // 10   11
+        //
+        // 00   01
+        // The sprite shader can then use the two least significant bits as the vertex index.
+        // The rest of the properties to transform the vertex positions and UVs (which are
+        // implicit) are baked into the instance transform, and UV offset and scale.
+        // See bevy_sprite/src/render/sprite.wgsl for the details.
+        sprite_meta.sprite_index_buffer.push(2);
+        sprite_meta.sprite_index_buffer.push(0);
+        sprite_meta.sprite_index_buffer.push(1);
+        sprite_meta.sprite_index_buffer.push(1);
+        sprite_meta.sprite_index_buffer.push(3);
+        sprite_meta.sprite_index_buffer.push(2);
+
+        sprite_meta
+            .sprite_index_buffer
+            .write_buffer(&render_device, &render_queue);
+    }
+}
+
+/// Groups the sprites of the render phases in batches, and stores their instances.
+struct SpriteBatcher<'a> {
+    render_device: &'a RenderDevice,
+    sprite_pipeline: &'a SpritePipeline,
+    gpu_images: &'a RenderAssets<GpuImage>,
+    extracted_sprites: &'a ExtractedSprites,
+    color_space: SpriteColorSpace,
+    image_bind_groups: &'a mut ImageBindGroups,
+    sprite_meta: &'a mut SpriteMeta,
+    /// The buffers of the sprites culled on the GPU, if it is supported.
+    culling_buffers: Option<&'a mut SpriteCullingBuffers>,
+    batches: &'a mut HashMap<(Entity, Entity), SpriteBatch>,
+    /// The images of the current batch, if it uses binding arrays
+    batch_textures: Vec<(AssetId<Image>, SamplerKey)>,
+    /// Index of the next sprite instance
+    index: u32,
+}
+
+impl SpriteBatcher<'_> {
+    /// Returns whether a view has 2D lighting, and the index of its frustum in
+    /// [`SpriteCullingBuffers`] if it culls the sprites on the GPU.
+    fn view_settings(
+        &mut self,
+        view: Entity,
+        settings: Option<(bool, Option<&Frustum>, bool)>,
+    ) -> (bool, Option<u32>) {
+        let Some((lit, frustum, gpu_culling)) = settings else {
+            return (false, None);
+        };
+        let culling_view = match (frustum, &mut self.culling_buffers) {
+            (Some(frustum), Some(culling_buffers)) if gpu_culling => {
+                Some(culling_buffers.push_view(view, frustum))
+            }
+            _ => None,
+        };
+        (lit, culling_view)
+    }
+
+    /// Batches the sprites of the items of a render phase of `view`, in their draw order. `lit` is
+    /// `true` if the view has 2D lighting, and `culling_view` is the index of its frustum if the
+    /// sprites are culled on the GPU.
+    fn batch_phase<I: CachedRenderPipelinePhaseItem>(
+        &mut self,
+        view: Entity,
+        items: &mut [I],
+        lit: bool,
+        culling_view: Option<u32>,
+    ) {
         let mut batch_item_index = 0;
         let mut batch_image_size = Vec2::ZERO;
         let mut batch_image_handle = AssetId::invalid();
+        let mut batch_sampler = SamplerKey {
+            sampler: SpriteSampler::Image,
+            repeat: false,
+        };
+        let mut batch_pipeline = CachedRenderPipelineId::INVALID;
+        let mut batch_material = None;
+        let mut batch_lighting = SpriteBatchLighting::Unlit;
+        let mut batch_stencil_reference = 0;
+        let mut batch_texture_index = 0;
+        let mut batch: Option<(Entity, SpriteBatch)> = None;
 
         // Iterate through the phase items and detect when successive sprites that can be batched.
         // Spawn an entity with a `SpriteBatch` component for each possible batch.
         // Compatible items share the same entity.
-        for item_index in 0..transparent_phase.items.len() {
-            let item = &transparent_phase.items[item_index];
-            let Some(extracted_sprite) = extracted_sprites.sprites.get(&item.entity) else {
+        for item_index in 0..items.len() {
+            let item = &items[item_index];
+            let Some(extracted_sprite) = self.extracted_sprites.sprites.get(&item.entity()) else {
                 // If there is a phase item that is not a sprite, then we must start a new
                 // batch to draw the other phase item(s) and to respect draw order. This can be
-                // done by invalidating the batch_image_handle
+                // done by invalidating the batch_image_handle and batch_pipeline
                 batch_image_handle = AssetId::invalid();
+                batch_pipeline = CachedRenderPipelineId::INVALID;
                 continue;
             };
 
-            let batch_image_changed = batch_image_handle != extracted_sprite.image_handle_id;
-            if batch_image_changed {
-                let Some(gpu_image) = gpu_images.get(extracted_sprite.image_handle_id) else {
+            let sampler = SamplerKey {
+                sampler: extracted_sprite.sampler,
+                repeat: extracted_sprite.uv_repeat,
+            };
+            // Only the sprites drawn by `queue_sprites` without an effect are lit
+            let lighting = match extracted_sprite.normal_map {
+                _ if !lit
+                    || extracted_sprite.effect != SpriteEffect::None
+                    || extracted_sprite.material.is_some() =>
+                {
+                    SpriteBatchLighting::Unlit
+                }
+                Some(normal_map) if self.gpu_images.get(normal_map).is_some() => {
+                    SpriteBatchLighting::NormalMap(normal_map)
+                }
+                _ => SpriteBatchLighting::Flat,
+            };
+            let mut batch_image_changed = false;
+            if batch_image_handle != extracted_sprite.image_handle_id
+                || batch_sampler != sampler
+                || batch_pipeline != item.cached_pipeline()
+                || batch_material != extracted_sprite.material
+                || batch_lighting != lighting
+                || batch_stencil_reference != extracted_sprite.stencil.reference()
+            {
+                let Some(gpu_image) = self.gpu_images.get(extracted_sprite.image_handle_id) else {
                     continue;
                 };
 
                 batch_image_size = gpu_image.size.as_vec2();
                 batch_image_handle = extracted_sprite.image_handle_id;
-                image_bind_groups
-                    .values
-                    .entry(batch_image_handle)
-                    .or_insert_with(|| {
-                        render_device.create_bind_group(
-                            "sprite_material_bind_group",
-                            &sprite_pipeline.material_layout,
-                            &BindGroupEntries::sequential((
-                                &gpu_image.texture_view,
-                                &gpu_image.sampler,
-                            )),
-                        )
+                batch_sampler = sampler;
+                let batch_texture = (batch_image_handle, batch_sampler);
+
+                // Sprites drawn with binding arrays only need a new batch when the pipeline
+                // changes or when there is no room left for their image
+                let bindless = self.sprite_pipeline.bindless_material_layout.is_some()
+                    && extracted_sprite.material.is_none();
+                let existing_texture = self
+                    .batch_textures
+                    .iter()
+                    .position(|texture| *texture == batch_texture);
+                let same_batch = bindless
+                    && batch_pipeline == item.cached_pipeline()
+                    && batch_material.is_none()
+                    && batch_lighting == lighting
+                    && batch_stencil_reference == extracted_sprite.stencil.reference()
+                    && (existing_texture.is_some()
+                        || self.batch_textures.len() < MAX_SPRITE_BATCH_TEXTURES);
+
+                if same_batch {
+                    batch_texture_index = existing_texture.unwrap_or_else(|| {
+                        self.batch_textures.push(batch_texture);
+                        self.batch_textures.len() - 1
                     });
+                } else {
+                    batch_image_changed = true;
+                    push_bindless_bind_group(
+                        &mut self.batch_textures,
+                        self.image_bind_groups,
+                        self.render_device,
+                        self.sprite_pipeline,
+                        self.gpu_images,
+                    );
+                    batch_pipeline = item.cached_pipeline();
+                    batch_material = extracted_sprite.material;
+                    batch_lighting = lighting;
+                    batch_stencil_reference = extracted_sprite.stencil.reference();
+                    batch_texture_index = 0;
+                    if let SpriteBatchLighting::NormalMap(normal_map) = lighting {
+                        self.image_bind_groups
+                            .normal_maps
+                            .entry((normal_map, batch_sampler))
+                            .or_insert_with(|| {
+                                let gpu_image = self.gpu_images.get(normal_map).unwrap();
+                                self.render_device.create_bind_group(
+                                    "sprite_normal_map_bind_group",
+                                    &self.sprite_pipeline.normal_map_layout,
+                                    &BindGroupEntries::sequential((
+                                        &gpu_image.texture_view,
+                                        self.sprite_pipeline.sampler(batch_sampler, gpu_image),
+                                    )),
+                                )
+                            });
+                    }
+                    if bindless {
+                        self.batch_textures.push(batch_texture);
+                    } else {
+                        self.image_bind_groups
+                            .values
+                            .entry(batch_texture)
+                            .or_insert_with(|| {
+                                self.render_device.create_bind_group(
+                                    "sprite_material_bind_group",
+                                    &self.sprite_pipeline.material_layout,
+                                    &BindGroupEntries::sequential((
+                                        &gpu_image.texture_view,
+                                        self.sprite_pipeline.sampler(batch_sampler, gpu_image),
+                                    )),
+                                )
+                            });
+                    }
+                }
             }
 
-            // By default, the size of the quad is the size of the texture
-            let mut quad_size = batch_image_size;
+            if batch_image_changed {
+                batch_item_index = item_index;
+                // The sprites culled on the GPU are stored in the culling buffers instead of
+                // the instance buffer
+                let (culled_index, first_instance) =
+                    match (culling_view, self.culling_buffers.as_deref_mut()) {
+                        (Some(culling_view), Some(culling_buffers)) => {
+                            let (culled_index, first_instance) =
+                                culling_buffers.push_batch(culling_view);
+                            (Some(culled_index), first_instance)
+                        }
+                        _ => (None, self.index),
+                    };
+
+                if let Some((entity, batch)) = batch.take() {
+                    self.batches.insert((view, entity), batch);
+                }
+                batch = Some((
+                    item.entity(),
+                    SpriteBatch {
+                        image_handle_id: batch_image_handle,
+                        sampler: batch_sampler,
+                        // The bind group is created once all the images of the batch are known
+                        bindless_index: (!self.batch_textures.is_empty())
+                            .then_some(self.image_bind_groups.bindless.len()),
+                        material: batch_material,
+                        lighting: batch_lighting,
+                        stencil_reference: batch_stencil_reference,
+                        culled_index,
+                        range: first_instance..first_instance,
+                    },
+                ));
+            }
+
+            // By default, the size of the quad is the size of the texture. If a rect is
+            // specified, it selects the area of the texture displayed in the quad.
+            let rect = extracted_sprite
+                .rect
+                .unwrap_or_else(|| Rect::from_corners(Vec2::ZERO, batch_image_size));
 
-            // Calculate vertex data for this item
-            let mut uv_offset_scale: Vec4;
+            let alpha_cutoff = match extracted_sprite.alpha_mode {
+                AlphaMode2d::Mask(cutoff) => cutoff,
+                AlphaMode2d::Blend | AlphaMode2d::Opaque => 0.0,
+            };
 
-            // If a rect is specified, adjust UVs and the size of the quad
-            if let Some(rect) = extracted_sprite.rect {
-                let rect_size = rect.size();
-                uv_offset_scale = Vec4::new(
-                    rect.min.x / batch_image_size.x,
-                    rect.max.y / batch_image_size.y,
-                    rect_size.x / batch_image_size.x,
-                    -rect_size.y / batch_image_size.y,
+            items[batch_item_index].batch_range_mut().end += 1;
+            if let Some((_, batch)) = &mut batch {
+                batch.range.end += 1;
+            }
+
+            // The instances of the sprites culled on the GPU are built by the culling shader
+            if let (Some(_), Some(culling_buffers)) =
+                (culling_view, self.culling_buffers.as_deref_mut())
+            {
+                culling_buffers.push_sprite(
+                    extracted_sprite,
+                    rect,
+                    batch_image_size,
+                    self.color_space,
+                    batch_texture_index as u32,
+                    alpha_cutoff,
                 );
-                quad_size = rect_size;
-            } else {
-                uv_offset_scale = Vec4::new(0.0, 1.0, 1.0, -1.0);
+                continue;
             }
 
+            let mut quad_size = rect.size();
+
+            // Calculate vertex data for this item, moving the displayed area by the UV transform
+            let uv_min = rect.min + extracted_sprite.uv_offset * rect.size();
+            let uv_size = rect.size() * extracted_sprite.uv_scale;
+            let mut uv_offset_scale = Vec4::new(
+                uv_min.x / batch_image_size.x,
+                (uv_min.y + uv_size.y) / batch_image_size.y,
+                uv_size.x / batch_image_size.x,
+                -uv_size.y / batch_image_size.y,
+            );
+
             if extracted_sprite.flip_x {
                 uv_offset_scale.x += uv_offset_scale.z;
                 uv_offset_scale.z *= -1.0;
@@ -698,6 +1717,8 @@ pub fn prepare_sprite_image_bind_groups(
             if let Some(custom_size) = extracted_sprite.custom_size {
                 quad_size = custom_size;
             }
+
+            let outline_size = extracted_sprite.effect.outline_size(quad_size);
             let transform = extracted_sprite.transform.affine()
                 * Affine3A::from_scale_rotation_translation(
                     quad_size.extend(1.0),
@@ -705,64 +1726,83 @@ pub fn prepare_sprite_image_bind_groups(
                     (quad_size * (-extracted_sprite.anchor - Vec2::splat(0.5))).extend(0.0),
                 );
 
-            // Store the vertex data and add the item to the render phase
-            sprite_meta
+            let color = match self.color_space {
+                SpriteColorSpace::Linear => extracted_sprite.color.to_f32_array(),
+                // The shader tints the sRGB-encoded color of the image
+                SpriteColorSpace::Srgb => {
+                    let color = Srgba::from(extracted_sprite.color);
+                    [color.red, color.green, color.blue, color.alpha]
+                }
+            };
+
+            // Store the vertex data
+            self.sprite_meta
                 .sprite_instance_buffer
                 .push(SpriteInstance::from(
                     &transform,
-                    &extracted_sprite.color,
+                    color,
                     &uv_offset_scale,
+                    outline_size,
+                    batch_texture_index as u32,
+                    alpha_cutoff,
+                    extracted_sprite.emissive,
                 ));
 
-            if batch_image_changed {
-                batch_item_index = item_index;
-
-                batches.push((
-                    item.entity,
-                    SpriteBatch {
-                        image_handle_id: batch_image_handle,
-                        range: index..index,
-                    },
-                ));
-            }
+            self.index += 1;
+        }
 
-            transparent_phase.items[batch_item_index]
-                .batch_range_mut()
-                .end += 1;
-            batches.last_mut().unwrap().1.range.end += 1;
-            index += 1;
+        if let Some((entity, batch)) = batch {
+            self.batches.insert((view, entity), batch);
         }
+        push_bindless_bind_group(
+            &mut self.batch_textures,
+            self.image_bind_groups,
+            self.render_device,
+            self.sprite_pipeline,
+            self.gpu_images,
+        );
     }
-    sprite_meta
-        .sprite_instance_buffer
-        .write_buffer(&render_device, &render_queue);
-
-    if sprite_meta.sprite_index_buffer.len() != 6 {
-        sprite_meta.sprite_index_buffer.clear();
-
-        // NOTE: This code is creating 6 indices pointing to 4 vertices.
-        // The vertices form the corners of a quad based on their two least significant bits.
-        // 10   11
-        //
-        // 00   01
-        // The sprite shader can then use the two least significant bits as the vertex index.
-        // The rest of the properties to transform the vertex positions and UVs (which are
-        // implicit) are baked into the instance transform, and UV offset and scale.
-        // See bevy_sprite/src/render/sprite.wgsl for the details.
-        sprite_meta.sprite_index_buffer.push(2);
-        sprite_meta.sprite_index_buffer.push(0);
-        sprite_meta.sprite_index_buffer.push(1);
-        sprite_meta.sprite_index_buffer.push(1);
-        sprite_meta.sprite_index_buffer.push(3);
-        sprite_meta.sprite_index_buffer.push(2);
+}
 
-        sprite_meta
-            .sprite_index_buffer
-            .write_buffer(&render_device, &render_queue);
+/// Creates the bind group of a batch using binding arrays from its images, then clears them.
+fn push_bindless_bind_group(
+    textures: &mut Vec<(AssetId<Image>, SamplerKey)>,
+    image_bind_groups: &mut ImageBindGroups,
+    render_device: &RenderDevice,
+    sprite_pipeline: &SpritePipeline,
+    gpu_images: &RenderAssets<GpuImage>,
+) {
+    let Some(layout) = &sprite_pipeline.bindless_material_layout else {
+        return;
+    };
+    if textures.is_empty() {
+        return;
     }
 
-    *previous_len = batches.len();
-    commands.insert_or_spawn_batch(batches);
+    let mut texture_views = Vec::with_capacity(MAX_SPRITE_BATCH_TEXTURES);
+    let mut samplers = Vec::with_capacity(MAX_SPRITE_BATCH_TEXTURES);
+    for (id, sampler) in textures.drain(..) {
+        let Some(gpu_image) = gpu_images.get(id) else {
+            continue;
+        };
+        texture_views.push(&*gpu_image.texture_view);
+        samplers.push(&**sprite_pipeline.sampler(sampler, gpu_image));
+    }
+    // Pad out the binding arrays, which is necessary on D3D12 and Metal
+    let dummy = &sprite_pipeline.dummy_white_gpu_image;
+    texture_views.resize(MAX_SPRITE_BATCH_TEXTURES, &*dummy.texture_view);
+    samplers.resize(MAX_SPRITE_BATCH_TEXTURES, &*dummy.sampler);
+
+    image_bind_groups
+        .bindless
+        .push(render_device.create_bind_group(
+            "sprite_bindless_material_bind_group",
+            layout,
+            &BindGroupEntries::sequential((
+                &texture_views[..],
+                BindingResource::SamplerArray(&samplers),
+            )),
+        ));
 }
 
 /// [`RenderCommand`] for sprite rendering.
@@ -770,71 +1810,119 @@ pub type DrawSprite = (
     SetItemPipeline,
     SetSpriteViewBindGroup<0>,
     SetSpriteTextureBindGroup<1>,
+    SetSpriteNormalMapBindGroup<2>,
     DrawSpriteBatch,
 );
 
 pub struct SetSpriteViewBindGroup<const I: usize>;
 impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteViewBindGroup<I> {
     type Param = ();
-    type ViewQuery = (Read<ViewUniformOffset>, Read<SpriteViewBindGroup>);
+    type ViewQuery = (
+        Read<ViewUniformOffset>,
+        Read<ViewLights2dUniformOffset>,
+        Read<SpriteViewBindGroup>,
+    );
     type ItemQuery = ();
 
     fn render<'w>(
         _item: &P,
-        (view_uniform, sprite_view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
+        (view_uniform, view_lights, sprite_view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
         _entity: Option<()>,
         _param: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        pass.set_bind_group(I, &sprite_view_bind_group.value, &[view_uniform.offset]);
+        pass.set_bind_group(
+            I,
+            &sprite_view_bind_group.value,
+            &[view_uniform.offset, view_lights.offset],
+        );
         RenderCommandResult::Success
     }
 }
 pub struct SetSpriteTextureBindGroup<const I: usize>;
 impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteTextureBindGroup<I> {
-    type Param = SRes<ImageBindGroups>;
-    type ViewQuery = ();
-    type ItemQuery = Read<SpriteBatch>;
+    type Param = (SRes<ImageBindGroups>, SRes<SpriteBatches>);
+    type ViewQuery = Entity;
+    type ItemQuery = ();
 
     fn render<'w>(
-        _item: &P,
-        _view: (),
-        batch: Option<&'_ SpriteBatch>,
-        image_bind_groups: SystemParamItem<'w, '_, Self::Param>,
+        item: &P,
+        view: Entity,
+        _entity: Option<()>,
+        (image_bind_groups, sprite_batches): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         let image_bind_groups = image_bind_groups.into_inner();
-        let Some(batch) = batch else {
+        let Some(batch) = sprite_batches.get(view, item.entity()) else {
             return RenderCommandResult::Failure;
         };
 
-        pass.set_bind_group(
-            I,
-            image_bind_groups
+        let bind_group = match batch.bindless_index {
+            Some(index) => &image_bind_groups.bindless[index],
+            None => image_bind_groups
                 .values
-                .get(&batch.image_handle_id)
+                .get(&(batch.image_handle_id, batch.sampler))
                 .unwrap(),
-            &[],
-        );
+        };
+        pass.set_bind_group(I, bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct SetSpriteNormalMapBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteNormalMapBindGroup<I> {
+    type Param = (
+        SRes<SpritePipeline>,
+        SRes<ImageBindGroups>,
+        SRes<SpriteBatches>,
+    );
+    type ViewQuery = Entity;
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        view: Entity,
+        _entity: Option<()>,
+        (sprite_pipeline, image_bind_groups, sprite_batches): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(batch) = sprite_batches.into_inner().get(view, item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+
+        let bind_group = match batch.lighting {
+            SpriteBatchLighting::Unlit => return RenderCommandResult::Success,
+            SpriteBatchLighting::Flat => &sprite_pipeline.into_inner().flat_normal_map_bind_group,
+            SpriteBatchLighting::NormalMap(normal_map) => image_bind_groups
+                .into_inner()
+                .normal_maps
+                .get(&(normal_map, batch.sampler))
+                .unwrap(),
+        };
+        pass.set_bind_group(I, bind_group, &[]);
         RenderCommandResult::Success
     }
 }
 
 pub struct DrawSpriteBatch;
 impl<P: PhaseItem> RenderCommand<P> for DrawSpriteBatch {
-    type Param = SRes<SpriteMeta>;
-    type ViewQuery = ();
-    type ItemQuery = Read<SpriteBatch>;
+    type Param = (
+        SRes<SpriteMeta>,
+        SRes<SpriteBatches>,
+        Option<SRes<SpriteCullingBuffers>>,
+    );
+    type ViewQuery = Entity;
+    type ItemQuery = ();
 
     fn render<'w>(
-        _item: &P,
-        _view: (),
-        batch: Option<&'_ SpriteBatch>,
-        sprite_meta: SystemParamItem<'w, '_, Self::Param>,
+        item: &P,
+        view: Entity,
+        _entity: Option<()>,
+        (sprite_meta, sprite_batches, culling_buffers): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         let sprite_meta = sprite_meta.into_inner();
-        let Some(batch) = batch else {
+        let Some(batch) = sprite_batches.into_inner().get(view, item.entity()) else {
             return RenderCommandResult::Failure;
         };
 
@@ -843,6 +1931,25 @@ impl<P: PhaseItem> RenderCommand<P> for DrawSpriteBatch {
             0,
             IndexFormat::Uint32,
         );
+        pass.set_stencil_reference(batch.stencil_reference);
+
+        // The visible instances of the batches culled on the GPU are copied to another buffer,
+        // and counted in the indirect parameters of the batch
+        if let Some(culled_index) = batch.culled_index {
+            let Some(culling_buffers) = culling_buffers.map(|buffers| buffers.into_inner()) else {
+                return RenderCommandResult::Failure;
+            };
+            let (Some(culled_instances), Some((indirect_parameters, indirect_offset))) = (
+                culling_buffers.culled_instances(),
+                culling_buffers.indirect_parameters(culled_index),
+            ) else {
+                return RenderCommandResult::Failure;
+            };
+            pass.set_vertex_buffer(0, culled_instances.slice(..));
+            pass.draw_indexed_indirect(indirect_parameters, indirect_offset);
+            return RenderCommandResult::Success;
+        }
+
         pass.set_vertex_buffer(
             0,
             sprite_meta
@@ -855,3 +1962,133 @@ impl<P: PhaseItem> RenderCommand<P> for DrawSpriteBatch {
         RenderCommandResult::Success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{entity::EntityHashMap, prelude::*, system::SystemState};
+    use bevy_hierarchy::{BuildWorldChildren, Children};
+    use bevy_math::Vec2;
+
+    use bevy_render::texture::{Image, ImageSampler};
+
+    use super::{
+        collect_masked_descendants, depth_before, repeat_sampler, SpriteEffect, SpriteStencil,
+    };
+    use crate::{SpriteMask, SpriteSampler};
+
+    #[test]
+    fn masks_are_sorted_before_the_sprites_they_clip() {
+        let mask_steps = SpriteStencil::WriteMask(1).depth_steps();
+        for depth in [-1.0, 0.0, 1.0, 500.0] {
+            let mask = depth_before(depth, mask_steps);
+            for effect in [
+                SpriteEffect::None,
+                SpriteEffect::Outline { thickness: 1.0 },
+                SpriteEffect::Silhouette,
+            ] {
+                assert!(mask < depth_before(depth, effect.depth_steps()));
+            }
+        }
+    }
+
+    #[test]
+    fn outlines_grow_the_quad_by_their_thickness() {
+        let quad_size = Vec2::new(10.0, 20.0);
+        assert_eq!(
+            SpriteEffect::Outline { thickness: 2.0 }.outline_size(quad_size),
+            Vec2::new(0.2, 0.1)
+        );
+        // Negative thicknesses would shrink the quad
+        assert_eq!(
+            SpriteEffect::Outline { thickness: -2.0 }.outline_size(quad_size),
+            Vec2::ZERO
+        );
+        // Empty quads have nothing to outline
+        assert_eq!(
+            SpriteEffect::Outline { thickness: 2.0 }.outline_size(Vec2::new(0.0, 20.0)),
+            Vec2::ZERO
+        );
+        for effect in [SpriteEffect::None, SpriteEffect::Silhouette] {
+            assert_eq!(effect.outline_size(quad_size), Vec2::ZERO);
+        }
+    }
+
+    #[test]
+    fn descendants_are_clipped_by_their_nearest_mask() {
+        let mut world = World::new();
+        let mut grandchild = Entity::PLACEHOLDER;
+        let mut nested_child = Entity::PLACEHOLDER;
+        let mut nested_mask = Entity::PLACEHOLDER;
+        let mask = world
+            .spawn(SpriteMask {
+                group: 1,
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn_empty().with_children(|parent| {
+                    grandchild = parent.spawn_empty().id();
+                });
+                nested_mask = parent
+                    .spawn(SpriteMask {
+                        group: 2,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        nested_child = parent.spawn_empty().id();
+                    })
+                    .id();
+            })
+            .id();
+        let unmasked = world.spawn_empty().id();
+
+        let mut system_state: SystemState<(Query<(Entity, &SpriteMask)>, Query<&Children>)> =
+            SystemState::new(&mut world);
+        let (masks, children) = system_state.get(&world);
+        let mut masked_descendants = EntityHashMap::default();
+        collect_masked_descendants(&mut masked_descendants, &masks, &children);
+
+        assert_eq!(masked_descendants.get(&grandchild), Some(&1));
+        assert_eq!(masked_descendants.get(&nested_child), Some(&2));
+        // Masks are drawn to the stencil instead of being clipped
+        assert_eq!(masked_descendants.get(&mask), None);
+        assert_eq!(masked_descendants.get(&nested_mask), None);
+        assert_eq!(masked_descendants.get(&unmasked), None);
+    }
+
+    #[test]
+    fn repeating_images_use_a_repeating_sampler() {
+        let nearest = Image {
+            sampler: ImageSampler::nearest(),
+            ..Default::default()
+        };
+        let linear = Image {
+            sampler: ImageSampler::linear(),
+            ..Default::default()
+        };
+        let default = Image::default();
+
+        assert_eq!(
+            repeat_sampler(SpriteSampler::Image, true, Some(&nearest)),
+            SpriteSampler::Nearest
+        );
+        assert_eq!(
+            repeat_sampler(SpriteSampler::Image, true, Some(&linear)),
+            SpriteSampler::Linear
+        );
+        // Drawn with the repeating copy of the default sampler
+        assert_eq!(
+            repeat_sampler(SpriteSampler::Image, true, Some(&default)),
+            SpriteSampler::Image
+        );
+        // The sampler of the image is kept when the UVs don't repeat
+        assert_eq!(
+            repeat_sampler(SpriteSampler::Image, false, Some(&nearest)),
+            SpriteSampler::Image
+        );
+        // Overridden samplers already have a repeating variant
+        assert_eq!(
+            repeat_sampler(SpriteSampler::Linear, true, Some(&nearest)),
+            SpriteSampler::Linear
+        );
+    }
+}
@@ -7,7 +7,15 @@
     view::View,
 }
 
-#import bevy_sprite::sprite_view_bindings::view
+#ifdef SRGB_COLORS
+#import bevy_render::color_operations::{linear_to_srgb, srgb_to_linear}
+#endif
+
+#import bevy_sprite::{
+    sprite_texture_bindings,
+    sprite_vertex_output::VertexOutput,
+    sprite_view_bindings::{lights, view},
+}
 
 struct VertexInput {
     @builtin(vertex_index) index: u32,
@@ -19,44 +27,247 @@ struct VertexInput {
     @location(2) i_model_transpose_col2: vec4<f32>,
     @location(3) i_color: vec4<f32>,
     @location(4) i_uv_offset_scale: vec4<f32>,
+    // NOTE: xy is the size of the outline on each side, relative to the size of the quad, z is
+    // the index of the image in the batch, and w the alpha cutoff of alpha masked sprites.
+    @location(5) i_effect: vec4<f32>,
+    @location(6) i_emissive: f32,
 }
 
-struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-    @location(1) @interpolate(flat) color: vec4<f32>,
-};
-
 @vertex
 fn vertex(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
 
-    let vertex_position = vec3<f32>(
+    var vertex_position = vec3<f32>(
         f32(in.index & 0x1u),
         f32((in.index & 0x2u) >> 1u),
         0.0
     );
 
-    out.clip_position = view.view_proj * affine3_to_square(mat3x4<f32>(
+#ifdef SPRITE_OUTLINE
+    // Grow the quad on each side so that the outline fits around the image.
+    vertex_position = vec3<f32>(
+        vertex_position.xy * (1.0 + 2.0 * in.i_effect.xy) - in.i_effect.xy,
+        0.0
+    );
+    out.quad_position = vertex_position.xy;
+    out.outline_size = in.i_effect.xy;
+    out.uv_offset_scale = in.i_uv_offset_scale;
+#endif
+
+    let model = affine3_to_square(mat3x4<f32>(
         in.i_model_transpose_col0,
         in.i_model_transpose_col1,
         in.i_model_transpose_col2,
-    )) * vec4<f32>(vertex_position, 1.0);
+    ));
+    let world_position = model * vec4<f32>(vertex_position, 1.0);
+    out.clip_position = view.view_proj * world_position;
+#ifdef SPRITE_PIXEL_SNAP
+    // Move the whole quad so that its origin is a whole number of pixels away from the camera.
+    // Rounding each corner on its own would make the sprite grow and shrink by a pixel as the
+    // camera moves.
+    let origin = view.view_proj * model * vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    let half_viewport_size = 0.5 * view.viewport.zw;
+    let pixel_offset = origin.xy / origin.w * half_viewport_size;
+    let snap = (round(pixel_offset) - pixel_offset) / half_viewport_size;
+    out.clip_position = vec4<f32>(
+        out.clip_position.xy + snap * out.clip_position.w,
+        out.clip_position.zw,
+    );
+#endif
     out.uv = vec2<f32>(vertex_position.xy) * in.i_uv_offset_scale.zw + in.i_uv_offset_scale.xy;
     out.color = in.i_color;
+    out.emissive = in.i_emissive;
+#ifdef SPRITE_BINDLESS
+    out.texture_index = u32(in.i_effect.z);
+#endif
+#ifdef SPRITE_ALPHA_MASK
+    out.alpha_cutoff = in.i_effect.w;
+#endif
+#ifdef SPRITE_LIGHTING
+    out.world_position = world_position.xy;
+    // The UVs go down the image, and are mirrored when the sprite is flipped.
+    out.tangent = normalize(model[0].xy) * sign(in.i_uv_offset_scale.z);
+    out.bitangent = normalize(model[1].xy) * -sign(in.i_uv_offset_scale.w);
+#endif
 
     return out;
 }
 
-@group(1) @binding(0) var sprite_texture: texture_2d<f32>;
-@group(1) @binding(1) var sprite_sampler: sampler;
+// Samples the image of the sprite.
+fn sample_sprite_texture(in: VertexOutput, uv: vec2<f32>) -> vec4<f32> {
+#ifdef SPRITE_BINDLESS
+    return textureSample(
+        sprite_texture_bindings::sprite_textures[in.texture_index],
+        sprite_texture_bindings::sprite_samplers[in.texture_index],
+        uv,
+    );
+#else
+    return textureSample(
+        sprite_texture_bindings::sprite_texture,
+        sprite_texture_bindings::sprite_sampler,
+        uv,
+    );
+#endif
+}
+
+// Multiplies the straight (not premultiplied) color of the texture by the tint.
+fn tint(color: vec4<f32>, texture_color: vec4<f32>) -> vec4<f32> {
+#ifdef SRGB_COLORS
+    // The tint is sRGB-encoded and applied to the sRGB-encoded texture color, like image editors do.
+    let rgb = srgb_to_linear(color.rgb * linear_to_srgb(texture_color.rgb));
+    return vec4<f32>(rgb, color.a * texture_color.a);
+#else
+    return color * texture_color;
+#endif
+}
+
+#ifdef SPRITE_LIGHTING
+// Returns the light reaching the sprite at the fragment, from the ambient light and the point
+// lights, shaded with its normal.
+fn lighting(in: VertexOutput, normal_map_color: vec4<f32>) -> vec3<f32> {
+    // The normal map is in the space of the image, with its Z axis facing the camera.
+    let image_normal = normal_map_color.xyz * 2.0 - 1.0;
+    let normal = normalize(vec3<f32>(
+        in.tangent * image_normal.x + in.bitangent * image_normal.y,
+        image_normal.z,
+    ));
+
+    var light = lights.ambient_color.rgb;
+    for (var i = 0u; i < lights.point_light_count; i++) {
+        let point_light = lights.point_lights[i];
+        let to_light = vec3<f32>(
+            point_light.position_height_radius.xy - in.world_position,
+            point_light.position_height_radius.z,
+        );
+        let distance_factor = length(to_light.xy) / point_light.position_height_radius.w;
+        let attenuation = pow(saturate(1.0 - distance_factor * distance_factor), 2.0);
+        let diffuse = max(dot(normal, normalize(to_light)), 0.0);
+        light += point_light.color.rgb * attenuation * diffuse;
+    }
+    return light;
+}
+#endif
+
+#ifdef SPRITE_OUTLINE
+const OUTLINE_DIRECTIONS: u32 = 8u;
+
+// Returns the alpha of the image at a position in its quad, transparent outside of the image.
+fn image_alpha(in: VertexOutput, quad_position: vec2<f32>) -> f32 {
+    let uv = quad_position * in.uv_offset_scale.zw + in.uv_offset_scale.xy;
+    // Sample the level 0 explicitly, since sampling in non-uniform control flow is not allowed.
+#ifdef SPRITE_BINDLESS
+    let alpha = textureSampleLevel(
+        sprite_texture_bindings::sprite_textures[in.texture_index],
+        sprite_texture_bindings::sprite_samplers[in.texture_index],
+        uv,
+        0.0,
+    ).a;
+#else
+    let alpha = textureSampleLevel(
+        sprite_texture_bindings::sprite_texture,
+        sprite_texture_bindings::sprite_sampler,
+        uv,
+        0.0,
+    ).a;
+#endif
+    let inside = all(quad_position >= vec2<f32>(0.0)) && all(quad_position <= vec2<f32>(1.0));
+    return select(0.0, alpha, inside);
+}
+
+// Returns the highest alpha of the image within the outline size of the fragment.
+//
+// The image is sampled in 8 directions at the outline size, and in the 8 directions in between
+// them at half of the outline size, to catch the features thinner than the gaps of the outer
+// samples. Opaque fragments stop sampling early, which skips most of the samples inside of the
+// image.
+fn outline_alpha(in: VertexOutput) -> f32 {
+    var alpha = image_alpha(in, in.quad_position);
+    for (var i = 0u; i < OUTLINE_DIRECTIONS && alpha < 1.0; i++) {
+        let angle = f32(i) * 6.2831855 / f32(OUTLINE_DIRECTIONS);
+        let offset = vec2<f32>(cos(angle), sin(angle)) * in.outline_size;
+        let inner_angle = angle + 3.1415927 / f32(OUTLINE_DIRECTIONS);
+        let inner_offset = vec2<f32>(cos(inner_angle), sin(inner_angle)) * in.outline_size * 0.5;
+        alpha = max(alpha, image_alpha(in, in.quad_position + offset));
+        alpha = max(alpha, image_alpha(in, in.quad_position + inner_offset));
+    }
+    return alpha;
+}
+#endif
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+#ifdef SPRITE_LIGHTING
+    let normal_map_color = textureSample(
+        sprite_texture_bindings::sprite_normal_map,
+        sprite_texture_bindings::sprite_normal_map_sampler,
+        in.uv,
+    );
+#endif
+
+#ifdef SPRITE_OUTLINE
+    // The outline is drawn behind the sprite, so it does not need to exclude the image.
+    var color = tint(in.color, vec4<f32>(1.0, 1.0, 1.0, outline_alpha(in)));
+#ifdef PREMULTIPLIED_ALPHA
+    color = vec4<f32>(color.rgb * color.a, color.a);
+#endif
+#else ifdef SPRITE_SILHOUETTE
+    let texture_color = sample_sprite_texture(in, in.uv);
+    var color = tint(in.color, vec4<f32>(1.0, 1.0, 1.0, texture_color.a));
+#ifdef PREMULTIPLIED_ALPHA
+    color = vec4<f32>(color.rgb * color.a, color.a);
+#endif
+#else
+    let texture_color = sample_sprite_texture(in, in.uv);
+
+#ifdef PREMULTIPLIED_ALPHA
+    // The texture is premultiplied, so the tint has to be premultiplied as well. This keeps the
+    // color of additive texels, whose alpha is 0.
+#ifdef SRGB_COLORS
+    // The sRGB tint is applied to the straight color of the texture, which additive texels don't
+    // have, so they are multiplied by the linear tint.
+    var color = vec4<f32>(srgb_to_linear(in.color.rgb) * in.color.a, in.color.a) * texture_color;
+    if texture_color.a > 0.0 {
+        color = tint(in.color, vec4<f32>(texture_color.rgb / texture_color.a, texture_color.a));
+        color = vec4<f32>(color.rgb * color.a, color.a);
+    }
+#else
+    var color = vec4<f32>(in.color.rgb * in.color.a, in.color.a) * texture_color;
+#endif
+#else
+    var color = tint(in.color, texture_color);
+#endif
+#endif
+
+#ifdef SPRITE_LIGHTING
+    // The color is linear here, including with `SRGB_COLORS`, so the light is added physically.
+    color = vec4<f32>(color.rgb * lighting(in, normal_map_color), color.a);
+#endif
+    // The emissive strength scales the linear color returned by `tint`, rather than the
+    // sRGB-encoded tint with `SRGB_COLORS`, so that it scales the light emitted by the sprite in
+    // both cases. It can exceed 1.0 on HDR targets.
+    color = vec4<f32>(color.rgb * in.emissive, color.a);
+
+#ifdef SPRITE_ALPHA_MASK
+    if color.a < in.alpha_cutoff {
+        discard;
+    }
+#endif
+#ifdef SPRITE_OPAQUE
+    // Opaque and alpha masked sprites are drawn without blending
+    color.a = 1.0;
+#endif
 
 #ifdef TONEMAP_IN_SHADER
+#ifdef PREMULTIPLIED_ALPHA
+    // Tonemapping operates on straight colors.
+    if color.a > 0.0 {
+        color = vec4<f32>(color.rgb / color.a, color.a);
+        color = tonemapping::tone_mapping(color, view.color_grading);
+        color = vec4<f32>(color.rgb * color.a, color.a);
+    }
+#else
     color = tonemapping::tone_mapping(color, view.color_grading);
+#endif
 #endif
 
     return color;
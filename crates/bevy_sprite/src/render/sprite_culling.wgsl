@@ -0,0 +1,284 @@
+// Culls the sprites of the views with `GpuCulling` against the frustum of their view.
+//
+// The sprites are culled in chunks of `WORKGROUP_SIZE` sprites, in three passes so that the
+// visible sprites keep their order, and transparent sprites are still drawn back to front:
+// - `count_visible` counts the visible sprites of each chunk.
+// - `scan_chunks` turns the counts of the chunks of each batch into the offsets of their visible
+//   sprites in the batch, and writes the number of visible sprites to the indirect parameters of
+//   the batch.
+// - `write_visible` builds the instances of the visible sprites of each chunk, and writes them to
+//   `culled_instances` at the offset of the chunk.
+
+#import bevy_render::color_operations::linear_to_srgb
+
+struct CulledSprite {
+    model_transpose_col0: vec4<f32>,
+    model_transpose_col1: vec4<f32>,
+    model_transpose_col2: vec4<f32>,
+    color: vec4<f32>,
+    // The area of the image displayed by the sprite, in pixels
+    rect: vec4<f32>,
+    uv_offset_scale: vec4<f32>,
+    custom_size_anchor: vec4<f32>,
+    image_size_outline: vec4<f32>,
+    flags: u32,
+    texture_index: u32,
+    alpha_cutoff: f32,
+    emissive: f32,
+}
+
+const CULLED_SPRITE_FLAGS_FLIP_X: u32 = 1u;
+const CULLED_SPRITE_FLAGS_FLIP_Y: u32 = 2u;
+const CULLED_SPRITE_FLAGS_CUSTOM_SIZE: u32 = 4u;
+const CULLED_SPRITE_FLAGS_SRGB_COLOR: u32 = 8u;
+
+struct SpriteInstance {
+    model_transpose_col0: vec4<f32>,
+    model_transpose_col1: vec4<f32>,
+    model_transpose_col2: vec4<f32>,
+    color: vec4<f32>,
+    uv_offset_scale: vec4<f32>,
+    // NOTE: xy is the size of the outline on each side, relative to the size of the quad.
+    effect: vec4<f32>,
+    emissive: f32,
+}
+
+struct CulledSpriteBatch {
+    first_instance: u32,
+    instance_count: u32,
+    first_chunk: u32,
+    chunk_count: u32,
+    view_index: u32,
+}
+
+struct SpriteCullingFrustum {
+    half_spaces: array<vec4<f32>, 6>,
+}
+
+struct IndirectParameters {
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: u32,
+    first_instance: u32,
+}
+
+@group(0) @binding(0) var<storage> sprites: array<CulledSprite>;
+@group(0) @binding(1) var<storage, read_write> culled_instances: array<SpriteInstance>;
+@group(0) @binding(2) var<storage> batches: array<CulledSpriteBatch>;
+// The index of the batch of each chunk
+@group(0) @binding(3) var<storage> chunks: array<u32>;
+// The number of visible sprites of each chunk, replaced by their offset in the batch of the chunk
+@group(0) @binding(4) var<storage, read_write> chunk_offsets: array<u32>;
+@group(0) @binding(5) var<storage> frusta: array<SpriteCullingFrustum>;
+@group(0) @binding(6) var<storage, read_write> indirect_parameters: array<IndirectParameters>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+var<workgroup> prefix_sums: array<u32, WORKGROUP_SIZE>;
+
+// The workgroups are dispatched in a 2D grid, to fit in the limits of a dispatch.
+fn workgroup_index(workgroup_id: vec3<u32>, num_workgroups: vec3<u32>) -> u32 {
+    return workgroup_id.x + workgroup_id.y * num_workgroups.x;
+}
+
+// Returns the sum of the values of the invocations of the workgroup up to `local_index`.
+fn inclusive_prefix_sum(local_index: u32, value: u32) -> u32 {
+    prefix_sums[local_index] = value;
+    workgroupBarrier();
+    for (var offset = 1u; offset < WORKGROUP_SIZE; offset *= 2u) {
+        var sum = prefix_sums[local_index];
+        if local_index >= offset {
+            sum += prefix_sums[local_index - offset];
+        }
+        workgroupBarrier();
+        prefix_sums[local_index] = sum;
+        workgroupBarrier();
+    }
+    let sum = prefix_sums[local_index];
+    workgroupBarrier();
+    return sum;
+}
+
+// Returns a row of the model matrix of the quad of a sprite, from the same row of the transform
+// of the sprite.
+fn quad_model_row(row: vec4<f32>, quad_size: vec2<f32>, quad_offset: vec2<f32>) -> vec4<f32> {
+    return vec4<f32>(row.xy * quad_size, row.z, dot(row.xy, quad_offset) + row.w);
+}
+
+// Builds the instance of a sprite, like the instances of the sprites batched on the CPU.
+fn build_instance(sprite: CulledSprite) -> SpriteInstance {
+    let image_size = sprite.image_size_outline.xy;
+    let rect_size = sprite.rect.zw - sprite.rect.xy;
+
+    // Move the displayed area by the UV transform
+    let uv_min = sprite.rect.xy + sprite.uv_offset_scale.xy * rect_size;
+    let uv_size = rect_size * sprite.uv_offset_scale.zw;
+    var uv_offset_scale = vec4<f32>(
+        uv_min.x / image_size.x,
+        (uv_min.y + uv_size.y) / image_size.y,
+        uv_size.x / image_size.x,
+        -uv_size.y / image_size.y,
+    );
+    if (sprite.flags & CULLED_SPRITE_FLAGS_FLIP_X) != 0u {
+        uv_offset_scale.x += uv_offset_scale.z;
+        uv_offset_scale.z *= -1.0;
+    }
+    if (sprite.flags & CULLED_SPRITE_FLAGS_FLIP_Y) != 0u {
+        uv_offset_scale.y += uv_offset_scale.w;
+        uv_offset_scale.w *= -1.0;
+    }
+
+    let quad_size = select(
+        rect_size,
+        sprite.custom_size_anchor.xy,
+        (sprite.flags & CULLED_SPRITE_FLAGS_CUSTOM_SIZE) != 0u,
+    );
+    let outline = sprite.image_size_outline.zw;
+    let outline_size = select(vec2<f32>(0.0), outline / quad_size, any(outline != vec2<f32>(0.0)));
+    let quad_offset = quad_size * (-sprite.custom_size_anchor.zw - vec2<f32>(0.5));
+
+    var color = sprite.color;
+    if (sprite.flags & CULLED_SPRITE_FLAGS_SRGB_COLOR) != 0u {
+        color = vec4<f32>(linear_to_srgb(color.rgb), color.a);
+    }
+
+    var instance: SpriteInstance;
+    instance.model_transpose_col0 = quad_model_row(sprite.model_transpose_col0, quad_size, quad_offset);
+    instance.model_transpose_col1 = quad_model_row(sprite.model_transpose_col1, quad_size, quad_offset);
+    instance.model_transpose_col2 = quad_model_row(sprite.model_transpose_col2, quad_size, quad_offset);
+    instance.color = color;
+    instance.uv_offset_scale = uv_offset_scale;
+    instance.effect = vec4<f32>(outline_size, f32(sprite.texture_index), sprite.alpha_cutoff);
+    instance.emissive = sprite.emissive;
+    return instance;
+}
+
+// Returns true if the quad of the instance, grown to fit its outline, isn't entirely outside of
+// one of the half spaces of the frustum.
+fn is_visible(instance: SpriteInstance, frustum: SpriteCullingFrustum) -> bool {
+    let quad_min = -instance.effect.xy;
+    let quad_max = vec2<f32>(1.0) + instance.effect.xy;
+    var corners: array<vec3<f32>, 4>;
+    for (var i = 0u; i < 4u; i += 1u) {
+        let local = vec4<f32>(
+            select(quad_min.x, quad_max.x, (i & 1u) != 0u),
+            select(quad_min.y, quad_max.y, (i & 2u) != 0u),
+            0.0,
+            1.0,
+        );
+        corners[i] = vec3<f32>(
+            dot(instance.model_transpose_col0, local),
+            dot(instance.model_transpose_col1, local),
+            dot(instance.model_transpose_col2, local),
+        );
+    }
+
+    for (var i = 0u; i < 6u; i += 1u) {
+        let half_space = frustum.half_spaces[i];
+        var inside = false;
+        for (var j = 0u; j < 4u; j += 1u) {
+            inside = inside || dot(half_space, vec4<f32>(corners[j], 1.0)) > 0.0;
+        }
+        if !inside {
+            return false;
+        }
+    }
+    return true;
+}
+
+// Builds the instance of the sprite of the chunk at `local_index`, and returns true if there is
+// such a sprite and it is visible.
+fn build_visible_instance(
+    batch: CulledSpriteBatch,
+    chunk_index: u32,
+    local_index: u32,
+    instance: ptr<function, SpriteInstance>,
+) -> bool {
+    let index = (chunk_index - batch.first_chunk) * WORKGROUP_SIZE + local_index;
+    if index >= batch.instance_count {
+        return false;
+    }
+    *instance = build_instance(sprites[batch.first_instance + index]);
+    return is_visible(*instance, frusta[batch.view_index]);
+}
+
+@compute
+@workgroup_size(64)
+fn count_visible(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let chunk_index = workgroup_index(workgroup_id, num_workgroups);
+    if chunk_index >= arrayLength(&chunks) {
+        return;
+    }
+    let batch = batches[chunks[chunk_index]];
+
+    var instance: SpriteInstance;
+    let visible = build_visible_instance(batch, chunk_index, local_index, &instance);
+    let visible_count = inclusive_prefix_sum(local_index, u32(visible));
+    if local_index == WORKGROUP_SIZE - 1u {
+        chunk_offsets[chunk_index] = visible_count;
+    }
+}
+
+@compute
+@workgroup_size(64)
+fn scan_chunks(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let batch_index = workgroup_index(workgroup_id, num_workgroups);
+    if batch_index >= arrayLength(&batches) {
+        return;
+    }
+    let batch = batches[batch_index];
+
+    // Each invocation handles a contiguous range of the chunks of the batch
+    let chunks_per_invocation = (batch.chunk_count + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let first_chunk = batch.first_chunk + min(local_index * chunks_per_invocation, batch.chunk_count);
+    let end_chunk = batch.first_chunk +
+        min((local_index + 1u) * chunks_per_invocation, batch.chunk_count);
+    var visible_count = 0u;
+    for (var chunk = first_chunk; chunk < end_chunk; chunk += 1u) {
+        visible_count += chunk_offsets[chunk];
+    }
+
+    // The visible sprites of the range start after the ones of the previous invocations
+    let visible_prefix_sum = inclusive_prefix_sum(local_index, visible_count);
+    var offset = visible_prefix_sum - visible_count;
+    for (var chunk = first_chunk; chunk < end_chunk; chunk += 1u) {
+        let chunk_visible_count = chunk_offsets[chunk];
+        chunk_offsets[chunk] = offset;
+        offset += chunk_visible_count;
+    }
+
+    if local_index == WORKGROUP_SIZE - 1u {
+        indirect_parameters[batch_index].instance_count = visible_prefix_sum;
+    }
+}
+
+@compute
+@workgroup_size(64)
+fn write_visible(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let chunk_index = workgroup_index(workgroup_id, num_workgroups);
+    if chunk_index >= arrayLength(&chunks) {
+        return;
+    }
+    let batch = batches[chunks[chunk_index]];
+
+    var instance: SpriteInstance;
+    let visible = build_visible_instance(batch, chunk_index, local_index, &instance);
+    let visible_index = inclusive_prefix_sum(local_index, u32(visible));
+    if visible {
+        culled_instances[batch.first_instance + chunk_offsets[chunk_index] + visible_index - 1u] =
+            instance;
+    }
+}
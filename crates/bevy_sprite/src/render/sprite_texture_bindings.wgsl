@@ -0,0 +1,15 @@
+#define_import_path bevy_sprite::sprite_texture_bindings
+
+#ifdef SPRITE_BINDLESS
+// NOTE: The size of the arrays must match `MAX_SPRITE_BATCH_TEXTURES`.
+@group(1) @binding(0) var sprite_textures: binding_array<texture_2d<f32>, 16u>;
+@group(1) @binding(1) var sprite_samplers: binding_array<sampler, 16u>;
+#else
+@group(1) @binding(0) var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1) var sprite_sampler: sampler;
+#endif
+
+#ifdef SPRITE_LIGHTING
+@group(2) @binding(0) var sprite_normal_map: texture_2d<f32>;
+@group(2) @binding(1) var sprite_normal_map_sampler: sampler;
+#endif
@@ -0,0 +1,27 @@
+#define_import_path bevy_sprite::sprite_vertex_output
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+#ifdef SPRITE_OUTLINE
+    // The position in the quad of the image, from (0, 0) to (1, 1) inside of the image.
+    @location(2) quad_position: vec2<f32>,
+    @location(3) @interpolate(flat) outline_size: vec2<f32>,
+    @location(4) @interpolate(flat) uv_offset_scale: vec4<f32>,
+#endif
+#ifdef SPRITE_BINDLESS
+    // The index of the image of the sprite in the binding arrays of its batch.
+    @location(5) @interpolate(flat) texture_index: u32,
+#endif
+#ifdef SPRITE_ALPHA_MASK
+    @location(6) @interpolate(flat) alpha_cutoff: f32,
+#endif
+#ifdef SPRITE_LIGHTING
+    @location(7) world_position: vec2<f32>,
+    // The directions of the X and Y axes of the image in the world.
+    @location(8) @interpolate(flat) tangent: vec2<f32>,
+    @location(9) @interpolate(flat) bitangent: vec2<f32>,
+#endif
+    @location(10) @interpolate(flat) emissive: f32,
+};
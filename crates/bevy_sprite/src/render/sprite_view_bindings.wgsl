@@ -1,9 +1,25 @@
 #define_import_path bevy_sprite::sprite_view_bindings
 
-#import bevy_render::view::View
+#import bevy_render::{globals::Globals, view::View}
 
 @group(0) @binding(0) var<uniform> view: View;
 
 @group(0) @binding(1) var dt_lut_texture: texture_3d<f32>;
 @group(0) @binding(2) var dt_lut_sampler: sampler;
 
+@group(0) @binding(3) var<uniform> globals: Globals;
+
+struct PointLight2d {
+    // The position of the light on the plane of the sprites, its height above them, and its radius.
+    position_height_radius: vec4<f32>,
+    color: vec4<f32>,
+};
+
+struct Lights2d {
+    // NOTE: The size of the array must match `MAX_POINT_LIGHTS_2D`.
+    point_lights: array<PointLight2d, 64u>,
+    ambient_color: vec4<f32>,
+    point_light_count: u32,
+};
+
+@group(0) @binding(4) var<uniform> lights: Lights2d;
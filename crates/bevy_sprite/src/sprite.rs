@@ -1,15 +1,26 @@
+use bevy_asset::Handle;
 use bevy_color::Color;
-use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_ecs::{
+    component::Component,
+    reflect::{ReflectComponent, ReflectResource},
+    system::Resource,
+};
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{extract_resource::ExtractResource, texture::Image, view::Visibility};
+use bevy_transform::components::Transform;
 
 use crate::TextureSlicer;
 
 /// Specifies the rendering properties of a sprite.
 ///
 /// This is commonly used as a component within [`SpriteBundle`](crate::bundle::SpriteBundle).
-#[derive(Component, Debug, Default, Clone, Reflect)]
+///
+/// [`Transform`] and [`Visibility`] are required components of `Sprite`, so they are inserted
+/// automatically when missing.
+#[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Default)]
+#[require(Transform, Visibility)]
 #[repr(C)]
 pub struct Sprite {
     /// The sprite's color tint
@@ -29,6 +40,253 @@ pub struct Sprite {
     pub rect: Option<Rect>,
     /// [`Anchor`] point of the sprite in the world
     pub anchor: Anchor,
+    /// Whether the sprite's image stores colors with premultiplied alpha.
+    ///
+    /// Images exported with premultiplied alpha show dark fringes around their transparent
+    /// edges when blended as straight alpha; setting this makes the sprite use premultiplied
+    /// alpha blending instead.
+    pub premultiplied_alpha: bool,
+    /// How the transparency of the sprite is handled
+    pub alpha_mode: AlphaMode2d,
+    /// The offset of the image in the sprite, relative to the size of the [`rect`](Self::rect),
+    /// or of the image if there is none.
+    ///
+    /// Animating the offset scrolls the image, for example for parallax backgrounds.
+    pub uv_offset: Vec2,
+    /// How many times the image fits in the sprite along each axis.
+    ///
+    /// The size of the sprite is unchanged, so values above `1.0` tile the image over the sprite
+    /// when [`uv_repeat`](Self::uv_repeat) is set.
+    pub uv_scale: Vec2,
+    /// Whether the image repeats when [`uv_offset`](Self::uv_offset) and
+    /// [`uv_scale`](Self::uv_scale) go past its edges. Otherwise, the edge pixels are stretched.
+    ///
+    /// The whole image repeats, even when only a [`rect`](Self::rect) or a section of a
+    /// [`TextureAtlas`](crate::TextureAtlas) is displayed. With [`SpriteSampler::Image`], the
+    /// image is drawn with a repeating sampler using the same magnification filter as its own.
+    pub uv_repeat: bool,
+    /// An optional normal map shading the sprite, for cameras with an
+    /// [`AmbientLight2d`](crate::AmbientLight2d).
+    ///
+    /// The normal map is sampled with the same coordinates as the image, so it must have the
+    /// same layout. It must be stored in a linear (not sRGB) texture, with its green channel
+    /// pointing up the image (the OpenGL convention). Lit sprites without a normal map are
+    /// shaded as if they were facing the camera.
+    pub normal_map: Option<Handle<Image>>,
+    /// Multiplies the color of the sprite, after its [`color`](Self::color) tint and its
+    /// lighting, in linear space.
+    ///
+    /// This is also in linear space when the tint is applied to sRGB-encoded colors, so doubling
+    /// it doubles the light emitted by the sprite rather than its sRGB-encoded color.
+    ///
+    /// Values above `1.0` make the sprite brighter than white on cameras with
+    /// [`hdr`](bevy_render::camera::Camera::hdr) enabled, which makes it glow with
+    /// [`BloomSettings`](bevy_core_pipeline::bloom::BloomSettings).
+    /// Without HDR, the color is clamped to `1.0` when it is written. The outline and shadow of
+    /// the sprite are not affected.
+    pub emissive: f32,
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            color: Color::default(),
+            flip_x: false,
+            flip_y: false,
+            custom_size: None,
+            rect: None,
+            anchor: Anchor::default(),
+            premultiplied_alpha: false,
+            alpha_mode: AlphaMode2d::default(),
+            uv_offset: Vec2::ZERO,
+            uv_scale: Vec2::ONE,
+            uv_repeat: false,
+            normal_map: None,
+            emissive: 1.0,
+        }
+    }
+}
+
+/// How the transparency of a [`Sprite`] is handled.
+///
+/// Opaque and alpha masked sprites are drawn front to back with depth writes before the
+/// transparent ones, so the pixels they hide are not drawn. This saves the cost of drawing the
+/// pixels of large sprites, such as backgrounds, that end up hidden.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum AlphaMode2d {
+    /// The sprite is blended with what is behind it, using its alpha.
+    #[default]
+    Blend,
+    /// The pixels with an alpha below the threshold are discarded, the others are drawn opaque.
+    Mask(f32),
+    /// The alpha of the sprite is ignored, and the whole sprite is drawn opaque.
+    Opaque,
+}
+
+/// Draws an outline around the visible pixels of a [`Sprite`]'s image.
+///
+/// The outline is drawn right behind the sprite, and follows its transparency: fully
+/// transparent pixels are outside of the shape. This is not supported for sprites using an
+/// [`ImageScaleMode`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpriteOutline {
+    /// The width of the outline, in the same unit as [`Sprite::custom_size`]: pixels of the
+    /// image when the sprite has no custom size.
+    pub thickness: f32,
+    /// The color of the outline.
+    pub color: Color,
+}
+
+impl Default for SpriteOutline {
+    fn default() -> Self {
+        Self {
+            thickness: 1.0,
+            color: Color::WHITE,
+        }
+    }
+}
+
+/// Draws a drop shadow behind a [`Sprite`]: a silhouette of its image, filled with a single
+/// color.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpriteShadow {
+    /// The offset of the shadow from the sprite, in world units.
+    pub offset: Vec2,
+    /// The color of the shadow. Its alpha multiplies the transparency of the image.
+    pub color: Color,
+}
+
+impl Default for SpriteShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::new(4.0, -4.0),
+            color: Color::srgba(0.0, 0.0, 0.0, 0.5),
+        }
+    }
+}
+
+/// Turns a [`Sprite`] into a mask: instead of being drawn, it clips the sprites of its group to
+/// the visible pixels of its image.
+///
+/// The descendants of the mask without a [`SpriteMaskInteraction`] are only visible inside of
+/// it, for example the map in the frame of a minimap. Other sprites are clipped by a
+/// [`SpriteMaskInteraction`] with the group of the mask.
+///
+/// A mask only clips the sprites drawn after it in the transparent pass: the sprites at its depth
+/// or in front of it, such as its children, or with a higher [`SpriteSortKey`]. Clipped sprites are always drawn in the transparent
+/// pass for this reason, even when they are opaque. Where masks of different groups overlap, the
+/// last one drawn wins. Masks don't draw the [`SpriteOutline`] and [`SpriteShadow`] of their
+/// sprite.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct SpriteMask {
+    /// The group of the mask, from `1` to `255`. The pixels outside of every mask are in the
+    /// group `0`.
+    pub group: u8,
+    /// The alpha below which the pixels of the image are outside of the mask.
+    pub alpha_cutoff: f32,
+}
+
+impl Default for SpriteMask {
+    fn default() -> Self {
+        Self {
+            group: 1,
+            alpha_cutoff: 0.5,
+        }
+    }
+}
+
+/// How a [`Sprite`] is clipped by the [`SpriteMask`]s.
+///
+/// Without this component, the descendants of a mask are only visible inside of it.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum SpriteMaskInteraction {
+    /// The sprite is not clipped, even when it is a descendant of a mask.
+    #[default]
+    None,
+    /// The sprite is only visible inside of the masks of the group.
+    VisibleInsideMask(u8),
+    /// The sprite is only visible outside of the masks of the group.
+    VisibleOutsideMask(u8),
+}
+
+/// Overrides the sampler of the image of a [`Sprite`].
+///
+/// Images are sampled with the sampler set in their [`Image::sampler`], or the default one of the
+/// `ImagePlugin`. This lets pixel art sprites use nearest filtering while the other sprites stay
+/// linear, even when they share an image. Sprites using different samplers aren't batched
+/// together.
+///
+/// [`Image::sampler`]: bevy_render::texture::Image::sampler
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum SpriteSampler {
+    /// Uses the sampler of the image.
+    #[default]
+    Image,
+    /// Uses nearest filtering, which keeps pixel art sharp.
+    Nearest,
+    /// Uses linear filtering.
+    Linear,
+}
+
+/// Overrides the depth used to sort a [`Sprite`] with the other 2d items, instead of its position
+/// along the view axis.
+///
+/// This keeps the [`Transform`](bevy_transform::components::Transform) of sprites purely
+/// spatial, and can be used to draw them by layer. The key is compared with the depth of the
+/// other items, which is the `Z` translation for cameras looking down the `-Z` axis. Sprites
+/// sharing the same key are grouped by image, so they can be batched together.
+///
+/// Opaque and alpha masked sprites are still depth tested with their translation: the key only
+/// changes the order in which they are drawn.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, PartialOrd, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct SpriteSortKey(pub f32);
+
+/// The color space in which [`Sprite::color`] tints the image of sprites, and in which the vertex
+/// colors of 2D meshes are interpreted.
+///
+/// This applies to all sprites and 2D meshes, and can be changed at any time.
+#[derive(Resource, ExtractResource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default)]
+pub enum SpriteColorSpace {
+    /// The colors are applied in linear space.
+    ///
+    /// [`Sprite::color`] multiplies the linear color of the image. This is physically correct,
+    /// but tints look lighter than the same tint applied in an image editor. Vertex colors are
+    /// interpreted as linear colors.
+    #[default]
+    Linear,
+    /// The colors are applied in sRGB space, the way image editors apply them.
+    ///
+    /// [`Sprite::color`] multiplies the sRGB-encoded color of the image, so a tint picked as a hex
+    /// color looks the same as in an image editor. Vertex colors are interpreted as sRGB-encoded
+    /// colors, and converted to linear colors.
+    Srgb,
+}
+
+/// Whether sprites are snapped to the pixels of the render target.
+///
+/// Sprites at sub-pixel positions are sampled between the pixels of their image, which makes
+/// pixel art shimmer as they move. Snapping moves every sprite so that its corner is a whole
+/// number of pixels away from the camera, so the pixels of the image stay aligned with the
+/// pixels of the screen and the sprites don't jitter as the camera moves.
+///
+/// This applies to all sprites, including the ones using a
+/// [`SpriteMaterial`](crate::SpriteMaterial), and can be changed at any time.
+#[derive(Resource, ExtractResource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default)]
+pub enum SpritePixelSnap {
+    /// The sprites are drawn at their exact position.
+    #[default]
+    Disabled,
+    /// The sprites are moved to the nearest pixel of the render target, relative to the camera.
+    Enabled,
 }
 
 /// Controls how the image is altered when scaled.
@@ -0,0 +1,287 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+
+use crate::TextureAtlas;
+
+/// A frame of a [`SpriteAnimation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct AnimationFrame {
+    /// The index of the frame in the [`TextureAtlasLayout`](crate::TextureAtlasLayout).
+    pub index: usize,
+    /// How long the frame is displayed.
+    pub duration: Duration,
+}
+
+/// How a [`SpriteAnimation`] continues once its last frame is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum AnimationMode {
+    /// Starts again from the first frame.
+    #[default]
+    Loop,
+    /// Stops on the last frame.
+    Once,
+    /// Plays the frames backward down to the first frame, then forward again.
+    PingPong,
+}
+
+/// Animates a sprite by advancing the [`index`](TextureAtlas::index) of its [`TextureAtlas`]
+/// through a list of frames.
+///
+/// ```
+/// # use bevy_sprite::{AnimationMode, SpriteAnimation};
+/// # use std::time::Duration;
+/// // Plays the frames 0 to 5 of the atlas at 10 frames per second, then stops.
+/// let animation = SpriteAnimation::from_range(0..=5, Duration::from_millis(100))
+///     .with_mode(AnimationMode::Once);
+/// ```
+///
+/// A [`SpriteAnimationEvent`] is sent every time the animation loops or finishes.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpriteAnimation {
+    /// The frames of the animation, in play order.
+    pub frames: Vec<AnimationFrame>,
+    /// What happens once the last frame is reached.
+    pub mode: AnimationMode,
+    /// The speed multiplier of the animation. Negative values are treated as `0.0`.
+    pub speed: f32,
+    /// Stops the animation on its current frame while set.
+    pub paused: bool,
+    current: usize,
+    elapsed: Duration,
+    backward: bool,
+    finished: bool,
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            mode: AnimationMode::Loop,
+            speed: 1.0,
+            paused: false,
+            current: 0,
+            elapsed: Duration::ZERO,
+            backward: false,
+            finished: false,
+        }
+    }
+}
+
+/// Sent by [`SpriteAnimation`]s when they reach the end of their frames.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpriteAnimationEvent {
+    /// The animation of this entity started again from its first frame, either in
+    /// [`AnimationMode::Loop`] or [`AnimationMode::PingPong`].
+    Looped(Entity),
+    /// The animation of this entity reached its last frame in [`AnimationMode::Once`].
+    Finished(Entity),
+}
+
+/// What happened while ticking a [`SpriteAnimation`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct AnimationTick {
+    looped: bool,
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    /// Creates an animation from a list of frames.
+    pub fn from_frames(frames: impl IntoIterator<Item = AnimationFrame>) -> Self {
+        Self {
+            frames: frames.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an animation playing the atlas indices in `range`, each one displayed for
+    /// `frame_duration`.
+    pub fn from_range(range: RangeInclusive<usize>, frame_duration: Duration) -> Self {
+        Self::from_frames(range.map(|index| AnimationFrame {
+            index,
+            duration: frame_duration,
+        }))
+    }
+
+    /// Sets the [`AnimationMode`] of the animation.
+    #[must_use]
+    pub const fn with_mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the speed multiplier of the animation.
+    #[must_use]
+    pub const fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The position of the current frame in [`frames`](Self::frames).
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// The atlas index of the current frame, if the animation has any frame.
+    pub fn current_index(&self) -> Option<usize> {
+        self.frames.get(self.current).map(|frame| frame.index)
+    }
+
+    /// Returns `true` if the animation reached its last frame in [`AnimationMode::Once`].
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Restarts the animation from its first frame.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.elapsed = Duration::ZERO;
+        self.backward = false;
+        self.finished = false;
+    }
+
+    /// Advances the animation by `delta`, scaled by its speed.
+    fn tick(&mut self, delta: Duration) -> AnimationTick {
+        let mut tick = AnimationTick::default();
+        if self.paused || self.finished || self.frames.is_empty() {
+            return tick;
+        }
+        // Frames without duration would never let the loop below end
+        if self.frames.iter().all(|frame| frame.duration.is_zero()) {
+            return tick;
+        }
+        self.current = self.current.min(self.frames.len() - 1);
+        self.elapsed += delta.mul_f32(self.speed.max(0.0));
+
+        while self.elapsed >= self.frames[self.current].duration {
+            self.elapsed -= self.frames[self.current].duration;
+            self.step(&mut tick);
+            if self.finished {
+                self.elapsed = Duration::ZERO;
+                break;
+            }
+        }
+        tick
+    }
+
+    /// Moves to the next frame.
+    fn step(&mut self, tick: &mut AnimationTick) {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            AnimationMode::Loop => {
+                if self.current == last {
+                    self.current = 0;
+                    tick.looped = true;
+                } else {
+                    self.current += 1;
+                }
+            }
+            AnimationMode::Once => {
+                if self.current == last {
+                    self.finished = true;
+                    tick.finished = true;
+                } else {
+                    self.current += 1;
+                }
+            }
+            AnimationMode::PingPong => {
+                if last == 0 {
+                    tick.looped = true;
+                } else if self.backward {
+                    self.current -= 1;
+                    if self.current == 0 {
+                        self.backward = false;
+                        tick.looped = true;
+                    }
+                } else {
+                    self.current += 1;
+                    if self.current == last {
+                        self.backward = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System advancing the [`SpriteAnimation`]s and updating the [`TextureAtlas`] of their sprites.
+pub fn animate_sprites(
+    time: Res<Time>,
+    mut animations: Query<(Entity, &mut SpriteAnimation, &mut TextureAtlas)>,
+    mut events: EventWriter<SpriteAnimationEvent>,
+) {
+    for (entity, mut animation, mut atlas) in &mut animations {
+        if animation.paused || animation.finished {
+            continue;
+        }
+        let tick = animation.tick(time.delta());
+        if tick.looped {
+            events.send(SpriteAnimationEvent::Looped(entity));
+        }
+        if tick.finished {
+            events.send(SpriteAnimationEvent::Finished(entity));
+        }
+        // Only trigger change detection when the frame changed
+        if let Some(index) = animation.current_index() {
+            if atlas.index != index {
+                atlas.index = index;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(100);
+
+    fn indices(animation: &mut SpriteAnimation, ticks: usize) -> Vec<usize> {
+        (0..ticks)
+            .map(|_| {
+                animation.tick(FRAME);
+                animation.current_index().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loop_mode() {
+        let mut animation = SpriteAnimation::from_range(3..=5, FRAME);
+        assert_eq!(indices(&mut animation, 4), vec![4, 5, 3, 4]);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn once_mode() {
+        let mut animation =
+            SpriteAnimation::from_range(0..=2, FRAME).with_mode(AnimationMode::Once);
+        assert_eq!(indices(&mut animation, 2), vec![1, 2]);
+        let tick = animation.tick(FRAME);
+        assert!(tick.finished);
+        assert!(animation.is_finished());
+        assert_eq!(animation.current_index(), Some(2));
+
+        animation.reset();
+        assert_eq!(animation.current_index(), Some(0));
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_mode() {
+        let mut animation =
+            SpriteAnimation::from_range(0..=2, FRAME).with_mode(AnimationMode::PingPong);
+        assert_eq!(indices(&mut animation, 5), vec![1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn long_delta_skips_frames() {
+        let mut animation = SpriteAnimation::from_range(0..=3, FRAME).with_speed(2.0);
+        let tick = animation.tick(FRAME * 3);
+        assert!(tick.looped);
+        assert_eq!(animation.current_index(), Some(2));
+    }
+}
@@ -155,6 +155,27 @@ impl TextureAtlas {
         let atlas = texture_atlases.get(&self.layout)?;
         atlas.textures.get(self.index).copied()
     }
+
+    /// Moves the atlas to `new_layout` if it points to `old_layout`, increasing its section
+    /// `index` by `offset`, and returns whether it was moved.
+    ///
+    /// This patches the atlases of an atlas merged into a bigger one with
+    /// [`TextureAtlasBuilder::add_atlas`], which returns the `offset` of its sections.
+    ///
+    /// [`TextureAtlasBuilder::add_atlas`]: crate::TextureAtlasBuilder::add_atlas
+    pub fn remap(
+        &mut self,
+        old_layout: impl Into<AssetId<TextureAtlasLayout>>,
+        new_layout: &Handle<TextureAtlasLayout>,
+        offset: usize,
+    ) -> bool {
+        if self.layout.id() != old_layout.into() {
+            return false;
+        }
+        self.layout = new_layout.clone();
+        self.index += offset;
+        true
+    }
 }
 
 impl From<Handle<TextureAtlasLayout>> for TextureAtlas {
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use bevy_asset::AssetId;
 use bevy_math::{URect, UVec2};
 use bevy_render::{
@@ -21,6 +23,8 @@ pub enum TextureAtlasBuilderError {
     NotEnoughSpace,
     #[error("added a texture with the wrong format in an atlas")]
     WrongFormat,
+    #[error("added an atlas whose texture data isn't available in the main world, or doesn't match its layout")]
+    MissingAtlasData,
 }
 
 #[derive(Debug)]
@@ -29,7 +33,7 @@ pub enum TextureAtlasBuilderError {
 /// sprites.
 pub struct TextureAtlasBuilder<'a> {
     /// Collection of texture's asset id (optional) and image data to be packed into an atlas
-    textures_to_place: Vec<(Option<AssetId<Image>>, &'a Image)>,
+    textures_to_place: Vec<(Option<AssetId<Image>>, Cow<'a, Image>)>,
     /// The initial atlas size in pixels.
     initial_size: UVec2,
     /// The absolute maximum size of the texture atlas in pixels.
@@ -40,6 +44,8 @@ pub struct TextureAtlasBuilder<'a> {
     auto_format_conversion: bool,
     /// The amount of padding in pixels to add along the right and bottom edges of the texture rects.
     padding: UVec2,
+    /// The amount of pixels by which the edges of the textures are repeated around them.
+    extrude: u32,
 }
 
 impl Default for TextureAtlasBuilder<'_> {
@@ -51,6 +57,7 @@ impl Default for TextureAtlasBuilder<'_> {
             format: TextureFormat::Rgba8UnormSrgb,
             auto_format_conversion: true,
             padding: UVec2::ZERO,
+            extrude: 0,
         }
     }
 }
@@ -87,7 +94,82 @@ impl<'a> TextureAtlasBuilder<'a> {
     /// Optionally an asset id can be passed that can later be used with the texture layout to retrieve the index of this texture.
     /// The insertion order will reflect the index of the added texture in the finished texture atlas.
     pub fn add_texture(&mut self, image_id: Option<AssetId<Image>>, texture: &'a Image) {
-        self.textures_to_place.push((image_id, texture));
+        self.textures_to_place
+            .push((image_id, Cow::Borrowed(texture)));
+    }
+
+    /// Adds all the sections of an already built texture atlas, to add more textures to it or
+    /// pack it again with different settings.
+    ///
+    /// The sections are copied out of `atlas_texture`, and keep the asset ids they were added
+    /// with. Returns the index of the first section in the finished texture atlas: the section `i`
+    /// of `layout` will be found at the index `offset + i`, so the indices are unchanged when the
+    /// atlas is the first thing added to the builder.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// # use bevy_sprite::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_asset::*;
+    /// # use bevy_render::prelude::*;
+    ///
+    /// fn add_to_atlas(
+    ///     new_texture: Handle<Image>,
+    ///     atlas_layout: Handle<TextureAtlasLayout>,
+    ///     atlas_texture: Handle<Image>,
+    ///     mut textures: ResMut<Assets<Image>>,
+    ///     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    /// ) {
+    ///     let mut builder = TextureAtlasBuilder::default();
+    ///     builder
+    ///         .add_atlas(
+    ///             layouts.get(&atlas_layout).unwrap(),
+    ///             textures.get(&atlas_texture).unwrap(),
+    ///         )
+    ///         .unwrap();
+    ///     let image = textures.get(&new_texture).unwrap();
+    ///     builder.add_texture(Some(new_texture.id()), image);
+    ///     let (layout, texture) = builder.finish().unwrap();
+    ///     // Replacing the assets keeps the `TextureAtlas` indices of the existing sprites valid
+    ///     layouts.insert(&atlas_layout, layout);
+    ///     textures.insert(&atlas_texture, texture);
+    /// }
+    /// ```
+    ///
+    /// When merging several atlases, the [`TextureAtlas`](crate::TextureAtlas) components
+    /// pointing to `layout` must be moved to the new layout with
+    /// [`TextureAtlas::remap`](crate::TextureAtlas::remap), which increases their index by the
+    /// returned offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TextureAtlasBuilderError::MissingAtlasData`] if the data of `atlas_texture`
+    /// isn't available, because its usage doesn't contain [`RenderAssetUsages::MAIN_WORLD`], or
+    /// if a section of `layout` is outside of it. Nothing is added to the builder then.
+    pub fn add_atlas(
+        &mut self,
+        layout: &TextureAtlasLayout,
+        atlas_texture: &Image,
+    ) -> TextureAtlasBuilderResult<usize> {
+        let offset = self.textures_to_place.len();
+        let mut image_ids = vec![None; layout.len()];
+        for (image_id, index) in layout.texture_handles.iter().flatten() {
+            if let Some(slot) = image_ids.get_mut(*index) {
+                *slot = Some(*image_id);
+            }
+        }
+        let textures = layout
+            .textures
+            .iter()
+            .zip(image_ids)
+            .map(|(rect, image_id)| {
+                let texture = Self::copy_texture_from_atlas(atlas_texture, *rect)?;
+                Ok((image_id, Cow::Owned(texture)))
+            })
+            .collect::<TextureAtlasBuilderResult<Vec<_>>>()?;
+        self.textures_to_place.extend(textures);
+        Ok(offset)
     }
 
     /// Sets the amount of padding in pixels to add between the textures in the texture atlas.
@@ -98,16 +180,65 @@ impl<'a> TextureAtlasBuilder<'a> {
         self
     }
 
+    /// Sets the amount of pixels by which the edges of each texture are repeated around it in the
+    /// texture atlas.
+    ///
+    /// Linear filtering and mipmaps sample the pixels around the section of a texture, so the
+    /// sprites can bleed the colors of their neighbors on their edges. Extruding the edges makes
+    /// those samples read the color of the texture itself. The margin is not part of the sections
+    /// of the [`TextureAtlasLayout`], and is added before the [`padding`](Self::padding).
+    pub fn extrude(mut self, extrude: u32) -> Self {
+        self.extrude = extrude;
+        self
+    }
+
+    fn copy_texture_from_atlas(
+        atlas_texture: &Image,
+        rect: URect,
+    ) -> TextureAtlasBuilderResult<Image> {
+        let format = atlas_texture.texture_descriptor.format;
+        let format_size = format.pixel_size();
+        let atlas_size = atlas_texture.size();
+        // The data of images only used in the render world is dropped once they're uploaded
+        let atlas_data_size = (atlas_size.x * atlas_size.y) as usize * format_size;
+        if atlas_texture.data.len() < atlas_data_size
+            || rect.max.x > atlas_size.x
+            || rect.max.y > atlas_size.y
+        {
+            return Err(TextureAtlasBuilderError::MissingAtlasData);
+        }
+        let atlas_width = atlas_size.x as usize;
+        let rect_width = rect.width() as usize;
+        let mut data = Vec::with_capacity(rect_width * rect.height() as usize * format_size);
+        for y in rect.min.y as usize..rect.max.y as usize {
+            let begin = (y * atlas_width + rect.min.x as usize) * format_size;
+            data.extend_from_slice(&atlas_texture.data[begin..begin + rect_width * format_size]);
+        }
+        Ok(Image::new(
+            Extent3d {
+                width: rect.width(),
+                height: rect.height(),
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            format,
+            RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+        ))
+    }
+
     fn copy_texture_to_atlas(
         atlas_texture: &mut Image,
         texture: &Image,
         packed_location: &PackedLocation,
         padding: UVec2,
+        extrude: u32,
     ) {
-        let rect_width = (packed_location.width() - padding.x) as usize;
-        let rect_height = (packed_location.height() - padding.y) as usize;
-        let rect_x = packed_location.x() as usize;
-        let rect_y = packed_location.y() as usize;
+        let extrude = extrude as usize;
+        let rect_width = (packed_location.width() - padding.x) as usize - 2 * extrude;
+        let rect_height = (packed_location.height() - padding.y) as usize - 2 * extrude;
+        let rect_x = packed_location.x() as usize + extrude;
+        let rect_y = packed_location.y() as usize + extrude;
         let atlas_width = atlas_texture.width() as usize;
         let format_size = atlas_texture.texture_descriptor.format.pixel_size();
 
@@ -118,6 +249,34 @@ impl<'a> TextureAtlasBuilder<'a> {
             let texture_end = texture_begin + rect_width * format_size;
             atlas_texture.data[begin..end]
                 .copy_from_slice(&texture.data[texture_begin..texture_end]);
+
+            // Repeat the first and last pixels of the row on its sides
+            for x in 0..extrude {
+                atlas_texture
+                    .data
+                    .copy_within(begin..begin + format_size, begin - (x + 1) * format_size);
+                atlas_texture
+                    .data
+                    .copy_within(end - format_size..end, end + x * format_size);
+            }
+        }
+
+        if extrude == 0 || rect_width == 0 || rect_height == 0 {
+            return;
+        }
+        // Repeat the first and last extruded rows above and below the texture
+        let row_begin = |y: usize| (y * atlas_width + rect_x - extrude) * format_size;
+        let row_size = (rect_width + 2 * extrude) * format_size;
+        for y in 0..extrude {
+            let first_row = row_begin(rect_y);
+            atlas_texture
+                .data
+                .copy_within(first_row..first_row + row_size, row_begin(rect_y - y - 1));
+            let last_row = row_begin(rect_y + rect_height - 1);
+            atlas_texture.data.copy_within(
+                last_row..last_row + row_size,
+                row_begin(rect_y + rect_height + y),
+            );
         }
     }
 
@@ -128,7 +287,13 @@ impl<'a> TextureAtlasBuilder<'a> {
         packed_location: &PackedLocation,
     ) {
         if self.format == texture.texture_descriptor.format {
-            Self::copy_texture_to_atlas(atlas_texture, texture, packed_location, self.padding);
+            Self::copy_texture_to_atlas(
+                atlas_texture,
+                texture,
+                packed_location,
+                self.padding,
+                self.extrude,
+            );
         } else if let Some(converted_texture) = texture.convert(self.format) {
             debug!(
                 "Converting texture from '{:?}' to '{:?}'",
@@ -139,6 +304,7 @@ impl<'a> TextureAtlasBuilder<'a> {
                 &converted_texture,
                 packed_location,
                 self.padding,
+                self.extrude,
             );
         } else {
             error!(
@@ -200,8 +366,8 @@ impl<'a> TextureAtlasBuilder<'a> {
                 index,
                 None,
                 RectToInsert::new(
-                    texture.width() + self.padding.x,
-                    texture.height() + self.padding.y,
+                    texture.width() + 2 * self.extrude + self.padding.x,
+                    texture.height() + 2 * self.extrude + self.padding.y,
                     1,
                 ),
             );
@@ -259,9 +425,8 @@ impl<'a> TextureAtlasBuilder<'a> {
         for (index, (image_id, texture)) in self.textures_to_place.iter().enumerate() {
             let (_, packed_location) = rect_placements.packed_locations().get(&index).unwrap();
 
-            let min = UVec2::new(packed_location.x(), packed_location.y());
-            let max =
-                min + UVec2::new(packed_location.width(), packed_location.height()) - self.padding;
+            let min = UVec2::new(packed_location.x(), packed_location.y()) + self.extrude;
+            let max = min + texture.size();
             if let Some(image_id) = image_id {
                 texture_ids.insert(*image_id, index);
             }
@@ -286,3 +451,138 @@ impl<'a> TextureAtlasBuilder<'a> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Assets;
+    use bevy_math::{URect, UVec2};
+    use bevy_render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    };
+
+    use super::{TextureAtlasBuilder, TextureAtlasBuilderError};
+    use crate::{TextureAtlas, TextureAtlasLayout};
+
+    fn filled_image(size: UVec2, pixel: [u8; 4]) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &pixel,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        )
+    }
+
+    fn pixel(image: &Image, position: UVec2) -> &[u8] {
+        let begin = (position.y * image.width() + position.x) as usize * 4;
+        &image.data[begin..begin + 4]
+    }
+
+    #[test]
+    fn extrudes_texture_edges() {
+        let red = filled_image(UVec2::new(2, 3), [255, 0, 0, 255]);
+        let mut builder = TextureAtlasBuilder::default()
+            .initial_size(UVec2::splat(8))
+            .extrude(1);
+        builder.add_texture(None, &red);
+        let (layout, atlas) = builder.finish().unwrap();
+
+        let rect = layout.textures[0];
+        assert_eq!(rect.size(), UVec2::new(2, 3));
+        let margin = URect::from_corners(rect.min - 1, rect.max + 1);
+        for y in margin.min.y..margin.max.y {
+            for x in margin.min.x..margin.max.x {
+                assert_eq!(pixel(&atlas, UVec2::new(x, y)), [255, 0, 0, 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn adds_textures_to_an_existing_atlas() {
+        let red = filled_image(UVec2::splat(4), [255, 0, 0, 255]);
+        let green = filled_image(UVec2::splat(2), [0, 255, 0, 255]);
+        let blue = filled_image(UVec2::splat(6), [0, 0, 255, 255]);
+
+        let mut builder = TextureAtlasBuilder::default().initial_size(UVec2::splat(8));
+        builder.add_texture(None, &red);
+        builder.add_texture(None, &green);
+        let (layout, atlas) = builder.finish().unwrap();
+
+        let mut builder = TextureAtlasBuilder::default()
+            .initial_size(UVec2::splat(8))
+            .padding(UVec2::ONE);
+        assert_eq!(builder.add_atlas(&layout, &atlas).unwrap(), 0);
+        builder.add_texture(None, &blue);
+        let (layout, atlas) = builder.finish().unwrap();
+
+        assert_eq!(layout.len(), 3);
+        for (index, color) in [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]]
+            .into_iter()
+            .enumerate()
+        {
+            let rect = layout.textures[index];
+            assert_eq!(pixel(&atlas, rect.min), color);
+            assert_eq!(pixel(&atlas, rect.max - 1), color);
+        }
+    }
+
+    #[test]
+    fn atlases_without_main_world_data_are_rejected() {
+        let red = filled_image(UVec2::splat(4), [255, 0, 0, 255]);
+        let mut builder = TextureAtlasBuilder::default().initial_size(UVec2::splat(8));
+        builder.add_texture(None, &red);
+        let (layout, mut atlas) = builder.finish().unwrap();
+        // The data of render world images is dropped once they're uploaded
+        atlas.data.clear();
+
+        let mut builder = TextureAtlasBuilder::default();
+        assert!(matches!(
+            builder.add_atlas(&layout, &atlas),
+            Err(TextureAtlasBuilderError::MissingAtlasData)
+        ));
+        assert!(builder.textures_to_place.is_empty());
+    }
+
+    #[test]
+    fn merged_atlases_are_remapped() {
+        let red = filled_image(UVec2::splat(4), [255, 0, 0, 255]);
+        let green = filled_image(UVec2::splat(2), [0, 255, 0, 255]);
+        let blue = filled_image(UVec2::splat(6), [0, 0, 255, 255]);
+
+        let mut builder = TextureAtlasBuilder::default().initial_size(UVec2::splat(8));
+        builder.add_texture(None, &red);
+        builder.add_texture(None, &green);
+        let (first_layout, first_atlas) = builder.finish().unwrap();
+        let mut builder = TextureAtlasBuilder::default().initial_size(UVec2::splat(8));
+        builder.add_texture(None, &blue);
+        let (second_layout, second_atlas) = builder.finish().unwrap();
+
+        let mut builder = TextureAtlasBuilder::default().initial_size(UVec2::splat(16));
+        assert_eq!(builder.add_atlas(&first_layout, &first_atlas).unwrap(), 0);
+        let offset = builder.add_atlas(&second_layout, &second_atlas).unwrap();
+        assert_eq!(offset, 2);
+        let (layout, atlas) = builder.finish().unwrap();
+
+        let mut layouts = Assets::<TextureAtlasLayout>::default();
+        let first_layout = layouts.add(first_layout);
+        let second_layout = layouts.add(second_layout);
+        let layout = layouts.add(layout);
+
+        let mut sprite = TextureAtlas {
+            layout: second_layout.clone(),
+            index: 0,
+        };
+        assert!(!sprite.remap(&first_layout, &layout, 0));
+        assert!(sprite.remap(&second_layout, &layout, offset));
+        assert_eq!(sprite.layout, layout);
+        assert_eq!(sprite.index, 2);
+        let rect = sprite.texture_rect(&layouts).unwrap();
+        assert_eq!(pixel(&atlas, rect.min), [0, 0, 255, 255]);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{ExtractedSprite, ImageScaleMode, Sprite, TextureAtlas, TextureAtlasLayout};
+use crate::{
+    ExtractedSprite, ImageScaleMode, Sprite, SpriteEffect, SpriteSampler, SpriteStencil,
+    TextureAtlas, TextureAtlasLayout,
+};
 
 use super::TextureSlice;
 use bevy_asset::{AssetEvent, Assets, Handle};
@@ -54,6 +57,18 @@ impl ComputedTextureSlices {
                 flip_y,
                 image_handle_id: handle.id(),
                 anchor: Self::redepend_anchor_from_sprite_to_slice(sprite, slice),
+                premultiplied_alpha: sprite.premultiplied_alpha,
+                alpha_mode: sprite.alpha_mode,
+                effect: SpriteEffect::None,
+                sampler: SpriteSampler::Image,
+                uv_offset: Vec2::ZERO,
+                uv_scale: Vec2::ONE,
+                uv_repeat: false,
+                sort_key: None,
+                normal_map: sprite.normal_map.as_ref().map(Handle::id),
+                emissive: sprite.emissive,
+                stencil: SpriteStencil::None,
+                material: None,
             }
         })
     }
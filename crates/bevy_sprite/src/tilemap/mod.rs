@@ -0,0 +1,489 @@
+use crate::{Material2d, Material2dPlugin, MaterialMesh2dBundle, Mesh2dHandle};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, Asset, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_math::{primitives::Rectangle, UVec2, Vec2};
+use bevy_reflect::prelude::*;
+use bevy_render::{
+    mesh::Mesh,
+    primitives::Aabb,
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_resource::*,
+    texture::{GpuImage, Image},
+    view::{Visibility, VisibilitySystems},
+};
+use bevy_transform::{components::Transform, TransformSystem};
+use fixedbitset::FixedBitSet;
+
+pub const TILEMAP_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(6107443126503318854);
+
+/// Adds support for rendering [`Tilemap`]s.
+#[derive(Default)]
+pub struct TilemapPlugin;
+
+impl Plugin for TilemapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TILEMAP_SHADER_HANDLE,
+            "tilemap.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(Material2dPlugin::<TilemapChunkMaterial>::default())
+            .register_type::<Tilemap>()
+            .register_type::<TilemapChunk>()
+            .add_systems(
+                PostUpdate,
+                update_tilemap_chunks
+                    .before(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::CalculateBounds),
+            );
+    }
+}
+
+/// A grid of tiles drawn from a tileset image.
+///
+/// Instead of one sprite per tile, the tilemap is split in chunks of
+/// [`chunk_size`](Self::chunk_size) tiles: each chunk is a single quad, rendered with a
+/// [`TilemapChunkMaterial`] which looks the tiles up in a texture of tile indices. Changing a tile
+/// only uploads the indices of its chunk again, and chunks outside the view are culled.
+///
+/// The tile `(0, 0)` is at the bottom-left corner of the tilemap, on the origin of its
+/// [`Transform`], and the tiles go right and up from there.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+pub struct Tilemap {
+    /// The tileset image, divided in a grid of tiles of the same size.
+    pub tileset: Handle<Image>,
+    /// The number of columns and rows of tiles in the [`tileset`](Self::tileset).
+    ///
+    /// The tiles of the tileset are indexed from left to right, then from top to bottom.
+    pub tileset_grid: UVec2,
+    /// The size of a tile, in world units.
+    pub tile_size: Vec2,
+    size: UVec2,
+    chunk_size: UVec2,
+    tiles: Vec<Option<u32>>,
+    #[reflect(ignore)]
+    dirty_chunks: FixedBitSet,
+}
+
+impl Tilemap {
+    /// The default number of columns and rows of tiles in a chunk.
+    pub const DEFAULT_CHUNK_SIZE: UVec2 = UVec2::splat(64);
+
+    /// Creates an empty tilemap of `size` tiles.
+    pub fn new(size: UVec2, tileset: Handle<Image>, tileset_grid: UVec2, tile_size: Vec2) -> Self {
+        let mut tilemap = Self {
+            tileset,
+            tileset_grid,
+            tile_size,
+            size,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            tiles: vec![None; size.x as usize * size.y as usize],
+            dirty_chunks: FixedBitSet::new(),
+        };
+        tilemap.mark_all_dirty();
+        tilemap
+    }
+
+    /// Sets the number of columns and rows of tiles in a chunk.
+    ///
+    /// Larger chunks mean fewer draw calls, but more indices to upload when a tile changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` has a zero component.
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: UVec2) -> Self {
+        assert!(
+            chunk_size.cmpgt(UVec2::ZERO).all(),
+            "The chunk size of a tilemap can't be zero"
+        );
+        self.chunk_size = chunk_size;
+        self.mark_all_dirty();
+        self
+    }
+
+    /// The number of columns and rows of tiles in the tilemap.
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// The number of columns and rows of tiles in a chunk.
+    pub fn chunk_size(&self) -> UVec2 {
+        self.chunk_size
+    }
+
+    /// The number of columns and rows of chunks in the tilemap.
+    pub fn chunk_count(&self) -> UVec2 {
+        (self.size + self.chunk_size - 1) / self.chunk_size
+    }
+
+    /// Returns the tileset index of the tile at `position`, or `None` if it is empty or outside
+    /// of the tilemap.
+    pub fn get(&self, position: UVec2) -> Option<u32> {
+        self.tile_index(position)
+            .and_then(|index| self.tiles[index])
+    }
+
+    /// Sets the tileset index of the tile at `position`, or empties it with `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is outside of the tilemap.
+    pub fn set(&mut self, position: UVec2, tile: Option<u32>) {
+        let Some(index) = self.tile_index(position) else {
+            panic!(
+                "Tile position {position} is outside of the tilemap of size {}",
+                self.size
+            );
+        };
+        if self.tiles[index] != tile {
+            self.tiles[index] = tile;
+            if let Some(chunk) = self.chunk_index(position / self.chunk_size) {
+                self.dirty_chunks.insert(chunk);
+            }
+        }
+    }
+
+    /// Sets every tile of the tilemap to `tile`.
+    pub fn fill(&mut self, tile: Option<u32>) {
+        self.tiles.fill(tile);
+        self.mark_all_dirty();
+    }
+
+    fn tile_index(&self, position: UVec2) -> Option<usize> {
+        position
+            .cmplt(self.size)
+            .all()
+            .then(|| position.y as usize * self.size.x as usize + position.x as usize)
+    }
+
+    fn chunk_index(&self, chunk: UVec2) -> Option<usize> {
+        let count = self.chunk_count();
+        chunk
+            .cmplt(count)
+            .all()
+            .then(|| chunk.y as usize * count.x as usize + chunk.x as usize)
+    }
+
+    fn mark_all_dirty(&mut self) {
+        let count = self.chunk_count();
+        self.dirty_chunks = FixedBitSet::with_capacity(count.x as usize * count.y as usize);
+        self.dirty_chunks.insert_range(..);
+    }
+
+    /// Returns the data of the tile indices texture of `chunk`, from its bottom row.
+    ///
+    /// Each tile is stored as its tileset index plus one, and empty tiles as `0`.
+    fn chunk_data(&self, chunk: UVec2) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.chunk_size.x as usize * self.chunk_size.y as usize);
+        for y in 0..self.chunk_size.y {
+            for x in 0..self.chunk_size.x {
+                let tile = self.get(chunk * self.chunk_size + UVec2::new(x, y));
+                data.push(tile.map_or(0, |tile| tile.saturating_add(1)));
+            }
+        }
+        bytemuck::cast_slice(&data).to_vec()
+    }
+}
+
+/// A chunk of a [`Tilemap`], spawned as a child of the tilemap entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct TilemapChunk {
+    /// The column and row of the chunk in the tilemap.
+    pub position: UVec2,
+}
+
+/// The chunk entities of a [`Tilemap`], along with their assets.
+///
+/// Inserted on the tilemap entity by [`update_tilemap_chunks`].
+#[derive(Component)]
+pub struct TilemapChunks {
+    chunks: Vec<(Entity, Handle<Image>, Handle<TilemapChunkMaterial>)>,
+    mesh: Handle<Mesh>,
+    chunk_count: UVec2,
+    chunk_size: UVec2,
+    tile_size: Vec2,
+}
+
+/// The [`Material2d`] rendering a chunk of a [`Tilemap`].
+///
+/// This is managed by [`update_tilemap_chunks`] and shouldn't be used directly.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[uniform(0, TilemapChunkUniform)]
+pub struct TilemapChunkMaterial {
+    /// The number of columns and rows of tiles in the tileset.
+    pub tileset_grid: UVec2,
+    /// The number of columns and rows of tiles in the chunk.
+    pub chunk_size: UVec2,
+    /// The tileset image.
+    #[texture(1)]
+    #[sampler(2)]
+    pub tileset: Handle<Image>,
+    /// An [`TextureFormat::R32Uint`] image with the tileset index plus one of each tile of the
+    /// chunk, or `0` for empty tiles.
+    #[texture(3, sample_type = "u_int")]
+    pub tiles: Handle<Image>,
+}
+
+/// The GPU representation of the uniform data of a [`TilemapChunkMaterial`].
+#[derive(Clone, Default, ShaderType)]
+pub struct TilemapChunkUniform {
+    pub tileset_grid: UVec2,
+    pub chunk_size: UVec2,
+}
+
+impl AsBindGroupShaderType<TilemapChunkUniform> for TilemapChunkMaterial {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> TilemapChunkUniform {
+        TilemapChunkUniform {
+            tileset_grid: self.tileset_grid.max(UVec2::ONE),
+            chunk_size: self.chunk_size,
+        }
+    }
+}
+
+impl Material2d for TilemapChunkMaterial {
+    fn fragment_shader() -> ShaderRef {
+        TILEMAP_SHADER_HANDLE.into()
+    }
+}
+
+/// System spawning the chunks of the [`Tilemap`]s, and uploading the tile indices of the chunks
+/// which changed.
+///
+/// The chunks of the entities whose [`Tilemap`] is removed are despawned.
+#[allow(clippy::too_many_arguments)]
+pub fn update_tilemap_chunks(
+    mut commands: Commands,
+    mut tilemaps: Query<(Entity, &mut Tilemap, Option<&mut TilemapChunks>), Changed<Tilemap>>,
+    mut removed_tilemaps: RemovedComponents<Tilemap>,
+    orphaned_chunks: Query<&TilemapChunks, Without<Tilemap>>,
+    mut chunk_transforms: Query<&mut Transform, With<TilemapChunk>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<TilemapChunkMaterial>>,
+) {
+    for entity in removed_tilemaps.read() {
+        let Ok(chunks) = orphaned_chunks.get(entity) else {
+            continue;
+        };
+        for (chunk_entity, ..) in &chunks.chunks {
+            if let Some(chunk_entity) = commands.get_entity(*chunk_entity) {
+                chunk_entity.despawn_recursive();
+            }
+        }
+        commands.entity(entity).remove::<TilemapChunks>();
+    }
+
+    for (entity, mut tilemap, chunks) in &mut tilemaps {
+        let chunk_count = tilemap.chunk_count();
+        let chunk_size = tilemap.chunk_size;
+        let chunk_mesh = Rectangle::from_size(chunk_size.as_vec2() * tilemap.tile_size);
+        let chunk_transform = |position: UVec2| {
+            let center = (position * chunk_size).as_vec2() + chunk_size.as_vec2() / 2.0;
+            Transform::from_translation((center * tilemap.tile_size).extend(0.0))
+        };
+
+        match chunks {
+            Some(mut chunks)
+                if chunks.chunk_count == chunk_count && chunks.chunk_size == chunk_size =>
+            {
+                let resized = chunks.tile_size != tilemap.tile_size;
+                if resized {
+                    chunks.tile_size = tilemap.tile_size;
+                    if let Some(mesh) = meshes.get_mut(&chunks.mesh) {
+                        *mesh = chunk_mesh.into();
+                    }
+                }
+                for (index, (chunk_entity, tiles, material)) in chunks.chunks.iter().enumerate() {
+                    let position =
+                        UVec2::new(index as u32 % chunk_count.x, index as u32 / chunk_count.x);
+                    if resized {
+                        if let Ok(mut transform) = chunk_transforms.get_mut(*chunk_entity) {
+                            *transform = chunk_transform(position);
+                            // The bounds of the chunk are computed again from its resized mesh
+                            commands.entity(*chunk_entity).remove::<Aabb>();
+                        }
+                    }
+                    let outdated = materials.get(material).is_some_and(|material| {
+                        material.tileset != tilemap.tileset
+                            || material.tileset_grid != tilemap.tileset_grid
+                    });
+                    if outdated {
+                        if let Some(material) = materials.get_mut(material) {
+                            material.tileset = tilemap.tileset.clone();
+                            material.tileset_grid = tilemap.tileset_grid;
+                        }
+                    }
+                    if tilemap.dirty_chunks.contains(index) {
+                        if let Some(image) = images.get_mut(tiles) {
+                            image.data = tilemap.chunk_data(position);
+                        }
+                    }
+                }
+            }
+            old_chunks => {
+                if let Some(old_chunks) = old_chunks {
+                    for (chunk_entity, ..) in &old_chunks.chunks {
+                        commands.entity(*chunk_entity).despawn_recursive();
+                    }
+                }
+                let mesh = meshes.add(chunk_mesh);
+                let mut chunks =
+                    Vec::with_capacity(chunk_count.x as usize * chunk_count.y as usize);
+                for y in 0..chunk_count.y {
+                    for x in 0..chunk_count.x {
+                        let position = UVec2::new(x, y);
+                        let tiles = images.add(Image::new(
+                            Extent3d {
+                                width: chunk_size.x,
+                                height: chunk_size.y,
+                                depth_or_array_layers: 1,
+                            },
+                            TextureDimension::D2,
+                            tilemap.chunk_data(position),
+                            TextureFormat::R32Uint,
+                            RenderAssetUsages::default(),
+                        ));
+                        let material = materials.add(TilemapChunkMaterial {
+                            tileset_grid: tilemap.tileset_grid,
+                            chunk_size,
+                            tileset: tilemap.tileset.clone(),
+                            tiles: tiles.clone(),
+                        });
+                        let chunk_entity = commands
+                            .spawn((
+                                TilemapChunk { position },
+                                MaterialMesh2dBundle {
+                                    mesh: Mesh2dHandle(mesh.clone()),
+                                    material: material.clone(),
+                                    transform: chunk_transform(position),
+                                    ..Default::default()
+                                },
+                            ))
+                            .set_parent(entity)
+                            .id();
+                        chunks.push((chunk_entity, tiles, material));
+                    }
+                }
+                commands.entity(entity).insert(TilemapChunks {
+                    chunks,
+                    mesh,
+                    chunk_count,
+                    chunk_size,
+                    tile_size: tilemap.tile_size,
+                });
+            }
+        }
+        tilemap.bypass_change_detection().dirty_chunks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{Assets, Handle};
+    use bevy_ecs::{entity::Entity, system::RunSystemOnce, world::World};
+    use bevy_math::{UVec2, Vec2};
+    use bevy_render::{mesh::Mesh, primitives::Aabb, texture::Image};
+
+    use super::{
+        update_tilemap_chunks, Tilemap, TilemapChunk, TilemapChunkMaterial, TilemapChunks,
+    };
+
+    fn tilemap_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<TilemapChunkMaterial>>();
+        world
+    }
+
+    #[test]
+    fn set_tiles() {
+        let mut tilemap = Tilemap::new(UVec2::new(5, 3), Handle::default(), UVec2::ONE, Vec2::ONE)
+            .with_chunk_size(UVec2::new(2, 2));
+        assert_eq!(tilemap.chunk_count(), UVec2::new(3, 2));
+        assert_eq!(tilemap.dirty_chunks.count_ones(..), 6);
+        tilemap.dirty_chunks.clear();
+
+        tilemap.set(UVec2::new(4, 2), Some(7));
+        assert_eq!(tilemap.get(UVec2::new(4, 2)), Some(7));
+        assert_eq!(tilemap.get(UVec2::new(3, 2)), None);
+        assert_eq!(tilemap.get(UVec2::new(5, 0)), None);
+        assert_eq!(tilemap.dirty_chunks.ones().collect::<Vec<_>>(), vec![5]);
+
+        // Setting the same tile again doesn't dirty the chunk
+        tilemap.dirty_chunks.clear();
+        tilemap.set(UVec2::new(4, 2), Some(7));
+        assert_eq!(tilemap.dirty_chunks.count_ones(..), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_outside_of_tilemap() {
+        let mut tilemap = Tilemap::new(UVec2::new(2, 2), Handle::default(), UVec2::ONE, Vec2::ONE);
+        tilemap.set(UVec2::new(2, 0), Some(0));
+    }
+
+    #[test]
+    fn chunk_data() {
+        let mut tilemap = Tilemap::new(UVec2::new(3, 3), Handle::default(), UVec2::ONE, Vec2::ONE)
+            .with_chunk_size(UVec2::new(2, 2));
+        tilemap.set(UVec2::new(0, 0), Some(0));
+        tilemap.set(UVec2::new(1, 1), Some(4));
+        tilemap.set(UVec2::new(2, 2), Some(1));
+
+        let indices = |data: Vec<u8>| {
+            data.chunks_exact(4)
+                .map(|bytes| u32::from_ne_bytes(bytes.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(indices(tilemap.chunk_data(UVec2::ZERO)), vec![1, 0, 0, 5]);
+        // The tiles outside of the tilemap are empty
+        assert_eq!(indices(tilemap.chunk_data(UVec2::ONE)), vec![2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn resized_chunks_compute_their_bounds_again() {
+        let mut world = tilemap_world();
+        let tilemap = Tilemap::new(UVec2::new(4, 2), Handle::default(), UVec2::ONE, Vec2::ONE)
+            .with_chunk_size(UVec2::new(2, 2));
+        let tilemap = world.spawn(tilemap).id();
+        world.run_system_once(update_tilemap_chunks);
+
+        let mut chunks = world.query::<(Entity, &TilemapChunk)>();
+        let chunk_entities: Vec<_> = chunks.iter(&world).map(|(entity, _)| entity).collect();
+        assert_eq!(chunk_entities.len(), 2);
+        for &chunk in &chunk_entities {
+            world.entity_mut(chunk).insert(Aabb::default());
+        }
+
+        world.get_mut::<Tilemap>(tilemap).unwrap().tile_size = Vec2::splat(2.0);
+        world.run_system_once(update_tilemap_chunks);
+        for chunk in chunk_entities {
+            assert!(world.get::<Aabb>(chunk).is_none());
+        }
+    }
+
+    #[test]
+    fn removing_the_tilemap_despawns_its_chunks() {
+        let mut world = tilemap_world();
+        let tilemap = Tilemap::new(UVec2::new(4, 4), Handle::default(), UVec2::ONE, Vec2::ONE)
+            .with_chunk_size(UVec2::new(2, 2));
+        let tilemap = world.spawn(tilemap).id();
+        world.run_system_once(update_tilemap_chunks);
+        let mut chunks = world.query::<&TilemapChunk>();
+        assert_eq!(chunks.iter(&world).count(), 4);
+
+        world.entity_mut(tilemap).remove::<Tilemap>();
+        world.run_system_once(update_tilemap_chunks);
+        assert_eq!(chunks.iter(&world).count(), 0);
+        assert!(world.get::<TilemapChunks>(tilemap).is_none());
+    }
+}
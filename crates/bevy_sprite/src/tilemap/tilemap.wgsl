@@ -0,0 +1,45 @@
+#import bevy_sprite::{
+    mesh2d_vertex_output::VertexOutput,
+    mesh2d_view_bindings::view,
+}
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+struct TilemapChunk {
+    tileset_grid: vec2<u32>,
+    chunk_size: vec2<u32>,
+};
+
+@group(2) @binding(0) var<uniform> chunk: TilemapChunk;
+@group(2) @binding(1) var tileset: texture_2d<f32>;
+@group(2) @binding(2) var tileset_sampler: sampler;
+// The tileset index plus one of each tile, from the bottom row of the chunk. 0 is an empty tile.
+@group(2) @binding(3) var tiles: texture_2d<u32>;
+
+@fragment
+fn fragment(
+    mesh: VertexOutput,
+) -> @location(0) vec4<f32> {
+    // The position in tiles from the top-left corner of the chunk
+    let position = mesh.uv * vec2<f32>(chunk.chunk_size);
+    let tile = min(vec2<u32>(position), chunk.chunk_size - 1u);
+    let index = textureLoad(tiles, vec2<u32>(tile.x, chunk.chunk_size.y - 1u - tile.y), 0).r;
+    if index == 0u {
+        discard;
+    }
+
+    let tileset_index = index - 1u;
+    let tileset_tile = vec2<u32>(
+        tileset_index % chunk.tileset_grid.x,
+        tileset_index / chunk.tileset_grid.x,
+    );
+    let uv = (vec2<f32>(tileset_tile) + fract(position)) / vec2<f32>(chunk.tileset_grid);
+    // Sample the first mip level, the derivatives are discontinuous across tiles
+    var output_color = textureSampleLevel(tileset, tileset_sampler, uv, 0.0);
+#ifdef TONEMAP_IN_SHADER
+    output_color = tonemapping::tone_mapping(output_color, view.color_grading);
+#endif
+    return output_color;
+}
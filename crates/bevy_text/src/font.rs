@@ -1,22 +1,91 @@
-use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph, VariableFont, VariationAxis};
 use bevy_asset::Asset;
-use bevy_reflect::TypePath;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
 use bevy_render::{
     render_asset::RenderAssetUsages,
     render_resource::{Extent3d, TextureDimension, TextureFormat},
     texture::Image,
 };
+use bevy_utils::HashMap;
 
 #[derive(Asset, TypePath, Debug, Clone)]
 pub struct Font {
     pub font: FontArc,
+    /// The data of the font, kept to create its instances if it is a variable font.
+    data: Option<Arc<[u8]>>,
+    axes: Vec<VariationAxis>,
+    named_instances: Vec<FontNamedInstance>,
+    instances: Arc<Mutex<HashMap<FontVariationsKey, FontArc>>>,
 }
 
 impl Font {
     pub fn try_from_bytes(font_data: Vec<u8>) -> Result<Self, InvalidFont> {
         let font = FontVec::try_from_vec(font_data)?;
+        let axes = font.variations();
+        let (data, named_instances) = if axes.is_empty() {
+            (None, Vec::new())
+        } else {
+            let data: Arc<[u8]> = font.as_slice().into();
+            let named_instances = parse_named_instances(&data);
+            (Some(data), named_instances)
+        };
         let font = FontArc::new(font);
-        Ok(Font { font })
+        Ok(Font {
+            font,
+            data,
+            axes,
+            named_instances,
+            instances: Default::default(),
+        })
+    }
+
+    /// Returns the variation axes of the font, or an empty slice if it isn't a variable font.
+    pub fn variation_axes(&self) -> &[VariationAxis] {
+        &self.axes
+    }
+
+    /// Returns the named instances, such as "Bold" or "Condensed Light", defined by a variable
+    /// font.
+    pub fn named_instances(&self) -> &[FontNamedInstance] {
+        &self.named_instances
+    }
+
+    /// Returns the variations of the named instance called `name`, if the font defines one.
+    pub fn named_instance(&self, name: &str) -> Option<&FontVariations> {
+        self.named_instances
+            .iter()
+            .find(|instance| instance.name == name)
+            .map(|instance| &instance.variations)
+    }
+
+    /// Returns the instance of the font with the given `variations`.
+    ///
+    /// The axes that aren't set by `variations` use their default value. If the font isn't a
+    /// variable font, [`Font::font`] is returned. Instances are created on first use and are
+    /// then cached.
+    pub fn instance(&self, variations: &FontVariations) -> FontArc {
+        let Some(data) = self.data.as_ref().filter(|_| !variations.is_empty()) else {
+            return self.font.clone();
+        };
+        let key = variations.key();
+        let mut instances = self
+            .instances
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(font) = instances.get(&key) {
+            return font.clone();
+        }
+        let Ok(mut font) = FontVec::try_from_vec(data.to_vec()) else {
+            return self.font.clone();
+        };
+        for (tag, value) in variations.iter() {
+            font.set_variation(&tag, value);
+        }
+        let font = FontArc::new(font);
+        instances.insert(key, font.clone());
+        font
     }
 
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Image {
@@ -51,3 +120,246 @@ impl Font {
         )
     }
 }
+
+/// The values of the variation axes of a variable [`Font`], used to select one of its instances.
+///
+/// Variable fonts contain a continuous range of styles in a single file, such as all the weights
+/// from thin to black. The axes that aren't set use the default value of the font.
+///
+/// ```
+/// # use bevy_text::{FontVariations, TextStyle};
+/// let style = TextStyle {
+///     font_variations: FontVariations::default().with_weight(650.0).with_width(75.0),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct FontVariations {
+    axes: Vec<([u8; 4], f32)>,
+}
+
+impl FontVariations {
+    /// The tag of the weight axis, usually ranging from `100.0` (thin) to `900.0` (black).
+    pub const WEIGHT: [u8; 4] = *b"wght";
+    /// The tag of the width axis, as a percentage of the normal width.
+    pub const WIDTH: [u8; 4] = *b"wdth";
+    /// The tag of the slant axis, as an angle in degrees. Negative values lean to the right.
+    pub const SLANT: [u8; 4] = *b"slnt";
+
+    /// Sets the value of the axis with the given `tag`, replacing its previous value.
+    pub fn set_axis(&mut self, tag: [u8; 4], value: f32) {
+        match self.axes.iter_mut().find(|(axis_tag, _)| *axis_tag == tag) {
+            Some((_, axis_value)) => *axis_value = value,
+            None => self.axes.push((tag, value)),
+        }
+    }
+
+    /// Returns the value of the axis with the given `tag`, if it is set.
+    pub fn axis(&self, tag: [u8; 4]) -> Option<f32> {
+        self.axes
+            .iter()
+            .find(|(axis_tag, _)| *axis_tag == tag)
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns these variations with the axis with the given `tag` set to `value`.
+    pub fn with_axis(mut self, tag: [u8; 4], value: f32) -> Self {
+        self.set_axis(tag, value);
+        self
+    }
+
+    /// Returns these variations with the [weight](Self::WEIGHT) axis set to `weight`.
+    pub fn with_weight(self, weight: f32) -> Self {
+        self.with_axis(Self::WEIGHT, weight)
+    }
+
+    /// Returns these variations with the [width](Self::WIDTH) axis set to `width`.
+    pub fn with_width(self, width: f32) -> Self {
+        self.with_axis(Self::WIDTH, width)
+    }
+
+    /// Returns these variations with the [slant](Self::SLANT) axis set to `slant`.
+    pub fn with_slant(self, slant: f32) -> Self {
+        self.with_axis(Self::SLANT, slant)
+    }
+
+    /// Iterates over the tags and values of the axes that are set.
+    pub fn iter(&self) -> impl Iterator<Item = ([u8; 4], f32)> + '_ {
+        self.axes.iter().copied()
+    }
+
+    /// Returns `true` if no axis is set.
+    pub fn is_empty(&self) -> bool {
+        self.axes.is_empty()
+    }
+
+    /// Returns a hashable key identifying the instance these variations select.
+    pub(crate) fn key(&self) -> FontVariationsKey {
+        let mut axes: Vec<_> = self
+            .axes
+            .iter()
+            .map(|(tag, value)| (*tag, value.to_bits()))
+            .collect();
+        axes.sort_unstable();
+        FontVariationsKey(axes)
+    }
+}
+
+/// A hashable version of [`FontVariations`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FontVariationsKey(Vec<([u8; 4], u32)>);
+
+/// A named instance of a variable [`Font`], such as "Bold" or "Condensed Light".
+#[derive(Debug, Clone)]
+pub struct FontNamedInstance {
+    /// The name of the instance.
+    pub name: String,
+    /// The variations selecting the instance.
+    pub variations: FontVariations,
+}
+
+/// Reads the named instances from the `fvar` table of a variable font.
+fn parse_named_instances(data: &[u8]) -> Vec<FontNamedInstance> {
+    let Ok(face) = ttf_parser::Face::parse(data, 0) else {
+        return Vec::new();
+    };
+    let Some(fvar) = face.raw_face().table(ttf_parser::Tag::from_bytes(b"fvar")) else {
+        return Vec::new();
+    };
+
+    parse_fvar_instances(fvar)
+        .into_iter()
+        .filter_map(|(name_id, variations)| {
+            let name = face
+                .names()
+                .into_iter()
+                .filter(|name| name.name_id == name_id && name.is_unicode())
+                .find_map(|name| name.to_string())?;
+            Some(FontNamedInstance { name, variations })
+        })
+        .collect()
+}
+
+/// Reads the name IDs and the variations of the instances defined by an `fvar` table.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/fvar>.
+fn parse_fvar_instances(fvar: &[u8]) -> Vec<(u16, FontVariations)> {
+    let read_u16 = |offset: usize| {
+        fvar.get(offset..offset + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let read_fixed = |offset: usize| {
+        fvar.get(offset..offset + 4).map(|bytes| {
+            i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32 / 65536.0
+        })
+    };
+    let (
+        Some(axes_offset),
+        Some(axis_count),
+        Some(axis_size),
+        Some(instance_count),
+        Some(instance_size),
+    ) = (
+        read_u16(4),
+        read_u16(8),
+        read_u16(10),
+        read_u16(12),
+        read_u16(14),
+    )
+    else {
+        return Vec::new();
+    };
+
+    let tags: Vec<[u8; 4]> = (0..axis_count)
+        .filter_map(|axis| {
+            let offset = axes_offset + axis * axis_size;
+            fvar.get(offset..offset + 4)
+                .map(|tag| [tag[0], tag[1], tag[2], tag[3]])
+        })
+        .collect();
+    let instances_offset = axes_offset + axis_count * axis_size;
+
+    (0..instance_count)
+        .filter_map(|instance| {
+            let offset = instances_offset + instance * instance_size;
+            let name_id = read_u16(offset)? as u16;
+            let mut variations = FontVariations::default();
+            for (axis, tag) in tags.iter().enumerate() {
+                variations.set_axis(*tag, read_fixed(offset + 4 + axis * 4)?);
+            }
+            Some((name_id, variations))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_fvar_instances, FontVariations};
+
+    /// Builds an `fvar` table with the given axes, and instances made of a name ID and a value
+    /// for each axis.
+    fn fvar_table(tags: &[[u8; 4]], instances: &[(u16, &[f32])]) -> Vec<u8> {
+        const AXIS_SIZE: u16 = 20;
+        // The optional PostScript name ID is included in the instances
+        let instance_size = 6 + 4 * tags.len() as u16;
+
+        let mut table = Vec::new();
+        for value in [
+            1,
+            0,
+            16,
+            2,
+            tags.len() as u16,
+            AXIS_SIZE,
+            instances.len() as u16,
+            instance_size,
+        ] {
+            table.extend_from_slice(&u16::to_be_bytes(value));
+        }
+        for tag in tags {
+            table.extend_from_slice(tag);
+            table.extend_from_slice(&[0; AXIS_SIZE as usize - 4]);
+        }
+        for (name_id, coordinates) in instances {
+            table.extend_from_slice(&name_id.to_be_bytes());
+            table.extend_from_slice(&[0; 2]);
+            for coordinate in *coordinates {
+                table.extend_from_slice(&((coordinate * 65536.0) as i32).to_be_bytes());
+            }
+            table.extend_from_slice(&[0xFF; 2]);
+        }
+        table
+    }
+
+    #[test]
+    fn fvar_instances_are_parsed() {
+        let fvar = fvar_table(
+            &[FontVariations::WEIGHT, FontVariations::WIDTH],
+            &[(256, &[700.0, 100.0]), (257, &[300.0, 62.5])],
+        );
+        let instances = parse_fvar_instances(&fvar);
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].0, 256);
+        assert_eq!(instances[0].1.axis(FontVariations::WEIGHT), Some(700.0));
+        assert_eq!(instances[0].1.axis(FontVariations::WIDTH), Some(100.0));
+        assert_eq!(instances[1].0, 257);
+        assert_eq!(instances[1].1.axis(FontVariations::WEIGHT), Some(300.0));
+        assert_eq!(instances[1].1.axis(FontVariations::WIDTH), Some(62.5));
+    }
+
+    #[test]
+    fn truncated_fvar_instances_are_skipped() {
+        let mut fvar = fvar_table(
+            &[FontVariations::WEIGHT],
+            &[(256, &[700.0]), (257, &[300.0])],
+        );
+        // The second instance loses its coordinate
+        fvar.truncate(fvar.len() - 6);
+        let instances = parse_fvar_instances(&fvar);
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].0, 256);
+
+        assert!(parse_fvar_instances(&[]).is_empty());
+    }
+}
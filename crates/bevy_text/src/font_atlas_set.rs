@@ -1,4 +1,4 @@
-use crate::{error::TextError, Font, FontAtlas};
+use crate::{error::TextError, Font, FontAtlas, FontVariations, FontVariationsKey};
 use ab_glyph::{GlyphId, OutlinedGlyph, Point};
 use bevy_asset::{AssetEvent, AssetId};
 use bevy_asset::{Assets, Handle};
@@ -14,13 +14,22 @@ type FontSizeKey = FloatOrd;
 #[derive(Default, Resource)]
 pub struct FontAtlasSets {
     // PERF: in theory this could be optimized with Assets storage ... consider making some fast "simple" AssetMap
-    pub(crate) sets: HashMap<AssetId<Font>, FontAtlasSet>,
+    pub(crate) sets: HashMap<(AssetId<Font>, FontVariationsKey), FontAtlasSet>,
 }
 
 impl FontAtlasSets {
+    /// Returns the font atlas set of the font `id` with its default variations.
     pub fn get(&self, id: impl Into<AssetId<Font>>) -> Option<&FontAtlasSet> {
-        let id: AssetId<Font> = id.into();
-        self.sets.get(&id)
+        self.get_with_variations(id, &FontVariations::default())
+    }
+
+    /// Returns the font atlas set of the instance of the font `id` with the given `variations`.
+    pub fn get_with_variations(
+        &self,
+        id: impl Into<AssetId<Font>>,
+        variations: &FontVariations,
+    ) -> Option<&FontAtlasSet> {
+        self.sets.get(&(id.into(), variations.key()))
     }
 }
 
@@ -31,7 +40,7 @@ pub fn remove_dropped_font_atlas_sets(
     // Clean up font atlas sets for removed fonts
     for event in font_events.read() {
         if let AssetEvent::Removed { id } = event {
-            font_atlas_sets.sets.remove(id);
+            font_atlas_sets.sets.retain(|(font_id, _), _| font_id != id);
         }
     }
 }
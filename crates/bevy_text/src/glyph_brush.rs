@@ -11,13 +11,13 @@ use glyph_brush_layout::{
 };
 
 use crate::{
-    error::TextError, BreakLineOn, Font, FontAtlasSet, FontAtlasSets, GlyphAtlasInfo, JustifyText,
-    TextSettings, YAxisOrientation,
+    error::TextError, BreakLineOn, Font, FontAtlasSet, FontAtlasSets, FontVariations,
+    FontVariationsKey, GlyphAtlasInfo, JustifyText, TextSettings, YAxisOrientation,
 };
 
 pub struct GlyphBrush {
     fonts: Vec<FontArc>,
-    asset_ids: Vec<AssetId<Font>>,
+    font_atlas_keys: Vec<(AssetId<Font>, FontVariationsKey)>,
     latest_font_id: FontId,
 }
 
@@ -25,7 +25,7 @@ impl Default for GlyphBrush {
     fn default() -> Self {
         GlyphBrush {
             fonts: Vec::new(),
-            asset_ids: Vec::new(),
+            font_atlas_keys: Vec::new(),
             latest_font_id: FontId(0),
         }
     }
@@ -64,22 +64,25 @@ impl GlyphBrush {
         textures: &mut Assets<Image>,
         text_settings: &TextSettings,
         y_axis_orientation: YAxisOrientation,
-    ) -> Result<Vec<PositionedGlyph>, TextError> {
+    ) -> Result<(Vec<PositionedGlyph>, Vec<PositionedCaret>), TextError> {
         if glyphs.is_empty() {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), Vec::new()));
         }
 
         let sections_data = sections
             .iter()
             .map(|section| {
-                let asset_id = &self.asset_ids[section.font_id.0];
-                let font = fonts.get(*asset_id).ok_or(TextError::NoSuchFont)?;
+                let font_atlas_key = &self.font_atlas_keys[section.font_id.0];
+                if !fonts.contains(font_atlas_key.0) {
+                    return Err(TextError::NoSuchFont);
+                }
+                let font = &self.fonts[section.font_id.0];
                 let font_size = section.scale.y;
                 Ok((
-                    asset_id,
+                    font_atlas_key,
                     font,
                     font_size,
-                    ab_glyph::Font::as_scaled(&font.font, font_size),
+                    ab_glyph::Font::as_scaled(font, font_size),
                 ))
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -87,6 +90,7 @@ impl GlyphBrush {
         let text_bounds = compute_text_bounds(&glyphs, |index| sections_data[index].3);
 
         let mut positioned_glyphs = Vec::new();
+        let mut positioned_carets = Vec::with_capacity(glyphs.len());
         for sg in glyphs {
             let SectionGlyph {
                 section_index: _,
@@ -98,11 +102,30 @@ impl GlyphBrush {
             let glyph_position = glyph.position;
             let adjust = GlyphPlacementAdjuster::new(&mut glyph);
             let section_data = sections_data[sg.section_index];
-            if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
+
+            // Whitespace has no outline, but the caret can still be placed before it
+            let scaled_font = &section_data.3;
+            let height = scaled_font.ascent() - scaled_font.descent();
+            let center_y = glyph_position.y - (scaled_font.ascent() + scaled_font.descent()) / 2.0;
+            positioned_carets.push(PositionedCaret {
+                position: Vec2::new(
+                    glyph_position.x - text_bounds.min.x,
+                    match y_axis_orientation {
+                        YAxisOrientation::BottomToTop => text_bounds.max.y - center_y,
+                        YAxisOrientation::TopToBottom => center_y - text_bounds.min.y,
+                    },
+                ),
+                advance: scaled_font.h_advance(glyph_id),
+                height,
+                section_index: sg.section_index,
+                byte_index,
+            });
+
+            if let Some(outlined_glyph) = section_data.1.outline_glyph(glyph) {
                 let bounds = outlined_glyph.px_bounds();
                 let font_atlas_set = font_atlas_sets
                     .sets
-                    .entry(*section_data.0)
+                    .entry(section_data.0.clone())
                     .or_insert_with(FontAtlasSet::default);
 
                 let atlas_info = font_atlas_set
@@ -148,16 +171,27 @@ impl GlyphBrush {
                 });
             }
         }
-        Ok(positioned_glyphs)
+        Ok((positioned_glyphs, positioned_carets))
     }
 
-    pub fn add_font(&mut self, asset_id: AssetId<Font>, font: FontArc) -> FontId {
+    /// Adds the instance of the font `asset_id` with the given `variations`.
+    pub fn add_font(
+        &mut self,
+        asset_id: AssetId<Font>,
+        variations: &FontVariations,
+        font: FontArc,
+    ) -> FontId {
         self.fonts.push(font);
-        self.asset_ids.push(asset_id);
+        self.font_atlas_keys.push((asset_id, variations.key()));
         let font_id = self.latest_font_id;
         self.latest_font_id = FontId(font_id.0 + 1);
         font_id
     }
+
+    /// Returns the font instance with the given `font_id`.
+    pub fn font(&self, font_id: FontId) -> &FontArc {
+        &self.fonts[font_id.0]
+    }
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -169,6 +203,22 @@ pub struct PositionedGlyph {
     pub byte_index: usize,
 }
 
+/// The position of a caret placed before a character of a text layout.
+///
+/// Unlike [`PositionedGlyph`]s, which are only created for the characters with an outline, there
+/// is a caret position for every character, including whitespace.
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct PositionedCaret {
+    /// The position of the center of the caret, at the start of the advance of the character.
+    pub position: Vec2,
+    /// The horizontal advance of the character, to place a caret after it.
+    pub advance: f32,
+    /// The height of the caret, from the ascent to the descent of the font.
+    pub height: f32,
+    pub section_index: usize,
+    pub byte_index: usize,
+}
+
 #[cfg(feature = "subpixel_glyph_atlas")]
 struct GlyphPlacementAdjuster;
 
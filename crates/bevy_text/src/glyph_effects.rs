@@ -0,0 +1,247 @@
+use std::f32::consts::TAU;
+
+use bevy_color::{Color, LinearRgba, Mix};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+
+use crate::TextLayoutInfo;
+
+/// A change to the way a single glyph of a text is drawn, see [`TextGlyphModifiers`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct GlyphModifier {
+    /// The offset of the glyph from its position in the layout, in logical pixels.
+    ///
+    /// Positive values move the glyph to the right and up, for both 2D and UI text.
+    pub offset: Vec2,
+    /// Overrides the color of the glyph, instead of the color of its [`TextStyle`](crate::TextStyle).
+    ///
+    /// The shadows and outlines of the glyph keep their own colors.
+    pub color: Option<Color>,
+}
+
+/// Changes the way each glyph of a text is drawn, without laying the text out again.
+///
+/// The modifiers are indexed like the glyphs of the [`TextLayoutInfo`] of the text: the glyph at
+/// `layout.glyphs[i]` is modified by `modifiers.0[i]`, and glyphs without a modifier are drawn
+/// unchanged. Whitespace has no glyph, so the indices don't match the characters of the text.
+///
+/// User systems can write the modifiers every frame for custom effects, reading the
+/// [`PositionedGlyph`](crate::PositionedGlyph)s of the layout to find the glyphs to modify. They
+/// are also written by [`TextGlyphEffects`].
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct TextGlyphModifiers(pub Vec<GlyphModifier>);
+
+/// Animates the glyphs of a text, for example for the dialogues of a game.
+///
+/// The effects are applied in order to the [`TextGlyphModifiers`] of the text, which are
+/// overwritten every frame: the offsets of the effects add up, and the last color wins.
+///
+/// ```
+/// # use bevy_text::{GlyphEffect, TextGlyphEffect, TextGlyphEffects};
+/// // Makes the second section of the text wave
+/// let effects = TextGlyphEffects(vec![TextGlyphEffect {
+///     effect: GlyphEffect::Wave {
+///         amplitude: 4.0,
+///         wavelength: 8.0,
+///         speed: 1.0,
+///     },
+///     section: Some(1),
+/// }]);
+/// ```
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[require(TextGlyphModifiers)]
+pub struct TextGlyphEffects(pub Vec<TextGlyphEffect>);
+
+/// A [`GlyphEffect`] applied to the glyphs of a text, see [`TextGlyphEffects`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct TextGlyphEffect {
+    /// How the glyphs are animated.
+    pub effect: GlyphEffect,
+    /// The index of the [`TextSection`](crate::TextSection) of the glyphs animated by the effect,
+    /// or `None` to animate the whole text.
+    pub section: Option<usize>,
+}
+
+/// An animation of the glyphs of a text.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub enum GlyphEffect {
+    /// Moves the glyphs up and down along a sine wave running through the text.
+    Wave {
+        /// The height of the wave above and below the line, in logical pixels.
+        amplitude: f32,
+        /// The length of the wave, in glyphs.
+        wavelength: f32,
+        /// The number of waves passing through each glyph per second.
+        speed: f32,
+    },
+    /// Moves each glyph to a random position around its own.
+    Shake {
+        /// The farthest a glyph moves along each axis, in logical pixels.
+        intensity: f32,
+        /// The number of times per second the glyphs move.
+        speed: f32,
+    },
+    /// Blends the color of the glyphs through a list of colors, looping back to the first one.
+    ColorCycle {
+        /// The colors the glyphs go through. The effect does nothing when this is empty.
+        colors: Vec<Color>,
+        /// The number of colors each glyph goes through per second.
+        speed: f32,
+        /// The difference between the colors of successive glyphs, in colors.
+        spread: f32,
+    },
+}
+
+impl GlyphEffect {
+    /// Applies the effect to the `modifier` of the glyph at `index` in the layout, `elapsed`
+    /// seconds after the start of the app.
+    pub fn apply(&self, index: usize, elapsed: f32, modifier: &mut GlyphModifier) {
+        let index = index as f32;
+        match self {
+            GlyphEffect::Wave {
+                amplitude,
+                wavelength,
+                speed,
+            } => {
+                let phase = elapsed * speed - index / wavelength;
+                modifier.offset.y += amplitude * (phase * TAU).sin();
+            }
+            GlyphEffect::Shake { intensity, speed } => {
+                let step = (elapsed * speed).floor();
+                modifier.offset += *intensity
+                    * Vec2::new(
+                        random_signed(index, step, 0.0),
+                        random_signed(index, step, 1.0),
+                    );
+            }
+            GlyphEffect::ColorCycle {
+                colors,
+                speed,
+                spread,
+            } => {
+                if colors.is_empty() {
+                    return;
+                }
+                let position = (elapsed * speed + index * spread).rem_euclid(colors.len() as f32);
+                let from = position as usize % colors.len();
+                let to = (from + 1) % colors.len();
+                let color = LinearRgba::from(colors[from])
+                    .mix(&LinearRgba::from(colors[to]), position.fract());
+                modifier.color = Some(color.into());
+            }
+        }
+    }
+}
+
+/// Returns a pseudo-random value in `[-1, 1]`, the same for the same arguments.
+fn random_signed(index: f32, step: f32, axis: f32) -> f32 {
+    let hash = (index * 12.9898 + step * 78.233 + axis * 37.719).sin() * 43758.547;
+    hash.rem_euclid(1.0) * 2.0 - 1.0
+}
+
+/// System writing the [`TextGlyphModifiers`] of the texts with [`TextGlyphEffects`].
+pub fn update_text_glyph_effects(
+    time: Res<Time>,
+    mut texts: Query<(&TextGlyphEffects, &TextLayoutInfo, &mut TextGlyphModifiers)>,
+) {
+    let elapsed = time.elapsed_seconds_wrapped();
+    for (effects, layout, mut modifiers) in &mut texts {
+        modifiers.0.clear();
+        modifiers
+            .0
+            .resize(layout.glyphs.len(), GlyphModifier::default());
+        for effect in &effects.0 {
+            for (index, (glyph, modifier)) in layout.glyphs.iter().zip(&mut modifiers.0).enumerate()
+            {
+                if effect
+                    .section
+                    .map_or(true, |section| section == glyph.section_index)
+                {
+                    effect.effect.apply(index, elapsed, modifier);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wave_offsets() {
+        let wave = GlyphEffect::Wave {
+            amplitude: 2.0,
+            wavelength: 4.0,
+            speed: 1.0,
+        };
+        let mut modifier = GlyphModifier::default();
+        wave.apply(1, 0.0, &mut modifier);
+        assert!((modifier.offset.y + 2.0).abs() < 1e-5);
+        assert_eq!(modifier.offset.x, 0.0);
+
+        // A quarter of a second later, the wave has moved by a glyph
+        let mut modifier = GlyphModifier::default();
+        wave.apply(1, 0.25, &mut modifier);
+        assert!(modifier.offset.y.abs() < 1e-5);
+    }
+
+    #[test]
+    fn shake_is_bounded_and_stable() {
+        let shake = GlyphEffect::Shake {
+            intensity: 3.0,
+            speed: 10.0,
+        };
+        for index in 0..32 {
+            let mut first = GlyphModifier::default();
+            shake.apply(index, 0.51, &mut first);
+            assert!(first.offset.abs().max_element() <= 3.0);
+
+            // The glyphs only move `speed` times per second
+            let mut second = GlyphModifier::default();
+            shake.apply(index, 0.59, &mut second);
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn color_cycle_blends_colors() {
+        let cycle = GlyphEffect::ColorCycle {
+            colors: vec![Color::BLACK, Color::WHITE],
+            speed: 1.0,
+            spread: 0.5,
+        };
+        let mut modifier = GlyphModifier::default();
+        cycle.apply(1, 0.0, &mut modifier);
+        assert_eq!(
+            modifier.color.map(LinearRgba::from),
+            Some(LinearRgba::rgb(0.5, 0.5, 0.5))
+        );
+
+        // The cycle loops back to the first color
+        let mut modifier = GlyphModifier::default();
+        cycle.apply(0, 2.0, &mut modifier);
+        assert_eq!(
+            modifier.color.map(LinearRgba::from),
+            Some(LinearRgba::BLACK)
+        );
+
+        let mut modifier = GlyphModifier::default();
+        GlyphEffect::ColorCycle {
+            colors: Vec::new(),
+            speed: 1.0,
+            spread: 1.0,
+        }
+        .apply(0, 1.0, &mut modifier);
+        assert_eq!(modifier.color, None);
+    }
+}
@@ -13,6 +13,7 @@ mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph_brush;
+mod glyph_effects;
 mod pipeline;
 mod text;
 mod text2d;
@@ -23,13 +24,17 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
+pub use glyph_effects::*;
 pub use pipeline::*;
 pub use text::*;
 pub use text2d::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Font, JustifyText, Text, Text2dBundle, TextError, TextSection, TextStyle};
+    pub use crate::{
+        Font, GlyphEffect, JustifyText, Text, Text2dBundle, TextError, TextGlyphEffect,
+        TextGlyphEffects, TextSection, TextStyle,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -87,6 +92,8 @@ impl Plugin for TextPlugin {
         app.init_asset::<Font>()
             .register_type::<Text>()
             .register_type::<Text2dBounds>()
+            .register_type::<TextGlyphModifiers>()
+            .register_type::<TextGlyphEffects>()
             .init_asset_loader::<FontLoader>()
             .init_resource::<TextSettings>()
             .init_resource::<FontAtlasSets>()
@@ -105,6 +112,7 @@ impl Plugin for TextPlugin {
                         // will never modify a pre-existing `Image` asset.
                         .ambiguous_with(CameraUpdateSystem),
                     remove_dropped_font_atlas_sets,
+                    update_text_glyph_effects.after(update_text2d_layout),
                 ),
             );
 
@@ -1,6 +1,7 @@
 use crate::{
     compute_text_bounds, error::TextError, glyph_brush::GlyphBrush, scale_value, BreakLineOn, Font,
-    FontAtlasSets, JustifyText, PositionedGlyph, Text, TextSection, TextSettings, YAxisOrientation,
+    FontAtlasSets, FontVariations, FontVariationsKey, JustifyText, PositionedCaret,
+    PositionedGlyph, Text, TextSection, TextSettings, YAxisOrientation,
 };
 use ab_glyph::PxScale;
 use bevy_asset::{AssetId, Assets, Handle};
@@ -18,7 +19,7 @@ use glyph_brush_layout::{FontId, GlyphPositioner, SectionGeometry, SectionText,
 #[derive(Default, Resource)]
 pub struct TextPipeline {
     brush: GlyphBrush,
-    map_font_id: HashMap<AssetId<Font>, FontId>,
+    map_font_id: HashMap<(AssetId<Font>, FontVariationsKey), FontId>,
 }
 
 /// Render information for a corresponding [`Text`] component.
@@ -28,16 +29,23 @@ pub struct TextPipeline {
 #[reflect(Component, Default)]
 pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
+    /// The caret positions before each character of the text, in the order of the layout.
+    pub carets: Vec<PositionedCaret>,
     pub logical_size: Vec2,
 }
 
 impl TextPipeline {
-    pub fn get_or_insert_font_id(&mut self, handle: &Handle<Font>, font: &Font) -> FontId {
+    pub fn get_or_insert_font_id(
+        &mut self,
+        handle: &Handle<Font>,
+        font: &Font,
+        variations: &FontVariations,
+    ) -> FontId {
         let brush = &mut self.brush;
         *self
             .map_font_id
-            .entry(handle.id())
-            .or_insert_with(|| brush.add_font(handle.id(), font.font.clone()))
+            .entry((handle.id(), variations.key()))
+            .or_insert_with(|| brush.add_font(handle.id(), variations, font.instance(variations)))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -62,10 +70,17 @@ impl TextPipeline {
                 let font = fonts
                     .get(&section.style.font)
                     .ok_or(TextError::NoSuchFont)?;
-                let font_id = self.get_or_insert_font_id(&section.style.font, font);
+                let font_id = self.get_or_insert_font_id(
+                    &section.style.font,
+                    font,
+                    &section.style.font_variations,
+                );
                 let font_size = scale_value(section.style.font_size, scale_factor);
 
-                scaled_fonts.push(ab_glyph::Font::as_scaled(&font.font, font_size));
+                scaled_fonts.push(ab_glyph::Font::into_scaled(
+                    self.brush.font(font_id).clone(),
+                    font_size,
+                ));
 
                 let section = SectionText {
                     font_id,
@@ -85,9 +100,9 @@ impl TextPipeline {
             return Ok(TextLayoutInfo::default());
         }
 
-        let size = compute_text_bounds(&section_glyphs, |index| scaled_fonts[index]).size();
+        let size = compute_text_bounds(&section_glyphs, |index| scaled_fonts[index].clone()).size();
 
-        let glyphs = self.brush.process_glyphs(
+        let (glyphs, carets) = self.brush.process_glyphs(
             section_glyphs,
             &sections,
             font_atlas_sets,
@@ -100,6 +115,7 @@ impl TextPipeline {
 
         Ok(TextLayoutInfo {
             glyphs,
+            carets,
             logical_size: size,
         })
     }
@@ -134,7 +150,7 @@ impl TextMeasureInfo {
         for (i, section) in sections.iter().enumerate() {
             match fonts.get(&section.style.font) {
                 Some(font) => {
-                    auto_fonts.push(font.font.clone());
+                    auto_fonts.push(font.instance(&section.style.font_variations));
                     out_sections.push(TextMeasureSection {
                         font_id: FontId(i),
                         scale: scale_value(section.style.font_size, scale_factor),
@@ -1,11 +1,12 @@
 use bevy_asset::Handle;
 use bevy_color::Color;
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::prelude::*;
 use bevy_utils::default;
 use serde::{Deserialize, Serialize};
 
-use crate::Font;
+use crate::{Font, FontVariations};
 
 #[derive(Component, Debug, Clone, Default, Reflect)]
 #[reflect(Component, Default)]
@@ -36,6 +37,7 @@ impl Text {
     ///         font: font_handle.clone(),
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// );
     ///
@@ -45,6 +47,7 @@ impl Text {
     ///         font: font_handle,
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// ) // You can still add text justifaction.
     /// .with_justify(JustifyText::Center);
@@ -73,6 +76,7 @@ impl Text {
     ///             font: font_handle.clone(),
     ///             font_size: 60.0,
     ///             color: BLUE.into(),
+    ///             ..Default::default()
     ///         },
     ///     ),
     ///     TextSection::new(
@@ -81,6 +85,7 @@ impl Text {
     ///             font: font_handle,
     ///             font_size: 60.0,
     ///             color: RED.into(),
+    ///             ..Default::default()
     ///         },
     ///     ),
     /// ]);
@@ -196,6 +201,21 @@ pub struct TextStyle {
     /// which can have a strong performance impact.
     pub font_size: f32,
     pub color: Color,
+    /// The values of the variation axes, such as the weight, used if [`font`](Self::font) is a
+    /// variable font.
+    ///
+    /// Like the font size, each combination of variations uses its own font atlases.
+    pub font_variations: FontVariations,
+    /// The shadow drawn behind the text, if any.
+    ///
+    /// This is currently only rendered for UI text.
+    pub shadow: Option<TextShadow>,
+    /// The outline drawn around the glyphs, if any.
+    ///
+    /// This is currently only rendered for UI text. The shadows and the outlines of the glyphs of
+    /// a section are drawn below all of its glyphs, but they may cover the glyphs of the
+    /// previous sections.
+    pub outline: Option<TextOutline>,
 }
 
 impl Default for TextStyle {
@@ -204,6 +224,55 @@ impl Default for TextStyle {
             font: Default::default(),
             font_size: 12.0,
             color: Color::WHITE,
+            font_variations: Default::default(),
+            shadow: None,
+            outline: None,
+        }
+    }
+}
+
+/// A shadow drawn behind the glyphs of a [`TextSection`], see [`TextStyle::shadow`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct TextShadow {
+    /// The offset of the shadow from the text, in logical pixels.
+    ///
+    /// Positive values move the shadow to the right and down.
+    pub offset: Vec2,
+    /// The radius of the blur of the shadow, in logical pixels.
+    ///
+    /// The blur is approximated by averaging a few samples of the glyphs, so large values
+    /// produce visible banding.
+    pub blur: f32,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::splat(2.0),
+            blur: 0.0,
+            color: Color::srgba(0.0, 0.0, 0.0, 0.75),
+        }
+    }
+}
+
+/// An outline drawn around the glyphs of a [`TextSection`], see [`TextStyle::outline`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct TextOutline {
+    /// The width of the outline, in logical pixels.
+    pub width: f32,
+    /// The color of the outline.
+    pub color: Color,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            color: Color::BLACK,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    BreakLineOn, Font, FontAtlasSets, PositionedGlyph, Text, TextError, TextLayoutInfo,
-    TextPipeline, TextSettings, YAxisOrientation,
+    BreakLineOn, Font, FontAtlasSets, PositionedGlyph, Text, TextError, TextGlyphModifiers,
+    TextLayoutInfo, TextPipeline, TextSettings, YAxisOrientation,
 };
 use bevy_asset::Assets;
 use bevy_color::LinearRgba;
@@ -23,7 +23,10 @@ use bevy_render::{
     view::{InheritedVisibility, NoFrustumCulling, ViewVisibility, Visibility},
     Extract,
 };
-use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, SpriteSource, TextureAtlasLayout};
+use bevy_sprite::{
+    AlphaMode2d, Anchor, ExtractedSprite, ExtractedSprites, SpriteEffect, SpriteSampler,
+    SpriteSource, SpriteStencil, TextureAtlasLayout,
+};
 use bevy_transform::prelude::{GlobalTransform, Transform};
 use bevy_utils::HashSet;
 use bevy_window::{PrimaryWindow, Window, WindowScaleFactorChanged};
@@ -106,6 +109,7 @@ pub fn extract_text2d_sprite(
             &TextLayoutInfo,
             &Anchor,
             &GlobalTransform,
+            Option<&TextGlyphModifiers>,
         )>,
     >,
 ) {
@@ -116,8 +120,15 @@ pub fn extract_text2d_sprite(
         .unwrap_or(1.0);
     let scaling = GlobalTransform::from_scale(Vec2::splat(scale_factor.recip()).extend(1.));
 
-    for (original_entity, view_visibility, text, text_layout_info, anchor, global_transform) in
-        text2d_query.iter()
+    for (
+        original_entity,
+        view_visibility,
+        text,
+        text_layout_info,
+        anchor,
+        global_transform,
+        modifiers,
+    ) in text2d_query.iter()
     {
         if !view_visibility.get() {
             continue;
@@ -130,17 +141,26 @@ pub fn extract_text2d_sprite(
             * scaling;
         let mut color = LinearRgba::WHITE;
         let mut current_section = usize::MAX;
-        for PositionedGlyph {
-            position,
-            atlas_info,
-            section_index,
-            ..
-        } in &text_layout_info.glyphs
+        for (
+            index,
+            PositionedGlyph {
+                position,
+                atlas_info,
+                section_index,
+                ..
+            },
+        ) in text_layout_info.glyphs.iter().enumerate()
         {
             if *section_index != current_section {
                 color = LinearRgba::from(text.sections[*section_index].style.color);
                 current_section = *section_index;
             }
+            let modifier = modifiers
+                .and_then(|modifiers| modifiers.0.get(index))
+                .copied()
+                .unwrap_or_default();
+            // The glyphs are laid out in physical pixels, the modifiers are in logical pixels
+            let position = *position + modifier.offset * scale_factor;
             let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
 
             let entity = commands.spawn_empty().id();
@@ -148,13 +168,25 @@ pub fn extract_text2d_sprite(
                 entity,
                 ExtractedSprite {
                     transform: transform * GlobalTransform::from_translation(position.extend(0.)),
-                    color,
+                    color: modifier.color.map_or(color, LinearRgba::from),
                     rect: Some(atlas.textures[atlas_info.glyph_index].as_rect()),
                     custom_size: None,
                     image_handle_id: atlas_info.texture.id(),
                     flip_x: false,
                     flip_y: false,
                     anchor: Anchor::Center.as_vec(),
+                    premultiplied_alpha: false,
+                    alpha_mode: AlphaMode2d::Blend,
+                    effect: SpriteEffect::None,
+                    sampler: SpriteSampler::Image,
+                    uv_offset: Vec2::ZERO,
+                    uv_scale: Vec2::ONE,
+                    uv_repeat: false,
+                    sort_key: None,
+                    normal_map: None,
+                    emissive: 1.0,
+                    stencil: SpriteStencil::None,
+                    material: None,
                     original_entity: Some(original_entity),
                 },
             );
@@ -3,15 +3,15 @@ use crate::{
     Node, UiImage,
 };
 use bevy_a11y::{
-    accesskit::{NodeBuilder, Rect, Role},
-    AccessibilityNode,
+    accesskit::{Action, ActionData, NodeBuilder, Rect, Role},
+    AccessibilityNode, ActionRequest, Focus,
 };
-use bevy_app::{App, Plugin, PostUpdate};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
 use bevy_ecs::{
-    prelude::{DetectChanges, Entity},
-    query::{Changed, Without},
+    prelude::{Component, DetectChanges, Entity, Event, EventReader, EventWriter},
+    query::{Changed, Or, With, Without},
     schedule::IntoSystemConfigs,
-    system::{Commands, Query},
+    system::{Commands, Query, ResMut},
     world::Ref,
 };
 use bevy_hierarchy::Children;
@@ -19,6 +19,75 @@ use bevy_render::{camera::CameraUpdateSystem, prelude::Camera};
 use bevy_text::Text;
 use bevy_transform::prelude::GlobalTransform;
 
+/// Overrides the role of a UI node in the accessibility tree.
+///
+/// Nodes with this component are no longer given a role automatically from their
+/// [`Button`], [`Label`] or [`UiImage`] components, which makes it possible to expose
+/// custom widgets, such as sliders or checkboxes, to screen readers.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccessibleRole(pub Role);
+
+/// The name announced by screen readers for a UI node.
+///
+/// Takes precedence over the name computed from the node's [`Text`] children.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessibleLabel(pub String);
+
+/// A numeric value exposed to the accessibility tree, for range widgets such as sliders.
+///
+/// Requires an [`AccessibleRole`] to be picked up. Assistive technologies can
+/// increment, decrement or set the value, which updates this component and sends an
+/// [`AccessibilityAction`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct AccessibleValue {
+    /// The current value.
+    pub value: f64,
+    /// The minimum value.
+    pub min: f64,
+    /// The maximum value.
+    pub max: f64,
+    /// The amount by which increment and decrement actions change the value.
+    pub step: f64,
+}
+
+impl AccessibleValue {
+    /// Creates a new value in the `min..=max` range, changed by `step` on increment and decrement.
+    pub fn new(value: f64, min: f64, max: f64, step: f64) -> Self {
+        Self {
+            value: value.clamp(min, max),
+            min,
+            max,
+            step,
+        }
+    }
+
+    /// Sets the value, clamped to the `min..=max` range.
+    pub fn set(&mut self, value: f64) {
+        self.value = value.clamp(self.min, self.max);
+    }
+}
+
+impl Default for AccessibleValue {
+    fn default() -> Self {
+        Self::new(0., 0., 1., 0.1)
+    }
+}
+
+/// Sent when an assistive technology requests an action on a UI node.
+///
+/// Focus, blur, increment, decrement and set value actions are already applied to the
+/// [`Focus`] resource and the node's [`AccessibleValue`] when this event is sent. Other
+/// actions, such as [`Action::Default`] (the equivalent of a click), are left to the app.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct AccessibilityAction {
+    /// The targeted UI node.
+    pub entity: Entity,
+    /// The requested action.
+    pub action: Action,
+    /// The data attached to the action, if any.
+    pub data: Option<ActionData>,
+}
+
 fn calc_name(texts: &Query<&Text>, children: &Children) -> Option<Box<str>> {
     let mut name = None;
     for child in children {
@@ -41,7 +110,7 @@ fn calc_bounds(
     if let Ok((camera, camera_transform)) = camera.get_single() {
         for (mut accessible, node, transform) in &mut nodes {
             if node.is_changed() || transform.is_changed() {
-                if let Some(translation) =
+                if let Ok(translation) =
                     camera.world_to_viewport(camera_transform, transform.translation())
                 {
                     let bounds = Rect::new(
@@ -59,11 +128,24 @@ fn calc_bounds(
 
 fn button_changed(
     mut commands: Commands,
-    mut query: Query<(Entity, &Children, Option<&mut AccessibilityNode>), Changed<Button>>,
+    mut query: Query<
+        (
+            Entity,
+            &Children,
+            Option<&AccessibleLabel>,
+            Option<&mut AccessibilityNode>,
+        ),
+        (
+            Or<(Changed<Button>, Changed<AccessibleLabel>)>,
+            Without<AccessibleRole>,
+        ),
+    >,
     texts: Query<&Text>,
 ) {
-    for (entity, children, accessible) in &mut query {
-        let name = calc_name(&texts, children);
+    for (entity, children, label, accessible) in &mut query {
+        let name = label
+            .map(|label| label.0.clone().into_boxed_str())
+            .or_else(|| calc_name(&texts, children));
         if let Some(mut accessible) = accessible {
             accessible.set_role(Role::Button);
             if let Some(name) = name {
@@ -86,13 +168,24 @@ fn button_changed(
 fn image_changed(
     mut commands: Commands,
     mut query: Query<
-        (Entity, &Children, Option<&mut AccessibilityNode>),
-        (Changed<UiImage>, Without<Button>),
+        (
+            Entity,
+            &Children,
+            Option<&AccessibleLabel>,
+            Option<&mut AccessibilityNode>,
+        ),
+        (
+            Or<(Changed<UiImage>, Changed<AccessibleLabel>)>,
+            Without<Button>,
+            Without<AccessibleRole>,
+        ),
     >,
     texts: Query<&Text>,
 ) {
-    for (entity, children, accessible) in &mut query {
-        let name = calc_name(&texts, children);
+    for (entity, children, label, accessible) in &mut query {
+        let name = label
+            .map(|label| label.0.clone().into_boxed_str())
+            .or_else(|| calc_name(&texts, children));
         if let Some(mut accessible) = accessible {
             accessible.set_role(Role::Image);
             if let Some(name) = name {
@@ -114,15 +207,32 @@ fn image_changed(
 
 fn label_changed(
     mut commands: Commands,
-    mut query: Query<(Entity, &Text, Option<&mut AccessibilityNode>), Changed<Label>>,
+    mut query: Query<
+        (
+            Entity,
+            &Text,
+            Option<&AccessibleLabel>,
+            Option<&mut AccessibilityNode>,
+        ),
+        (
+            Or<(Changed<Label>, Changed<AccessibleLabel>)>,
+            With<Label>,
+            Without<AccessibleRole>,
+        ),
+    >,
 ) {
-    for (entity, text, accessible) in &mut query {
-        let values = text
-            .sections
-            .iter()
-            .map(|v| v.value.to_string())
-            .collect::<Vec<String>>();
-        let name = Some(values.join(" ").into_boxed_str());
+    for (entity, text, label, accessible) in &mut query {
+        let name = Some(label.map_or_else(
+            || {
+                let values = text
+                    .sections
+                    .iter()
+                    .map(|v| v.value.to_string())
+                    .collect::<Vec<String>>();
+                values.join(" ").into_boxed_str()
+            },
+            |label| label.0.clone().into_boxed_str(),
+        ));
         if let Some(mut accessible) = accessible {
             accessible.set_role(Role::StaticText);
             if let Some(name) = name {
@@ -142,11 +252,107 @@ fn label_changed(
     }
 }
 
+fn role_changed(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &AccessibleRole,
+            Option<&AccessibleLabel>,
+            Option<&AccessibleValue>,
+            Option<&Children>,
+            Option<&mut AccessibilityNode>,
+        ),
+        Or<(
+            Changed<AccessibleRole>,
+            Changed<AccessibleLabel>,
+            Changed<AccessibleValue>,
+        )>,
+    >,
+    texts: Query<&Text>,
+) {
+    for (entity, role, label, value, children, accessible) in &mut query {
+        let name = label
+            .map(|label| label.0.clone().into_boxed_str())
+            .or_else(|| children.and_then(|children| calc_name(&texts, children)));
+        let mut node = NodeBuilder::new(role.0);
+        if let Some(name) = name {
+            node.set_name(name);
+        }
+        node.add_action(Action::Focus);
+        if let Some(value) = value {
+            node.set_numeric_value(value.value);
+            node.set_min_numeric_value(value.min);
+            node.set_max_numeric_value(value.max);
+            node.set_numeric_value_step(value.step);
+            node.add_action(Action::Increment);
+            node.add_action(Action::Decrement);
+            node.add_action(Action::SetValue);
+        } else {
+            node.add_action(Action::Default);
+        }
+        if let Some(mut accessible) = accessible {
+            accessible.0 = node;
+        } else {
+            commands
+                .entity(entity)
+                .try_insert(AccessibilityNode::from(node));
+        }
+    }
+}
+
+/// Applies the actions requested by assistive technologies to UI nodes, and forwards them
+/// as [`AccessibilityAction`] events.
+fn handle_action_requests(
+    mut requests: EventReader<ActionRequest>,
+    mut focus: ResMut<Focus>,
+    mut values: Query<&mut AccessibleValue>,
+    nodes: Query<(), (With<Node>, With<AccessibilityNode>)>,
+    mut actions: EventWriter<AccessibilityAction>,
+) {
+    for request in requests.read() {
+        let Ok(entity) = Entity::try_from_bits(request.target.0) else {
+            continue;
+        };
+        if !nodes.contains(entity) {
+            continue;
+        }
+        match request.action {
+            Action::Focus => focus.0 = Some(entity),
+            Action::Blur if focus.0 == Some(entity) => focus.0 = None,
+            Action::Increment | Action::Decrement | Action::SetValue => {
+                if let Ok(mut value) = values.get_mut(entity) {
+                    let new_value = match (request.action, &request.data) {
+                        (Action::Increment, _) => value.value + value.step,
+                        (Action::Decrement, _) => value.value - value.step,
+                        (_, Some(ActionData::NumericValue(new_value))) => *new_value,
+                        (_, Some(ActionData::Value(new_value))) => {
+                            new_value.parse().unwrap_or(value.value)
+                        }
+                        _ => value.value,
+                    };
+                    value.set(new_value);
+                }
+            }
+            _ => {}
+        }
+        actions.send(AccessibilityAction {
+            entity,
+            action: request.action,
+            data: request.data.clone(),
+        });
+    }
+}
+
 /// `AccessKit` integration for `bevy_ui`.
 pub(crate) struct AccessibilityPlugin;
 
 impl Plugin for AccessibilityPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<Focus>()
+            .add_event::<ActionRequest>()
+            .add_event::<AccessibilityAction>()
+            .add_systems(PreUpdate, handle_action_requests);
         app.add_systems(
             PostUpdate,
             (
@@ -159,6 +365,7 @@ impl Plugin for AccessibilityPlugin {
                 button_changed,
                 image_changed,
                 label_changed,
+                role_changed,
             ),
         );
     }
@@ -0,0 +1,504 @@
+//! This module contains the drag-and-drop of UI nodes: the [`Draggable`] nodes, the
+//! [`DropTarget`]s they can be dropped on, the [`DragMap`] of the current drags, and the events
+//! sent by [`update_drag_and_drop`].
+
+use bevy_ecs::prelude::*;
+use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::HashMap;
+
+use crate::{HoverMap, Interaction, PointerId, UiPointerEvent, UiPointerEventKind, UiStack};
+
+/// The distance a pointer pressed on a [`Draggable`] node must move before the node is dragged,
+/// in logical pixels, so that clicking the node doesn't drag it.
+pub const DRAG_THRESHOLD: f32 = 4.;
+
+/// A set of drag groups, used to filter the [`Draggable`] nodes accepted by a [`DropTarget`].
+///
+/// A node can be dropped on a target if they share at least one group.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct DragGroups(pub u32);
+
+impl DragGroups {
+    /// All the groups.
+    pub const ALL: Self = Self(u32::MAX);
+    /// No group.
+    pub const NONE: Self = Self(0);
+
+    /// Returns the set containing only the group `group`, which must be less than 32.
+    pub const fn group(group: u32) -> Self {
+        Self(1 << group)
+    }
+
+    /// Returns `true` if `self` and `other` share at least one group.
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl Default for DragGroups {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Marks a UI node that can be dragged by pressing a pointer on it, or on one of its descendants,
+/// and moving it.
+///
+/// The dragged node is the payload of the drag, unless it has a [`DragPayload`]: the systems
+/// handling the [`DragStart`], [`DragOver`] and [`DragDrop`] events query the components of
+/// [`payload`](DragDrop::payload) they need, such as the item of an inventory slot.
+///
+/// Dragging doesn't move the node. A node following the pointer should have
+/// [`FocusPolicy::Pass`](crate::FocusPolicy::Pass), so that it doesn't hide the drop targets
+/// under it.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Draggable {
+    /// The groups of the node, which must be accepted by a [`DropTarget`] to be dropped on it.
+    pub groups: DragGroups,
+}
+
+/// The entity carried by the drags of a [`Draggable`] node, instead of the node itself.
+///
+/// This lets the node of an inventory slot carry the entity of the item it shows, for example, so
+/// that the drop targets can query the components of the item.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct DragPayload(pub Entity);
+
+/// Marks a UI node that [`Draggable`] nodes can be dropped on.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct DropTarget {
+    /// The groups of the [`Draggable`] nodes accepted by this target.
+    pub accepts: DragGroups,
+}
+
+/// Sent when a pointer starts dragging a [`Draggable`] node.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragStart {
+    /// The pointer dragging the node.
+    pub pointer: PointerId,
+    /// The dragged node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`DragPayload`].
+    pub payload: Entity,
+}
+
+/// Sent every frame a dragged node is over a [`DropTarget`] accepting it.
+///
+/// The target is the topmost accepting node under the pointer.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragOver {
+    /// The pointer dragging the node.
+    pub pointer: PointerId,
+    /// The dragged node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`DragPayload`].
+    pub payload: Entity,
+    /// The drop target under the pointer.
+    pub target: Entity,
+}
+
+/// Sent when a dragged node is released over a [`DropTarget`] accepting it.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragDrop {
+    /// The pointer that dragged the node.
+    pub pointer: PointerId,
+    /// The dropped node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`DragPayload`].
+    pub payload: Entity,
+    /// The drop target under the pointer.
+    pub target: Entity,
+}
+
+/// Sent when a drag ends, after the [`DragDrop`] if the node was dropped on a target.
+///
+/// This is also sent when the node is released outside of a target, or despawned while dragged.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragEnd {
+    /// The pointer that dragged the node.
+    pub pointer: PointerId,
+    /// The dragged node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`DragPayload`].
+    pub payload: Entity,
+    /// The target the node was dropped on, if any.
+    pub target: Option<Entity>,
+}
+
+/// The [`Draggable`] nodes dragged by each pointer.
+///
+/// Updated in [`update_drag_and_drop`].
+#[derive(Resource, Debug, Default)]
+pub struct DragMap {
+    drags: HashMap<PointerId, PointerDrag>,
+}
+
+#[derive(Debug)]
+struct PointerDrag {
+    dragged: Entity,
+    /// The entity carried by the drag, read when the pointer is pressed.
+    payload: Entity,
+    /// The camera and the position of the pointer in its viewport when it was pressed.
+    start: Option<(Entity, Vec2)>,
+    /// Whether the pointer moved by [`DRAG_THRESHOLD`] since it was pressed.
+    started: bool,
+    /// The drop target under the pointer in the last frame.
+    target: Option<Entity>,
+}
+
+impl DragMap {
+    /// Returns the node dragged by `pointer`, if any.
+    pub fn get(&self, pointer: PointerId) -> Option<Entity> {
+        self.drags
+            .get(&pointer)
+            .filter(|drag| drag.started)
+            .map(|drag| drag.dragged)
+    }
+
+    /// Returns the entity carried by the drag of `pointer`, if any, see [`DragPayload`].
+    pub fn payload(&self, pointer: PointerId) -> Option<Entity> {
+        self.drags
+            .get(&pointer)
+            .filter(|drag| drag.started)
+            .map(|drag| drag.payload)
+    }
+
+    /// Returns `true` if `pointer` is pressed on a [`Draggable`] node, even if it hasn't moved by
+    /// [`DRAG_THRESHOLD`] yet.
+    pub(crate) fn has_pointer(&self, pointer: PointerId) -> bool {
+        self.drags.contains_key(&pointer)
+    }
+
+    /// Returns `true` if any pointer drags `entity`.
+    pub fn is_dragged(&self, entity: Entity) -> bool {
+        self.drags
+            .values()
+            .any(|drag| drag.started && drag.dragged == entity)
+    }
+}
+
+/// Starts, updates and ends the drags of the [`Draggable`] nodes in the [`DragMap`], from the
+/// [`UiPointerEvent`]s and the [`HoverMap`], and sends the [`DragStart`], [`DragOver`], [`DragDrop`]
+/// and [`DragEnd`] events.
+#[allow(clippy::too_many_arguments)]
+pub fn update_drag_and_drop(
+    mut drag_map: ResMut<DragMap>,
+    mut pointer_events: EventReader<UiPointerEvent>,
+    mut start_events: EventWriter<DragStart>,
+    mut over_events: EventWriter<DragOver>,
+    mut drop_events: EventWriter<DragDrop>,
+    mut end_events: EventWriter<DragEnd>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches_input: Res<Touches>,
+    hover_map: Res<HoverMap>,
+    ui_stack: Res<UiStack>,
+    draggable_query: Query<(&Draggable, Option<&DragPayload>)>,
+    drop_target_query: Query<&DropTarget>,
+) {
+    let drags = &mut drag_map.drags;
+
+    // The events bubble up from the pressed node, so the first draggable listener is the
+    // innermost draggable node.
+    for event in pointer_events.read() {
+        if event.kind != UiPointerEventKind::Down || drags.contains_key(&event.pointer) {
+            continue;
+        }
+        if let Ok((_, payload)) = draggable_query.get(event.listener) {
+            let start = hover_map.pointer_camera_positions(event.pointer).next();
+            drags.insert(
+                event.pointer,
+                PointerDrag {
+                    dragged: event.listener,
+                    payload: payload.map_or(event.listener, |payload| payload.0),
+                    start,
+                    started: false,
+                    target: None,
+                },
+            );
+        }
+    }
+
+    drags.retain(|&pointer, drag| {
+        let Ok((draggable, _)) = draggable_query.get(drag.dragged) else {
+            if drag.started {
+                end_events.send(DragEnd {
+                    pointer,
+                    dragged: drag.dragged,
+                    payload: drag.payload,
+                    target: None,
+                });
+            }
+            return false;
+        };
+        let released = match pointer {
+            PointerId::Mouse => !mouse_button_input.pressed(MouseButton::Left),
+            PointerId::Touch(id) => touches_input.get_pressed(id).is_none(),
+        };
+
+        if !drag.started {
+            if released {
+                return false;
+            }
+            drag.started = drag.start.map_or(true, |(camera, start)| {
+                hover_map
+                    .pointer_position(pointer, camera)
+                    .is_some_and(|position| position.distance(start) >= DRAG_THRESHOLD)
+            });
+            if !drag.started {
+                return true;
+            }
+            start_events.send(DragStart {
+                pointer,
+                dragged: drag.dragged,
+                payload: drag.payload,
+            });
+        }
+
+        let accepts = |entity: Entity| {
+            drop_target_query
+                .get(entity)
+                .is_ok_and(|drop_target| drop_target.accepts.intersects(draggable.groups))
+        };
+        // Touches leave the hover map once they end, so they are dropped where they last were.
+        let target = if hover_map.pointers().any(|hovering| hovering == pointer) {
+            ui_stack.uinodes.iter().rev().copied().find(|&entity| {
+                entity != drag.dragged
+                    && hover_map.get(pointer, entity) != Interaction::None
+                    && accepts(entity)
+            })
+        } else {
+            drag.target.filter(|&entity| accepts(entity))
+        };
+        drag.target = target;
+
+        if !released {
+            if let Some(target) = target {
+                over_events.send(DragOver {
+                    pointer,
+                    dragged: drag.dragged,
+                    payload: drag.payload,
+                    target,
+                });
+            }
+            return true;
+        }
+
+        if let Some(target) = target {
+            drop_events.send(DragDrop {
+                pointer,
+                dragged: drag.dragged,
+                payload: drag.payload,
+                target,
+            });
+        }
+        end_events.send(DragEnd {
+            pointer,
+            dragged: drag.dragged,
+            payload: drag.payload,
+            target,
+        });
+        false
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, prelude::*, schedule::Schedule};
+    use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
+    use bevy_math::Vec2;
+
+    use crate::{HoverMap, Interaction, PointerId, UiPointerEvent, UiPointerEventKind, UiStack};
+
+    use super::{
+        update_drag_and_drop, DragDrop, DragEnd, DragGroups, DragMap, DragOver, DragPayload,
+        DragStart, Draggable, DropTarget,
+    };
+
+    fn drain<E: Event>(world: &mut World) -> Vec<E> {
+        world.resource_mut::<Events<E>>().drain().collect()
+    }
+
+    fn move_mouse(world: &mut World, camera: Entity, position: Vec2) {
+        let mut hover_map = world.resource_mut::<HoverMap>();
+        hover_map
+            .positions
+            .entry(PointerId::Mouse)
+            .or_default()
+            .insert(camera, position);
+    }
+
+    fn drag_world() -> (World, Schedule) {
+        let mut world = World::default();
+        world.init_resource::<DragMap>();
+        world.init_resource::<HoverMap>();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        world.init_resource::<Touches>();
+        world.init_resource::<Events<UiPointerEvent>>();
+        world.init_resource::<Events<DragStart>>();
+        world.init_resource::<Events<DragOver>>();
+        world.init_resource::<Events<DragDrop>>();
+        world.init_resource::<Events<DragEnd>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_drag_and_drop);
+        (world, schedule)
+    }
+
+    fn press_mouse(world: &mut World, node: Entity) {
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        world.send_event(UiPointerEvent {
+            pointer: PointerId::Mouse,
+            kind: UiPointerEventKind::Down,
+            target: node,
+            listener: node,
+        });
+    }
+
+    #[test]
+    fn nodes_are_dragged_past_the_threshold_and_dropped_on_targets() {
+        let (mut world, mut schedule) = drag_world();
+
+        let camera = world.spawn_empty().id();
+        let item = world
+            .spawn(Draggable {
+                groups: DragGroups::group(1),
+            })
+            .id();
+        let slot = world
+            .spawn(DropTarget {
+                accepts: DragGroups::group(1),
+            })
+            .id();
+        let other_slot = world
+            .spawn(DropTarget {
+                accepts: DragGroups::group(2),
+            })
+            .id();
+        world.insert_resource(UiStack {
+            uinodes: vec![slot, other_slot, item],
+        });
+
+        // Pressing the item doesn't drag it until the pointer moves
+        move_mouse(&mut world, camera, Vec2::new(10., 10.));
+        world
+            .resource_mut::<HoverMap>()
+            .pointers
+            .entry(PointerId::Mouse)
+            .or_default()
+            .extend([
+                (item, Interaction::Pressed),
+                (other_slot, Interaction::Hovered),
+                (slot, Interaction::Hovered),
+            ]);
+        press_mouse(&mut world, item);
+        schedule.run(&mut world);
+        move_mouse(&mut world, camera, Vec2::new(12., 10.));
+        schedule.run(&mut world);
+        assert!(drain::<DragStart>(&mut world).is_empty());
+        assert_eq!(world.resource::<DragMap>().get(PointerId::Mouse), None);
+
+        // The drag skips the targets which don't accept the groups of the item
+        move_mouse(&mut world, camera, Vec2::new(20., 10.));
+        schedule.run(&mut world);
+        assert_eq!(
+            drain::<DragStart>(&mut world),
+            vec![DragStart {
+                pointer: PointerId::Mouse,
+                dragged: item,
+                payload: item,
+            }]
+        );
+        assert_eq!(
+            drain::<DragOver>(&mut world),
+            vec![DragOver {
+                pointer: PointerId::Mouse,
+                dragged: item,
+                payload: item,
+                target: slot,
+            }]
+        );
+        assert!(world.resource::<DragMap>().is_dragged(item));
+
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        schedule.run(&mut world);
+        assert_eq!(
+            drain::<DragDrop>(&mut world),
+            vec![DragDrop {
+                pointer: PointerId::Mouse,
+                dragged: item,
+                payload: item,
+                target: slot,
+            }]
+        );
+        assert_eq!(
+            drain::<DragEnd>(&mut world),
+            vec![DragEnd {
+                pointer: PointerId::Mouse,
+                dragged: item,
+                payload: item,
+                target: Some(slot),
+            }]
+        );
+        assert!(!world.resource::<DragMap>().is_dragged(item));
+    }
+
+    #[test]
+    fn drags_carry_their_payload() {
+        let (mut world, mut schedule) = drag_world();
+
+        let camera = world.spawn_empty().id();
+        let sword = world.spawn_empty().id();
+        let item = world.spawn((Draggable::default(), DragPayload(sword))).id();
+        let slot = world.spawn(DropTarget::default()).id();
+        world.insert_resource(UiStack {
+            uinodes: vec![slot, item],
+        });
+
+        move_mouse(&mut world, camera, Vec2::ZERO);
+        world
+            .resource_mut::<HoverMap>()
+            .pointers
+            .entry(PointerId::Mouse)
+            .or_default()
+            .extend([(item, Interaction::Pressed), (slot, Interaction::Hovered)]);
+        press_mouse(&mut world, item);
+        schedule.run(&mut world);
+        move_mouse(&mut world, camera, Vec2::new(10., 0.));
+        schedule.run(&mut world);
+        assert_eq!(
+            drain::<DragStart>(&mut world),
+            vec![DragStart {
+                pointer: PointerId::Mouse,
+                dragged: item,
+                payload: sword,
+            }]
+        );
+        assert_eq!(
+            world.resource::<DragMap>().payload(PointerId::Mouse),
+            Some(sword)
+        );
+
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        schedule.run(&mut world);
+        assert_eq!(
+            drain::<DragDrop>(&mut world),
+            vec![DragDrop {
+                pointer: PointerId::Mouse,
+                dragged: item,
+                payload: sword,
+                target: slot,
+            }]
+        );
+    }
+}
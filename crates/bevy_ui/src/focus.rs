@@ -1,4 +1,8 @@
-use crate::{CalculatedClip, DefaultUiCamera, Node, TargetCamera, UiScale, UiStack};
+use crate::{
+    resolve_border_radius, BorderRadius, CalculatedClip, DefaultUiCamera, Node, TargetCamera,
+    UiScale, UiStack,
+};
+use bevy_asset::{Assets, Handle};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::Entity,
@@ -8,9 +12,12 @@ use bevy_ecs::{
     system::{Local, Query, Res},
 };
 use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Rect, UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::{camera::NormalizedRenderTarget, prelude::Camera, view::ViewVisibility};
+use bevy_render::{
+    camera::NormalizedRenderTarget, prelude::Camera, render_resource::TextureFormat,
+    texture::Image, view::ViewVisibility,
+};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
 use bevy_window::{PrimaryWindow, Window};
@@ -122,6 +129,94 @@ impl Default for FocusPolicy {
     }
 }
 
+/// The shape of a node hit by the pointers, instead of its rect rounded by its [`BorderRadius`].
+///
+/// Circular buttons and irregular HUD elements only respond to the pointers over their visible
+/// parts with a shape matching them. The shape is stretched over the node, and is still clipped
+/// by the node's rect and [`CalculatedClip`].
+///
+/// Used by both [`ui_focus_system`] and [`update_hover_map`](crate::update_hover_map).
+#[derive(Component, Clone, PartialEq, Debug, Reflect)]
+#[reflect(Component, PartialEq)]
+pub enum PickingShape {
+    /// The circle inscribed in the node, which is an ellipse if the node isn't square.
+    Circle,
+    /// A polygon, whose vertices are relative to the size of the node: `(0., 0.)` is the top-left
+    /// corner, and `(1., 1.)` the bottom-right one, like [`RelativeCursorPosition`].
+    ///
+    /// The polygon is usually convex, but any polygon whose edges don't cross is picked correctly.
+    Polygon(Vec<Vec2>),
+    /// The pixels of an image whose alpha is at least `alpha_threshold`.
+    ///
+    /// Only images with an 8-bit RGBA or BGRA format are alpha-tested, and an 8-bit single channel
+    /// format is used as the mask itself. The node is picked by its rect while the image is loading
+    /// or if its format isn't supported. The image must be kept in the main world, with
+    /// [`RenderAssetUsages::MAIN_WORLD`](bevy_render::render_asset::RenderAssetUsages::MAIN_WORLD).
+    ImageMask {
+        /// The image stretched over the node.
+        image: Handle<Image>,
+        /// The minimum alpha of the picked pixels, between `0.` and `1.`.
+        alpha_threshold: f32,
+    },
+}
+
+impl PickingShape {
+    /// Creates an image mask picking the pixels of `image` which aren't fully transparent.
+    pub fn image_mask(image: Handle<Image>) -> Self {
+        Self::ImageMask {
+            image,
+            alpha_threshold: f32::EPSILON,
+        }
+    }
+
+    /// Returns `true` if `point`, relative to the size of the node, is inside the shape.
+    ///
+    /// Returns `None` for an [`PickingShape::ImageMask`] whose image isn't available.
+    fn contains(&self, point: Vec2, images: &Assets<Image>) -> Option<bool> {
+        match self {
+            PickingShape::Circle => Some((point - 0.5).length_squared() <= 0.25),
+            PickingShape::Polygon(vertices) => {
+                // Count the edges crossed by a ray going right from the point
+                let mut inside = false;
+                let mut previous = *vertices.last()?;
+                for &vertex in vertices {
+                    if (vertex.y > point.y) != (previous.y > point.y) {
+                        let x = vertex.x
+                            + (point.y - vertex.y) * (previous.x - vertex.x)
+                                / (previous.y - vertex.y);
+                        if point.x < x {
+                            inside = !inside;
+                        }
+                    }
+                    previous = vertex;
+                }
+                Some(inside)
+            }
+            PickingShape::ImageMask {
+                image,
+                alpha_threshold,
+            } => {
+                let image = images.get(image)?;
+                let (alpha_offset, pixel_size) = match image.texture_descriptor.format {
+                    TextureFormat::Rgba8Unorm
+                    | TextureFormat::Rgba8UnormSrgb
+                    | TextureFormat::Bgra8Unorm
+                    | TextureFormat::Bgra8UnormSrgb => (3, 4),
+                    TextureFormat::R8Unorm => (0, 1),
+                    _ => return None,
+                };
+                let size = image.size();
+                let pixel = (point * size.as_vec2())
+                    .as_uvec2()
+                    .min(size.saturating_sub(UVec2::ONE));
+                let index = (pixel.y * size.x + pixel.x) as usize * pixel_size + alpha_offset;
+                let alpha = *image.data.get(index)?;
+                Some(f32::from(alpha) / 255. >= *alpha_threshold)
+            }
+        }
+    }
+}
+
 /// Contains entities whose Interaction should be set to None
 #[derive(Default)]
 pub struct State {
@@ -141,6 +236,8 @@ pub struct NodeQuery {
     calculated_clip: Option<&'static CalculatedClip>,
     view_visibility: Option<&'static ViewVisibility>,
     target_camera: Option<&'static TargetCamera>,
+    border_radius: Option<&'static BorderRadius>,
+    picking_shape: Option<&'static PickingShape>,
 }
 
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
@@ -157,6 +254,7 @@ pub fn ui_focus_system(
     touches_input: Res<Touches>,
     ui_scale: Res<UiScale>,
     ui_stack: Res<UiStack>,
+    images: Res<Assets<Image>>,
     mut node_query: Query<NodeQuery>,
 ) {
     let primary_window = primary_window.iter().next();
@@ -243,25 +341,30 @@ pub fn ui_focus_system(
 
             let node_rect = node.node.logical_rect(node.global_transform);
 
-            // Intersect with the calculated clip rect to find the bounds of the visible region of the node
+            // Intersect with the calculated clip rect to find the bounds of the visible region of
+            // the node. The clip rect is axis-aligned in UI space, so it is moved into the space of
+            // the node like the cursor.
             let visible_rect = node
                 .calculated_clip
-                .map(|clip| node_rect.intersect(clip.clip))
+                .map(|clip| node_rect.intersect(node_clip_rect(clip.clip, node.global_transform)))
                 .unwrap_or(node_rect);
 
+            let cursor_position = camera_cursor_positions.get(&camera_entity).copied();
+            // The cursor position without the rotation and scale of the node, to compare it with
+            // its axis-aligned rect
+            let node_cursor_position = cursor_position
+                .map(|cursor_position| node_point(cursor_position, node.global_transform));
+
             // The mouse position relative to the node
             // (0., 0.) is the top-left corner, (1., 1.) is the bottom-right corner
             // Coordinates are relative to the entire node, not just the visible region.
-            let relative_cursor_position =
-                camera_cursor_positions
-                    .get(&camera_entity)
-                    .and_then(|cursor_position| {
-                        // ensure node size is non-zero in all dimensions, otherwise relative position will be
-                        // +/-inf. if the node is hidden, the visible rect min/max will also be -inf leading to
-                        // false positives for mouse_over (#12395)
-                        (node_rect.size().cmpgt(Vec2::ZERO).all())
-                            .then_some((*cursor_position - node_rect.min) / node_rect.size())
-                    });
+            let relative_cursor_position = node_cursor_position.and_then(|cursor_position| {
+                // ensure node size is non-zero in all dimensions, otherwise relative position will be
+                // +/-inf. if the node is hidden, the visible rect min/max will also be -inf leading to
+                // false positives for mouse_over (#12395)
+                (node_rect.size().cmpgt(Vec2::ZERO).all())
+                    .then_some((cursor_position - node_rect.min) / node_rect.size())
+            });
 
             // If the current cursor position is within the bounds of the node's visible area, consider it for
             // clicking
@@ -270,7 +373,31 @@ pub fn ui_focus_system(
                 normalized: relative_cursor_position,
             };
 
-            let contains_cursor = relative_cursor_position_component.mouse_over();
+            // The cursor must be inside of the clip rect, which is checked in UI space to be exact
+            // for rotated nodes, and inside of the node's rect, excluding the parts that are cut
+            // off by its rounded corners or outside of its picking shape.
+            let contains_cursor = relative_cursor_position.is_some()
+                && node.calculated_clip.map_or(true, |clip| {
+                    cursor_position
+                        .is_some_and(|cursor_position| clip.clip.contains(cursor_position))
+                })
+                && node_cursor_position.is_some_and(|cursor_position| {
+                    let viewport_size = camera_query
+                        .get(camera_entity)
+                        .ok()
+                        .and_then(|(_, camera)| camera.logical_viewport_size())
+                        .unwrap_or(Vec2::ZERO);
+                    pick_node(
+                        cursor_position,
+                        node_rect,
+                        node.border_radius,
+                        node.picking_shape,
+                        &images,
+                        viewport_size / ui_scale.0,
+                        ui_scale.0,
+                        node.node.root_scale(),
+                    )
+                });
 
             // Save the relative cursor position to the correct component
             if let Some(mut node_relative_cursor_position_component) = node.relative_cursor_position
@@ -332,3 +459,215 @@ pub fn ui_focus_system(
         }
     }
 }
+
+/// Maps `point`, in logical UI coordinates, to the coordinates of [`Node::logical_rect`] by undoing
+/// the rotation and scale of the node's `transform` around its center.
+///
+/// The layout places every node as an axis-aligned rect, but a `Transform` can still rotate or
+/// scale it when it is drawn, so picking the mapped point hits the node where it is drawn.
+pub(crate) fn node_point(point: Vec2, transform: &GlobalTransform) -> Vec2 {
+    transform
+        .affine()
+        .inverse()
+        .transform_point3(point.extend(0.))
+        .truncate()
+        + transform.translation().truncate()
+}
+
+/// Returns the bounding rect of the UI space `clip` rect, once moved into the space of the node
+/// with the given `transform` by [`node_point`].
+pub(crate) fn node_clip_rect(clip: Rect, transform: &GlobalTransform) -> Rect {
+    [
+        clip.min,
+        Vec2::new(clip.max.x, clip.min.y),
+        clip.max,
+        Vec2::new(clip.min.x, clip.max.y),
+    ]
+    .into_iter()
+    .map(|corner| node_point(corner, transform))
+    .fold(
+        Rect {
+            min: Vec2::INFINITY,
+            max: Vec2::NEG_INFINITY,
+        },
+        |rect, corner| rect.union_point(corner),
+    )
+}
+
+/// Returns `true` if `point` is inside `node_rect` once its corners are rounded by
+/// `border_radius`.
+///
+/// This uses the same signed distance function as the UI shaders, so points are picked exactly
+/// where the rounded node is drawn. All the values are in logical UI coordinates, and
+/// `viewport_size` is the size of the UI viewport used to resolve viewport-relative radii.
+pub(crate) fn pick_rounded_rect(
+    point: Vec2,
+    node_rect: Rect,
+    border_radius: Option<&BorderRadius>,
+    viewport_size: Vec2,
+    ui_scale: f32,
+    root_scale: f32,
+) -> bool {
+    let Some(border_radius) = border_radius else {
+        return node_rect.contains(point);
+    };
+    let corner_radii = resolve_border_radius(
+        border_radius,
+        node_rect.size(),
+        viewport_size,
+        ui_scale,
+        root_scale,
+    );
+    node_rect.contains_rounded(point, corner_radii)
+}
+
+/// Returns `true` if `point` is inside the [`PickingShape`] of the node covering `node_rect`, or
+/// inside `node_rect` rounded by `border_radius` if it has none, see [`pick_rounded_rect`].
+pub(crate) fn pick_node(
+    point: Vec2,
+    node_rect: Rect,
+    border_radius: Option<&BorderRadius>,
+    picking_shape: Option<&PickingShape>,
+    images: &Assets<Image>,
+    viewport_size: Vec2,
+    ui_scale: f32,
+    root_scale: f32,
+) -> bool {
+    if let Some(picking_shape) = picking_shape {
+        if node_rect.is_empty() {
+            return false;
+        }
+        let relative_point = (point - node_rect.min) / node_rect.size();
+        if let Some(contains) = picking_shape.contains(relative_point, images) {
+            return contains && node_rect.contains(point);
+        }
+    }
+    pick_rounded_rect(
+        point,
+        node_rect,
+        border_radius,
+        viewport_size,
+        ui_scale,
+        root_scale,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{Assets, Handle};
+    use bevy_math::{Quat, Rect, Vec2, Vec3};
+    use bevy_render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    };
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::{node_clip_rect, node_point, PickingShape};
+
+    #[test]
+    fn node_point_undoes_rotation_and_scale() {
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(100., 50., 0.)
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::new(2., 1., 1.)),
+        );
+        // The point 10 pixels to the right of the center of the unrotated node is drawn 20
+        // pixels below the center once the node is scaled and rotated
+        let point = node_point(Vec2::new(100., 70.), &transform);
+        assert!(point.abs_diff_eq(Vec2::new(110., 50.), 1e-4));
+
+        let identity = GlobalTransform::from_xyz(100., 50., 0.);
+        assert!(node_point(Vec2::new(30., 40.), &identity).abs_diff_eq(Vec2::new(30., 40.), 1e-4));
+    }
+
+    #[test]
+    fn clip_rects_are_moved_into_the_space_of_the_node() {
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(100., 50., 0.)
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+        );
+        // A clip rect 20 pixels wide and 10 pixels tall around the center of the node covers 10
+        // pixels of its width and 20 pixels of its height once it is rotated
+        let clip = Rect::new(90., 45., 110., 55.);
+        let rect = node_clip_rect(clip, &transform);
+        assert!(rect.min.abs_diff_eq(Vec2::new(95., 40.), 1e-4));
+        assert!(rect.max.abs_diff_eq(Vec2::new(105., 60.), 1e-4));
+
+        let identity = GlobalTransform::from_xyz(100., 50., 0.);
+        assert_eq!(node_clip_rect(clip, &identity), clip);
+    }
+
+    #[test]
+    fn picking_shapes_contain_points() {
+        let images = Assets::<Image>::default();
+
+        let circle = PickingShape::Circle;
+        assert_eq!(circle.contains(Vec2::new(0.5, 0.5), &images), Some(true));
+        assert_eq!(circle.contains(Vec2::new(0.9, 0.5), &images), Some(true));
+        // The corners of the node are outside of the circle
+        assert_eq!(circle.contains(Vec2::new(0.05, 0.05), &images), Some(false));
+
+        let triangle = PickingShape::Polygon(vec![
+            Vec2::new(0.5, 0.),
+            Vec2::new(1., 1.),
+            Vec2::new(0., 1.),
+        ]);
+        assert_eq!(triangle.contains(Vec2::new(0.5, 0.5), &images), Some(true));
+        assert_eq!(triangle.contains(Vec2::new(0.1, 0.2), &images), Some(false));
+        assert_eq!(triangle.contains(Vec2::new(0.9, 0.2), &images), Some(false));
+
+        // The mask isn't loaded
+        let mask = PickingShape::image_mask(Handle::default());
+        assert_eq!(mask.contains(Vec2::new(0.5, 0.5), &images), None);
+    }
+
+    #[test]
+    fn image_masks_contain_the_pixels_above_their_alpha_threshold() {
+        let mut images = Assets::<Image>::default();
+        let mut add_image = |data: Vec<u8>, format| {
+            images.add(Image::new(
+                Extent3d {
+                    width: 2,
+                    height: 2,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                data,
+                format,
+                RenderAssetUsages::MAIN_WORLD,
+            ))
+        };
+        // The alphas of the pixels are 0 and 255 on the top row, and 100 and 200 on the bottom one
+        let rgba = add_image(
+            vec![
+                255, 0, 0, 0, 255, 0, 0, 255, //
+                255, 0, 0, 100, 255, 0, 0, 200,
+            ],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let r8 = add_image(vec![0, 255, 100, 200], TextureFormat::R8Unorm);
+        let unsupported = add_image(vec![0; 2 * 2 * 8], TextureFormat::Rgba16Float);
+
+        for image in [rgba.clone(), r8] {
+            let mask = PickingShape::ImageMask {
+                image,
+                alpha_threshold: 0.5,
+            };
+            assert_eq!(mask.contains(Vec2::new(0.25, 0.25), &images), Some(false));
+            assert_eq!(mask.contains(Vec2::new(0.75, 0.25), &images), Some(true));
+            assert_eq!(mask.contains(Vec2::new(0.25, 0.75), &images), Some(false));
+            assert_eq!(mask.contains(Vec2::new(0.75, 0.75), &images), Some(true));
+            // Points on the far edges of the node pick the last pixels
+            assert_eq!(mask.contains(Vec2::new(1., 1.), &images), Some(true));
+        }
+
+        // Only fully transparent pixels are missed by default
+        let mask = PickingShape::image_mask(rgba);
+        assert_eq!(mask.contains(Vec2::new(0.25, 0.25), &images), Some(false));
+        assert_eq!(mask.contains(Vec2::new(0.25, 0.75), &images), Some(true));
+
+        let mask = PickingShape::image_mask(unsupported);
+        assert_eq!(mask.contains(Vec2::new(0.5, 0.5), &images), None);
+    }
+}
@@ -1,8 +1,12 @@
 use thiserror::Error;
 
-use crate::{ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiScale};
+use crate::{
+    ContentSize, DefaultUiCamera, Direction, Node, Outline, ScrollPosition, Style, TargetCamera,
+    UiRootScale, UiScale, UiScaleMode, Val,
+};
+use bevy_asset::{AssetEvent, AssetId};
 use bevy_ecs::{
-    change_detection::{DetectChanges, DetectChangesMut},
+    change_detection::{DetectChanges, DetectChangesMut, Mut},
     entity::Entity,
     event::EventReader,
     query::{With, Without},
@@ -11,8 +15,11 @@ use bevy_ecs::{
     world::Ref,
 };
 use bevy_hierarchy::{Children, Parent};
-use bevy_math::{UVec2, Vec2};
-use bevy_render::camera::{Camera, NormalizedRenderTarget};
+use bevy_math::{BVec2, UVec2, Vec2};
+use bevy_render::{
+    camera::{Camera, NormalizedRenderTarget},
+    texture::Image,
+};
 use bevy_transform::components::Transform;
 use bevy_utils::tracing::warn;
 use bevy_utils::{HashMap, HashSet};
@@ -77,10 +84,15 @@ pub fn ui_layout_system(
     cameras: Query<(Entity, &Camera)>,
     default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
+    ui_scale_mode: Res<UiScaleMode>,
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut resize_events: EventReader<bevy_window::WindowResized>,
+    mut image_events: EventReader<AssetEvent<Image>>,
     mut ui_surface: ResMut<UiSurface>,
-    root_node_query: Query<(Entity, Option<&TargetCamera>), (With<Node>, Without<Parent>)>,
+    root_node_query: Query<
+        (Entity, Option<&TargetCamera>, Option<&UiRootScale>),
+        (With<Node>, Without<Parent>),
+    >,
     mut style_query: Query<
         (
             Entity,
@@ -93,7 +105,8 @@ pub fn ui_layout_system(
     children_query: Query<(Entity, Ref<Children>), With<Node>>,
     just_children_query: Query<&Children>,
     mut removed_components: UiLayoutSystemRemovedComponentParam,
-    mut node_transform_query: Query<(&mut Node, &mut Transform)>,
+    mut node_transform_query: Query<(&mut Node, &mut Transform, Option<&Style>)>,
+    mut scroll_position_query: Query<(&mut ScrollPosition, &Style)>,
 ) {
     struct CameraLayoutInfo {
         size: UVec2,
@@ -109,15 +122,27 @@ pub fn ui_layout_system(
     };
 
     let resized_windows: HashSet<Entity> = resize_events.read().map(|event| event.window).collect();
+    // Cameras rendering to an image lay the UI out again when it is modified, as it may be resized
+    let resized_images: HashSet<AssetId<Image>> = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
     let calculate_camera_layout_info = |camera: &Camera| {
         let size = camera.physical_viewport_size().unwrap_or(UVec2::ZERO);
         let scale_factor = camera.target_scaling_factor().unwrap_or(1.0);
         let camera_target = camera
             .target
             .normalize(primary_window.get_single().map(|(e, _)| e).ok());
-        let resized = matches!(camera_target,
-          Some(NormalizedRenderTarget::Window(window_ref)) if resized_windows.contains(&window_ref.entity())
-        );
+        let resized = match camera_target {
+            Some(NormalizedRenderTarget::Window(window_ref)) => {
+                resized_windows.contains(&window_ref.entity())
+            }
+            Some(NormalizedRenderTarget::Image(image)) => resized_images.contains(&image.id()),
+            _ => false,
+        };
         CameraLayoutInfo {
             size,
             resized,
@@ -128,7 +153,13 @@ pub fn ui_layout_system(
 
     // Precalculate the layout info for each camera, so we have fast access to it for each node
     let mut camera_layout_info: HashMap<Entity, CameraLayoutInfo> = HashMap::new();
-    for (entity, target_camera) in &root_node_query {
+    // The `UiRootScale` of every node belonging to a scaled root. Nodes missing from this map use a scale of 1.
+    let mut root_scales: HashMap<Entity, f32> = HashMap::new();
+    let primary_window_scale_factor = primary_window
+        .get_single()
+        .map(|(_, window)| window.resolution.scale_factor())
+        .unwrap_or(1.);
+    for (entity, target_camera, root_scale) in &root_node_query {
         match camera_with_default(target_camera) {
             Some(camera_entity) => {
                 let Ok((_, camera)) = cameras.get(camera_entity) else {
@@ -142,6 +173,16 @@ pub fn ui_layout_system(
                     .entry(camera_entity)
                     .or_insert_with(|| calculate_camera_layout_info(camera));
                 layout_info.root_nodes.push(entity);
+                // `UiScale` may only account for the scale factor of the primary window, so the
+                // roots of other targets are scaled according to the `UiScaleMode`
+                let root_scale = root_scale.map_or(1., |&UiRootScale(root_scale)| root_scale)
+                    * ui_scale_mode.root_scale(
+                        primary_window_scale_factor,
+                        camera.target_scaling_factor().unwrap_or(1.),
+                    );
+                if root_scale != 1. {
+                    collect_root_scales(entity, root_scale, &just_children_query, &mut root_scales);
+                }
             }
             None => {
                 if cameras.is_empty() {
@@ -168,9 +209,16 @@ pub fn ui_layout_system(
         if let Some(camera) =
             camera_with_default(target_camera).and_then(|c| camera_layout_info.get(&c))
         {
+            // Nodes whose root scale changed, including nodes moved to another root, are synced
+            // again with their new scale
+            let root_scale = root_scales.get(&entity).copied().unwrap_or(1.);
+            let root_scale_changed = node_transform_query
+                .get(entity)
+                .is_ok_and(|(node, ..)| node.root_scale != root_scale);
             if camera.resized
                 || !scale_factor_events.is_empty()
                 || ui_scale.is_changed()
+                || root_scale_changed
                 || style.is_changed()
                 || content_size
                     .as_ref()
@@ -178,7 +226,7 @@ pub fn ui_layout_system(
                     .unwrap_or(false)
             {
                 let layout_context = LayoutContext::new(
-                    camera.scale_factor,
+                    camera.scale_factor * root_scale,
                     [camera.size.x as f32, camera.size.y as f32].into(),
                 );
                 let measure = content_size.and_then(|mut c| c.measure.take());
@@ -226,31 +274,53 @@ pub fn ui_layout_system(
                 *root,
                 &ui_surface,
                 &mut node_transform_query,
+                &mut scroll_position_query,
                 &just_children_query,
                 inverse_target_scale_factor,
+                root_scales.get(root).copied().unwrap_or(1.),
+                Direction::LeftToRight,
+                Vec2::ZERO,
+                Vec2::ZERO,
                 Vec2::ZERO,
                 Vec2::ZERO,
             );
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn update_uinode_geometry_recursive(
         entity: Entity,
         ui_surface: &UiSurface,
-        node_transform_query: &mut Query<(&mut Node, &mut Transform)>,
+        node_transform_query: &mut Query<(&mut Node, &mut Transform, Option<&Style>)>,
+        scroll_position_query: &mut Query<(&mut ScrollPosition, &Style)>,
         children_query: &Query<&Children>,
         inverse_target_scale_factor: f32,
+        root_scale: f32,
+        parent_direction: Direction,
+        parent_layout_size: Vec2,
         parent_size: Vec2,
+        parent_scroll_offset: Vec2,
         mut absolute_location: Vec2,
     ) {
-        if let Ok((mut node, mut transform)) = node_transform_query.get_mut(entity) {
+        if let Ok((mut node, mut transform, style)) = node_transform_query.get_mut(entity) {
             let Ok(layout) = ui_surface.get_layout(entity) else {
                 return;
             };
+            let direction = match style.map_or(Direction::Inherit, |style| style.direction) {
+                Direction::Inherit => parent_direction,
+                direction => direction,
+            };
             let layout_size =
                 inverse_target_scale_factor * Vec2::new(layout.size.width, layout.size.height);
-            let layout_location =
+            let mut layout_location =
                 inverse_target_scale_factor * Vec2::new(layout.location.x, layout.location.y);
+            // Taffy lays out children from the left edge, so the children of a right to left node
+            // are mirrored to start from its right edge
+            if parent_direction == Direction::RightToLeft {
+                layout_location.x = parent_layout_size.x - layout_location.x - layout_size.x;
+            }
+            // The children of a scrolled node are moved by its scroll offset
+            layout_location -= parent_scroll_offset;
 
             absolute_location += layout_location;
 
@@ -265,18 +335,46 @@ pub fn ui_layout_system(
                 node.calculated_size = rounded_size;
                 node.unrounded_size = layout_size;
             }
+            if node.root_scale != root_scale {
+                node.root_scale = root_scale;
+            }
+            if node.direction != direction {
+                node.direction = direction;
+            }
             if transform.translation.truncate() != rounded_location {
                 transform.translation = rounded_location.extend(0.);
             }
             if let Ok(children) = children_query.get(entity) {
+                let mut scroll_offset = match scroll_position_query.get_mut(entity) {
+                    Ok((scroll_position, style)) => clamp_scroll_position(
+                        scroll_position,
+                        style,
+                        ui_surface,
+                        entity,
+                        children,
+                        inverse_target_scale_factor,
+                        parent_layout_size.x,
+                        layout_size,
+                    ),
+                    _ => Vec2::ZERO,
+                };
+                // The content of a right to left node overflows it on the left
+                if direction == Direction::RightToLeft {
+                    scroll_offset.x = -scroll_offset.x;
+                }
                 for &child_uinode in children {
                     update_uinode_geometry_recursive(
                         child_uinode,
                         ui_surface,
                         node_transform_query,
+                        scroll_position_query,
                         children_query,
                         inverse_target_scale_factor,
+                        root_scale,
+                        direction,
+                        layout_size,
                         rounded_size,
+                        scroll_offset,
                         absolute_location,
                     );
                 }
@@ -285,31 +383,116 @@ pub fn ui_layout_system(
     }
 }
 
+/// Keeps the [`ScrollPosition`] of a node within the distance by which its `children` overflow
+/// it, and returns the offset of its children along the scrolling axes of its `style`.
+///
+/// The content of the node ends with the padding and border of its right and bottom edges, so
+/// that the last child can be scrolled fully into view.
+#[allow(clippy::too_many_arguments)]
+fn clamp_scroll_position(
+    mut scroll_position: Mut<ScrollPosition>,
+    style: &Style,
+    ui_surface: &UiSurface,
+    entity: Entity,
+    children: &Children,
+    inverse_target_scale_factor: f32,
+    parent_width: f32,
+    layout_size: Vec2,
+) -> Vec2 {
+    if !style.overflow.is_scroll() {
+        return Vec2::ZERO;
+    }
+    let content_end = children
+        .iter()
+        .filter_map(|child| ui_surface.get_layout(*child).ok())
+        .fold(Vec2::ZERO, |content_end, layout| {
+            content_end.max(Vec2::new(
+                layout.location.x + layout.size.width,
+                layout.location.y + layout.size.height,
+            ))
+        });
+    let content_size = inverse_target_scale_factor
+        * (content_end
+            + end_padding_and_border(
+                ui_surface,
+                entity,
+                parent_width / inverse_target_scale_factor,
+            ));
+    let max_offset = (content_size - layout_size).max(Vec2::ZERO);
+    let scroll_axes = BVec2::new(style.overflow.x.is_scroll(), style.overflow.y.is_scroll());
+    let offset = Vec2::select(
+        scroll_axes,
+        scroll_position.offset.clamp(Vec2::ZERO, max_offset),
+        scroll_position.offset,
+    );
+    scroll_position.set_if_neq(ScrollPosition::new(offset));
+
+    round_layout_coords(Vec2::select(scroll_axes, offset, Vec2::ZERO))
+}
+
+/// Returns the sum of the padding and border of the right and bottom edges of `entity`, in
+/// physical pixels, with percentages resolved against the `parent_width` like in the layout.
+fn end_padding_and_border(ui_surface: &UiSurface, entity: Entity, parent_width: f32) -> Vec2 {
+    let Some(style) = ui_surface
+        .entity_to_taffy
+        .get(&entity)
+        .and_then(|&node| ui_surface.taffy.style(node).ok())
+    else {
+        return Vec2::ZERO;
+    };
+    let resolve = |value: taffy::style::LengthPercentage| match value {
+        taffy::style::LengthPercentage::Length(length) => length,
+        taffy::style::LengthPercentage::Percent(percent) => percent * parent_width,
+    };
+    Vec2::new(
+        resolve(style.padding.right) + resolve(style.border.right),
+        resolve(style.padding.bottom) + resolve(style.border.bottom),
+    )
+}
+
+/// Assigns `root_scale` to `entity` and all of its descendants.
+fn collect_root_scales(
+    entity: Entity,
+    root_scale: f32,
+    children_query: &Query<&Children>,
+    root_scales: &mut HashMap<Entity, f32>,
+) {
+    root_scales.insert(entity, root_scale);
+    if let Ok(children) = children_query.get(entity) {
+        for &child in children {
+            collect_root_scales(child, root_scale, children_query, root_scales);
+        }
+    }
+}
+
 /// Resolve and update the widths of Node outlines
 pub fn resolve_outlines_system(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
-    mut outlines_query: Query<(&Outline, &mut Node)>,
+    mut outlines_query: Query<(&Outline, &mut Node, Option<&TargetCamera>)>,
 ) {
-    let viewport_size = primary_window
-        .get_single()
-        .map(|window| window.size())
-        .unwrap_or(Vec2::ZERO)
-        / ui_scale.0;
+    for (outline, mut node, target_camera) in outlines_query.iter_mut() {
+        let viewport_size = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|camera| camera_query.get(camera).ok())
+            .and_then(Camera::logical_viewport_size)
+            .unwrap_or(Vec2::ZERO)
+            / ui_scale.0;
 
-    for (outline, mut node) in outlines_query.iter_mut() {
         let node = node.bypass_change_detection();
-        node.outline_width = outline
-            .width
-            .resolve(node.size().x, viewport_size)
-            .unwrap_or(0.)
-            .max(0.);
-
-        node.outline_offset = outline
-            .offset
-            .resolve(node.size().x, viewport_size)
-            .unwrap_or(0.)
-            .max(0.);
+        // Like borders, fixed outlines are scaled by the `UiRootScale` of their root
+        let resolve = |value: Val| match value {
+            Val::Px(px) => (px * node.root_scale).max(0.),
+            value => value
+                .resolve(node.size().x, viewport_size)
+                .unwrap_or(0.)
+                .max(0.),
+        };
+        let (width, offset) = (resolve(outline.width), resolve(outline.offset));
+        node.outline_width = width;
+        node.outline_offset = offset;
     }
 }
 
@@ -361,15 +544,19 @@ mod tests {
     use bevy_math::{vec2, Rect, UVec2, Vec2};
     use bevy_render::camera::ManualTextureViews;
     use bevy_render::camera::OrthographicProjection;
+    use bevy_render::camera::RenderTarget;
     use bevy_render::prelude::Camera;
+    use bevy_render::render_asset::RenderAssetUsages;
+    use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
     use bevy_render::texture::Image;
-    use bevy_transform::prelude::GlobalTransform;
+    use bevy_transform::prelude::{GlobalTransform, Transform};
     use bevy_transform::systems::{propagate_transforms, sync_simple_transforms};
     use bevy_utils::prelude::default;
     use bevy_utils::HashMap;
     use bevy_window::PrimaryWindow;
     use bevy_window::Window;
     use bevy_window::WindowCreated;
+    use bevy_window::WindowRef;
     use bevy_window::WindowResized;
     use bevy_window::WindowResolution;
     use bevy_window::WindowScaleFactorChanged;
@@ -378,7 +565,7 @@ mod tests {
     use crate::layout::ui_surface::UiSurface;
     use crate::prelude::*;
     use crate::ui_layout_system;
-    use crate::update::update_target_camera_system;
+    use crate::update::{update_target_camera_system, update_ui_scale_system};
     use crate::ContentSize;
 
     #[test]
@@ -393,6 +580,7 @@ mod tests {
     fn setup_ui_test_world() -> (World, Schedule) {
         let mut world = World::new();
         world.init_resource::<UiScale>();
+        world.init_resource::<UiScaleMode>();
         world.init_resource::<UiSurface>();
         world.init_resource::<Events<WindowScaleFactorChanged>>();
         world.init_resource::<Events<WindowResized>>();
@@ -924,6 +1112,316 @@ mod tests {
         assert_eq!(layout.size.height, content_size.y);
     }
 
+    #[test]
+    fn scroll_position_should_offset_children_within_their_overflow() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let child = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(200.),
+                    flex_shrink: 0.,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+        let parent = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(50.),
+                    overflow: Overflow::scroll_y(),
+                    ..Default::default()
+                },
+                scroll_position: ScrollPosition::new(Vec2::new(20., 30.)),
+                ..Default::default()
+            })
+            .add_child(child)
+            .id();
+
+        ui_schedule.run(&mut world);
+
+        // Only the scrolling axis is offset
+        let parent_position = world.get::<GlobalTransform>(parent).unwrap().translation();
+        let child_position = world.get::<GlobalTransform>(child).unwrap().translation();
+        assert_eq!(
+            child_position.truncate() - parent_position.truncate(),
+            Vec2::new(0., 75. - 30.)
+        );
+
+        // The offset is clamped to the overflowing distance of the child
+        world.get_mut::<ScrollPosition>(parent).unwrap().offset = Vec2::new(20., 500.);
+        ui_schedule.run(&mut world);
+        assert_eq!(
+            world.get::<ScrollPosition>(parent).unwrap().offset,
+            Vec2::new(20., 150.)
+        );
+        let child_position = world.get::<GlobalTransform>(child).unwrap().translation();
+        assert_eq!(
+            child_position.truncate() - parent_position.truncate(),
+            Vec2::new(0., 75. - 150.)
+        );
+    }
+
+    #[test]
+    fn scroll_position_should_reach_the_end_padding() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let child = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(80.),
+                    height: Val::Px(200.),
+                    flex_shrink: 0.,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+        let parent = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(50.),
+                    padding: UiRect::all(Val::Px(10.)),
+                    border: UiRect::bottom(Val::Px(5.)),
+                    overflow: Overflow::scroll_y(),
+                    ..Default::default()
+                },
+                scroll_position: ScrollPosition::new(Vec2::new(0., 500.)),
+                ..Default::default()
+            })
+            .add_child(child)
+            .id();
+
+        ui_schedule.run(&mut world);
+
+        // The child ends 210 pixels from the top of the parent, followed by 15 pixels of padding
+        // and border
+        assert_eq!(
+            world.get::<ScrollPosition>(parent).unwrap().offset,
+            Vec2::new(0., 225. - 50.)
+        );
+    }
+
+    #[test]
+    fn ui_root_scale_should_only_scale_its_own_tree() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let style = Style {
+            width: Val::Px(10.),
+            height: Val::Px(20.),
+            ..Default::default()
+        };
+        let spawn_tree = |world: &mut World, root_scale: Option<UiRootScale>| {
+            let child = world
+                .spawn(NodeBundle {
+                    style: style.clone(),
+                    ..Default::default()
+                })
+                .id();
+            let mut root = world.spawn(NodeBundle::default());
+            if let Some(root_scale) = root_scale {
+                root.insert(root_scale);
+            }
+            root.add_child(child);
+            child
+        };
+        let unscaled = spawn_tree(&mut world, None);
+        let scaled = spawn_tree(&mut world, Some(UiRootScale(2.)));
+
+        ui_schedule.run(&mut world);
+
+        let node = world.get::<Node>(unscaled).unwrap();
+        assert_eq!(node.size(), Vec2::new(10., 20.));
+        assert_eq!(node.root_scale(), 1.);
+        let node = world.get::<Node>(scaled).unwrap();
+        assert_eq!(node.size(), Vec2::new(20., 40.));
+        assert_eq!(node.root_scale(), 2.);
+
+        let scaled_root = world.get::<Parent>(scaled).unwrap().get();
+        world.entity_mut(scaled_root).remove::<UiRootScale>();
+        ui_schedule.run(&mut world);
+
+        let node = world.get::<Node>(scaled).unwrap();
+        assert_eq!(node.size(), Vec2::new(10., 20.));
+        assert_eq!(node.root_scale(), 1.);
+    }
+
+    #[test]
+    fn physical_ui_scale_mode_should_scale_each_root_for_its_target() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+        *world.resource_mut::<UiScaleMode>() = UiScaleMode::Physical { user_factor: 1. };
+
+        let window = world
+            .spawn(Window {
+                resolution: WindowResolution::new(WINDOW_WIDTH, WINDOW_HEIGHT)
+                    .with_scale_factor_override(2.),
+                ..default()
+            })
+            .id();
+        let camera = world
+            .spawn(Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Window(WindowRef::Entity(window)),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+
+        let style = Style {
+            width: Val::Px(10.),
+            height: Val::Px(20.),
+            ..Default::default()
+        };
+        let primary_node = world
+            .spawn(NodeBundle {
+                style: style.clone(),
+                ..Default::default()
+            })
+            .id();
+        let secondary_node = world
+            .spawn((
+                NodeBundle {
+                    style,
+                    ..Default::default()
+                },
+                TargetCamera(camera),
+            ))
+            .id();
+
+        ui_schedule.run(&mut world);
+
+        // Both nodes are 10 by 20 physical pixels
+        let node = world.get::<Node>(primary_node).unwrap();
+        assert_eq!(node.size(), Vec2::new(10., 20.));
+        assert_eq!(node.root_scale(), 1.);
+        let node = world.get::<Node>(secondary_node).unwrap();
+        assert_eq!(node.size(), Vec2::new(5., 10.));
+        assert_eq!(node.root_scale(), 0.5);
+    }
+
+    #[test]
+    fn auto_ui_scale_mode_should_follow_the_window_scale_factor() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+        *world.resource_mut::<UiScaleMode>() = UiScaleMode::Auto { user_factor: 1.5 };
+        ui_schedule.add_systems(update_ui_scale_system.before(ui_layout_system));
+
+        let mut primary_window = world.query_filtered::<&mut Window, With<PrimaryWindow>>();
+        primary_window
+            .single_mut(&mut world)
+            .resolution
+            .set_scale_factor_override(Some(2.));
+        let node = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(10.),
+                    height: Val::Px(20.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+
+        ui_schedule.run(&mut world);
+
+        // The DPI scale factor isn't cancelled out: the user factor is applied on top of it
+        assert_eq!(world.resource::<UiScale>().0, 1.5);
+        let node = world.get::<Node>(node).unwrap();
+        assert_eq!(node.size(), Vec2::new(10., 20.));
+        assert_eq!(node.root_scale(), 1.);
+
+        *world.resource_mut::<UiScaleMode>() = UiScaleMode::Physical { user_factor: 1.5 };
+        ui_schedule.run(&mut world);
+        assert_eq!(world.resource::<UiScale>().0, 0.75);
+    }
+
+    #[test]
+    fn ui_root_scale_should_follow_reparented_nodes() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let child = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(10.),
+                    height: Val::Px(20.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+        let unscaled_root = world.spawn(NodeBundle::default()).add_child(child).id();
+        let scaled_root = world.spawn((NodeBundle::default(), UiRootScale(2.))).id();
+
+        ui_schedule.run(&mut world);
+        assert_eq!(
+            world.get::<Node>(child).unwrap().size(),
+            Vec2::new(10., 20.)
+        );
+
+        world.entity_mut(scaled_root).add_child(child);
+        ui_schedule.run(&mut world);
+        let node = world.get::<Node>(child).unwrap();
+        assert_eq!(node.size(), Vec2::new(20., 40.));
+        assert_eq!(node.root_scale(), 2.);
+
+        world.entity_mut(unscaled_root).add_child(child);
+        ui_schedule.run(&mut world);
+        let node = world.get::<Node>(child).unwrap();
+        assert_eq!(node.size(), Vec2::new(10., 20.));
+        assert_eq!(node.root_scale(), 1.);
+    }
+
+    #[test]
+    fn right_to_left_nodes_should_lay_out_children_from_their_right_edge() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let child_style = Style {
+            width: Val::Px(20.),
+            height: Val::Px(20.),
+            ..Default::default()
+        };
+        let first = world
+            .spawn(NodeBundle {
+                style: child_style.clone(),
+                ..Default::default()
+            })
+            .id();
+        let second = world
+            .spawn(NodeBundle {
+                style: child_style,
+                ..Default::default()
+            })
+            .id();
+        world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(20.),
+                    padding: UiRect::left(Val::Px(10.)),
+                    direction: Direction::RightToLeft,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .push_children(&[first, second]);
+
+        ui_schedule.run(&mut world);
+
+        // The left padding is the start padding of the root, on its right side
+        let first_transform = world.get::<Transform>(first).unwrap();
+        assert_eq!(first_transform.translation.x, 70. + 0.5 * (20. - 100.));
+        let second_transform = world.get::<Transform>(second).unwrap();
+        assert_eq!(second_transform.translation.x, 50. + 0.5 * (20. - 100.));
+        assert_eq!(
+            world.get::<Node>(first).unwrap().direction(),
+            Direction::RightToLeft
+        );
+    }
+
     #[test]
     fn measure_funcs_should_be_removed_on_content_size_removal() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
@@ -1021,6 +1519,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ui_layout_follows_resized_image_target() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let size = |width, height| Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let image = world.resource_mut::<Assets<Image>>().add(Image::new_fill(
+            size(100, 50),
+            TextureDimension::D2,
+            &[0; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        ));
+        let camera = world
+            .spawn(Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(image.clone()),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+
+        // Viewport units are only resolved again when the target is resized
+        let ui_root = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Vw(100.),
+                        height: Val::Vh(100.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                TargetCamera(camera),
+            ))
+            .id();
+
+        ui_schedule.run(&mut world);
+        let layout = world.resource::<UiSurface>().get_layout(ui_root).unwrap();
+        assert_eq!(layout.size.width, 100.);
+        assert_eq!(layout.size.height, 50.);
+
+        world
+            .resource_mut::<Assets<Image>>()
+            .get_mut(&image)
+            .unwrap()
+            .resize(size(200, 80));
+        world.send_event(AssetEvent::Modified { id: image.id() });
+
+        ui_schedule.run(&mut world);
+        let layout = world.resource::<UiSurface>().get_layout(ui_root).unwrap();
+        assert_eq!(layout.size.width, 200.);
+        assert_eq!(layout.size.height, 80.);
+    }
+
     #[test]
     fn no_camera_ui() {
         let mut world = World::new();
@@ -21,29 +21,47 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_reflect::Reflect;
 #[cfg(feature = "bevy_text")]
 mod accessibility;
+mod drag;
 mod focus;
 mod geometry;
 mod layout;
+mod modal;
+mod pointer;
 mod render;
+mod scroll;
 mod stack;
 mod texture_slice;
+mod transition;
 mod ui_node;
+mod world_ui;
 
+#[cfg(feature = "bevy_text")]
+pub use accessibility::{AccessibilityAction, AccessibleLabel, AccessibleRole, AccessibleValue};
+pub use drag::*;
 pub use focus::*;
 pub use geometry::*;
 pub use layout::*;
 pub use measurement::*;
+pub use modal::*;
+pub use pointer::*;
 pub use render::*;
+pub use scroll::*;
+pub use transition::*;
 pub use ui_material::*;
 pub use ui_node::*;
 use widget::UiImageSize;
+pub use world_ui::*;
 
 #[doc(hidden)]
 pub mod prelude {
+    #[cfg(feature = "bevy_text")]
+    #[doc(hidden)]
+    pub use crate::widget::TextInput;
     #[doc(hidden)]
     pub use crate::{
         geometry::*, node_bundles::*, ui_material::*, ui_node::*, widget::Button, widget::Label,
-        Interaction, UiMaterialPlugin, UiScale,
+        widget::ViewportNode, Interaction, TransitionStyle, UiMaterialPlugin, UiScale, UiScaleMode,
+        UiTextureSurface, UiTransition, WorldUi,
     };
     // `bevy_sprite` re-exports for texture slicing
     #[doc(hidden)]
@@ -61,7 +79,7 @@ use bevy_transform::TransformSystem;
 use layout::ui_surface::UiSurface;
 use stack::ui_stack_system;
 pub use stack::UiStack;
-use update::{update_clipping_system, update_target_camera_system};
+use update::{update_clipping_system, update_target_camera_system, update_ui_scale_system};
 
 /// The basic plugin for Bevy UI
 #[derive(Default)]
@@ -93,6 +111,54 @@ impl Default for UiScale {
     }
 }
 
+/// Controls how [`UiScale`] is driven.
+///
+/// The final scale of fixed UI values is always the camera target's scale factor multiplied by
+/// [`UiScale`] (and by the [`UiRootScale`] of the node's root, if any).
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Resource, Default)]
+pub enum UiScaleMode {
+    /// [`UiScale`] is set manually and never overwritten.
+    #[default]
+    Manual,
+    /// [`UiScale`] is kept equal to the given user factor, so that the UI follows the window's
+    /// DPI scale factor combined with a user accessibility preference.
+    Auto {
+        /// The accessibility factor chosen by the user.
+        user_factor: f32,
+    },
+    /// [`UiScale`] cancels out the primary window's DPI scale factor, so that one UI unit maps
+    /// to `user_factor` physical pixels regardless of the display.
+    ///
+    /// The roots rendered to other targets are scaled by [`UiScaleMode::root_scale`] on top of
+    /// [`UiScale`], so that this holds on every target whatever its own scale factor.
+    Physical {
+        /// The accessibility factor chosen by the user.
+        user_factor: f32,
+    },
+}
+
+impl UiScaleMode {
+    /// Returns the [`UiScale`] this mode produces for a window with the given scale factor,
+    /// or `None` for [`UiScaleMode::Manual`].
+    pub fn ui_scale(&self, window_scale_factor: f32) -> Option<f32> {
+        match *self {
+            UiScaleMode::Manual => None,
+            UiScaleMode::Auto { user_factor } => Some(user_factor),
+            UiScaleMode::Physical { user_factor } => Some(user_factor / window_scale_factor),
+        }
+    }
+
+    /// Returns the factor by which the UI roots of a render target with the given scale factor
+    /// are scaled on top of [`UiScale`], given the scale factor of the primary window [`UiScale`]
+    /// is computed from.
+    pub fn root_scale(&self, primary_window_scale_factor: f32, target_scale_factor: f32) -> f32 {
+        match *self {
+            UiScaleMode::Manual | UiScaleMode::Auto { .. } => 1.,
+            UiScaleMode::Physical { .. } => primary_window_scale_factor / target_scale_factor,
+        }
+    }
+}
+
 // Marks systems that can be ambiguous with [`widget::text_system`] if the `bevy_text` feature is enabled.
 // See https://github.com/bevyengine/bevy/pull/11391 for more details.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -109,29 +175,69 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UiSurface>()
             .init_resource::<UiScale>()
+            .init_resource::<UiScaleMode>()
             .init_resource::<UiStack>()
+            .init_resource::<ModalStack>()
+            .init_resource::<HoverMap>()
+            .init_resource::<PointerCapture>()
+            .init_resource::<DragMap>()
+            .add_event::<UiPointerEvent>()
+            .add_event::<DragStart>()
+            .add_event::<DragOver>()
+            .add_event::<DragDrop>()
+            .add_event::<DragEnd>()
             .register_type::<BackgroundColor>()
             .register_type::<CalculatedClip>()
             .register_type::<ContentSize>()
+            .register_type::<DragGroups>()
+            .register_type::<Draggable>()
+            .register_type::<DragPayload>()
+            .register_type::<DropTarget>()
             .register_type::<FocusPolicy>()
             .register_type::<Interaction>()
+            .register_type::<PickingShape>()
+            .register_type::<PointerBubbling>()
             .register_type::<Node>()
             .register_type::<RelativeCursorPosition>()
+            .register_type::<ScrollPosition>()
             .register_type::<Style>()
             .register_type::<TargetCamera>()
             .register_type::<UiImage>()
             .register_type::<UiImageSize>()
             .register_type::<UiRect>()
             .register_type::<UiScale>()
+            .register_type::<UiScaleMode>()
+            .register_type::<UiRootScale>()
             .register_type::<BorderColor>()
+            .register_type::<BorderSideColors>()
+            .register_type::<BorderStroke>()
+            .register_type::<BorderImage>()
             .register_type::<BorderRadius>()
             .register_type::<widget::Button>()
             .register_type::<widget::Label>()
+            .register_type::<widget::ViewportNode>()
+            .register_type::<WorldUi>()
+            .register_type::<UiTextureSurface>()
             .register_type::<ZIndex>()
             .register_type::<Outline>()
+            .register_type::<BoxShadow>()
+            .register_type::<BackgroundGradient>()
+            .register_type::<UiTransition>()
             .add_systems(
                 PreUpdate,
-                ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                (
+                    ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                    update_hover_map
+                        .in_set(UiSystem::Focus)
+                        .after(ui_focus_system),
+                    update_drag_and_drop
+                        .in_set(UiSystem::Focus)
+                        .after(update_hover_map),
+                    update_scroll_position
+                        .in_set(UiSystem::Focus)
+                        .after(update_drag_and_drop),
+                    start_interaction_transitions.after(UiSystem::Focus),
+                ),
             );
 
         app.add_systems(
@@ -139,11 +245,23 @@ impl Plugin for UiPlugin {
             (
                 check_visibility::<WithNode>.in_set(VisibilitySystems::CheckVisibility),
                 update_target_camera_system.before(UiSystem::Layout),
+                update_world_ui_panels
+                    .before(update_target_camera_system)
+                    .before(TransformSystem::TransformPropagate)
+                    .in_set(AmbiguousWithTextSystem)
+                    .in_set(AmbiguousWithUpdateText2DLayout),
+                update_ui_scale_system
+                    .before(UiSystem::Layout)
+                    .before(widget::update_image_content_size_system),
+                (update_modal_layers, block_focus_outside_modal).before(UiSystem::Layout),
+                update_ui_transitions.before(UiSystem::Layout),
                 apply_deferred
                     .after(update_target_camera_system)
                     .before(UiSystem::Layout),
                 ui_layout_system
                     .in_set(UiSystem::Layout)
+                    // The layout is sized to the render targets computed by the cameras
+                    .after(bevy_render::camera::CameraUpdateSystem)
                     .before(TransformSystem::TransformPropagate),
                 resolve_outlines_system
                     .in_set(UiSystem::Outlines)
@@ -167,6 +285,10 @@ impl Plugin for UiPlugin {
                     .before(UiSystem::Layout)
                     .in_set(AmbiguousWithTextSystem)
                     .in_set(AmbiguousWithUpdateText2DLayout),
+                widget::update_viewport_render_target_size
+                    .after(UiSystem::Layout)
+                    .in_set(AmbiguousWithTextSystem)
+                    .in_set(AmbiguousWithUpdateText2DLayout),
                 (
                     texture_slice::compute_slices_on_asset_event,
                     texture_slice::compute_slices_on_image_change,
@@ -197,13 +319,27 @@ fn build_text_interop(app: &mut App) {
     use bevy_text::TextLayoutInfo;
 
     app.register_type::<TextLayoutInfo>()
-        .register_type::<TextFlags>();
+        .register_type::<TextFlags>()
+        .register_type::<widget::TextInput>()
+        .init_resource::<bevy_window::Clipboard>()
+        .add_event::<widget::TextInputCommit>()
+        .add_systems(
+            PreUpdate,
+            (
+                widget::focus_text_input_on_press,
+                widget::text_input_keyboard_system,
+            )
+                .chain()
+                .after(UiSystem::Focus),
+        );
 
     app.add_systems(
         PostUpdate,
         (
             widget::measure_text_system
                 .before(UiSystem::Layout)
+                .after(update_ui_scale_system)
+                .after(update_target_camera_system)
                 // Potential conflict: `Assets<Image>`
                 // In practice, they run independently since `bevy_render::camera_update_system`
                 // will only ever observe its own render target, and `widget::measure_text_system`
@@ -219,8 +355,14 @@ fn build_text_interop(app: &mut App) {
             widget::text_system
                 .after(UiSystem::Layout)
                 .after(bevy_text::remove_dropped_font_atlas_sets)
+                // The glyph effects are applied to the glyphs laid out this frame
+                .before(bevy_text::update_text_glyph_effects)
                 // Text2d and bevy_ui text are entirely on separate entities
                 .ambiguous_with(bevy_text::update_text2d_layout),
+            widget::update_text_input_text.before(widget::measure_text_system),
+            widget::update_text_input_ime
+                .after(widget::text_system)
+                .after(TransformSystem::TransformPropagate),
         ),
     );
 
@@ -0,0 +1,382 @@
+//! This module contains the [`ModalStack`], which manages modal UI layers.
+
+use bevy_a11y::Focus;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::prelude::*;
+
+use crate::{
+    node_bundles::NodeBundle, BackgroundColor, FocusPolicy, PositionType, Style, TargetCamera, Val,
+    ZIndex,
+};
+
+/// The stack of modal UI layers, such as dialogs and pause menus.
+///
+/// Pushing the root node of a modal onto the stack places it above all other UI nodes, behind
+/// a full-screen backdrop that dims the layers below it. Only the topmost modal can be
+/// interacted with:
+/// - The backdrop has [`FocusPolicy::Block`], so the lower layers never receive an
+///   [`Interaction`](crate::Interaction).
+/// - Keyboard [`Focus`] is cleared whenever it is on an entity outside of the topmost modal.
+///
+/// Modals whose root node is despawned are removed from the stack automatically. The
+/// [`ZIndex`] a root node had before being pushed is restored once it is removed from the stack.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::DespawnRecursiveExt;
+/// # use bevy_ui::{prelude::*, ModalStack};
+/// fn open_dialog(mut commands: Commands, mut modals: ResMut<ModalStack>) {
+///     let dialog = commands.spawn(NodeBundle::default()).id();
+///     modals.push(dialog);
+/// }
+///
+/// fn close_dialog(mut commands: Commands, mut modals: ResMut<ModalStack>) {
+///     if let Some(dialog) = modals.pop() {
+///         commands.entity(dialog).despawn_recursive();
+///     }
+/// }
+/// ```
+#[derive(Debug, Resource)]
+pub struct ModalStack {
+    /// The color of the backdrop drawn behind each modal.
+    ///
+    /// Defaults to a translucent black.
+    pub backdrop_color: Color,
+    layers: Vec<ModalLayer>,
+    /// The layers removed from the stack, whose backdrop is despawned and whose root node gets
+    /// its previous [`ZIndex`] back.
+    removed_layers: Vec<ModalLayer>,
+}
+
+#[derive(Debug)]
+struct ModalLayer {
+    root: Entity,
+    backdrop: Option<Entity>,
+    /// The [`ZIndex`] of the root node before it was placed above the lower layers, or `None`
+    /// until it is placed.
+    previous_z_index: Option<Option<ZIndex>>,
+}
+
+impl Default for ModalStack {
+    fn default() -> Self {
+        Self {
+            backdrop_color: Color::srgba(0., 0., 0., 0.5),
+            layers: Vec::new(),
+            removed_layers: Vec::new(),
+        }
+    }
+}
+
+impl ModalStack {
+    /// The global [`ZIndex`] of the backdrop of the bottommost modal.
+    ///
+    /// Each modal layer uses two consecutive global z-indices starting from this value: one for
+    /// its backdrop and one for its root node.
+    pub const BASE_Z_INDEX: i32 = 1 << 24;
+
+    /// Pushes `root` on top of the stack, making it the topmost modal.
+    ///
+    /// If `root` is already on the stack, it is moved to the top. The [`ZIndex`] of `root` is
+    /// overwritten with a global z-index while it is on the stack, and restored once it is
+    /// removed.
+    pub fn push(&mut self, root: Entity) {
+        // Roots removed since the last update keep their backdrop and previous z-index
+        let position = |layers: &[ModalLayer]| layers.iter().position(|layer| layer.root == root);
+        let layer = if let Some(index) = position(&self.layers) {
+            self.layers.remove(index)
+        } else if let Some(index) = position(&self.removed_layers) {
+            self.removed_layers.remove(index)
+        } else {
+            ModalLayer {
+                root,
+                backdrop: None,
+                previous_z_index: None,
+            }
+        };
+        self.layers.push(layer);
+    }
+
+    /// Removes the topmost modal from the stack and returns its root node.
+    ///
+    /// The root node itself isn't despawned, and gets its previous [`ZIndex`] back.
+    pub fn pop(&mut self) -> Option<Entity> {
+        let layer = self.layers.pop()?;
+        let root = layer.root;
+        self.removed_layers.push(layer);
+        Some(root)
+    }
+
+    /// Removes `root` from the stack, wherever it is. Returns `true` if it was on the stack.
+    ///
+    /// The root node itself isn't despawned, and gets its previous [`ZIndex`] back.
+    pub fn remove(&mut self, root: Entity) -> bool {
+        let Some(index) = self.layers.iter().position(|layer| layer.root == root) else {
+            return false;
+        };
+        let layer = self.layers.remove(index);
+        self.removed_layers.push(layer);
+        true
+    }
+
+    /// Removes all modals from the stack.
+    ///
+    /// The root nodes aren't despawned, and get their previous [`ZIndex`] back.
+    pub fn clear(&mut self) {
+        self.removed_layers.append(&mut self.layers);
+    }
+
+    /// Returns the root node of the topmost modal, if any.
+    pub fn top(&self) -> Option<Entity> {
+        self.layers.last().map(|layer| layer.root)
+    }
+
+    /// Returns `true` if `root` is on the stack.
+    pub fn contains(&self, root: Entity) -> bool {
+        self.layers.iter().any(|layer| layer.root == root)
+    }
+
+    /// Returns the number of modals on the stack.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if there are no modals on the stack.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Iterates over the root nodes of the modals, from the bottommost to the topmost.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entity> + '_ {
+        self.layers.iter().map(|layer| layer.root)
+    }
+}
+
+/// Marks the full-screen backdrop node spawned behind each modal of the [`ModalStack`].
+#[derive(Component, Debug)]
+pub struct ModalBackdrop;
+
+/// Spawns, updates and despawns the backdrops of the [`ModalStack`], and places each modal
+/// above the layers below it.
+pub(crate) fn update_modal_layers(
+    mut commands: Commands,
+    mut modal_stack: ResMut<ModalStack>,
+    roots: Query<(Option<&ZIndex>, Option<&TargetCamera>), Without<ModalBackdrop>>,
+    mut backdrops: Query<
+        (
+            Entity,
+            &mut ZIndex,
+            &mut BackgroundColor,
+            Option<&TargetCamera>,
+        ),
+        With<ModalBackdrop>,
+    >,
+) {
+    let modal_stack = &mut *modal_stack;
+
+    // Forget about the modals whose root node has been despawned.
+    let (layers, removed_layers) = (&mut modal_stack.layers, &mut modal_stack.removed_layers);
+    let mut index = 0;
+    while index < layers.len() {
+        if roots.contains(layers[index].root) {
+            index += 1;
+        } else {
+            removed_layers.push(layers.remove(index));
+        }
+    }
+
+    for layer in modal_stack.removed_layers.drain(..) {
+        if let Some(entity_commands) = layer
+            .backdrop
+            .and_then(|backdrop| commands.get_entity(backdrop))
+        {
+            entity_commands.despawn_recursive();
+        }
+        if let (Some(previous_z_index), Some(mut root)) =
+            (layer.previous_z_index, commands.get_entity(layer.root))
+        {
+            match previous_z_index {
+                Some(z_index) => root.insert(z_index),
+                None => root.remove::<ZIndex>(),
+            };
+        }
+    }
+
+    for (index, layer) in modal_stack.layers.iter_mut().enumerate() {
+        let backdrop_z_index = ZIndex::Global(ModalStack::BASE_Z_INDEX + 2 * index as i32);
+        let root_z_index = ZIndex::Global(ModalStack::BASE_Z_INDEX + 2 * index as i32 + 1);
+        let Ok((z_index, target_camera)) = roots.get(layer.root) else {
+            continue;
+        };
+
+        if layer.previous_z_index.is_none() {
+            layer.previous_z_index = Some(z_index.copied());
+        }
+        if z_index != Some(&root_z_index) {
+            commands.entity(layer.root).insert(root_z_index);
+        }
+
+        if let Some(Ok((backdrop, mut z_index, mut background_color, backdrop_target_camera))) =
+            layer.backdrop.map(|backdrop| backdrops.get_mut(backdrop))
+        {
+            z_index.set_if_neq(backdrop_z_index);
+            background_color.set_if_neq(modal_stack.backdrop_color.into());
+            if backdrop_target_camera != target_camera {
+                let mut backdrop = commands.entity(backdrop);
+                match target_camera {
+                    Some(target_camera) => backdrop.insert(target_camera.clone()),
+                    None => backdrop.remove::<TargetCamera>(),
+                };
+            }
+            continue;
+        }
+
+        let mut backdrop = commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..Default::default()
+                },
+                background_color: modal_stack.backdrop_color.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: backdrop_z_index,
+                ..Default::default()
+            },
+            ModalBackdrop,
+        ));
+        if let Some(target_camera) = target_camera {
+            backdrop.insert(target_camera.clone());
+        }
+        layer.backdrop = Some(backdrop.id());
+    }
+}
+
+/// Clears the keyboard [`Focus`] when it is on an entity outside of the topmost modal of the
+/// [`ModalStack`].
+pub(crate) fn block_focus_outside_modal(
+    modal_stack: Res<ModalStack>,
+    focus: Option<ResMut<Focus>>,
+    parents: Query<&Parent>,
+) {
+    let (Some(top), Some(mut focus)) = (modal_stack.top(), focus) else {
+        return;
+    };
+    let Some(focused) = focus.0 else {
+        return;
+    };
+
+    let inside_modal = focused == top
+        || parents
+            .iter_ancestors(focused)
+            .any(|ancestor| ancestor == top);
+    if !inside_modal {
+        focus.0 = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_a11y::Focus;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_hierarchy::BuildWorldChildren;
+
+    use crate::{Node, ZIndex};
+
+    use super::{block_focus_outside_modal, update_modal_layers, ModalBackdrop, ModalStack};
+
+    fn run(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_modal_layers, block_focus_outside_modal));
+        schedule.run(world);
+    }
+
+    fn backdrops(world: &mut World) -> Vec<ZIndex> {
+        let mut query = world.query_filtered::<&ZIndex, bevy_ecs::query::With<ModalBackdrop>>();
+        let mut z_indices: Vec<_> = query.iter(world).copied().collect();
+        z_indices.sort_by_key(|z_index| match z_index {
+            ZIndex::Local(z) | ZIndex::Global(z) => *z,
+        });
+        z_indices
+    }
+
+    #[test]
+    fn modals_are_stacked_above_backdrops() {
+        let mut world = World::default();
+        world.init_resource::<ModalStack>();
+
+        let first = world.spawn(Node::default()).id();
+        let second = world.spawn(Node::default()).id();
+        world.resource_mut::<ModalStack>().push(first);
+        world.resource_mut::<ModalStack>().push(second);
+        run(&mut world);
+
+        let base = ModalStack::BASE_Z_INDEX;
+        assert_eq!(
+            backdrops(&mut world),
+            vec![ZIndex::Global(base), ZIndex::Global(base + 2)]
+        );
+        assert_eq!(world.get::<ZIndex>(first), Some(&ZIndex::Global(base + 1)));
+        assert_eq!(world.get::<ZIndex>(second), Some(&ZIndex::Global(base + 3)));
+
+        assert_eq!(world.resource_mut::<ModalStack>().pop(), Some(second));
+        run(&mut world);
+        assert_eq!(backdrops(&mut world), vec![ZIndex::Global(base)]);
+
+        // Despawning the root node of a modal removes it from the stack.
+        world.despawn(first);
+        run(&mut world);
+        assert!(world.resource::<ModalStack>().is_empty());
+        assert!(backdrops(&mut world).is_empty());
+    }
+
+    #[test]
+    fn removed_modals_get_their_z_index_back() {
+        let mut world = World::default();
+        world.init_resource::<ModalStack>();
+
+        let first = world.spawn((Node::default(), ZIndex::Local(3))).id();
+        let second = world.spawn((Node::default(), ZIndex::Global(-1))).id();
+        let third = world.spawn(Node::default()).id();
+        for root in [first, second, third] {
+            world.resource_mut::<ModalStack>().push(root);
+        }
+        run(&mut world);
+
+        assert_eq!(world.resource_mut::<ModalStack>().pop(), Some(third));
+        assert!(world.resource_mut::<ModalStack>().remove(first));
+        run(&mut world);
+        assert_eq!(world.get::<ZIndex>(first), Some(&ZIndex::Local(3)));
+        assert_eq!(world.get::<ZIndex>(third), None);
+        assert_eq!(
+            world.get::<ZIndex>(second),
+            Some(&ZIndex::Global(ModalStack::BASE_Z_INDEX + 1))
+        );
+
+        world.resource_mut::<ModalStack>().clear();
+        run(&mut world);
+        assert_eq!(world.get::<ZIndex>(second), Some(&ZIndex::Global(-1)));
+        assert!(backdrops(&mut world).is_empty());
+    }
+
+    #[test]
+    fn focus_is_restricted_to_topmost_modal() {
+        let mut world = World::default();
+        world.init_resource::<ModalStack>();
+        world.init_resource::<Focus>();
+
+        let background = world.spawn(Node::default()).id();
+        let modal = world.spawn(Node::default()).id();
+        let button = world.spawn(Node::default()).set_parent(modal).id();
+        world.resource_mut::<ModalStack>().push(modal);
+
+        world.resource_mut::<Focus>().0 = Some(button);
+        run(&mut world);
+        assert_eq!(world.resource::<Focus>().0, Some(button));
+
+        world.resource_mut::<Focus>().0 = Some(background);
+        run(&mut world);
+        assert_eq!(world.resource::<Focus>().0, None);
+    }
+}
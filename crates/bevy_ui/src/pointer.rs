@@ -0,0 +1,812 @@
+//! This module contains the [`HoverMap`], which tracks the UI nodes under each pointer, the
+//! [`UiPointerEvent`]s sent when this changes, and the [`PointerCapture`] of pointers by nodes.
+
+use bevy_asset::Assets;
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_hierarchy::Parent;
+pub use bevy_input::pointer::PointerId;
+use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::NormalizedRenderTarget, prelude::Camera, texture::Image, view::ViewVisibility,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use bevy_window::{PrimaryWindow, Window};
+
+use crate::{
+    node_point, pick_node, quad_viewport_position,
+    widget::{map_to_viewport, ViewportNode},
+    BorderRadius, CalculatedClip, DefaultUiCamera, FocusPolicy, Interaction, Node, PickingShape,
+    TargetCamera, UiScale, UiStack, UiTextureSurface, WorldUi, WorldUiPanel,
+};
+
+/// The UI nodes under each pointer, along with whether they are hovered or pressed.
+///
+/// A pointer hovers all the nodes under it from the topmost one down to the first node with a
+/// [`FocusPolicy::Block`], like [`Interaction`]. Unlike [`Interaction`], this is tracked for every
+/// node and separately for each pointer, so multiple touches can interact with different nodes.
+///
+/// Updated in [`update_hover_map`], which also sends the resulting [`UiPointerEvent`]s.
+#[derive(Resource, Debug, Default)]
+pub struct HoverMap {
+    pub(crate) pointers: HashMap<PointerId, EntityHashMap<Interaction>>,
+    pub(crate) positions: HashMap<PointerId, EntityHashMap<Vec2>>,
+    /// The topmost node under each pointer, the target of its [`UiPointerEvent`]s.
+    pub(crate) targets: HashMap<PointerId, Entity>,
+}
+
+impl HoverMap {
+    /// Returns the interaction of `pointer` with `entity`.
+    ///
+    /// This is [`Interaction::None`] if `entity` isn't under `pointer`.
+    pub fn get(&self, pointer: PointerId, entity: Entity) -> Interaction {
+        self.pointers
+            .get(&pointer)
+            .and_then(|entities| entities.get(&entity))
+            .copied()
+            .unwrap_or(Interaction::None)
+    }
+
+    /// Iterates over the nodes under `pointer` and their interaction with it.
+    pub fn iter_pointer(
+        &self,
+        pointer: PointerId,
+    ) -> impl Iterator<Item = (Entity, Interaction)> + '_ {
+        self.pointers
+            .get(&pointer)
+            .into_iter()
+            .flat_map(|entities| {
+                entities
+                    .iter()
+                    .map(|(entity, interaction)| (*entity, *interaction))
+            })
+    }
+
+    /// Iterates over the pointers currently over an interface, whether they hover a node or not.
+    pub fn pointers(&self) -> impl Iterator<Item = PointerId> + '_ {
+        self.pointers.keys().copied()
+    }
+
+    /// Returns the position of `pointer` in the logical viewport coordinates of `camera`.
+    ///
+    /// This is `None` if the pointer isn't over the viewport of the camera. The cameras displayed
+    /// by a [`ViewportNode`] get the positions of the pointers over the node, moved into their
+    /// viewport.
+    pub fn pointer_position(&self, pointer: PointerId, camera: Entity) -> Option<Vec2> {
+        self.positions
+            .get(&pointer)
+            .and_then(|positions| positions.get(&camera))
+            .copied()
+    }
+
+    /// Iterates over the cameras whose viewport `pointer` is over, and its position in each of
+    /// them.
+    pub(crate) fn pointer_camera_positions(
+        &self,
+        pointer: PointerId,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        self.positions
+            .get(&pointer)
+            .into_iter()
+            .flat_map(|positions| {
+                positions
+                    .iter()
+                    .map(|(camera, position)| (*camera, *position))
+            })
+    }
+
+    /// Returns `true` if any pointer hovers or presses `entity`.
+    pub fn is_hovered(&self, entity: Entity) -> bool {
+        self.pointers
+            .values()
+            .any(|entities| entities.contains_key(&entity))
+    }
+
+    /// Returns `true` if any pointer presses `entity`.
+    pub fn is_pressed(&self, entity: Entity) -> bool {
+        self.pointers
+            .values()
+            .any(|entities| entities.get(&entity) == Some(&Interaction::Pressed))
+    }
+}
+
+/// The UI nodes capturing pointers.
+///
+/// While a node captures a pointer, the pointer only interacts with this node in the [`HoverMap`]
+/// and its [`UiPointerEvent`]s target it, even when the pointer leaves the node. This keeps a
+/// slider dragged when the cursor moves past its end, for example. The node isn't sent a
+/// [`UiPointerEventKind::Click`] if the pointer is released outside of it.
+///
+/// Captures are released when their pointer is released, or when their node is despawned.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::{PointerCapture, UiPointerEvent, UiPointerEventKind};
+/// #[derive(Component)]
+/// struct SliderThumb;
+///
+/// fn capture_slider_drag(
+///     mut events: EventReader<UiPointerEvent>,
+///     mut pointer_capture: ResMut<PointerCapture>,
+///     thumbs: Query<(), With<SliderThumb>>,
+/// ) {
+///     for event in events.read() {
+///         if event.kind == UiPointerEventKind::Down && thumbs.contains(event.listener) {
+///             pointer_capture.capture(event.pointer, event.listener);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct PointerCapture {
+    pointers: HashMap<PointerId, Entity>,
+}
+
+impl PointerCapture {
+    /// Captures `pointer` by `entity`, and returns the entity which captured it before, if any.
+    pub fn capture(&mut self, pointer: PointerId, entity: Entity) -> Option<Entity> {
+        self.pointers.insert(pointer, entity)
+    }
+
+    /// Releases `pointer`, and returns the entity which captured it, if any.
+    pub fn release(&mut self, pointer: PointerId) -> Option<Entity> {
+        self.pointers.remove(&pointer)
+    }
+
+    /// Returns the entity capturing `pointer`, if any.
+    pub fn get(&self, pointer: PointerId) -> Option<Entity> {
+        self.pointers.get(&pointer).copied()
+    }
+}
+
+/// The kind of a [`UiPointerEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum UiPointerEventKind {
+    /// The pointer started hovering the target.
+    Over,
+    /// The pointer stopped hovering the target.
+    Out,
+    /// The pointer was pressed over the target.
+    Down,
+    /// The pointer was released over the target.
+    Up,
+    /// The pointer was pressed and then released over the target.
+    Click,
+}
+
+/// An event sent when a pointer interacts with a UI node.
+///
+/// Events target the topmost node under the pointer, or the node capturing it, and bubble up the
+/// hierarchy: an event is first sent with the target as its [`listener`](Self::listener), then
+/// once for each of its ancestors, until an entity with [`PointerBubbling::Stop`] is reached.
+/// This lets composite widgets, such as a button containing an icon and a label, handle the
+/// events of their children:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::{widget::Button, UiPointerEvent, UiPointerEventKind};
+/// fn on_button_click(mut events: EventReader<UiPointerEvent>, buttons: Query<(), With<Button>>) {
+///     for event in events.read() {
+///         if event.kind == UiPointerEventKind::Click && buttons.contains(event.listener) {
+///             // The button or one of its children was clicked.
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiPointerEvent {
+    /// The pointer that interacted with the node.
+    pub pointer: PointerId,
+    /// The kind of interaction.
+    pub kind: UiPointerEventKind,
+    /// The node the pointer interacted with.
+    pub target: Entity,
+    /// The entity the event is currently sent to: the [`target`](Self::target) or one of its
+    /// ancestors.
+    pub listener: Entity,
+}
+
+/// Controls whether the [`UiPointerEvent`]s sent to an entity bubble up to its parent.
+#[derive(Component, Copy, Clone, Default, Eq, PartialEq, Debug, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum PointerBubbling {
+    /// The events are sent to the parent of the entity.
+    #[default]
+    Bubble,
+    /// The events stop at this entity.
+    Stop,
+}
+
+/// The state of a pointer for the current frame.
+struct PointerInput {
+    id: PointerId,
+    /// The position of the pointer in the logical viewport coordinates of each camera.
+    camera_positions: EntityHashMap<Vec2>,
+    pressed: bool,
+    just_pressed: bool,
+    just_released: bool,
+}
+
+/// Updates the [`HoverMap`] and sends the [`UiPointerEvent`]s, taking the [`PointerCapture`] into
+/// account.
+#[allow(clippy::too_many_arguments)]
+pub fn update_hover_map(
+    mut hover_map: ResMut<HoverMap>,
+    mut pointer_capture: ResMut<PointerCapture>,
+    mut pointer_events: EventWriter<UiPointerEvent>,
+    camera_query: Query<(Entity, &Camera)>,
+    default_ui_camera: DefaultUiCamera,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches_input: Res<Touches>,
+    ui_scale: Res<UiScale>,
+    ui_stack: Res<UiStack>,
+    images: Res<Assets<Image>>,
+    node_query: Query<(
+        &Node,
+        &GlobalTransform,
+        &ViewVisibility,
+        Option<&FocusPolicy>,
+        Option<&CalculatedClip>,
+        Option<&TargetCamera>,
+        Option<&BorderRadius>,
+        Option<&PickingShape>,
+    )>,
+    viewport_query: Query<&ViewportNode>,
+    world_ui_query: Query<(&WorldUi, &WorldUiPanel)>,
+    surface_query: Query<(&UiTextureSurface, &GlobalTransform)>,
+    transform_query: Query<&GlobalTransform>,
+    parent_query: Query<(Option<&Parent>, Option<&PointerBubbling>)>,
+) {
+    let primary_window = primary_window.iter().next();
+    pointer_capture
+        .pointers
+        .retain(|_, entity| node_query.contains(*entity));
+
+    // The logical viewport positions of the given window position, for each camera rendering to
+    // a window. If `window_position` is `None`, the cursor position of each window is used.
+    let camera_positions = |window_position: Option<Vec2>| -> EntityHashMap<Vec2> {
+        camera_query
+            .iter()
+            .filter_map(|(entity, camera)| {
+                let Some(NormalizedRenderTarget::Window(window_ref)) =
+                    camera.target.normalize(primary_window)
+                else {
+                    return None;
+                };
+                let viewport_position = camera
+                    .logical_viewport_rect()
+                    .map(|rect| rect.min)
+                    .unwrap_or_default();
+                let position = window_position.or_else(|| {
+                    windows
+                        .get(window_ref.entity())
+                        .ok()
+                        .and_then(Window::cursor_position)
+                })?;
+                Some((entity, position - viewport_position))
+            })
+            .collect()
+    };
+
+    let mut pointers = vec![PointerInput {
+        id: PointerId::Mouse,
+        camera_positions: camera_positions(None),
+        pressed: mouse_button_input.pressed(MouseButton::Left),
+        just_pressed: mouse_button_input.just_pressed(MouseButton::Left),
+        just_released: mouse_button_input.just_released(MouseButton::Left),
+    }];
+    for touch in touches_input.iter() {
+        pointers.push(PointerInput {
+            id: PointerId::Touch(touch.id()),
+            camera_positions: camera_positions(Some(touch.position())),
+            pressed: true,
+            just_pressed: touches_input.just_pressed(touch.id()),
+            just_released: false,
+        });
+    }
+    for touch in touches_input
+        .iter_just_released()
+        .chain(touches_input.iter_just_canceled())
+    {
+        pointers.push(PointerInput {
+            id: PointerId::Touch(touch.id()),
+            camera_positions: camera_positions(Some(touch.position())),
+            pressed: false,
+            just_pressed: false,
+            just_released: true,
+        });
+    }
+
+    // Move the pointers over viewport nodes into the viewport of their cameras
+    for pointer in &mut pointers {
+        for entity in ui_stack.uinodes.iter().rev() {
+            let Ok(viewport) = viewport_query.get(*entity) else {
+                continue;
+            };
+            let Ok((node, transform, view_visibility, _, clip, target_camera, _, _)) =
+                node_query.get(*entity)
+            else {
+                continue;
+            };
+            if !view_visibility.get() || pointer.camera_positions.contains_key(&viewport.camera) {
+                continue;
+            }
+            let Some(position) = target_camera
+                .map(TargetCamera::entity)
+                .or(default_ui_camera.get())
+                .and_then(|camera| pointer.camera_positions.get(&camera))
+                .map(|position| *position / ui_scale.0)
+            else {
+                continue;
+            };
+            let Some(viewport_size) = camera_query
+                .get(viewport.camera)
+                .ok()
+                .and_then(|(_, camera)| camera.logical_viewport_size())
+            else {
+                continue;
+            };
+            let node_rect = node.logical_rect(transform);
+            let node_position = node_point(position, transform);
+            if !clip.map_or(true, |clip| clip.clip.contains(position))
+                || !node_rect.contains(node_position)
+            {
+                continue;
+            }
+            if let Some(position) = map_to_viewport(node_rect, node_position, viewport_size) {
+                pointer.camera_positions.insert(viewport.camera, position);
+            }
+        }
+    }
+
+    // Move the pointers over world space panels and surfaces into the viewport of their UI
+    // cameras, through the nearest one hit by the ray cast from the camera they are seen through
+    for pointer in &mut pointers {
+        let world_ui_quads = world_ui_query.iter().filter_map(|(world_ui, panel)| {
+            let quad_transform = transform_query.get(panel.quad()).ok()?;
+            Some((
+                world_ui.camera,
+                quad_transform,
+                panel.camera(),
+                world_ui.size.as_vec2(),
+            ))
+        });
+        let surface_quads = surface_query.iter().filter_map(|(surface, transform)| {
+            let (_, ui_camera) = camera_query.get(surface.ui_camera).ok()?;
+            Some((
+                surface.camera,
+                transform,
+                surface.ui_camera,
+                ui_camera.logical_viewport_size()?,
+            ))
+        });
+        let nearest = world_ui_quads
+            .chain(surface_quads)
+            .filter_map(
+                |(camera_entity, quad_transform, ui_camera, viewport_size)| {
+                    let position = pointer.camera_positions.get(&camera_entity)?;
+                    let (_, camera) = camera_query.get(camera_entity).ok()?;
+                    let camera_transform = transform_query.get(camera_entity).ok()?;
+                    // The positions are relative to the viewport, while rays are cast from the
+                    // target
+                    let viewport_position = camera
+                        .logical_viewport_rect()
+                        .map(|rect| rect.min)
+                        .unwrap_or_default();
+                    let ray =
+                        camera.viewport_ray(camera_transform, *position + viewport_position)?;
+                    let (distance, position) =
+                        quad_viewport_position(quad_transform, viewport_size, ray)?;
+                    Some((distance, ui_camera, position))
+                },
+            )
+            .min_by(|(a, ..), (b, ..)| a.total_cmp(b));
+        if let Some((_, camera, position)) = nearest {
+            pointer.camera_positions.insert(camera, position);
+        }
+    }
+
+    let mut previous_pointers = std::mem::take(&mut hover_map.pointers);
+    let mut previous_targets = std::mem::take(&mut hover_map.targets);
+    hover_map.positions.clear();
+    let mut events = Vec::new();
+    for pointer in pointers {
+        let previous = previous_pointers.remove(&pointer.id).unwrap_or_default();
+
+        // Traverse the nodes from the topmost one, until one blocks the pointer.
+        let mut hits = Vec::new();
+        for entity in ui_stack.uinodes.iter().rev() {
+            let Ok((
+                node,
+                transform,
+                view_visibility,
+                focus_policy,
+                clip,
+                target_camera,
+                border_radius,
+                picking_shape,
+            )) = node_query.get(*entity)
+            else {
+                continue;
+            };
+            if !view_visibility.get() {
+                continue;
+            }
+            let Some((camera, position)) = target_camera
+                .map(TargetCamera::entity)
+                .or(default_ui_camera.get())
+                .and_then(|camera| Some((camera, pointer.camera_positions.get(&camera)?)))
+            else {
+                continue;
+            };
+            let position = *position / ui_scale.0;
+            let node_rect = node.logical_rect(transform);
+            // The clip rect is axis-aligned in UI space, while the node rect is in the space of the
+            // node, which may be rotated or scaled: each is checked against the position in its
+            // own space.
+            if node_rect.is_empty() || !clip.map_or(true, |clip| clip.clip.contains(position)) {
+                continue;
+            }
+            let viewport_size = camera_query
+                .get(camera)
+                .ok()
+                .and_then(|(_, camera)| camera.logical_viewport_size())
+                .unwrap_or(Vec2::ZERO);
+            if !pick_node(
+                node_point(position, transform),
+                node_rect,
+                border_radius,
+                picking_shape,
+                &images,
+                viewport_size / ui_scale.0,
+                ui_scale.0,
+                node.root_scale(),
+            ) {
+                continue;
+            }
+
+            hits.push(*entity);
+            if *focus_policy.unwrap_or(&FocusPolicy::Block) == FocusPolicy::Block {
+                break;
+            }
+        }
+
+        let previous_target = previous_targets.remove(&pointer.id);
+        update_pointer(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer,
+            &hits,
+            &previous,
+            previous_target,
+            &mut events,
+        );
+    }
+
+    for (pointer, kind, target) in events {
+        pointer_events.send_batch(bubble(pointer, kind, target, &parent_query));
+    }
+}
+
+/// Updates the [`HoverMap`] entry of `pointer` from the nodes it `hits`, from the topmost one,
+/// and pushes the events sent to the topmost node.
+///
+/// `previous` and `previous_target` are the interactions and the target of the pointer in the
+/// previous frame.
+#[allow(clippy::too_many_arguments)]
+fn update_pointer(
+    hover_map: &mut HoverMap,
+    pointer_capture: &mut PointerCapture,
+    pointer: PointerInput,
+    hits: &[Entity],
+    previous: &EntityHashMap<Interaction>,
+    previous_target: Option<Entity>,
+    events: &mut Vec<(PointerId, UiPointerEventKind, Entity)>,
+) {
+    // A captured pointer only interacts with the node capturing it, wherever it is.
+    let targets = match pointer_capture.get(pointer.id) {
+        Some(captured) => vec![captured],
+        None => hits.to_vec(),
+    };
+    let mut current = EntityHashMap::default();
+    for entity in &targets {
+        let was_pressed = previous.get(entity) == Some(&Interaction::Pressed);
+        let interaction = if pointer.pressed && (pointer.just_pressed || was_pressed) {
+            Interaction::Pressed
+        } else {
+            Interaction::Hovered
+        };
+        current.insert(*entity, interaction);
+    }
+
+    // The events are sent to the topmost target only, and bubble up to its ancestors.
+    let target = targets.first().copied();
+    if previous_target != target {
+        if let Some(previous_target) = previous_target {
+            events.push((pointer.id, UiPointerEventKind::Out, previous_target));
+        }
+        if let Some(target) = target {
+            events.push((pointer.id, UiPointerEventKind::Over, target));
+        }
+    }
+    // Touches are removed once they end.
+    let ended = pointer.just_released && pointer.id != PointerId::Mouse;
+    if let Some(target) = target {
+        if pointer.just_pressed {
+            events.push((pointer.id, UiPointerEventKind::Down, target));
+        }
+        if pointer.just_released {
+            events.push((pointer.id, UiPointerEventKind::Up, target));
+            if previous.get(&target) == Some(&Interaction::Pressed) && hits.contains(&target) {
+                events.push((pointer.id, UiPointerEventKind::Click, target));
+            }
+        }
+        if ended {
+            events.push((pointer.id, UiPointerEventKind::Out, target));
+        } else {
+            hover_map.targets.insert(pointer.id, target);
+        }
+    }
+
+    if pointer.just_released {
+        pointer_capture.release(pointer.id);
+    }
+    if !ended {
+        hover_map.pointers.insert(pointer.id, current);
+        hover_map
+            .positions
+            .insert(pointer.id, pointer.camera_positions);
+    }
+}
+
+/// Returns the events sent to `target` and its ancestors, until an entity with
+/// [`PointerBubbling::Stop`] is reached.
+fn bubble(
+    pointer: PointerId,
+    kind: UiPointerEventKind,
+    target: Entity,
+    parent_query: &Query<(Option<&Parent>, Option<&PointerBubbling>)>,
+) -> Vec<UiPointerEvent> {
+    let mut events = Vec::new();
+    let mut listener = Some(target);
+    while let Some(current) = listener {
+        events.push(UiPointerEvent {
+            pointer,
+            kind,
+            target,
+            listener: current,
+        });
+        listener = match parent_query.get(current) {
+            Ok((Some(parent), bubbling)) if bubbling != Some(&PointerBubbling::Stop) => {
+                Some(parent.get())
+            }
+            _ => None,
+        };
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{entity::EntityHashMap, prelude::*, system::SystemState};
+    use bevy_hierarchy::{BuildWorldChildren, Parent};
+
+    use super::{
+        bubble, update_pointer, HoverMap, PointerBubbling, PointerCapture, PointerId, PointerInput,
+        UiPointerEventKind,
+    };
+    use crate::Interaction;
+
+    fn pointer(
+        id: PointerId,
+        pressed: bool,
+        just_pressed: bool,
+        just_released: bool,
+    ) -> PointerInput {
+        PointerInput {
+            id,
+            camera_positions: EntityHashMap::default(),
+            pressed,
+            just_pressed,
+            just_released,
+        }
+    }
+
+    /// Runs a frame of `pointer` over `hits`, and returns the kinds and targets of its events.
+    fn frame(
+        hover_map: &mut HoverMap,
+        pointer_capture: &mut PointerCapture,
+        pointer: PointerInput,
+        hits: &[Entity],
+    ) -> Vec<(UiPointerEventKind, Entity)> {
+        let id = pointer.id;
+        let previous = hover_map.pointers.remove(&id).unwrap_or_default();
+        let previous_target = hover_map.targets.remove(&id);
+        let mut events = Vec::new();
+        update_pointer(
+            hover_map,
+            pointer_capture,
+            pointer,
+            hits,
+            &previous,
+            previous_target,
+            &mut events,
+        );
+        events
+            .into_iter()
+            .map(|(event_pointer, kind, target)| {
+                assert_eq!(event_pointer, id);
+                (kind, target)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn events_target_the_topmost_node() {
+        use UiPointerEventKind::*;
+
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let child = world.spawn_empty().id();
+        let mut hover_map = HoverMap::default();
+        let mut pointer_capture = PointerCapture::default();
+        let mouse = PointerId::Mouse;
+
+        // Both nodes are hovered, but only the topmost one is sent events
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, false, false, false),
+            &[child, parent],
+        );
+        assert_eq!(events, vec![(Over, child)]);
+        assert_eq!(hover_map.get(mouse, parent), Interaction::Hovered);
+
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, true, true, false),
+            &[child, parent],
+        );
+        assert_eq!(events, vec![(Down, child)]);
+        assert_eq!(hover_map.get(mouse, parent), Interaction::Pressed);
+
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, false, false, true),
+            &[child, parent],
+        );
+        assert_eq!(events, vec![(Up, child), (Click, child)]);
+
+        // Moving to the parent leaves the child
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, false, false, false),
+            &[parent],
+        );
+        assert_eq!(events, vec![(Out, child), (Over, parent)]);
+    }
+
+    #[test]
+    fn ended_touches_leave_their_target() {
+        use UiPointerEventKind::*;
+
+        let mut world = World::default();
+        let button = world.spawn_empty().id();
+        let mut hover_map = HoverMap::default();
+        let mut pointer_capture = PointerCapture::default();
+        let touch = PointerId::Touch(0);
+
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(touch, true, true, false),
+            &[button],
+        );
+        assert_eq!(events, vec![(Over, button), (Down, button)]);
+
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(touch, false, false, true),
+            &[button],
+        );
+        assert_eq!(events, vec![(Up, button), (Click, button), (Out, button)]);
+        assert_eq!(hover_map.pointers().count(), 0);
+        assert!(!hover_map.targets.contains_key(&touch));
+    }
+
+    #[test]
+    fn captured_pointers_target_their_node_until_released() {
+        use UiPointerEventKind::*;
+
+        let mut world = World::default();
+        let slider = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        let mut hover_map = HoverMap::default();
+        let mut pointer_capture = PointerCapture::default();
+        let mouse = PointerId::Mouse;
+
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, true, true, false),
+            &[slider],
+        );
+        assert_eq!(events, vec![(Over, slider), (Down, slider)]);
+        pointer_capture.capture(mouse, slider);
+
+        // The captured pointer keeps pressing the slider outside of it
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, true, false, false),
+            &[other],
+        );
+        assert!(events.is_empty());
+        assert_eq!(hover_map.get(mouse, slider), Interaction::Pressed);
+        assert_eq!(hover_map.get(mouse, other), Interaction::None);
+
+        // Releasing it outside of the slider doesn't click it, and releases the capture
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, false, false, true),
+            &[other],
+        );
+        assert_eq!(events, vec![(Up, slider)]);
+        assert_eq!(pointer_capture.get(mouse), None);
+
+        let events = frame(
+            &mut hover_map,
+            &mut pointer_capture,
+            pointer(mouse, false, false, false),
+            &[other],
+        );
+        assert_eq!(events, vec![(Out, slider), (Over, other)]);
+        assert_eq!(hover_map.get(mouse, other), Interaction::Hovered);
+    }
+
+    #[test]
+    fn events_bubble_until_stopped() {
+        let mut world = World::default();
+        let root = world.spawn_empty().id();
+        let button = world.spawn(PointerBubbling::Stop).set_parent(root).id();
+        let label = world.spawn_empty().set_parent(button).id();
+
+        let mut state: SystemState<Query<(Option<&Parent>, Option<&PointerBubbling>)>> =
+            SystemState::new(&mut world);
+        let parent_query = state.get(&world);
+
+        let listeners: Vec<_> = bubble(
+            PointerId::Mouse,
+            UiPointerEventKind::Click,
+            label,
+            &parent_query,
+        )
+        .into_iter()
+        .map(|event| {
+            assert_eq!(event.target, label);
+            event.listener
+        })
+        .collect();
+        assert_eq!(listeners, vec![label, button]);
+
+        let listeners: Vec<_> = bubble(
+            PointerId::Mouse,
+            UiPointerEventKind::Click,
+            root,
+            &parent_query,
+        )
+        .into_iter()
+        .map(|event| event.listener)
+        .collect();
+        assert_eq!(listeners, vec![root]);
+    }
+}
@@ -2,7 +2,7 @@ mod pipeline;
 mod render_pass;
 mod ui_material_pipeline;
 
-use bevy_color::{Alpha, LinearRgba};
+use bevy_color::{Alpha, Color, LinearRgba};
 use bevy_core_pipeline::core_2d::graph::{Core2d, Node2d};
 use bevy_core_pipeline::core_3d::graph::{Core3d, Node3d};
 use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
@@ -21,16 +21,21 @@ pub use ui_material_pipeline::*;
 
 use crate::graph::{NodeUi, SubGraphUi};
 use crate::{
-    texture_slice::ComputedTextureSlices, BackgroundColor, BorderColor, BorderRadius,
-    CalculatedClip, ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiImage,
-    UiScale, Val,
+    texture_slice::ComputedTextureSlices, BackgroundColor, BackgroundGradient, BorderColor,
+    BorderImage, BorderRadius, BorderSideColors, BorderStroke, BoxShadow, CalculatedClip,
+    ContentSize, DefaultUiCamera, Direction, Node, Outline, Style, TargetCamera, UiImage, UiScale,
+    Val,
 };
 
+#[cfg(feature = "bevy_text")]
+use crate::widget::{caret_position, TextInput};
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy_ecs::prelude::*;
-use bevy_math::{FloatOrd, Mat4, Rect, URect, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
+use bevy_math::{
+    Affine2, FloatOrd, Mat2, Mat4, Rect, URect, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles,
+};
 use bevy_render::{
     camera::Camera,
     render_asset::RenderAssets,
@@ -44,8 +49,8 @@ use bevy_render::{
 };
 use bevy_sprite::TextureAtlasLayout;
 #[cfg(feature = "bevy_text")]
-use bevy_text::{PositionedGlyph, Text, TextLayoutInfo};
-use bevy_transform::components::GlobalTransform;
+use bevy_text::{PositionedGlyph, Text, TextGlyphModifiers, TextLayoutInfo, TextSection};
+use bevy_transform::components::{GlobalTransform, Transform};
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
 use std::ops::Range;
@@ -66,6 +71,7 @@ pub const UI_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(130128470471
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum RenderUiSystem {
+    ExtractBoxShadows,
     ExtractBackgrounds,
     ExtractImages,
     ExtractBorders,
@@ -91,6 +97,7 @@ pub fn build_ui_render(app: &mut App) {
         .configure_sets(
             ExtractSchedule,
             (
+                RenderUiSystem::ExtractBoxShadows,
                 RenderUiSystem::ExtractBackgrounds,
                 RenderUiSystem::ExtractImages,
                 RenderUiSystem::ExtractBorders,
@@ -102,12 +109,23 @@ pub fn build_ui_render(app: &mut App) {
             ExtractSchedule,
             (
                 extract_default_ui_camera_view,
+                extract_uinode_box_shadows.in_set(RenderUiSystem::ExtractBoxShadows),
                 extract_uinode_background_colors.in_set(RenderUiSystem::ExtractBackgrounds),
+                extract_uinode_background_gradients.in_set(RenderUiSystem::ExtractBackgrounds),
                 extract_uinode_images.in_set(RenderUiSystem::ExtractImages),
                 extract_uinode_borders.in_set(RenderUiSystem::ExtractBorders),
+                extract_uinode_border_images.in_set(RenderUiSystem::ExtractBorders),
                 extract_uinode_outlines.in_set(RenderUiSystem::ExtractBorders),
                 #[cfg(feature = "bevy_text")]
                 extract_uinode_text.in_set(RenderUiSystem::ExtractText),
+                #[cfg(feature = "bevy_text")]
+                extract_text_input_selections
+                    .in_set(RenderUiSystem::ExtractText)
+                    .before(extract_uinode_text),
+                #[cfg(feature = "bevy_text")]
+                extract_text_input_carets
+                    .in_set(RenderUiSystem::ExtractText)
+                    .after(extract_uinode_text),
             ),
         )
         .add_systems(
@@ -153,7 +171,40 @@ fn get_ui_graph(render_app: &mut SubApp) -> RenderGraph {
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum NodeType {
     Rect,
-    Border,
+    Border {
+        /// The stroke of the border.
+        stroke: BorderStroke,
+        /// The only side of the border to draw, or `None` to draw all of them.
+        /// Ordering: left, top, right, bottom.
+        side: Option<usize>,
+    },
+    Shadow {
+        /// The distance over which the edges of the shadow fade out.
+        blur_radius: f32,
+    },
+    /// A segment of a gradient, between two of its stops.
+    /// The color of the node is the color at the start of the segment.
+    Gradient {
+        /// The color at the end of the segment.
+        end_color: LinearRgba,
+        /// The positions of the start and the end of the segment along the gradient.
+        range: [f32; 2],
+        /// Maps the points of the node, relative to its center, to the gradient: their
+        /// position along a linear gradient is the x coordinate, and along a radial gradient
+        /// the length.
+        mapping: Affine2,
+        /// Whether the gradient is radial.
+        radial: bool,
+    },
+    /// The outline or the shadow of a glyph of a text, drawn by sampling the glyph in its atlas
+    /// around each point of the node.
+    /// The rect of the node is the rect of the glyph grown by the outline width or the blur radius.
+    GlyphEffect {
+        /// The width of the outline, in logical pixels, or zero for a shadow.
+        outline_width: f32,
+        /// The radius of the blur of the shadow, in logical pixels, or zero for an outline.
+        blur: f32,
+    },
 }
 
 pub struct ExtractedUiNode {
@@ -190,18 +241,21 @@ pub fn extract_uinode_background_colors(
     default_ui_camera: Extract<DefaultUiCamera>,
     ui_scale: Extract<Res<UiScale>>,
     uinode_query: Extract<
-        Query<(
-            Entity,
-            &Node,
-            &GlobalTransform,
-            &ViewVisibility,
-            Option<&CalculatedClip>,
-            Option<&TargetCamera>,
-            &BackgroundColor,
-            Option<&BorderRadius>,
-            &Style,
-            Option<&Parent>,
-        )>,
+        Query<
+            (
+                Entity,
+                &Node,
+                &GlobalTransform,
+                &ViewVisibility,
+                Option<&CalculatedClip>,
+                Option<&TargetCamera>,
+                &BackgroundColor,
+                Option<&BorderRadius>,
+                &Style,
+                Option<&Parent>,
+            ),
+            Without<BackgroundGradient>,
+        >,
     >,
     node_query: Extract<Query<&Node>>,
 ) {
@@ -243,14 +297,31 @@ pub fn extract_uinode_background_colors(
             .and_then(|parent| node_query.get(parent.get()).ok())
             .map(|parent_node| parent_node.size().x)
             .unwrap_or(ui_logical_viewport_size.x);
-        let left =
-            resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size);
-        let right =
-            resolve_border_thickness(style.border.right, parent_width, ui_logical_viewport_size);
-        let top =
-            resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size);
-        let bottom =
-            resolve_border_thickness(style.border.bottom, parent_width, ui_logical_viewport_size);
+        let border_sides = uinode.flip_sides(style.border);
+        let left = resolve_border_thickness(
+            border_sides.left,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
+        let right = resolve_border_thickness(
+            border_sides.right,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
+        let top = resolve_border_thickness(
+            border_sides.top,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
+        let bottom = resolve_border_thickness(
+            border_sides.bottom,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
 
         let border = [left, top, right, bottom];
 
@@ -260,6 +331,7 @@ pub fn extract_uinode_background_colors(
                 uinode.size(),
                 ui_logical_viewport_size,
                 ui_scale.0,
+                uinode.root_scale(),
             )
         } else {
             [0.; 4]
@@ -289,6 +361,187 @@ pub fn extract_uinode_background_colors(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn extract_uinode_background_gradients(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    camera_query: Extract<Query<(Entity, &Camera)>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            &BackgroundGradient,
+            Option<&BorderRadius>,
+            &Style,
+            Option<&Parent>,
+        )>,
+    >,
+    node_query: Extract<Query<&Node>>,
+) {
+    for (
+        uinode,
+        transform,
+        view_visibility,
+        clip,
+        camera,
+        gradient,
+        border_radius,
+        style,
+        parent,
+    ) in &uinode_query
+    {
+        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
+        else {
+            continue;
+        };
+
+        let stops = gradient.resolved_stops();
+
+        // Skip invisible gradients
+        if !view_visibility.get()
+            || uinode.size().x <= 0.
+            || uinode.size().y <= 0.
+            || stops.iter().all(|(color, _)| color.is_fully_transparent())
+        {
+            continue;
+        }
+
+        let ui_logical_viewport_size = camera_query
+            .get(camera_entity)
+            .ok()
+            .and_then(|(_, c)| c.logical_viewport_size())
+            .unwrap_or(Vec2::ZERO)
+            // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
+            // so we have to divide by `UiScale` to get the size of the UI viewport.
+            / ui_scale.0;
+
+        // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
+        // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
+        let parent_width = parent
+            .and_then(|parent| node_query.get(parent.get()).ok())
+            .map(|parent_node| parent_node.size().x)
+            .unwrap_or(ui_logical_viewport_size.x);
+        let border_sides = uinode.flip_sides(style.border);
+        let border = [
+            border_sides.left,
+            border_sides.top,
+            border_sides.right,
+            border_sides.bottom,
+        ]
+        .map(|value| {
+            resolve_border_thickness(
+                value,
+                parent_width,
+                ui_logical_viewport_size,
+                uinode.root_scale(),
+            )
+        });
+
+        let border_radius = if let Some(border_radius) = border_radius {
+            resolve_border_radius(
+                border_radius,
+                uinode.size(),
+                ui_logical_viewport_size,
+                ui_scale.0,
+                uinode.root_scale(),
+            )
+        } else {
+            [0.; 4]
+        };
+
+        let (mapping, radial) = gradient_mapping(gradient, uinode.size());
+        let transform = transform.compute_matrix();
+
+        // Each segment between two stops is drawn separately, the shader only keeps its pixels.
+        // The colors of the first and last stops extend before and after the gradient.
+        let (Some(&(first_color, first_position)), Some(&(last_color, last_position))) =
+            (stops.first(), stops.last())
+        else {
+            continue;
+        };
+        let segments = std::iter::once(((first_color, f32::MIN), (first_color, first_position)))
+            .chain(stops.windows(2).map(|stops| (stops[0], stops[1])))
+            .chain(std::iter::once((
+                (last_color, last_position),
+                (last_color, f32::MAX),
+            )));
+        for ((start_color, start), (end_color, end)) in segments {
+            // Skip the segments with a hard transition or without any color
+            if end <= start
+                || (start_color.is_fully_transparent() && end_color.is_fully_transparent())
+            {
+                continue;
+            }
+            extracted_uinodes.uinodes.insert(
+                commands.spawn_empty().id(),
+                ExtractedUiNode {
+                    stack_index: uinode.stack_index,
+                    transform,
+                    color: start_color.into(),
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: uinode.calculated_size,
+                    },
+                    clip: clip.map(|clip| clip.clip),
+                    image: AssetId::default(),
+                    atlas_size: None,
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border,
+                    border_radius,
+                    node_type: NodeType::Gradient {
+                        end_color: end_color.into(),
+                        range: [start, end],
+                        mapping,
+                        radial,
+                    },
+                },
+            );
+        }
+    }
+}
+
+/// Returns the mapping of the points of a node of `size`, relative to its center, to `gradient`,
+/// and whether it is radial. See [`NodeType::Gradient`].
+fn gradient_mapping(gradient: &BackgroundGradient, size: Vec2) -> (Affine2, bool) {
+    match *gradient {
+        BackgroundGradient::Linear { angle, .. } => {
+            // The gradient line goes through the center, and the corners of the node are at its
+            // start and end
+            let direction = Vec2::new(angle.sin(), -angle.cos());
+            let length = (size * direction).abs().element_sum().max(f32::EPSILON);
+            let scale = direction / length;
+            (
+                Affine2::from_mat2_translation(
+                    Mat2::from_cols(Vec2::new(scale.x, 0.), Vec2::new(scale.y, 0.)),
+                    Vec2::new(0.5, 0.),
+                ),
+                false,
+            )
+        }
+        BackgroundGradient::Radial { center, .. } => {
+            // The ellipses are circles relative to the size of the node
+            let radius = [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE]
+                .into_iter()
+                .map(|corner| corner.distance(center))
+                .fold(f32::EPSILON, f32::max);
+            (
+                Affine2::from_mat2_translation(
+                    Mat2::from_diagonal(1. / (size * radius)),
+                    (Vec2::splat(0.5) - center) / radius,
+                ),
+                true,
+            )
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn extract_uinode_images(
     mut commands: Commands,
@@ -385,14 +638,31 @@ pub fn extract_uinode_images(
             .and_then(|parent| node_query.get(parent.get()).ok())
             .map(|parent_node| parent_node.size().x)
             .unwrap_or(ui_logical_viewport_size.x);
-        let left =
-            resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size);
-        let right =
-            resolve_border_thickness(style.border.right, parent_width, ui_logical_viewport_size);
-        let top =
-            resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size);
-        let bottom =
-            resolve_border_thickness(style.border.bottom, parent_width, ui_logical_viewport_size);
+        let border_sides = uinode.flip_sides(style.border);
+        let left = resolve_border_thickness(
+            border_sides.left,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
+        let right = resolve_border_thickness(
+            border_sides.right,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
+        let top = resolve_border_thickness(
+            border_sides.top,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
+        let bottom = resolve_border_thickness(
+            border_sides.bottom,
+            parent_width,
+            ui_logical_viewport_size,
+            uinode.root_scale(),
+        );
 
         let border = [left, top, right, bottom];
 
@@ -402,6 +672,7 @@ pub fn extract_uinode_images(
                 uinode.size(),
                 ui_logical_viewport_size,
                 ui_scale.0,
+                uinode.root_scale(),
             )
         } else {
             [0.; 4]
@@ -428,10 +699,17 @@ pub fn extract_uinode_images(
     }
 }
 
-pub(crate) fn resolve_border_thickness(value: Val, parent_width: f32, viewport_size: Vec2) -> f32 {
+/// Resolves the thickness of a border, whose [`Val::Px`] values are scaled by the `root_scale` of
+/// the node, see [`Node::root_scale`].
+pub(crate) fn resolve_border_thickness(
+    value: Val,
+    parent_width: f32,
+    viewport_size: Vec2,
+    root_scale: f32,
+) -> f32 {
     match value {
         Val::Auto => 0.,
-        Val::Px(px) => px.max(0.),
+        Val::Px(px) => (px * root_scale).max(0.),
         Val::Percent(percent) => (parent_width * percent / 100.).max(0.),
         Val::Vw(percent) => (viewport_size.x * percent / 100.).max(0.),
         Val::Vh(percent) => (viewport_size.y * percent / 100.).max(0.),
@@ -440,31 +718,50 @@ pub(crate) fn resolve_border_thickness(value: Val, parent_width: f32, viewport_s
     }
 }
 
+/// Resolves the radii of the corners of a node, whose [`Val::Px`] values are scaled by the
+/// `root_scale` of the node, see [`Node::root_scale`].
 pub(crate) fn resolve_border_radius(
     &values: &BorderRadius,
     node_size: Vec2,
     viewport_size: Vec2,
     ui_scale: f32,
+    root_scale: f32,
 ) -> [f32; 4] {
-    let max_radius = 0.5 * node_size.min_element() * ui_scale;
-    [
+    let radii = [
         values.top_left,
         values.top_right,
         values.bottom_right,
         values.bottom_left,
     ]
-    .map(|value| {
-        match value {
-            Val::Auto => 0.,
-            Val::Px(px) => ui_scale * px,
-            Val::Percent(percent) => node_size.min_element() * percent / 100.,
-            Val::Vw(percent) => viewport_size.x * percent / 100.,
-            Val::Vh(percent) => viewport_size.y * percent / 100.,
-            Val::VMin(percent) => viewport_size.min_element() * percent / 100.,
-            Val::VMax(percent) => viewport_size.max_element() * percent / 100.,
+    .map(|value| match value {
+        Val::Auto => 0.,
+        Val::Px(px) => ui_scale * root_scale * px,
+        Val::Percent(percent) => node_size.min_element() * percent / 100.,
+        Val::Vw(percent) => viewport_size.x * percent / 100.,
+        Val::Vh(percent) => viewport_size.y * percent / 100.,
+        Val::VMin(percent) => viewport_size.min_element() * percent / 100.,
+        Val::VMax(percent) => viewport_size.max_element() * percent / 100.,
+    });
+    Rect::from_corners(Vec2::ZERO, node_size * ui_scale).clamp_corner_radii(radii)
+}
+
+/// Scales the dashes of a border like the other [`Val::Px`] lengths of the node, such as its
+/// border radius.
+pub(crate) fn resolve_border_stroke(
+    stroke: BorderStroke,
+    ui_scale: f32,
+    root_scale: f32,
+) -> BorderStroke {
+    match stroke {
+        BorderStroke::Dashed { dash, gap } => {
+            let scale = ui_scale * root_scale;
+            BorderStroke::Dashed {
+                dash: dash * scale,
+                gap: gap * scale,
+            }
         }
-        .clamp(0., max_radius)
-    })
+        stroke => stroke,
+    }
 }
 
 #[inline]
@@ -507,8 +804,10 @@ pub fn extract_uinode_borders(
                 &Style,
                 &BorderColor,
                 &BorderRadius,
+                Option<&BorderSideColors>,
+                Option<&BorderStroke>,
             ),
-            Without<ContentSize>,
+            (Without<ContentSize>, Without<BorderImage>),
         >,
     >,
     node_query: Extract<Query<&Node>>,
@@ -525,6 +824,8 @@ pub fn extract_uinode_borders(
         style,
         border_color,
         border_radius,
+        side_colors,
+        stroke,
     ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
@@ -532,9 +833,20 @@ pub fn extract_uinode_borders(
             continue;
         };
 
+        // The left and right colors are the start and end colors of a right to left node
+        let side_colors = side_colors.map(|side_colors| {
+            let [left, top, right, bottom] = side_colors.to_array();
+            match node.direction() {
+                Direction::RightToLeft => [right, top, left, bottom],
+                _ => [left, top, right, bottom],
+            }
+        });
+
         // Skip invisible borders
         if !view_visibility.get()
-            || border_color.0.is_fully_transparent()
+            || side_colors.map_or(border_color.0.is_fully_transparent(), |side_colors| {
+                side_colors.iter().all(Color::is_fully_transparent)
+            })
             || node.size().x <= 0.
             || node.size().y <= 0.
         {
@@ -556,14 +868,31 @@ pub fn extract_uinode_borders(
             .and_then(|parent| node_query.get(parent.get()).ok())
             .map(|parent_node| parent_node.size().x)
             .unwrap_or(ui_logical_viewport_size.x);
-        let left =
-            resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size);
-        let right =
-            resolve_border_thickness(style.border.right, parent_width, ui_logical_viewport_size);
-        let top =
-            resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size);
-        let bottom =
-            resolve_border_thickness(style.border.bottom, parent_width, ui_logical_viewport_size);
+        let border_sides = node.flip_sides(style.border);
+        let left = resolve_border_thickness(
+            border_sides.left,
+            parent_width,
+            ui_logical_viewport_size,
+            node.root_scale(),
+        );
+        let right = resolve_border_thickness(
+            border_sides.right,
+            parent_width,
+            ui_logical_viewport_size,
+            node.root_scale(),
+        );
+        let top = resolve_border_thickness(
+            border_sides.top,
+            parent_width,
+            ui_logical_viewport_size,
+            node.root_scale(),
+        );
+        let bottom = resolve_border_thickness(
+            border_sides.bottom,
+            parent_width,
+            ui_logical_viewport_size,
+            node.root_scale(),
+        );
 
         let border = [left, top, right, bottom];
 
@@ -577,33 +906,136 @@ pub fn extract_uinode_borders(
             node.size(),
             ui_logical_viewport_size,
             ui_scale.0,
+            node.root_scale(),
         );
 
         let border_radius = clamp_radius(border_radius, node.size(), border.into());
         let transform = global_transform.compute_matrix();
+        let stroke = resolve_border_stroke(
+            stroke.copied().unwrap_or_default(),
+            ui_scale.0,
+            node.root_scale(),
+        );
 
-        extracted_uinodes.uinodes.insert(
-            commands.spawn_empty().id(),
-            ExtractedUiNode {
-                stack_index: node.stack_index,
-                // This translates the uinode's transform to the center of the current border rectangle
-                transform,
-                color: border_color.0.into(),
-                rect: Rect {
-                    max: node.size(),
-                    ..Default::default()
+        // Each side with its own color is drawn separately, the shader only keeps its pixels
+        let sides: [Option<(Option<usize>, Color)>; 4] = match side_colors {
+            Some(side_colors) => std::array::from_fn(|side| {
+                (border[side] > 0. && !side_colors[side].is_fully_transparent())
+                    .then_some((Some(side), side_colors[side]))
+            }),
+            None => [Some((None, border_color.0)), None, None, None],
+        };
+
+        for (side, color) in sides.into_iter().flatten() {
+            extracted_uinodes.uinodes.insert(
+                commands.spawn_empty().id(),
+                ExtractedUiNode {
+                    stack_index: node.stack_index,
+                    // This translates the uinode's transform to the center of the current border rectangle
+                    transform,
+                    color: color.into(),
+                    rect: Rect {
+                        max: node.size(),
+                        ..Default::default()
+                    },
+                    image,
+                    atlas_size: None,
+                    clip: clip.map(|clip| clip.clip),
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border_radius,
+                    border,
+                    node_type: NodeType::Border { stroke, side },
                 },
-                image,
-                atlas_size: None,
-                clip: clip.map(|clip| clip.clip),
-                flip_x: false,
-                flip_y: false,
-                camera_entity,
-                border_radius,
-                border,
-                node_type: NodeType::Border,
-            },
-        );
+            );
+        }
+    }
+}
+
+pub fn extract_uinode_border_images(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    images: Extract<Res<Assets<Image>>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            &BorderImage,
+        )>,
+    >,
+) {
+    for (node, global_transform, view_visibility, clip, camera, border_image) in &uinode_query {
+        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
+        else {
+            continue;
+        };
+
+        // Skip invisible borders and images that are not loaded yet
+        if !view_visibility.get()
+            || border_image.color.is_fully_transparent()
+            || node.size().x <= 0.
+            || node.size().y <= 0.
+        {
+            continue;
+        }
+        let Some(image) = images.get(&border_image.image) else {
+            continue;
+        };
+
+        let image_size = image.size_f32();
+        let texture_rect = Rect {
+            min: Vec2::ZERO,
+            max: image_size,
+        };
+        // The region of the image the center slices are cut from, as computed by the slicer
+        let border = border_image.slicer.border;
+        let center_rect = Rect {
+            min: texture_rect.min + Vec2::new(border.left, border.bottom),
+            max: texture_rect.max - Vec2::new(border.right, border.top),
+        };
+
+        for slice in border_image
+            .slicer
+            .compute_slices(texture_rect, Some(node.size()))
+        {
+            let is_center = center_rect.contains(slice.texture_rect.min)
+                && center_rect.contains(slice.texture_rect.max);
+            if is_center && !border_image.fill {
+                continue;
+            }
+            let transform = global_transform
+                .mul_transform(Transform::from_translation(
+                    (slice.offset * Vec2::new(1.0, -1.0)).extend(0.0),
+                ))
+                .compute_matrix();
+            let scale = slice.draw_size / slice.texture_rect.size();
+            extracted_uinodes.uinodes.insert(
+                commands.spawn_empty().id(),
+                ExtractedUiNode {
+                    stack_index: node.stack_index,
+                    transform,
+                    color: border_image.color.into(),
+                    rect: Rect {
+                        min: slice.texture_rect.min * scale,
+                        max: slice.texture_rect.max * scale,
+                    },
+                    image: border_image.image.id(),
+                    atlas_size: Some(image_size * scale),
+                    clip: clip.map(|clip| clip.clip),
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border_radius: [0.; 4],
+                    border: [0.; 4],
+                    node_type: NodeType::Rect,
+                },
+            );
+        }
     }
 }
 
@@ -701,6 +1133,116 @@ pub fn extract_uinode_outlines(
     }
 }
 
+pub fn extract_uinode_box_shadows(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    camera_query: Extract<Query<(Entity, &Camera)>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            Option<&BorderRadius>,
+            &BoxShadow,
+        )>,
+    >,
+) {
+    let image = AssetId::<Image>::default();
+    for (node, global_transform, view_visibility, clip, camera, border_radius, box_shadow) in
+        &uinode_query
+    {
+        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
+        else {
+            continue;
+        };
+
+        // Skip invisible shadows
+        if !view_visibility.get()
+            || box_shadow.color.is_fully_transparent()
+            || node.size().x <= 0.
+            || node.size().y <= 0.
+        {
+            continue;
+        }
+
+        let ui_logical_viewport_size = camera_query
+            .get(camera_entity)
+            .ok()
+            .and_then(|(_, c)| c.logical_viewport_size())
+            .unwrap_or(Vec2::ZERO)
+            // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
+            // so we have to divide by `UiScale` to get the size of the UI viewport.
+            / ui_scale.0;
+
+        let resolve =
+            |value: Val, size: f32| value.resolve(size, ui_logical_viewport_size).unwrap_or(0.);
+        let offset = Vec2::new(
+            resolve(box_shadow.x_offset, node.size().x),
+            resolve(box_shadow.y_offset, node.size().y),
+        );
+        let spread_radius = resolve(box_shadow.spread_radius, node.size().x);
+        let blur_radius = resolve(box_shadow.blur_radius, node.size().x).max(0.);
+
+        let shadow_size = node.size() + 2. * spread_radius;
+        if shadow_size.x <= 0. || shadow_size.y <= 0. {
+            continue;
+        }
+
+        // The rounded corners of the node grow and shrink with the shadow
+        let border_radius = border_radius
+            .map(|border_radius| {
+                resolve_border_radius(
+                    border_radius,
+                    node.size(),
+                    ui_logical_viewport_size,
+                    ui_scale.0,
+                    node.root_scale(),
+                )
+            })
+            .unwrap_or_default()
+            .map(|radius| {
+                if radius > 0. {
+                    (radius + spread_radius).max(0.)
+                } else {
+                    0.
+                }
+            });
+        let border_radius = clamp_radius(border_radius, shadow_size, Vec4::ZERO);
+
+        extracted_uinodes.uinodes.insert(
+            commands.spawn_empty().id(),
+            ExtractedUiNode {
+                stack_index: node.stack_index,
+                transform: global_transform.compute_matrix()
+                    * Mat4::from_translation(offset.extend(0.)),
+                color: box_shadow.color.into(),
+                // The quad covers the blurred edges of the shadow
+                rect: Rect {
+                    max: shadow_size + 2. * SHADOW_BLUR_EXTENT * blur_radius,
+                    ..Default::default()
+                },
+                image,
+                atlas_size: None,
+                clip: clip.map(|clip| clip.clip),
+                flip_x: false,
+                flip_y: false,
+                camera_entity,
+                border: [0.; 4],
+                border_radius,
+                node_type: NodeType::Shadow { blur_radius },
+            },
+        );
+    }
+}
+
+/// The distance the blurred edges of a shadow extend beyond its shape, relative to its blur
+/// radius. This should match the value in `ui.wgsl`.
+const SHADOW_BLUR_EXTENT: f32 = 1.5;
+
 /// The UI camera is "moved back" by this many units (plus the [`UI_CAMERA_TRANSFORM_OFFSET`]) and also has a view
 /// distance of this many units. This ensures that with a left-handed projection,
 /// as ui elements are "stacked on top of each other", they are within the camera's view
@@ -801,11 +1343,20 @@ pub fn extract_uinode_text(
             Option<&TargetCamera>,
             &Text,
             &TextLayoutInfo,
+            Option<&TextGlyphModifiers>,
         )>,
     >,
 ) {
-    for (uinode, global_transform, view_visibility, clip, camera, text, text_layout_info) in
-        &uinode_query
+    for (
+        uinode,
+        global_transform,
+        view_visibility,
+        clip,
+        camera,
+        text,
+        text_layout_info,
+        modifiers,
+    ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
@@ -841,21 +1392,53 @@ pub fn extract_uinode_text(
         transform.translation = transform.translation.round();
         transform.translation *= inverse_scale_factor;
 
-        let mut color = LinearRgba::WHITE;
-        let mut current_section = usize::MAX;
-        for PositionedGlyph {
-            position,
-            atlas_info,
-            section_index,
-            ..
-        } in &text_layout_info.glyphs
-        {
-            if *section_index != current_section {
-                color = LinearRgba::from(text.sections[*section_index].style.color);
-                current_section = *section_index;
-            }
+        for (index, layer) in glyph_draw_order(&text_layout_info.glyphs, &text.sections) {
+            let PositionedGlyph {
+                position,
+                atlas_info,
+                section_index,
+                ..
+            } = &text_layout_info.glyphs[index];
+            let style = &text.sections[*section_index].style;
+            let modifier = modifiers
+                .and_then(|modifiers| modifiers.0.get(index))
+                .copied()
+                .unwrap_or_default();
+            // The modifiers move the glyphs up with positive offsets, the UI Y axis points down
+            let position =
+                *position * inverse_scale_factor + Vec2::new(modifier.offset.x, -modifier.offset.y);
             let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
 
+            // The nodes of the outline and of the shadow are grown to fit them around the glyph
+            let (offset, margin, color, node_type) = match layer {
+                GlyphLayer::Shadow => {
+                    let Some(shadow) = style.shadow else {
+                        continue;
+                    };
+                    let blur = shadow.blur.max(0.);
+                    let node_type = NodeType::GlyphEffect {
+                        outline_width: 0.,
+                        blur,
+                    };
+                    (shadow.offset, blur, shadow.color, node_type)
+                }
+                GlyphLayer::Outline => {
+                    let Some(outline) = style.outline else {
+                        continue;
+                    };
+                    let node_type = NodeType::GlyphEffect {
+                        outline_width: outline.width,
+                        blur: 0.,
+                    };
+                    (Vec2::ZERO, outline.width, outline.color, node_type)
+                }
+                GlyphLayer::Fill => (
+                    Vec2::ZERO,
+                    0.,
+                    modifier.color.unwrap_or(style.color),
+                    NodeType::Rect,
+                ),
+            };
             let mut rect = atlas.textures[atlas_info.glyph_index].as_rect();
             rect.min *= inverse_scale_factor;
             rect.max *= inverse_scale_factor;
@@ -863,10 +1446,9 @@ pub fn extract_uinode_text(
                 commands.spawn_empty().id(),
                 ExtractedUiNode {
                     stack_index: uinode.stack_index,
-                    transform: transform
-                        * Mat4::from_translation(position.extend(0.) * inverse_scale_factor),
-                    color,
-                    rect,
+                    transform: transform * Mat4::from_translation((position + offset).extend(0.)),
+                    color: color.into(),
+                    rect: rect.inflate(margin),
                     image: atlas_info.texture.id(),
                     atlas_size: Some(atlas.size.as_vec2() * inverse_scale_factor),
                     clip: clip.map(|clip| clip.clip),
@@ -875,13 +1457,246 @@ pub fn extract_uinode_text(
                     camera_entity,
                     border: [0.; 4],
                     border_radius: [0.; 4],
-                    node_type: NodeType::Rect,
+                    node_type,
                 },
             );
         }
     }
 }
 
+/// The layers the glyphs of a text are drawn in.
+#[cfg(feature = "bevy_text")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GlyphLayer {
+    Shadow,
+    Outline,
+    Fill,
+}
+
+/// Returns the index of each glyph of a text along with the layer it is drawn in, in drawing
+/// order.
+///
+/// The glyphs of each section are drawn in three passes: their shadows, then their outlines and
+/// finally the glyphs themselves, so that the shadow and the outline of a glyph never cover the
+/// other glyphs of its section. The layers a section has no style for are skipped.
+#[cfg(feature = "bevy_text")]
+fn glyph_draw_order(
+    glyphs: &[PositionedGlyph],
+    sections: &[TextSection],
+) -> Vec<(usize, GlyphLayer)> {
+    let mut order = Vec::new();
+    let mut start = 0;
+    while start < glyphs.len() {
+        let section_index = glyphs[start].section_index;
+        let end = glyphs[start..]
+            .iter()
+            .position(|glyph| glyph.section_index != section_index)
+            .map_or(glyphs.len(), |len| start + len);
+        let style = &sections[section_index].style;
+        let layers = [
+            (GlyphLayer::Shadow, style.shadow.is_some()),
+            (
+                GlyphLayer::Outline,
+                style.outline.is_some_and(|outline| outline.width > 0.),
+            ),
+            (GlyphLayer::Fill, true),
+        ];
+        for (layer, _) in layers.into_iter().filter(|(_, drawn)| *drawn) {
+            order.extend((start..end).map(|index| (index, layer)));
+        }
+        start = end;
+    }
+    order
+}
+
+/// Extracts a rectangle behind each selected character of the focused [`TextInput`].
+///
+/// This runs before [`extract_uinode_text`], so that the rectangles are drawn behind the glyphs.
+#[cfg(feature = "bevy_text")]
+#[allow(clippy::too_many_arguments)]
+pub fn extract_text_input_selections(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    camera_query: Extract<Query<(Entity, &Camera)>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
+    focus: Extract<Option<Res<bevy_a11y::Focus>>>,
+    input_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            &TextInput,
+            &TextLayoutInfo,
+        )>,
+    >,
+) {
+    let Some(Ok((uinode, global_transform, view_visibility, clip, camera, input, layout))) = focus
+        .as_ref()
+        .and_then(|focus| focus.0)
+        .map(|entity| input_query.get(entity))
+    else {
+        return;
+    };
+    let selection = input.displayed_selection();
+    if selection.is_empty() || input.selection_color.is_fully_transparent() {
+        return;
+    }
+    let Some((camera_entity, transform, inverse_scale_factor)) = text_input_transform(
+        uinode,
+        global_transform,
+        view_visibility,
+        camera,
+        &camera_query,
+        &default_ui_camera,
+        ui_scale.0,
+    ) else {
+        return;
+    };
+
+    for caret in layout
+        .carets
+        .iter()
+        .filter(|caret| selection.contains(&caret.byte_index))
+    {
+        let size = Vec2::new(caret.advance, caret.height) * inverse_scale_factor;
+        let center = caret.position * inverse_scale_factor + Vec2::X * size.x / 2.;
+        extracted_uinodes.uinodes.insert(
+            commands.spawn_empty().id(),
+            ExtractedUiNode {
+                stack_index: uinode.stack_index,
+                transform: transform * Mat4::from_translation(center.extend(0.)),
+                color: input.selection_color.into(),
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: size,
+                },
+                clip: clip.map(|clip| clip.clip),
+                image: AssetId::default(),
+                atlas_size: None,
+                flip_x: false,
+                flip_y: false,
+                camera_entity,
+                border: [0.; 4],
+                border_radius: [0.; 4],
+                node_type: NodeType::Rect,
+            },
+        );
+    }
+}
+
+/// Extracts the caret of the focused [`TextInput`].
+///
+/// This runs after [`extract_uinode_text`], so that the caret is drawn in front of the glyphs.
+#[cfg(feature = "bevy_text")]
+#[allow(clippy::too_many_arguments)]
+pub fn extract_text_input_carets(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    camera_query: Extract<Query<(Entity, &Camera)>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
+    focus: Extract<Option<Res<bevy_a11y::Focus>>>,
+    input_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            &TextInput,
+            &TextLayoutInfo,
+        )>,
+    >,
+) {
+    let Some(Ok((uinode, global_transform, view_visibility, clip, camera, input, layout))) = focus
+        .as_ref()
+        .and_then(|focus| focus.0)
+        .map(|entity| input_query.get(entity))
+    else {
+        return;
+    };
+    let Some(byte_index) = input.displayed_caret() else {
+        return;
+    };
+    let Some((camera_entity, transform, inverse_scale_factor)) = text_input_transform(
+        uinode,
+        global_transform,
+        view_visibility,
+        camera,
+        &camera_query,
+        &default_ui_camera,
+        ui_scale.0,
+    ) else {
+        return;
+    };
+
+    // An empty input has no caret position, its caret is placed at the left of the node
+    let (center, height) = caret_position(&layout.carets, byte_index)
+        .map(|(position, height)| {
+            (
+                position * inverse_scale_factor,
+                height * inverse_scale_factor,
+            )
+        })
+        .unwrap_or((Vec2::new(0., uinode.size().y / 2.), uinode.size().y));
+    // The caret is a logical pixel wide, and at least a physical pixel
+    let width = inverse_scale_factor.max(1.);
+    extracted_uinodes.uinodes.insert(
+        commands.spawn_empty().id(),
+        ExtractedUiNode {
+            stack_index: uinode.stack_index,
+            transform: transform * Mat4::from_translation(center.extend(0.)),
+            color: input.caret_color.into(),
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: Vec2::new(width, height),
+            },
+            clip: clip.map(|clip| clip.clip),
+            image: AssetId::default(),
+            atlas_size: None,
+            flip_x: false,
+            flip_y: false,
+            camera_entity,
+            border: [0.; 4],
+            border_radius: [0.; 4],
+            node_type: NodeType::Rect,
+        },
+    );
+}
+
+/// Returns the camera of a visible [`TextInput`] node, the transform of its top left corner
+/// aligned to the physical pixels like its text, and the inverse of its scale factor.
+#[cfg(feature = "bevy_text")]
+fn text_input_transform(
+    uinode: &Node,
+    global_transform: &GlobalTransform,
+    view_visibility: &ViewVisibility,
+    camera: Option<&TargetCamera>,
+    camera_query: &Query<(Entity, &Camera)>,
+    default_ui_camera: &DefaultUiCamera,
+    ui_scale: f32,
+) -> Option<(Entity, Mat4, f32)> {
+    let camera_entity = camera
+        .map(TargetCamera::entity)
+        .or(default_ui_camera.get())?;
+    if !view_visibility.get() || uinode.size().x == 0. || uinode.size().y == 0. {
+        return None;
+    }
+    let scale_factor = camera_query
+        .get(camera_entity)
+        .ok()
+        .and_then(|(_, c)| c.target_scaling_factor())
+        .unwrap_or(1.0)
+        * ui_scale;
+    let mut transform = global_transform.affine()
+        * bevy_math::Affine3A::from_translation((-0.5 * uinode.size()).extend(0.));
+    transform.translation = (transform.translation * scale_factor).round() / scale_factor;
+    Some((camera_entity, transform.into(), scale_factor.recip()))
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
 struct UiVertex {
@@ -899,6 +1714,18 @@ struct UiVertex {
     pub border: [f32; 4],
     /// Size of the UI node.
     pub size: [f32; 2],
+    /// Length of the dashes and of the gaps between them for dashed borders.
+    /// A gap of zero draws a solid border.
+    /// For gradients, the positions of the start and the end of the segment.
+    pub stroke: [f32; 2],
+    /// Position along the gradient, see [`NodeType::Gradient`].
+    pub gradient: [f32; 2],
+    /// Color at the end of the gradient segment.
+    pub end_color: [f32; 4],
+    /// For glyph outlines, the width of the outline, in logical pixels.
+    pub outline_width: f32,
+    /// For box shadows and glyph shadows, the radius of the blur, in logical pixels.
+    pub blur: f32,
 }
 
 #[derive(Resource)]
@@ -941,6 +1768,14 @@ pub mod shader_flags {
     /// Ordering: top left, top right, bottom right, bottom left.
     pub const CORNERS: [u32; 4] = [0, 2, 2 | 4, 4];
     pub const BORDER: u32 = 8;
+    /// Restricts a border to a single side.
+    /// Ordering: left, top, right, bottom.
+    pub const BORDER_SIDES: [u32; 4] = [16, 32, 64, 128];
+    pub const DOTTED: u32 = 256;
+    pub const SHADOW: u32 = 512;
+    pub const GRADIENT: u32 = 1024;
+    pub const RADIAL: u32 = 2048;
+    pub const GLYPH_EFFECT: u32 = 4096;
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -968,14 +1803,16 @@ pub fn queue_uinodes(
             &ui_pipeline,
             UiPipelineKey { hdr: view.hdr },
         );
+        // Shadows are drawn behind their node, and in front of the nodes below it
+        let stack_index = match extracted_uinode.node_type {
+            NodeType::Shadow { .. } => extracted_uinode.stack_index as f32 - 0.5,
+            _ => extracted_uinode.stack_index as f32,
+        };
         transparent_phase.add(TransparentUi {
             draw_function,
             pipeline,
             entity: *entity,
-            sort_key: (
-                FloatOrd(extracted_uinode.stack_index as f32),
-                entity.index(),
-            ),
+            sort_key: (FloatOrd(stack_index), entity.index()),
             // batch_range will be calculated in prepare_uinodes
             batch_range: 0..0,
             extra_index: PhaseItemExtraIndex::NONE,
@@ -1167,10 +2004,10 @@ pub fn prepare_uinodes(
                             continue;
                         }
                     }
+                    let atlas_extent = extracted_uinode.atlas_size.unwrap_or(uinode_rect.max);
                     let uvs = if flags == shader_flags::UNTEXTURED {
                         [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]
                     } else {
-                        let atlas_extent = extracted_uinode.atlas_size.unwrap_or(uinode_rect.max);
                         if extracted_uinode.flip_x {
                             std::mem::swap(&mut uinode_rect.max.x, &mut uinode_rect.min.x);
                             positions_diff[0].x *= -1.;
@@ -1207,8 +2044,65 @@ pub fn prepare_uinodes(
                     };
 
                     let color = extracted_uinode.color.to_f32_array();
-                    if extracted_uinode.node_type == NodeType::Border {
-                        flags |= shader_flags::BORDER;
+                    let radius = extracted_uinode.border_radius;
+                    let border = extracted_uinode.border;
+                    let size: [f32; 2] = rect_size.xy().into();
+                    let mut stroke = [0.; 2];
+                    let mut gradient = [[0.; 2]; 4];
+                    let mut end_color = [0.; 4];
+                    let mut outline_width = 0.;
+                    let mut blur = 0.;
+                    match extracted_uinode.node_type {
+                        NodeType::Rect => {}
+                        NodeType::Border {
+                            stroke: border_stroke,
+                            side,
+                        } => {
+                            flags |= shader_flags::BORDER;
+                            if let Some(side) = side {
+                                flags |= shader_flags::BORDER_SIDES[side];
+                            }
+                            match border_stroke {
+                                BorderStroke::Solid => {}
+                                BorderStroke::Dashed { dash, gap } => stroke = [dash, gap],
+                                BorderStroke::Dotted => flags |= shader_flags::DOTTED,
+                            }
+                        }
+                        NodeType::Shadow { blur_radius } => {
+                            flags |= shader_flags::SHADOW;
+                            blur = blur_radius;
+                        }
+                        NodeType::Gradient {
+                            end_color: segment_end_color,
+                            range,
+                            mapping,
+                            radial,
+                        } => {
+                            flags |= shader_flags::GRADIENT;
+                            if radial {
+                                flags |= shader_flags::RADIAL;
+                            }
+                            stroke = range;
+                            end_color = segment_end_color.to_f32_array();
+                            // The gradient is mapped from the clipped corners, so that clipping
+                            // doesn't stretch it
+                            gradient = std::array::from_fn(|i| {
+                                mapping
+                                    .transform_point2(
+                                        QUAD_VERTEX_POSITIONS[i].xy() * rect_size.xy()
+                                            + positions_diff[i],
+                                    )
+                                    .into()
+                            });
+                        }
+                        NodeType::GlyphEffect {
+                            outline_width: glyph_outline_width,
+                            blur: glyph_blur,
+                        } => {
+                            flags |= shader_flags::GLYPH_EFFECT;
+                            outline_width = glyph_outline_width;
+                            blur = glyph_blur;
+                        }
                     }
 
                     for i in 0..4 {
@@ -1217,9 +2111,14 @@ pub fn prepare_uinodes(
                             uv: uvs[i].into(),
                             color,
                             flags: flags | shader_flags::CORNERS[i],
-                            radius: extracted_uinode.border_radius,
-                            border: extracted_uinode.border,
-                            size: rect_size.xy().into(),
+                            radius,
+                            border,
+                            size,
+                            stroke,
+                            gradient: gradient[i],
+                            end_color,
+                            outline_width,
+                            blur,
                         });
                     }
 
@@ -1244,3 +2143,187 @@ pub fn prepare_uinodes(
     }
     extracted_uinodes.uinodes.clear();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        extract_uinode_box_shadows, resolve_border_stroke, ExtractedUiNodes, NodeType,
+        SHADOW_BLUR_EXTENT,
+    };
+    use crate::{BorderStroke, BoxShadow, Node, TargetCamera, UiScale, Val};
+    use bevy_color::Color;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_math::{Vec2, Vec3};
+    use bevy_render::{view::ViewVisibility, MainWorld};
+    use bevy_transform::components::GlobalTransform;
+    #[cfg(feature = "bevy_text")]
+    use {
+        super::{glyph_draw_order, GlyphLayer},
+        bevy_text::{
+            GlyphAtlasInfo, PositionedGlyph, TextOutline, TextSection, TextShadow, TextStyle,
+        },
+    };
+
+    /// Extracts the shadow of a visible 100x50 node, returning the extracted nodes.
+    fn extract_box_shadow(box_shadow: BoxShadow) -> ExtractedUiNodes {
+        let mut render_world = World::new();
+        render_world.init_resource::<ExtractedUiNodes>();
+        render_world.init_resource::<MainWorld>();
+        {
+            let mut main_world = render_world.resource_mut::<MainWorld>();
+            main_world.init_resource::<UiScale>();
+            let camera = main_world.spawn_empty().id();
+            let mut view_visibility = ViewVisibility::default();
+            view_visibility.set();
+            main_world.spawn((
+                Node {
+                    calculated_size: Vec2::new(100., 50.),
+                    ..Default::default()
+                },
+                GlobalTransform::IDENTITY,
+                view_visibility,
+                TargetCamera(camera),
+                box_shadow,
+            ));
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(extract_uinode_box_shadows);
+        schedule.run(&mut render_world);
+        render_world.remove_resource::<ExtractedUiNodes>().unwrap()
+    }
+
+    #[test]
+    fn box_shadow_extraction() {
+        let extracted = extract_box_shadow(BoxShadow::new(
+            Color::BLACK,
+            Val::Px(4.),
+            Val::Px(-2.),
+            Val::Px(5.),
+            Val::Px(8.),
+        ));
+        assert_eq!(extracted.uinodes.len(), 1);
+        let shadow = extracted.uinodes.values().next().unwrap();
+
+        assert_eq!(shadow.node_type, NodeType::Shadow { blur_radius: 8. });
+        // The quad is grown by the spread and covers the blurred edges
+        let blurred_edges = 2. * SHADOW_BLUR_EXTENT * 8.;
+        assert_eq!(
+            shadow.rect.size(),
+            Vec2::new(110. + blurred_edges, 60. + blurred_edges)
+        );
+        assert_eq!(shadow.transform.w_axis.truncate(), Vec3::new(4., -2., 0.));
+    }
+
+    #[test]
+    fn box_shadows_without_area_are_not_extracted() {
+        let transparent = BoxShadow {
+            color: Color::NONE,
+            ..Default::default()
+        };
+        assert!(extract_box_shadow(transparent).uinodes.is_empty());
+
+        // The spread shrinks the 50 pixels tall node to nothing
+        let shrunk = BoxShadow {
+            spread_radius: Val::Px(-25.),
+            ..Default::default()
+        };
+        assert!(extract_box_shadow(shrunk).uinodes.is_empty());
+    }
+
+    #[test]
+    fn dashes_are_scaled_like_px_lengths() {
+        assert_eq!(
+            resolve_border_stroke(BorderStroke::Dashed { dash: 4., gap: 2. }, 2., 1.5),
+            BorderStroke::Dashed { dash: 12., gap: 6. }
+        );
+        assert_eq!(
+            resolve_border_stroke(BorderStroke::Dotted, 2., 1.5),
+            BorderStroke::Dotted
+        );
+        assert_eq!(
+            resolve_border_stroke(BorderStroke::Solid, 2., 1.5),
+            BorderStroke::Solid
+        );
+    }
+
+    #[cfg(feature = "bevy_text")]
+    fn glyphs(section_indices: &[usize]) -> Vec<PositionedGlyph> {
+        section_indices
+            .iter()
+            .enumerate()
+            .map(|(byte_index, &section_index)| PositionedGlyph {
+                position: Vec2::ZERO,
+                size: Vec2::ONE,
+                atlas_info: GlyphAtlasInfo {
+                    texture_atlas: Default::default(),
+                    texture: Default::default(),
+                    glyph_index: 0,
+                },
+                section_index,
+                byte_index,
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "bevy_text")]
+    fn section(shadow: Option<TextShadow>, outline: Option<TextOutline>) -> TextSection {
+        TextSection::from_style(TextStyle {
+            shadow,
+            outline,
+            ..Default::default()
+        })
+    }
+
+    #[cfg(feature = "bevy_text")]
+    #[test]
+    fn glyph_effects_are_drawn_per_section_below_the_glyphs() {
+        use GlyphLayer::*;
+
+        let sections = [
+            section(Some(TextShadow::default()), Some(TextOutline::default())),
+            section(None, Some(TextOutline::default())),
+        ];
+        let order = glyph_draw_order(&glyphs(&[0, 0, 1, 1]), &sections);
+        assert_eq!(
+            order,
+            [
+                (0, Shadow),
+                (1, Shadow),
+                (0, Outline),
+                (1, Outline),
+                (0, Fill),
+                (1, Fill),
+                (2, Outline),
+                (3, Outline),
+                (2, Fill),
+                (3, Fill),
+            ]
+        );
+    }
+
+    #[cfg(feature = "bevy_text")]
+    #[test]
+    fn glyphs_without_effects_are_only_filled() {
+        let sections = [
+            section(None, None),
+            section(
+                None,
+                Some(TextOutline {
+                    width: 0.,
+                    ..Default::default()
+                }),
+            ),
+        ];
+        let order = glyph_draw_order(&glyphs(&[0, 1, 0]), &sections);
+
+        assert_eq!(
+            order,
+            [
+                (0, GlyphLayer::Fill),
+                (1, GlyphLayer::Fill),
+                (2, GlyphLayer::Fill)
+            ]
+        );
+    }
+}
@@ -71,6 +71,16 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x4,
                 // border size
                 VertexFormat::Float32x2,
+                // border stroke
+                VertexFormat::Float32x2,
+                // gradient
+                VertexFormat::Float32x2,
+                // gradient end color
+                VertexFormat::Float32x4,
+                // glyph outline width
+                VertexFormat::Float32,
+                // glyph shadow blur
+                VertexFormat::Float32,
             ],
         );
         let shader_defs = Vec::new();
@@ -4,6 +4,17 @@ const TEXTURED = 1u;
 const RIGHT_VERTEX = 2u;
 const BOTTOM_VERTEX = 4u;
 const BORDER: u32 = 8u;
+const BORDER_LEFT: u32 = 16u;
+const BORDER_SIDES: u32 = 240u;
+const DOTTED: u32 = 256u;
+const SHADOW: u32 = 512u;
+const GRADIENT: u32 = 1024u;
+const RADIAL: u32 = 2048u;
+const GLYPH_EFFECT: u32 = 4096u;
+
+// The distance the blurred edges of a shadow extend beyond its shape, relative to its blur radius.
+// This should match the value in `render/mod.rs`.
+const SHADOW_BLUR_EXTENT: f32 = 1.5;
 
 fn enabled(flags: u32, mask: u32) -> bool {
     return (flags & mask) != 0u;
@@ -17,11 +28,21 @@ struct VertexOutput {
 
     @location(2) @interpolate(flat) size: vec2<f32>,
     @location(3) @interpolate(flat) flags: u32,
-    @location(4) @interpolate(flat) radius: vec4<f32>,    
-    @location(5) @interpolate(flat) border: vec4<f32>,    
+    @location(4) @interpolate(flat) radius: vec4<f32>,
+    @location(5) @interpolate(flat) border: vec4<f32>,
 
     // Position relative to the center of the rectangle.
     @location(6) point: vec2<f32>,
+    // x: dash length, y: gap length.
+    // For gradients, x: start of the segment, y: end of the segment.
+    @location(7) @interpolate(flat) stroke: vec2<f32>,
+    // Position along a linear gradient in x, or along a radial gradient as the length.
+    @location(8) gradient: vec2<f32>,
+    @location(9) @interpolate(flat) end_color: vec4<f32>,
+    // For glyph outlines, the width of the outline. For box shadows and glyph shadows, the blur
+    // radius.
+    @location(10) @interpolate(flat) outline_width: f32,
+    @location(11) @interpolate(flat) blur: f32,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -38,6 +59,13 @@ fn vertex(
     // x: left, y: top, z: right, w: bottom.
     @location(5) border: vec4<f32>,
     @location(6) size: vec2<f32>,
+
+    // x: dash length, y: gap length.
+    @location(7) stroke: vec2<f32>,
+    @location(8) gradient: vec2<f32>,
+    @location(9) end_color: vec4<f32>,
+    @location(10) outline_width: f32,
+    @location(11) blur: f32,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
@@ -47,6 +75,11 @@ fn vertex(
     out.radius = radius;
     out.size = size;
     out.border = border;
+    out.stroke = stroke;
+    out.gradient = gradient;
+    out.end_color = end_color;
+    out.outline_width = outline_width;
+    out.blur = blur;
     var point = 0.49999 * size;
     if (flags & RIGHT_VERTEX) == 0u {
         point.x *= -1.;
@@ -62,31 +95,31 @@ fn vertex(
 @group(1) @binding(0) var sprite_texture: texture_2d<f32>;
 @group(1) @binding(1) var sprite_sampler: sampler;
 
-// The returned value is the shortest distance from the given point to the boundary of the rounded 
+// The returned value is the shortest distance from the given point to the boundary of the rounded
 // box.
-// 
-// Negative values indicate that the point is inside the rounded box, positive values that the point 
+//
+// Negative values indicate that the point is inside the rounded box, positive values that the point
 // is outside, and zero is exactly on the boundary.
 //
-// Arguments: 
-//  - `point`        -> The function will return the distance from this point to the closest point on 
+// Arguments:
+//  - `point`        -> The function will return the distance from this point to the closest point on
 //                    the boundary.
 //  - `size`         -> The maximum width and height of the box.
-//  - `corner_radii` -> The radius of each rounded corner. Ordered counter clockwise starting 
+//  - `corner_radii` -> The radius of each rounded corner. Ordered counter clockwise starting
 //                    top left:
 //                      x: top left, y: top right, z: bottom right, w: bottom left.
 fn sd_rounded_box(point: vec2<f32>, size: vec2<f32>, corner_radii: vec4<f32>) -> f32 {
     // If 0.0 < y then select bottom left (w) and bottom right corner radius (z).
     // Else select top left (x) and top right corner radius (y).
     let rs = select(corner_radii.xy, corner_radii.wz, 0.0 < point.y);
-    // w and z are swapped above so that both pairs are in left to right order, otherwise this second 
+    // w and z are swapped above so that both pairs are in left to right order, otherwise this second
     // select statement would return the incorrect value for the bottom pair.
     let radius = select(rs.x, rs.y, 0.0 < point.x);
     // Vector from the corner closest to the point, to the point.
     let corner_to_point = abs(point) - 0.5 * size;
     // Vector from the center of the radius circle to the point.
     let q = corner_to_point + radius;
-    // Length from center of the radius circle to the point, zeros a component if the point is not 
+    // Length from center of the radius circle to the point, zeros a component if the point is not
     // within the quadrant of the radius circle that is part of the curved corner.
     let l = length(max(q, vec2(0.0)));
     let m = min(max(q.x, q.y), 0.0);
@@ -107,7 +140,7 @@ fn sd_inset_rounded_box(point: vec2<f32>, size: vec2<f32>, radius: vec4<f32>, in
     r.y = r.y - max(inset.z, inset.y);
 
     // Bottom right corner.
-    r.z = r.z - max(inset.z, inset.w); 
+    r.z = r.z - max(inset.z, inset.w);
 
     // Bottom left corner.
     r.w = r.w - max(inset.x, inset.w);
@@ -120,6 +153,62 @@ fn sd_inset_rounded_box(point: vec2<f32>, size: vec2<f32>, radius: vec4<f32>, in
     return sd_rounded_box(inner_point, inner_size, r);
 }
 
+// Returns the side of the border the point belongs to: 0u for left, 1u for top, 2u for right and 3u
+// for bottom. The side is the closest one relative to its thickness, so that adjacent sides meet
+// along the diagonals of the corners.
+fn border_side(point: vec2<f32>, size: vec2<f32>, border: vec4<f32>) -> u32 {
+    let half_size = 0.5 * size;
+    let d = vec4(
+        point.x + half_size.x,
+        point.y + half_size.y,
+        half_size.x - point.x,
+        half_size.y - point.y,
+    ) / max(border, vec4(1e-6));
+    var side = 0u;
+    var closest = d.x;
+    if d.y < closest {
+        side = 1u;
+        closest = d.y;
+    }
+    if d.z < closest {
+        side = 2u;
+        closest = d.z;
+    }
+    if d.w < closest {
+        side = 3u;
+    }
+    return side;
+}
+
+// Returns the coverage of the border stroke at the point: 1.0 on solid parts, 0.0 in the gaps
+// between dashes or dots, and 0.0 on the sides that aren't drawn.
+fn stroke_coverage(in: VertexOutput) -> f32 {
+    let dotted = enabled(in.flags, DOTTED);
+    if !enabled(in.flags, BORDER_SIDES) && !dotted && in.stroke.y <= 0.0 {
+        return 1.0;
+    }
+
+    let side = border_side(in.point, in.size, in.border);
+    if enabled(in.flags, BORDER_SIDES) && !enabled(in.flags, BORDER_LEFT << side) {
+        return 0.0;
+    }
+
+    var dash = in.stroke.x;
+    var gap = in.stroke.y;
+    if dotted {
+        dash = in.border[side];
+        gap = dash;
+    }
+    if gap <= 0.0 || dash <= 0.0 {
+        return 1.0;
+    }
+
+    // Distance along the side, from its top or left end.
+    let along = select(in.point.x + 0.5 * in.size.x, in.point.y + 0.5 * in.size.y, side % 2u == 0u);
+    let m = along % (dash + gap);
+    return saturate(min(m, dash - m) + 0.5);
+}
+
 // get alpha for antialiasing for sdf
 fn antialias(distance: f32) -> f32 {
     // Using the fwidth(distance) was causing artifacts, so just use the distance.
@@ -127,7 +216,7 @@ fn antialias(distance: f32) -> f32 {
 }
 
 fn draw(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
-    // Only use the color sampled from the texture if the `TEXTURED` flag is enabled. 
+    // Only use the color sampled from the texture if the `TEXTURED` flag is enabled.
     // This allows us to draw both textured and untextured shapes together in the same batch.
     let color = select(in.color, in.color * texture_color, enabled(in.flags, TEXTURED));
 
@@ -139,21 +228,21 @@ fn draw(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     // Signed distance from the exterior boundary.
     let external_distance = sd_rounded_box(in.point, in.size, in.radius);
 
-    // Signed distance from the border's internal edge (the signed distance is negative if the point 
+    // Signed distance from the border's internal edge (the signed distance is negative if the point
     // is inside the rect but not on the border).
     // If the border size is set to zero, this is the same as as the external distance.
     let internal_distance = sd_inset_rounded_box(in.point, in.size, in.radius, in.border);
 
     // Signed distance from the border (the intersection of the rect with its border).
-    // Points inside the border have negative signed distance. Any point outside the border, whether 
+    // Points inside the border have negative signed distance. Any point outside the border, whether
     // outside the outside edge, or inside the inner edge have positive signed distance.
     let border_distance = max(external_distance, -internal_distance);
 
-    // At external edges with no border, `border_distance` is equal to zero. 
-    // This select statement ensures we only perform anti-aliasing where a non-zero width border 
-    // is present, otherwise an outline about the external boundary would be drawn even without 
+    // At external edges with no border, `border_distance` is equal to zero.
+    // This select statement ensures we only perform anti-aliasing where a non-zero width border
+    // is present, otherwise an outline about the external boundary would be drawn even without
     // a border.
-    let t = select(1.0 - step(0.0, border_distance), antialias(border_distance), external_distance < internal_distance);
+    let t = select(1.0 - step(0.0, border_distance), antialias(border_distance), external_distance < internal_distance) * stroke_coverage(in);
 
     // Blend mode ALPHA_BLENDING is used for UI elements, so we don't premultiply alpha here.
     return vec4(color.rgb, saturate(color.a * t));
@@ -168,12 +257,106 @@ fn draw_background(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     return vec4(color.rgb, saturate(color.a * t));
 }
 
+// Approximation of the error function, with a maximum error of 5e-4.
+fn erf(x: f32) -> f32 {
+    let s = sign(x);
+    let a = abs(x);
+    var r = 1.0 + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+    r = r * r;
+    return s - s / (r * r);
+}
+
+fn draw_shadow(in: VertexOutput) -> vec4<f32> {
+    let blur_radius = in.blur;
+
+    // The quad is larger than the shadow to cover its blurred edges.
+    let size = in.size - 2.0 * SHADOW_BLUR_EXTENT * blur_radius;
+    let distance = sd_rounded_box(in.point, size, in.radius);
+
+    // The edges are blurred with a gaussian whose standard deviation is half of the blur radius,
+    // as in CSS.
+    var t = antialias(distance);
+    if 0.0 < blur_radius {
+        let sigma = 0.5 * blur_radius;
+        t = 0.5 - 0.5 * erf(distance / (sigma * sqrt(2.0)));
+    }
+    return vec4(in.color.rgb, saturate(in.color.a * t));
+}
+
+// Draws a segment of a gradient, between two of its stops.
+fn draw_gradient(in: VertexOutput) -> vec4<f32> {
+    let t = select(in.gradient.x, length(in.gradient), enabled(in.flags, RADIAL));
+
+    // The other segments draw the rest of the gradient.
+    if t < in.stroke.x || in.stroke.y <= t {
+        return vec4(0.0);
+    }
+    let color = mix(in.color, in.end_color, saturate((t - in.stroke.x) / (in.stroke.y - in.stroke.x)));
+
+    // Like backgrounds, gradients are only drawn inside the border.
+    let internal_distance = sd_inset_rounded_box(in.point, in.size, in.radius, in.border);
+    return vec4(color.rgb, saturate(color.a * antialias(internal_distance)));
+}
+
+// Returns the coverage of the glyph `offset` logical pixels away from the fragment, which is zero
+// outside of the glyph so that its neighbors in the atlas aren't sampled.
+fn glyph_coverage(
+    in: VertexOutput,
+    offset: vec2<f32>,
+    glyph_half_size: vec2<f32>,
+    uv_per_point: vec2<f32>,
+) -> f32 {
+    let inside = all(abs(in.point + offset) <= glyph_half_size);
+    let uv = in.uv + offset * uv_per_point;
+    return select(0.0, textureSampleLevel(sprite_texture, sprite_sampler, uv, 0.0).a, inside);
+}
+
+// Draws the outline or the shadow of a glyph, whose node is the glyph grown by the width of the
+// outline or by the blur radius of the shadow.
+// `uv_per_point` is the size of a logical pixel in UV space.
+fn draw_glyph_effect(in: VertexOutput, uv_per_point: vec2<f32>) -> vec4<f32> {
+    let glyph_half_size = 0.5 * in.size - in.outline_width - in.blur;
+    var coverage = 0.0;
+    if 0.0 < in.outline_width {
+        // The outline is the glyph grown by its width: sample it in 8 directions, π / 4 apart.
+        coverage = glyph_coverage(in, vec2(0.0), glyph_half_size, uv_per_point);
+        for (var i = 0u; i < 8u; i += 1u) {
+            let angle = f32(i) * 0.7853982;
+            let offset = vec2(cos(angle), sin(angle)) * in.outline_width;
+            coverage = max(coverage, glyph_coverage(in, offset, glyph_half_size, uv_per_point));
+        }
+    } else {
+        // The blur of the shadow averages a grid of samples spaced by its radius.
+        for (var x = -1; x <= 1; x += 1) {
+            for (var y = -1; y <= 1; y += 1) {
+                let offset = vec2(f32(x), f32(y)) * in.blur;
+                coverage += glyph_coverage(in, offset, glyph_half_size, uv_per_point) / 9.0;
+            }
+        }
+    }
+    return vec4(in.color.rgb, saturate(in.color.a * coverage));
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
-
-    if enabled(in.flags, BORDER) {
-        return draw(in, texture_color);    
+    // Derivatives must be computed in uniform control flow. The UVs scale each axis of the node
+    // independently, so the scale is recovered per axis, even if the node is rotated.
+    let uv_dx = dpdx(in.uv);
+    let uv_dy = dpdy(in.uv);
+    let point_dx = dpdx(in.point);
+    let point_dy = dpdy(in.point);
+    let uv_per_point = (uv_dx * point_dx + uv_dy * point_dy)
+        / max(point_dx * point_dx + point_dy * point_dy, vec2(1e-12));
+
+    if enabled(in.flags, GLYPH_EFFECT) {
+        return draw_glyph_effect(in, uv_per_point);
+    } else if enabled(in.flags, SHADOW) {
+        return draw_shadow(in);
+    } else if enabled(in.flags, GRADIENT) {
+        return draw_gradient(in);
+    } else if enabled(in.flags, BORDER) {
+        return draw(in, texture_color);
     } else {
         return draw_background(in, texture_color);
     }
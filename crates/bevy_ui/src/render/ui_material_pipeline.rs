@@ -10,6 +10,7 @@ use bevy_ecs::{
 };
 use bevy_math::{FloatOrd, Mat4, Rect, Vec2, Vec4Swizzles};
 use bevy_render::{
+    camera::Camera,
     extract_component::ExtractComponentPlugin,
     globals::{GlobalsBuffer, GlobalsUniform},
     render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
@@ -21,7 +22,6 @@ use bevy_render::{
     Extract, ExtractSchedule, Render, RenderSet,
 };
 use bevy_transform::prelude::GlobalTransform;
-use bevy_window::{PrimaryWindow, Window};
 use bytemuck::{Pod, Zeroable};
 
 use crate::*;
@@ -352,6 +352,7 @@ impl<M: UiMaterial> Default for ExtractedUiMaterialNodes<M> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_ui_material_nodes<M: UiMaterial>(
     mut extracted_uinodes: ResMut<ExtractedUiMaterialNodes<M>>,
     materials: Extract<Res<Assets<M>>>,
@@ -372,17 +373,9 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
             Without<BackgroundColor>,
         >,
     >,
-    windows: Extract<Query<&Window, With<PrimaryWindow>>>,
+    camera_query: Extract<Query<&Camera>>,
     ui_scale: Extract<Res<UiScale>>,
 ) {
-    let ui_logical_viewport_size = windows
-        .get_single()
-        .map(|window| window.size())
-        .unwrap_or(Vec2::ZERO)
-        // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
-        // so we have to divide by `UiScale` to get the size of the UI viewport.
-        / ui_scale.0;
-
     // If there is only one camera, we use it as default
     let default_single_camera = default_ui_camera.get();
 
@@ -405,24 +398,42 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
                 continue;
             }
 
+            let ui_logical_viewport_size = camera_query
+                .get(camera_entity)
+                .ok()
+                .and_then(Camera::logical_viewport_size)
+                .unwrap_or(Vec2::ZERO)
+                // The logical viewport size of the camera only takes into account the scale factor of its target and not `UiScale`,
+                // so we have to divide by `UiScale` to get the size of the UI viewport.
+                / ui_scale.0;
+
             // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
             // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
             let parent_width = uinode.size().x;
-            let left =
-                resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size)
-                    / uinode.size().x;
+            let border_sides = uinode.flip_sides(style.border);
+            let left = resolve_border_thickness(
+                border_sides.left,
+                parent_width,
+                ui_logical_viewport_size,
+                uinode.root_scale(),
+            ) / uinode.size().x;
             let right = resolve_border_thickness(
-                style.border.right,
+                border_sides.right,
                 parent_width,
                 ui_logical_viewport_size,
+                uinode.root_scale(),
             ) / uinode.size().x;
-            let top =
-                resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size)
-                    / uinode.size().y;
+            let top = resolve_border_thickness(
+                border_sides.top,
+                parent_width,
+                ui_logical_viewport_size,
+                uinode.root_scale(),
+            ) / uinode.size().y;
             let bottom = resolve_border_thickness(
-                style.border.bottom,
+                border_sides.bottom,
                 parent_width,
                 ui_logical_viewport_size,
+                uinode.root_scale(),
             ) / uinode.size().y;
 
             extracted_uinodes.uinodes.insert(
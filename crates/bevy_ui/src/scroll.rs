@@ -0,0 +1,211 @@
+//! This module contains [`update_scroll_position`], which scrolls the nodes with
+//! [`OverflowAxis::Scroll`](crate::OverflowAxis::Scroll) under the pointers.
+
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+use bevy_input::{
+    mouse::{MouseButton, MouseScrollUnit, MouseWheel},
+    touch::Touches,
+    ButtonInput,
+};
+use bevy_math::Vec2;
+
+use crate::{DragMap, HoverMap, Interaction, PointerId, ScrollPosition, Style, UiScale, UiStack};
+
+/// The distance scrolled by each line of a [`MouseScrollUnit::Line`] mouse wheel event, in
+/// logical pixels.
+pub const SCROLL_LINE_HEIGHT: f32 = 20.;
+
+/// Scrolls the nodes under the pointers by updating their [`ScrollPosition`], which is inserted
+/// on the nodes without one the first time they are scrolled.
+///
+/// The mouse wheel scrolls the nearest scrolling ancestor of the topmost node under the cursor,
+/// starting with the node itself, and touches or the mouse moving with its left button pressed
+/// drag the content of the one under them. A node scrolling only horizontally is scrolled by the
+/// vertical wheel too. Pointers dragging a [`Draggable`](crate::Draggable) node don't scroll.
+///
+/// The offsets are kept within the scrollable distance by the layout, see [`ScrollPosition`].
+#[allow(clippy::too_many_arguments)]
+pub fn update_scroll_position(
+    mut commands: Commands,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches_input: Res<Touches>,
+    hover_map: Res<HoverMap>,
+    drag_map: Res<DragMap>,
+    ui_stack: Res<UiStack>,
+    ui_scale: Res<UiScale>,
+    // The camera and the position of the cursor in its viewport while the left button is pressed
+    mut mouse_drag_position: Local<Option<(Entity, Vec2)>>,
+    parent_query: Query<&Parent>,
+    mut scroll_query: Query<(Option<&mut ScrollPosition>, &Style)>,
+) {
+    let mut mouse_delta = mouse_wheel_events
+        .read()
+        .map(|event| {
+            let delta = Vec2::new(event.x, event.y);
+            match event.unit {
+                MouseScrollUnit::Line => delta * SCROLL_LINE_HEIGHT,
+                MouseScrollUnit::Pixel => delta,
+            }
+        })
+        .sum::<Vec2>();
+
+    let last_mouse_drag_position = mouse_drag_position.take();
+    if mouse_button_input.pressed(MouseButton::Left) {
+        *mouse_drag_position = match last_mouse_drag_position {
+            Some((camera, _)) => hover_map
+                .pointer_position(PointerId::Mouse, camera)
+                .map(|position| (camera, position)),
+            None => hover_map.pointer_camera_positions(PointerId::Mouse).next(),
+        };
+        if let (Some((_, last_position)), Some((_, position))) =
+            (last_mouse_drag_position, *mouse_drag_position)
+        {
+            if !drag_map.has_pointer(PointerId::Mouse) {
+                mouse_delta += position - last_position;
+            }
+        }
+    }
+
+    let mut scrolls = Vec::new();
+    if mouse_delta != Vec2::ZERO {
+        scrolls.push((PointerId::Mouse, mouse_delta));
+    }
+    for touch in touches_input.iter() {
+        let pointer = PointerId::Touch(touch.id());
+        if touch.delta() != Vec2::ZERO && !drag_map.has_pointer(pointer) {
+            scrolls.push((pointer, touch.delta()));
+        }
+    }
+
+    for (pointer, delta) in scrolls {
+        // The topmost node under the pointer
+        let Some(&target) = ui_stack
+            .uinodes
+            .iter()
+            .rev()
+            .find(|&&entity| hover_map.get(pointer, entity) != Interaction::None)
+        else {
+            continue;
+        };
+
+        let mut entity = Some(target);
+        while let Some(current) = entity {
+            if let Ok((scroll_position, style)) = scroll_query.get_mut(current) {
+                if style.overflow.is_scroll() {
+                    // Moving the wheel or the pointer down brings the content down, towards its
+                    // start
+                    let mut offset = -delta / ui_scale.0;
+                    if !style.overflow.y.is_scroll() && offset.x == 0. {
+                        offset.x = offset.y;
+                    }
+                    match scroll_position {
+                        Some(mut scroll_position) => {
+                            scroll_position.offset =
+                                (scroll_position.offset + offset).max(Vec2::ZERO);
+                        }
+                        None => {
+                            commands
+                                .entity(current)
+                                .insert(ScrollPosition::new(offset.max(Vec2::ZERO)));
+                        }
+                    }
+                    break;
+                }
+            }
+            entity = parent_query.get(current).ok().map(Parent::get);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, prelude::*, schedule::Schedule};
+    use bevy_input::{
+        mouse::{MouseButton, MouseWheel},
+        touch::Touches,
+        ButtonInput,
+    };
+    use bevy_math::Vec2;
+
+    use crate::{
+        DragMap, HoverMap, Interaction, Overflow, PointerId, ScrollPosition, Style, UiScale,
+        UiStack,
+    };
+
+    use super::update_scroll_position;
+
+    fn move_mouse(world: &mut World, camera: Entity, position: Vec2) {
+        world
+            .resource_mut::<HoverMap>()
+            .positions
+            .entry(PointerId::Mouse)
+            .or_default()
+            .insert(camera, position);
+    }
+
+    #[test]
+    fn dragging_the_mouse_scrolls_nodes_without_a_scroll_position() {
+        let mut world = World::default();
+        world.init_resource::<Events<MouseWheel>>();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        world.init_resource::<Touches>();
+        world.init_resource::<HoverMap>();
+        world.init_resource::<DragMap>();
+        world.init_resource::<UiScale>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_scroll_position);
+
+        let camera = world.spawn_empty().id();
+        let list = world
+            .spawn(Style {
+                overflow: Overflow::scroll_y(),
+                ..Default::default()
+            })
+            .id();
+        world.insert_resource(UiStack {
+            uinodes: vec![list],
+        });
+        world
+            .resource_mut::<HoverMap>()
+            .pointers
+            .entry(PointerId::Mouse)
+            .or_default()
+            .insert(list, Interaction::Pressed);
+
+        // The content follows the cursor once the button is pressed
+        move_mouse(&mut world, camera, Vec2::new(10., 50.));
+        schedule.run(&mut world);
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        schedule.run(&mut world);
+        assert!(world.get::<ScrollPosition>(list).is_none());
+
+        move_mouse(&mut world, camera, Vec2::new(15., 30.));
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<ScrollPosition>(list),
+            Some(&ScrollPosition::new(Vec2::new(0., 20.)))
+        );
+
+        move_mouse(&mut world, camera, Vec2::new(15., 20.));
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<ScrollPosition>(list).unwrap().offset,
+            Vec2::new(0., 30.)
+        );
+
+        // Releasing the button stops the scroll
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        move_mouse(&mut world, camera, Vec2::new(15., 0.));
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<ScrollPosition>(list).unwrap().offset,
+            Vec2::new(0., 30.)
+        );
+    }
+}
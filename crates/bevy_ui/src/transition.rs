@@ -0,0 +1,507 @@
+//! This module contains [`UiTransition`], which tweens style properties of UI nodes over time.
+
+use std::time::Duration;
+
+use bevy_color::{Color, Mix};
+use bevy_ecs::prelude::*;
+use bevy_math::{cubic_splines::CubicSegment, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+
+use crate::{BackgroundColor, BorderRadius, Interaction, Style, UiRect, Val};
+
+/// An easing curve, mapping the linear progress of a [`UiTransition`] to the progress of the
+/// tweened values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum EaseFunction {
+    /// The values change at a constant rate.
+    Linear,
+    /// The values change slowly at the start, then quickly.
+    QuadraticIn,
+    /// The values change quickly at the start, then slowly.
+    QuadraticOut,
+    /// The values change slowly at both ends.
+    #[default]
+    QuadraticInOut,
+    /// The values change very slowly at the start, then very quickly.
+    CubicIn,
+    /// The values change very quickly at the start, then very slowly.
+    CubicOut,
+    /// The values change very slowly at both ends.
+    CubicInOut,
+    /// A cubic Bézier curve from `(0, 0)` to `(1, 1)` with the two given control points, like the
+    /// `cubic-bezier()` easing function of CSS.
+    CubicBezier(Vec2, Vec2),
+}
+
+impl EaseFunction {
+    /// Returns the eased progress at `t`, between `0.0` at the start of the transition and `1.0`
+    /// at its end.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EaseFunction::Linear => t,
+            EaseFunction::QuadraticIn => t * t,
+            EaseFunction::QuadraticOut => t * (2.0 - t),
+            EaseFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            EaseFunction::CubicIn => t * t * t,
+            EaseFunction::CubicOut => 1.0 - (1.0 - t).powi(3),
+            EaseFunction::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            EaseFunction::CubicBezier(p1, p2) => CubicSegment::new_bezier(p1, p2).ease(t),
+        }
+    }
+}
+
+/// The values of the style properties tweened by a [`UiTransition`].
+///
+/// The properties which are `None` aren't changed by the transition.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct TransitionStyle {
+    /// The [`Style::width`] of the node.
+    pub width: Option<Val>,
+    /// The [`Style::height`] of the node.
+    pub height: Option<Val>,
+    /// The [`Style::margin`] of the node.
+    pub margin: Option<UiRect>,
+    /// The [`BackgroundColor`] of the node.
+    pub background_color: Option<Color>,
+    /// The [`BorderRadius`] of the node, which is only tweened if the node has one.
+    pub border_radius: Option<BorderRadius>,
+}
+
+impl TransitionStyle {
+    /// Returns this style with the given [`width`](Self::width).
+    pub const fn with_width(mut self, width: Val) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Returns this style with the given [`height`](Self::height).
+    pub const fn with_height(mut self, height: Val) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Returns this style with the given [`margin`](Self::margin).
+    pub const fn with_margin(mut self, margin: UiRect) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    /// Returns this style with the given [`background_color`](Self::background_color).
+    pub fn with_background_color(mut self, color: impl Into<Color>) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    /// Returns this style with the given [`border_radius`](Self::border_radius).
+    pub const fn with_border_radius(mut self, border_radius: BorderRadius) -> Self {
+        self.border_radius = Some(border_radius);
+        self
+    }
+
+    /// Returns `true` if this style doesn't change any property.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns this style, with the properties it doesn't set taken from `other`.
+    fn or(&self, other: &Self) -> Self {
+        Self {
+            width: self.width.or(other.width),
+            height: self.height.or(other.height),
+            margin: self.margin.or(other.margin),
+            background_color: self.background_color.or(other.background_color),
+            border_radius: self.border_radius.or(other.border_radius),
+        }
+    }
+
+    /// Returns the current values of the properties set in `target`.
+    fn current(
+        target: &Self,
+        style: &Style,
+        background_color: Option<&BackgroundColor>,
+        border_radius: Option<&BorderRadius>,
+    ) -> Self {
+        Self {
+            width: target.width.map(|_| style.width),
+            height: target.height.map(|_| style.height),
+            margin: target.margin.map(|_| style.margin),
+            background_color: target
+                .background_color
+                .and(background_color.map(|color| color.0)),
+            border_radius: target.border_radius.and(border_radius.copied()),
+        }
+    }
+
+    /// Returns the values at the eased progress `t` from `self` to `target`.
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        fn lerp<T>(start: Option<T>, end: Option<T>, f: impl Fn(T, T) -> T) -> Option<T> {
+            match (start, end) {
+                (Some(start), Some(end)) => Some(f(start, end)),
+                (_, end) => end,
+            }
+        }
+        Self {
+            width: lerp(self.width, target.width, |a, b| lerp_val(a, b, t)),
+            height: lerp(self.height, target.height, |a, b| lerp_val(a, b, t)),
+            margin: lerp(self.margin, target.margin, |a, b| UiRect {
+                left: lerp_val(a.left, b.left, t),
+                right: lerp_val(a.right, b.right, t),
+                top: lerp_val(a.top, b.top, t),
+                bottom: lerp_val(a.bottom, b.bottom, t),
+            }),
+            background_color: lerp(self.background_color, target.background_color, |a, b| {
+                a.mix(&b, t)
+            }),
+            border_radius: lerp(self.border_radius, target.border_radius, |a, b| {
+                BorderRadius {
+                    top_left: lerp_val(a.top_left, b.top_left, t),
+                    top_right: lerp_val(a.top_right, b.top_right, t),
+                    bottom_left: lerp_val(a.bottom_left, b.bottom_left, t),
+                    bottom_right: lerp_val(a.bottom_right, b.bottom_right, t),
+                }
+            }),
+        }
+    }
+}
+
+/// Interpolates two [`Val`]s of the same unit, or switches from `start` to `end` at the end of
+/// the transition if their units differ.
+fn lerp_val(start: Val, end: Val, t: f32) -> Val {
+    match (start, end) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(a + (b - a) * t),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(a + (b - a) * t),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(a + (b - a) * t),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(a + (b - a) * t),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(a + (b - a) * t),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(a + (b - a) * t),
+        _ if t < 1.0 => start,
+        _ => end,
+    }
+}
+
+/// Tweens the size, margins, [`BackgroundColor`] and [`BorderRadius`] of a UI node over time,
+/// from their current values to a [`TransitionStyle`].
+///
+/// A transition is started when the [`Interaction`] of the node changes, to the style of the new
+/// state, or with [`UiTransition::transition_to`]. Starting a transition while another one is
+/// running continues from the current values, so that the node never jumps.
+///
+/// The values of the node when its [`Interaction`] is first seen are its implicit normal style:
+/// the properties tweened by the hovered or pressed styles which aren't set by the
+/// [`normal`](UiTransition::normal) style transition back to these values.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_color::palettes::basic::{BLUE, NAVY};
+/// # use bevy_ui::{node_bundles::ButtonBundle, BackgroundColor, TransitionStyle, UiTransition};
+/// fn spawn_button(mut commands: Commands) {
+///     commands.spawn((
+///         ButtonBundle {
+///             background_color: BackgroundColor(NAVY.into()),
+///             ..Default::default()
+///         },
+///         UiTransition::new(Duration::from_millis(150))
+///             .with_normal(TransitionStyle::default().with_background_color(NAVY))
+///             .with_hovered(TransitionStyle::default().with_background_color(BLUE)),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct UiTransition {
+    /// The duration of the transitions.
+    pub duration: Duration,
+    /// The easing curve of the transitions.
+    pub easing: EaseFunction,
+    /// The style transitioned to when the [`Interaction`] of the node becomes
+    /// [`Interaction::None`], on top of the initial values of the node.
+    pub normal: TransitionStyle,
+    /// The style transitioned to when the node is hovered, or [`normal`](Self::normal) if it
+    /// is empty.
+    pub hovered: TransitionStyle,
+    /// The style transitioned to when the node is pressed, or [`hovered`](Self::hovered) if it
+    /// is empty.
+    pub pressed: TransitionStyle,
+    /// The values of the properties of the node before its first interaction, or `None` if it
+    /// hasn't been interacted with yet.
+    initial: Option<TransitionStyle>,
+    /// The values of the properties when the current transition started, which are read from
+    /// the node when it is `None`.
+    start: Option<TransitionStyle>,
+    target: TransitionStyle,
+    /// The time elapsed since the current transition started, or `None` if it is finished.
+    elapsed: Option<Duration>,
+}
+
+impl UiTransition {
+    /// Creates transitions lasting `duration`, with [`EaseFunction::QuadraticInOut`].
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: EaseFunction::default(),
+            normal: TransitionStyle::default(),
+            hovered: TransitionStyle::default(),
+            pressed: TransitionStyle::default(),
+            initial: None,
+            start: None,
+            target: TransitionStyle::default(),
+            elapsed: None,
+        }
+    }
+
+    /// Returns these transitions with the given `easing`.
+    pub fn with_easing(mut self, easing: EaseFunction) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns these transitions with the given [`normal`](Self::normal) style.
+    pub fn with_normal(mut self, style: TransitionStyle) -> Self {
+        self.normal = style;
+        self
+    }
+
+    /// Returns these transitions with the given [`hovered`](Self::hovered) style.
+    pub fn with_hovered(mut self, style: TransitionStyle) -> Self {
+        self.hovered = style;
+        self
+    }
+
+    /// Returns these transitions with the given [`pressed`](Self::pressed) style.
+    pub fn with_pressed(mut self, style: TransitionStyle) -> Self {
+        self.pressed = style;
+        self
+    }
+
+    /// Starts a transition from the current values of the node to `target`.
+    pub fn transition_to(&mut self, target: TransitionStyle) {
+        self.start = None;
+        self.target = target;
+        self.elapsed = Some(Duration::ZERO);
+    }
+
+    /// Returns `true` if a transition is running.
+    pub fn is_running(&self) -> bool {
+        self.elapsed.is_some()
+    }
+
+    /// Returns the style transitioned to for the given `interaction`.
+    pub fn style_for(&self, interaction: Interaction) -> &TransitionStyle {
+        let hovered = if self.hovered.is_empty() {
+            &self.normal
+        } else {
+            &self.hovered
+        };
+        match interaction {
+            Interaction::None => &self.normal,
+            Interaction::Hovered => hovered,
+            Interaction::Pressed if self.pressed.is_empty() => hovered,
+            Interaction::Pressed => &self.pressed,
+        }
+    }
+}
+
+/// Starts the [`UiTransition`]s of the nodes whose [`Interaction`] changed.
+///
+/// The values of the node are captured the first time its [`Interaction`] changes, so that the
+/// properties which aren't set by the [`normal`](UiTransition::normal) style are transitioned
+/// back to them.
+pub fn start_interaction_transitions(
+    mut transitions: Query<
+        (
+            &Interaction,
+            &mut UiTransition,
+            &Style,
+            Option<&BackgroundColor>,
+            Option<&BorderRadius>,
+        ),
+        Changed<Interaction>,
+    >,
+) {
+    for (&interaction, mut transition, style, background_color, border_radius) in &mut transitions {
+        let transition = &mut *transition;
+        let initial = transition
+            .initial
+            .get_or_insert_with(|| {
+                let properties = transition
+                    .normal
+                    .or(&transition.hovered)
+                    .or(&transition.pressed);
+                TransitionStyle::current(&properties, style, background_color, border_radius)
+            })
+            .clone();
+        let target = transition
+            .style_for(interaction)
+            .or(&transition.normal)
+            .or(&initial);
+        if !target.is_empty() && target != transition.target {
+            transition.transition_to(target);
+        }
+    }
+}
+
+/// Advances the running [`UiTransition`]s, and writes the tweened values to the nodes.
+pub fn update_ui_transitions(
+    time: Res<Time>,
+    mut transitions: Query<(
+        &mut UiTransition,
+        &mut Style,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderRadius>,
+    )>,
+) {
+    for (mut transition, mut style, mut background_color, mut border_radius) in &mut transitions {
+        let Some(elapsed) = transition.elapsed else {
+            continue;
+        };
+        let transition = &mut *transition;
+        let start = transition.start.get_or_insert_with(|| {
+            TransitionStyle::current(
+                &transition.target,
+                &style,
+                background_color.as_deref(),
+                border_radius.as_deref(),
+            )
+        });
+
+        let elapsed = elapsed + time.delta();
+        let progress = if elapsed >= transition.duration {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / transition.duration.as_secs_f32()
+        };
+        let values = start.interpolate(&transition.target, transition.easing.ease(progress));
+        transition.elapsed = (progress < 1.0).then_some(elapsed);
+
+        if let Some(width) = values.width {
+            style.width = width;
+        }
+        if let Some(height) = values.height {
+            style.height = height;
+        }
+        if let Some(margin) = values.margin {
+            style.margin = margin;
+        }
+        if let (Some(color), Some(background_color)) =
+            (values.background_color, background_color.as_mut())
+        {
+            background_color.0 = color;
+        }
+        if let (Some(radius), Some(border_radius)) = (values.border_radius, border_radius.as_mut())
+        {
+            **border_radius = radius;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_color::Color;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_time::Time;
+
+    use super::{
+        lerp_val, start_interaction_transitions, update_ui_transitions, EaseFunction,
+        TransitionStyle, UiTransition,
+    };
+    use crate::{Interaction, Style, Val};
+
+    #[test]
+    fn ease_functions_end_at_bounds() {
+        for easing in [
+            EaseFunction::Linear,
+            EaseFunction::QuadraticIn,
+            EaseFunction::QuadraticOut,
+            EaseFunction::QuadraticInOut,
+            EaseFunction::CubicIn,
+            EaseFunction::CubicOut,
+            EaseFunction::CubicInOut,
+        ] {
+            assert_eq!(easing.ease(0.0), 0.0);
+            assert_eq!(easing.ease(1.0), 1.0);
+        }
+        assert_eq!(EaseFunction::QuadraticInOut.ease(0.5), 0.5);
+        assert_eq!(EaseFunction::CubicIn.ease(0.5), 0.125);
+    }
+
+    #[test]
+    fn transition_style_interpolation() {
+        assert_eq!(lerp_val(Val::Px(10.), Val::Px(20.), 0.5), Val::Px(15.));
+        assert_eq!(lerp_val(Val::Auto, Val::Px(20.), 0.5), Val::Auto);
+        assert_eq!(lerp_val(Val::Auto, Val::Px(20.), 1.0), Val::Px(20.));
+
+        let start = TransitionStyle::default()
+            .with_width(Val::Percent(0.))
+            .with_background_color(Color::BLACK);
+        let target = start
+            .clone()
+            .with_width(Val::Percent(100.))
+            .with_height(Val::Px(50.));
+        let values = start.interpolate(&target, 0.25);
+        assert_eq!(values.width, Some(Val::Percent(25.)));
+        // The properties without a start value are set immediately
+        assert_eq!(values.height, Some(Val::Px(50.)));
+        assert_eq!(values.background_color, Some(Color::BLACK));
+        assert_eq!(values.margin, None);
+    }
+
+    #[test]
+    fn interrupted_transitions_return_to_the_initial_values() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((start_interaction_transitions, update_ui_transitions).chain());
+
+        let entity = world
+            .spawn((
+                Interaction::None,
+                Style {
+                    width: Val::Px(100.),
+                    ..Default::default()
+                },
+                UiTransition::new(Duration::from_secs(1))
+                    .with_easing(EaseFunction::Linear)
+                    .with_hovered(TransitionStyle::default().with_width(Val::Px(200.))),
+            ))
+            .id();
+        let mut advance = |world: &mut World, millis| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            schedule.run(world);
+            world.get::<Style>(entity).unwrap().width
+        };
+        assert_eq!(advance(&mut world, 0), Val::Px(100.));
+
+        *world.get_mut::<Interaction>(entity).unwrap() = Interaction::Hovered;
+        assert_eq!(advance(&mut world, 0), Val::Px(100.));
+        assert_eq!(advance(&mut world, 500), Val::Px(150.));
+
+        // The hover is interrupted halfway, and the empty normal style goes back to the initial
+        // width from the current one
+        *world.get_mut::<Interaction>(entity).unwrap() = Interaction::None;
+        assert_eq!(advance(&mut world, 0), Val::Px(150.));
+        assert_eq!(advance(&mut world, 500), Val::Px(125.));
+        assert_eq!(advance(&mut world, 500), Val::Px(100.));
+        assert!(!world.get::<UiTransition>(entity).unwrap().is_running());
+    }
+}
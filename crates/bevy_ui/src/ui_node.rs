@@ -8,6 +8,7 @@ use bevy_render::{
     camera::{Camera, RenderTarget},
     texture::Image,
 };
+use bevy_sprite::TextureSlicer;
 use bevy_transform::prelude::GlobalTransform;
 use bevy_utils::warn_once;
 use bevy_window::{PrimaryWindow, WindowRef};
@@ -44,6 +45,15 @@ pub struct Node {
     ///
     /// Automatically calculated by [`super::layout::ui_layout_system`].
     pub(crate) unrounded_size: Vec2,
+    /// The [`UiRootScale`] of the root node this node belongs to, multiplied by the
+    /// [`UiScaleMode::root_scale`](crate::UiScaleMode::root_scale) of its target.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) root_scale: f32,
+    /// The resolved [`Direction`] of the node, which is never [`Direction::Inherit`].
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) direction: Direction,
 }
 
 impl Node {
@@ -67,6 +77,35 @@ impl Node {
         self.unrounded_size
     }
 
+    /// The [`UiRootScale`] of the root node this node belongs to, or `1.0` if it has none,
+    /// multiplied by the [`UiScaleMode::root_scale`](crate::UiScaleMode::root_scale) of its target.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn root_scale(&self) -> f32 {
+        self.root_scale
+    }
+
+    /// The direction the node is laid out in, resolved from the [`Style::direction`] of the node
+    /// and its ancestors. Root nodes inheriting their direction are laid out left to right.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Swaps the left and right sides of `rect` if the node is laid out right to left, where they
+    /// are its start and end sides.
+    pub(crate) fn flip_sides(&self, rect: UiRect) -> UiRect {
+        match self.direction {
+            Direction::RightToLeft => UiRect {
+                left: rect.right,
+                right: rect.left,
+                ..rect
+            },
+            _ => rect,
+        }
+    }
+
     /// Returns the size of the node in physical pixels based on the given scale factor and `UiScale`.
     #[inline]
     pub fn physical_size(&self, scale_factor: f32, ui_scale: f32) -> Vec2 {
@@ -118,6 +157,8 @@ impl Node {
         outline_width: 0.,
         outline_offset: 0.,
         unrounded_size: Vec2::ZERO,
+        root_scale: 1.,
+        direction: Direction::LeftToRight,
     };
 }
 
@@ -175,7 +216,11 @@ pub struct Style {
 
     /// Defines the text direction. For example, English is written LTR (left-to-right) while Arabic is written RTL (right-to-left).
     ///
-    /// Note: the corresponding CSS property also affects box layout order, but this isn't yet implemented in Bevy.
+    /// The children of a right to left node are laid out from its right edge: the horizontal main
+    /// axis of flex layouts and the columns of grids are reversed, and the left and right sides of
+    /// the `margin`, `padding`, `border` and `left`/`right` of its subtree are its start and end
+    /// sides. The left and right `JustifyText` of its text nodes are swapped
+    /// the same way.
     ///
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/direction>
     pub direction: Direction,
@@ -875,10 +920,39 @@ impl Overflow {
         }
     }
 
+    /// Scroll overflowing items on both axes, see [`ScrollPosition`]
+    pub const fn scroll() -> Self {
+        Self {
+            x: OverflowAxis::Scroll,
+            y: OverflowAxis::Scroll,
+        }
+    }
+
+    /// Scroll overflowing items on the x axis, see [`ScrollPosition`]
+    pub const fn scroll_x() -> Self {
+        Self {
+            x: OverflowAxis::Scroll,
+            y: OverflowAxis::Visible,
+        }
+    }
+
+    /// Scroll overflowing items on the y axis, see [`ScrollPosition`]
+    pub const fn scroll_y() -> Self {
+        Self {
+            x: OverflowAxis::Visible,
+            y: OverflowAxis::Scroll,
+        }
+    }
+
     /// Overflow is visible on both axes
     pub const fn is_visible(&self) -> bool {
         self.x.is_visible() && self.y.is_visible()
     }
+
+    /// Overflowing items can be scrolled on at least one axis
+    pub const fn is_scroll(&self) -> bool {
+        self.x.is_scroll() || self.y.is_scroll()
+    }
 }
 
 impl Default for Overflow {
@@ -902,6 +976,9 @@ pub enum OverflowAxis {
     Clip,
     /// Hide overflowing items by influencing layout and then clipping.
     Hidden,
+    /// Hide overflowing items by clipping, and offset them by the [`ScrollPosition`] of the node
+    /// to scroll through them.
+    Scroll,
 }
 
 impl OverflowAxis {
@@ -911,6 +988,11 @@ impl OverflowAxis {
     pub const fn is_visible(&self) -> bool {
         matches!(self, Self::Visible)
     }
+
+    /// Overflowing items can be scrolled on this axis
+    pub const fn is_scroll(&self) -> bool {
+        matches!(self, Self::Scroll)
+    }
 }
 
 impl Default for OverflowAxis {
@@ -919,6 +1001,37 @@ impl Default for OverflowAxis {
     }
 }
 
+/// How far the children of a node with [`OverflowAxis::Scroll`] are scrolled, in logical pixels.
+///
+/// The children are moved up and to the left by the offset, and clipped to the node. The layout
+/// keeps the offset between zero, and the distance by which the children overflow the node along
+/// each scrolling axis. On the other axes, the offset is ignored.
+///
+/// The mouse wheel scrolls the node under the cursor, and touches or the mouse with its left button
+/// pressed scroll the node they drag, see [`update_scroll_position`](crate::update_scroll_position).
+/// Setting the offset scrolls the node programmatically, for example to keep the selected item of a
+/// list in view.
+///
+/// This is part of the [`NodeBundle`](crate::node_bundles::NodeBundle), and inserted on the other
+/// nodes the first time they are scrolled.
+///
+/// The nodes are picked where they are scrolled to, and the parts of the children clipped by the
+/// node can't be picked.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ScrollPosition {
+    /// The horizontal and vertical offset of the children. `(0, 0)` shows the start of the
+    /// children.
+    pub offset: Vec2,
+}
+
+impl ScrollPosition {
+    /// Creates a scroll position with the given offset.
+    pub const fn new(offset: Vec2) -> Self {
+        Self { offset }
+    }
+}
+
 /// The strategy used to position this node
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect)]
 #[reflect(Default, PartialEq)]
@@ -1708,6 +1821,186 @@ impl<T: Into<Color>> From<T> for BackgroundColor {
     }
 }
 
+/// A color of a [`BackgroundGradient`], at a position along the gradient.
+#[derive(Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ColorStop {
+    /// The color at the stop.
+    pub color: Color,
+    /// The position of the stop along the gradient, from `0.0` at its start to `1.0` at its end.
+    ///
+    /// If `None`, the stop is placed halfway between the neighbouring stops, or at the start or
+    /// the end of the gradient if it is the first or the last stop. Positions before the position
+    /// of a previous stop are moved to the position of that stop.
+    pub position: Option<f32>,
+}
+
+impl ColorStop {
+    /// Creates a stop of `color` at `position`, from `0.0` to `1.0` along the gradient.
+    pub const fn new(color: Color, position: f32) -> Self {
+        Self {
+            color,
+            position: Some(position),
+        }
+    }
+
+    /// Creates a stop of `color` placed between the neighbouring stops.
+    pub const fn auto(color: Color) -> Self {
+        Self {
+            color,
+            position: None,
+        }
+    }
+}
+
+impl<T: Into<Color>> From<T> for ColorStop {
+    fn from(color: T) -> Self {
+        Self::auto(color.into())
+    }
+}
+
+/// A gradient drawn as the background of the node, instead of its [`BackgroundColor`].
+///
+/// The gradient is rounded by the [`BorderRadius`] of the node and clipped like its background.
+/// The colors are interpolated in linear RGB.
+///
+/// ```
+/// # use std::f32::consts::FRAC_PI_2;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_color::palettes::basic::{BLUE, RED, YELLOW};
+/// fn setup_ui(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle {
+///             style: Style {
+///                 width: Val::Px(200.),
+///                 height: Val::Px(100.),
+///                 ..Default::default()
+///             },
+///             ..Default::default()
+///         },
+///         // From red on the left to blue on the right, through yellow at a quarter of the width
+///         BackgroundGradient::linear(
+///             FRAC_PI_2,
+///             [
+///                 ColorStop::from(RED),
+///                 ColorStop::new(YELLOW.into(), 0.25),
+///                 ColorStop::from(BLUE),
+///             ],
+///         ),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum BackgroundGradient {
+    /// The colors change along a line through the center of the node, whose length is such that
+    /// the corners of the node get the colors of the start and the end of the gradient.
+    Linear {
+        /// The direction of the gradient line in radians, clockwise from the top of the node:
+        /// `0.0` goes from the bottom to the top and `FRAC_PI_2` from the left to the right.
+        angle: f32,
+        /// The colors of the gradient.
+        stops: Vec<ColorStop>,
+    },
+    /// The colors change along ellipses around a center, with the aspect ratio of the node. The
+    /// end of the gradient is the ellipse reaching the corner the farthest from the center.
+    Radial {
+        /// The center of the gradient, relative to the node: `(0.0, 0.0)` is its top left corner
+        /// and `(1.0, 1.0)` its bottom right corner.
+        center: Vec2,
+        /// The colors of the gradient.
+        stops: Vec<ColorStop>,
+    },
+}
+
+impl BackgroundGradient {
+    /// Creates a linear gradient in the direction of `angle`, in radians clockwise from the top
+    /// of the node.
+    pub fn linear(angle: f32, stops: impl IntoIterator<Item = impl Into<ColorStop>>) -> Self {
+        Self::Linear {
+            angle,
+            stops: stops.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Creates a radial gradient around `center`, relative to the node.
+    pub fn radial(center: Vec2, stops: impl IntoIterator<Item = impl Into<ColorStop>>) -> Self {
+        Self::Radial {
+            center,
+            stops: stops.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the colors of the gradient.
+    pub fn stops(&self) -> &[ColorStop] {
+        match self {
+            Self::Linear { stops, .. } | Self::Radial { stops, .. } => stops,
+        }
+    }
+
+    /// Returns the colors of the gradient with their resolved positions, in increasing order.
+    pub fn resolved_stops(&self) -> Vec<(Color, f32)> {
+        let stops = self.stops();
+        let mut positions: Vec<Option<f32>> = stops.iter().map(|stop| stop.position).collect();
+        if let Some(first) = positions.first_mut() {
+            first.get_or_insert(0.);
+        }
+        if let Some(last) = positions.last_mut() {
+            last.get_or_insert(1.);
+        }
+
+        // Stops can't be placed before the previous ones
+        let mut max = f32::NEG_INFINITY;
+        for position in positions.iter_mut().flatten() {
+            max = max.max(*position);
+            *position = max;
+        }
+
+        // Spread the stops without position evenly between the surrounding ones
+        let mut resolved = Vec::with_capacity(stops.len());
+        let mut previous = 0;
+        for (index, stop) in stops.iter().enumerate() {
+            let position = match positions[index] {
+                Some(position) => {
+                    previous = index;
+                    position
+                }
+                None => {
+                    let start = positions[previous].unwrap_or(0.);
+                    let (next, end) = positions[index..]
+                        .iter()
+                        .enumerate()
+                        .find_map(|(offset, position)| Some((index + offset, (*position)?)))
+                        .unwrap_or((stops.len(), 1.));
+                    start + (end - start) * (index - previous) as f32 / (next - previous) as f32
+                }
+            };
+            resolved.push((stop.color, position));
+        }
+        resolved
+    }
+}
+
+impl Default for BackgroundGradient {
+    fn default() -> Self {
+        Self::Linear {
+            angle: 0.,
+            stops: Vec::new(),
+        }
+    }
+}
+
 /// The border color of the UI node.
 #[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
 #[reflect(Component, Default)]
@@ -1734,6 +2027,90 @@ impl Default for BorderColor {
     }
 }
 
+/// Overrides the [`BorderColor`] of a UI node with a color for each side.
+///
+/// Adjacent sides meet along the diagonal of their corner, as in CSS.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct BorderSideColors {
+    pub left: Color,
+    pub top: Color,
+    pub right: Color,
+    pub bottom: Color,
+}
+
+impl BorderSideColors {
+    /// Creates a new [`BorderSideColors`] with the same color on each side.
+    pub const fn all(color: Color) -> Self {
+        Self {
+            left: color,
+            top: color,
+            right: color,
+            bottom: color,
+        }
+    }
+
+    /// The colors of the sides, ordered left, top, right, bottom.
+    pub const fn to_array(self) -> [Color; 4] {
+        [self.left, self.top, self.right, self.bottom]
+    }
+}
+
+impl Default for BorderSideColors {
+    fn default() -> Self {
+        Self::all(Color::WHITE)
+    }
+}
+
+/// The stroke used to draw the border of a UI node.
+///
+/// Dashes and dots start from the top or left end of each side.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum BorderStroke {
+    /// A continuous line.
+    #[default]
+    Solid,
+    /// A line of dashes.
+    Dashed {
+        /// The length of each dash, in logical pixels scaled by [`UiScale`](crate::UiScale).
+        dash: f32,
+        /// The length of the gap between two dashes, in logical pixels scaled by
+        /// [`UiScale`](crate::UiScale).
+        gap: f32,
+    },
+    /// A line of square dots, as long as the border is thick and separated by the same length.
+    Dotted,
+}
+
+/// Draws the border of a UI node with a 9-sliced image instead of the [`BorderColor`].
+///
+/// The corners and the sides of the image are drawn over the border of the node, sized by
+/// the [`BorderRect`](bevy_sprite::BorderRect) of the [`slicer`](Self::slicer) rather than by
+/// [`Style::border`]. The center of the image is only drawn if [`fill`](Self::fill) is set.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct BorderImage {
+    /// The image to slice.
+    pub image: Handle<Image>,
+    /// How the image is sliced and scaled.
+    pub slicer: TextureSlicer,
+    /// The color the image is tinted with.
+    pub color: Color,
+    /// Whether the center slice of the image is drawn over the background of the node.
+    pub fill: bool,
+}
+
 #[derive(Component, Copy, Clone, Default, Debug, Reflect)]
 #[reflect(Component, Default)]
 #[cfg_attr(
@@ -1818,6 +2195,92 @@ impl Outline {
     }
 }
 
+/// The [`BoxShadow`] component adds a drop shadow behind a UI node.
+/// Shadows do not take up space in the layout.
+///
+/// The shadow has the shape of the node, rounded by its [`BorderRadius`], and is blurred outside
+/// of its edges.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_color::Color;
+/// fn setup_ui(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle {
+///             style: Style {
+///                 width: Val::Px(100.),
+///                 height: Val::Px(100.),
+///                 ..Default::default()
+///             },
+///             border_radius: BorderRadius::all(Val::Px(8.)),
+///             ..Default::default()
+///         },
+///         BoxShadow::new(
+///             Color::srgba(0., 0., 0., 0.5),
+///             Val::Px(4.),
+///             Val::Px(4.),
+///             Val::ZERO,
+///             Val::Px(10.),
+///         ),
+///     ));
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct BoxShadow {
+    /// The color of the shadow.
+    pub color: Color,
+    /// The horizontal offset of the shadow, positive values moving it to the right.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub x_offset: Val,
+    /// The vertical offset of the shadow, positive values moving it down.
+    ///
+    /// Percentage `Val` values are resolved based on the height of the [`Node`].
+    pub y_offset: Val,
+    /// The distance the shadow extends beyond the edges of the node before it is blurred.
+    /// Negative values shrink the shadow.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub spread_radius: Val,
+    /// The distance over which the edges of the shadow fade out. A radius of zero draws a sharp
+    /// shadow.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub blur_radius: Val,
+}
+
+impl BoxShadow {
+    /// Creates a new shadow
+    pub const fn new(
+        color: Color,
+        x_offset: Val,
+        y_offset: Val,
+        spread_radius: Val,
+        blur_radius: Val,
+    ) -> Self {
+        Self {
+            color,
+            x_offset,
+            y_offset,
+            spread_radius,
+            blur_radius,
+        }
+    }
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self::new(Color::BLACK, Val::ZERO, Val::ZERO, Val::ZERO, Val::ZERO)
+    }
+}
+
 /// The 2D texture displayed for this UI node
 #[derive(Component, Clone, Debug, Reflect, Default)]
 #[reflect(Component, Default)]
@@ -2169,7 +2632,9 @@ impl BorderRadius {
 
 #[cfg(test)]
 mod tests {
-    use crate::GridPlacement;
+    use bevy_color::Color;
+
+    use crate::{BackgroundGradient, ColorStop, GridPlacement};
 
     #[test]
     fn invalid_grid_placement_values() {
@@ -2196,12 +2661,37 @@ mod tests {
         assert_eq!(GridPlacement::start_span(3, 5).get_end(), None);
         assert_eq!(GridPlacement::end_span(-4, 12).get_start(), None);
     }
+
+    #[test]
+    fn gradient_stops_positions() {
+        let gradient = BackgroundGradient::linear(
+            0.,
+            [
+                ColorStop::auto(Color::WHITE),
+                ColorStop::auto(Color::BLACK),
+                ColorStop::new(Color::WHITE, 0.6),
+                ColorStop::new(Color::BLACK, 0.4),
+                ColorStop::auto(Color::WHITE),
+                ColorStop::auto(Color::BLACK),
+            ],
+        );
+        let positions: Vec<f32> = gradient
+            .resolved_stops()
+            .into_iter()
+            .map(|(_, position)| position)
+            .collect();
+        assert_eq!(positions, [0., 0.3, 0.6, 0.6, 0.8, 1.]);
+    }
 }
 
 /// Indicates that this root [`Node`] entity should be rendered to a specific camera.
 /// UI then will be laid out respecting the camera's viewport and scale factor, and
 /// rendered to this camera's [`bevy_render::camera::RenderTarget`].
 ///
+/// The camera can render to any window, not only the primary one, or to an image. The UI
+/// of each camera is scaled by the scale factor of its render target, and picked with the
+/// cursor of its window.
+///
 /// Setting this component on a non-root node will have no effect. It will be overridden
 /// by the root node's component.
 ///
@@ -2215,6 +2705,25 @@ impl TargetCamera {
     }
 }
 
+/// Scales a UI tree independently of the other roots rendered to the same camera.
+///
+/// The factor is applied on top of [`UiScale`](crate::UiScale) and the target's scale factor,
+/// so a debug overlay can stay small while the game HUD follows the user's preferred size.
+/// Like [`UiScale`](crate::UiScale), it only affects fixed values such as [`Val::Px`], including
+/// the thickness of borders and outlines and the radius of corners, and the size at which text
+/// is rasterized. Nodes moved to another root take the scale of their new root.
+///
+/// Setting this component on a non-root node has no effect.
+#[derive(Component, Clone, Copy, Debug, Reflect, PartialEq)]
+#[reflect(Component, Default)]
+pub struct UiRootScale(pub f32);
+
+impl Default for UiRootScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
 #[derive(Component)]
 /// Marker used to identify default cameras, they will have priority over the [`PrimaryWindow`] camera.
 ///
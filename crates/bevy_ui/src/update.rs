@@ -1,17 +1,18 @@
 //! This module contains systems that update the UI when something changes
 
-use crate::{CalculatedClip, Display, OverflowAxis, Style, TargetCamera};
+use crate::{CalculatedClip, Display, OverflowAxis, Style, TargetCamera, UiScale, UiScaleMode};
 
 use super::Node;
 use bevy_ecs::{
     entity::Entity,
     query::{Changed, With, Without},
-    system::{Commands, Query},
+    system::{Commands, Query, Res, ResMut},
 };
 use bevy_hierarchy::{Children, Parent};
 use bevy_math::Rect;
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashSet;
+use bevy_window::{PrimaryWindow, Window};
 
 /// Updates clipping for all nodes
 pub fn update_clipping_system(
@@ -181,3 +182,24 @@ fn update_children_target_camera(
         );
     }
 }
+
+/// Updates [`UiScale`] from the primary window's scale factor according to the [`UiScaleMode`].
+///
+/// The roots rendered to other targets are scaled for their own scale factor during layout, see
+/// [`UiScaleMode::root_scale`].
+pub fn update_ui_scale_system(
+    mode: Res<UiScaleMode>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let window_scale_factor = primary_window
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.);
+
+    if let Some(scale) = mode.ui_scale(window_scale_factor) {
+        if ui_scale.0 != scale {
+            ui_scale.0 = scale;
+        }
+    }
+}
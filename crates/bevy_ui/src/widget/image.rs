@@ -1,13 +1,13 @@
 use crate::{
-    measurement::AvailableSpace, ContentSize, Measure, Node, NodeMeasure, UiImage, UiScale,
+    measurement::AvailableSpace, ContentSize, DefaultUiCamera, Measure, Node, NodeMeasure,
+    TargetCamera, UiImage, UiScale,
 };
 use bevy_asset::Assets;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
 use bevy_math::{UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::texture::Image;
+use bevy_render::{camera::Camera, texture::Image};
 use bevy_sprite::{TextureAtlas, TextureAtlasLayout};
-use bevy_window::{PrimaryWindow, Window};
 
 /// The size of the image's texture
 ///
@@ -69,9 +69,11 @@ type UpdateImageFilter = (With<Node>, Without<bevy_text::Text>);
 type UpdateImageFilter = With<Node>;
 
 /// Updates content size of the node based on the image provided
+#[allow(clippy::too_many_arguments)]
 pub fn update_image_content_size_system(
-    mut previous_combined_scale_factor: Local<f32>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    mut previous_combined_scale_factors: Local<EntityHashMap<f32>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
     textures: Res<Assets<Image>>,
 
@@ -82,24 +84,37 @@ pub fn update_image_content_size_system(
             &UiImage,
             &mut UiImageSize,
             Option<&TextureAtlas>,
+            Option<&TargetCamera>,
         ),
         UpdateImageFilter,
     >,
 ) {
-    let combined_scale_factor = windows
-        .get_single()
-        .map(|window| window.resolution.scale_factor())
-        .unwrap_or(1.)
-        * ui_scale.0;
+    // The scale factor of the render target of each camera, multiplied by `UiScale`
+    let mut combined_scale_factors = EntityHashMap::default();
+
+    for (mut content_size, image, mut image_size, atlas_image, target_camera) in &mut query {
+        let Some(camera) = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+        else {
+            continue;
+        };
+        let combined_scale_factor = *combined_scale_factors.entry(camera).or_insert_with(|| {
+            camera_query
+                .get(camera)
+                .ok()
+                .and_then(Camera::target_scaling_factor)
+                .unwrap_or(1.)
+                * ui_scale.0
+        });
 
-    for (mut content_size, image, mut image_size, atlas_image) in &mut query {
         if let Some(size) = match atlas_image {
             Some(atlas) => atlas.texture_rect(&atlases).map(|t| t.size()),
             None => textures.get(&image.texture).map(|t| t.size()),
         } {
             // Update only if size or scale factor has changed to avoid needless layout calculations
             if size != image_size.size
-                || combined_scale_factor != *previous_combined_scale_factor
+                || previous_combined_scale_factors.get(&camera) != Some(&combined_scale_factor)
                 || content_size.is_added()
             {
                 image_size.size = size;
@@ -111,5 +126,5 @@ pub fn update_image_content_size_system(
         }
     }
 
-    *previous_combined_scale_factor = combined_scale_factor;
+    *previous_combined_scale_factors = combined_scale_factors;
 }
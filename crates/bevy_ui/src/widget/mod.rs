@@ -5,9 +5,15 @@ mod image;
 mod label;
 #[cfg(feature = "bevy_text")]
 mod text;
+#[cfg(feature = "bevy_text")]
+mod text_input;
+mod viewport;
 
 pub use button::*;
 pub use image::*;
 pub use label::*;
 #[cfg(feature = "bevy_text")]
 pub use text::*;
+#[cfg(feature = "bevy_text")]
+pub use text_input::*;
+pub use viewport::*;
@@ -1,21 +1,23 @@
-use crate::{ContentSize, FixedMeasure, Measure, Node, NodeMeasure, UiScale};
+use crate::{
+    ContentSize, DefaultUiCamera, Direction, FixedMeasure, Measure, Node, NodeMeasure,
+    TargetCamera, UiScale,
+};
 use bevy_asset::Assets;
 use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
     prelude::{Component, DetectChanges},
-    query::With,
     reflect::ReflectComponent,
     system::{Local, Query, Res, ResMut},
     world::{Mut, Ref},
 };
 use bevy_math::Vec2;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::texture::Image;
+use bevy_render::{camera::Camera, texture::Image};
 use bevy_sprite::TextureAtlasLayout;
 use bevy_text::{
-    scale_value, BreakLineOn, Font, FontAtlasSets, Text, TextError, TextLayoutInfo,
+    scale_value, BreakLineOn, Font, FontAtlasSets, JustifyText, Text, TextError, TextLayoutInfo,
     TextMeasureInfo, TextPipeline, TextSettings, YAxisOrientation,
 };
-use bevy_window::{PrimaryWindow, Window};
 use taffy::style::AvailableSpace;
 
 /// Text system flags
@@ -28,6 +30,8 @@ pub struct TextFlags {
     needs_new_measure_func: bool,
     /// If set the text will be recomputed
     needs_recompute: bool,
+    /// The [`UiRootScale`](crate::UiRootScale) the current measure function was created with
+    measured_root_scale: f32,
 }
 
 impl Default for TextFlags {
@@ -35,6 +39,7 @@ impl Default for TextFlags {
         Self {
             needs_new_measure_func: true,
             needs_recompute: true,
+            measured_root_scale: 1.,
         }
     }
 }
@@ -82,10 +87,12 @@ fn create_text_measure(
     fonts: &Assets<Font>,
     scale_factor: f32,
     text: Ref<Text>,
+    node: &Node,
     mut content_size: Mut<ContentSize>,
     mut text_flags: Mut<TextFlags>,
 ) {
-    match TextMeasureInfo::from_text(&text, fonts, scale_factor) {
+    let root_scale = node.root_scale();
+    match TextMeasureInfo::from_text(&text, fonts, scale_factor * root_scale) {
         Ok(measure) => {
             if text.linebreak_behavior == BreakLineOn::NoWrap {
                 content_size.set(NodeMeasure::Fixed(FixedMeasure { size: measure.max }));
@@ -96,6 +103,7 @@ fn create_text_measure(
             // Text measure func created successfully, so set `TextFlags` to schedule a recompute
             text_flags.needs_new_measure_func = false;
             text_flags.needs_recompute = true;
+            text_flags.measured_root_scale = root_scale;
         }
         Err(TextError::NoSuchFont) => {
             // Try again next frame
@@ -111,41 +119,72 @@ fn create_text_measure(
 /// A `Measure` is used by the UI's layout algorithm to determine the appropriate amount of space
 /// to provide for the text given the fonts, the text itself and the constraints of the layout.
 ///
-/// * All measures are regenerated if the primary window's scale factor or [`UiScale`] is changed.
+/// * The measures of the text nodes of a camera are regenerated if the scale factor of its render
+///   target or [`UiScale`] is changed.
+/// * A measure is regenerated if the [`UiRootScale`](crate::UiRootScale) of its root node is changed.
 /// * Changes that only modify the colors of a `Text` do not require a new `Measure`. This system
 /// is only able to detect that a `Text` component has changed and will regenerate the `Measure` on
 /// color changes. This can be expensive, particularly for large blocks of text, and the [`bypass_change_detection`](bevy_ecs::change_detection::DetectChangesMut::bypass_change_detection)
 /// method should be called when only changing the `Text`'s colors.
 pub fn measure_text_system(
-    mut last_scale_factor: Local<f32>,
+    mut last_scale_factors: Local<EntityHashMap<f32>>,
     fonts: Res<Assets<Font>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(Entity, &Camera)>,
+    default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
-    mut text_query: Query<(Ref<Text>, &mut ContentSize, &mut TextFlags), With<Node>>,
+    mut text_query: Query<(
+        Ref<Text>,
+        &Node,
+        &mut ContentSize,
+        &mut TextFlags,
+        Option<&TargetCamera>,
+    )>,
 ) {
-    let window_scale_factor = windows
-        .get_single()
-        .map(|window| window.resolution.scale_factor())
-        .unwrap_or(1.);
-
-    let scale_factor = ui_scale.0 * window_scale_factor;
-
-    #[allow(clippy::float_cmp)]
-    if *last_scale_factor == scale_factor {
-        // scale factor unchanged, only create new measure funcs for modified text
-        for (text, content_size, text_flags) in &mut text_query {
-            if text.is_changed() || text_flags.needs_new_measure_func || content_size.is_added() {
-                create_text_measure(&fonts, scale_factor, text, content_size, text_flags);
-            }
-        }
-    } else {
-        // scale factor changed, create new measure funcs for all text
-        *last_scale_factor = scale_factor;
+    let mut scale_factors = EntityHashMap::default();
+    for (text, node, content_size, text_flags, target_camera) in &mut text_query {
+        let Some((camera, scale_factor)) = camera_scale_factor(
+            &mut scale_factors,
+            &camera_query,
+            &default_ui_camera,
+            target_camera,
+            ui_scale.0,
+        ) else {
+            continue;
+        };
 
-        for (text, content_size, text_flags) in &mut text_query {
-            create_text_measure(&fonts, scale_factor, text, content_size, text_flags);
+        if last_scale_factors.get(&camera) != Some(&scale_factor)
+            || text.is_changed()
+            || text_flags.needs_new_measure_func
+            || text_flags.measured_root_scale != node.root_scale()
+            || content_size.is_added()
+        {
+            create_text_measure(&fonts, scale_factor, text, node, content_size, text_flags);
         }
     }
+    *last_scale_factors = scale_factors;
+}
+
+/// Returns the camera rendering a text node and the scale factor of its render target multiplied
+/// by `ui_scale`, caching the scale factor of each camera in `scale_factors`.
+fn camera_scale_factor(
+    scale_factors: &mut EntityHashMap<f32>,
+    camera_query: &Query<(Entity, &Camera)>,
+    default_ui_camera: &DefaultUiCamera,
+    target_camera: Option<&TargetCamera>,
+    ui_scale: f32,
+) -> Option<(Entity, f32)> {
+    let camera = target_camera
+        .map(TargetCamera::entity)
+        .or(default_ui_camera.get())?;
+    let scale_factor = *scale_factors.entry(camera).or_insert_with(|| {
+        camera_query
+            .get(camera)
+            .ok()
+            .and_then(|(_, camera)| camera.target_scaling_factor())
+            .unwrap_or(1.)
+            * ui_scale
+    });
+    Some((camera, scale_factor))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -177,11 +216,20 @@ fn queue_text(
             )
         };
 
+        // The left and right alignments are the start and end alignments of right to left text
+        let justify = match (node.direction(), text.justify) {
+            (Direction::RightToLeft, JustifyText::Left) => JustifyText::Right,
+            (Direction::RightToLeft, JustifyText::Right) => JustifyText::Left,
+            (_, justify) => justify,
+        };
+
+        // Glyphs are rasterized at the root's scale, but the node size and the logical
+        // coordinates of the layout are expressed relative to the camera's scale factor.
         match text_pipeline.queue_text(
             fonts,
             &text.sections,
-            scale_factor,
-            text.justify,
+            scale_factor * node.root_scale(),
+            justify,
             text.linebreak_behavior,
             physical_node_size,
             font_atlas_sets,
@@ -218,49 +266,41 @@ fn queue_text(
 #[allow(clippy::too_many_arguments)]
 pub fn text_system(
     mut textures: ResMut<Assets<Image>>,
-    mut last_scale_factor: Local<f32>,
+    mut last_scale_factors: Local<EntityHashMap<f32>>,
     fonts: Res<Assets<Font>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(Entity, &Camera)>,
+    default_ui_camera: DefaultUiCamera,
     text_settings: Res<TextSettings>,
     ui_scale: Res<UiScale>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut font_atlas_sets: ResMut<FontAtlasSets>,
     mut text_pipeline: ResMut<TextPipeline>,
-    mut text_query: Query<(Ref<Node>, &Text, &mut TextLayoutInfo, &mut TextFlags)>,
+    mut text_query: Query<(
+        Ref<Node>,
+        &Text,
+        &mut TextLayoutInfo,
+        &mut TextFlags,
+        Option<&TargetCamera>,
+    )>,
 ) {
-    // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
-    let window_scale_factor = windows
-        .get_single()
-        .map(|window| window.resolution.scale_factor())
-        .unwrap_or(1.);
-
-    let scale_factor = ui_scale.0 * window_scale_factor;
-    let inverse_scale_factor = scale_factor.recip();
-    if *last_scale_factor == scale_factor {
-        // Scale factor unchanged, only recompute text for modified text nodes
-        for (node, text, text_layout_info, text_flags) in &mut text_query {
-            if node.is_changed() || text_flags.needs_recompute {
-                queue_text(
-                    &fonts,
-                    &mut text_pipeline,
-                    &mut font_atlas_sets,
-                    &mut texture_atlases,
-                    &mut textures,
-                    &text_settings,
-                    scale_factor,
-                    inverse_scale_factor,
-                    text,
-                    node,
-                    text_flags,
-                    text_layout_info,
-                );
-            }
-        }
-    } else {
-        // Scale factor changed, recompute text for all text nodes
-        *last_scale_factor = scale_factor;
+    let mut scale_factors = EntityHashMap::default();
+    for (node, text, text_layout_info, text_flags, target_camera) in &mut text_query {
+        let Some((camera, scale_factor)) = camera_scale_factor(
+            &mut scale_factors,
+            &camera_query,
+            &default_ui_camera,
+            target_camera,
+            ui_scale.0,
+        ) else {
+            continue;
+        };
 
-        for (node, text, text_layout_info, text_flags) in &mut text_query {
+        // Recompute the text of the modified text nodes, or of all the text nodes of a camera
+        // whose scale factor changed
+        if last_scale_factors.get(&camera) != Some(&scale_factor)
+            || node.is_changed()
+            || text_flags.needs_recompute
+        {
             queue_text(
                 &fonts,
                 &mut text_pipeline,
@@ -269,7 +309,7 @@ pub fn text_system(
                 &mut textures,
                 &text_settings,
                 scale_factor,
-                inverse_scale_factor,
+                scale_factor.recip(),
                 text,
                 node,
                 text_flags,
@@ -277,4 +317,5 @@ pub fn text_system(
             );
         }
     }
+    *last_scale_factors = scale_factors;
 }
@@ -0,0 +1,564 @@
+use crate::{Interaction, Node, UiScale};
+use bevy_a11y::Focus;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    ButtonInput, ButtonState,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_text::{PositionedCaret, Text, TextLayoutInfo, TextSection};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{Clipboard, Ime, PrimaryWindow, Window};
+use std::ops::Range;
+
+/// An editable single line of text, displayed by the [`Text`] of the node.
+///
+/// The input is edited with the keyboard while it has the keyboard [`Focus`], which it takes when
+/// it is pressed. It draws a caret and the selected range, supports the usual editing keys, copies
+/// to and pastes from the [`Clipboard`] resource, and shows the composition of the IME of the
+/// primary window. A [`TextInputCommit`] event is sent when <kbd>Enter</kbd> is pressed.
+///
+/// The value replaces the sections of the [`Text`], which only keeps the style of its first
+/// section, so the node should be spawned with a [`TextBundle`](crate::node_bundles::TextBundle).
+///
+/// # Clipboard
+///
+/// The [`Clipboard`] resource is synchronized with the clipboard of the operating system when the
+/// `clipboard` feature is enabled. Otherwise, it is internal to the app: text copied from a
+/// [`TextInput`] can't be pasted in other apps, and text copied in other apps can't be pasted in a
+/// [`TextInput`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::{node_bundles::TextBundle, widget::TextInput};
+/// # use bevy_text::TextStyle;
+/// fn spawn_name_input(mut commands: Commands) {
+///     commands.spawn((
+///         TextBundle::from_section("", TextStyle::default()),
+///         TextInput::new("Player"),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(Interaction)]
+pub struct TextInput {
+    /// The color of the caret.
+    pub caret_color: Color,
+    /// The color drawn behind the selected text.
+    pub selection_color: Color,
+    value: String,
+    /// The byte index of the caret in the value.
+    cursor: usize,
+    /// The byte index where the selection started, the selection spanning to the caret.
+    anchor: usize,
+    /// The text being composed by the IME, displayed at the caret.
+    preedit: String,
+    /// The byte range of the caret in the preedit text, hidden if `None`.
+    preedit_cursor: Option<(usize, usize)>,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl TextInput {
+    /// Creates an input with the given value, and the caret at its end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = single_line(&value.into());
+        Self {
+            caret_color: Color::WHITE,
+            selection_color: Color::srgba(0.2, 0.4, 1.0, 0.5),
+            cursor: value.len(),
+            anchor: value.len(),
+            value,
+            preedit: String::new(),
+            preedit_cursor: None,
+        }
+    }
+
+    /// Returns the value of the input.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the value of the input, and moves the caret to its end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = single_line(&value.into());
+        self.cursor = self.value.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Returns the byte index of the caret in the value.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the byte range of the selected text, which is empty if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Returns the selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.value[self.selection()]
+    }
+
+    /// Returns the text being composed by the IME, which isn't part of the value yet.
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Selects the given byte range of the value, placing the caret at its end.
+    ///
+    /// The range is clamped to the value, and its bounds to the nearest character boundaries
+    /// before them.
+    pub fn select(&mut self, range: Range<usize>) {
+        self.anchor = self.floor_char_boundary(range.start);
+        self.cursor = self.floor_char_boundary(range.end);
+    }
+
+    /// Selects the whole value.
+    pub fn select_all(&mut self) {
+        self.select(0..self.value.len());
+    }
+
+    /// Inserts `text` at the caret, replacing the selected text, and places the caret after it.
+    ///
+    /// Line breaks are replaced by spaces.
+    pub fn insert(&mut self, text: &str) {
+        let range = self.selection();
+        let text = single_line(text);
+        self.value.replace_range(range.clone(), &text);
+        self.cursor = range.start + text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Removes the selected text and returns it.
+    pub fn take_selected_text(&mut self) -> String {
+        let range = self.selection();
+        self.cursor = range.start;
+        self.anchor = range.start;
+        self.value.drain(range).collect()
+    }
+
+    /// Deletes the selected text, or the character before the caret if nothing is selected.
+    pub fn delete_backward(&mut self) {
+        if self.anchor == self.cursor {
+            self.anchor = self.previous_char_boundary();
+        }
+        self.take_selected_text();
+    }
+
+    /// Deletes the selected text, or the character after the caret if nothing is selected.
+    pub fn delete_forward(&mut self) {
+        if self.anchor == self.cursor {
+            self.anchor = self.next_char_boundary();
+        }
+        self.take_selected_text();
+    }
+
+    /// Moves the caret one character to the left, extending the selection if `extend` is `true`.
+    ///
+    /// Without `extend`, a selection is collapsed to its start instead.
+    pub fn move_left(&mut self, extend: bool) {
+        let cursor = match (extend, self.selection()) {
+            (false, selection) if !selection.is_empty() => selection.start,
+            _ => self.previous_char_boundary(),
+        };
+        self.move_to(cursor, extend);
+    }
+
+    /// Moves the caret one character to the right, extending the selection if `extend` is `true`.
+    ///
+    /// Without `extend`, a selection is collapsed to its end instead.
+    pub fn move_right(&mut self, extend: bool) {
+        let cursor = match (extend, self.selection()) {
+            (false, selection) if !selection.is_empty() => selection.end,
+            _ => self.next_char_boundary(),
+        };
+        self.move_to(cursor, extend);
+    }
+
+    /// Moves the caret to the start of the value, extending the selection if `extend` is `true`.
+    pub fn move_to_start(&mut self, extend: bool) {
+        self.move_to(0, extend);
+    }
+
+    /// Moves the caret to the end of the value, extending the selection if `extend` is `true`.
+    pub fn move_to_end(&mut self, extend: bool) {
+        self.move_to(self.value.len(), extend);
+    }
+
+    fn move_to(&mut self, cursor: usize, extend: bool) {
+        self.cursor = cursor;
+        if !extend {
+            self.anchor = cursor;
+        }
+    }
+
+    fn previous_char_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .chars()
+            .next_back()
+            .map_or(0, |char| self.cursor - char.len_utf8())
+    }
+
+    fn next_char_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |char| self.cursor + char.len_utf8())
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        let mut index = index.min(self.value.len());
+        while !self.value.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    /// Sets the text being composed by the IME, which replaces the selected text.
+    fn set_preedit(&mut self, preedit: String, cursor: Option<(usize, usize)>) {
+        if !preedit.is_empty() {
+            self.take_selected_text();
+        }
+        self.preedit = preedit;
+        self.preedit_cursor = cursor;
+    }
+
+    /// Returns the displayed text, the value with the preedit text at the caret.
+    fn displayed_text(&self) -> String {
+        let mut text = self.value.clone();
+        text.insert_str(self.cursor, &self.preedit);
+        text
+    }
+
+    /// Returns the byte index of the caret in the displayed text, or `None` if the IME hides it.
+    pub(crate) fn displayed_caret(&self) -> Option<usize> {
+        if self.preedit.is_empty() {
+            Some(self.cursor)
+        } else {
+            self.preedit_cursor.map(|(_, end)| self.cursor + end)
+        }
+    }
+
+    /// Returns the byte range of the selection in the displayed text.
+    pub(crate) fn displayed_selection(&self) -> Range<usize> {
+        if self.preedit.is_empty() {
+            self.selection()
+        } else {
+            self.cursor..self.cursor
+        }
+    }
+}
+
+/// Returns the position of the center and the height of a caret placed before the character at
+/// `byte_index` of the displayed text, or after the last character if it is at the end of the
+/// text, in physical pixels relative to the top left of the node.
+///
+/// Returns `None` for an empty text.
+pub(crate) fn caret_position(carets: &[PositionedCaret], byte_index: usize) -> Option<(Vec2, f32)> {
+    carets
+        .iter()
+        .find(|caret| caret.byte_index >= byte_index)
+        .map(|caret| (caret.position, caret.height))
+        .or_else(|| {
+            carets
+                .last()
+                .map(|caret| (caret.position + Vec2::X * caret.advance, caret.height))
+        })
+}
+
+fn single_line(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
+
+/// Sent when <kbd>Enter</kbd> is pressed in a [`TextInput`].
+///
+/// The text being composed by the IME is confirmed with <kbd>Enter</kbd> instead.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct TextInputCommit {
+    /// The entity of the [`TextInput`].
+    pub entity: Entity,
+    /// The value of the input.
+    pub value: String,
+}
+
+/// Gives the keyboard [`Focus`] to the [`TextInput`]s when they are pressed.
+pub fn focus_text_input_on_press(
+    inputs: Query<(Entity, &Interaction), (Changed<Interaction>, With<TextInput>)>,
+    focus: Option<ResMut<Focus>>,
+) {
+    let Some(mut focus) = focus else {
+        return;
+    };
+    for (entity, interaction) in &inputs {
+        if *interaction == Interaction::Pressed && focus.0 != Some(entity) {
+            focus.0 = Some(entity);
+        }
+    }
+}
+
+/// Edits the focused [`TextInput`] with the keyboard and IME events.
+///
+/// The text being composed by the IME in an input is discarded when it loses the focus.
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_keyboard_system(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut ime_events: EventReader<Ime>,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Option<Res<Focus>>,
+    mut inputs: Query<&mut TextInput>,
+    mut clipboard: ResMut<Clipboard>,
+    mut commits: EventWriter<TextInputCommit>,
+    mut previously_focused: Local<Option<Entity>>,
+) {
+    let focused = focus.and_then(|focus| focus.0);
+    if *previously_focused != focused {
+        if let Some(mut input) = previously_focused.and_then(|entity| inputs.get_mut(entity).ok()) {
+            if !input.preedit.is_empty() {
+                input.set_preedit(String::new(), None);
+            }
+        }
+        *previously_focused = focused;
+    }
+    let Some((entity, mut input)) =
+        focused.and_then(|entity| Some((entity, inputs.get_mut(entity).ok()?)))
+    else {
+        keyboard_events.clear();
+        ime_events.clear();
+        return;
+    };
+
+    for event in ime_events.read() {
+        match event {
+            Ime::Preedit { value, cursor, .. } => input.set_preedit(value.clone(), *cursor),
+            Ime::Commit { value, .. } => {
+                input.set_preedit(String::new(), None);
+                input.insert(value);
+            }
+            Ime::Disabled { .. } => input.set_preedit(String::new(), None),
+            Ime::Enabled { .. } => {}
+        }
+    }
+
+    let shortcut = is_shortcut(&keys);
+    let extend = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for event in keyboard_events.read() {
+        // The keys are handled by the IME while it composes text
+        if event.state != ButtonState::Pressed || !input.preedit.is_empty() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(character) if shortcut => match character.to_lowercase().as_str() {
+                "a" => input.select_all(),
+                "c" => clipboard.0 = input.selected_text().to_owned(),
+                "x" => clipboard.0 = input.take_selected_text(),
+                "v" => input.insert(&clipboard.0),
+                _ => {}
+            },
+            Key::Character(character) => input.insert(character),
+            Key::Space => input.insert(" "),
+            Key::Backspace => input.delete_backward(),
+            Key::Delete => input.delete_forward(),
+            Key::ArrowLeft => input.move_left(extend),
+            Key::ArrowRight => input.move_right(extend),
+            Key::Home => input.move_to_start(extend),
+            Key::End => input.move_to_end(extend),
+            Key::Enter => {
+                commits.send(TextInputCommit {
+                    entity,
+                    value: input.value.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns whether the pressed keys turn character keys into shortcuts.
+///
+/// On Windows, <kbd>AltGr</kbd> is reported as <kbd>Ctrl</kbd> and right <kbd>Alt</kbd>, so
+/// <kbd>Ctrl</kbd> is ignored while right <kbd>Alt</kbd> is pressed, to type the characters of
+/// <kbd>AltGr</kbd> on keyboard layouts such as the German one.
+fn is_shortcut(keys: &ButtonInput<KeyCode>) -> bool {
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        && !keys.pressed(KeyCode::AltRight);
+    control || keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight])
+}
+
+/// Writes the value of the [`TextInput`]s, with the text being composed by the IME, to their
+/// [`Text`].
+pub fn update_text_input_text(mut inputs: Query<(&TextInput, &mut Text), Changed<TextInput>>) {
+    for (input, mut text) in &mut inputs {
+        let displayed = input.displayed_text();
+        if text.sections.len() == 1 && text.sections[0].value == displayed {
+            continue;
+        }
+        let style = text
+            .sections
+            .first()
+            .map(|section| section.style.clone())
+            .unwrap_or_default();
+        text.sections = vec![TextSection::new(displayed, style)];
+    }
+}
+
+/// Enables the IME of the primary window when a [`TextInput`] gains the keyboard [`Focus`], and
+/// places the IME candidate box at its caret.
+///
+/// The IME is disabled when the focus leaves the [`TextInput`]s, and is otherwise left as is, so
+/// that other systems can enable it.
+pub fn update_text_input_ime(
+    focus: Option<Res<Focus>>,
+    inputs: Query<(&TextInput, &Node, &GlobalTransform, &TextLayoutInfo)>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut previously_focused: Local<Option<Entity>>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    let focused_entity = focus
+        .and_then(|focus| focus.0)
+        .filter(|&entity| inputs.contains(entity));
+    if focused_entity.is_some() != previously_focused.is_some() {
+        window.ime_enabled = focused_entity.is_some();
+    }
+    *previously_focused = focused_entity;
+    let focused = focused_entity.and_then(|entity| inputs.get(entity).ok());
+    let Some((input, node, transform, layout)) = focused else {
+        return;
+    };
+
+    // The candidate box is placed below the caret, in logical window coordinates
+    let top_left = (transform.translation().truncate() - 0.5 * node.size()) * ui_scale.0;
+    let caret = input
+        .displayed_caret()
+        .and_then(|byte_index| caret_position(&layout.carets, byte_index))
+        .map_or(
+            Vec2::new(0., node.size().y * ui_scale.0),
+            |(position, height)| (position + Vec2::Y * height / 2.) / window.scale_factor(),
+        );
+    let ime_position = top_left + caret;
+    if window.ime_position != ime_position {
+        window.ime_position = ime_position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_a11y::Focus;
+    use bevy_ecs::{entity::Entity, event::Events, schedule::Schedule, world::World};
+    use bevy_input::{
+        keyboard::{KeyCode, KeyboardInput},
+        ButtonInput,
+    };
+    use bevy_window::{Clipboard, Ime};
+
+    use super::{is_shortcut, text_input_keyboard_system, TextInput, TextInputCommit};
+
+    #[test]
+    fn alt_graph_is_not_a_shortcut() {
+        let mut keys = ButtonInput::<KeyCode>::default();
+        assert!(!is_shortcut(&keys));
+
+        keys.press(KeyCode::ControlLeft);
+        assert!(is_shortcut(&keys));
+
+        // AltGr on Windows
+        keys.press(KeyCode::AltRight);
+        assert!(!is_shortcut(&keys));
+
+        keys.release_all();
+        keys.press(KeyCode::SuperLeft);
+        keys.press(KeyCode::AltRight);
+        assert!(is_shortcut(&keys));
+    }
+
+    #[test]
+    fn text_input_editing() {
+        let mut input = TextInput::new("héllo");
+        assert_eq!(input.cursor(), 6);
+
+        input.move_left(false);
+        input.move_left(true);
+        input.move_left(true);
+        assert_eq!(input.selected_text(), "ll");
+        input.insert("LL");
+        assert_eq!(input.value(), "héLLo");
+        assert_eq!(input.cursor(), 5);
+
+        input.move_to_start(false);
+        input.move_right(false);
+        input.move_right(true);
+        assert_eq!(input.selection(), 1..3);
+        input.delete_backward();
+        assert_eq!(input.value(), "hLLo");
+
+        input.delete_backward();
+        assert_eq!(input.value(), "LLo");
+        input.delete_backward();
+        assert_eq!(input.value(), "LLo");
+
+        input.move_to_end(true);
+        assert_eq!(input.take_selected_text(), "LLo");
+        assert_eq!(input.value(), "");
+
+        input.insert("a\nb");
+        assert_eq!(input.value(), "a b");
+        input.move_to_start(false);
+        input.delete_forward();
+        assert_eq!(input.value(), " b");
+    }
+
+    #[test]
+    fn text_input_preedit_replaces_selection() {
+        let mut input = TextInput::new("abc");
+        input.select(1..2);
+        input.set_preedit("に".to_owned(), Some((3, 3)));
+        assert_eq!(input.value(), "ac");
+        assert_eq!(input.displayed_text(), "aにc");
+        assert_eq!(input.displayed_caret(), Some(4));
+
+        input.set_preedit(String::new(), None);
+        input.insert("日");
+        assert_eq!(input.value(), "a日c");
+        assert_eq!(input.displayed_caret(), Some(4));
+    }
+
+    #[test]
+    fn preedit_is_discarded_when_the_focus_changes() {
+        let mut world = World::new();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<Events<Ime>>();
+        world.init_resource::<Events<TextInputCommit>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Clipboard>();
+        let first = world.spawn(TextInput::new("a")).id();
+        let second = world.spawn(TextInput::new("b")).id();
+        world.insert_resource(Focus(Some(first)));
+
+        // The system keeps track of the previous focus, so it must not be recreated
+        let mut schedule = Schedule::default();
+        schedule.add_systems(text_input_keyboard_system);
+        world.send_event(Ime::Preedit {
+            window: Entity::PLACEHOLDER,
+            value: "に".to_owned(),
+            cursor: Some((3, 3)),
+        });
+        schedule.run(&mut world);
+        assert_eq!(world.get::<TextInput>(first).unwrap().preedit(), "に");
+
+        world.resource_mut::<Focus>().0 = Some(second);
+        schedule.run(&mut world);
+        assert_eq!(world.get::<TextInput>(first).unwrap().preedit(), "");
+        assert_eq!(world.get::<TextInput>(first).unwrap().value(), "a");
+    }
+}
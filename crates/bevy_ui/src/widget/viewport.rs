@@ -0,0 +1,123 @@
+use crate::{DefaultUiCamera, Node, TargetCamera, UiImage, UiScale};
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_math::{Rect, UVec2, Vec2};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    camera::{Camera, RenderTarget},
+    render_resource::Extent3d,
+    texture::Image,
+};
+
+/// Displays the render target of another camera inside the UI layout.
+///
+/// The camera must render to an image, which is resized to the physical size of the node and
+/// displayed as its [`UiImage`]. The pointers over the node are moved into the viewport of the
+/// camera, so that the [`HoverMap`](crate::HoverMap) picks the UI rendered by this camera, and
+/// [`HoverMap::pointer_position`](crate::HoverMap::pointer_position) can be used to cast rays
+/// from it. This can be used for editor views, minimaps or picture-in-picture.
+///
+/// Viewports can't be nested: the pointers are only moved into the cameras displayed by the nodes
+/// of a camera rendering to a window.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+#[require(UiImage)]
+pub struct ViewportNode {
+    /// The camera whose render target is displayed.
+    pub camera: Entity,
+}
+
+impl ViewportNode {
+    /// Creates a node displaying the render target of `camera`.
+    pub const fn new(camera: Entity) -> Self {
+        Self { camera }
+    }
+}
+
+/// Resizes the render targets of the cameras displayed by [`ViewportNode`]s to the size of their
+/// nodes, and sets them as the [`UiImage`] of the nodes.
+pub fn update_viewport_render_target_size(
+    mut viewport_query: Query<(&ViewportNode, &Node, Option<&TargetCamera>, &mut UiImage)>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (viewport, node, target_camera, mut ui_image) in &mut viewport_query {
+        let Ok(camera) = camera_query.get(viewport.camera) else {
+            continue;
+        };
+        let RenderTarget::Image(handle) = &camera.target else {
+            continue;
+        };
+        if ui_image.texture != *handle {
+            ui_image.texture = handle.clone();
+        }
+
+        let scale_factor = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|ui_camera| camera_query.get(ui_camera).ok())
+            .and_then(Camera::target_scaling_factor)
+            .unwrap_or(1.0);
+        let size = (node.size() * scale_factor * ui_scale.0)
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE);
+        // Only access the image mutably when it needs to be resized, to avoid uploading it again
+        if images.get(handle).is_some_and(|image| image.size() != size) {
+            if let Some(image) = images.get_mut(handle) {
+                image.resize(Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                });
+            }
+        }
+    }
+}
+
+/// Moves `position`, over a [`ViewportNode`] covering `node_rect`, into the viewport of size
+/// `viewport_size` of the displayed camera.
+///
+/// Returns `None` if `position` is outside of `node_rect`.
+pub(crate) fn map_to_viewport(
+    node_rect: Rect,
+    position: Vec2,
+    viewport_size: Vec2,
+) -> Option<Vec2> {
+    if node_rect.is_empty() || !node_rect.contains(position) {
+        return None;
+    }
+    Some((position - node_rect.min) / node_rect.size() * viewport_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Rect, Vec2};
+
+    use super::map_to_viewport;
+
+    #[test]
+    fn maps_positions_into_the_viewport() {
+        let node_rect = Rect::new(100.0, 50.0, 300.0, 150.0);
+        let viewport_size = Vec2::new(800.0, 400.0);
+
+        assert_eq!(
+            map_to_viewport(node_rect, Vec2::new(100.0, 50.0), viewport_size),
+            Some(Vec2::ZERO)
+        );
+        assert_eq!(
+            map_to_viewport(node_rect, Vec2::new(250.0, 75.0), viewport_size),
+            Some(Vec2::new(600.0, 100.0))
+        );
+        assert_eq!(
+            map_to_viewport(node_rect, Vec2::new(50.0, 75.0), viewport_size),
+            None
+        );
+        assert_eq!(
+            map_to_viewport(Rect::default(), Vec2::ZERO, viewport_size),
+            None
+        );
+    }
+}
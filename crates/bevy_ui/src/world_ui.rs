@@ -0,0 +1,408 @@
+//! This module contains [`WorldUi`], which renders a UI subtree on a quad in world space, and
+//! [`UiTextureSurface`], which picks the UI rendered to an image through a quad displaying it.
+
+use bevy_asset::{Assets, Handle};
+use bevy_color::Color;
+use bevy_core_pipeline::core_2d::Camera2dBundle;
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_math::{primitives::InfinitePlane3d, Quat, Ray3d, UVec2, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, ClearColorConfig, RenderTarget},
+    prelude::SpatialBundle,
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    texture::Image,
+    view::RenderLayers,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::TargetCamera;
+
+/// Renders the UI subtree of this root node on a quad in world space, attached to an
+/// [`anchor`](Self::anchor) entity, for health bars, nameplates or menus in VR.
+///
+/// The subtree is laid out in a viewport of [`size`](Self::size) pixels, rendered to an image by
+/// a camera spawned for the panel, which becomes the [`TargetCamera`] of the root. The quad is a
+/// child of the anchor, and is displayed with an unlit material when the `bevy_pbr` feature is
+/// enabled. Otherwise, the image of the [`WorldUiPanel`] can be displayed with any material.
+///
+/// The pointers over the viewport of [`camera`](Self::camera) are ray-cast against the quad, and
+/// the ones hitting it are moved into the viewport of the panel, so that the
+/// [`HoverMap`](crate::HoverMap) picks its nodes. Only the nearest panel under a pointer is
+/// picked, and the panels aren't occluded by the rest of the scene.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{UVec2, Vec3};
+/// # use bevy_ui::{node_bundles::NodeBundle, WorldUi};
+/// fn spawn_nameplate(mut commands: Commands, character: Entity, camera: Entity) {
+///     commands.spawn((
+///         NodeBundle::default(),
+///         WorldUi::new(character, camera, UVec2::new(200, 40))
+///             .with_offset(Vec3::Y * 2.)
+///             .billboard(),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct WorldUi {
+    /// The entity the quad is attached to.
+    pub anchor: Entity,
+    /// The camera the quad is seen through, whose pointers are ray-cast against it.
+    pub camera: Entity,
+    /// The size of the viewport the subtree is laid out in, in pixels.
+    pub size: UVec2,
+    /// The size of a pixel of the viewport on the quad, in world units.
+    pub pixel_size: f32,
+    /// The position of the center of the quad, relative to the anchor.
+    pub offset: Vec3,
+    /// Whether the quad always faces the camera, instead of following the rotation of the anchor.
+    pub billboard: bool,
+}
+
+impl WorldUi {
+    /// Creates a panel of `size` pixels attached to `anchor`, seen through `camera`.
+    ///
+    /// The pixels are a hundredth of a world unit wide, and the quad faces the `+Z` direction of
+    /// the anchor.
+    pub const fn new(anchor: Entity, camera: Entity, size: UVec2) -> Self {
+        Self {
+            anchor,
+            camera,
+            size,
+            pixel_size: 0.01,
+            offset: Vec3::ZERO,
+            billboard: false,
+        }
+    }
+
+    /// Returns this panel with the given [`pixel_size`](Self::pixel_size).
+    pub const fn with_pixel_size(mut self, pixel_size: f32) -> Self {
+        self.pixel_size = pixel_size;
+        self
+    }
+
+    /// Returns this panel with the given [`offset`](Self::offset).
+    pub const fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns this panel facing the camera.
+    pub const fn billboard(mut self) -> Self {
+        self.billboard = true;
+        self
+    }
+
+    /// Returns the size of the quad, in world units.
+    pub fn world_size(&self) -> Vec2 {
+        self.size.as_vec2() * self.pixel_size
+    }
+}
+
+/// The entities rendering a [`WorldUi`], inserted on its root node by [`update_world_ui_panels`].
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct WorldUiPanel {
+    camera: Entity,
+    quad: Entity,
+    image: Handle<Image>,
+}
+
+impl WorldUiPanel {
+    /// Returns the camera rendering the subtree.
+    pub fn camera(&self) -> Entity {
+        self.camera
+    }
+
+    /// Returns the quad displaying the subtree, a child of the anchor.
+    pub fn quad(&self) -> Entity {
+        self.quad
+    }
+
+    /// Returns the image the subtree is rendered to.
+    pub fn image(&self) -> &Handle<Image> {
+        &self.image
+    }
+}
+
+fn world_ui_image(size: UVec2) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 4],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+/// Returns the transform of the quad of `world_ui` relative to its anchor.
+fn quad_transform(
+    world_ui: &WorldUi,
+    anchor_transform: Option<&GlobalTransform>,
+    camera_transform: Option<&GlobalTransform>,
+) -> Transform {
+    let rotation = match (world_ui.billboard, anchor_transform, camera_transform) {
+        (true, Some(anchor), Some(camera)) => {
+            anchor.compute_transform().rotation.inverse() * camera.compute_transform().rotation
+        }
+        _ => Quat::IDENTITY,
+    };
+    Transform {
+        translation: world_ui.offset,
+        rotation,
+        scale: world_ui.world_size().extend(1.),
+    }
+}
+
+/// Spawns the camera and the quad rendering each [`WorldUi`], keeps them in sync with it, and
+/// despawns them with it.
+///
+/// The quads follow the cameras with a frame of delay, as this runs before the transforms are
+/// propagated.
+#[allow(clippy::too_many_arguments)]
+pub fn update_world_ui_panels(
+    mut commands: Commands,
+    mut panels: Local<EntityHashMap<(Entity, Entity)>>,
+    world_ui_query: Query<(Entity, Ref<WorldUi>, Option<&WorldUiPanel>)>,
+    mut quad_query: Query<&mut Transform>,
+    transform_query: Query<&GlobalTransform>,
+    mut images: ResMut<Assets<Image>>,
+    #[cfg(feature = "bevy_pbr")] mut meshes: Option<ResMut<Assets<bevy_render::mesh::Mesh>>>,
+    #[cfg(feature = "bevy_pbr")] mut materials: Option<ResMut<Assets<bevy_pbr::StandardMaterial>>>,
+) {
+    panels.retain(|root, (camera, quad)| {
+        if world_ui_query.contains(*root) {
+            return true;
+        }
+        if let Some(entity) = commands.get_entity(*camera) {
+            entity.despawn_recursive();
+        }
+        if let Some(entity) = commands.get_entity(*quad) {
+            entity.despawn_recursive();
+        }
+        if let Some(mut entity) = commands.get_entity(*root) {
+            entity.remove::<(WorldUiPanel, TargetCamera)>();
+        }
+        false
+    });
+
+    for (root, world_ui, panel) in &world_ui_query {
+        let transform = quad_transform(
+            &world_ui,
+            transform_query.get(world_ui.anchor).ok(),
+            transform_query.get(world_ui.camera).ok(),
+        );
+
+        let Some(panel) = panel else {
+            let image = images.add(world_ui_image(world_ui.size));
+            let camera = commands
+                .spawn((
+                    Camera2dBundle {
+                        camera: Camera {
+                            // Render the panel before the cameras displaying it
+                            order: -1,
+                            target: RenderTarget::Image(image.clone()),
+                            clear_color: ClearColorConfig::Custom(Color::NONE),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    // The camera only renders the UI targeting it
+                    RenderLayers::none(),
+                ))
+                .id();
+            let mut quad = commands.spawn(SpatialBundle::from_transform(transform));
+            // Parenting to a missing anchor would panic, the quad stays in world space instead
+            if transform_query.contains(world_ui.anchor) {
+                quad.set_parent(world_ui.anchor);
+            }
+            let quad = quad.id();
+
+            #[cfg(feature = "bevy_pbr")]
+            if let (Some(meshes), Some(materials)) = (meshes.as_mut(), materials.as_mut()) {
+                commands.entity(quad).insert((
+                    meshes.add(bevy_math::primitives::Rectangle::new(1., 1.)),
+                    materials.add(bevy_pbr::StandardMaterial {
+                        base_color_texture: Some(image.clone()),
+                        unlit: true,
+                        alpha_mode: bevy_render::alpha::AlphaMode::Blend,
+                        cull_mode: None,
+                        double_sided: true,
+                        ..Default::default()
+                    }),
+                ));
+            }
+
+            commands.entity(root).insert((
+                TargetCamera(camera),
+                WorldUiPanel {
+                    camera,
+                    quad,
+                    image,
+                },
+            ));
+            panels.insert(root, (camera, quad));
+            continue;
+        };
+
+        if let Ok(mut current) = quad_query.get_mut(panel.quad) {
+            if *current != transform {
+                *current = transform;
+            }
+        }
+        if world_ui.is_changed() && transform_query.contains(world_ui.anchor) {
+            if let Some(mut quad) = commands.get_entity(panel.quad) {
+                quad.set_parent(world_ui.anchor);
+            }
+        }
+
+        let size = world_ui.size.max(UVec2::ONE);
+        // Only access the image mutably when it needs to be resized, to avoid uploading it again
+        if images
+            .get(&panel.image)
+            .is_some_and(|image| image.size() != size)
+        {
+            if let Some(image) = images.get_mut(&panel.image) {
+                image.resize(Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                });
+            }
+        }
+    }
+}
+
+/// Marks an entity displaying the image rendered by a UI camera as a surface the pointers can
+/// interact with that UI through, for screens in the world or monitors showing a menu.
+///
+/// Unlike [`WorldUi`], the camera rendering the UI and the entity displaying its image are
+/// managed by the user: the [`ui_camera`](Self::ui_camera) targets a
+/// [`RenderTarget::Image`], which the UI roots targeting it are laid out to fill, and the surface
+/// is a quad of size `1.0` in the `XY` plane of its [`GlobalTransform`], such as a
+/// [`Rectangle::new(1., 1.)`](bevy_math::primitives::Rectangle::new) mesh scaled by its
+/// [`Transform`], with the image as its texture.
+///
+/// The pointers over the viewport of [`camera`](Self::camera) are ray-cast against the quad, and
+/// the ones hitting it are moved into the viewport of the UI camera, so that the
+/// [`HoverMap`](crate::HoverMap) picks its nodes. Like [`WorldUi`] panels, only the nearest
+/// surface under a pointer is picked, and the surfaces aren't occluded by the rest of the scene.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiTextureSurface {
+    /// The camera rendering the UI displayed by the surface.
+    pub ui_camera: Entity,
+    /// The camera the surface is seen through, whose pointers are ray-cast against it.
+    pub camera: Entity,
+}
+
+impl Default for UiTextureSurface {
+    fn default() -> Self {
+        Self {
+            ui_camera: Entity::PLACEHOLDER,
+            camera: Entity::PLACEHOLDER,
+        }
+    }
+}
+
+impl UiTextureSurface {
+    /// Creates a surface displaying the UI rendered by `ui_camera`, seen through `camera`.
+    pub const fn new(ui_camera: Entity, camera: Entity) -> Self {
+        Self { ui_camera, camera }
+    }
+}
+
+/// Casts `ray` against the unit quad of `quad_transform`, and returns the distance to the hit and
+/// its position in a viewport of `viewport_size` covering the quad.
+pub(crate) fn quad_viewport_position(
+    quad_transform: &GlobalTransform,
+    viewport_size: Vec2,
+    ray: Ray3d,
+) -> Option<(f32, Vec2)> {
+    let distance = ray.intersect_plane(
+        quad_transform.translation(),
+        InfinitePlane3d::new(quad_transform.back()),
+    )?;
+    let local = quad_transform
+        .affine()
+        .inverse()
+        .transform_point3(ray.get_point(distance));
+    if local.x.abs() > 0.5 || local.y.abs() > 0.5 {
+        return None;
+    }
+    // The top left corner of the viewport is at the top left of the quad
+    let position = Vec2::new(local.x + 0.5, 0.5 - local.y) * viewport_size;
+    Some((distance, position))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Assets;
+    use bevy_ecs::{entity::Entity, schedule::Schedule, world::World};
+    use bevy_hierarchy::Parent;
+    use bevy_math::{Ray3d, UVec2, Vec2, Vec3};
+    use bevy_render::texture::Image;
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::{quad_viewport_position, update_world_ui_panels, WorldUi, WorldUiPanel};
+
+    #[test]
+    fn ray_hits_quad_viewport() {
+        let world_ui = WorldUi::new(
+            Entity::PLACEHOLDER,
+            Entity::PLACEHOLDER,
+            UVec2::new(200, 100),
+        );
+        let quad = GlobalTransform::from(
+            Transform::from_xyz(0., 1., 0.).with_scale(world_ui.world_size().extend(1.)),
+        );
+        let size = world_ui.size.as_vec2();
+
+        let ray = Ray3d::new(Vec3::new(-0.5, 1.25, 10.), Vec3::NEG_Z);
+        let (distance, position) = quad_viewport_position(&quad, size, ray).unwrap();
+        assert!((distance - 10.).abs() < 1e-4);
+        assert!(position.abs_diff_eq(Vec2::new(50., 25.), 1e-3));
+
+        // Seen from behind
+        let ray = Ray3d::new(Vec3::new(0., 1., -10.), Vec3::Z);
+        let (_, position) = quad_viewport_position(&quad, size, ray).unwrap();
+        assert!(position.abs_diff_eq(Vec2::new(100., 50.), 1e-3));
+
+        // Missing the quad
+        let ray = Ray3d::new(Vec3::new(1.5, 1., 10.), Vec3::NEG_Z);
+        assert!(quad_viewport_position(&quad, size, ray).is_none());
+    }
+
+    #[test]
+    fn missing_anchor_does_not_parent_the_quad() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Image>>();
+        let anchor = world.spawn_empty().id();
+        world.despawn(anchor);
+        let root = world
+            .spawn(WorldUi::new(
+                anchor,
+                Entity::PLACEHOLDER,
+                UVec2::new(200, 100),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_world_ui_panels);
+        schedule.run(&mut world);
+
+        let quad = world.get::<WorldUiPanel>(root).unwrap().quad;
+        assert!(world.get::<Transform>(quad).is_some());
+        assert!(world.get::<Parent>(quad).is_none());
+    }
+}
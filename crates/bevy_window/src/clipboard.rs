@@ -0,0 +1,12 @@
+use bevy_ecs::system::Resource;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// The text of the clipboard, copied and cut from text inputs, and pasted into them.
+///
+/// Without a windowing backend synchronizing it, this clipboard is internal to the app. With the
+/// `clipboard` feature, `bevy_winit` synchronizes it with the clipboard of the operating system on
+/// Windows, macOS and Linux: the system clipboard is read when a window gains the focus, since only
+/// other apps can change it in the meantime, and written when this resource changes.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct Clipboard(pub String);
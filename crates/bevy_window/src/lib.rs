@@ -13,17 +13,21 @@
 
 use bevy_a11y::Focus;
 
+mod clipboard;
 mod cursor;
 mod event;
 mod raw_handle;
 mod system;
+mod virtual_keyboard;
 mod window;
 
 pub use crate::raw_handle::*;
 
+pub use clipboard::*;
 pub use cursor::*;
 pub use event::*;
 pub use system::*;
+pub use virtual_keyboard::*;
 pub use window::*;
 
 #[allow(missing_docs)]
@@ -106,7 +110,10 @@ impl Plugin for WindowPlugin {
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
             .add_event::<WindowThemeChanged>()
-            .add_event::<ApplicationLifetime>();
+            .add_event::<ApplicationLifetime>()
+            .add_event::<VirtualKeyboardRequest>()
+            .init_resource::<VirtualKeyboard>()
+            .init_resource::<Clipboard>();
 
         if let Some(primary_window) = &self.primary_window {
             let initial_focus = app
@@ -153,7 +160,10 @@ impl Plugin for WindowPlugin {
             .register_type::<FileDragAndDrop>()
             .register_type::<WindowMoved>()
             .register_type::<WindowThemeChanged>()
-            .register_type::<ApplicationLifetime>();
+            .register_type::<ApplicationLifetime>()
+            .register_type::<VirtualKeyboardRequest>()
+            .register_type::<VirtualKeyboard>()
+            .register_type::<Clipboard>();
 
         // Register window descriptor and related types
         app.register_type::<Window>()
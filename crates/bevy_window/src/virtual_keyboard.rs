@@ -0,0 +1,96 @@
+use bevy_ecs::{entity::Entity, event::Event, system::Resource};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A request to show or hide the virtual keyboard of touch screen platforms, such as Android.
+///
+/// This is handled by the windowing backend, and ignored on the platforms without a virtual
+/// keyboard. The resulting state is reported in the [`VirtualKeyboard`] resource.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct VirtualKeyboardRequest {
+    /// The window receiving the text typed on the keyboard.
+    pub window: Entity,
+    /// Whether the keyboard should be shown or hidden.
+    pub visible: bool,
+}
+
+impl VirtualKeyboardRequest {
+    /// Requests the virtual keyboard to be shown to type text in `window`.
+    pub const fn show(window: Entity) -> Self {
+        Self {
+            window,
+            visible: true,
+        }
+    }
+
+    /// Requests the virtual keyboard of `window` to be hidden.
+    ///
+    /// This is ignored if the keyboard is typing into another window.
+    pub const fn hide(window: Entity) -> Self {
+        Self {
+            window,
+            visible: false,
+        }
+    }
+}
+
+/// The state of the virtual keyboard, updated by the windowing backend.
+///
+/// The keyboard covers the bottom of the window it types into: UI showing a focused text input
+/// should move it above the [`bottom_inset`](Self::bottom_inset), which
+/// [`occluded_height`](Self::occluded_height) computes.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct VirtualKeyboard {
+    /// Whether the virtual keyboard is shown.
+    ///
+    /// This becomes `false` when the user dismisses the keyboard, without a
+    /// [`VirtualKeyboardRequest`].
+    pub visible: bool,
+    /// The window receiving the text typed on the keyboard, set by the last request to show it.
+    pub window: Option<Entity>,
+    /// The height of the bottom of [`window`](Self::window) covered by the virtual keyboard, in
+    /// logical pixels.
+    ///
+    /// This is `0.0` when the keyboard is hidden, and on the platforms that don't report it.
+    pub bottom_inset: f32,
+}
+
+impl VirtualKeyboard {
+    /// Returns how far content must be moved up to be above the virtual keyboard, or `0.0` if it
+    /// is already visible.
+    ///
+    /// `bottom` is the position of the bottom edge of the content, and `window_height` the height
+    /// of the window, both in logical pixels from the top of the window.
+    pub fn occluded_height(&self, window_height: f32, bottom: f32) -> f32 {
+        (bottom - (window_height - self.bottom_inset)).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VirtualKeyboard;
+
+    #[test]
+    fn occluded_height() {
+        let keyboard = VirtualKeyboard {
+            visible: true,
+            window: None,
+            bottom_inset: 300.0,
+        };
+        assert_eq!(keyboard.occluded_height(800.0, 400.0), 0.0);
+        assert_eq!(keyboard.occluded_height(800.0, 550.0), 50.0);
+        assert_eq!(
+            VirtualKeyboard::default().occluded_height(800.0, 550.0),
+            0.0
+        );
+    }
+}
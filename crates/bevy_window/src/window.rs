@@ -272,6 +272,17 @@ pub struct Window {
     ///
     /// - Only supported on Windows.
     pub skip_taskbar: bool,
+    /// Sets whether the content of the window is protected from being captured by other apps,
+    /// for example by screenshots and screen recordings.
+    ///
+    /// Combined with [`WindowLevel::AlwaysOnTop`] and disabling [`Cursor::hit_test`], this
+    /// allows making overlays that stay above other windows, let the mouse click through, and
+    /// don't show up in captures.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Wayland / X11:** Unsupported.
+    pub content_protected: bool,
     /// Optional hint given to the rendering API regarding the maximum number of queued frames admissible on the GPU.
     ///
     /// Given values are usually within the 1-3 range. If not provided, this will default to 2.
@@ -281,6 +292,18 @@ pub struct Window {
     /// [`wgpu::SurfaceConfiguration::desired_maximum_frame_latency`]:
     /// https://docs.rs/wgpu/latest/wgpu/type.SurfaceConfiguration.html#structfield.desired_maximum_frame_latency
     pub desired_maximum_frame_latency: Option<NonZeroU32>,
+    /// Sets the window icon, shown in the title bar and in the taskbar or dock.
+    ///
+    /// If `None` is provided, the default icon of the platform is used.
+    ///
+    /// To use an `Image` asset as the icon, see `WindowIconImage` in `bevy_render`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Sets the icon of the title bar and the taskbar.
+    /// - **X11:** Sets the icon of the window, usually shown in the title bar and the taskbar.
+    /// - **macOS / Wayland / iOS / Android / Web:** Unsupported.
+    pub icon: Option<WindowIcon>,
 }
 
 impl Default for Window {
@@ -310,7 +333,9 @@ impl Default for Window {
             window_theme: None,
             visible: true,
             skip_taskbar: false,
+            content_protected: false,
             desired_maximum_frame_latency: None,
+            icon: None,
         }
     }
 }
@@ -330,6 +355,29 @@ impl Window {
         self.internal.minimize_request = Some(minimized);
     }
 
+    /// Requests the user's attention to the window, usually by flashing its taskbar entry or
+    /// bouncing its dock icon.
+    ///
+    /// The request is cancelled automatically once the window is focused.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** [`WindowAttention::Critical`] bounces the dock icon until the application is
+    ///   focused, [`WindowAttention::Informational`] bounces it once.
+    /// - **Windows:** [`WindowAttention::Critical`] flashes both the window and the taskbar entry
+    ///   until the window is focused, [`WindowAttention::Informational`] only flashes the taskbar
+    ///   entry.
+    /// - **X11 / Wayland:** Both variants behave the same.
+    /// - **iOS / Android / Web:** Unsupported.
+    pub fn request_attention(&mut self, attention: WindowAttention) {
+        self.internal.attention_request = Some(Some(attention));
+    }
+
+    /// Cancels a previous [`Window::request_attention`] call.
+    pub fn cancel_attention_request(&mut self) {
+        self.internal.attention_request = Some(None);
+    }
+
     /// The window's client area width in logical pixels.
     ///
     /// See [`WindowResolution`] for an explanation about logical/physical sizes.
@@ -869,6 +917,8 @@ pub struct InternalWindowState {
     minimize_request: Option<bool>,
     /// If this is true then next frame we will ask to maximize/un-maximize the window depending on `maximized`.
     maximize_request: Option<bool>,
+    /// If this is `Some` then next frame we will request or cancel a request for the user's attention.
+    attention_request: Option<Option<WindowAttention>>,
     /// Unscaled cursor position.
     physical_cursor_position: Option<DVec2>,
 }
@@ -883,6 +933,13 @@ impl InternalWindowState {
     pub fn take_minimize_request(&mut self) -> Option<bool> {
         self.minimize_request.take()
     }
+
+    /// Consumes the current attention request, if it exists. This should only be called by window backends.
+    ///
+    /// The inner value is `None` if the previous request should be cancelled.
+    pub fn take_attention_request(&mut self) -> Option<Option<WindowAttention>> {
+        self.attention_request.take()
+    }
 }
 
 /// References a screen monitor.
@@ -1132,6 +1189,67 @@ pub enum WindowTheme {
     Dark,
 }
 
+/// An icon for a [`Window`], stored as raw RGBA8 pixels.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq)]
+pub struct WindowIcon {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl WindowIcon {
+    /// Creates an icon from RGBA8 pixels, in row-major order starting from the top left pixel.
+    ///
+    /// Returns `None` if `rgba` doesn't contain exactly `width * height` pixels.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Option<Self> {
+        if width == 0 || height == 0 || rgba.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+        Some(Self {
+            rgba,
+            width,
+            height,
+        })
+    }
+
+    /// The RGBA8 pixels of the icon.
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// The width of the icon in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the icon in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// The kind of attention requested with [`Window::request_attention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq)]
+pub enum WindowAttention {
+    /// Requests the user's attention until the window is focused.
+    Critical,
+
+    /// Requests the user's attention once, or until the window is focused, depending on the platform.
+    Informational,
+}
+
 /// Specifies which [`Window`] control buttons should be enabled.
 ///
 /// ## Platform-specific
@@ -1229,4 +1347,27 @@ mod tests {
         window.set_physical_cursor_position(Some(DVec2::new(400., 600.)));
         assert!(window.physical_cursor_position().is_none());
     }
+
+    #[test]
+    fn window_icon_size_must_match_pixels() {
+        assert!(WindowIcon::from_rgba(vec![255; 2 * 3 * 4], 2, 3).is_some());
+        assert!(WindowIcon::from_rgba(vec![255; 2 * 3 * 4], 3, 3).is_none());
+        assert!(WindowIcon::from_rgba(Vec::new(), 0, 0).is_none());
+    }
+
+    #[test]
+    fn attention_requests_are_consumed() {
+        let mut window = Window::default();
+        assert_eq!(window.internal.take_attention_request(), None);
+
+        window.request_attention(WindowAttention::Critical);
+        assert_eq!(
+            window.internal.take_attention_request(),
+            Some(Some(WindowAttention::Critical))
+        );
+        assert_eq!(window.internal.take_attention_request(), None);
+
+        window.cancel_attention_request();
+        assert_eq!(window.internal.take_attention_request(), Some(None));
+    }
 }
@@ -0,0 +1,78 @@
+//! Synchronizes the [`Clipboard`] resource with the clipboard of the operating system.
+
+use bevy_app::{App, Last, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_utils::tracing::warn;
+use bevy_window::{Clipboard, WindowFocused};
+
+/// Reads the clipboard of the operating system into the [`Clipboard`] resource when a window
+/// gains the focus, and writes the resource to it when it changes.
+pub(crate) struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        let clipboard = match arboard::Clipboard::new() {
+            Ok(clipboard) => clipboard,
+            Err(err) => {
+                warn!("Failed to access the system clipboard: {err}");
+                return;
+            }
+        };
+        app.init_resource::<Clipboard>()
+            .insert_non_send_resource(SystemClipboard {
+                clipboard,
+                text: String::new(),
+            })
+            .add_systems(PreUpdate, read_system_clipboard)
+            .add_systems(Last, write_system_clipboard);
+    }
+}
+
+/// The clipboard of the operating system, accessed from the main thread like the windows.
+///
+/// It owns the copied text on Linux, so it lives as long as the app.
+struct SystemClipboard {
+    clipboard: arboard::Clipboard,
+    /// The text last read from or written to the system clipboard, to only write the text
+    /// changed by the app.
+    text: String,
+}
+
+/// Reads the system clipboard into the [`Clipboard`] resource when a window gains the focus.
+///
+/// Other apps can only change the system clipboard while the app doesn't have the focus.
+fn read_system_clipboard(
+    mut focused_events: EventReader<WindowFocused>,
+    mut system_clipboard: NonSendMut<SystemClipboard>,
+    mut clipboard: ResMut<Clipboard>,
+) {
+    if !focused_events
+        .read()
+        .fold(false, |gained, event| gained || event.focused)
+    {
+        return;
+    }
+    match system_clipboard.clipboard.get_text() {
+        Ok(text) => {
+            system_clipboard.text.clone_from(&text);
+            clipboard.set_if_neq(Clipboard(text));
+        }
+        // The clipboard is empty, or holds something else than text
+        Err(arboard::Error::ContentNotAvailable) => {}
+        Err(err) => warn!("Failed to read the system clipboard: {err}"),
+    }
+}
+
+/// Writes the [`Clipboard`] resource to the system clipboard when the app changes it.
+fn write_system_clipboard(
+    clipboard: Res<Clipboard>,
+    mut system_clipboard: NonSendMut<SystemClipboard>,
+) {
+    if !clipboard.is_changed() || clipboard.0 == system_clipboard.text {
+        return;
+    }
+    if let Err(err) = system_clipboard.clipboard.set_text(clipboard.0.as_str()) {
+        warn!("Failed to write the system clipboard: {err}");
+    }
+    system_clipboard.text.clone_from(&clipboard.0);
+}
@@ -6,7 +6,10 @@ use bevy_input::{
     ButtonState,
 };
 use bevy_math::Vec2;
-use bevy_window::{CursorIcon, EnabledButtons, WindowLevel, WindowTheme};
+use bevy_utils::tracing::warn;
+use bevy_window::{
+    CursorIcon, EnabledButtons, WindowAttention, WindowIcon, WindowLevel, WindowTheme,
+};
 use winit::keyboard::{Key, NamedKey, NativeKey};
 
 pub fn convert_keyboard_input(
@@ -688,6 +691,19 @@ pub fn convert_window_theme(theme: WindowTheme) -> winit::window::Theme {
     }
 }
 
+pub fn convert_window_attention(attention: WindowAttention) -> winit::window::UserAttentionType {
+    match attention {
+        WindowAttention::Critical => winit::window::UserAttentionType::Critical,
+        WindowAttention::Informational => winit::window::UserAttentionType::Informational,
+    }
+}
+
+pub fn convert_window_icon(icon: &WindowIcon) -> Option<winit::window::Icon> {
+    winit::window::Icon::from_rgba(icon.rgba().to_vec(), icon.width(), icon.height())
+        .map_err(|err| warn!("Could not use the window icon: {err}"))
+        .ok()
+}
+
 pub fn convert_enabled_buttons(enabled_buttons: EnabledButtons) -> winit::window::WindowButtons {
     let mut window_buttons = winit::window::WindowButtons::empty();
     if enabled_buttons.minimize {
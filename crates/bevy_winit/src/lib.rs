@@ -13,6 +13,11 @@
 //! See `winit_runner` for details.
 
 pub mod accessibility;
+#[cfg(all(
+    feature = "clipboard",
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+))]
+mod clipboard;
 mod converters;
 mod system;
 mod winit_config;
@@ -144,8 +149,17 @@ impl Plugin for WinitPlugin {
                     .chain(),
             );
 
+        #[cfg(target_os = "android")]
+        app.add_systems(bevy_app::PreUpdate, system::update_virtual_keyboard);
+
         app.add_plugins(AccessKitPlugin);
 
+        #[cfg(all(
+            feature = "clipboard",
+            not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+        ))]
+        app.add_plugins(clipboard::ClipboardPlugin);
+
         let event_loop = event_loop_builder
             .build()
             .expect("Failed to build event loop");
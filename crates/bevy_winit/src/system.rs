@@ -23,8 +23,8 @@ use winit::platform::web::WindowExtWebSys;
 
 use crate::{
     converters::{
-        self, convert_enabled_buttons, convert_window_level, convert_window_theme,
-        convert_winit_theme,
+        self, convert_enabled_buttons, convert_window_attention, convert_window_icon,
+        convert_window_level, convert_window_theme, convert_winit_theme,
     },
     get_best_videomode, get_fitting_videomode, CreateWindowParams, WinitWindows,
 };
@@ -283,6 +283,10 @@ pub(crate) fn changed_windows(
             winit_window.set_minimized(minimized);
         }
 
+        if let Some(attention) = window.internal.take_attention_request() {
+            winit_window.request_user_attention(attention.map(convert_window_attention));
+        }
+
         if window.focused != cache.window.focused && window.focused {
             winit_window.focus_window();
         }
@@ -291,6 +295,10 @@ pub(crate) fn changed_windows(
             winit_window.set_window_level(convert_window_level(window.window_level));
         }
 
+        if window.content_protected != cache.window.content_protected {
+            winit_window.set_content_protected(window.content_protected);
+        }
+
         // Currently unsupported changes
         if window.transparent != cache.window.transparent {
             window.transparent = cache.window.transparent;
@@ -324,6 +332,70 @@ pub(crate) fn changed_windows(
             winit_window.set_visible(window.visible);
         }
 
+        if window.icon != cache.window.icon {
+            winit_window.set_window_icon(window.icon.as_ref().and_then(convert_window_icon));
+        }
+
         cache.window = window.clone();
     }
 }
+
+/// Shows or hides the Android soft keyboard on [`VirtualKeyboardRequest`]s, and updates the
+/// [`VirtualKeyboard`] resource.
+///
+/// The keyboard can also be dismissed by the user, with the back button for example: it is then
+/// reported as hidden once the area it covered is given back to the window.
+///
+/// [`VirtualKeyboardRequest`]: bevy_window::VirtualKeyboardRequest
+/// [`VirtualKeyboard`]: bevy_window::VirtualKeyboard
+#[cfg(target_os = "android")]
+pub(crate) fn update_virtual_keyboard(
+    mut requests: bevy_ecs::event::EventReader<bevy_window::VirtualKeyboardRequest>,
+    mut virtual_keyboard: bevy_ecs::system::ResMut<bevy_window::VirtualKeyboard>,
+    windows: Query<&Window>,
+    mut keyboard_covered_window: bevy_ecs::system::Local<bool>,
+) {
+    use bevy_ecs::change_detection::DetectChangesMut;
+
+    let Some(android_app) = crate::ANDROID_APP.get() else {
+        return;
+    };
+
+    let mut state = *virtual_keyboard;
+    for request in requests.read() {
+        if request.visible {
+            android_app.show_soft_input(true);
+            state.visible = true;
+            state.window = Some(request.window);
+            *keyboard_covered_window = false;
+        } else if state.window == Some(request.window) {
+            // Only the window the keyboard types into can hide it
+            android_app.hide_soft_input(false);
+            state.visible = false;
+        }
+    }
+
+    // The content rect of the activity is the part of its native window that isn't covered by
+    // system UI, such as the keyboard
+    let window = state.window.and_then(|window| windows.get(window).ok());
+    state.bottom_inset = match (window, android_app.native_window()) {
+        (Some(window), Some(native_window)) if state.visible => {
+            let content_bottom = android_app.content_rect().bottom;
+            (native_window.height() - content_bottom).max(0) as f32 / window.scale_factor()
+        }
+        _ => 0.0,
+    };
+    if state.bottom_inset > 0.0 {
+        *keyboard_covered_window = true;
+    } else if *keyboard_covered_window {
+        // The keyboard was dismissed without a request
+        state.visible = false;
+        *keyboard_covered_window = false;
+    }
+    if window.is_none() {
+        state.visible = false;
+        state.window = None;
+    }
+
+    virtual_keyboard.set_if_neq(state);
+}
@@ -14,7 +14,9 @@ use winit::{
 
 use crate::{
     accessibility::{prepare_accessibility_for_window, AccessKitAdapters, WinitActionHandlers},
-    converters::{convert_enabled_buttons, convert_window_level, convert_window_theme},
+    converters::{
+        convert_enabled_buttons, convert_window_icon, convert_window_level, convert_window_theme,
+    },
 };
 
 /// A resource mapping window entities to their `winit`-backend [`Window`](winit::window::Window)
@@ -100,7 +102,9 @@ impl WinitWindows {
             .with_enabled_buttons(convert_enabled_buttons(window.enabled_buttons))
             .with_decorations(window.decorations)
             .with_transparent(window.transparent)
-            .with_visible(window.visible);
+            .with_visible(window.visible)
+            .with_content_protected(window.content_protected)
+            .with_window_icon(window.icon.as_ref().and_then(convert_window_icon));
 
         #[cfg(target_os = "windows")]
         {
@@ -115,6 +115,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         font: font.clone(),
         font_size: 16.0,
         color: Color::WHITE,
+        ..default()
     };
 
     // Load textures
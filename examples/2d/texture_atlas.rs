@@ -125,6 +125,7 @@ fn setup(
         font: font.clone(),
         font_size: 50.0,
         color: Color::WHITE,
+        ..default()
     };
 
     // labels to indicate padding
@@ -174,6 +175,7 @@ fn setup(
         font,
         font_size: 30.0,
         color: Color::WHITE,
+        ..default()
     };
 
     let base_y = 170.0; // y position of the sprites
@@ -189,6 +189,7 @@ fn setup(
         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
         font_size: 25.0,
         color: ORANGE.into(),
+        ..default()
     };
 
     commands.spawn(
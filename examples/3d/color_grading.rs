@@ -328,6 +328,7 @@ fn add_help_text(
                     font: font.clone(),
                     font_size: 24.0,
                     color: Color::WHITE,
+                    ..default()
                 },
             )
         })
@@ -347,6 +348,7 @@ fn add_text<'a>(
             font: font.clone(),
             font_size: 18.0,
             color,
+            ..default()
         },
     ))
 }
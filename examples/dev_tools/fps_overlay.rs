@@ -18,6 +18,7 @@ fn main() {
                         color: Color::srgb(0.0, 1.0, 0.0),
                         // If we want, we can use a custom font
                         font: default(),
+                        ..default()
                     },
                 },
             },
@@ -122,6 +122,7 @@ fn build_ui(
                 font: asset_server.load(FONT_BOLD),
                 font_size: FONT_SIZE,
                 color: FONT_COLOR,
+                ..default()
             },
         ));
 
@@ -149,6 +150,7 @@ fn build_ui(
                     font: asset_server.load(FONT_MEDIUM),
                     font_size: FONT_SIZE,
                     color: FONT_COLOR,
+                    ..default()
                 },
             ));
 
@@ -159,6 +161,7 @@ fn build_ui(
                     font: asset_server.load(FONT_MEDIUM),
                     font_size: FONT_SIZE,
                     color: FONT_COLOR,
+                    ..default()
                 },
             ));
         }
@@ -200,6 +203,7 @@ fn build_stepping_hint(mut commands: Commands, asset_server: Res<AssetServer>) {
             font: asset_server.load(FONT_MEDIUM),
             font_size: 18.0,
             color: FONT_COLOR,
+            ..default()
         },
     )])
     .with_style(Style {
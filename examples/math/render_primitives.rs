@@ -360,6 +360,7 @@ fn setup_text(
         font,
         font_size,
         color: Color::WHITE,
+        ..default()
     };
     let instructions = "Press 'C' to switch between 2D and 3D mode\n\
         Press 'Up' or 'Down' to switch to the next/previous primitive";
@@ -6,11 +6,14 @@
 //!
 //! Add the `--colored` arg to run with color tinted sprites. This will cause the sprites to be rendered
 //! in multiple batches, reducing performance but useful for testing.
+//!
+//! Add the `--gpu-culling` arg to cull the sprites on the GPU instead of the CPU, when supported.
 
 use bevy::{
     color::palettes::css::*,
     diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
     prelude::*,
+    render::view::{GpuCulling, NoCpuCulling},
     window::{PresentMode, WindowResolution},
     winit::{UpdateMode, WinitSettings},
 };
@@ -24,10 +27,15 @@ const COLORS: [Color; 3] = [Color::Srgba(BLUE), Color::Srgba(WHITE), Color::Srgb
 #[derive(Resource)]
 struct ColorTint(bool);
 
+#[derive(Resource)]
+struct SpriteGpuCulling(bool);
+
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
     App::new()
-        .insert_resource(ColorTint(
-            std::env::args().nth(1).unwrap_or_default() == "--colored",
+        .insert_resource(ColorTint(args.iter().any(|arg| arg == "--colored")))
+        .insert_resource(SpriteGpuCulling(
+            args.iter().any(|arg| arg == "--gpu-culling"),
         ))
         // Since this is also used as a benchmark, we want it to display performance data.
         .add_plugins((
@@ -55,7 +63,12 @@ fn main() {
         .run();
 }
 
-fn setup(mut commands: Commands, assets: Res<AssetServer>, color_tint: Res<ColorTint>) {
+fn setup(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    color_tint: Res<ColorTint>,
+    gpu_culling: Res<SpriteGpuCulling>,
+) {
     warn!(include_str!("warning_string.txt"));
 
     let mut rng = rand::thread_rng();
@@ -70,7 +83,10 @@ fn setup(mut commands: Commands, assets: Res<AssetServer>, color_tint: Res<Color
 
     // Spawns the camera
 
-    commands.spawn(Camera2dBundle::default());
+    let mut camera = commands.spawn(Camera2dBundle::default());
+    if gpu_culling.0 {
+        camera.insert((GpuCulling, NoCpuCulling));
+    }
 
     // Builds and spawns the sprites
     let mut sprites = vec![];
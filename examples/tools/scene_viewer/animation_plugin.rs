@@ -78,10 +78,10 @@ fn assign_clips(
 
     for (clip_id, clip) in clips.iter() {
         let mut ancestor_player = None;
-        for target_id in clip.curves().keys() {
+        for target_id in clip.targets() {
             // If the animation clip refers to entities that aren't present in
             // the scene, bail.
-            let Some(&target) = animation_target_id_to_entity.get(target_id) else {
+            let Some(&target) = animation_target_id_to_entity.get(&target_id) else {
                 continue;
             };
 
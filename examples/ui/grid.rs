@@ -217,6 +217,7 @@ fn spawn_nested_text_bundle(builder: &mut ChildBuilder, font: Handle<Font>, text
             font,
             font_size: 24.0,
             color: Color::BLACK,
+            ..default()
         },
     ));
 }
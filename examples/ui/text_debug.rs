@@ -69,6 +69,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: YELLOW.into(),
+                    ..default()
                 },
             )
             .with_text_justify(JustifyText::Right)
@@ -84,6 +85,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: WHITE.into(),
+                    ..default()
                 },
             )
             .with_style(Style {
@@ -111,6 +113,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 40.0,
                     color: Color::srgb(0.8, 0.2, 0.7),
+                    ..default()
                 },
             )
             .with_text_justify(JustifyText::Center)
@@ -127,6 +130,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 35.0,
                     color: YELLOW.into(),
+                    ..default()
                 },
             )
             .with_text_justify(JustifyText::Left)
@@ -144,6 +148,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: Color::WHITE,
+                        ..default()
                     },
                 ),
                 TextSection::new(
@@ -152,12 +157,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: RED.into(),
+                        ..default()
                     },
                 ),
                 TextSection::from_style(TextStyle {
                     font: font.clone(),
                     font_size: 25.0,
                     color: ORANGE_RED.into(),
+                    ..default()
                 }),
                 TextSection::new(
                     " fps, ",
@@ -165,12 +172,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: YELLOW.into(),
+                        ..default()
                     },
                 ),
                 TextSection::from_style(TextStyle {
                     font: font.clone(),
                     font_size: 25.0,
                     color: LIME.into(),
+                    ..default()
                 }),
                 TextSection::new(
                     " ms/frame",
@@ -178,6 +187,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: BLUE.into(),
+                        ..default()
                     },
                 ),
             ]),
@@ -31,6 +31,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
         font_size: 16.,
         color: Color::BLACK,
+        ..default()
     };
 
     commands